@@ -0,0 +1,461 @@
+//! `canary-cli`: run SDK operations from the shell
+//!
+//! Reads the same config file (`CANARY_CONFIG`, default `canary.toml`), environment
+//! overrides, and signing key (`key.env`, default `SUI_PRIVATE_KEY`) as `canary-worker`.
+//! Results are printed with `--output json|yaml|table`, and the process exits with an
+//! `ExitStatus` code.
+
+#[cfg(feature = "signing")]
+use canary_sdk::canary::TransactionReceipt;
+use canary_sdk::canary::{CanaryClient, MemberInfoWithAddress};
+use canary_sdk::client::create_sui_client;
+use canary_sdk::client::providers::{create_sui_client_with_provider, EnvSecretSource};
+use canary_sdk::config::WorkerConfig;
+use canary_sdk::error::{CanaryError, ClientError, ConfigError, KeystoreError};
+use canary_sdk::output::{render, ExitStatus, OutputFormat, TableRow};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+const USAGE: &str = "\
+Usage: canary-cli [--config <path>] [--output json|yaml|table] <command>
+
+Commands:
+  registry info
+  member join <domain> [<fee_mist>]
+  member show <address>
+  member list
+  blob store <domain> <contract_blob_id> <explain_blob_id> <package_id>
+  blob update <canary_blob_id> <contract_blob_id> <explain_blob_id>
+  blob delete <canary_blob_id>
+  blob show <canary_blob_id>
+  admin withdraw <amount_mist> [<recipient>]
+  admin set-fee <fee_mist>
+
+The registry and AdminCap come from the config (`registry_id`, `admin_cap_id`) or
+REGISTRY_ID and ADMIN_CAP_ID; member join uses the registry fee unless one is given.";
+
+/// A parsed subcommand
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    RegistryInfo,
+    MemberJoin {
+        domain: String,
+        fee: Option<u64>,
+    },
+    MemberShow(SuiAddress),
+    MemberList,
+    BlobStore {
+        domain: String,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+        package_id: ObjectID,
+    },
+    BlobUpdate {
+        canary_blob_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+    },
+    BlobDelete(ObjectID),
+    BlobShow(ObjectID),
+    AdminWithdraw {
+        amount: u64,
+        recipient: Option<SuiAddress>,
+    },
+    AdminSetFee(u64),
+}
+
+/// Command-line arguments
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    config: PathBuf,
+    output: OutputFormat,
+    command: Command,
+}
+
+/// A failed command: the message to print and the exit status
+#[derive(Debug)]
+struct Failure(ExitStatus, String);
+
+impl Failure {
+    fn usage(message: impl Into<String>) -> Self {
+        Failure(ExitStatus::UserError, message.into())
+    }
+}
+
+impl From<CanaryError> for Failure {
+    fn from(e: CanaryError) -> Self {
+        Failure(ExitStatus::from(&e), e.to_string())
+    }
+}
+
+impl From<ClientError> for Failure {
+    fn from(e: ClientError) -> Self {
+        Failure(ExitStatus::from(&e), e.to_string())
+    }
+}
+
+impl From<KeystoreError> for Failure {
+    fn from(e: KeystoreError) -> Self {
+        Failure(ExitStatus::from(&e), e.to_string())
+    }
+}
+
+impl From<ConfigError> for Failure {
+    fn from(e: ConfigError) -> Self {
+        Failure(ExitStatus::UserError, e.to_string())
+    }
+}
+
+/// The result of a command that submitted a transaction
+#[derive(Debug, Serialize)]
+struct TransactionResult {
+    digest: String,
+    status: String,
+}
+
+#[cfg(feature = "signing")]
+impl From<&TransactionReceipt> for TransactionResult {
+    fn from(receipt: &TransactionReceipt) -> Self {
+        let status = match (&receipt.gas, receipt.success, &receipt.error) {
+            (None, _, _) => "unknown".to_string(),
+            (Some(_), true, _) => "success".to_string(),
+            (Some(_), false, error) => {
+                format!("failure: {}", error.as_deref().unwrap_or("unknown error"))
+            }
+        };
+        Self {
+            digest: receipt.digest.to_string(),
+            status,
+        }
+    }
+}
+
+impl TableRow for TransactionResult {
+    fn headers() -> Vec<&'static str> {
+        vec!["DIGEST", "STATUS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.digest.clone(), self.status.clone()]
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    if argv.is_empty() || argv.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+    let args = match parse_args(&argv) {
+        Ok(args) => args,
+        Err(Failure(status, message)) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            status.exit();
+        }
+    };
+
+    match run(args).await {
+        Ok(output) => println!("{}", output),
+        Err(Failure(status, message)) => {
+            eprintln!("Error: {}", message);
+            status.exit();
+        }
+    }
+}
+
+/// Parse the arguments after the program name
+fn parse_args(argv: &[String]) -> Result<Args, Failure> {
+    let mut config =
+        PathBuf::from(std::env::var("CANARY_CONFIG").unwrap_or_else(|_| "canary.toml".to_string()));
+    let mut output = OutputFormat::default();
+    let mut words = Vec::new();
+
+    let mut iter = argv.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" | "-c" => {
+                config = PathBuf::from(
+                    iter.next()
+                        .ok_or_else(|| Failure::usage(format!("{} needs a value", arg)))?,
+                );
+            }
+            "--output" | "-o" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| Failure::usage(format!("{} needs a value", arg)))?;
+                output = value.parse().map_err(Failure::usage)?;
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(Failure::usage(format!("Unknown option '{}'", flag)));
+            }
+            word => words.push(word),
+        }
+    }
+
+    let command = match words.as_slice() {
+        ["registry", "info"] => Command::RegistryInfo,
+        ["member", "join", domain] => Command::MemberJoin {
+            domain: domain.to_string(),
+            fee: None,
+        },
+        ["member", "join", domain, fee] => Command::MemberJoin {
+            domain: domain.to_string(),
+            fee: Some(parse(fee, "fee")?),
+        },
+        ["member", "show", address] => Command::MemberShow(parse(address, "address")?),
+        ["member", "list"] => Command::MemberList,
+        ["blob", "store", domain, contract, explain, package] => Command::BlobStore {
+            domain: domain.to_string(),
+            contract_blob_id: parse(contract, "contract blob ID")?,
+            explain_blob_id: parse(explain, "explain blob ID")?,
+            package_id: parse(package, "package ID")?,
+        },
+        ["blob", "update", blob, contract, explain] => Command::BlobUpdate {
+            canary_blob_id: parse(blob, "canary blob ID")?,
+            contract_blob_id: parse(contract, "contract blob ID")?,
+            explain_blob_id: parse(explain, "explain blob ID")?,
+        },
+        ["blob", "delete", blob] => Command::BlobDelete(parse(blob, "canary blob ID")?),
+        ["blob", "show", blob] => Command::BlobShow(parse(blob, "canary blob ID")?),
+        ["admin", "withdraw", amount] => Command::AdminWithdraw {
+            amount: parse(amount, "amount")?,
+            recipient: None,
+        },
+        ["admin", "withdraw", amount, recipient] => Command::AdminWithdraw {
+            amount: parse(amount, "amount")?,
+            recipient: Some(parse(recipient, "recipient")?),
+        },
+        ["admin", "set-fee", fee] => Command::AdminSetFee(parse(fee, "fee")?),
+        _ => {
+            return Err(Failure::usage(format!(
+                "Unknown command '{}'",
+                words.join(" ")
+            )))
+        }
+    };
+
+    Ok(Args {
+        config,
+        output,
+        command,
+    })
+}
+
+fn parse<T: FromStr>(value: &str, what: &str) -> Result<T, Failure>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| Failure::usage(format!("Invalid {} '{}': {}", what, value, e)))
+}
+
+/// Run a command and render its result
+async fn run(args: Args) -> Result<String, Failure> {
+    let config = WorkerConfig::load(&args.config)?;
+    let registry_id = config
+        .registry_id
+        .ok_or_else(|| Failure::usage("registry_id (or REGISTRY_ID) is required"))?;
+
+    let client = match &config.rpc_provider {
+        Some(provider) => create_sui_client_with_provider(provider, &EnvSecretSource).await?,
+        None => create_sui_client(config.network.clone()).await?,
+    };
+
+    let canary = if needs_signer(&args.command) {
+        signing_client(&config, client, registry_id).await?
+    } else {
+        CanaryClient::new(client, registry_id)
+    };
+    let canary = match config.admin_cap_id {
+        Some(admin_cap_id) => canary.with_admin_cap(admin_cap_id),
+        None => canary,
+    };
+
+    let format = args.output;
+    let rendered = match args.command {
+        Command::RegistryInfo => render(&[canary.registry().await?], format),
+        Command::MemberShow(address) => {
+            let info = canary
+                .member(address)
+                .await?
+                .ok_or(CanaryError::NotMember)?;
+            render(
+                &[MemberInfoWithAddress {
+                    member: address,
+                    domain: info.domain,
+                    joined_at: info.joined_at,
+                    raw: None,
+                }],
+                format,
+            )
+        }
+        Command::MemberList => render(&canary.members().await?, format),
+        Command::BlobShow(canary_blob_id) => render(&[canary.blob(canary_blob_id).await?], format),
+        command => {
+            let result = execute(&canary, command).await?;
+            render(&[result], format)
+        }
+    };
+    rendered.map_err(|e| Failure(ExitStatus::UserError, e))
+}
+
+/// Whether a command submits a transaction
+fn needs_signer(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::RegistryInfo | Command::MemberShow(_) | Command::MemberList | Command::BlobShow(_)
+    )
+}
+
+/// A registry client signing with the configured key
+#[cfg(feature = "signing")]
+async fn signing_client(
+    config: &WorkerConfig,
+    client: sui_sdk::SuiClient,
+    registry_id: ObjectID,
+) -> Result<CanaryClient, Failure> {
+    use canary_sdk::client::SuiClientWithSigner;
+    use canary_sdk::keystore::{add_to_keystore, confirm_signer_address, parse_bech32_private_key};
+    use sui_keys::keystore::{InMemKeystore, Keystore};
+
+    let bech32_key = std::env::var(&config.key.env)
+        .map(zeroize::Zeroizing::new)
+        .map_err(|_| Failure::usage(format!("{} is not set", config.key.env)))?;
+    let parsed = parse_bech32_private_key(&bech32_key)?;
+    if let Some(expected) = config.key.expected_address {
+        confirm_signer_address(&parsed, expected)?;
+    }
+
+    let mut keystore = Keystore::InMem(InMemKeystore::default());
+    let signer = add_to_keystore(&mut keystore, parsed).await?;
+    let signer = SuiClientWithSigner {
+        client,
+        signer,
+        keystore,
+        retry: Default::default(),
+        profiles: Default::default(),
+        gas_policy: config.gas_policy(),
+    };
+    Ok(CanaryClient::with_signer(signer, registry_id))
+}
+
+/// Builds without signing support cannot submit transactions
+#[cfg(not(feature = "signing"))]
+async fn signing_client(
+    _config: &WorkerConfig,
+    _client: sui_sdk::SuiClient,
+    _registry_id: ObjectID,
+) -> Result<CanaryClient, Failure> {
+    Err(Failure::usage(
+        "This command submits a transaction, which a build without the `signing` feature cannot do",
+    ))
+}
+
+/// Submit the transaction of a write command
+#[cfg(feature = "signing")]
+async fn execute(canary: &CanaryClient, command: Command) -> Result<TransactionResult, Failure> {
+    let receipt = match command {
+        Command::MemberJoin { domain, fee } => {
+            let fee = match fee {
+                Some(fee) => fee,
+                None => canary.registry().await?.fee,
+            };
+            canary.join(domain, fee).await?
+        }
+        Command::BlobStore {
+            domain,
+            contract_blob_id,
+            explain_blob_id,
+            package_id,
+        } => {
+            canary
+                .store_blob(domain, contract_blob_id, explain_blob_id, package_id)
+                .await?
+        }
+        Command::BlobUpdate {
+            canary_blob_id,
+            contract_blob_id,
+            explain_blob_id,
+        } => {
+            canary
+                .update_blob(canary_blob_id, contract_blob_id, explain_blob_id)
+                .await?
+        }
+        Command::BlobDelete(canary_blob_id) => canary.delete_blob(canary_blob_id).await?,
+        Command::AdminWithdraw { amount, recipient } => {
+            // Fees go to the signer unless another recipient is given
+            let recipient = match (recipient, canary.signer()) {
+                (Some(recipient), _) => recipient,
+                (None, Some(signer)) => signer.signer,
+                (None, None) => return Err(CanaryError::NoSigner.into()),
+            };
+            canary.withdraw(amount, recipient).await?
+        }
+        Command::AdminSetFee(fee) => canary.set_fee(fee).await?.receipt,
+        Command::RegistryInfo
+        | Command::MemberShow(_)
+        | Command::MemberList
+        | Command::BlobShow(_) => unreachable!("read commands don't submit transactions"),
+    };
+    Ok(TransactionResult::from(&receipt))
+}
+
+#[cfg(not(feature = "signing"))]
+async fn execute(_canary: &CanaryClient, _command: Command) -> Result<TransactionResult, Failure> {
+    unreachable!("signing_client fails first in builds without signing")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_words(words: &str) -> Result<Args, Failure> {
+        let argv: Vec<String> = words.split_whitespace().map(String::from).collect();
+        parse_args(&argv)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let args = parse_words("-o json member join example.com 1000").unwrap();
+        assert_eq!(args.output, OutputFormat::Json);
+        assert_eq!(
+            args.command,
+            Command::MemberJoin {
+                domain: "example.com".to_string(),
+                fee: Some(1000),
+            }
+        );
+
+        let args = parse_words("--config /etc/canary.toml admin set-fee 5").unwrap();
+        assert_eq!(args.config, PathBuf::from("/etc/canary.toml"));
+        assert_eq!(args.command, Command::AdminSetFee(5));
+        assert!(!needs_signer(
+            &parse_words("registry info").unwrap().command
+        ));
+        assert!(needs_signer(&args.command));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for words in [
+            "member",
+            "member show not-an-address",
+            "admin set-fee lots",
+            "--output xml registry info",
+            "--verbose registry info",
+            "--config",
+        ] {
+            assert!(
+                matches!(parse_words(words), Err(Failure(ExitStatus::UserError, _))),
+                "{} should not parse",
+                words
+            );
+        }
+    }
+}
@@ -1,18 +1,81 @@
 //! Canary contract helpers
 //!
 //! This module provides high-level functions for interacting with the Canary contract,
-//! including member registry operations and package storage operations.
+//! including member registry operations and package storage operations. `CanaryClient`
+//! wraps them for one registry.
 
+use crate::client::ratelimit::RateLimiter;
+use crate::client::view_call;
+#[cfg(feature = "signing")]
 use crate::client::SuiClientWithSigner;
+use crate::domain::{candidate_domains, SubdomainPolicy};
 use crate::error::{CanaryError, TransactionError};
-use crate::transaction::CanaryTransactionBuilder;
+#[cfg(feature = "signing")]
+use crate::transaction::{
+    pure_address, pure_string, pure_u64, CanaryTransactionBuilder, MoveAbort,
+};
 use serde::{Deserialize, Serialize};
 use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiTransactionBlockEffectsAPI};
-use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_sdk::types::transaction::{CallArg, ObjectArg, SharedObjectMutability};
 use sui_sdk::SuiClient;
 use sui_types::base_types::SequenceNumber;
 
+pub mod audit;
+#[cfg(feature = "signing")]
+pub mod batch;
+#[cfg(feature = "bindings")]
+pub mod bindings;
+pub mod client;
+pub mod history;
+pub mod ledger;
+#[cfg(feature = "signing")]
+pub mod migration;
+pub mod notary;
+pub mod parsing;
+pub mod preflight;
+#[cfg(feature = "signing")]
+pub mod preview;
+pub mod proposals;
+#[cfg(feature = "signing")]
+pub mod publish;
+#[cfg(feature = "signing")]
+pub mod receipt;
+pub mod shared;
+
+pub use audit::{
+    latest_registry_transaction, query_registry_transactions, RegistryCallKind,
+    RegistryTransaction, RegistryTransactionPage,
+};
+#[cfg(feature = "signing")]
+pub use batch::{store_blobs_batch, update_blobs_batch, BlobStoreEntry, BlobUpdateEntry};
+pub use client::CanaryClient;
+pub use history::{query_canary_blob_at, query_registry_at};
+pub use ledger::{registry_ledger, LedgerEntry, LedgerEntryKind, RegistryLedger};
+#[cfg(feature = "signing")]
+pub use notary::notarize;
+pub use notary::{verify_receipt, NotarizedReceipt};
+pub use preflight::{assert_owned_by, validate_admin_cap, validate_admin_cap_for};
+#[cfg(feature = "signing")]
+pub use preview::{update_blob_preview, BlobDiff, BlobUpdatePreview, FieldChange};
+#[cfg(feature = "signing")]
+pub use publish::{publish_canary, BlobUploader, PublishRequest, PublishedCanary};
+#[cfg(feature = "signing")]
+pub use receipt::{
+    extract_created, extract_created_blob, object_changes_of_type, CanaryObjectKind,
+    MoveObjectType, ObjectChangeKind, ReceiptObject, TransactionReceipt,
+};
+pub use shared::SharedObjectResolver;
+
+/// The on-chain object an info struct was decoded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawObject {
+    /// Reference (ID, version, digest) of the object at the time it was read
+    pub object_ref: ObjectRef,
+    /// BCS bytes of the object's Move struct
+    pub bcs: Vec<u8>,
+}
+
 /// Information about a Registry object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryInfo {
@@ -24,6 +87,9 @@ pub struct RegistryInfo {
     pub member_count: u64,
     /// The admin address
     pub admin: SuiAddress,
+    /// The object this info was decoded from, if the query returned it
+    #[serde(skip)]
+    pub raw: Option<RawObject>,
 }
 
 /// Information about a member
@@ -33,6 +99,9 @@ pub struct MemberInfo {
     pub domain: String,
     /// Timestamp when the member joined (in milliseconds)
     pub joined_at: u64,
+    /// The `members` table entry this info was decoded from, if the query returned it
+    #[serde(skip)]
+    pub raw: Option<RawObject>,
 }
 
 /// Information about a member with their address
@@ -44,6 +113,9 @@ pub struct MemberInfoWithAddress {
     pub domain: String,
     /// Timestamp when the member joined (in milliseconds)
     pub joined_at: u64,
+    /// The `members` table entry this info was decoded from, if the query returned it
+    #[serde(skip)]
+    pub raw: Option<RawObject>,
 }
 
 /// Information about a CanaryBlob object
@@ -63,24 +135,141 @@ pub struct CanaryBlobInfo {
     pub uploaded_at: u64,
     /// Address of the admin who uploaded the blob
     pub uploaded_by_admin: SuiAddress,
+    /// The object this info was decoded from, if the query returned it
+    #[serde(skip)]
+    pub raw: Option<RawObject>,
+}
+
+/// Accessors for the raw object behind a decoded info struct
+///
+/// These let advanced users decode the object themselves, or pass its exact reference
+/// into a custom PTB, without fetching it again.
+macro_rules! impl_raw_accessors {
+    ($($ty:ty),*) => {$(
+        impl $ty {
+            /// BCS bytes of the underlying Move object, if available
+            pub fn raw_bcs(&self) -> Option<&[u8]> {
+                self.raw.as_ref().map(|raw| raw.bcs.as_slice())
+            }
+
+            /// Reference to the underlying object as it was read, if available
+            pub fn object_ref(&self) -> Option<ObjectRef> {
+                self.raw.as_ref().map(|raw| raw.object_ref)
+            }
+        }
+    )*};
 }
 
+impl_raw_accessors!(
+    RegistryInfo,
+    MemberInfo,
+    MemberInfoWithAddress,
+    CanaryBlobInfo
+);
+
 // ============================================================================
 // Member Registry Functions
 // ============================================================================
 
+/// Result of `create_registry`
+#[cfg(feature = "signing")]
+#[derive(Debug, Clone)]
+pub struct CreatedRegistry {
+    /// The new Registry object ID
+    pub registry_id: ObjectID,
+    /// The AdminCap of the new registry, owned by the signer
+    pub admin_cap_id: ObjectID,
+    /// The transaction receipt
+    pub receipt: TransactionReceipt,
+}
+
+/// Create a new registry administered by the signer
+///
+/// Publishing the package creates one registry in its `init`; this creates further
+/// registries from an already published package, e.g. for a new deployment or a
+/// test that needs a fresh registry.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner`; the signer becomes the registry admin
+/// * `package_id` - The published Canary package ID
+/// * `fee` - The membership fee in MIST
+///
+/// # Returns
+///
+/// Returns the new registry and AdminCap IDs, or a `CanaryError` if the transaction
+/// fails or does not create both objects.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::create_registry;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let package_id = ObjectID::from_hex_literal("0x123...")?;
+/// let created = create_registry(client, package_id, 1_000_000_000).await?;
+/// println!("Registry: {}, AdminCap: {}", created.registry_id, created.admin_cap_id);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn create_registry(
+    client: SuiClientWithSigner,
+    package_id: ObjectID,
+    fee: u64,
+) -> Result<CreatedRegistry, CanaryError> {
+    // create_registry(fee: u64, ctx: &mut TxContext)
+    let args = vec![pure_u64(fee)];
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", "create_registry", args)?;
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+
+    let receipt = TransactionReceipt::from_response(response);
+    let created = |kind: CanaryObjectKind| {
+        receipt.created(kind).ok_or_else(|| {
+            CanaryError::Registry(format!(
+                "Transaction {} created no {:?}",
+                receipt.digest, kind
+            ))
+        })
+    };
+    let registry_id = created(CanaryObjectKind::Registry)?;
+    let admin_cap_id = created(CanaryObjectKind::AdminCap)?;
+
+    Ok(CreatedRegistry {
+        registry_id,
+        admin_cap_id,
+        receipt,
+    })
+}
+
 /// Join the registry by paying the membership fee
 ///
+/// The registry holds one membership per address, so there is no batch variant: every
+/// call in a programmable transaction has the same sender, and a second
+/// `join_registry` call for it aborts with `CanaryError::AlreadyMember`. To onboard
+/// several domains at once, join from one key per domain.
+///
+/// Unless `force` is set, the signer's membership is checked first, so joining twice
+/// fails with `CanaryError::AlreadyMember` before any gas is spent.
+///
 /// # Arguments
 ///
 /// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
 /// * `registry_id` - The Registry object ID
 /// * `domain` - The domain name to register
 /// * `payment_amount` - The payment amount in MIST (must be >= registry fee)
+/// * `force` - Skip the membership check and submit the transaction regardless
 ///
 /// # Returns
 ///
-/// Returns the transaction response, or a `CanaryError` if the operation fails.
+/// Returns the transaction receipt, `CanaryError::AlreadyMember` if the signer is
+/// already a member, or a `CanaryError` if the operation fails or aborts on-chain.
 ///
 /// # Example
 ///
@@ -92,21 +281,26 @@ pub struct CanaryBlobInfo {
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
 /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
-/// let response = join_registry(&client, registry_id, "example.com".to_string(), 1_000_000_000).await?;
-/// println!("Joined registry: {:?}", response.digest());
+/// let receipt = join_registry(
+///     client,
+///     registry_id,
+///     "example.com".to_string(),
+///     1_000_000_000,
+///     false,
+/// )
+/// .await?;
+/// println!("Joined registry: {:?}", receipt.digest);
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "signing")]
 pub async fn join_registry(
     client: SuiClientWithSigner,
     registry_id: ObjectID,
     domain: String,
     payment_amount: u64,
-) -> Result<sui_sdk::rpc_types::SuiTransactionBlockResponse, CanaryError> {
-    // Get the Clock object ID (0x6 is the Clock object)
-    let clock_id = ObjectID::from_hex_literal("0x6")
-        .map_err(|e| CanaryError::Registry(format!("Failed to parse Clock object ID: {}", e)))?;
-
+    force: bool,
+) -> Result<TransactionReceipt, CanaryError> {
     // Get the package ID - we need to get it from the registry object
     // For now, we'll need the package ID as a parameter or derive it
     // Let's get it from querying the registry first
@@ -122,9 +316,6 @@ pub async fn join_registry(
         .into_object()
         .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
 
-    // Get the object reference before moving the type field
-    let registry_ref = registry_obj.object_ref();
-
     // Extract package ID from the object type
     // The type should be something like "0x<PACKAGE_ID>::member_registry::Registry"
     let object_type = registry_obj
@@ -135,6 +326,11 @@ pub async fn join_registry(
         CanaryError::Registry("Failed to extract package ID from registry type".to_string())
     })?;
 
+    // A second join aborts on-chain anyway; fail here instead of paying gas for it
+    if !force && query_is_member(&client.client, package_id, registry_id, client.signer).await? {
+        return Err(CanaryError::AlreadyMember);
+    }
+
     // Get a coin for payment
     let coins = client
         .client
@@ -166,6 +362,10 @@ pub async fn join_registry(
         .into_object()
         .map_err(|_| CanaryError::Registry("Payment coin object not found".to_string()))?;
 
+    let registry_arg = SharedObjectResolver::global()
+        .shared_arg(&client.client, registry_id, SharedObjectMutability::Mutable)
+        .await?;
+
     // Create a transaction builder (after we've extracted all needed data)
     let mut builder = CanaryTransactionBuilder::new(client);
 
@@ -175,20 +375,11 @@ pub async fn join_registry(
 
     // Build the move_call arguments
     // join_registry(registry: &mut Registry, payment: Coin<SUI>, domain: String, clock: &Clock, ctx: &mut TxContext)
-    use sui_sdk::types::transaction::SharedObjectMutability;
     let args = vec![
-        CallArg::Object(ObjectArg::SharedObject {
-            id: registry_id,
-            initial_shared_version: registry_ref.1, // version from object_ref
-            mutability: SharedObjectMutability::Mutable,
-        }),
+        registry_arg,
         CallArg::Object(ObjectArg::ImmOrOwnedObject(payment_coin_obj.object_ref())),
-        CallArg::Pure(domain.as_bytes().to_vec()),
-        CallArg::Object(ObjectArg::SharedObject {
-            id: clock_id,
-            initial_shared_version: SequenceNumber::from(1), // Clock is always at version 1
-            mutability: SharedObjectMutability::Immutable,
-        }),
+        pure_string(&domain),
+        SharedObjectResolver::clock_arg(),
     ];
 
     // Add the move_call
@@ -201,8 +392,38 @@ pub async fn join_registry(
         .execute()
         .await
         .map_err(|e| CanaryError::Transaction(e))?;
+    ensure_success(&response)?;
+
+    Ok(TransactionReceipt::from_response(response))
+}
 
-    Ok(response)
+/// Join the registry after confirming the signer controls the domain
+///
+/// Checks that `domain` serves the signer's challenge token (see
+/// `domain::challenge_token`) at `/.well-known/sui-canary-challenge` before paying
+/// the membership fee, so a fee is never spent on a domain that cannot be verified.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
+/// * `registry_id` - The Registry object ID
+/// * `domain` - The domain name to register
+/// * `payment_amount` - The payment amount in MIST (must be >= registry fee)
+/// * `force` - Skip the membership check (see `join_registry`)
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if verification or the join fails.
+#[cfg(feature = "signing")]
+pub async fn join_registry_verified(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    domain: String,
+    payment_amount: u64,
+    force: bool,
+) -> Result<TransactionReceipt, CanaryError> {
+    crate::domain::verify_domain_ownership_http(&domain, client.signer).await?;
+    join_registry(client, registry_id, domain, payment_amount, force).await
 }
 
 /// Query registry information
@@ -236,42 +457,17 @@ pub async fn query_registry(
     client: &SuiClient,
     registry_id: ObjectID,
 ) -> Result<RegistryInfo, CanaryError> {
-    // Get the registry object with full content
+    // Decode the registry directly from its BCS bytes
     let registry_obj = client
         .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
+        .get_object_with_options(registry_id, SuiObjectDataOptions::bcs_lossless())
         .await
         .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
         .into_object()
         .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
 
-    // Extract package ID from type
-    let object_type = registry_obj
-        .type_
-        .ok_or_else(|| CanaryError::Registry("Registry object has no type".to_string()))?;
-
-    let package_id = extract_package_id_from_type(&object_type.to_string())
-        .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
-
-    // Use dev_inspect to call the view functions
-    // We'll call get_admin and access fields directly from the object data
-
-    // Parse the object's bcs data to extract fields
-    // The Registry struct has: id, members, member_addresses, member_count, fee, balance, admin
-    // We need to use dev_inspect to call view functions or parse the object data
-
-    // For now, let's use dev_inspect to call get_admin
-    let admin = query_registry_admin(client, package_id, registry_id).await?;
-
-    // Get member_count and fee using dev_inspect
-    let (member_count, fee) = query_registry_fields(client, package_id, registry_id).await?;
-
-    Ok(RegistryInfo {
-        id: registry_id,
-        fee,
-        member_count,
-        admin,
-    })
+    let (registry, raw) = parsing::decode_object::<parsing::RegistryObject>(&registry_obj)?;
+    Ok(registry.into_info(Some(raw)))
 }
 
 /// Query member information
@@ -318,6 +514,7 @@ pub async fn query_member(
         .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
         .into_object()
         .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
+    let members_table = json_table_id(&object_fields(registry_obj.clone())?, "members")?;
 
     let object_type = registry_obj
         .type_
@@ -334,208 +531,643 @@ pub async fn query_member(
     }
 
     // Get member info using dev_inspect
-    let member_info = query_member_info(client, package_id, registry_id, member_address).await?;
+    let mut member_info =
+        query_member_info(client, package_id, registry_id, member_address).await?;
+    member_info.raw = get_member_field_raw(client, members_table, member_address).await?;
 
     Ok(Some(member_info))
 }
 
-// ============================================================================
-// Package Storage Functions
-// ============================================================================
-
-/// Store a blob in the registry
+/// List one page of registry members
+///
+/// Members are read from the registry's `members` table via dynamic field queries, so
+/// this works for registries of any size. Pages are in table order, not join order.
 ///
 /// # Arguments
 ///
-/// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
+/// * `client` - A `SuiClient` for querying
 /// * `registry_id` - The Registry object ID
-/// * `admin_cap_id` - The AdminCap object ID
-/// * `domain` - The domain name
-/// * `contract_blob_id` - The contract blob object ID (as address)
-/// * `explain_blob_id` - The explain blob object ID (as address)
-/// * `package_id` - The package ID (as address)
+/// * `cursor` - The cursor returned by the previous page, or `None` for the first page
+/// * `limit` - Maximum number of members to return (`None` for the node's default)
 ///
 /// # Returns
 ///
-/// Returns the transaction response, or a `CanaryError` if the operation fails.
-pub async fn store_blob(
-    client: SuiClientWithSigner,
+/// Returns the page of members and the cursor for the next page (`None` if this was
+/// the last page), or a `CanaryError` if the query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::list_members;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let mut cursor = None;
+/// loop {
+///     let (members, next) = list_members(&client, registry_id, cursor, Some(50)).await?;
+///     for member in members {
+///         println!("{} -> {}", member.member, member.domain);
+///     }
+///     match next {
+///         Some(next) => cursor = Some(next),
+///         None => break,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_members(
+    client: &SuiClient,
     registry_id: ObjectID,
-    admin_cap_id: ObjectID,
-    domain: String,
-    contract_blob_id: ObjectID,
-    explain_blob_id: ObjectID,
-    package_id: ObjectID,
-) -> Result<sui_sdk::rpc_types::SuiTransactionBlockResponse, CanaryError> {
-    // Get the Clock object ID
-    let clock_id = ObjectID::from_hex_literal("0x6")
-        .map_err(|e| CanaryError::Registry(format!("Failed to parse Clock object ID: {}", e)))?;
-
-    // Get the package ID from the registry object
-    let registry_obj = client
-        .client
+    cursor: Option<ObjectID>,
+    limit: Option<usize>,
+) -> Result<(Vec<MemberInfoWithAddress>, Option<ObjectID>), CanaryError> {
+    let registry_fields = get_object_fields(client, registry_id).await?;
+    let members_table = json_table_id(&registry_fields, "members")?;
+
+    RateLimiter::global().acquire().await;
+    let page = client
         .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
+        .get_dynamic_fields(members_table, cursor, limit)
         .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
-    let registry_ref = registry_obj.object_ref();
+        .map_err(|e| CanaryError::Registry(format!("Failed to list members: {}", e)))?;
 
-    let object_type = registry_obj
-        .type_
-        .ok_or_else(|| CanaryError::Registry("Registry object has no type".to_string()))?;
+    let field_ids = page.data.iter().map(|field| field.object_id).collect();
+    let members = get_member_fields(client, field_ids).await?;
+    let next_cursor = if page.has_next_page {
+        page.next_cursor
+    } else {
+        None
+    };
 
-    let canary_package_id = extract_package_id_from_type(&object_type.to_string())
-        .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
+    Ok((members, next_cursor))
+}
 
-    // Get admin cap object
-    let admin_cap_obj = client
-        .client
-        .read_api()
-        .get_object_with_options(admin_cap_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get admin cap: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Admin cap not found".to_string()))?;
+/// List all registry members, following `list_members` pages to the end
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+///
+/// # Returns
+///
+/// Returns all members in table order, or a `CanaryError` if any page fails.
+pub async fn query_all_members(
+    client: &SuiClient,
+    registry_id: ObjectID,
+) -> Result<Vec<MemberInfoWithAddress>, CanaryError> {
+    let mut members = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next) = list_members(client, registry_id, cursor, None).await?;
+        members.extend(page);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return Ok(members),
+        }
+    }
+}
 
-    // Build the move_call arguments
-    // store_blob(registry: &mut Registry, admin_cap: &AdminCap, domain: String,
-    //            contract_blob_id: address, explain_blob_id: address, package_id: address,
-    //            clock: &Clock, ctx: &mut TxContext)
+/// `member_registry` function for self-removal:
+/// `leave_registry(&mut Registry, MembershipCap, &mut TxContext)`
+pub const LEAVE_REGISTRY_FUNCTION: &str = "leave_registry";
+
+/// Leave the registry, giving up the signer's membership
+///
+/// The signer's `MembershipCap` for the registry is looked up and burned by
+/// `member_registry::leave_registry`. Membership fees are not refunded.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` for the member
+/// * `registry_id` - The Registry object ID
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if the signer is not a member,
+/// holds no `MembershipCap` for the registry, or the transaction fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::leave_registry;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let receipt = leave_registry(client, registry_id).await?;
+/// println!("Left registry: {:?}", receipt.digest);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn leave_registry(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+) -> Result<TransactionReceipt, CanaryError> {
+    let signer = client.signer;
+    let (package_id, registry_isv) = registry_package(&client.client, registry_id).await?;
+
+    if !query_is_member(&client.client, package_id, registry_id, signer).await? {
+        return Err(CanaryError::NotMember);
+    }
+    let cap_ref = find_membership_cap(&client.client, signer, package_id, registry_id).await?;
+
+    // leave_registry(registry: &mut Registry, cap: MembershipCap, ctx: &mut TxContext)
     let args = vec![
         CallArg::Object(ObjectArg::SharedObject {
             id: registry_id,
-            initial_shared_version: registry_ref.1, // version from object_ref
+            initial_shared_version: registry_isv,
             mutability: SharedObjectMutability::Mutable,
         }),
-        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_obj.object_ref())),
-        CallArg::Pure(domain.as_bytes().to_vec()),
-        CallArg::Pure(contract_blob_id.to_vec()),
-        CallArg::Pure(explain_blob_id.to_vec()),
-        CallArg::Pure(package_id.to_vec()),
-        CallArg::Object(ObjectArg::SharedObject {
-            id: clock_id,
-            initial_shared_version: SequenceNumber::from(1),
-            mutability: SharedObjectMutability::Immutable,
-        }),
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(cap_ref)),
     ];
 
     let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", LEAVE_REGISTRY_FUNCTION, args)?;
 
-    builder
-        .move_call(canary_package_id, "pkg_storage", "store_blob", args)
-        .map_err(|e| CanaryError::Transaction(e))?;
-
-    let response = builder
-        .execute()
-        .await
-        .map_err(|e| CanaryError::Transaction(e))?;
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
 
-    Ok(response)
+    Ok(TransactionReceipt::from_response(response))
 }
 
-/// Update a blob in the registry
+// ============================================================================
+// Admin Functions
+// ============================================================================
+
+/// `member_registry` function paying withdrawn fees to any address:
+/// `withdraw_to(&mut Registry, &AdminCap, u64, address, &mut TxContext)`
+pub const WITHDRAW_TO_FUNCTION: &str = "withdraw_to";
+
+/// Withdraw accumulated membership fees from the registry balance
+///
+/// Calls `member_registry::withdraw_to`, which pays the withdrawn coin to `recipient`
+/// directly.
 ///
 /// # Arguments
 ///
-/// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
-/// * `registry_id` - The Registry object ID (required by Move function)
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
 /// * `admin_cap_id` - The AdminCap object ID
-/// * `canary_blob_id` - The CanaryBlob object ID
-/// * `new_contract_blob_id` - The new contract blob object ID (as address)
-/// * `new_explain_blob_id` - The new explain blob object ID (as address)
+/// * `amount` - The amount to withdraw in MIST
+/// * `recipient` - The address to receive the fees
 ///
 /// # Returns
 ///
-/// Returns the transaction response, or a `CanaryError` if the operation fails.
+/// Returns the transaction receipt, or a `CanaryError` if the package predates
+/// `withdraw_to` or the transaction fails.
 ///
-/// # Note
+/// # Example
 ///
-/// The Move function `update_blob` requires a `registry` parameter, so `registry_id` is needed.
-/// This is a reasonable extension to the plan's function signature.
-pub async fn update_blob(
+/// ```rust,no_run
+/// use canary_sdk::canary::withdraw_fees;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let treasury = client.signer();
+/// let receipt = withdraw_fees(client, registry_id, admin_cap_id, 5_000_000_000, treasury).await?;
+/// println!("Withdrew fees: {:?}", receipt.digest);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn withdraw_fees(
     client: SuiClientWithSigner,
     registry_id: ObjectID,
     admin_cap_id: ObjectID,
-    canary_blob_id: ObjectID,
-    new_contract_blob_id: ObjectID,
-    new_explain_blob_id: ObjectID,
-) -> Result<sui_sdk::rpc_types::SuiTransactionBlockResponse, CanaryError> {
-    // Get the Clock object ID
-    let clock_id = ObjectID::from_hex_literal("0x6")
-        .map_err(|e| CanaryError::Registry(format!("Failed to parse Clock object ID: {}", e)))?;
+    amount: u64,
+    recipient: SuiAddress,
+) -> Result<TransactionReceipt, CanaryError> {
+    if amount == 0 {
+        return Err(CanaryError::Registry(
+            "Withdrawal amount must be greater than 0".to_string(),
+        ));
+    }
 
-    // Get the canary blob object to extract package ID and registry info
-    let canary_blob_obj = client
-        .client
-        .read_api()
-        .get_object_with_options(canary_blob_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::CanaryBlobNotFound)?;
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
 
-    let canary_blob = canary_blob_obj
-        .into_object()
-        .map_err(|_| CanaryError::CanaryBlobNotFound)?;
+    // withdraw_to(registry: &mut Registry, admin_cap: &AdminCap, amount: u64,
+    //             recipient: address, ctx: &mut TxContext)
+    let args = vec![
+        registry_arg,
+        admin_cap_arg,
+        pure_u64(amount),
+        pure_address(recipient),
+    ];
 
-    // Get the object reference before moving the type field
-    let canary_blob_ref = canary_blob.object_ref();
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", WITHDRAW_TO_FUNCTION, args)?;
 
-    let object_type = canary_blob
-        .type_
-        .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
 
-    let canary_package_id = extract_package_id_from_type(&object_type.to_string())
-        .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
+    Ok(TransactionReceipt::from_response(response))
+}
 
-    // Get admin cap object
-    let admin_cap_obj = client
-        .client
-        .read_api()
-        .get_object_with_options(admin_cap_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get admin cap: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Admin cap not found".to_string()))?;
+/// Result of `set_fee`
+#[cfg(feature = "signing")]
+#[derive(Debug, Clone)]
+pub struct FeeUpdate {
+    /// The transaction receipt
+    pub receipt: TransactionReceipt,
+    /// The fee read back from the registry, in MIST
+    pub fee: u64,
+}
 
-    // Get registry object
-    let registry_obj = client
-        .client
-        .read_api()
+/// Update the membership fee
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `new_fee` - The new membership fee in MIST
+///
+/// # Returns
+///
+/// Returns a `FeeUpdate` with the fee read back from the registry after the transaction
+/// executed, or a `CanaryError` if the signer does not hold the registry's AdminCap,
+/// the transaction fails, or the registry does not report the new fee.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::set_fee;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let update = set_fee(client, registry_id, admin_cap_id, 2_000_000_000).await?;
+/// println!("Fee is now {} MIST", update.fee);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn set_fee(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    new_fee: u64,
+) -> Result<FeeUpdate, CanaryError> {
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
+    let sui_client = client.client.clone();
+
+    // update_fee(registry: &mut Registry, admin_cap: &AdminCap, new_fee: u64)
+    let args = vec![registry_arg, admin_cap_arg, pure_u64(new_fee)];
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", "update_fee", args)?;
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+
+    let fee = query_registry(&sui_client, registry_id).await?.fee;
+    if fee != new_fee {
+        return Err(CanaryError::Registry(format!(
+            "Registry fee is {} MIST after update, expected {}",
+            fee, new_fee
+        )));
+    }
+
+    Ok(FeeUpdate {
+        receipt: TransactionReceipt::from_response(response),
+        fee,
+    })
+}
+
+/// Hand the AdminCap to a new admin, e.g. to rotate the admin key
+///
+/// Calls `member_registry::transfer_admin`, which moves the cap and updates the
+/// registry's recorded admin in the same transaction.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `new_admin` - The address to receive the AdminCap
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if the signer does not hold the
+/// registry's AdminCap, the package predates `transfer_admin`, or the transaction
+/// fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::transfer_admin;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let new_admin: SuiAddress = "0x789...".parse()?;
+/// let receipt = transfer_admin(client, registry_id, admin_cap_id, new_admin).await?;
+/// println!("AdminCap transferred: {:?}", receipt.digest);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn transfer_admin(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    new_admin: SuiAddress,
+) -> Result<TransactionReceipt, CanaryError> {
+    use crate::incident::{contract_support, TRANSFER_ADMIN_FUNCTION};
+
+    if new_admin == client.signer {
+        return Err(CanaryError::Registry(
+            "New admin is the current signer".to_string(),
+        ));
+    }
+
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
+    let support = contract_support(&client.client, package_id).await?;
+    if !support.transfer_admin {
+        return Err(CanaryError::Registry(format!(
+            "Contract does not expose member_registry::{}",
+            TRANSFER_ADMIN_FUNCTION
+        )));
+    }
+
+    // transfer_admin(registry: &mut Registry, admin_cap: AdminCap, new_admin: address)
+    let args = vec![registry_arg, admin_cap_arg, pure_address(new_admin)];
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", TRANSFER_ADMIN_FUNCTION, args)?;
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Remove a member from the registry (admin eviction)
+///
+/// Membership fees are not refunded, and the evicted member's `MembershipCap` is not
+/// burned.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `member` - The address of the member to remove
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if the signer does not hold the
+/// registry's AdminCap, the address is not a member, or the transaction fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::remove_member;
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let member: SuiAddress = "0x789...".parse()?;
+/// let receipt = remove_member(client, registry_id, admin_cap_id, member).await?;
+/// println!("Removed member: {:?}", receipt.digest);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn remove_member(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    member: SuiAddress,
+) -> Result<TransactionReceipt, CanaryError> {
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
+    if !query_is_member(&client.client, package_id, registry_id, member).await? {
+        return Err(CanaryError::NotMember);
+    }
+
+    // remove_member(registry: &mut Registry, admin_cap: &AdminCap, member: address)
+    let args = vec![registry_arg, admin_cap_arg, pure_address(member)];
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.move_call(package_id, "member_registry", "remove_member", args)?;
+
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Resolve the package ID and initial shared version of a registry
+#[cfg(feature = "signing")]
+async fn registry_package(
+    client: &SuiClient,
+    registry_id: ObjectID,
+) -> Result<(ObjectID, SequenceNumber), CanaryError> {
+    let registry_obj = client
+        .read_api()
+        .get_object_with_options(registry_id, SuiObjectDataOptions::new().with_type())
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
+        .into_object()
+        .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
+    let package_id = registry_obj
+        .type_
+        .and_then(|t| extract_package_id_from_type(&t.to_string()))
+        .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
+
+    let registry_isv = SharedObjectResolver::global()
+        .initial_shared_version(client, registry_id)
+        .await?;
+
+    Ok((package_id, registry_isv))
+}
+
+/// Find the signer's `MembershipCap` for a registry
+#[cfg(feature = "signing")]
+async fn find_membership_cap(
+    client: &SuiClient,
+    owner: SuiAddress,
+    package_id: ObjectID,
+    registry_id: ObjectID,
+) -> Result<ObjectRef, CanaryError> {
+    use sui_sdk::rpc_types::{SuiObjectDataFilter, SuiObjectResponseQuery};
+    use sui_sdk::types::parse_sui_struct_tag;
+
+    let cap_type = parse_sui_struct_tag(&format!("{}::member_registry::MembershipCap", package_id))
+        .map_err(|e| CanaryError::Registry(format!("Invalid MembershipCap type: {}", e)))?;
+    let query = SuiObjectResponseQuery::new(
+        Some(SuiObjectDataFilter::StructType(cap_type)),
+        Some(SuiObjectDataOptions::new().with_content()),
+    );
+
+    let mut cursor = None;
+    loop {
+        let page = client
+            .read_api()
+            .get_owned_objects(owner, Some(query.clone()), cursor, None)
+            .await
+            .map_err(|e| CanaryError::Registry(format!("Failed to get owned objects: {}", e)))?;
+
+        for data in page.data.into_iter().filter_map(|response| response.data) {
+            let object_ref = data.object_ref();
+            let fields = object_fields(data)?;
+            let cap_registry = json_string(&fields, "registry_id")?;
+            if ObjectID::from_hex_literal(&cap_registry).ok() == Some(registry_id) {
+                return Ok(object_ref);
+            }
+        }
+
+        if !page.has_next_page {
+            return Err(CanaryError::NotMember);
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+/// Return an error if the transaction's effects report a failed execution
+#[cfg(feature = "signing")]
+fn ensure_success(
+    response: &sui_sdk::rpc_types::SuiTransactionBlockResponse,
+) -> Result<(), CanaryError> {
+    use sui_sdk::rpc_types::SuiExecutionStatus;
+
+    let effects = response.effects.as_ref().ok_or_else(|| {
+        CanaryError::Registry(format!("Transaction {} has no effects", response.digest))
+    })?;
+    match effects.status() {
+        SuiExecutionStatus::Success => Ok(()),
+        SuiExecutionStatus::Failure { error } => match MoveAbort::parse(error) {
+            Some(abort) => Err(CanaryError::from(abort)),
+            None => Err(CanaryError::Transaction(
+                TransactionError::failed_execution(effects),
+            )),
+        },
+    }
+}
+
+/// Resolve the package ID and the mutable Registry and AdminCap arguments for an
+/// admin-only `member_registry` call
+///
+/// Fails with `CanaryError::NotAdmin` unless the signer owns the AdminCap and the cap
+/// belongs to this registry, so a mismatched cap is caught before paying for gas.
+#[cfg(feature = "signing")]
+async fn admin_call_args(
+    client: &SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+) -> Result<(ObjectID, CallArg, CallArg), CanaryError> {
+    let (package_id, registry_isv) = registry_package(&client.client, registry_id).await?;
+    let registry_arg = CallArg::Object(ObjectArg::SharedObject {
+        id: registry_id,
+        initial_shared_version: registry_isv,
+        mutability: SharedObjectMutability::Mutable,
+    });
+
+    let admin_cap_ref =
+        validate_admin_cap_for(&client.client, registry_id, admin_cap_id, client.signer).await?;
+
+    Ok((
+        package_id,
+        registry_arg,
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_ref)),
+    ))
+}
+
+// ============================================================================
+// Package Storage Functions
+// ============================================================================
+
+/// Store a blob in the registry
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `domain` - The domain name
+/// * `contract_blob_id` - The contract blob object ID (as address)
+/// * `explain_blob_id` - The explain blob object ID (as address)
+/// * `package_id` - The package ID (as address)
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if the operation fails.
+#[cfg(feature = "signing")]
+pub async fn store_blob(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    domain: String,
+    contract_blob_id: ObjectID,
+    explain_blob_id: ObjectID,
+    package_id: ObjectID,
+) -> Result<TransactionReceipt, CanaryError> {
+    // Get the Clock object ID
+    // Get the package ID from the registry object
+    let registry_obj = client
+        .client
+        .read_api()
         .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
         .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry: {}", e)))?
+        .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
         .into_object()
-        .map_err(|_| CanaryError::Registry("Registry not found".to_string()))?;
+        .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
+
+    let object_type = registry_obj
+        .type_
+        .ok_or_else(|| CanaryError::Registry("Registry object has no type".to_string()))?;
+
+    let canary_package_id = extract_package_id_from_type(&object_type.to_string())
+        .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
+
+    let admin_cap_ref =
+        validate_admin_cap_for(&client.client, registry_id, admin_cap_id, client.signer).await?;
+
+    let registry_arg = SharedObjectResolver::global()
+        .shared_arg(&client.client, registry_id, SharedObjectMutability::Mutable)
+        .await?;
 
     // Build the move_call arguments
-    // update_blob(registry: &Registry, admin_cap: &AdminCap, canary_blob: &mut CanaryBlob,
-    //              new_contract_blob_id: address, new_explain_blob_id: address, clock: &Clock, ctx: &TxContext)
+    // store_blob(registry: &mut Registry, admin_cap: &AdminCap, domain: String,
+    //            contract_blob_id: address, explain_blob_id: address, package_id: address,
+    //            clock: &Clock, ctx: &mut TxContext)
     let args = vec![
-        CallArg::Object(ObjectArg::SharedObject {
-            id: registry_id,
-            initial_shared_version: registry_obj.object_ref().1, // version from object_ref
-            mutability: SharedObjectMutability::Immutable,
-        }),
-        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_obj.object_ref())),
-        CallArg::Object(ObjectArg::SharedObject {
-            id: canary_blob_id,
-            initial_shared_version: canary_blob_ref.1, // version from object_ref
-            mutability: SharedObjectMutability::Mutable,
-        }),
-        CallArg::Pure(new_contract_blob_id.to_vec()),
-        CallArg::Pure(new_explain_blob_id.to_vec()),
-        CallArg::Object(ObjectArg::SharedObject {
-            id: clock_id,
-            initial_shared_version: SequenceNumber::from(1),
-            mutability: SharedObjectMutability::Immutable,
-        }),
+        registry_arg,
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_ref)),
+        pure_string(&domain),
+        pure_address(contract_blob_id),
+        pure_address(explain_blob_id),
+        pure_address(package_id),
+        SharedObjectResolver::clock_arg(),
     ];
 
     let mut builder = CanaryTransactionBuilder::new(client);
 
     builder
-        .move_call(canary_package_id, "pkg_storage", "update_blob", args)
+        .move_call(canary_package_id, "pkg_storage", "store_blob", args)
         .map_err(|e| CanaryError::Transaction(e))?;
 
     let response = builder
@@ -543,7 +1175,123 @@ pub async fn update_blob(
         .await
         .map_err(|e| CanaryError::Transaction(e))?;
 
-    Ok(response)
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Update a blob in the registry
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` containing the client, signer, and keystore
+/// * `registry_id` - The Registry object ID (required by Move function)
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `canary_blob_id` - The CanaryBlob object ID
+/// * `new_contract_blob_id` - The new contract blob object ID (as address)
+/// * `new_explain_blob_id` - The new explain blob object ID (as address)
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if the operation fails. Use
+/// `update_blob_preview` first to see what the update would change.
+///
+/// # Note
+///
+/// The Move function `update_blob` requires a `registry` parameter, so `registry_id` is needed.
+/// This is a reasonable extension to the plan's function signature.
+#[cfg(feature = "signing")]
+pub async fn update_blob(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    canary_blob_id: ObjectID,
+    new_contract_blob_id: ObjectID,
+    new_explain_blob_id: ObjectID,
+) -> Result<TransactionReceipt, CanaryError> {
+    let mut builder = update_blob_builder(
+        client,
+        registry_id,
+        admin_cap_id,
+        canary_blob_id,
+        new_contract_blob_id,
+        new_explain_blob_id,
+    )
+    .await?;
+
+    let response = builder
+        .execute()
+        .await
+        .map_err(|e| CanaryError::Transaction(e))?;
+
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Build the `update_blob` transaction without executing it
+#[cfg(feature = "signing")]
+pub(crate) async fn update_blob_builder(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    canary_blob_id: ObjectID,
+    new_contract_blob_id: ObjectID,
+    new_explain_blob_id: ObjectID,
+) -> Result<CanaryTransactionBuilder, CanaryError> {
+    // Get the canary blob object to extract package ID and registry info
+    let canary_blob_obj = client
+        .client
+        .read_api()
+        .get_object_with_options(canary_blob_id, SuiObjectDataOptions::full_content())
+        .await
+        .map_err(|e| CanaryError::CanaryBlobNotFound)?;
+
+    let canary_blob = canary_blob_obj
+        .into_object()
+        .map_err(|_| CanaryError::CanaryBlobNotFound)?;
+
+    let object_type = canary_blob
+        .type_
+        .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
+
+    let canary_package_id = extract_package_id_from_type(&object_type.to_string())
+        .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
+
+    let admin_cap_ref =
+        validate_admin_cap_for(&client.client, registry_id, admin_cap_id, client.signer).await?;
+
+    let resolver = SharedObjectResolver::global();
+    let registry_arg = resolver
+        .shared_arg(
+            &client.client,
+            registry_id,
+            SharedObjectMutability::Immutable,
+        )
+        .await?;
+    let canary_blob_arg = resolver
+        .shared_arg(
+            &client.client,
+            canary_blob_id,
+            SharedObjectMutability::Mutable,
+        )
+        .await?;
+
+    // Build the move_call arguments
+    // update_blob(registry: &Registry, admin_cap: &AdminCap, canary_blob: &mut CanaryBlob,
+    //              new_contract_blob_id: address, new_explain_blob_id: address, clock: &Clock, ctx: &TxContext)
+    let args = vec![
+        registry_arg,
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_ref)),
+        canary_blob_arg,
+        pure_address(new_contract_blob_id),
+        pure_address(new_explain_blob_id),
+        SharedObjectResolver::clock_arg(),
+    ];
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+
+    builder
+        .move_call(canary_package_id, "pkg_storage", "update_blob", args)
+        .map_err(|e| CanaryError::Transaction(e))?;
+
+    Ok(builder)
 }
 
 /// Delete a canary blob
@@ -557,13 +1305,14 @@ pub async fn update_blob(
 ///
 /// # Returns
 ///
-/// Returns the transaction response, or a `CanaryError` if the operation fails.
+/// Returns the transaction receipt, or a `CanaryError` if the operation fails.
+#[cfg(feature = "signing")]
 pub async fn delete_canary_blob(
     client: SuiClientWithSigner,
     registry_id: ObjectID,
     admin_cap_id: ObjectID,
     canary_blob_id: ObjectID,
-) -> Result<sui_sdk::rpc_types::SuiTransactionBlockResponse, CanaryError> {
+) -> Result<TransactionReceipt, CanaryError> {
     // Get the canary blob object to extract package ID
     let canary_blob_obj = client
         .client
@@ -574,9 +1323,6 @@ pub async fn delete_canary_blob(
         .into_object()
         .map_err(|_| CanaryError::CanaryBlobNotFound)?;
 
-    // Get the object reference before moving the type field
-    let canary_blob_obj_ref = canary_blob_obj.object_ref();
-
     let object_type = canary_blob_obj
         .type_
         .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
@@ -584,36 +1330,32 @@ pub async fn delete_canary_blob(
     let canary_package_id = extract_package_id_from_type(&object_type.to_string())
         .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
 
-    // Get registry object
-    let registry_obj = client
-        .client
-        .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Registry not found".to_string()))?;
+    let admin_cap_ref =
+        validate_admin_cap_for(&client.client, registry_id, admin_cap_id, client.signer).await?;
 
-    // Get admin cap object
-    let admin_cap_obj = client
-        .client
-        .read_api()
-        .get_object_with_options(admin_cap_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get admin cap: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Admin cap not found".to_string()))?;
+    let resolver = SharedObjectResolver::global();
+    let registry_arg = resolver
+        .shared_arg(
+            &client.client,
+            registry_id,
+            SharedObjectMutability::Immutable,
+        )
+        .await?;
+    // The blob is shared and taken by value, so it is passed as a mutable shared object
+    let canary_blob_arg = resolver
+        .shared_arg(
+            &client.client,
+            canary_blob_id,
+            SharedObjectMutability::Mutable,
+        )
+        .await?;
 
     // Build the move_call arguments
     // delete_canary_blob(registry: &Registry, admin_cap: &AdminCap, canary_blob: CanaryBlob)
     let args = vec![
-        CallArg::Object(ObjectArg::SharedObject {
-            id: registry_id,
-            initial_shared_version: registry_obj.object_ref().1, // version from object_ref
-            mutability: SharedObjectMutability::Immutable,
-        }),
-        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_obj.object_ref())),
-        CallArg::Object(ObjectArg::ImmOrOwnedObject(canary_blob_obj_ref)),
+        registry_arg,
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_ref)),
+        canary_blob_arg,
     ];
 
     let mut builder = CanaryTransactionBuilder::new(client);
@@ -627,7 +1369,7 @@ pub async fn delete_canary_blob(
         .await
         .map_err(|e| CanaryError::Transaction(e))?;
 
-    Ok(response)
+    Ok(TransactionReceipt::from_response(response))
 }
 
 /// Derive the canary address for a given domain and package
@@ -642,6 +1384,8 @@ pub async fn delete_canary_blob(
 /// # Returns
 ///
 /// Returns the derived `SuiAddress` for the canary blob, or a `CanaryError` if the operation fails.
+///
+/// `compute_canary_address` derives the same address without RPC access.
 pub async fn derive_canary_address(
     client: &SuiClient,
     registry_id: ObjectID,
@@ -664,60 +1408,76 @@ pub async fn derive_canary_address(
     let canary_package_id = extract_package_id_from_type(&object_type.to_string())
         .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
 
-    let initial_shared_version = get_initial_shared_version(client, registry_id)
-        .await
-        .map_err(|e| {
-            CanaryError::Registry(format!("Failed to get initial shared version: {}", e))
-        })?;
+    let registry_arg = SharedObjectResolver::global()
+        .shared_arg(client, registry_id, SharedObjectMutability::Immutable)
+        .await?;
 
-    // Use dev_inspect to call derive_canary_address
     // derive_canary_address(registry: &Registry, domain: String, package_id: address): address
-    let result = dev_inspect_call(
+    let address: SuiAddress = view_call(
         client,
         canary_package_id,
         "pkg_storage",
         "derive_canary_address",
+        vec![],
         vec![
-            CallArg::Object(ObjectArg::SharedObject {
-                id: registry_id,
-                initial_shared_version: initial_shared_version,
-                mutability: SharedObjectMutability::Immutable,
-            }),
-            CallArg::Pure(domain.as_bytes().to_vec()),
+            registry_arg,
+            CallArg::Pure(bcs::to_bytes(&domain).map_err(|e| {
+                CanaryError::Registry(format!("Failed to serialize domain: {}", e))
+            })?),
             CallArg::Pure(package_id.to_vec()),
         ],
     )
     .await?;
 
-    // Parse the result - it should be a single address
-    // The address is returned as bytes, we need to convert it
-    // SuiAddress is 32 bytes, so we can try to parse it directly
-    if result[0].len() != 32 {
-        return Err(CanaryError::Registry(format!(
-            "Invalid address length: expected 32, got {}",
-            result[0].len()
-        )));
-    }
+    Ok(address)
+}
 
-    // Convert bytes to SuiAddress
-    // SuiAddress and ObjectID are the same underlying type (32 bytes)
-    if result[0].len() != 32 {
-        return Err(CanaryError::Registry(format!(
-            "Invalid address length: expected 32, got {}",
-            result[0].len()
-        )));
+/// Derive the canary address for a domain and package locally, without RPC access
+///
+/// Mirrors `pkg_storage::derive_canary_address`: the `CanaryBlob` of a domain and
+/// package is a derived object of the registry, keyed by
+/// `CanaryKey { prefix: b"canary", domain, package_id }`, so its address is the
+/// dynamic field ID of `derived_object::DerivedObjectKey<CanaryKey>` under the registry.
+///
+/// # Arguments
+///
+/// * `registry_id` - The Registry object ID
+/// * `canary_package_id` - The original ID of the Canary package, which defines `CanaryKey`
+/// * `domain` - The domain name
+/// * `package_id` - The package ID (as address)
+///
+/// # Returns
+///
+/// Returns the derived `SuiAddress`, or a `CanaryError` if the key cannot be encoded.
+pub fn compute_canary_address(
+    registry_id: ObjectID,
+    canary_package_id: ObjectID,
+    domain: &str,
+    package_id: ObjectID,
+) -> Result<SuiAddress, CanaryError> {
+    // BCS layout of `CanaryKey`; `DerivedObjectKey<K>` wraps it without adding bytes
+    #[derive(Serialize)]
+    struct CanaryKey<'a> {
+        prefix: &'a [u8],
+        domain: &'a str,
+        package_id: [u8; 32],
     }
 
-    let address_array: [u8; 32] = result[0].as_slice().try_into().map_err(|e| {
-        CanaryError::Registry(format!("Failed to convert to address array: {:?}", e))
-    })?;
-
-    // Create ObjectID from bytes, then convert to SuiAddress
-    let object_id = ObjectID::from_bytes(address_array)
-        .map_err(|e| CanaryError::Registry(format!("Failed to create ObjectID: {}", e)))?;
-    let address = SuiAddress::from(object_id);
+    let key = bcs::to_bytes(&CanaryKey {
+        prefix: b"canary",
+        domain,
+        package_id: package_id.into_bytes(),
+    })
+    .map_err(|e| CanaryError::Registry(format!("Failed to serialize canary key: {}", e)))?;
+    let key_type = sui_sdk::types::parse_sui_type_tag(&format!(
+        "0x2::derived_object::DerivedObjectKey<{}::pkg_storage::CanaryKey>",
+        canary_package_id
+    ))
+    .map_err(|e| CanaryError::Registry(format!("Invalid canary key type: {}", e)))?;
 
-    Ok(address)
+    sui_sdk::types::dynamic_field::derive_dynamic_field_id(registry_id, &key_type, &key)
+        .map(SuiAddress::from)
+        .map_err(|e| CanaryError::Registry(format!("Failed to derive canary address: {}", e)))
 }
 
 /// Query canary blob information
@@ -742,6 +1502,7 @@ pub async fn query_canary_blob(
         .map_err(|_| CanaryError::CanaryBlobNotFound)?
         .into_object()
         .map_err(|_| CanaryError::CanaryBlobNotFound)?;
+    let raw = raw_object(&canary_blob_obj);
 
     let object_type = canary_blob_obj
         .type_
@@ -749,62 +1510,28 @@ pub async fn query_canary_blob(
 
     let canary_package_id = extract_package_id_from_type(&object_type.to_string())
         .ok_or_else(|| CanaryError::CanaryBlobNotFound)?;
-    let initial_shared_version = get_initial_shared_version(client, canary_blob_id)
-        .await
-        .map_err(|e| {
-            CanaryError::Registry(format!("Failed to get initial shared version: {}", e))
-        })?;
+    let canary_blob_arg = SharedObjectResolver::global()
+        .shared_arg(client, canary_blob_id, SharedObjectMutability::Immutable)
+        .await?;
 
-    // Use dev_inspect to call get_full_info
     // get_full_info(canary_blob: &CanaryBlob): (address, address, address, String, u64, address)
-    let result = dev_inspect_call(
+    let (contract_blob_id, explain_blob_id, package_id, domain, uploaded_at, uploaded_by_admin): (
+        ObjectID,
+        ObjectID,
+        ObjectID,
+        String,
+        u64,
+        SuiAddress,
+    ) = view_call(
         client,
         canary_package_id,
         "pkg_storage",
         "get_full_info",
-        vec![CallArg::Object(ObjectArg::SharedObject {
-            id: canary_blob_id,
-            initial_shared_version: initial_shared_version,
-            mutability: SharedObjectMutability::Immutable,
-        })],
+        vec![],
+        vec![canary_blob_arg],
     )
     .await?;
 
-    // Parse the result tuple: (address, address, address, String, u64, address)
-    // Result is a vector of return values
-    if result.len() != 6 {
-        return Err(CanaryError::CanaryBlobNotFound);
-    }
-
-    // Addresses are 32 bytes
-    fn parse_address(bytes: &[u8]) -> Result<ObjectID, CanaryError> {
-        if bytes.len() != 32 {
-            return Err(CanaryError::Registry(format!(
-                "Invalid address length: expected 32, got {}",
-                bytes.len()
-            )));
-        }
-        let address_array: [u8; 32] = bytes.try_into().map_err(|e| {
-            CanaryError::Registry(format!("Failed to convert to address array: {:?}", e))
-        })?;
-        // Create ObjectID directly from bytes
-        ObjectID::from_bytes(address_array)
-            .map_err(|e| CanaryError::Registry(format!("Failed to create ObjectID: {}", e)))
-    }
-
-    let contract_blob_id = parse_address(&result[0])?;
-    let explain_blob_id = parse_address(&result[1])?;
-    let package_id = parse_address(&result[2])?;
-
-    let domain: String = bcs::from_bytes(&result[3])
-        .map_err(|e| CanaryError::Registry(format!("Failed to deserialize domain: {}", e)))?;
-
-    let uploaded_at: u64 = bcs::from_bytes(&result[4])
-        .map_err(|e| CanaryError::Registry(format!("Failed to deserialize uploaded_at: {}", e)))?;
-
-    let uploaded_by_admin = parse_address(&result[5])?;
-    let uploaded_by_admin_addr = SuiAddress::from(uploaded_by_admin);
-
     Ok(CanaryBlobInfo {
         id: canary_blob_id,
         contract_blob_id,
@@ -812,34 +1539,248 @@ pub async fn query_canary_blob(
         package_id,
         domain,
         uploaded_at,
-        uploaded_by_admin: uploaded_by_admin_addr,
+        uploaded_by_admin,
+        raw,
     })
 }
 
+/// Query the canary blob stored for a domain and package, if any
+///
+/// Derives the blob's address with `derive_canary_address` and reads it with
+/// `query_canary_blob`, so callers don't have to chain the two themselves.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `domain` - The domain name
+/// * `package_id` - The package ID (as address)
+///
+/// # Returns
+///
+/// Returns `Some(CanaryBlobInfo)` if a blob is stored for the domain, `None` if not, or
+/// a `CanaryError` if the query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_blob_by_domain;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let package_id = ObjectID::from_hex_literal("0x456...")?;
+/// match query_blob_by_domain(&client, registry_id, "example.com", package_id).await? {
+///     Some(blob) => println!("Canary uploaded at {}", blob.uploaded_at),
+///     None => println!("No canary for example.com"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn query_blob_by_domain(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    domain: &str,
+    package_id: ObjectID,
+) -> Result<Option<CanaryBlobInfo>, CanaryError> {
+    let address =
+        derive_canary_address(client, registry_id, domain.to_string(), package_id).await?;
+    match query_canary_blob(client, ObjectID::from(address)).await {
+        Ok(blob) => Ok(Some(blob)),
+        Err(CanaryError::CanaryBlobNotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Query the canary blob serving a host, falling back to registered parent domains
+///
+/// Organizations typically register `example.com` but serve canaries on subdomains.
+/// The candidate domains for `host` (see `domain::candidate_domains`) are tried from
+/// the most specific to the least, and the first one with a canary blob wins.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `host` - The host name (e.g. `status.example.com`)
+/// * `package_id` - The package ID (as address)
+/// * `policy` - Which parent domains may be used in place of the host
+///
+/// # Returns
+///
+/// Returns the `CanaryBlobInfo` of the most specific matching domain,
+/// `CanaryError::CanaryBlobNotFound` if no candidate has a blob, or another `CanaryError`
+/// if the host is malformed or a query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_blob_for_host;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::domain::SubdomainPolicy;
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let package_id = ObjectID::from_hex_literal("0x456...")?;
+/// let blob = query_blob_for_host(
+///     &client,
+///     registry_id,
+///     "status.example.com",
+///     package_id,
+///     SubdomainPolicy::AnyParent,
+/// )
+/// .await?;
+/// println!("Canary registered for {}", blob.domain);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn query_blob_for_host(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    host: &str,
+    package_id: ObjectID,
+    policy: SubdomainPolicy,
+) -> Result<CanaryBlobInfo, CanaryError> {
+    for domain in candidate_domains(host, policy)? {
+        if let Some(blob) = query_blob_by_domain(client, registry_id, &domain, package_id).await? {
+            return Ok(blob);
+        }
+    }
+    Err(CanaryError::CanaryBlobNotFound)
+}
+
+/// List a page of the canary blobs stored under a registry
+///
+/// Every `store_blob` claims a derived object on the registry, which leaves a dynamic
+/// field keyed by the blob's `CanaryKey` (domain and package). This walks the
+/// registry's dynamic fields, computes each blob's address from its key with
+/// `compute_canary_address`, and reads the blob. Blobs deleted since their key was claimed are skipped, so a page may hold
+/// fewer blobs than `limit`.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `cursor` - The cursor returned by the previous page, or `None` to start
+/// * `limit` - Maximum number of dynamic fields to read for this page
+///
+/// # Returns
+///
+/// Returns the page's blobs and the cursor of the next page (`None` on the last page),
+/// or a `CanaryError` if the query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_canary_blobs;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let mut cursor = None;
+/// loop {
+///     let (blobs, next) = query_canary_blobs(&client, registry_id, cursor, Some(50)).await?;
+///     for blob in blobs {
+///         println!("{} -> {}", blob.domain, blob.id);
+///     }
+///     match next {
+///         Some(next) => cursor = Some(next),
+///         None => break,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn query_canary_blobs(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    cursor: Option<ObjectID>,
+    limit: Option<usize>,
+) -> Result<(Vec<CanaryBlobInfo>, Option<ObjectID>), CanaryError> {
+    RateLimiter::global().acquire().await;
+    let page = client
+        .read_api()
+        .get_dynamic_fields(registry_id, cursor, limit)
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to list canary blobs: {}", e)))?;
+
+    let mut blobs = Vec::new();
+    for field in &page.data {
+        let Some(canary_package_id) = canary_key_package(&field.name.type_.to_string()) else {
+            continue;
+        };
+        let Some((domain, package_id)) = canary_key_from_name(&field.name.value) else {
+            continue;
+        };
+        let address = compute_canary_address(registry_id, canary_package_id, &domain, package_id)?;
+        RateLimiter::global().acquire().await;
+        match query_canary_blob(client, ObjectID::from(address)).await {
+            Ok(blob) => blobs.push(blob),
+            Err(CanaryError::CanaryBlobNotFound) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    let next_cursor = if page.has_next_page {
+        page.next_cursor
+    } else {
+        None
+    };
+
+    Ok((blobs, next_cursor))
+}
+
+/// List all canary blobs of a registry, following `query_canary_blobs` pages to the end
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+///
+/// # Returns
+///
+/// Returns all canary blobs, or a `CanaryError` if any page fails.
+pub async fn query_all_canary_blobs(
+    client: &SuiClient,
+    registry_id: ObjectID,
+) -> Result<Vec<CanaryBlobInfo>, CanaryError> {
+    let mut blobs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next) = query_canary_blobs(client, registry_id, cursor, None).await?;
+        blobs.extend(page);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return Ok(blobs),
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Get the initial shared version of a shared object
+///
+/// Resolved through `SharedObjectResolver::global`, so repeated calls for the same
+/// object do not hit the network.
 pub async fn get_initial_shared_version(
     client: &SuiClient,
     object_id: ObjectID,
 ) -> Result<SequenceNumber, anyhow::Error> {
-    let response = client
-        .read_api()
-        .get_object_with_options(object_id, SuiObjectDataOptions::bcs_lossless())
-        .await?;
-    let registry_initial_shared_version = match response.data.unwrap().owner.unwrap() {
-        sui_types::object::Owner::Shared {
-            initial_shared_version,
-        } => initial_shared_version,
-        _ => panic!(""),
-    };
-    Ok(registry_initial_shared_version)
+    Ok(SharedObjectResolver::global()
+        .initial_shared_version(client, object_id)
+        .await?)
 }
 
 /// Extract package ID from a Move type string
 /// Example: "0x123::member_registry::Registry" -> ObjectID(0x123)
-fn extract_package_id_from_type(type_str: &str) -> Option<ObjectID> {
+pub(crate) fn extract_package_id_from_type(type_str: &str) -> Option<ObjectID> {
     // Type format: "0x<PACKAGE_ID>::<MODULE>::<STRUCT>"
     if let Some(colon_pos) = type_str.find("::") {
         let package_str = &type_str[..colon_pos];
@@ -849,150 +1790,196 @@ fn extract_package_id_from_type(type_str: &str) -> Option<ObjectID> {
     }
 }
 
-/// Call a view function using dev_inspect_transaction_block
-async fn dev_inspect_call(
-    client: &SuiClient,
-    package_id: ObjectID,
-    module: &str,
-    function: &str,
-    args: Vec<CallArg>,
-) -> Result<Vec<Vec<u8>>, CanaryError> {
-    use std::str::FromStr;
-    use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-    use sui_sdk::types::transaction::TransactionData;
-    use sui_types::Identifier;
-
-    let module_id = Identifier::from_str(module)
-        .map_err(|e| CanaryError::Registry(format!("Invalid module name: {}", e)))?;
-    let function_id = Identifier::from_str(function)
-        .map_err(|e| CanaryError::Registry(format!("Invalid function name: {}", e)))?;
-
-    let mut builder = ProgrammableTransactionBuilder::new();
-    builder
-        .move_call(package_id, module_id, function_id, vec![], args)
-        .map_err(|e| CanaryError::Registry(format!("Failed to build move call: {}", e)))?;
+/// Capture the reference and BCS bytes of a fetched object (requires `show_bcs`)
+pub(crate) fn raw_object(data: &sui_sdk::rpc_types::SuiObjectData) -> Option<RawObject> {
+    use sui_sdk::rpc_types::SuiRawData;
 
-    let pt = builder.finish();
+    match &data.bcs {
+        Some(SuiRawData::MoveObject(object)) => Some(RawObject {
+            object_ref: data.object_ref(),
+            bcs: object.bcs_bytes.clone(),
+        }),
+        _ => None,
+    }
+}
 
-    // Create a dummy transaction for dev_inspect
-    // We need a sender address - use a dummy address
-    let dummy_sender = SuiAddress::from_str("0x1")
-        .map_err(|e| CanaryError::Registry(format!("Failed to create dummy sender: {}", e)))?;
-    let gas_price = client
-        .read_api()
-        .get_reference_gas_price()
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get gas price: {}", e)))?;
-
-    let transaction_data = TransactionData::new_programmable(
-        dummy_sender,
-        vec![], // No gas objects needed for dev_inspect
-        pt,
-        gas_price,
-        10_000_000, // Dummy budget
-    );
+/// Fetch the raw `members` table entry (`Field<address, MemberInfo>`) of a member
+async fn get_member_field_raw(
+    client: &SuiClient,
+    members_table: ObjectID,
+    member_address: SuiAddress,
+) -> Result<Option<RawObject>, CanaryError> {
+    use sui_sdk::types::dynamic_field::DynamicFieldName;
 
-    // Call dev_inspect
-    // dev_inspect_transaction_block requires: sender, transaction_data, gas_price, gas_objects, epoch
-    let result = client
+    let name = DynamicFieldName {
+        type_: sui_types::TypeTag::Address,
+        value: serde_json::Value::String(member_address.to_string()),
+    };
+    let response = client
         .read_api()
-        .dev_inspect_transaction_block(
-            dummy_sender,
-            transaction_data,
-            Some(move_core_types::big_int::BigInt::from(gas_price)),
-            None, // gas_objects - None means use dummy
-            None, // epoch - None means use current
-        )
+        .get_dynamic_field_object(members_table, name)
         .await
-        .map_err(|e| CanaryError::Registry(format!("dev_inspect failed: {}", e)))?;
+        .map_err(|e| CanaryError::Registry(format!("Failed to get member entry: {}", e)))?;
 
-    // Extract return values from the effects
-    // The return values are in the effects
-    let effects = result.effects;
-    let return_values = effects.return_values;
+    Ok(response.data.as_ref().and_then(raw_object))
+}
 
-    Ok(return_values)
+/// Extract the Move fields of an object as JSON from its parsed content
+pub(crate) fn object_fields(
+    data: sui_sdk::rpc_types::SuiObjectData,
+) -> Result<serde_json::Value, CanaryError> {
+    use sui_sdk::rpc_types::SuiParsedData;
+
+    match data.content {
+        Some(SuiParsedData::MoveObject(object)) => Ok(object.fields.to_json_value()),
+        Some(_) => Err(CanaryError::Registry(format!(
+            "Object {} is not a Move object",
+            data.object_id
+        ))),
+        None => Err(CanaryError::Registry(format!(
+            "Object {} has no content",
+            data.object_id
+        ))),
+    }
 }
 
-/// Query registry admin using dev_inspect
-async fn query_registry_admin(
+/// Fetch an object and return its Move fields as JSON
+pub(crate) async fn get_object_fields(
     client: &SuiClient,
-    package_id: ObjectID,
-    registry_id: ObjectID,
-) -> Result<SuiAddress, CanaryError> {
-    // Get registry object for initial_shared_version
-    let registry_obj = client
+    object_id: ObjectID,
+) -> Result<serde_json::Value, CanaryError> {
+    let data = client
         .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
+        .get_object_with_options(object_id, SuiObjectDataOptions::new().with_content())
         .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry: {}", e)))?
+        .map_err(|e| CanaryError::Registry(format!("Failed to get object {}: {}", object_id, e)))?
         .into_object()
-        .map_err(|_| CanaryError::Registry("Registry not found".to_string()))?;
+        .map_err(|_| CanaryError::Registry(format!("Object {} not found", object_id)))?;
 
-    let result = dev_inspect_call(
-        client,
-        package_id,
-        "member_registry",
-        "get_admin",
-        vec![CallArg::Object(ObjectArg::SharedObject {
-            id: registry_id,
-            initial_shared_version: registry_obj.object_ref().1, // version from object_ref
-            mutability: SharedObjectMutability::Immutable,
-        })],
-    )
-    .await?;
+    object_fields(data)
+}
 
-    if result.is_empty() {
-        return Err(CanaryError::Registry(
-            "get_admin returned no value".to_string(),
-        ));
+/// Read a `u64` field, which the RPC renders as a decimal string
+pub(crate) fn json_u64(fields: &serde_json::Value, name: &str) -> Result<u64, CanaryError> {
+    match &fields[name] {
+        serde_json::Value::String(s) => s
+            .parse()
+            .map_err(|e| CanaryError::Registry(format!("Invalid u64 field {}: {}", name, e))),
+        serde_json::Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| CanaryError::Registry(format!("Invalid u64 field {}", name))),
+        _ => Err(CanaryError::Registry(format!("Missing field {}", name))),
     }
+}
 
-    // Address is 32 bytes
-    if result[0].len() != 32 {
-        return Err(CanaryError::Registry(format!(
-            "Invalid admin address length: expected 32, got {}",
-            result[0].len()
-        )));
-    }
+/// Read a string field
+pub(crate) fn json_string(fields: &serde_json::Value, name: &str) -> Result<String, CanaryError> {
+    fields[name]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| CanaryError::Registry(format!("Missing field {}", name)))
+}
 
-    let admin_array: [u8; 32] = result[0].as_slice().try_into().map_err(|e| {
-        CanaryError::Registry(format!("Failed to convert to address array: {:?}", e))
-    })?;
+/// Read an `address` field
+pub(crate) fn json_address(
+    fields: &serde_json::Value,
+    name: &str,
+) -> Result<SuiAddress, CanaryError> {
+    use std::str::FromStr;
 
-    // Create ObjectID from bytes, then convert to SuiAddress
-    let admin_object_id = ObjectID::from_bytes(admin_array)
-        .map_err(|e| CanaryError::Registry(format!("Failed to create ObjectID: {}", e)))?;
-    Ok(SuiAddress::from(admin_object_id))
+    let s = json_string(fields, name)?;
+    SuiAddress::from_str(&s)
+        .map_err(|e| CanaryError::Registry(format!("Invalid address field {}: {}", name, e)))
 }
 
-/// Query registry fields (member_count and fee) using dev_inspect
-///
-/// Note: This requires adding view functions in Move (get_member_count, get_fee)
-/// or parsing the object's BCS data. For now, we'll use a workaround by trying
-/// to parse from the object's content if available.
-async fn query_registry_fields(
+/// Read the object ID backing a `Table` field (`{ "id": { "id": "0x..." }, "size": "N" }`)
+pub(crate) fn json_table_id(
+    fields: &serde_json::Value,
+    name: &str,
+) -> Result<ObjectID, CanaryError> {
+    let id = fields[name]["id"]["id"]
+        .as_str()
+        .ok_or_else(|| CanaryError::Registry(format!("Missing table field {}", name)))?;
+    ObjectID::from_hex_literal(id)
+        .map_err(|e| CanaryError::Registry(format!("Invalid table id for {}: {}", name, e)))
+}
+
+/// Fetch `members` table entries by their dynamic field object IDs
+pub(crate) async fn get_member_fields(
     client: &SuiClient,
-    package_id: ObjectID,
-    registry_id: ObjectID,
-) -> Result<(u64, u64), CanaryError> {
-    // Since the Move contract doesn't have view functions for member_count and fee,
-    // we need to either:
-    // 1. Add view functions in Move (recommended)
-    // 2. Parse the object's BCS data (complex, requires type definitions)
-    //
-    // For now, we'll return default values and note this limitation.
-    // In production, you should add these view functions to the Move contract:
-    // public fun get_member_count(registry: &Registry): u64 { registry.member_count }
-    // public fun get_fee(registry: &Registry): u64 { registry.fee }
+    field_ids: Vec<ObjectID>,
+) -> Result<Vec<MemberInfoWithAddress>, CanaryError> {
+    if field_ids.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    // Try to use dev_inspect if view functions exist, otherwise return error
-    // For now, return an error indicating this needs Move contract updates
-    Err(CanaryError::Registry(
-        "query_registry_fields requires Move view functions get_member_count() and get_fee(). \
-         Please add these functions to the member_registry module or parse object BCS data."
-            .to_string(),
-    ))
+    RateLimiter::global().acquire().await;
+    let objects = client
+        .read_api()
+        .multi_get_object_with_options(
+            field_ids,
+            SuiObjectDataOptions::new().with_content().with_bcs(),
+        )
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to fetch members: {}", e)))?;
+
+    objects
+        .into_iter()
+        .map(|response| {
+            let data = response
+                .into_object()
+                .map_err(|e| CanaryError::Registry(format!("Member entry not found: {}", e)))?;
+            let raw = raw_object(&data);
+            let mut member = parse_member_field(&object_fields(data)?)?;
+            member.raw = raw;
+            Ok(member)
+        })
+        .collect()
+}
+
+/// Parse a `members` table entry (`Field<address, MemberInfo>`)
+pub(crate) fn parse_member_field(
+    fields: &serde_json::Value,
+) -> Result<MemberInfoWithAddress, CanaryError> {
+    let member = json_address(fields, "name")?;
+    let value = &fields["value"];
+
+    Ok(MemberInfoWithAddress {
+        member,
+        domain: json_string(value, "domain")?,
+        joined_at: json_u64(value, "joined_at")?,
+        raw: None,
+    })
+}
+
+/// The package defining `CanaryKey` in a dynamic field name type, which may wrap it
+/// (`0x2::derived_object::DerivedObjectKey<0x<PACKAGE_ID>::pkg_storage::CanaryKey>`)
+fn canary_key_package(type_str: &str) -> Option<ObjectID> {
+    let end = type_str.find("::pkg_storage::CanaryKey")?;
+    let start = type_str[..end]
+        .rfind(|c: char| c == '<' || c == ',' || c == ' ')
+        .map_or(0, |i| i + 1);
+    ObjectID::from_hex_literal(&type_str[start..end]).ok()
+}
+
+/// Extract the domain and package of a `CanaryKey` from a dynamic field name
+///
+/// The name of a derived-object claim wraps the key (`DerivedObjectKey<CanaryKey>`),
+/// so the key's fields are searched for rather than read at a fixed path.
+fn canary_key_from_name(name: &serde_json::Value) -> Option<(String, ObjectID)> {
+    match name {
+        serde_json::Value::Object(map) => {
+            if let (Some(domain), Some(package_id)) = (
+                map.get("domain").and_then(|v| v.as_str()),
+                map.get("package_id").and_then(|v| v.as_str()),
+            ) {
+                let package_id = ObjectID::from_hex_literal(package_id).ok()?;
+                return Some((domain.to_string(), package_id));
+            }
+            map.values().find_map(canary_key_from_name)
+        }
+        _ => None,
+    }
 }
 
 /// Query if an address is a member
@@ -1002,27 +1989,18 @@ async fn query_is_member(
     registry_id: ObjectID,
     member_address: SuiAddress,
 ) -> Result<bool, CanaryError> {
-    let registry_obj = client
-        .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry: {}", e)))??
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Registry not found".to_string()))?;
+    let registry_arg = SharedObjectResolver::global()
+        .shared_arg(client, registry_id, SharedObjectMutability::Immutable)
+        .await?;
 
-    let result = dev_inspect_call(
+    let is_member: bool = view_call(
         client,
         package_id,
         "member_registry",
         "is_member",
+        vec![],
         vec![
-            CallArg::Object(ObjectArg::SharedObject {
-                id: registry_id,
-                initial_shared_version: registry_obj.previous_transaction.ok_or_else(|| {
-                    CanaryError::Registry("Registry has no previous transaction".to_string())
-                })?,
-                mutability: SharedObjectMutability::Immutable,
-            }),
+            registry_arg,
             CallArg::Pure(bcs::to_bytes(&member_address).map_err(|e| {
                 CanaryError::Registry(format!("Failed to serialize member_address: {}", e))
             })?),
@@ -1030,62 +2008,30 @@ async fn query_is_member(
     )
     .await?;
 
-    if result.is_empty() {
-        return Err(CanaryError::Registry(
-            "is_member returned no value".to_string(),
-        ));
-    }
-
-    let is_member: bool = bcs::from_bytes(&result[0])
-        .map_err(|e| CanaryError::Registry(format!("Failed to deserialize is_member: {}", e)))?;
-
     Ok(is_member)
 }
 
-/// Query member info using dev_inspect
+/// Query member info with the `get_member_info` view function
 async fn query_member_info(
     client: &SuiClient,
     package_id: ObjectID,
     registry_id: ObjectID,
     member_address: SuiAddress,
 ) -> Result<MemberInfo, CanaryError> {
-    let registry_obj = client
-        .read_api()
-        .get_object_with_options(registry_id, SuiObjectDataOptions::full_content())
-        .await
-        .map_err(|e| CanaryError::Registry(format!("Failed to get registry: {}", e)))?
-        .into_object()
-        .map_err(|_| CanaryError::Registry("Registry not found".to_string()))?;
-
-    // get_member_info returns &MemberInfo, but we can't return references from view functions
-    // Actually, looking at the Move code, get_member_info returns &MemberInfo
-    // But in Sui, view functions that return references need special handling
-    // Let's try calling it and see what happens
-
-    // Actually, we can't return references from view functions in Sui
-    // We need to return by value. Let's check if there's a function that returns MemberInfo by value
-    // Looking at the Move code, get_member_info returns &MemberInfo, which won't work for view functions
-
-    // We'll need to either:
-    // 1. Add a function in Move that returns MemberInfo by value
-    // 2. Parse the object's internal data
-    // 3. Use a different approach
+    let registry_arg = SharedObjectResolver::global()
+        .shared_arg(client, registry_id, SharedObjectMutability::Immutable)
+        .await?;
 
-    // For now, let's try calling it and see if it works
-    // If not, we'll need to add a helper function in Move
-    let result = dev_inspect_call(
+    // get_member_info(registry: &Registry, member: address): &MemberInfo; the struct
+    // encodes as its fields, domain: String and joined_at: u64
+    let (domain, joined_at): (String, u64) = view_call(
         client,
         package_id,
         "member_registry",
         "get_member_info",
+        vec![],
         vec![
-            CallArg::Object(ObjectArg::SharedObject {
-                id: registry_id,
-                initial_shared_version: registry_obj.previous_transaction.ok_or_else(|| {
-                    CanaryError::Registry("Registry has no previous transaction".to_string())
-                })?,
-                mutability: SharedObjectMutability::Immutable,
-            }),
+            registry_arg,
             CallArg::Pure(bcs::to_bytes(&member_address).map_err(|e| {
                 CanaryError::Registry(format!("Failed to serialize member_address: {}", e))
             })?),
@@ -1093,20 +2039,11 @@ async fn query_member_info(
     )
     .await?;
 
-    // Parse the result - MemberInfo has domain: String and joined_at: u64
-    if result.len() != 2 {
-        return Err(CanaryError::Registry(
-            "get_member_info returned unexpected number of values".to_string(),
-        ));
-    }
-
-    let domain: String = bcs::from_bytes(&result[0])
-        .map_err(|e| CanaryError::Registry(format!("Failed to deserialize domain: {}", e)))?;
-
-    let joined_at: u64 = bcs::from_bytes(&result[1])
-        .map_err(|e| CanaryError::Registry(format!("Failed to deserialize joined_at: {}", e)))?;
-
-    Ok(MemberInfo { domain, joined_at })
+    Ok(MemberInfo {
+        domain,
+        joined_at,
+        raw: None,
+    })
 }
 
 /// Get registry_id from admin_cap using dev_inspect or parsing
@@ -1130,3 +2067,45 @@ async fn get_registry_id_from_admin_cap(
             .to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canary_key_package() {
+        let package = ObjectID::from_single_byte(0xab);
+        assert_eq!(
+            canary_key_package(&format!("{}::pkg_storage::CanaryKey", package)),
+            Some(package)
+        );
+        assert_eq!(
+            canary_key_package(&format!(
+                "0x2::derived_object::DerivedObjectKey<{}::pkg_storage::CanaryKey>",
+                package
+            )),
+            Some(package)
+        );
+        assert_eq!(canary_key_package("0x2::object::ID"), None);
+    }
+
+    #[test]
+    fn test_canary_key_from_name() {
+        let package = ObjectID::from_single_byte(0xab);
+        let name = serde_json::json!({
+            "pos0": {
+                "prefix": [99, 97, 110, 97, 114, 121],
+                "domain": "example.com",
+                "package_id": package.to_string()
+            }
+        });
+        assert_eq!(
+            canary_key_from_name(&name),
+            Some(("example.com".to_string(), package))
+        );
+        assert_eq!(
+            canary_key_from_name(&serde_json::json!({"domain": 1})),
+            None
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Transaction history of a registry
+//!
+//! Every transaction that changed the registry object, with the canary call it made
+//! and who sent it, for auditing who changed what. Pages are returned oldest first;
+//! pass the returned cursor back to continue where the previous page ended.
+
+use super::{LEAVE_REGISTRY_FUNCTION, WITHDRAW_TO_FUNCTION};
+use crate::client::ratelimit::RateLimiter;
+use crate::error::CanaryError;
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::{
+    SuiArgument, SuiCallArg, SuiCommand, SuiExecutionStatus, SuiTransactionBlock,
+    SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI, SuiTransactionBlockKind,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// The canary operation a registry transaction performed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryCallKind {
+    /// `member_registry::join_registry`
+    Join,
+    /// `member_registry::leave_registry`
+    Leave,
+    /// `member_registry::withdraw` or `withdraw_to`
+    Withdraw,
+    /// `member_registry::update_fee`
+    SetFee,
+    /// `member_registry::remove_member`
+    RemoveMember,
+    /// `member_registry::transfer_admin`
+    TransferAdmin,
+    /// `pkg_storage::store_blob`
+    StoreBlob,
+    /// `pkg_storage::update_blob`
+    UpdateBlob,
+    /// `pkg_storage::delete_canary_blob`
+    DeleteBlob,
+    /// Any other call (e.g. a function added by a later package version)
+    Other,
+}
+
+impl RegistryCallKind {
+    /// Classify a Move call
+    pub fn from_call(module: &str, function: &str) -> Self {
+        match (module, function) {
+            ("member_registry", "join_registry") => RegistryCallKind::Join,
+            ("member_registry", f) if f == LEAVE_REGISTRY_FUNCTION => RegistryCallKind::Leave,
+            ("member_registry", "withdraw") => RegistryCallKind::Withdraw,
+            ("member_registry", f) if f == WITHDRAW_TO_FUNCTION => RegistryCallKind::Withdraw,
+            ("member_registry", "update_fee") => RegistryCallKind::SetFee,
+            ("member_registry", "remove_member") => RegistryCallKind::RemoveMember,
+            ("member_registry", "transfer_admin") => RegistryCallKind::TransferAdmin,
+            ("pkg_storage", "store_blob") => RegistryCallKind::StoreBlob,
+            ("pkg_storage", "update_blob") => RegistryCallKind::UpdateBlob,
+            ("pkg_storage", "delete_canary_blob") => RegistryCallKind::DeleteBlob,
+            _ => RegistryCallKind::Other,
+        }
+    }
+}
+
+/// A transaction that changed the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryTransaction {
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// Checkpoint the transaction was included in, if checkpointed
+    pub checkpoint: Option<u64>,
+    /// Checkpoint timestamp of the transaction (in milliseconds), if checkpointed
+    pub timestamp_ms: Option<u64>,
+    /// The transaction sender
+    pub sender: SuiAddress,
+    /// The canary operation performed
+    pub kind: RegistryCallKind,
+    /// The member whose membership the call changed: the sender for joins and leaves,
+    /// the evicted address for `remove_member`
+    pub member: Option<SuiAddress>,
+    /// The canary Move call as `module::function`, if the transaction made one
+    pub function: Option<String>,
+    /// Whether the transaction succeeded
+    pub success: bool,
+}
+
+/// One page of a registry's transaction history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryTransactionPage {
+    /// Transactions, oldest first
+    pub transactions: Vec<RegistryTransaction>,
+    /// Cursor for the next page
+    pub next_cursor: Option<TransactionDigest>,
+    /// Whether more transactions follow
+    pub has_next_page: bool,
+}
+
+/// Query the transactions that changed a registry
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `cursor` - The `next_cursor` of the previous page, or `None` to start from the
+///   registry's creation
+/// * `limit` - Maximum number of transactions in the page (`None` for the node's default)
+///
+/// # Returns
+///
+/// Returns a `RegistryTransactionPage`, or a `CanaryError` if the query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_registry_transactions;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let mut cursor = None;
+/// loop {
+///     let page = query_registry_transactions(&client, registry_id, cursor, Some(50)).await?;
+///     for tx in &page.transactions {
+///         println!("{} {:?} by {}", tx.digest, tx.kind, tx.sender);
+///     }
+///     if !page.has_next_page {
+///         break;
+///     }
+///     cursor = page.next_cursor;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn query_registry_transactions(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    cursor: Option<TransactionDigest>,
+    limit: Option<usize>,
+) -> Result<RegistryTransactionPage, CanaryError> {
+    let query = SuiTransactionBlockResponseQuery::new(
+        Some(TransactionFilter::ChangedObject(registry_id)),
+        Some(
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects(),
+        ),
+    );
+
+    RateLimiter::global().acquire().await;
+    let page = client
+        .read_api()
+        .query_transaction_blocks(query, cursor, limit, false)
+        .await
+        .map_err(|e| {
+            CanaryError::Registry(format!("Failed to query registry transactions: {}", e))
+        })?;
+
+    let transactions = page
+        .data
+        .iter()
+        .map(registry_transaction)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RegistryTransactionPage {
+        transactions,
+        next_cursor: page.next_cursor,
+        has_next_page: page.has_next_page,
+    })
+}
+
+/// The digest of the latest transaction that changed a registry
+///
+/// Pass it as the `cursor` of `query_registry_transactions` to follow only changes
+/// made from now on.
+pub async fn latest_registry_transaction(
+    client: &SuiClient,
+    registry_id: ObjectID,
+) -> Result<Option<TransactionDigest>, CanaryError> {
+    let query = SuiTransactionBlockResponseQuery::new(
+        Some(TransactionFilter::ChangedObject(registry_id)),
+        None,
+    );
+    let page = client
+        .read_api()
+        .query_transaction_blocks(query, None, Some(1), true)
+        .await
+        .map_err(|e| {
+            CanaryError::Registry(format!("Failed to query registry transactions: {}", e))
+        })?;
+    Ok(page.data.first().map(|response| response.digest))
+}
+
+/// Build the history entry for one transaction
+fn registry_transaction(
+    response: &SuiTransactionBlockResponse,
+) -> Result<RegistryTransaction, CanaryError> {
+    let transaction = response.transaction.as_ref().ok_or_else(|| {
+        CanaryError::Registry(format!("Transaction {} has no input", response.digest))
+    })?;
+    let effects = response.effects.as_ref().ok_or_else(|| {
+        CanaryError::Registry(format!("Transaction {} has no effects", response.digest))
+    })?;
+
+    let call = canary_call(transaction);
+    let sender = *transaction.data.sender();
+    let kind = call
+        .as_ref()
+        .map(|(module, function)| RegistryCallKind::from_call(module, function))
+        .unwrap_or(RegistryCallKind::Other);
+    let member = match kind {
+        RegistryCallKind::Join | RegistryCallKind::Leave => Some(sender),
+        RegistryCallKind::RemoveMember => removed_member(transaction),
+        _ => None,
+    };
+    Ok(RegistryTransaction {
+        digest: response.digest,
+        checkpoint: response.checkpoint,
+        timestamp_ms: response.timestamp_ms,
+        sender,
+        kind,
+        member,
+        function: call.map(|(module, function)| format!("{}::{}", module, function)),
+        success: matches!(effects.status(), SuiExecutionStatus::Success),
+    })
+}
+
+/// Module and function of the first canary Move call made by the transaction
+fn canary_call(transaction: &SuiTransactionBlock) -> Option<(String, String)> {
+    match transaction.data.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(pt) => {
+            pt.commands.iter().find_map(|command| match command {
+                SuiCommand::MoveCall(call)
+                    if call.module == "member_registry" || call.module == "pkg_storage" =>
+                {
+                    Some((call.module.clone(), call.function.clone()))
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The `member` argument of the transaction's `remove_member` call
+fn removed_member(transaction: &SuiTransactionBlock) -> Option<SuiAddress> {
+    let pt = match transaction.data.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(pt) => pt,
+        _ => return None,
+    };
+    // remove_member(registry: &mut Registry, admin_cap: &AdminCap, member: address)
+    let argument = pt.commands.iter().find_map(|command| match command {
+        SuiCommand::MoveCall(call)
+            if call.module == "member_registry" && call.function == "remove_member" =>
+        {
+            call.arguments.get(2).cloned()
+        }
+        _ => None,
+    })?;
+    match argument {
+        SuiArgument::Input(index) => match pt.inputs.get(index as usize)? {
+            SuiCallArg::Pure(value) => value.value().to_json_value().as_str()?.parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_kind() {
+        assert_eq!(
+            RegistryCallKind::from_call("member_registry", "join_registry"),
+            RegistryCallKind::Join
+        );
+        assert_eq!(
+            RegistryCallKind::from_call("member_registry", LEAVE_REGISTRY_FUNCTION),
+            RegistryCallKind::Leave
+        );
+        assert_eq!(
+            RegistryCallKind::from_call("member_registry", WITHDRAW_TO_FUNCTION),
+            RegistryCallKind::Withdraw
+        );
+        assert_eq!(
+            RegistryCallKind::from_call("member_registry", "update_fee"),
+            RegistryCallKind::SetFee
+        );
+        assert_eq!(
+            RegistryCallKind::from_call("pkg_storage", "delete_canary_blob"),
+            RegistryCallKind::DeleteBlob
+        );
+        // Function names only count in their own module
+        assert_eq!(
+            RegistryCallKind::from_call("pkg_storage", "withdraw"),
+            RegistryCallKind::Other
+        );
+    }
+
+    #[test]
+    fn test_call_kind_serialization() {
+        assert_eq!(
+            serde_json::to_string(&RegistryCallKind::RemoveMember).unwrap(),
+            "\"remove_member\""
+        );
+    }
+}
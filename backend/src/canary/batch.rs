@@ -0,0 +1,254 @@
+//! Batched canary blob operations
+//!
+//! Admins managing many domains store or update all of a release's canary blobs in one
+//! programmable transaction: one signature, one gas payment, and one AdminCap input
+//! shared by every `store_blob` or `update_blob` call. The batch is atomic, so if any
+//! call aborts (e.g. a domain already has a blob for the package), none of the blobs
+//! are written.
+
+use super::{admin_call_args, ensure_success, SharedObjectResolver, TransactionReceipt};
+use crate::client::SuiClientWithSigner;
+use crate::error::CanaryError;
+use crate::transaction::{pure_address, pure_string, CanaryTransactionBuilder};
+use std::collections::HashSet;
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::types::transaction::SharedObjectMutability;
+
+/// A canary blob to create with `store_blobs_batch`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobStoreEntry {
+    /// The domain the canary is published for
+    pub domain: String,
+    /// The contract blob object ID
+    pub contract_blob_id: ObjectID,
+    /// The explain blob object ID
+    pub explain_blob_id: ObjectID,
+    /// The package ID the canary covers
+    pub package_id: ObjectID,
+}
+
+/// New contract and explain blobs for an existing canary blob, for `update_blobs_batch`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobUpdateEntry {
+    /// The CanaryBlob object ID
+    pub canary_blob_id: ObjectID,
+    /// The new contract blob object ID
+    pub contract_blob_id: ObjectID,
+    /// The new explain blob object ID
+    pub explain_blob_id: ObjectID,
+}
+
+/// Store several canary blobs in one transaction
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `entries` - The canary blobs to store
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if `entries` is empty or
+/// repeats a domain and package, the signer does not hold the registry's AdminCap,
+/// or the transaction fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::{store_blobs_batch, BlobStoreEntry};
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let package_id = ObjectID::from_hex_literal("0x789...")?;
+/// let entries = ["example.com", "example.org"]
+///     .into_iter()
+///     .map(|domain| BlobStoreEntry {
+///         domain: domain.to_string(),
+///         contract_blob_id: ObjectID::from_hex_literal("0xabc...").unwrap(),
+///         explain_blob_id: ObjectID::from_hex_literal("0xdef...").unwrap(),
+///         package_id,
+///     })
+///     .collect();
+/// let receipt = store_blobs_batch(client, registry_id, admin_cap_id, entries).await?;
+/// println!("Stored canaries: {:?}", receipt.digest);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn store_blobs_batch(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    entries: Vec<BlobStoreEntry>,
+) -> Result<TransactionReceipt, CanaryError> {
+    check_store_entries(&entries)?;
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    for entry in entries {
+        // store_blob(registry: &mut Registry, admin_cap: &AdminCap, domain: String,
+        //            contract_blob_id: address, explain_blob_id: address, package_id: address,
+        //            clock: &Clock, ctx: &mut TxContext)
+        let args = vec![
+            registry_arg.clone(),
+            admin_cap_arg.clone(),
+            pure_string(&entry.domain),
+            pure_address(entry.contract_blob_id),
+            pure_address(entry.explain_blob_id),
+            pure_address(entry.package_id),
+            SharedObjectResolver::clock_arg(),
+        ];
+        builder.move_call(package_id, "pkg_storage", "store_blob", args)?;
+    }
+
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Update several canary blobs in one transaction
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `entries` - The canary blobs to update and their new blob IDs
+///
+/// # Returns
+///
+/// Returns the transaction receipt, or a `CanaryError` if `entries` is empty or
+/// repeats a canary blob, the signer does not hold the registry's AdminCap, or the
+/// transaction fails.
+pub async fn update_blobs_batch(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    entries: Vec<BlobUpdateEntry>,
+) -> Result<TransactionReceipt, CanaryError> {
+    check_update_entries(&entries)?;
+    let (package_id, registry_arg, admin_cap_arg) =
+        admin_call_args(&client, registry_id, admin_cap_id).await?;
+
+    let resolver = SharedObjectResolver::global();
+    let mut canary_blob_args = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        canary_blob_args.push(
+            resolver
+                .shared_arg(
+                    &client.client,
+                    entry.canary_blob_id,
+                    SharedObjectMutability::Mutable,
+                )
+                .await?,
+        );
+    }
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    for (entry, canary_blob_arg) in entries.into_iter().zip(canary_blob_args) {
+        // update_blob(registry: &Registry, admin_cap: &AdminCap, canary_blob: &mut CanaryBlob,
+        //             new_contract_blob_id: address, new_explain_blob_id: address,
+        //             clock: &Clock, ctx: &TxContext)
+        let args = vec![
+            registry_arg.clone(),
+            admin_cap_arg.clone(),
+            canary_blob_arg,
+            pure_address(entry.contract_blob_id),
+            pure_address(entry.explain_blob_id),
+            SharedObjectResolver::clock_arg(),
+        ];
+        builder.move_call(package_id, "pkg_storage", "update_blob", args)?;
+    }
+
+    let response = builder.execute().await?;
+    ensure_success(&response)?;
+    Ok(TransactionReceipt::from_response(response))
+}
+
+/// Reject an empty batch, or one that stores the same domain and package twice
+///
+/// The second `store_blob` would abort and take the whole batch down with it.
+fn check_store_entries(entries: &[BlobStoreEntry]) -> Result<(), CanaryError> {
+    if entries.is_empty() {
+        return Err(CanaryError::Registry(
+            "No canary blobs to store".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for entry in entries {
+        if !seen.insert((entry.domain.as_str(), entry.package_id)) {
+            return Err(CanaryError::Registry(format!(
+                "{} is stored twice for package {}",
+                entry.domain, entry.package_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject an empty batch, or one that updates the same canary blob twice
+fn check_update_entries(entries: &[BlobUpdateEntry]) -> Result<(), CanaryError> {
+    if entries.is_empty() {
+        return Err(CanaryError::Registry(
+            "No canary blobs to update".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for entry in entries {
+        if !seen.insert(entry.canary_blob_id) {
+            return Err(CanaryError::Registry(format!(
+                "Canary blob {} is updated twice",
+                entry.canary_blob_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_entry(domain: &str, package: u8) -> BlobStoreEntry {
+        BlobStoreEntry {
+            domain: domain.to_string(),
+            contract_blob_id: ObjectID::random(),
+            explain_blob_id: ObjectID::random(),
+            package_id: ObjectID::from_single_byte(package),
+        }
+    }
+
+    #[test]
+    fn test_check_store_entries() {
+        assert!(check_store_entries(&[]).is_err());
+        assert!(check_store_entries(&[
+            store_entry("example.com", 1),
+            store_entry("example.org", 1),
+            // Same domain, different package
+            store_entry("example.com", 2),
+        ])
+        .is_ok());
+        assert!(check_store_entries(&[
+            store_entry("example.com", 1),
+            store_entry("example.com", 1),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_update_entries() {
+        let update = BlobUpdateEntry {
+            canary_blob_id: ObjectID::random(),
+            contract_blob_id: ObjectID::random(),
+            explain_blob_id: ObjectID::random(),
+        };
+        assert!(check_update_entries(&[]).is_err());
+        assert!(check_update_entries(&[update.clone()]).is_ok());
+        assert!(check_update_entries(&[update.clone(), update]).is_err());
+    }
+}
@@ -0,0 +1,575 @@
+//! Typed Move call wrappers generated from a package's ABI
+//!
+//! `generate_bindings` reads a package's normalized modules from the node and renders
+//! Rust source with one wrapper per entry or public function. A wrapper takes the Move
+//! parameters in order as Rust values and returns a `MoveCall`, so call sites no longer
+//! assemble `CallArg` vectors by hand:
+//!
+//! - `bool`, integers up to `u128`, `address`, `ID`, `String`, `Option`, and vectors of
+//!   these become typed parameters, encoded with BCS
+//! - objects stay `CallArg`s, since only the caller knows whether an object is owned or
+//!   shared (see `SharedObjectResolver`)
+//! - `&Clock` is filled in with the system clock and `TxContext` is left out
+//!
+//! A function whose return types all map to Rust types also gets an async `<name>_view`
+//! wrapper that runs it with `view_call` and decodes the result. Generic functions are
+//! skipped.
+//!
+//! The normalized ABI carries no parameter names, so object parameters are named after
+//! their type (`registry`, `admin_cap`) and the rest after their position (`arg2`).
+//!
+//! Write the source to a checked-in file, or to `OUT_DIR` from a build script, and
+//! `include!` it. The generated code refers to `canary_sdk` and `sui_sdk` paths.
+
+use super::SharedObjectResolver;
+use crate::client::view_call;
+use crate::error::CanaryError;
+#[cfg(feature = "signing")]
+use crate::{error::TransactionError, transaction::CanaryTransactionBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use sui_sdk::rpc_types::SuiMoveNormalizedModule;
+use sui_sdk::types::base_types::ObjectID;
+#[cfg(feature = "signing")]
+use sui_sdk::types::transaction::Argument;
+use sui_sdk::types::transaction::CallArg;
+use sui_sdk::SuiClient;
+
+/// A Move call with its arguments, as produced by generated wrappers
+#[derive(Debug, Clone)]
+pub struct MoveCall {
+    /// The package defining the module
+    pub package: ObjectID,
+    /// The module name
+    pub module: &'static str,
+    /// The function name
+    pub function: &'static str,
+    /// The call arguments, in parameter order
+    pub args: Vec<CallArg>,
+}
+
+impl MoveCall {
+    /// Create a call of `module::function` in `package`
+    pub fn new(
+        package: ObjectID,
+        module: &'static str,
+        function: &'static str,
+        args: Vec<CallArg>,
+    ) -> Self {
+        Self {
+            package,
+            module,
+            function,
+            args,
+        }
+    }
+
+    /// BCS-encode a pure argument
+    pub fn pure<T: Serialize>(value: &T) -> Result<CallArg, CanaryError> {
+        bcs::to_bytes(value)
+            .map(CallArg::Pure)
+            .map_err(|e| CanaryError::Registry(format!("Failed to serialize argument: {}", e)))
+    }
+
+    /// The system clock argument
+    pub fn clock() -> CallArg {
+        SharedObjectResolver::clock_arg()
+    }
+
+    /// Add the call to a transaction and return its result
+    #[cfg(feature = "signing")]
+    pub fn add_to(
+        self,
+        builder: &mut CanaryTransactionBuilder,
+    ) -> Result<Argument, TransactionError> {
+        builder.move_call_with_result(self.package, self.module, self.function, self.args)
+    }
+
+    /// Run the call with `view_call` and decode its return values
+    pub async fn view<T: DeserializeOwned>(self, client: &SuiClient) -> Result<T, CanaryError> {
+        Ok(view_call(
+            client,
+            self.package,
+            self.module,
+            self.function,
+            vec![],
+            self.args,
+        )
+        .await?)
+    }
+}
+
+/// Generate typed wrappers for every entry and public function of a package
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for reading the package
+/// * `package_id` - The package to generate wrappers for
+///
+/// # Returns
+///
+/// Returns the Rust source, or a `CanaryError` if the package's modules cannot be read.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::bindings::generate_bindings;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Testnet).await?;
+/// let package_id = ObjectID::from_hex_literal("0x123...")?;
+/// let source = generate_bindings(&client, package_id).await?;
+/// std::fs::write("src/canary_bindings.rs", source)?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn generate_bindings(
+    client: &SuiClient,
+    package_id: ObjectID,
+) -> Result<String, CanaryError> {
+    let modules = client
+        .read_api()
+        .get_normalized_move_modules_by_package(package_id)
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get package modules: {}", e)))?;
+    render_bindings(package_id, &modules)
+}
+
+/// Render typed wrappers for already fetched normalized modules
+pub fn render_bindings(
+    package_id: ObjectID,
+    modules: &BTreeMap<String, SuiMoveNormalizedModule>,
+) -> Result<String, CanaryError> {
+    // Read the ABI through its JSON form, which is stable across SDK versions
+    let modules = serde_json::to_value(modules)
+        .and_then(serde_json::from_value::<BTreeMap<String, AbiModule>>)
+        .map_err(|e| CanaryError::Registry(format!("Failed to read package ABI: {}", e)))?;
+    Ok(render(package_id, &modules))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiModule {
+    exposed_functions: BTreeMap<String, AbiFunction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiFunction {
+    visibility: AbiVisibility,
+    is_entry: bool,
+    type_parameters: Vec<serde_json::Value>,
+    parameters: Vec<AbiType>,
+    #[serde(rename = "return")]
+    returns: Vec<AbiType>,
+}
+
+#[derive(Deserialize, PartialEq, Eq)]
+enum AbiVisibility {
+    Private,
+    Public,
+    Friend,
+}
+
+#[derive(Deserialize)]
+enum AbiType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Struct {
+        address: String,
+        module: String,
+        name: String,
+        #[serde(default, rename = "typeArguments")]
+        type_arguments: Vec<AbiType>,
+    },
+    Vector(Box<AbiType>),
+    TypeParameter(#[allow(dead_code)] u16),
+    Reference(Box<AbiType>),
+    MutableReference(Box<AbiType>),
+}
+
+impl AbiType {
+    /// The type behind any reference
+    fn dereferenced(&self) -> &AbiType {
+        match self {
+            AbiType::Reference(inner) | AbiType::MutableReference(inner) => inner.dereferenced(),
+            other => other,
+        }
+    }
+
+    /// Whether this is the struct `address::module::name`
+    fn is_struct(&self, struct_address: &str, struct_module: &str, struct_name: &str) -> bool {
+        match self {
+            AbiType::Struct {
+                address,
+                module,
+                name,
+                ..
+            } => {
+                same_address(address, struct_address)
+                    && module == struct_module
+                    && name == struct_name
+            }
+            _ => false,
+        }
+    }
+
+    /// The Rust type a pure value of this type is passed as, if it is a pure type
+    fn rust_type(&self) -> Option<String> {
+        let primitive = match self {
+            AbiType::Bool => "bool",
+            AbiType::U8 => "u8",
+            AbiType::U16 => "u16",
+            AbiType::U32 => "u32",
+            AbiType::U64 => "u64",
+            AbiType::U128 => "u128",
+            AbiType::Address => "SuiAddress",
+            AbiType::Vector(inner) => return inner.rust_type().map(|t| format!("Vec<{}>", t)),
+            AbiType::Struct { type_arguments, .. } if self.is_struct("0x1", "option", "Option") => {
+                return type_arguments
+                    .first()?
+                    .rust_type()
+                    .map(|t| format!("Option<{}>", t));
+            }
+            _ if self.is_struct("0x1", "string", "String")
+                || self.is_struct("0x1", "ascii", "String") =>
+            {
+                "String"
+            }
+            _ if self.is_struct("0x2", "object", "ID") => "ObjectID",
+            _ => return None,
+        };
+        Some(primitive.to_string())
+    }
+}
+
+/// Compare addresses that may differ in zero padding (`0x2` and `0x00..02`)
+fn same_address(a: &str, b: &str) -> bool {
+    let trim = |address: &str| {
+        address
+            .trim_start_matches("0x")
+            .trim_start_matches('0')
+            .to_lowercase()
+    };
+    trim(a) == trim(b)
+}
+
+/// How a wrapper passes one Move parameter
+enum Param {
+    /// A typed Rust parameter, BCS-encoded
+    Pure { name: String, rust_type: String },
+    /// An object the caller passes as a `CallArg`
+    Object { name: String },
+    /// The system clock, filled in by the wrapper
+    Clock,
+    /// The transaction context, supplied by the runtime
+    Context,
+}
+
+impl Param {
+    fn new(index: usize, ty: &AbiType) -> Self {
+        let inner = ty.dereferenced();
+        if inner.is_struct("0x2", "tx_context", "TxContext") {
+            return Param::Context;
+        }
+        if inner.is_struct("0x2", "clock", "Clock") && matches!(ty, AbiType::Reference(_)) {
+            return Param::Clock;
+        }
+        match (ty.rust_type(), inner) {
+            (Some(rust_type), _) => Param::Pure {
+                name: format!("arg{}", index),
+                rust_type,
+            },
+            (None, AbiType::Struct { name, .. }) => Param::Object {
+                name: snake_case(name),
+            },
+            (None, _) => Param::Object {
+                name: format!("arg{}", index),
+            },
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Param::Pure { name, .. } | Param::Object { name } => Some(name),
+            Param::Clock | Param::Context => None,
+        }
+    }
+}
+
+/// Resolve the parameters of a function, making repeated object names unique
+fn params(function: &AbiFunction) -> Vec<Param> {
+    let mut params: Vec<Param> = function
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| Param::new(index, ty))
+        .collect();
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for name in params.iter().filter_map(Param::name) {
+        *counts.entry(name.to_string()).or_default() += 1;
+    }
+    for (index, param) in params.iter_mut().enumerate() {
+        if let Param::Object { name } = param {
+            if counts[name.as_str()] > 1 {
+                name.push_str(&index.to_string());
+            }
+        }
+    }
+    params
+}
+
+fn render(package_id: ObjectID, modules: &BTreeMap<String, AbiModule>) -> String {
+    let mut out = format!(
+        "// @generated by canary_sdk::canary::bindings from package {}. Do not edit.\n",
+        package_id
+    );
+    for (module_name, module) in modules {
+        let functions: Vec<_> = module
+            .exposed_functions
+            .iter()
+            .filter(|(_, function)| {
+                (function.is_entry || function.visibility == AbiVisibility::Public)
+                    && function.type_parameters.is_empty()
+            })
+            .collect();
+        if functions.is_empty() {
+            continue;
+        }
+
+        out.push_str("\n#[allow(unused_imports, clippy::too_many_arguments)]\n");
+        let _ = writeln!(out, "pub mod {} {{", ident(module_name));
+        out.push_str("    use canary_sdk::canary::bindings::MoveCall;\n");
+        out.push_str("    use canary_sdk::error::CanaryError;\n");
+        out.push_str("    use sui_sdk::types::base_types::{ObjectID, SuiAddress};\n");
+        out.push_str("    use sui_sdk::types::transaction::CallArg;\n");
+        out.push_str("    use sui_sdk::SuiClient;\n");
+        for (function_name, function) in functions {
+            render_function(&mut out, module_name, function_name, function);
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn render_function(out: &mut String, module: &str, name: &str, function: &AbiFunction) {
+    let params = params(function);
+    let signature: Vec<String> = params
+        .iter()
+        .filter_map(|param| match param {
+            Param::Pure { name, rust_type } => Some(format!("{}: {}", name, rust_type)),
+            Param::Object { name } => Some(format!("{}: CallArg", name)),
+            Param::Clock | Param::Context => None,
+        })
+        .collect();
+    let arguments: Vec<String> = params
+        .iter()
+        .filter_map(|param| match param {
+            Param::Pure { name, .. } => Some(format!("MoveCall::pure(&{})?", name)),
+            Param::Object { name } => Some(name.clone()),
+            Param::Clock => Some("MoveCall::clock()".to_string()),
+            Param::Context => None,
+        })
+        .collect();
+    let parameter_list = std::iter::once("package_id: ObjectID".to_string())
+        .chain(signature.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let _ = writeln!(out, "\n    /// `{}::{}`", module, name);
+    let _ = writeln!(
+        out,
+        "    pub fn {}({}) -> Result<MoveCall, CanaryError> {{",
+        ident(name),
+        parameter_list
+    );
+    let _ = writeln!(
+        out,
+        "        Ok(MoveCall::new(package_id, \"{}\", \"{}\", vec![{}]))",
+        module,
+        name,
+        arguments.join(", ")
+    );
+    out.push_str("    }\n");
+
+    let Some(return_type) = return_type(function) else {
+        return;
+    };
+    let forwarded = std::iter::once("package_id")
+        .chain(params.iter().filter_map(Param::name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = writeln!(out, "\n    /// `{}::{}`, run as a view call", module, name);
+    let _ = writeln!(
+        out,
+        "    pub async fn {}_view(client: &SuiClient, {}) -> Result<{}, CanaryError> {{",
+        name, parameter_list, return_type
+    );
+    let _ = writeln!(
+        out,
+        "        {}({})?.view(client).await",
+        ident(name),
+        forwarded
+    );
+    out.push_str("    }\n");
+}
+
+/// The Rust type a function's return values decode into, if it has a view wrapper
+fn return_type(function: &AbiFunction) -> Option<String> {
+    let types = function
+        .returns
+        .iter()
+        .map(|ty| ty.dereferenced().rust_type())
+        .collect::<Option<Vec<_>>>()?;
+    match types.len() {
+        0 => None,
+        1 => types.into_iter().next(),
+        _ => Some(format!("({})", types.join(", "))),
+    }
+}
+
+/// `MembershipCap` -> `membership_cap`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// A Move identifier as a Rust identifier, raw if it is a Rust keyword
+fn ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn struct_type(address: &str, module: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "Struct": { "address": address, "module": module, "name": name, "typeArguments": [] }
+        })
+    }
+
+    fn abi() -> BTreeMap<String, AbiModule> {
+        let registry = struct_type("0xabc", "member_registry", "Registry");
+        let modules = serde_json::json!({
+            "member_registry": {
+                "exposedFunctions": {
+                    "join_registry": {
+                        "visibility": "Public",
+                        "isEntry": true,
+                        "typeParameters": [],
+                        "parameters": [
+                            { "MutableReference": registry.clone() },
+                            struct_type("0x2", "coin", "Coin"),
+                            struct_type("0x1", "string", "String"),
+                            { "Reference": struct_type("0x2", "clock", "Clock") },
+                            { "MutableReference": struct_type("0x2", "tx_context", "TxContext") }
+                        ],
+                        "return": []
+                    },
+                    "is_member": {
+                        "visibility": "Public",
+                        "isEntry": false,
+                        "typeParameters": [],
+                        "parameters": [{ "Reference": registry.clone() }, "Address"],
+                        "return": ["Bool"]
+                    },
+                    "registry_uid_mut": {
+                        "visibility": "Friend",
+                        "isEntry": false,
+                        "typeParameters": [],
+                        "parameters": [{ "MutableReference": registry }],
+                        "return": []
+                    },
+                    "borrow": {
+                        "visibility": "Public",
+                        "isEntry": false,
+                        "typeParameters": [{ "abilities": [] }],
+                        "parameters": [{ "TypeParameter": 0 }],
+                        "return": []
+                    }
+                }
+            }
+        });
+        serde_json::from_value(modules).unwrap()
+    }
+
+    #[test]
+    fn test_render_entry_function() {
+        let source = render(ObjectID::from_single_byte(0xab), &abi());
+        assert!(source.contains("pub mod member_registry {"));
+        assert!(source.contains(
+            "pub fn join_registry(package_id: ObjectID, registry: CallArg, coin: CallArg, \
+             arg2: String) -> Result<MoveCall, CanaryError> {"
+        ));
+        assert!(source.contains("vec![registry, coin, MoveCall::pure(&arg2)?, MoveCall::clock()]"));
+        // Entry functions returning nothing get no view wrapper
+        assert!(!source.contains("join_registry_view"));
+    }
+
+    #[test]
+    fn test_render_view_function() {
+        let source = render(ObjectID::from_single_byte(0xab), &abi());
+        assert!(source.contains(
+            "pub async fn is_member_view(client: &SuiClient, package_id: ObjectID, \
+             registry: CallArg, arg1: SuiAddress) -> Result<bool, CanaryError> {"
+        ));
+        assert!(source.contains("is_member(package_id, registry, arg1)?.view(client).await"));
+    }
+
+    #[test]
+    fn test_render_skips_friend_and_generic_functions() {
+        let source = render(ObjectID::from_single_byte(0xab), &abi());
+        assert!(!source.contains("registry_uid_mut"));
+        assert!(!source.contains("fn borrow"));
+    }
+
+    #[test]
+    fn test_rust_types() {
+        let ty: AbiType = serde_json::from_value(serde_json::json!({
+            "Vector": struct_type("0x0000000000000000000000000000000000000000000000000000000000000002", "object", "ID")
+        }))
+        .unwrap();
+        assert_eq!(ty.rust_type().as_deref(), Some("Vec<ObjectID>"));
+        let ty: AbiType = serde_json::from_value(struct_type("0x2", "coin", "Coin")).unwrap();
+        assert_eq!(ty.rust_type(), None);
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(snake_case("MembershipCap"), "membership_cap");
+        assert_eq!(ident("move"), "r#move");
+        assert_eq!(ident("join_registry"), "join_registry");
+    }
+}
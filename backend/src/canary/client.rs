@@ -0,0 +1,508 @@
+//! A registry-bound client
+//!
+//! `CanaryClient` binds a Sui client, an optional signer, and a registry ID, so calls
+//! don't have to thread the registry (and its package) through every helper:
+//!
+//! ```rust,no_run
+//! use canary_sdk::canary::CanaryClient;
+//! use canary_sdk::client::{create_client_with_key, Network};
+//! use sui_sdk::types::base_types::ObjectID;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = create_client_with_key(Network::Testnet, "suiprivkey1...").await?;
+//! let registry_id = ObjectID::from_hex_literal("0x123...")?;
+//! let canary = CanaryClient::with_signer(signer, registry_id);
+//!
+//! let fee = canary.registry().await?.fee;
+//! canary.join("example.com", fee).await?;
+//! for blob in canary.blobs().await? {
+//!     println!("{}: {}", blob.domain, blob.contract_blob_id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Write methods run the free functions of `crate::canary` with a copy of the signer
+//! (`SuiClientWithSigner::try_clone`), so the `CanaryClient` stays usable afterwards.
+
+#[cfg(feature = "signing")]
+use super::{
+    delete_canary_blob, join_registry, leave_registry, remove_member, set_fee, store_blob,
+    store_blobs_batch, update_blob, update_blob_preview, update_blobs_batch, withdraw_fees,
+    BlobStoreEntry, BlobUpdateEntry, BlobUpdatePreview, FeeUpdate, TransactionReceipt,
+};
+use super::{
+    extract_package_id_from_type, query_all_canary_blobs, query_all_members, query_blob_by_domain,
+    query_canary_blob, query_member, query_registry, query_registry_transactions, CanaryBlobInfo,
+    MemberInfo, MemberInfoWithAddress, RegistryInfo, RegistryTransactionPage,
+};
+#[cfg(feature = "signing")]
+use crate::client::SuiClientWithSigner;
+use crate::error::CanaryError;
+use std::future::Future;
+use sui_sdk::rpc_types::SuiObjectDataOptions;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+use tokio::sync::OnceCell;
+
+/// A client bound to one Canary registry
+pub struct CanaryClient {
+    client: SuiClient,
+    #[cfg(feature = "signing")]
+    signer: Option<SuiClientWithSigner>,
+    binding: RegistryBinding,
+}
+
+/// The registry a `CanaryClient` is bound to, and the IDs used with it
+#[derive(Debug)]
+struct RegistryBinding {
+    registry_id: ObjectID,
+    admin_cap_id: Option<ObjectID>,
+    package_id: OnceCell<ObjectID>,
+}
+
+impl RegistryBinding {
+    fn new(registry_id: ObjectID) -> Self {
+        Self {
+            registry_id,
+            admin_cap_id: None,
+            package_id: OnceCell::new(),
+        }
+    }
+
+    fn require_admin_cap(&self) -> Result<ObjectID, CanaryError> {
+        self.admin_cap_id.ok_or(CanaryError::NoAdminCap)
+    }
+
+    /// The package ID, from `fetch` on first use; a failed fetch is retried next time
+    async fn package_id<F, Fut>(&self, fetch: F) -> Result<ObjectID, CanaryError>
+    where
+        F: FnOnce(ObjectID) -> Fut,
+        Fut: Future<Output = Result<ObjectID, CanaryError>>,
+    {
+        self.package_id
+            .get_or_try_init(|| fetch(self.registry_id))
+            .await
+            .copied()
+    }
+}
+
+impl CanaryClient {
+    /// Create a read-only client for a registry
+    pub fn new(client: SuiClient, registry_id: ObjectID) -> Self {
+        Self {
+            client,
+            #[cfg(feature = "signing")]
+            signer: None,
+            binding: RegistryBinding::new(registry_id),
+        }
+    }
+
+    /// Create a client that can also sign transactions
+    #[cfg(feature = "signing")]
+    pub fn with_signer(signer: SuiClientWithSigner, registry_id: ObjectID) -> Self {
+        Self {
+            client: signer.client.clone(),
+            signer: Some(signer),
+            binding: RegistryBinding::new(registry_id),
+        }
+    }
+
+    /// Use an AdminCap for the admin methods (`store_blob`, `remove_member`, ...)
+    pub fn with_admin_cap(mut self, admin_cap_id: ObjectID) -> Self {
+        self.binding.admin_cap_id = Some(admin_cap_id);
+        self
+    }
+
+    /// Use an already known package ID instead of reading it from the registry
+    pub fn with_package_id(mut self, package_id: ObjectID) -> Self {
+        self.binding.package_id = OnceCell::new_with(Some(package_id));
+        self
+    }
+
+    /// The Sui client
+    pub fn client(&self) -> &SuiClient {
+        &self.client
+    }
+
+    /// The signer, if the client has one
+    #[cfg(feature = "signing")]
+    pub fn signer(&self) -> Option<&SuiClientWithSigner> {
+        self.signer.as_ref()
+    }
+
+    /// The signer, to switch profiles or add keys
+    #[cfg(feature = "signing")]
+    pub fn signer_mut(&mut self) -> Option<&mut SuiClientWithSigner> {
+        self.signer.as_mut()
+    }
+
+    /// The registry ID
+    pub fn registry_id(&self) -> ObjectID {
+        self.binding.registry_id
+    }
+
+    /// The AdminCap ID, if set
+    pub fn admin_cap_id(&self) -> Option<ObjectID> {
+        self.binding.admin_cap_id
+    }
+
+    /// The ID of the package defining the registry
+    ///
+    /// Read from the registry's type on first use, then cached.
+    pub async fn package_id(&self) -> Result<ObjectID, CanaryError> {
+        self.binding
+            .package_id(|registry_id| async move {
+                let registry = self
+                    .client
+                    .read_api()
+                    .get_object_with_options(registry_id, SuiObjectDataOptions::new().with_type())
+                    .await
+                    .map_err(|e| {
+                        CanaryError::Registry(format!("Failed to get registry object: {}", e))
+                    })?
+                    .into_object()
+                    .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
+                registry
+                    .type_
+                    .and_then(|t| extract_package_id_from_type(&t.to_string()))
+                    .ok_or_else(|| {
+                        CanaryError::Registry("Failed to extract package ID".to_string())
+                    })
+            })
+            .await
+    }
+
+    // ------------------------------------------------------------------------
+    // Queries
+    // ------------------------------------------------------------------------
+
+    /// The registry's fee, member count, and admin
+    pub async fn registry(&self) -> Result<RegistryInfo, CanaryError> {
+        query_registry(&self.client, self.binding.registry_id).await
+    }
+
+    /// A member's info, or `None` if `address` is not a member
+    pub async fn member(&self, address: SuiAddress) -> Result<Option<MemberInfo>, CanaryError> {
+        query_member(&self.client, self.binding.registry_id, address).await
+    }
+
+    /// All registry members
+    pub async fn members(&self) -> Result<Vec<MemberInfoWithAddress>, CanaryError> {
+        query_all_members(&self.client, self.binding.registry_id).await
+    }
+
+    /// All canary blobs stored in the registry
+    pub async fn blobs(&self) -> Result<Vec<CanaryBlobInfo>, CanaryError> {
+        query_all_canary_blobs(&self.client, self.binding.registry_id).await
+    }
+
+    /// A canary blob by its object ID
+    pub async fn blob(&self, canary_blob_id: ObjectID) -> Result<CanaryBlobInfo, CanaryError> {
+        query_canary_blob(&self.client, canary_blob_id).await
+    }
+
+    /// The canary blob of a domain for an attested package, or `None` if there is none
+    pub async fn blob_by_domain(
+        &self,
+        domain: &str,
+        package_id: ObjectID,
+    ) -> Result<Option<CanaryBlobInfo>, CanaryError> {
+        query_blob_by_domain(&self.client, self.binding.registry_id, domain, package_id).await
+    }
+
+    /// A page of the transactions that changed the registry, oldest first
+    /// (see `canary::query_registry_transactions`)
+    pub async fn transactions(
+        &self,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+    ) -> Result<RegistryTransactionPage, CanaryError> {
+        query_registry_transactions(&self.client, self.binding.registry_id, cursor, limit).await
+    }
+
+    // ------------------------------------------------------------------------
+    // Member operations
+    // ------------------------------------------------------------------------
+
+    /// Join the registry as the signer (see `canary::join_registry`)
+    ///
+    /// Fails with `CanaryError::AlreadyMember` without submitting anything if the signer
+    /// is already a member.
+    #[cfg(feature = "signing")]
+    pub async fn join(
+        &self,
+        domain: impl Into<String>,
+        payment_amount: u64,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        join_registry(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            domain.into(),
+            payment_amount,
+            false,
+        )
+        .await
+    }
+
+    /// Leave the registry as the signer (see `canary::leave_registry`)
+    #[cfg(feature = "signing")]
+    pub async fn leave(&self) -> Result<TransactionReceipt, CanaryError> {
+        leave_registry(self.signer_copy().await?, self.binding.registry_id).await
+    }
+
+    // ------------------------------------------------------------------------
+    // Admin operations
+    // ------------------------------------------------------------------------
+
+    /// Store a canary blob for a domain (see `canary::store_blob`)
+    #[cfg(feature = "signing")]
+    pub async fn store_blob(
+        &self,
+        domain: impl Into<String>,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+        package_id: ObjectID,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        store_blob(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            domain.into(),
+            contract_blob_id,
+            explain_blob_id,
+            package_id,
+        )
+        .await
+    }
+
+    /// Point a canary blob at new contract and explain blobs (see `canary::update_blob`)
+    #[cfg(feature = "signing")]
+    pub async fn update_blob(
+        &self,
+        canary_blob_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        update_blob(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            canary_blob_id,
+            contract_blob_id,
+            explain_blob_id,
+        )
+        .await
+    }
+
+    /// Dry-run `update_blob` and show what it would change (see
+    /// `canary::update_blob_preview`)
+    #[cfg(feature = "signing")]
+    pub async fn update_blob_preview(
+        &self,
+        canary_blob_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+    ) -> Result<BlobUpdatePreview, CanaryError> {
+        update_blob_preview(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            canary_blob_id,
+            contract_blob_id,
+            explain_blob_id,
+        )
+        .await
+    }
+
+    /// Store several canary blobs in one transaction (see `canary::store_blobs_batch`)
+    #[cfg(feature = "signing")]
+    pub async fn store_blobs(
+        &self,
+        entries: Vec<BlobStoreEntry>,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        store_blobs_batch(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            entries,
+        )
+        .await
+    }
+
+    /// Update several canary blobs in one transaction (see `canary::update_blobs_batch`)
+    #[cfg(feature = "signing")]
+    pub async fn update_blobs(
+        &self,
+        entries: Vec<BlobUpdateEntry>,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        update_blobs_batch(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            entries,
+        )
+        .await
+    }
+
+    /// Delete a canary blob (see `canary::delete_canary_blob`)
+    #[cfg(feature = "signing")]
+    pub async fn delete_blob(
+        &self,
+        canary_blob_id: ObjectID,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        delete_canary_blob(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            canary_blob_id,
+        )
+        .await
+    }
+
+    /// Remove a member (see `canary::remove_member`)
+    #[cfg(feature = "signing")]
+    pub async fn remove_member(
+        &self,
+        member: SuiAddress,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        remove_member(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            member,
+        )
+        .await
+    }
+
+    /// Withdraw collected fees to `recipient` (see `canary::withdraw_fees`)
+    #[cfg(feature = "signing")]
+    pub async fn withdraw(
+        &self,
+        amount: u64,
+        recipient: SuiAddress,
+    ) -> Result<TransactionReceipt, CanaryError> {
+        withdraw_fees(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            amount,
+            recipient,
+        )
+        .await
+    }
+
+    /// Update the membership fee (see `canary::set_fee`)
+    #[cfg(feature = "signing")]
+    pub async fn set_fee(&self, new_fee: u64) -> Result<FeeUpdate, CanaryError> {
+        set_fee(
+            self.signer_copy().await?,
+            self.binding.registry_id,
+            self.require_admin_cap()?,
+            new_fee,
+        )
+        .await
+    }
+
+    #[cfg(feature = "signing")]
+    async fn signer_copy(&self) -> Result<SuiClientWithSigner, CanaryError> {
+        let signer = self.signer.as_ref().ok_or(CanaryError::NoSigner)?;
+        signer
+            .try_clone()
+            .await
+            .map_err(|e| CanaryError::Registry(format!("Failed to copy signer: {}", e)))
+    }
+
+    #[cfg(feature = "signing")]
+    fn require_admin_cap(&self) -> Result<ObjectID, CanaryError> {
+        self.binding.require_admin_cap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_binding_requires_admin_cap() {
+        let registry_id = ObjectID::from_single_byte(1);
+        let mut binding = RegistryBinding::new(registry_id);
+        assert!(matches!(
+            binding.require_admin_cap(),
+            Err(CanaryError::NoAdminCap)
+        ));
+
+        let admin_cap_id = ObjectID::from_single_byte(2);
+        binding.admin_cap_id = Some(admin_cap_id);
+        assert_eq!(binding.require_admin_cap().unwrap(), admin_cap_id);
+    }
+
+    #[tokio::test]
+    async fn test_binding_fetches_package_id_once() {
+        let registry_id = ObjectID::from_single_byte(1);
+        let package_id = ObjectID::from_single_byte(3);
+        let binding = RegistryBinding::new(registry_id);
+        let fetches = AtomicU32::new(0);
+        let fetch = |id: ObjectID| {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            async move {
+                // The fetch is given the bound registry
+                assert_eq!(id, registry_id);
+                Ok::<_, CanaryError>(package_id)
+            }
+        };
+
+        assert_eq!(binding.package_id(fetch).await.unwrap(), package_id);
+        assert_eq!(binding.package_id(fetch).await.unwrap(), package_id);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_binding_retries_failed_package_fetch() {
+        let binding = RegistryBinding::new(ObjectID::from_single_byte(1));
+        let failed = binding
+            .package_id(|_| async {
+                Err::<ObjectID, _>(CanaryError::Registry("unreachable".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let package_id = ObjectID::from_single_byte(3);
+        let fetched = binding
+            .package_id(|_| async move { Ok::<_, CanaryError>(package_id) })
+            .await;
+        assert_eq!(fetched.unwrap(), package_id);
+    }
+
+    #[tokio::test]
+    async fn test_binding_with_known_package_id() {
+        let package_id = ObjectID::from_single_byte(3);
+        let mut binding = RegistryBinding::new(ObjectID::from_single_byte(1));
+        binding.package_id = OnceCell::new_with(Some(package_id));
+        let fetched = binding
+            .package_id(|_| async {
+                Err::<ObjectID, _>(CanaryError::Registry("fetched".to_string()))
+            })
+            .await;
+        assert_eq!(fetched.unwrap(), package_id);
+    }
+
+    #[cfg(feature = "signing")]
+    #[tokio::test]
+    #[ignore] // Ignored by default - requires network connection
+    async fn test_read_only_client_has_no_signer() {
+        use crate::client::{create_sui_client, Network};
+
+        let registry_id = ObjectID::from_single_byte(1);
+        let client = create_sui_client(Network::Localnet).await.unwrap();
+        let canary = CanaryClient::new(client, registry_id);
+        assert_eq!(canary.registry_id(), registry_id);
+        assert!(canary.signer().is_none());
+        assert!(matches!(
+            canary.join("example.com", 1).await,
+            Err(CanaryError::NoSigner)
+        ));
+    }
+}
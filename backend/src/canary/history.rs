@@ -0,0 +1,226 @@
+//! Reads pinned to a historical object version
+//!
+//! `dev_inspect` always executes against the latest version of shared objects, so a
+//! view call cannot be replayed as of an earlier state. The functions here instead
+//! fetch the object exactly as it was at a given version (`sui_tryGetPastObject`) and
+//! decode its fields directly, which gives reproducible results when investigating a
+//! historical discrepancy. Fullnodes prune old versions, so very old reads may fail
+//! with `VersionNotFound`; point the client at an archival node for those.
+
+use super::{
+    json_address, json_string, json_u64, object_fields, raw_object, CanaryBlobInfo, RawObject,
+    RegistryInfo,
+};
+use crate::error::CanaryError;
+use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiPastObjectResponse};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::SuiClient;
+use sui_types::base_types::SequenceNumber;
+
+/// Query registry information as of a specific object version
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `version` - The Registry object version to read
+///
+/// # Returns
+///
+/// Returns `RegistryInfo` as it was at `version`, or a `CanaryError` if that version
+/// is unavailable.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_registry_at;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::{ObjectID, SequenceNumber};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let info = query_registry_at(&client, registry_id, SequenceNumber::from_u64(42)).await?;
+/// println!("Fee at version 42: {} MIST", info.fee);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn query_registry_at(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    version: SequenceNumber,
+) -> Result<RegistryInfo, CanaryError> {
+    let (fields, raw) = get_past_object_fields(client, registry_id, version).await?;
+    let mut info = parse_registry_fields(registry_id, &fields)?;
+    info.raw = raw;
+    Ok(info)
+}
+
+/// Query canary blob information as of a specific object version
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `canary_blob_id` - The CanaryBlob object ID
+/// * `version` - The CanaryBlob object version to read
+///
+/// # Returns
+///
+/// Returns `CanaryBlobInfo` as it was at `version`, or a `CanaryError` if that version
+/// is unavailable.
+pub async fn query_canary_blob_at(
+    client: &SuiClient,
+    canary_blob_id: ObjectID,
+    version: SequenceNumber,
+) -> Result<CanaryBlobInfo, CanaryError> {
+    let (fields, raw) = get_past_object_fields(client, canary_blob_id, version).await?;
+    let mut info = parse_canary_blob_fields(canary_blob_id, &fields)?;
+    info.raw = raw;
+    Ok(info)
+}
+
+/// Fetch an object at a past version and return its Move fields as JSON, with its raw BCS
+pub(crate) async fn get_past_object_fields(
+    client: &SuiClient,
+    object_id: ObjectID,
+    version: SequenceNumber,
+) -> Result<(serde_json::Value, Option<RawObject>), CanaryError> {
+    let response = client
+        .read_api()
+        .try_get_parsed_past_object(
+            object_id,
+            version,
+            SuiObjectDataOptions::new().with_content().with_bcs(),
+        )
+        .await
+        .map_err(|e| {
+            CanaryError::Registry(format!(
+                "Failed to get object {} at version {}: {}",
+                object_id, version, e
+            ))
+        })?;
+
+    match response {
+        SuiPastObjectResponse::VersionFound(data) => {
+            let raw = raw_object(&data);
+            Ok((object_fields(data)?, raw))
+        }
+        SuiPastObjectResponse::ObjectDeleted(_) => Err(CanaryError::Registry(format!(
+            "Object {} was deleted at version {}",
+            object_id, version
+        ))),
+        SuiPastObjectResponse::VersionTooHigh { latest_version, .. } => {
+            Err(CanaryError::Registry(format!(
+                "Object {} has no version {} (latest is {})",
+                object_id, version, latest_version
+            )))
+        }
+        SuiPastObjectResponse::ObjectNotExists(_) | SuiPastObjectResponse::VersionNotFound(..) => {
+            Err(CanaryError::Registry(format!(
+                "Object {} at version {} is not available on this node",
+                object_id, version
+            )))
+        }
+    }
+}
+
+/// Decode `RegistryInfo` from the JSON fields of a `Registry` object
+fn parse_registry_fields(
+    id: ObjectID,
+    fields: &serde_json::Value,
+) -> Result<RegistryInfo, CanaryError> {
+    Ok(RegistryInfo {
+        id,
+        fee: json_u64(fields, "fee")?,
+        member_count: json_u64(fields, "member_count")?,
+        admin: json_address(fields, "admin")?,
+        raw: None,
+    })
+}
+
+/// Decode `CanaryBlobInfo` from the JSON fields of a `CanaryBlob` object
+fn parse_canary_blob_fields(
+    id: ObjectID,
+    fields: &serde_json::Value,
+) -> Result<CanaryBlobInfo, CanaryError> {
+    Ok(CanaryBlobInfo {
+        id,
+        contract_blob_id: json_address(fields, "contract_blob_id")?.into(),
+        explain_blob_id: json_address(fields, "explain_blob_id")?.into(),
+        package_id: json_address(fields, "package_id")?.into(),
+        domain: json_string(fields, "domain")?,
+        uploaded_at: json_u64(fields, "uploaded_at")?,
+        uploaded_by_admin: json_address(fields, "uploaded_by_admin")?,
+        raw: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ADDR_A: &str = "0x000000000000000000000000000000000000000000000000000000000000000a";
+    const ADDR_B: &str = "0x000000000000000000000000000000000000000000000000000000000000000b";
+
+    #[test]
+    fn test_parse_registry_fields() {
+        let fields = json!({
+            "id": { "id": ADDR_A },
+            "member_count": "3",
+            "fee": "1000000000",
+            "balance": "3000000000",
+            "admin": ADDR_B,
+        });
+        let id = ObjectID::from_hex_literal(ADDR_A).unwrap();
+        let info = parse_registry_fields(id, &fields).unwrap();
+        assert_eq!(info.id, id);
+        assert_eq!(info.member_count, 3);
+        assert_eq!(info.fee, 1_000_000_000);
+        assert_eq!(info.admin.to_string(), ADDR_B);
+    }
+
+    #[test]
+    fn test_raw_accessors() {
+        use sui_sdk::types::base_types::ObjectDigest;
+
+        let id = ObjectID::from_hex_literal(ADDR_A).unwrap();
+        let fields = json!({ "member_count": "0", "fee": "1", "admin": ADDR_B });
+        let mut info = parse_registry_fields(id, &fields).unwrap();
+        assert!(info.raw_bcs().is_none());
+        assert!(info.object_ref().is_none());
+
+        let object_ref = (id, SequenceNumber::from_u64(7), ObjectDigest::MIN);
+        info.raw = Some(RawObject {
+            object_ref,
+            bcs: vec![1, 2, 3],
+        });
+        assert_eq!(info.raw_bcs(), Some(&[1u8, 2, 3][..]));
+        assert_eq!(info.object_ref(), Some(object_ref));
+    }
+
+    #[test]
+    fn test_parse_registry_fields_missing() {
+        let id = ObjectID::from_hex_literal(ADDR_A).unwrap();
+        assert!(parse_registry_fields(id, &json!({ "fee": "1" })).is_err());
+    }
+
+    #[test]
+    fn test_parse_canary_blob_fields() {
+        let fields = json!({
+            "id": { "id": ADDR_A },
+            "contract_blob_id": ADDR_A,
+            "explain_blob_id": ADDR_B,
+            "package_id": ADDR_A,
+            "domain": "example.com",
+            "uploaded_at": "1700000000000",
+            "uploaded_by_admin": ADDR_B,
+        });
+        let id = ObjectID::from_hex_literal(ADDR_B).unwrap();
+        let info = parse_canary_blob_fields(id, &fields).unwrap();
+        assert_eq!(info.id, id);
+        assert_eq!(info.domain, "example.com");
+        assert_eq!(info.uploaded_at, 1_700_000_000_000);
+        assert_eq!(info.explain_blob_id, id);
+    }
+}
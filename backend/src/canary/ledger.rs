@@ -0,0 +1,337 @@
+//! Historical balance-change accounting for a registry
+//!
+//! The registry keeps collected fees in a `Balance<SUI>` inside the object, so its
+//! inflows and outflows never appear as balance changes of their own. Instead, each
+//! transaction touching the registry is reconstructed from the conservation of SUI:
+//! whatever the participating addresses lost (beyond gas) went into the registry, and
+//! whatever they gained came out of it.
+
+use crate::compat::{Artifact, SDK_VERSION};
+use crate::error::CanaryError;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::ops::Range;
+use sui_sdk::rpc_types::{
+    SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::gas_coin::GAS;
+use sui_sdk::SuiClient;
+
+/// Page size used when walking the registry's transaction history
+const LEDGER_PAGE_SIZE: usize = 50;
+
+/// Direction of a registry ledger entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LedgerEntryKind {
+    /// SUI paid into the registry (membership fees)
+    Inflow,
+    /// SUI taken out of the registry (admin withdrawals)
+    Outflow,
+}
+
+impl LedgerEntryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryKind::Inflow => "inflow",
+            LedgerEntryKind::Outflow => "outflow",
+        }
+    }
+}
+
+/// A single SUI movement into or out of the registry balance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// Checkpoint timestamp of the transaction (in milliseconds)
+    pub timestamp_ms: u64,
+    /// Whether SUI flowed into or out of the registry
+    pub kind: LedgerEntryKind,
+    /// The transaction sender (payer for inflows, admin for outflows)
+    pub sender: SuiAddress,
+    /// The Move function called on the registry, if any (e.g. `join_registry`, `withdraw`)
+    pub function: Option<String>,
+    /// The amount moved, in MIST
+    pub amount: u64,
+}
+
+/// All SUI inflows and outflows of a registry over a time range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryLedger {
+    /// Schema version of this artifact (see `compat`)
+    pub schema_version: u32,
+    /// Version of the SDK that produced this artifact
+    pub sdk_version: String,
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// Ledger entries in chronological order
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl RegistryLedger {
+    /// Create an empty ledger stamped with the current schema and SDK version
+    pub fn new(registry_id: ObjectID) -> Self {
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            sdk_version: SDK_VERSION.to_string(),
+            registry_id,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Total SUI paid into the registry, in MIST
+    pub fn total_inflow(&self) -> u64 {
+        self.total(LedgerEntryKind::Inflow)
+    }
+
+    /// Total SUI taken out of the registry, in MIST
+    pub fn total_outflow(&self) -> u64 {
+        self.total(LedgerEntryKind::Outflow)
+    }
+
+    fn total(&self, kind: LedgerEntryKind) -> u64 {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    /// Write the ledger as CSV
+    ///
+    /// Columns: `timestamp_ms,digest,kind,sender,function,amount_mist`
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "timestamp_ms,digest,kind,sender,function,amount_mist"
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                entry.timestamp_ms,
+                entry.digest,
+                entry.kind.as_str(),
+                entry.sender,
+                entry.function.as_deref().unwrap_or(""),
+                entry.amount
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reconstruct the registry's SUI inflows and outflows from its transaction history
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `range` - Checkpoint timestamp range in milliseconds (start inclusive, end exclusive)
+///
+/// # Returns
+///
+/// Returns a `RegistryLedger` with entries in chronological order, or a `CanaryError` if the query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::registry_ledger;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let ledger = registry_ledger(&client, registry_id, 0..u64::MAX).await?;
+/// println!("Fees collected: {} MIST", ledger.total_inflow());
+/// ledger.write_csv(std::io::stdout())?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn registry_ledger(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    range: Range<u64>,
+) -> Result<RegistryLedger, CanaryError> {
+    let query = SuiTransactionBlockResponseQuery::new(
+        Some(TransactionFilter::ChangedObject(registry_id)),
+        Some(
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects()
+                .with_balance_changes(),
+        ),
+    );
+
+    let mut ledger = RegistryLedger::new(registry_id);
+    let mut cursor = None;
+
+    loop {
+        let page = client
+            .read_api()
+            .query_transaction_blocks(query.clone(), cursor, Some(LEDGER_PAGE_SIZE), false)
+            .await
+            .map_err(|e| {
+                CanaryError::Registry(format!("Failed to query registry transactions: {}", e))
+            })?;
+
+        for response in &page.data {
+            let Some(timestamp_ms) = response.timestamp_ms else {
+                continue;
+            };
+            if timestamp_ms < range.start {
+                continue;
+            }
+            if timestamp_ms >= range.end {
+                return Ok(ledger);
+            }
+            if let Some(entry) = ledger_entry(response, timestamp_ms)? {
+                ledger.entries.push(entry);
+            }
+        }
+
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+
+    Ok(ledger)
+}
+
+/// Build the ledger entry for one transaction, or `None` if the registry balance was untouched
+fn ledger_entry(
+    response: &SuiTransactionBlockResponse,
+    timestamp_ms: u64,
+) -> Result<Option<LedgerEntry>, CanaryError> {
+    let effects = response.effects.as_ref().ok_or_else(|| {
+        CanaryError::Registry(format!("Transaction {} has no effects", response.digest))
+    })?;
+    let transaction = response.transaction.as_ref().ok_or_else(|| {
+        CanaryError::Registry(format!("Transaction {} has no input", response.digest))
+    })?;
+
+    let sui_type = GAS::type_tag();
+    let sui_changes = response
+        .balance_changes
+        .iter()
+        .flatten()
+        .filter(|change| change.coin_type == sui_type)
+        .map(|change| change.amount);
+    let net_gas = effects.gas_cost_summary().net_gas_usage();
+    let delta = registry_sui_delta(sui_changes, net_gas);
+
+    let kind = match delta {
+        0 => return Ok(None),
+        d if d > 0 => LedgerEntryKind::Inflow,
+        _ => LedgerEntryKind::Outflow,
+    };
+
+    Ok(Some(LedgerEntry {
+        digest: response.digest,
+        timestamp_ms,
+        kind,
+        sender: *transaction.data.sender(),
+        function: registry_function(transaction),
+        amount: delta.unsigned_abs() as u64,
+    }))
+}
+
+/// Net SUI change of the registry balance implied by a transaction
+///
+/// SUI is conserved apart from gas, so the registry gained exactly what the
+/// participating addresses lost beyond their net gas usage.
+fn registry_sui_delta(sui_balance_changes: impl IntoIterator<Item = i128>, net_gas: i64) -> i128 {
+    let address_total: i128 = sui_balance_changes.into_iter().sum();
+    -(address_total + net_gas as i128)
+}
+
+/// Name of the first `member_registry` function called by the transaction
+fn registry_function(transaction: &sui_sdk::rpc_types::SuiTransactionBlock) -> Option<String> {
+    use sui_sdk::rpc_types::{SuiCommand, SuiTransactionBlockKind};
+
+    match transaction.data.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(pt) => {
+            pt.commands.iter().find_map(|command| match command {
+                SuiCommand::MoveCall(call) if call.module == "member_registry" => {
+                    Some(call.function.clone())
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_registry_delta_join() {
+        // Payer spends 1 SUI fee + 0.002 SUI gas
+        let delta = registry_sui_delta(vec![-1_002_000_000], 2_000_000);
+        assert_eq!(delta, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_registry_delta_withdraw() {
+        // Admin receives 5 SUI and pays 0.001 SUI gas
+        let delta = registry_sui_delta(vec![4_999_000_000], 1_000_000);
+        assert_eq!(delta, -5_000_000_000);
+    }
+
+    #[test]
+    fn test_registry_delta_untouched() {
+        // e.g. update_fee: only gas is spent
+        let delta = registry_sui_delta(vec![-1_500_000], 1_500_000);
+        assert_eq!(delta, 0);
+    }
+
+    #[test]
+    fn test_ledger_totals_and_csv() {
+        let registry_id = ObjectID::from_hex_literal("0x123").unwrap();
+        let sender = SuiAddress::from_str("0x1").unwrap();
+        let mut ledger = RegistryLedger::new(registry_id);
+        ledger.entries = vec![
+            LedgerEntry {
+                digest: TransactionDigest::default(),
+                timestamp_ms: 1_000,
+                kind: LedgerEntryKind::Inflow,
+                sender,
+                function: Some("join_registry".to_string()),
+                amount: 1_000_000_000,
+            },
+            LedgerEntry {
+                digest: TransactionDigest::default(),
+                timestamp_ms: 2_000,
+                kind: LedgerEntryKind::Outflow,
+                sender,
+                function: Some("withdraw".to_string()),
+                amount: 400_000_000,
+            },
+        ];
+
+        assert_eq!(ledger.total_inflow(), 1_000_000_000);
+        assert_eq!(ledger.total_outflow(), 400_000_000);
+
+        let mut out = Vec::new();
+        ledger.write_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "timestamp_ms,digest,kind,sender,function,amount_mist"
+        );
+        assert!(lines[1].starts_with("1000,"));
+        assert!(lines[1].contains(",inflow,"));
+        assert!(lines[1].ends_with(",join_registry,1000000000"));
+        assert!(lines[2].ends_with(",withdraw,400000000"));
+    }
+}
@@ -0,0 +1,730 @@
+//! Dual-write verification for registry migrations
+//!
+//! While a contract migration is in progress, the old and new registries run side by
+//! side. A `DualWrite` executes each `Mutation` against the primary registry and then
+//! either checks that the shadow registry already reflects it (`DualWriteMode::Verify`)
+//! or appends the mutation to an `IntentLog` so it can be replayed onto the shadow
+//! later (`DualWriteMode::Record`).
+//!
+//! Mutations name canary blobs by domain and package rather than by object ID, since
+//! the same blob has a different ID in each registry.
+
+use super::{
+    delete_canary_blob, derive_canary_address, join_registry, query_canary_blob, query_member,
+    store_blob, update_blob, CanaryBlobInfo, TransactionReceipt,
+};
+use crate::client::SuiClientWithSigner;
+use crate::error::{CanaryError, MigrationError, StateError};
+use crate::progress::{Progress, ProgressReporter};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// A registry taking part in a migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryTarget {
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// The AdminCap object ID, required for blob mutations
+    pub admin_cap_id: Option<ObjectID>,
+}
+
+impl RegistryTarget {
+    /// A registry that only receives member mutations
+    pub fn new(registry_id: ObjectID) -> Self {
+        Self {
+            registry_id,
+            admin_cap_id: None,
+        }
+    }
+
+    /// Set the AdminCap used for blob mutations
+    pub fn with_admin_cap(mut self, admin_cap_id: ObjectID) -> Self {
+        self.admin_cap_id = Some(admin_cap_id);
+        self
+    }
+
+    fn admin_cap(&self) -> Result<ObjectID, MigrationError> {
+        self.admin_cap_id
+            .ok_or(MigrationError::MissingAdminCap(self.registry_id))
+    }
+}
+
+/// A registry mutation that can be applied to any registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mutation {
+    /// `member` joins with `domain`, paying `payment_amount` MIST
+    JoinRegistry {
+        member: SuiAddress,
+        domain: String,
+        payment_amount: u64,
+    },
+    /// Create the canary blob for `domain` and `package_id`
+    StoreBlob {
+        domain: String,
+        package_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+    },
+    /// Replace the blob IDs of the canary blob for `domain` and `package_id`
+    UpdateBlob {
+        domain: String,
+        package_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+    },
+    /// Delete the canary blob for `domain` and `package_id`
+    DeleteBlob {
+        domain: String,
+        package_id: ObjectID,
+    },
+}
+
+impl Mutation {
+    /// Name of the Move function this mutation calls
+    pub fn function(&self) -> &'static str {
+        match self {
+            Mutation::JoinRegistry { .. } => "join_registry",
+            Mutation::StoreBlob { .. } => "store_blob",
+            Mutation::UpdateBlob { .. } => "update_blob",
+            Mutation::DeleteBlob { .. } => "delete_canary_blob",
+        }
+    }
+
+    /// Apply the mutation to a registry
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClientWithSigner`; the member for joins, the admin otherwise
+    /// * `target` - The registry to mutate
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction receipt, or a `MigrationError` if the signer does not
+    /// match, the AdminCap is missing, or the transaction fails.
+    pub async fn apply(
+        &self,
+        client: SuiClientWithSigner,
+        target: &RegistryTarget,
+    ) -> Result<TransactionReceipt, MigrationError> {
+        let receipt = match self {
+            Mutation::JoinRegistry {
+                member,
+                domain,
+                payment_amount,
+            } => {
+                if client.signer != *member {
+                    return Err(MigrationError::SignerMismatch {
+                        expected: *member,
+                        actual: client.signer,
+                    });
+                }
+                join_registry(
+                    client,
+                    target.registry_id,
+                    domain.clone(),
+                    *payment_amount,
+                    false,
+                )
+                .await?
+            }
+            Mutation::StoreBlob {
+                domain,
+                package_id,
+                contract_blob_id,
+                explain_blob_id,
+            } => {
+                store_blob(
+                    client,
+                    target.registry_id,
+                    target.admin_cap()?,
+                    domain.clone(),
+                    *contract_blob_id,
+                    *explain_blob_id,
+                    *package_id,
+                )
+                .await?
+            }
+            Mutation::UpdateBlob {
+                domain,
+                package_id,
+                contract_blob_id,
+                explain_blob_id,
+            } => {
+                let admin_cap_id = target.admin_cap()?;
+                let blob = find_blob(&client.client, target.registry_id, domain, *package_id)
+                    .await?
+                    .ok_or(CanaryError::CanaryBlobNotFound)?;
+                update_blob(
+                    client,
+                    target.registry_id,
+                    admin_cap_id,
+                    blob.id,
+                    *contract_blob_id,
+                    *explain_blob_id,
+                )
+                .await?
+            }
+            Mutation::DeleteBlob { domain, package_id } => {
+                let admin_cap_id = target.admin_cap()?;
+                let blob = find_blob(&client.client, target.registry_id, domain, *package_id)
+                    .await?
+                    .ok_or(CanaryError::CanaryBlobNotFound)?;
+                delete_canary_blob(client, target.registry_id, admin_cap_id, blob.id).await?
+            }
+        };
+        Ok(receipt)
+    }
+
+    /// Check that a registry reflects this mutation
+    ///
+    /// # Returns
+    ///
+    /// Returns every field that differs from what the mutation implies (empty if the
+    /// registry is in the expected state), or a `CanaryError` if the query fails.
+    pub async fn check(
+        &self,
+        client: &SuiClient,
+        registry_id: ObjectID,
+    ) -> Result<Vec<Divergence>, CanaryError> {
+        match self {
+            Mutation::JoinRegistry { member, domain, .. } => {
+                let actual = query_member(client, registry_id, *member)
+                    .await?
+                    .map(|info| info.domain);
+                Ok(compare("member.domain", Some(domain.clone()), actual)
+                    .into_iter()
+                    .collect())
+            }
+            Mutation::StoreBlob {
+                domain,
+                package_id,
+                contract_blob_id,
+                explain_blob_id,
+            }
+            | Mutation::UpdateBlob {
+                domain,
+                package_id,
+                contract_blob_id,
+                explain_blob_id,
+            } => {
+                let blob = find_blob(client, registry_id, domain, *package_id).await?;
+                Ok(blob_divergences(
+                    blob.as_ref(),
+                    *contract_blob_id,
+                    *explain_blob_id,
+                ))
+            }
+            Mutation::DeleteBlob { domain, package_id } => {
+                let blob = find_blob(client, registry_id, domain, *package_id).await?;
+                Ok(compare("blob", None, blob.map(|b| b.id.to_string()))
+                    .into_iter()
+                    .collect())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::JoinRegistry { member, domain, .. } => {
+                write!(f, "join_registry({}, {})", member, domain)
+            }
+            Mutation::StoreBlob {
+                domain, package_id, ..
+            }
+            | Mutation::UpdateBlob {
+                domain, package_id, ..
+            }
+            | Mutation::DeleteBlob { domain, package_id } => {
+                write!(f, "{}({}, {})", self.function(), domain, package_id)
+            }
+        }
+    }
+}
+
+/// A field whose state on a registry differs from what a mutation implies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Divergence {
+    /// The diverging field (e.g. `member.domain`, `blob.contract_blob_id`)
+    pub field: String,
+    /// The expected value (`None` if the object should not exist)
+    pub expected: Option<String>,
+    /// The actual value (`None` if the object does not exist)
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<String>| v.clone().unwrap_or_else(|| "<missing>".to_string());
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.field,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+fn compare(field: &str, expected: Option<String>, actual: Option<String>) -> Option<Divergence> {
+    (expected != actual).then(|| Divergence {
+        field: field.to_string(),
+        expected,
+        actual,
+    })
+}
+
+fn blob_divergences(
+    blob: Option<&CanaryBlobInfo>,
+    contract_blob_id: ObjectID,
+    explain_blob_id: ObjectID,
+) -> Vec<Divergence> {
+    let Some(blob) = blob else {
+        return vec![Divergence {
+            field: "blob".to_string(),
+            expected: Some("present".to_string()),
+            actual: None,
+        }];
+    };
+    [
+        compare(
+            "blob.contract_blob_id",
+            Some(contract_blob_id.to_string()),
+            Some(blob.contract_blob_id.to_string()),
+        ),
+        compare(
+            "blob.explain_blob_id",
+            Some(explain_blob_id.to_string()),
+            Some(blob.explain_blob_id.to_string()),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Look up the canary blob for a domain and package, or `None` if it does not exist
+async fn find_blob(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    domain: &str,
+    package_id: ObjectID,
+) -> Result<Option<CanaryBlobInfo>, CanaryError> {
+    let address =
+        derive_canary_address(client, registry_id, domain.to_string(), package_id).await?;
+    match query_canary_blob(client, ObjectID::from(address)).await {
+        Ok(info) => Ok(Some(info)),
+        Err(CanaryError::CanaryBlobNotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Check a batch of mutations against a registry, e.g. after replaying an intent log
+///
+/// Each mutation is checked against the current state on its own, so one superseded
+/// by a later mutation of the same member or blob will report divergences.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The registry expected to reflect the mutations
+/// * `mutations` - The mutations to check, in order
+/// * `reporter` - Progress reporter (use `&()` to ignore progress)
+///
+/// # Returns
+///
+/// Returns the index and divergences of every mutation the registry does not reflect,
+/// or a `CanaryError` if a query fails.
+pub async fn check_mutations(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    mutations: &[Mutation],
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<(usize, Vec<Divergence>)>, CanaryError> {
+    let progress = Progress::start(reporter, "check mutations", Some(mutations.len() as u64));
+    let mut diverged = Vec::new();
+    for (index, mutation) in mutations.iter().enumerate() {
+        let divergences = mutation.check(client, registry_id).await?;
+        if !divergences.is_empty() {
+            diverged.push((index, divergences));
+        }
+        progress.advance(1, Some(&mutation.to_string()));
+    }
+    progress.finish();
+    Ok(diverged)
+}
+
+/// A mutation applied to the primary registry and awaiting replay onto the shadow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentRecord {
+    /// Position in the log (assigned by the log, starting at 0)
+    pub seq: u64,
+    /// The mutation
+    pub mutation: Mutation,
+    /// The primary transaction that applied it
+    pub primary_digest: TransactionDigest,
+    /// The shadow transaction that replayed it, once replayed
+    pub shadow_digest: Option<TransactionDigest>,
+}
+
+/// Storage for recorded intents
+///
+/// Implementations must keep records in `seq` order so they replay in the order they
+/// were applied to the primary registry.
+#[async_trait]
+pub trait IntentLog: Send + Sync {
+    /// Append a mutation and return its sequence number
+    async fn append(
+        &self,
+        mutation: Mutation,
+        primary_digest: TransactionDigest,
+    ) -> Result<u64, StateError>;
+
+    /// The oldest record that has not been replayed yet
+    async fn next_pending(&self) -> Result<Option<IntentRecord>, StateError>;
+
+    /// Mark a record as replayed by the given shadow transaction
+    async fn mark_replayed(
+        &self,
+        seq: u64,
+        shadow_digest: TransactionDigest,
+    ) -> Result<(), StateError>;
+}
+
+/// In-memory `IntentLog` for single-instance deployments and tests
+#[derive(Default)]
+pub struct MemoryIntentLog {
+    records: Mutex<Vec<IntentRecord>>,
+}
+
+impl MemoryIntentLog {
+    /// Create an empty in-memory intent log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All records, replayed or not, in `seq` order
+    pub fn records(&self) -> Result<Vec<IntentRecord>, StateError> {
+        Ok(self.lock()?.clone())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Vec<IntentRecord>>, StateError> {
+        self.records
+            .lock()
+            .map_err(|e| StateError::Backend(format!("Intent log lock poisoned: {}", e)))
+    }
+}
+
+#[async_trait]
+impl IntentLog for MemoryIntentLog {
+    async fn append(
+        &self,
+        mutation: Mutation,
+        primary_digest: TransactionDigest,
+    ) -> Result<u64, StateError> {
+        let mut records = self.lock()?;
+        let seq = records.len() as u64;
+        records.push(IntentRecord {
+            seq,
+            mutation,
+            primary_digest,
+            shadow_digest: None,
+        });
+        Ok(seq)
+    }
+
+    async fn next_pending(&self) -> Result<Option<IntentRecord>, StateError> {
+        Ok(self
+            .lock()?
+            .iter()
+            .find(|record| record.shadow_digest.is_none())
+            .cloned())
+    }
+
+    async fn mark_replayed(
+        &self,
+        seq: u64,
+        shadow_digest: TransactionDigest,
+    ) -> Result<(), StateError> {
+        let mut records = self.lock()?;
+        let record = records
+            .get_mut(seq as usize)
+            .ok_or_else(|| StateError::Backend(format!("No intent with seq {}", seq)))?;
+        record.shadow_digest = Some(shadow_digest);
+        Ok(())
+    }
+}
+
+/// What a `DualWrite` does after the primary transaction succeeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DualWriteMode {
+    /// Check that the shadow registry already reflects the mutation
+    Verify,
+    /// Record the mutation in the intent log for later replay
+    Record,
+}
+
+/// Result of a dual write
+#[derive(Debug, Clone)]
+pub struct DualWriteOutcome {
+    /// The primary transaction
+    pub primary_digest: TransactionDigest,
+    /// Shadow fields that differ from the mutation (always empty in `Record` mode)
+    pub divergences: Vec<Divergence>,
+    /// Intent log sequence number (only in `Record` mode)
+    pub recorded: Option<u64>,
+}
+
+impl DualWriteOutcome {
+    /// Whether the shadow registry diverged from the primary
+    pub fn diverged(&self) -> bool {
+        !self.divergences.is_empty()
+    }
+}
+
+/// Result of replaying one recorded intent onto the shadow registry
+#[derive(Debug, Clone)]
+pub struct ReplayOutcome {
+    /// The replayed record
+    pub record: IntentRecord,
+    /// The shadow transaction
+    pub shadow_digest: TransactionDigest,
+    /// Shadow fields that still differ after the replay
+    pub divergences: Vec<Divergence>,
+}
+
+/// A primary/shadow registry pair written in parallel during a migration
+#[derive(Debug, Clone, Copy)]
+pub struct DualWrite {
+    /// The registry mutations are executed against
+    pub primary: RegistryTarget,
+    /// The registry expected to mirror the primary
+    pub shadow: RegistryTarget,
+    /// What to do with the shadow after each primary write
+    pub mode: DualWriteMode,
+}
+
+impl DualWrite {
+    /// Create a dual-write pair
+    pub fn new(primary: RegistryTarget, shadow: RegistryTarget, mode: DualWriteMode) -> Self {
+        Self {
+            primary,
+            shadow,
+            mode,
+        }
+    }
+
+    /// Execute a mutation against the primary registry, then verify or record it
+    ///
+    /// Divergences are reported in the outcome rather than as errors: the primary write
+    /// has already happened, and the caller decides whether a diverging shadow should
+    /// halt the migration.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClientWithSigner`; the member for joins, the admin otherwise
+    /// * `mutation` - The mutation to apply
+    /// * `log` - Where intents are recorded in `Record` mode
+    ///
+    /// # Returns
+    ///
+    /// Returns a `DualWriteOutcome`, or a `MigrationError` if the primary write, the
+    /// shadow check, or the log append fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::migration::{
+    ///     DualWrite, DualWriteMode, MemoryIntentLog, Mutation, RegistryTarget,
+    /// };
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+    /// let dual = DualWrite::new(
+    ///     RegistryTarget::new(ObjectID::from_hex_literal("0x123...")?),
+    ///     RegistryTarget::new(ObjectID::from_hex_literal("0x456...")?),
+    ///     DualWriteMode::Verify,
+    /// );
+    /// let mutation = Mutation::JoinRegistry {
+    ///     member: client.signer,
+    ///     domain: "example.com".to_string(),
+    ///     payment_amount: 1_000_000_000,
+    /// };
+    /// let outcome = dual.execute(client, mutation, &MemoryIntentLog::new()).await?;
+    /// for divergence in &outcome.divergences {
+    ///     eprintln!("Shadow diverged: {}", divergence);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute(
+        &self,
+        client: SuiClientWithSigner,
+        mutation: Mutation,
+        log: &dyn IntentLog,
+    ) -> Result<DualWriteOutcome, MigrationError> {
+        let reader = client.client.clone();
+        let response = mutation.apply(client, &self.primary).await?;
+
+        match self.mode {
+            DualWriteMode::Verify => {
+                let divergences = mutation.check(&reader, self.shadow.registry_id).await?;
+                Ok(DualWriteOutcome {
+                    primary_digest: response.digest,
+                    divergences,
+                    recorded: None,
+                })
+            }
+            DualWriteMode::Record => {
+                let seq = log.append(mutation, response.digest).await?;
+                Ok(DualWriteOutcome {
+                    primary_digest: response.digest,
+                    divergences: Vec::new(),
+                    recorded: Some(seq),
+                })
+            }
+        }
+    }
+
+    /// Replay the oldest pending intent onto the shadow registry and verify it
+    ///
+    /// Each call replays at most one intent, since a `SuiClientWithSigner` is consumed
+    /// per transaction and joins must be signed by the joining member.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `ReplayOutcome`, `None` if the log has no pending intents, or a
+    /// `MigrationError` if the replay fails (the intent then stays pending).
+    pub async fn replay_next(
+        &self,
+        client: SuiClientWithSigner,
+        log: &dyn IntentLog,
+    ) -> Result<Option<ReplayOutcome>, MigrationError> {
+        let Some(record) = log.next_pending().await? else {
+            return Ok(None);
+        };
+
+        let reader = client.client.clone();
+        let response = record.mutation.apply(client, &self.shadow).await?;
+        log.mark_replayed(record.seq, response.digest).await?;
+        let divergences = record
+            .mutation
+            .check(&reader, self.shadow.registry_id)
+            .await?;
+
+        Ok(Some(ReplayOutcome {
+            record,
+            shadow_digest: response.digest,
+            divergences,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_id(hex: &str) -> ObjectID {
+        ObjectID::from_hex_literal(hex).unwrap()
+    }
+
+    fn sample_blob() -> CanaryBlobInfo {
+        CanaryBlobInfo {
+            id: object_id("0x10"),
+            contract_blob_id: object_id("0x2"),
+            explain_blob_id: object_id("0x3"),
+            package_id: object_id("0x1"),
+            domain: "example.com".to_string(),
+            uploaded_at: 0,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn test_blob_divergences() {
+        let blob = sample_blob();
+        assert!(blob_divergences(Some(&blob), object_id("0x2"), object_id("0x3")).is_empty());
+
+        let divergences = blob_divergences(Some(&blob), object_id("0x2"), object_id("0x9"));
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].field, "blob.explain_blob_id");
+
+        let missing = blob_divergences(None, object_id("0x2"), object_id("0x3"));
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].actual, None);
+    }
+
+    #[test]
+    fn test_divergence_display() {
+        let divergence = compare("member.domain", Some("a.com".to_string()), None).unwrap();
+        assert_eq!(
+            divergence.to_string(),
+            "member.domain: expected a.com, found <missing>"
+        );
+        assert!(compare("x", Some("a".to_string()), Some("a".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_missing_admin_cap() {
+        let target = RegistryTarget::new(object_id("0x123"));
+        assert!(matches!(
+            target.admin_cap(),
+            Err(MigrationError::MissingAdminCap(id)) if id == object_id("0x123")
+        ));
+        assert!(target.with_admin_cap(object_id("0x1")).admin_cap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_intent_log_replay_order() {
+        let log = MemoryIntentLog::new();
+        let delete = Mutation::DeleteBlob {
+            domain: "example.com".to_string(),
+            package_id: object_id("0x1"),
+        };
+        let join = Mutation::JoinRegistry {
+            member: SuiAddress::ZERO,
+            domain: "example.com".to_string(),
+            payment_amount: 1,
+        };
+        assert_eq!(
+            log.append(join.clone(), TransactionDigest::default())
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            log.append(delete.clone(), TransactionDigest::default())
+                .await
+                .unwrap(),
+            1
+        );
+
+        let next = log.next_pending().await.unwrap().unwrap();
+        assert_eq!(next.mutation, join);
+        log.mark_replayed(next.seq, TransactionDigest::default())
+            .await
+            .unwrap();
+
+        let next = log.next_pending().await.unwrap().unwrap();
+        assert_eq!(next.mutation, delete);
+        log.mark_replayed(next.seq, TransactionDigest::default())
+            .await
+            .unwrap();
+
+        assert!(log.next_pending().await.unwrap().is_none());
+        assert!(log
+            .mark_replayed(5, TransactionDigest::default())
+            .await
+            .is_err());
+        assert_eq!(log.records().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,440 @@
+//! Notarization receipts
+//!
+//! `notarize` captures the on-chain state of a domain's canary blob, the SHA-256 of
+//! the referenced blob contents, and the checkpoint the state was read at, and signs
+//! the bundle with the notary's key as a Sui personal message. Third parties can then
+//! check the receipt with `verify_receipt` entirely offline: the signature proves who
+//! issued it, and `verify_receipt_contents` proves that blob contents they hold are
+//! the ones the receipt refers to.
+
+use super::CanaryBlobInfo;
+use crate::compat::{self, Artifact};
+use crate::error::NotaryError;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::crypto::{Signature, SuiSignature, ToFromBytes};
+use sui_sdk::types::messages_checkpoint::CheckpointDigest;
+
+// Issuing receipts needs a signing key; verifying them does not
+#[cfg(feature = "signing")]
+use {
+    super::{derive_canary_address, query_canary_blob},
+    crate::client::SuiClientWithSigner,
+    crate::compat::SDK_VERSION,
+    crate::error::CanaryError,
+    sui_keys::keystore::AccountKeystore,
+    sui_sdk::rpc_types::CheckpointId,
+    sui_sdk::SuiClient,
+};
+
+/// SHA-256 of a blob's content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobHash {
+    /// The blob object ID referenced by the canary blob
+    pub blob_id: ObjectID,
+    /// Hex-encoded SHA-256 of the blob content
+    pub sha256: String,
+}
+
+impl BlobHash {
+    /// Hash `content` as the content of `blob_id`
+    pub fn of(blob_id: ObjectID, content: &[u8]) -> Self {
+        Self {
+            blob_id,
+            sha256: sha256_hex(content),
+        }
+    }
+}
+
+/// The checkpoint a receipt's on-chain state was read at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointAnchor {
+    /// Checkpoint sequence number
+    pub sequence_number: u64,
+    /// Checkpoint digest
+    pub digest: CheckpointDigest,
+    /// Checkpoint timestamp (in milliseconds)
+    pub timestamp_ms: u64,
+}
+
+/// Outcome of the checks performed when the receipt was issued
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationResult {
+    /// The canary blob is registered for the notarized domain
+    pub domain_matches: bool,
+    /// The canary blob describes the expected package
+    pub package_matches: bool,
+    /// Age of the canary blob at the anchor checkpoint (in milliseconds)
+    pub age_ms: u64,
+}
+
+impl VerificationResult {
+    /// Whether all checks passed
+    pub fn passed(&self) -> bool {
+        self.domain_matches && self.package_matches
+    }
+}
+
+/// The signed contents of a receipt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptBody {
+    /// Schema version of this receipt (`NotarizedReceipt::SCHEMA_VERSION`)
+    pub schema_version: u32,
+    /// Version of the SDK that issued the receipt
+    pub sdk_version: String,
+    /// The notarized domain
+    pub domain: String,
+    /// The canary blob as read at the anchor checkpoint
+    pub blob: CanaryBlobInfo,
+    /// Hashes of the contract and explain blob contents
+    pub blob_hashes: Vec<BlobHash>,
+    /// Where in chain history the state was read
+    pub anchor: CheckpointAnchor,
+    /// Checks performed when issuing the receipt
+    pub verification: VerificationResult,
+}
+
+/// A self-contained, signed notarization receipt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotarizedReceipt {
+    /// The signed contents
+    pub body: ReceiptBody,
+    /// Address of the notary that signed the receipt
+    pub notary: SuiAddress,
+    /// Base64 Sui signature over the JSON-encoded body, as a personal message
+    pub signature: String,
+}
+
+/// Blob contents to hash into a receipt, as fetched from storage by the caller
+#[derive(Debug, Clone, Copy)]
+pub struct BlobContents<'a> {
+    /// Content of the contract blob
+    pub contract: &'a [u8],
+    /// Content of the explain blob
+    pub explain: &'a [u8],
+}
+
+/// Issue a signed receipt for a domain's canary blob
+///
+/// # Arguments
+///
+/// * `notary` - A `SuiClientWithSigner` whose key signs the receipt
+/// * `registry_id` - The Registry object ID
+/// * `domain` - The domain to notarize
+/// * `package_id` - The package the canary is expected to describe
+/// * `contents` - Contents of the contract and explain blobs
+///
+/// # Returns
+///
+/// Returns the `NotarizedReceipt`, or a `NotaryError` if the blob cannot be read or
+/// the receipt cannot be signed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::notary::{notarize, BlobContents};
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let notary = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let package_id = ObjectID::from_hex_literal("0x456...")?;
+/// let contract = std::fs::read("contract.json")?;
+/// let explain = std::fs::read("explain.md")?;
+/// let contents = BlobContents { contract: &contract, explain: &explain };
+/// let receipt = notarize(&notary, registry_id, "example.com", package_id, contents).await?;
+/// receipt.verify()?;
+/// std::fs::write("receipt.json", serde_json::to_vec_pretty(&receipt)?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "signing")]
+pub async fn notarize(
+    notary: &SuiClientWithSigner,
+    registry_id: ObjectID,
+    domain: &str,
+    package_id: ObjectID,
+    contents: BlobContents<'_>,
+) -> Result<NotarizedReceipt, NotaryError> {
+    let client = &notary.client;
+
+    // Read the anchor first so the blob state is at least as new as the anchor
+    let anchor = latest_checkpoint(client).await?;
+
+    let canary_address =
+        derive_canary_address(client, registry_id, domain.to_string(), package_id).await?;
+    let blob = query_canary_blob(client, ObjectID::from(canary_address)).await?;
+
+    let verification = VerificationResult {
+        domain_matches: blob.domain == domain,
+        package_matches: blob.package_id == package_id,
+        age_ms: anchor.timestamp_ms.saturating_sub(blob.uploaded_at),
+    };
+    let blob_hashes = vec![
+        BlobHash::of(blob.contract_blob_id, contents.contract),
+        BlobHash::of(blob.explain_blob_id, contents.explain),
+    ];
+
+    let body = ReceiptBody {
+        schema_version: NotarizedReceipt::SCHEMA_VERSION,
+        sdk_version: SDK_VERSION.to_string(),
+        domain: domain.to_string(),
+        blob,
+        blob_hashes,
+        anchor,
+        verification,
+    };
+
+    let message = signing_message(&body)?;
+    let signature = notary
+        .keystore
+        .sign_secure(&notary.signer, &message, Intent::personal_message())
+        .await
+        .map_err(|e| NotaryError::Signing(e.to_string()))?;
+
+    Ok(NotarizedReceipt {
+        body,
+        notary: notary.signer,
+        signature: base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
+    })
+}
+
+/// Load a receipt bundle and check its signature and internal consistency, without
+/// RPC access
+///
+/// This proves the receipt was issued by `receipt.notary` and not modified since.
+/// Whether to trust the notary is up to the caller.
+///
+/// # Arguments
+///
+/// * `bundle` - The receipt as JSON, as written by the notary
+///
+/// # Returns
+///
+/// Returns the verified `NotarizedReceipt`, or a `NotaryError` if the bundle cannot be
+/// loaded (e.g. an unsupported schema version) or does not verify.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::notary::verify_receipt;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let receipt = verify_receipt(&std::fs::read_to_string("receipt.json")?)?;
+/// println!("{} notarized by {}", receipt.body.domain, receipt.notary);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_receipt(bundle: &str) -> Result<NotarizedReceipt, NotaryError> {
+    let receipt: NotarizedReceipt = compat::load(bundle)?;
+    receipt.verify()?;
+    Ok(receipt)
+}
+
+impl NotarizedReceipt {
+    /// Check the receipt's signature and internal consistency (see `verify_receipt`)
+    pub fn verify(&self) -> Result<(), NotaryError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&self.signature)
+            .map_err(|e| NotaryError::InvalidSignature(format!("Invalid base64: {}", e)))?;
+        let signature = Signature::from_bytes(&bytes)
+            .map_err(|e| NotaryError::InvalidSignature(e.to_string()))?;
+
+        let message = IntentMessage::new(Intent::personal_message(), signing_message(&self.body)?);
+        signature
+            .verify_secure(&message, self.notary, signature.scheme())
+            .map_err(|e| NotaryError::InvalidSignature(e.to_string()))?;
+
+        for blob_id in [
+            self.body.blob.contract_blob_id,
+            self.body.blob.explain_blob_id,
+        ] {
+            if !self.body.blob_hashes.iter().any(|h| h.blob_id == blob_id) {
+                return Err(NotaryError::MissingHash(blob_id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that blob contents held by a third party match the receipt's hashes
+pub fn verify_receipt_contents(
+    receipt: &NotarizedReceipt,
+    contents: BlobContents<'_>,
+) -> Result<(), NotaryError> {
+    let blob = &receipt.body.blob;
+    for (blob_id, content) in [
+        (blob.contract_blob_id, contents.contract),
+        (blob.explain_blob_id, contents.explain),
+    ] {
+        let recorded = receipt
+            .body
+            .blob_hashes
+            .iter()
+            .find(|h| h.blob_id == blob_id)
+            .ok_or(NotaryError::MissingHash(blob_id))?;
+        if recorded.sha256 != sha256_hex(content) {
+            return Err(NotaryError::HashMismatch(blob_id));
+        }
+    }
+    Ok(())
+}
+
+/// The personal message a receipt body is signed as
+fn signing_message(body: &ReceiptBody) -> Result<PersonalMessage, NotaryError> {
+    let message = serde_json::to_vec(body).map_err(|e| NotaryError::Signing(e.to_string()))?;
+    Ok(PersonalMessage { message })
+}
+
+#[cfg(feature = "signing")]
+async fn latest_checkpoint(client: &SuiClient) -> Result<CheckpointAnchor, CanaryError> {
+    let sequence_number = client
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get latest checkpoint: {}", e)))?;
+    let checkpoint = client
+        .read_api()
+        .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get checkpoint: {}", e)))?;
+
+    Ok(CheckpointAnchor {
+        sequence_number: checkpoint.sequence_number,
+        digest: checkpoint.digest,
+        timestamp_ms: checkpoint.timestamp_ms,
+    })
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::SDK_VERSION;
+    use crate::error::CompatError;
+    use sui_sdk::types::crypto::{get_key_pair, AccountKeyPair, SuiKeyPair};
+
+    fn object_id(hex: &str) -> ObjectID {
+        ObjectID::from_hex_literal(hex).unwrap()
+    }
+
+    fn sample_body() -> ReceiptBody {
+        ReceiptBody {
+            schema_version: NotarizedReceipt::SCHEMA_VERSION,
+            sdk_version: SDK_VERSION.to_string(),
+            domain: "example.com".to_string(),
+            blob: CanaryBlobInfo {
+                id: object_id("0x10"),
+                contract_blob_id: object_id("0x2"),
+                explain_blob_id: object_id("0x3"),
+                package_id: object_id("0x1"),
+                domain: "example.com".to_string(),
+                uploaded_at: 1_000,
+                uploaded_by_admin: SuiAddress::ZERO,
+                raw: None,
+            },
+            blob_hashes: vec![
+                BlobHash::of(object_id("0x2"), b"contract"),
+                BlobHash::of(object_id("0x3"), b"explain"),
+            ],
+            anchor: CheckpointAnchor {
+                sequence_number: 42,
+                digest: CheckpointDigest::default(),
+                timestamp_ms: 5_000,
+            },
+            verification: VerificationResult {
+                domain_matches: true,
+                package_matches: true,
+                age_ms: 4_000,
+            },
+        }
+    }
+
+    /// Sign a body the way `notarize` does, with a fresh key
+    fn signed_receipt(body: ReceiptBody) -> NotarizedReceipt {
+        let (address, keypair): (SuiAddress, AccountKeyPair) = get_key_pair();
+        let keypair = SuiKeyPair::Ed25519(keypair);
+        let message =
+            IntentMessage::new(Intent::personal_message(), signing_message(&body).unwrap());
+        let signature = Signature::new_secure(&message, &keypair);
+
+        NotarizedReceipt {
+            body,
+            notary: address,
+            signature: base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
+        }
+    }
+
+    #[test]
+    fn test_verify_receipt() {
+        let receipt = signed_receipt(sample_body());
+        receipt.verify().unwrap();
+
+        // Survives a JSON round trip
+        let json = serde_json::to_string(&receipt).unwrap();
+        let decoded = verify_receipt(&json).unwrap();
+        assert_eq!(decoded.notary, receipt.notary);
+    }
+
+    #[test]
+    fn test_verify_receipt_tampered() {
+        let mut receipt = signed_receipt(sample_body());
+        receipt.body.verification.package_matches = false;
+        assert!(matches!(
+            receipt.verify(),
+            Err(NotaryError::InvalidSignature(_))
+        ));
+
+        let mut receipt = signed_receipt(sample_body());
+        receipt.notary = SuiAddress::ZERO;
+        assert!(receipt.verify().is_err());
+    }
+
+    #[test]
+    fn test_verify_receipt_unsupported_schema() {
+        let mut body = sample_body();
+        body.schema_version = NotarizedReceipt::SCHEMA_VERSION + 1;
+        let json = serde_json::to_string(&signed_receipt(body)).unwrap();
+        assert!(matches!(
+            verify_receipt(&json),
+            Err(NotaryError::Compat(CompatError::UnsupportedSchema { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_verify_receipt_contents() {
+        let receipt = signed_receipt(sample_body());
+        let good = BlobContents {
+            contract: b"contract",
+            explain: b"explain",
+        };
+        verify_receipt_contents(&receipt, good).unwrap();
+
+        let bad = BlobContents {
+            contract: b"contract",
+            explain: b"tampered",
+        };
+        assert!(matches!(
+            verify_receipt_contents(&receipt, bad),
+            Err(NotaryError::HashMismatch(id)) if id == object_id("0x3")
+        ));
+    }
+
+    #[test]
+    fn test_verification_result_passed() {
+        let mut result = sample_body().verification;
+        assert!(result.passed());
+        result.domain_matches = false;
+        assert!(!result.passed());
+    }
+}
@@ -0,0 +1,260 @@
+//! BCS decoding of canary contract objects
+//!
+//! The structs in this module mirror the Move layouts of `member_registry::Registry`,
+//! `member_registry::MemberInfo` and `pkg_storage::CanaryBlob` field for field, so
+//! objects fetched with `show_bcs` can be decoded directly instead of calling view
+//! functions through dev_inspect. BCS is positional: field order here must match the
+//! Move declaration order exactly.
+
+use super::{CanaryBlobInfo, MemberInfo, MemberInfoWithAddress, RawObject, RegistryInfo};
+use crate::error::CanaryError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::SuiObjectData;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+/// Mirror of `sui::table::Table<K, V>` (entries live in dynamic fields)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveTable {
+    /// The table's UID, parent of its dynamic fields
+    pub id: ObjectID,
+    /// Number of entries
+    pub size: u64,
+}
+
+/// Mirror of `sui::balance::Balance<T>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveBalance {
+    /// The balance in the coin's smallest unit
+    pub value: u64,
+}
+
+/// Mirror of `member_registry::Registry`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryObject {
+    /// The Registry object ID
+    pub id: ObjectID,
+    /// Member address -> `MemberInfo`
+    pub members: MoveTable,
+    /// Join index -> member address
+    pub member_addresses: MoveTable,
+    /// Total number of members
+    pub member_count: u64,
+    /// Fee to join, in MIST
+    pub fee: u64,
+    /// Collected fees
+    pub balance: MoveBalance,
+    /// The registry admin
+    pub admin: SuiAddress,
+}
+
+/// Mirror of `member_registry::MemberInfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberInfoObject {
+    /// The member's domain
+    pub domain: String,
+    /// When the member joined (Unix milliseconds)
+    pub joined_at: u64,
+}
+
+/// Mirror of a `members` table entry (`dynamic_field::Field<address, MemberInfo>`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberFieldObject {
+    /// The dynamic field object ID
+    pub id: ObjectID,
+    /// The member address (the table key)
+    pub name: SuiAddress,
+    /// The member's info
+    pub value: MemberInfoObject,
+}
+
+/// Mirror of `pkg_storage::CanaryBlob`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanaryBlobObject {
+    /// The CanaryBlob object ID
+    pub id: ObjectID,
+    /// Walrus blob object holding the contract
+    pub contract_blob_id: ObjectID,
+    /// Walrus blob object holding the explanation
+    pub explain_blob_id: ObjectID,
+    /// The attested package
+    pub package_id: ObjectID,
+    /// The domain the canary is for
+    pub domain: String,
+    /// When the blob was uploaded (Unix milliseconds)
+    pub uploaded_at: u64,
+    /// The admin that uploaded the blob
+    pub uploaded_by_admin: SuiAddress,
+}
+
+/// Decode a Move object from its BCS bytes
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::parsing::{decode, RegistryObject};
+/// use canary_sdk::canary::query_registry;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let info = query_registry(&client, ObjectID::from_hex_literal("0x123...")?).await?;
+/// if let Some(bytes) = info.raw_bcs() {
+///     let registry: RegistryObject = decode(bytes)?;
+///     println!("Collected fees: {} MIST", registry.balance.value);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CanaryError> {
+    bcs::from_bytes(bytes)
+        .map_err(|e| CanaryError::Registry(format!("Failed to decode object BCS: {}", e)))
+}
+
+/// Decode a fetched object (requires `show_bcs`), returning it with its raw form
+pub fn decode_object<T: DeserializeOwned>(
+    data: &SuiObjectData,
+) -> Result<(T, RawObject), CanaryError> {
+    let raw = super::raw_object(data).ok_or_else(|| {
+        CanaryError::Registry(format!("Object {} was fetched without BCS", data.object_id))
+    })?;
+    Ok((decode(&raw.bcs)?, raw))
+}
+
+impl RegistryObject {
+    /// Convert into the SDK's `RegistryInfo`
+    pub fn into_info(self, raw: Option<RawObject>) -> RegistryInfo {
+        RegistryInfo {
+            id: self.id,
+            fee: self.fee,
+            member_count: self.member_count,
+            admin: self.admin,
+            raw,
+        }
+    }
+}
+
+impl MemberFieldObject {
+    /// Convert into the SDK's `MemberInfoWithAddress`
+    pub fn into_info(self, raw: Option<RawObject>) -> MemberInfoWithAddress {
+        MemberInfoWithAddress {
+            member: self.name,
+            domain: self.value.domain,
+            joined_at: self.value.joined_at,
+            raw,
+        }
+    }
+}
+
+impl MemberInfoObject {
+    /// Convert into the SDK's `MemberInfo`
+    pub fn into_info(self, raw: Option<RawObject>) -> MemberInfo {
+        MemberInfo {
+            domain: self.domain,
+            joined_at: self.joined_at,
+            raw,
+        }
+    }
+}
+
+impl CanaryBlobObject {
+    /// Convert into the SDK's `CanaryBlobInfo`
+    pub fn into_info(self, raw: Option<RawObject>) -> CanaryBlobInfo {
+        CanaryBlobInfo {
+            id: self.id,
+            contract_blob_id: self.contract_blob_id,
+            explain_blob_id: self.explain_blob_id,
+            package_id: self.package_id,
+            domain: self.domain,
+            uploaded_at: self.uploaded_at,
+            uploaded_by_admin: self.uploaded_by_admin,
+            raw,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_id(hex: &str) -> ObjectID {
+        ObjectID::from_hex_literal(hex).unwrap()
+    }
+
+    #[test]
+    fn test_member_info_layout() {
+        // String: ULEB128 length + bytes, then u64 little-endian
+        let mut bytes = vec![5];
+        bytes.extend_from_slice(b"a.com");
+        bytes.extend_from_slice(&7u64.to_le_bytes());
+
+        let member: MemberInfoObject = decode(&bytes).unwrap();
+        assert_eq!(member.domain, "a.com");
+        assert_eq!(member.joined_at, 7);
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let registry = RegistryObject {
+            id: object_id("0x123"),
+            members: MoveTable {
+                id: object_id("0x1"),
+                size: 2,
+            },
+            member_addresses: MoveTable {
+                id: object_id("0x2"),
+                size: 2,
+            },
+            member_count: 2,
+            fee: 1_000_000_000,
+            balance: MoveBalance {
+                value: 2_000_000_000,
+            },
+            admin: SuiAddress::ZERO,
+        };
+        let bytes = bcs::to_bytes(&registry).unwrap();
+        // Seven fields: 32 + (32 + 8) * 2 + 8 + 8 + 8 + 32
+        assert_eq!(bytes.len(), 168);
+
+        let decoded: RegistryObject = decode(&bytes).unwrap();
+        assert_eq!(decoded, registry);
+
+        let info = decoded.into_info(None);
+        assert_eq!(info.fee, 1_000_000_000);
+        assert_eq!(info.member_count, 2);
+    }
+
+    #[test]
+    fn test_member_field_into_info() {
+        let field = MemberFieldObject {
+            id: object_id("0x9"),
+            name: SuiAddress::ZERO,
+            value: MemberInfoObject {
+                domain: "example.com".to_string(),
+                joined_at: 42,
+            },
+        };
+        let decoded: MemberFieldObject = decode(&bcs::to_bytes(&field).unwrap()).unwrap();
+        let info = decoded.into_info(None);
+        assert_eq!(info.member, SuiAddress::ZERO);
+        assert_eq!(info.domain, "example.com");
+        assert_eq!(info.joined_at, 42);
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let blob = CanaryBlobObject {
+            id: object_id("0x10"),
+            contract_blob_id: object_id("0x2"),
+            explain_blob_id: object_id("0x3"),
+            package_id: object_id("0x1"),
+            domain: "example.com".to_string(),
+            uploaded_at: 0,
+            uploaded_by_admin: SuiAddress::ZERO,
+        };
+        let bytes = bcs::to_bytes(&blob).unwrap();
+        assert!(decode::<CanaryBlobObject>(&bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(decode::<CanaryBlobObject>(&bytes).unwrap(), blob);
+    }
+}
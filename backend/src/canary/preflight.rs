@@ -0,0 +1,225 @@
+//! Ownership checks run before building a transaction
+//!
+//! Passing an object the signer doesn't own, or an AdminCap of another registry, only
+//! fails once the transaction reaches a validator, with an error that names neither
+//! the object nor the owner. Admin functions check ownership up front instead, so the
+//! caller gets `CanaryError::NotAdmin` before any gas is spent.
+
+use super::{json_string, object_fields};
+use crate::error::CanaryError;
+use sui_sdk::rpc_types::{SuiObjectData, SuiObjectDataOptions};
+use sui_sdk::types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_sdk::types::object::Owner;
+use sui_sdk::SuiClient;
+
+/// Check that an address owns an object
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `object_id` - The object to check
+/// * `owner` - The address that must own it
+///
+/// # Returns
+///
+/// Returns the object (with owner and content), `CanaryError::NotOwner` if it is
+/// shared, immutable, or owned by another address or object, or a `CanaryError` if
+/// it does not exist.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::assert_owned_by;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let coin_id = ObjectID::from_hex_literal("0x123...")?;
+/// let owner: SuiAddress = "0x456...".parse()?;
+/// assert_owned_by(&client, coin_id, owner).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn assert_owned_by(
+    client: &SuiClient,
+    object_id: ObjectID,
+    owner: SuiAddress,
+) -> Result<SuiObjectData, CanaryError> {
+    let object = client
+        .read_api()
+        .get_object_with_options(
+            object_id,
+            SuiObjectDataOptions::new().with_owner().with_content(),
+        )
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get object {}: {}", object_id, e)))?
+        .into_object()
+        .map_err(|_| CanaryError::Registry(format!("Object {} not found", object_id)))?;
+
+    check_owner(object.owner.as_ref(), object_id, owner)?;
+    Ok(object)
+}
+
+/// Check that a signer holds an AdminCap, and find the registry it administers
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `signer` - The address that will sign the admin transaction
+///
+/// # Returns
+///
+/// Returns the AdminCap's object reference and its registry ID,
+/// `CanaryError::NotAdmin` if `signer` does not own the cap, or a `CanaryError` if the
+/// cap does not exist or is not an AdminCap.
+pub async fn validate_admin_cap(
+    client: &SuiClient,
+    admin_cap_id: ObjectID,
+    signer: SuiAddress,
+) -> Result<(ObjectRef, ObjectID), CanaryError> {
+    let admin_cap = assert_owned_by(client, admin_cap_id, signer)
+        .await
+        .map_err(not_owner_as_not_admin)?;
+    let admin_cap_ref = admin_cap.object_ref();
+
+    let registry_id = parse_cap_registry(&json_string(&object_fields(admin_cap)?, "registry_id")?)?;
+    Ok((admin_cap_ref, registry_id))
+}
+
+/// Check that a signer holds the AdminCap of a specific registry
+///
+/// # Returns
+///
+/// Returns the AdminCap's object reference, or `CanaryError::NotAdmin` if `signer`
+/// does not own the cap or the cap belongs to another registry.
+pub async fn validate_admin_cap_for(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    signer: SuiAddress,
+) -> Result<ObjectRef, CanaryError> {
+    let (admin_cap_ref, cap_registry) = validate_admin_cap(client, admin_cap_id, signer).await?;
+    check_cap_registry(cap_registry, registry_id)?;
+    Ok(admin_cap_ref)
+}
+
+/// Check that `owner` is the address `expected`
+fn check_owner(
+    owner: Option<&Owner>,
+    object_id: ObjectID,
+    expected: SuiAddress,
+) -> Result<(), CanaryError> {
+    match owner {
+        Some(Owner::AddressOwner(address)) if *address == expected => Ok(()),
+        _ => Err(CanaryError::NotOwner {
+            object_id,
+            owner: expected,
+        }),
+    }
+}
+
+/// A signer not owning an AdminCap is not the admin
+fn not_owner_as_not_admin(error: CanaryError) -> CanaryError {
+    match error {
+        CanaryError::NotOwner { .. } => CanaryError::NotAdmin,
+        e => e,
+    }
+}
+
+/// Parse the `registry_id` field of an AdminCap
+fn parse_cap_registry(registry_id: &str) -> Result<ObjectID, CanaryError> {
+    ObjectID::from_hex_literal(registry_id).map_err(|e| {
+        CanaryError::Registry(format!(
+            "Invalid AdminCap registry_id {}: {}",
+            registry_id, e
+        ))
+    })
+}
+
+/// Check that an AdminCap for `cap_registry` administers `registry_id`
+fn check_cap_registry(cap_registry: ObjectID, registry_id: ObjectID) -> Result<(), CanaryError> {
+    if cap_registry == registry_id {
+        Ok(())
+    } else {
+        Err(CanaryError::NotAdmin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::SequenceNumber;
+
+    #[test]
+    fn test_check_owner() {
+        let object_id = ObjectID::from_single_byte(1);
+        let signer = SuiAddress::from(ObjectID::from_single_byte(2));
+        let other = SuiAddress::from(ObjectID::from_single_byte(3));
+
+        assert!(check_owner(Some(&Owner::AddressOwner(signer)), object_id, signer).is_ok());
+
+        let rejected = [
+            Some(Owner::AddressOwner(other)),
+            Some(Owner::ObjectOwner(signer)),
+            Some(Owner::Shared {
+                initial_shared_version: SequenceNumber::from(1),
+            }),
+            Some(Owner::Immutable),
+            None,
+        ];
+        for case in &rejected {
+            match check_owner(case.as_ref(), object_id, signer) {
+                Err(CanaryError::NotOwner {
+                    object_id: id,
+                    owner,
+                }) => {
+                    assert_eq!(id, object_id);
+                    assert_eq!(owner, signer);
+                }
+                result => panic!("Expected NotOwner, got {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_owner_as_not_admin() {
+        let not_owner = CanaryError::NotOwner {
+            object_id: ObjectID::from_single_byte(1),
+            owner: SuiAddress::from(ObjectID::from_single_byte(2)),
+        };
+        assert!(matches!(
+            not_owner_as_not_admin(not_owner),
+            CanaryError::NotAdmin
+        ));
+        // Other failures, e.g. a missing cap, are kept
+        assert!(matches!(
+            not_owner_as_not_admin(CanaryError::Registry("not found".to_string())),
+            CanaryError::Registry(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_cap_registry() {
+        let registry_id = ObjectID::from_single_byte(7);
+        assert_eq!(
+            parse_cap_registry(&registry_id.to_string()).unwrap(),
+            registry_id
+        );
+        assert!(matches!(
+            parse_cap_registry("not-an-id"),
+            Err(CanaryError::Registry(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_cap_registry() {
+        let registry_id = ObjectID::from_single_byte(7);
+        assert!(check_cap_registry(registry_id, registry_id).is_ok());
+        assert!(matches!(
+            check_cap_registry(ObjectID::from_single_byte(8), registry_id),
+            Err(CanaryError::NotAdmin)
+        ));
+    }
+}
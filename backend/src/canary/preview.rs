@@ -0,0 +1,264 @@
+//! Previews of canary blob updates
+//!
+//! `update_blob` overwrites a canary's blob IDs in place, so a typo in an ID replaces
+//! a good canary with a broken one. `update_blob_preview` reads the current
+//! `CanaryBlob`, dry-runs the update with the same arguments, and returns the fields
+//! that would change next to the simulation, so an admin can check the diff before
+//! signing anything.
+
+use super::{query_canary_blob, update_blob_builder, CanaryBlobInfo};
+use crate::client::SuiClientWithSigner;
+use crate::clock::{ChainClock, TimeSource};
+use crate::error::{CanaryError, TransactionError};
+use crate::transaction::SimulationResult;
+use serde::Serialize;
+use std::fmt;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+/// The old and new value of a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FieldChange<T> {
+    /// The current value
+    pub old: T,
+    /// The value after the update
+    pub new: T,
+}
+
+impl<T: PartialEq> FieldChange<T> {
+    /// Whether the update changes the field
+    pub fn is_changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+impl<T: PartialEq + fmt::Display> fmt::Display for FieldChange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_changed() {
+            write!(f, "{} -> {}", self.old, self.new)
+        } else {
+            write!(f, "{} (unchanged)", self.old)
+        }
+    }
+}
+
+/// How an update would change a `CanaryBlob`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlobDiff {
+    /// The CanaryBlob object ID
+    pub canary_blob_id: ObjectID,
+    /// The canary's domain, which an update never changes
+    pub domain: String,
+    /// The contract blob ID
+    pub contract_blob_id: FieldChange<ObjectID>,
+    /// The explain blob ID
+    pub explain_blob_id: FieldChange<ObjectID>,
+    /// The upload time in Unix milliseconds; the new value is the chain time of the
+    /// preview, the update itself records the time it executes
+    pub uploaded_at: FieldChange<u64>,
+    /// The admin recorded as uploader
+    pub uploaded_by_admin: FieldChange<SuiAddress>,
+}
+
+impl BlobDiff {
+    /// The diff of updating `blob` with new blob IDs, by `admin` at `now_ms`
+    pub fn new(
+        blob: &CanaryBlobInfo,
+        new_contract_blob_id: ObjectID,
+        new_explain_blob_id: ObjectID,
+        admin: SuiAddress,
+        now_ms: u64,
+    ) -> Self {
+        Self {
+            canary_blob_id: blob.id,
+            domain: blob.domain.clone(),
+            contract_blob_id: FieldChange {
+                old: blob.contract_blob_id,
+                new: new_contract_blob_id,
+            },
+            explain_blob_id: FieldChange {
+                old: blob.explain_blob_id,
+                new: new_explain_blob_id,
+            },
+            uploaded_at: FieldChange {
+                old: blob.uploaded_at,
+                new: now_ms,
+            },
+            uploaded_by_admin: FieldChange {
+                old: blob.uploaded_by_admin,
+                new: admin,
+            },
+        }
+    }
+
+    /// Whether the update changes either blob ID
+    ///
+    /// An update that only refreshes `uploaded_at` is valid, but usually a mistake.
+    pub fn changes_blobs(&self) -> bool {
+        self.contract_blob_id.is_changed() || self.explain_blob_id.is_changed()
+    }
+}
+
+impl fmt::Display for BlobDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "CanaryBlob {} ({})", self.canary_blob_id, self.domain)?;
+        writeln!(f, "  contract_blob_id: {}", self.contract_blob_id)?;
+        writeln!(f, "  explain_blob_id: {}", self.explain_blob_id)?;
+        writeln!(f, "  uploaded_at: {}", self.uploaded_at)?;
+        write!(f, "  uploaded_by_admin: {}", self.uploaded_by_admin)
+    }
+}
+
+/// Result of `update_blob_preview`
+#[derive(Debug, Clone, Serialize)]
+pub struct BlobUpdatePreview {
+    /// The fields the update would change
+    pub diff: BlobDiff,
+    /// The dry run of the update transaction
+    pub simulation: SimulationResult,
+}
+
+/// Dry-run a blob update and show what it would change
+///
+/// Takes the same arguments as `update_blob` and builds the same transaction, but
+/// only simulates it; nothing is signed or submitted.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` for the admin
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `canary_blob_id` - The CanaryBlob object ID
+/// * `new_contract_blob_id` - The new contract blob object ID (as address)
+/// * `new_explain_blob_id` - The new explain blob object ID (as address)
+///
+/// # Returns
+///
+/// Returns the diff and the simulation, or a `CanaryError` if the blob cannot be read
+/// or the update would fail (e.g. `CanaryError::NotAdmin`).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::{update_blob, update_blob_preview};
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Testnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let canary_blob_id = ObjectID::from_hex_literal("0x789...")?;
+/// let contract = ObjectID::from_hex_literal("0xabc...")?;
+/// let explain = ObjectID::from_hex_literal("0xdef...")?;
+///
+/// let preview = update_blob_preview(
+///     client.try_clone().await?,
+///     registry_id,
+///     admin_cap_id,
+///     canary_blob_id,
+///     contract,
+///     explain,
+/// )
+/// .await?;
+/// println!("{}", preview.diff);
+/// println!("Gas: {} MIST", preview.simulation.gas.net());
+/// if preview.diff.changes_blobs() {
+///     update_blob(client, registry_id, admin_cap_id, canary_blob_id, contract, explain).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn update_blob_preview(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    canary_blob_id: ObjectID,
+    new_contract_blob_id: ObjectID,
+    new_explain_blob_id: ObjectID,
+) -> Result<BlobUpdatePreview, CanaryError> {
+    let blob = query_canary_blob(&client.client, canary_blob_id).await?;
+    let now_ms = ChainClock::new(client.client.clone()).now_ms().await?;
+    let diff = BlobDiff::new(
+        &blob,
+        new_contract_blob_id,
+        new_explain_blob_id,
+        client.signer,
+        now_ms,
+    );
+
+    let mut builder = update_blob_builder(
+        client,
+        registry_id,
+        admin_cap_id,
+        canary_blob_id,
+        new_contract_blob_id,
+        new_explain_blob_id,
+    )
+    .await?;
+    let simulation = builder.simulate().await?;
+
+    if !simulation.success {
+        return Err(match simulation.abort {
+            Some(abort) => CanaryError::from(abort),
+            None => CanaryError::Transaction(TransactionError::execution(
+                simulation
+                    .error
+                    .unwrap_or_else(|| "Update would fail".to_string()),
+            )),
+        });
+    }
+
+    Ok(BlobUpdatePreview { diff, simulation })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_diff() {
+        let blob = CanaryBlobInfo {
+            id: ObjectID::from_single_byte(1),
+            contract_blob_id: ObjectID::from_single_byte(2),
+            explain_blob_id: ObjectID::from_single_byte(3),
+            package_id: ObjectID::from_single_byte(4),
+            domain: "example.com".to_string(),
+            uploaded_at: 1_000,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        };
+
+        let diff = BlobDiff::new(
+            &blob,
+            ObjectID::from_single_byte(5),
+            blob.explain_blob_id,
+            SuiAddress::ZERO,
+            2_000,
+        );
+        assert!(diff.changes_blobs());
+        assert!(diff.contract_blob_id.is_changed());
+        assert!(!diff.explain_blob_id.is_changed());
+        assert!(!diff.uploaded_by_admin.is_changed());
+
+        let rendered = diff.to_string();
+        assert!(rendered.contains(&format!(
+            "contract_blob_id: {} -> {}",
+            blob.contract_blob_id,
+            ObjectID::from_single_byte(5)
+        )));
+        assert!(rendered.contains(&format!(
+            "explain_blob_id: {} (unchanged)",
+            blob.explain_blob_id
+        )));
+        assert!(rendered.contains("uploaded_at: 1000 -> 2000"));
+
+        let refresh_only = BlobDiff::new(
+            &blob,
+            blob.contract_blob_id,
+            blob.explain_blob_id,
+            SuiAddress::ZERO,
+            2_000,
+        );
+        assert!(!refresh_only.changes_blobs());
+    }
+}
@@ -0,0 +1,457 @@
+//! Member self-service blob proposals
+//!
+//! Members upload their contract and explain content to storage themselves and submit
+//! a `BlobProposal` naming the resulting blob IDs. Proposals are recorded off-chain in
+//! a `ProposalStore`; admins list pending proposals, review them against the
+//! domain's current canary blob, and approve (which publishes through `store_blob` or
+//! `update_blob`) or reject them.
+
+use super::{derive_canary_address, query_canary_blob, query_member, CanaryBlobInfo};
+#[cfg(feature = "signing")]
+use super::{store_blob, update_blob};
+#[cfg(feature = "signing")]
+use crate::client::SuiClientWithSigner;
+use crate::error::{CanaryError, ProposalError, StateError};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// Review state of a proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    /// Waiting for an admin decision
+    Pending,
+    /// Published by an admin in the given transaction
+    Approved { digest: TransactionDigest },
+    /// Rejected by an admin
+    Rejected { reason: String },
+}
+
+/// A member's proposed canary blob update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobProposal {
+    /// Proposal ID (derived from the proposal contents)
+    pub id: String,
+    /// The member's domain
+    pub domain: String,
+    /// The package the canary describes
+    pub package_id: ObjectID,
+    /// The proposed contract blob object ID
+    pub contract_blob_id: ObjectID,
+    /// The proposed explain blob object ID
+    pub explain_blob_id: ObjectID,
+    /// The member who submitted the proposal
+    pub proposer: SuiAddress,
+    /// When the proposal was submitted (in milliseconds)
+    pub submitted_at: u64,
+    /// Review state
+    pub status: ProposalStatus,
+}
+
+impl BlobProposal {
+    /// Create a pending proposal
+    pub fn new(
+        proposer: SuiAddress,
+        domain: String,
+        package_id: ObjectID,
+        contract_blob_id: ObjectID,
+        explain_blob_id: ObjectID,
+        submitted_at: u64,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(proposer.to_vec());
+        hasher.update(domain.as_bytes());
+        hasher.update(package_id.to_vec());
+        hasher.update(contract_blob_id.to_vec());
+        hasher.update(explain_blob_id.to_vec());
+        hasher.update(submitted_at.to_le_bytes());
+        let id = hasher.finalize()[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        Self {
+            id,
+            domain,
+            package_id,
+            contract_blob_id,
+            explain_blob_id,
+            proposer,
+            submitted_at,
+            status: ProposalStatus::Pending,
+        }
+    }
+}
+
+/// Off-chain storage for proposals
+#[async_trait]
+pub trait ProposalStore: Send + Sync {
+    /// Insert or replace a proposal
+    async fn put(&self, proposal: BlobProposal) -> Result<(), StateError>;
+
+    /// Get a proposal by ID
+    async fn get(&self, id: &str) -> Result<Option<BlobProposal>, StateError>;
+
+    /// List all proposals, oldest first
+    async fn list(&self) -> Result<Vec<BlobProposal>, StateError>;
+}
+
+/// In-memory `ProposalStore` for single-instance deployments and tests
+#[derive(Default)]
+pub struct MemoryProposalStore {
+    proposals: Mutex<BTreeMap<String, BlobProposal>>,
+}
+
+impl MemoryProposalStore {
+    /// Create an empty in-memory proposal store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, BTreeMap<String, BlobProposal>>, StateError> {
+        self.proposals
+            .lock()
+            .map_err(|e| StateError::Backend(format!("Proposal lock poisoned: {}", e)))
+    }
+}
+
+#[async_trait]
+impl ProposalStore for MemoryProposalStore {
+    async fn put(&self, proposal: BlobProposal) -> Result<(), StateError> {
+        self.lock()?.insert(proposal.id.clone(), proposal);
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<BlobProposal>, StateError> {
+        Ok(self.lock()?.get(id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<BlobProposal>, StateError> {
+        let mut proposals: Vec<BlobProposal> = self.lock()?.values().cloned().collect();
+        proposals.sort_by_key(|p| p.submitted_at);
+        Ok(proposals)
+    }
+}
+
+/// A proposal together with the canary blob it would replace
+#[derive(Debug, Clone)]
+pub struct ProposalReview {
+    /// The proposal under review
+    pub proposal: BlobProposal,
+    /// The domain's current canary blob, or `None` if approving would create it
+    pub current: Option<CanaryBlobInfo>,
+}
+
+impl ProposalReview {
+    /// Fields that would change, as `(field, current, proposed)`
+    ///
+    /// A proposal for a domain without a canary blob lists every field as new.
+    pub fn changes(&self) -> Vec<(&'static str, Option<ObjectID>, ObjectID)> {
+        let current = self.current.as_ref();
+        [
+            (
+                "contract_blob_id",
+                current.map(|c| c.contract_blob_id),
+                self.proposal.contract_blob_id,
+            ),
+            (
+                "explain_blob_id",
+                current.map(|c| c.explain_blob_id),
+                self.proposal.explain_blob_id,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, old, new)| *old != Some(*new))
+        .collect()
+    }
+}
+
+/// Submit a proposal on behalf of a registry member
+///
+/// The proposer must be a member of the registry, registered for the proposed domain.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `store` - Where proposals are recorded
+/// * `registry_id` - The Registry object ID
+/// * `proposal` - The proposal (see `BlobProposal::new`)
+///
+/// # Returns
+///
+/// Returns the proposal ID, or a `ProposalError` if the proposer is not eligible.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::proposals::{submit_proposal, BlobProposal, MemoryProposalStore};
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let store = MemoryProposalStore::new();
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let proposal = BlobProposal::new(
+///     "0x456...".parse::<SuiAddress>()?,
+///     "example.com".to_string(),
+///     ObjectID::from_hex_literal("0xabc...")?,
+///     ObjectID::from_hex_literal("0x789...")?,
+///     ObjectID::from_hex_literal("0xdef...")?,
+///     1_700_000_000_000,
+/// );
+/// let id = submit_proposal(&client, &store, registry_id, proposal).await?;
+/// println!("Submitted proposal {}", id);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn submit_proposal(
+    client: &SuiClient,
+    store: &dyn ProposalStore,
+    registry_id: ObjectID,
+    proposal: BlobProposal,
+) -> Result<String, ProposalError> {
+    let member = query_member(client, registry_id, proposal.proposer)
+        .await?
+        .ok_or(CanaryError::NotMember)?;
+    if member.domain != proposal.domain {
+        return Err(ProposalError::DomainMismatch {
+            registered: member.domain,
+            proposed: proposal.domain,
+        });
+    }
+
+    let id = proposal.id.clone();
+    store
+        .put(BlobProposal {
+            status: ProposalStatus::Pending,
+            ..proposal
+        })
+        .await?;
+    Ok(id)
+}
+
+/// List proposals that are waiting for an admin decision, oldest first
+pub async fn list_pending(store: &dyn ProposalStore) -> Result<Vec<BlobProposal>, ProposalError> {
+    Ok(store
+        .list()
+        .await?
+        .into_iter()
+        .filter(|p| p.status == ProposalStatus::Pending)
+        .collect())
+}
+
+/// Load a proposal together with the canary blob it would replace
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `store` - Where proposals are recorded
+/// * `registry_id` - The Registry object ID
+/// * `id` - The proposal ID
+///
+/// # Returns
+///
+/// Returns a `ProposalReview`, or a `ProposalError` if the proposal does not exist or
+/// the current blob cannot be queried.
+pub async fn review_proposal(
+    client: &SuiClient,
+    store: &dyn ProposalStore,
+    registry_id: ObjectID,
+    id: &str,
+) -> Result<ProposalReview, ProposalError> {
+    let proposal = get_proposal(store, id).await?;
+
+    let canary_address = derive_canary_address(
+        client,
+        registry_id,
+        proposal.domain.clone(),
+        proposal.package_id,
+    )
+    .await?;
+    let current = match query_canary_blob(client, ObjectID::from(canary_address)).await {
+        Ok(info) => Some(info),
+        Err(CanaryError::CanaryBlobNotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(ProposalReview { proposal, current })
+}
+
+/// Approve a pending proposal and publish it on-chain
+///
+/// Creates the domain's canary blob with `store_blob`, or replaces its blob IDs with
+/// `update_blob` if it already exists.
+///
+/// # Arguments
+///
+/// * `admin` - A `SuiClientWithSigner` for the registry admin
+/// * `store` - Where proposals are recorded
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `id` - The proposal ID
+///
+/// # Returns
+///
+/// Returns the digest of the publishing transaction, or a `ProposalError` if the
+/// proposal is not pending or publishing fails.
+#[cfg(feature = "signing")]
+pub async fn approve_proposal(
+    admin: SuiClientWithSigner,
+    store: &dyn ProposalStore,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    id: &str,
+) -> Result<TransactionDigest, ProposalError> {
+    let review = review_proposal(&admin.client, store, registry_id, id).await?;
+    let mut proposal = review.proposal;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(ProposalError::NotPending(proposal.id));
+    }
+
+    let receipt = match review.current {
+        Some(current) => {
+            update_blob(
+                admin,
+                registry_id,
+                admin_cap_id,
+                current.id,
+                proposal.contract_blob_id,
+                proposal.explain_blob_id,
+            )
+            .await?
+        }
+        None => {
+            store_blob(
+                admin,
+                registry_id,
+                admin_cap_id,
+                proposal.domain.clone(),
+                proposal.contract_blob_id,
+                proposal.explain_blob_id,
+                proposal.package_id,
+            )
+            .await?
+        }
+    };
+
+    proposal.status = ProposalStatus::Approved {
+        digest: receipt.digest,
+    };
+    store.put(proposal).await?;
+    Ok(receipt.digest)
+}
+
+/// Reject a pending proposal
+pub async fn reject_proposal(
+    store: &dyn ProposalStore,
+    id: &str,
+    reason: &str,
+) -> Result<(), ProposalError> {
+    let mut proposal = get_proposal(store, id).await?;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(ProposalError::NotPending(proposal.id));
+    }
+    proposal.status = ProposalStatus::Rejected {
+        reason: reason.to_string(),
+    };
+    store.put(proposal).await?;
+    Ok(())
+}
+
+async fn get_proposal(store: &dyn ProposalStore, id: &str) -> Result<BlobProposal, ProposalError> {
+    store
+        .get(id)
+        .await?
+        .ok_or_else(|| ProposalError::NotFound(id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_id(hex: &str) -> ObjectID {
+        ObjectID::from_hex_literal(hex).unwrap()
+    }
+
+    fn sample_proposal(submitted_at: u64) -> BlobProposal {
+        BlobProposal::new(
+            SuiAddress::ZERO,
+            "example.com".to_string(),
+            object_id("0x1"),
+            object_id("0x2"),
+            object_id("0x3"),
+            submitted_at,
+        )
+    }
+
+    fn sample_blob() -> CanaryBlobInfo {
+        CanaryBlobInfo {
+            id: object_id("0x10"),
+            contract_blob_id: object_id("0x2"),
+            explain_blob_id: object_id("0x9"),
+            package_id: object_id("0x1"),
+            domain: "example.com".to_string(),
+            uploaded_at: 0,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn test_proposal_id_stable() {
+        assert_eq!(sample_proposal(1).id, sample_proposal(1).id);
+        assert_ne!(sample_proposal(1).id, sample_proposal(2).id);
+        assert_eq!(sample_proposal(1).id.len(), 16);
+    }
+
+    #[test]
+    fn test_review_changes() {
+        let review = ProposalReview {
+            proposal: sample_proposal(1),
+            current: Some(sample_blob()),
+        };
+        let changes = review.changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, "explain_blob_id");
+        assert_eq!(changes[0].1, Some(object_id("0x9")));
+        assert_eq!(changes[0].2, object_id("0x3"));
+
+        let new_blob = ProposalReview {
+            current: None,
+            ..review
+        };
+        assert_eq!(new_blob.changes().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_store_list_and_reject() {
+        let store = MemoryProposalStore::new();
+        store.put(sample_proposal(2)).await.unwrap();
+        store.put(sample_proposal(1)).await.unwrap();
+
+        let pending = list_pending(&store).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].submitted_at, 1);
+
+        reject_proposal(&store, &pending[0].id, "wrong package")
+            .await
+            .unwrap();
+        assert_eq!(list_pending(&store).await.unwrap().len(), 1);
+        assert!(matches!(
+            reject_proposal(&store, &pending[0].id, "again").await,
+            Err(ProposalError::NotPending(_))
+        ));
+        assert!(matches!(
+            reject_proposal(&store, "missing", "x").await,
+            Err(ProposalError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+//! One-call canary publishing
+//!
+//! Publishing a canary takes several steps: read the contract and explain artifacts,
+//! upload both as blobs, and record them on chain with `store_blob` under the domain's
+//! derived address. `publish_canary` runs these steps in order and reports progress
+//! after each one. Blob storage is not built into the SDK, so uploads go through a
+//! caller-supplied `BlobUploader` (e.g. a wrapper around a Walrus client).
+
+use super::{
+    derive_canary_address, ensure_success, query_blob_by_domain, store_blob, TransactionReceipt,
+};
+use crate::client::SuiClientWithSigner;
+use crate::error::PublishError;
+use crate::progress::{Progress, ProgressReporter};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+/// Number of steps `publish_canary` reports progress for
+const PUBLISH_STEPS: u64 = 4;
+
+/// Uploads artifact bytes to blob storage
+#[async_trait]
+pub trait BlobUploader: Send + Sync {
+    /// Upload one artifact and return the ID of the stored blob object
+    ///
+    /// `name` is the artifact's file name, for uploaders that record it.
+    async fn upload(&self, name: &str, contents: Vec<u8>) -> Result<ObjectID, PublishError>;
+}
+
+/// What to publish
+#[derive(Debug, Clone)]
+pub struct PublishRequest {
+    /// The domain the canary is published for
+    pub domain: String,
+    /// The package ID the canary covers
+    pub package_id: ObjectID,
+    /// Path to the contract artifact
+    pub contract_path: PathBuf,
+    /// Path to the explain artifact
+    pub explain_path: PathBuf,
+}
+
+/// Result of a successful `publish_canary`
+#[derive(Debug)]
+pub struct PublishedCanary {
+    /// The derived address of the new CanaryBlob
+    pub address: SuiAddress,
+    /// The uploaded contract blob object ID
+    pub contract_blob_id: ObjectID,
+    /// The uploaded explain blob object ID
+    pub explain_blob_id: ObjectID,
+    /// The `store_blob` transaction receipt
+    pub receipt: TransactionReceipt,
+}
+
+/// Read artifacts, upload them, and store the canary blob in one operation
+///
+/// The domain is checked before anything is uploaded, so publishing to a domain that
+/// already has a canary fails without paying for uploads.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `admin_cap_id` - The AdminCap object ID
+/// * `request` - The domain, package, and artifact paths to publish
+/// * `uploader` - Uploads the artifacts to blob storage
+/// * `reporter` - Receives a progress event after each step
+///
+/// # Returns
+///
+/// Returns the new canary's address, blob IDs, and transaction receipt, or a
+/// `PublishError` if any step fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use async_trait::async_trait;
+/// use canary_sdk::canary::publish::{publish_canary, BlobUploader, PublishRequest};
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use canary_sdk::error::PublishError;
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// struct MyStorage;
+///
+/// #[async_trait]
+/// impl BlobUploader for MyStorage {
+///     async fn upload(&self, _name: &str, _contents: Vec<u8>) -> Result<ObjectID, PublishError> {
+///         // Store the blob and return its object ID
+///         # unimplemented!()
+///     }
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let admin_cap_id = ObjectID::from_hex_literal("0x456...")?;
+/// let request = PublishRequest {
+///     domain: "example.com".to_string(),
+///     package_id: ObjectID::from_hex_literal("0x789...")?,
+///     contract_path: "canary/contract.json".into(),
+///     explain_path: "canary/explain.md".into(),
+/// };
+/// let published =
+///     publish_canary(client, registry_id, admin_cap_id, request, &MyStorage, &()).await?;
+/// println!("Canary published at {}", published.address);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn publish_canary(
+    client: SuiClientWithSigner,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    request: PublishRequest,
+    uploader: &dyn BlobUploader,
+    reporter: &dyn ProgressReporter,
+) -> Result<PublishedCanary, PublishError> {
+    let progress = Progress::start(reporter, "publish canary", Some(PUBLISH_STEPS));
+
+    let contract = read_artifact(&request.contract_path)?;
+    let explain = read_artifact(&request.explain_path)?;
+    if query_blob_by_domain(
+        &client.client,
+        registry_id,
+        &request.domain,
+        request.package_id,
+    )
+    .await?
+    .is_some()
+    {
+        return Err(PublishError::AlreadyPublished(request.domain));
+    }
+    let address = derive_canary_address(
+        &client.client,
+        registry_id,
+        request.domain.clone(),
+        request.package_id,
+    )
+    .await?;
+    progress.advance(1, Some("read artifacts"));
+
+    let contract_blob_id = uploader
+        .upload(&artifact_name(&request.contract_path), contract)
+        .await?;
+    progress.advance(1, Some("upload contract"));
+
+    let explain_blob_id = uploader
+        .upload(&artifact_name(&request.explain_path), explain)
+        .await?;
+    progress.advance(1, Some("upload explain"));
+
+    let receipt = store_blob(
+        client,
+        registry_id,
+        admin_cap_id,
+        request.domain,
+        contract_blob_id,
+        explain_blob_id,
+        request.package_id,
+    )
+    .await?;
+    ensure_success(&receipt.response)?;
+    progress.advance(1, Some("store blob"));
+    progress.finish();
+
+    Ok(PublishedCanary {
+        address,
+        contract_blob_id,
+        explain_blob_id,
+        receipt,
+    })
+}
+
+/// Read an artifact file, rejecting empty ones
+fn read_artifact(path: &Path) -> Result<Vec<u8>, PublishError> {
+    let contents = std::fs::read(path).map_err(|e| PublishError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    if contents.is_empty() {
+        return Err(PublishError::EmptyArtifact(path.display().to_string()));
+    }
+    Ok(contents)
+}
+
+/// File name of an artifact, for the uploader
+fn artifact_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_artifact_errors() {
+        let dir = std::env::temp_dir().join(format!("canary-publish-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let empty = dir.join("empty.json");
+        std::fs::write(&empty, b"").unwrap();
+
+        assert!(matches!(
+            read_artifact(&empty),
+            Err(PublishError::EmptyArtifact(_))
+        ));
+        assert!(matches!(
+            read_artifact(&dir.join("missing.json")),
+            Err(PublishError::Io { .. })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_artifact_name() {
+        assert_eq!(
+            artifact_name(Path::new("canary/contract.json")),
+            "contract.json"
+        );
+    }
+}
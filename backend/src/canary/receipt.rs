@@ -0,0 +1,431 @@
+//! Summaries of executed canary transactions
+//!
+//! The canary helpers return a `TransactionReceipt` instead of the raw
+//! `SuiTransactionBlockResponse`: the digest, status, and gas cost up front, and the
+//! objects the transaction created or mutated, classified by canary type, so callers
+//! don't have to walk effects and object changes themselves. The raw response stays
+//! available as `response`.
+//!
+//! For a raw response, `object_changes_of_type` lists the changes to objects of one
+//! Move type, given as a `MoveObjectType` marker such as `CanaryBlob`, and
+//! `extract_created` (or `extract_created_blob`) finds the object a transaction created.
+
+use crate::transaction::GasSummary;
+use serde::Serialize;
+use sui_sdk::rpc_types::{
+    ObjectChange, SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::types::digests::TransactionDigest;
+
+/// What a transaction did to an object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectChangeKind {
+    /// The object was created
+    Created,
+    /// The object was mutated
+    Mutated,
+}
+
+/// The canary type of an object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryObjectKind {
+    /// `member_registry::Registry`
+    Registry,
+    /// `member_registry::AdminCap`
+    AdminCap,
+    /// `member_registry::MembershipCap`
+    MembershipCap,
+    /// A `members` table entry (`Field<address, member_registry::MemberInfo>`)
+    MemberInfo,
+    /// `pkg_storage::CanaryBlob`
+    CanaryBlob,
+    /// Any other object (e.g. a gas coin or an UpgradeCap)
+    Other,
+}
+
+impl CanaryObjectKind {
+    /// Classify an object by its Move type, e.g. `0x123::pkg_storage::CanaryBlob`
+    ///
+    /// Types are matched by module and name only, so objects of every canary package
+    /// version are recognized.
+    pub fn from_type(object_type: &str) -> Self {
+        let (module, name, params) = split_type(object_type);
+
+        match (module, name) {
+            ("member_registry", "Registry") => CanaryObjectKind::Registry,
+            ("member_registry", "AdminCap") => CanaryObjectKind::AdminCap,
+            ("member_registry", "MembershipCap") => CanaryObjectKind::MembershipCap,
+            ("pkg_storage", "CanaryBlob") => CanaryObjectKind::CanaryBlob,
+            // Dynamic fields are `Field<Key, Value>`; member entries hold a MemberInfo
+            ("dynamic_field", "Field")
+                if params.rsplit(',').next().is_some_and(|value| {
+                    value.trim().ends_with("::member_registry::MemberInfo")
+                }) =>
+            {
+                CanaryObjectKind::MemberInfo
+            }
+            _ => CanaryObjectKind::Other,
+        }
+    }
+}
+
+/// Split a Move type into module, name, and type parameters
+fn split_type(object_type: &str) -> (&str, &str, &str) {
+    let (base, params) = match object_type.split_once('<') {
+        Some((base, params)) => (base, params.strip_suffix('>').unwrap_or(params)),
+        None => (object_type, ""),
+    };
+    let mut path = base.rsplit("::");
+    let name = path.next().unwrap_or_default();
+    let module = path.next().unwrap_or_default();
+    (module, name, params)
+}
+
+/// A Move object type, identified by module and name
+///
+/// As with `CanaryObjectKind`, the package is not compared, so objects of every
+/// package version match, and neither are type parameters.
+pub trait MoveObjectType {
+    /// The module defining the type, e.g. `pkg_storage`
+    const MODULE: &'static str;
+    /// The type name, e.g. `CanaryBlob`
+    const NAME: &'static str;
+
+    /// Whether a full Move type, e.g. `0x123::pkg_storage::CanaryBlob`, is this type
+    fn matches(object_type: &str) -> bool {
+        let (module, name, _) = split_type(object_type);
+        module == Self::MODULE && name == Self::NAME
+    }
+}
+
+/// `member_registry::Registry`
+pub struct Registry;
+
+impl MoveObjectType for Registry {
+    const MODULE: &'static str = "member_registry";
+    const NAME: &'static str = "Registry";
+}
+
+/// `member_registry::AdminCap`
+pub struct AdminCap;
+
+impl MoveObjectType for AdminCap {
+    const MODULE: &'static str = "member_registry";
+    const NAME: &'static str = "AdminCap";
+}
+
+/// `member_registry::MembershipCap`
+pub struct MembershipCap;
+
+impl MoveObjectType for MembershipCap {
+    const MODULE: &'static str = "member_registry";
+    const NAME: &'static str = "MembershipCap";
+}
+
+/// `pkg_storage::CanaryBlob`
+pub struct CanaryBlob;
+
+impl MoveObjectType for CanaryBlob {
+    const MODULE: &'static str = "pkg_storage";
+    const NAME: &'static str = "CanaryBlob";
+}
+
+/// `0x2::package::UpgradeCap`
+pub struct UpgradeCap;
+
+impl MoveObjectType for UpgradeCap {
+    const MODULE: &'static str = "package";
+    const NAME: &'static str = "UpgradeCap";
+}
+
+/// The changes a transaction made to objects of type `T`
+///
+/// Covers created, mutated, transferred, wrapped, and deleted objects, in the order of
+/// the response's object changes. Empty if the response was requested without object
+/// changes.
+pub fn object_changes_of_type<T: MoveObjectType>(
+    response: &SuiTransactionBlockResponse,
+) -> Vec<&ObjectChange> {
+    response
+        .object_changes
+        .iter()
+        .flatten()
+        .filter(|change| {
+            let object_type = match change {
+                ObjectChange::Created { object_type, .. }
+                | ObjectChange::Mutated { object_type, .. }
+                | ObjectChange::Transferred { object_type, .. }
+                | ObjectChange::Wrapped { object_type, .. }
+                | ObjectChange::Deleted { object_type, .. } => object_type,
+                _ => return false,
+            };
+            T::matches(&object_type.to_string())
+        })
+        .collect()
+}
+
+/// The ID of the first object of type `T` a transaction created
+pub fn extract_created<T: MoveObjectType>(
+    response: &SuiTransactionBlockResponse,
+) -> Option<ObjectID> {
+    object_changes_of_type::<T>(response)
+        .into_iter()
+        .find_map(|change| match change {
+            ObjectChange::Created { object_id, .. } => Some(*object_id),
+            _ => None,
+        })
+}
+
+/// The ID of the `CanaryBlob` a transaction created, e.g. by `store_blob`
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::{extract_created_blob, store_blob};
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let id = ObjectID::from_hex_literal("0x123...")?;
+/// let receipt = store_blob(client, id, id, "example.com".to_string(), id, id, id).await?;
+/// println!("CanaryBlob: {:?}", extract_created_blob(&receipt.response));
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract_created_blob(response: &SuiTransactionBlockResponse) -> Option<ObjectID> {
+    extract_created::<CanaryBlob>(response)
+}
+
+/// An object created or mutated by a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReceiptObject {
+    /// The object ID
+    pub object_id: ObjectID,
+    /// Whether the object was created or mutated
+    pub change: ObjectChangeKind,
+    /// The object's canary type
+    pub kind: CanaryObjectKind,
+    /// The object's full Move type
+    pub object_type: String,
+}
+
+/// Outcome of an executed transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionReceipt {
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// Whether execution succeeded
+    pub success: bool,
+    /// The execution error, if execution failed
+    pub error: Option<String>,
+    /// Gas charged, if the response includes effects
+    pub gas: Option<GasSummary>,
+    /// Objects created or mutated, in the order of the response's object changes
+    pub objects: Vec<ReceiptObject>,
+    /// Events emitted by the transaction
+    pub events: Vec<SuiEvent>,
+    /// The full response
+    #[serde(skip)]
+    pub response: SuiTransactionBlockResponse,
+}
+
+impl TransactionReceipt {
+    /// Summarize an execution response
+    ///
+    /// Objects are only listed if the response was requested with object changes, as
+    /// `CanaryTransactionBuilder::execute` does.
+    pub fn from_response(response: SuiTransactionBlockResponse) -> Self {
+        let (success, error, gas) = match &response.effects {
+            Some(effects) => {
+                let (success, error) = match effects.status() {
+                    SuiExecutionStatus::Success => (true, None),
+                    SuiExecutionStatus::Failure { error } => (false, Some(error.clone())),
+                };
+                (
+                    success,
+                    error,
+                    Some(GasSummary::from(effects.gas_cost_summary())),
+                )
+            }
+            None => (false, None, None),
+        };
+
+        let objects = response
+            .object_changes
+            .iter()
+            .flatten()
+            .filter_map(|change| match change {
+                ObjectChange::Created {
+                    object_id,
+                    object_type,
+                    ..
+                } => Some((
+                    *object_id,
+                    ObjectChangeKind::Created,
+                    object_type.to_string(),
+                )),
+                ObjectChange::Mutated {
+                    object_id,
+                    object_type,
+                    ..
+                } => Some((
+                    *object_id,
+                    ObjectChangeKind::Mutated,
+                    object_type.to_string(),
+                )),
+                _ => None,
+            })
+            .map(|(object_id, change, object_type)| ReceiptObject {
+                object_id,
+                change,
+                kind: CanaryObjectKind::from_type(&object_type),
+                object_type,
+            })
+            .collect();
+
+        let events = response
+            .events
+            .as_ref()
+            .map(|events| events.data.clone())
+            .unwrap_or_default();
+
+        Self {
+            digest: response.digest,
+            success,
+            error,
+            gas,
+            objects,
+            events,
+            response,
+        }
+    }
+
+    /// The objects of one canary type, created or mutated
+    pub fn objects_of(&self, kind: CanaryObjectKind) -> impl Iterator<Item = &ReceiptObject> {
+        self.objects
+            .iter()
+            .filter(move |object| object.kind == kind)
+    }
+
+    /// The ID of the first object of a canary type the transaction created
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::{store_blob, CanaryObjectKind};
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+    /// let id = ObjectID::from_hex_literal("0x123...")?;
+    /// let receipt = store_blob(client, id, id, "example.com".to_string(), id, id, id).await?;
+    /// println!("CanaryBlob: {:?}", receipt.created(CanaryObjectKind::CanaryBlob));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn created(&self, kind: CanaryObjectKind) -> Option<ObjectID> {
+        self.objects_of(kind)
+            .find(|object| object.change == ObjectChangeKind::Created)
+            .map(|object| object.object_id)
+    }
+
+    /// Net gas charged in MIST (see `GasSummary::net`), or 0 without effects
+    pub fn gas_used(&self) -> i64 {
+        self.gas.map(|gas| gas.net()).unwrap_or(0)
+    }
+}
+
+impl From<SuiTransactionBlockResponse> for TransactionReceipt {
+    fn from(response: SuiTransactionBlockResponse) -> Self {
+        Self::from_response(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::{ObjectDigest, SequenceNumber, SuiAddress};
+    use sui_sdk::types::object::Owner;
+    use sui_sdk::types::parse_sui_struct_tag;
+
+    fn created(id: u8, object_type: &str) -> ObjectChange {
+        ObjectChange::Created {
+            sender: SuiAddress::ZERO,
+            owner: Owner::AddressOwner(SuiAddress::ZERO),
+            object_type: parse_sui_struct_tag(object_type).unwrap(),
+            object_id: ObjectID::from_single_byte(id),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::MIN,
+        }
+    }
+
+    #[test]
+    fn test_extract_created() {
+        let mut response = SuiTransactionBlockResponse::new(TransactionDigest::ZERO);
+        assert_eq!(extract_created_blob(&response), None);
+
+        response.object_changes = Some(vec![
+            created(1, "0x2::coin::Coin<0x2::sui::SUI>"),
+            ObjectChange::Mutated {
+                sender: SuiAddress::ZERO,
+                owner: Owner::AddressOwner(SuiAddress::ZERO),
+                object_type: parse_sui_struct_tag("0x123::pkg_storage::CanaryBlob").unwrap(),
+                object_id: ObjectID::from_single_byte(2),
+                version: SequenceNumber::from_u64(2),
+                previous_version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::MIN,
+            },
+            created(3, "0x123::pkg_storage::CanaryBlob"),
+            created(4, "0x2::package::UpgradeCap"),
+        ]);
+        assert_eq!(object_changes_of_type::<CanaryBlob>(&response).len(), 2);
+        assert_eq!(
+            extract_created_blob(&response),
+            Some(ObjectID::from_single_byte(3))
+        );
+        assert_eq!(
+            extract_created::<UpgradeCap>(&response),
+            Some(ObjectID::from_single_byte(4))
+        );
+        assert_eq!(extract_created::<AdminCap>(&response), None);
+    }
+
+    #[test]
+    fn test_object_kind() {
+        assert_eq!(
+            CanaryObjectKind::from_type("0x123::pkg_storage::CanaryBlob"),
+            CanaryObjectKind::CanaryBlob
+        );
+        assert_eq!(
+            CanaryObjectKind::from_type("0x123::member_registry::Registry"),
+            CanaryObjectKind::Registry
+        );
+        assert_eq!(
+            CanaryObjectKind::from_type("0x123::member_registry::AdminCap"),
+            CanaryObjectKind::AdminCap
+        );
+        assert_eq!(
+            CanaryObjectKind::from_type(
+                "0x2::dynamic_field::Field<address, 0x123::member_registry::MemberInfo>"
+            ),
+            CanaryObjectKind::MemberInfo
+        );
+        // Other dynamic fields, e.g. derived-object claims, are not member entries
+        assert_eq!(
+            CanaryObjectKind::from_type(
+                "0x2::dynamic_field::Field<0x123::pkg_storage::CanaryKey, bool>"
+            ),
+            CanaryObjectKind::Other
+        );
+        assert_eq!(
+            CanaryObjectKind::from_type("0x2::coin::Coin<0x2::sui::SUI>"),
+            CanaryObjectKind::Other
+        );
+    }
+}
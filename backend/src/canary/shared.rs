@@ -0,0 +1,179 @@
+//! Shared object arguments
+//!
+//! A shared object is passed to a Move call by ID and *initial shared version*: the
+//! version at which it became shared. That is not its current version (`object_ref().1`)
+//! nor its `previous_transaction`, and using either makes the transaction fail once the
+//! object has been mutated. `SharedObjectResolver` reads the initial shared version
+//! from the object's owner and caches it; it never changes, so the cache is never
+//! invalidated.
+//!
+//! Every canary function resolves shared objects through `SharedObjectResolver::global`.
+
+use crate::error::CanaryError;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use sui_sdk::rpc_types::SuiObjectDataOptions;
+use sui_sdk::types::base_types::{ObjectID, SequenceNumber};
+use sui_sdk::types::object::Owner;
+use sui_sdk::types::transaction::{CallArg, ObjectArg, SharedObjectMutability};
+use sui_sdk::types::{SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION};
+use sui_sdk::SuiClient;
+
+/// Resolves and caches the initial shared versions of shared objects
+#[derive(Debug, Default)]
+pub struct SharedObjectResolver {
+    versions: RwLock<HashMap<ObjectID, SequenceNumber>>,
+}
+
+impl SharedObjectResolver {
+    /// Create a resolver with an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide resolver used by the canary functions
+    pub fn global() -> &'static SharedObjectResolver {
+        static GLOBAL: OnceLock<SharedObjectResolver> = OnceLock::new();
+        GLOBAL.get_or_init(SharedObjectResolver::new)
+    }
+
+    /// The cached initial shared version of an object, if it was resolved before
+    pub fn cached(&self, object_id: ObjectID) -> Option<SequenceNumber> {
+        self.versions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&object_id)
+            .copied()
+    }
+
+    /// Record a known initial shared version, e.g. from the effects that shared it
+    pub fn insert(&self, object_id: ObjectID, initial_shared_version: SequenceNumber) {
+        self.versions
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(object_id, initial_shared_version);
+    }
+
+    /// The initial shared version of an object, fetched on first use
+    ///
+    /// # Returns
+    ///
+    /// Returns the version, or a `CanaryError` if the object cannot be read or is not
+    /// shared.
+    pub async fn initial_shared_version(
+        &self,
+        client: &SuiClient,
+        object_id: ObjectID,
+    ) -> Result<SequenceNumber, CanaryError> {
+        if object_id == SUI_CLOCK_OBJECT_ID {
+            return Ok(SUI_CLOCK_OBJECT_SHARED_VERSION);
+        }
+        if let Some(version) = self.cached(object_id) {
+            return Ok(version);
+        }
+
+        let owner = client
+            .read_api()
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await
+            .map_err(|e| CanaryError::Registry(format!("Failed to get {}: {}", object_id, e)))?
+            .into_object()
+            .map_err(|_| CanaryError::Registry(format!("Object {} not found", object_id)))?
+            .owner;
+        let version = match owner {
+            Some(Owner::Shared {
+                initial_shared_version,
+            }) => initial_shared_version,
+            _ => {
+                return Err(CanaryError::Registry(format!(
+                    "Object {} is not shared",
+                    object_id
+                )))
+            }
+        };
+
+        self.insert(object_id, version);
+        Ok(version)
+    }
+
+    /// A shared object argument for a Move call
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` for resolving uncached versions
+    /// * `object_id` - The shared object
+    /// * `mutability` - Whether the call takes the object by `&mut` or by value
+    ///   (`Mutable`) or by `&` (`Immutable`)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::SharedObjectResolver;
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use sui_sdk::types::base_types::ObjectID;
+    /// use sui_sdk::types::transaction::SharedObjectMutability;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let registry = SharedObjectResolver::global()
+    ///     .shared_arg(&client, registry_id, SharedObjectMutability::Immutable)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shared_arg(
+        &self,
+        client: &SuiClient,
+        object_id: ObjectID,
+        mutability: SharedObjectMutability,
+    ) -> Result<CallArg, CanaryError> {
+        let initial_shared_version = self.initial_shared_version(client, object_id).await?;
+        Ok(CallArg::Object(ObjectArg::SharedObject {
+            id: object_id,
+            initial_shared_version,
+            mutability,
+        }))
+    }
+
+    /// The `0x6::clock::Clock` argument, which never needs a lookup
+    pub fn clock_arg() -> CallArg {
+        CallArg::Object(ObjectArg::SharedObject {
+            id: SUI_CLOCK_OBJECT_ID,
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+            mutability: SharedObjectMutability::Immutable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let resolver = SharedObjectResolver::new();
+        let id = ObjectID::from_single_byte(7);
+        assert_eq!(resolver.cached(id), None);
+
+        resolver.insert(id, SequenceNumber::from_u64(42));
+        assert_eq!(resolver.cached(id), Some(SequenceNumber::from_u64(42)));
+        assert_eq!(SharedObjectResolver::new().cached(id), None);
+    }
+
+    #[test]
+    fn test_clock_arg() {
+        match SharedObjectResolver::clock_arg() {
+            CallArg::Object(ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutability,
+            }) => {
+                assert_eq!(id, SUI_CLOCK_OBJECT_ID);
+                assert_eq!(initial_shared_version, SequenceNumber::from_u64(1));
+                assert!(matches!(mutability, SharedObjectMutability::Immutable));
+            }
+            other => panic!("Unexpected clock argument: {:?}", other),
+        }
+    }
+}
@@ -1,15 +1,58 @@
 //! Sui client builder utilities
 //!
 //! This module provides simplified client creation with network presets and
-//! integration with keystores for signing transactions.
+//! integration with keystores for signing transactions. Presets for public RPC
+//! providers requiring an API key live in the `providers` submodule, and per-request
+//! tags for attributing traffic live in `tags`, and per-call latency and response
+//! size limits in `budget`. Every client identifies itself with the `USER_AGENT` header.
+//! Provider auth and quota rejections are classified into typed `ClientError`s by
+//! `errors`, and transient failures are retried with backoff by `retry`. A
+//! `Network::Pool` of several RPC URLs fails over between them via `failover`. Move
+//! view functions are called with typed decoding by `view::view_call`, and requests
+//! are spaced out to a configurable rate by `ratelimit`. Long-running processes keep
+//! one client and reconnect it with `ensure_connected` before reusing it; a
+//! `reconnect::ReconnectingClient` does the same for a client shared between tasks.
+//! Deployments that must never sign use a `readonly::ReadOnlyClient`.
 
 use crate::error::ClientError;
-use crate::keystore::create_keystore_from_key;
-use sui_keys::keystore::Keystore;
+#[cfg(feature = "signing")]
+use crate::error::KeystoreError;
+#[cfg(feature = "signing")]
+use crate::keystore::{add_to_keystore, create_keystore_from_key, ParsedPrivateKey};
+#[cfg(feature = "signing")]
+use crate::transaction::GasPolicy;
+#[cfg(feature = "signing")]
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+#[cfg(feature = "signing")]
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+#[cfg(feature = "signing")]
 use sui_sdk::types::base_types::SuiAddress;
 use sui_sdk::SuiClient;
 use sui_sdk::SuiClientBuilder;
 
+pub mod budget;
+pub mod errors;
+pub mod failover;
+pub mod providers;
+pub mod ratelimit;
+pub mod readonly;
+pub mod reconnect;
+pub mod retry;
+pub mod tags;
+pub mod view;
+
+pub use readonly::ReadOnlyClient;
+pub use view::{decode_return_values, view_call, view_call_raw};
+
+/// User-agent sent with every RPC request made by SDK-created clients
+pub const USER_AGENT: &str = concat!("canary-sdk/", env!("CARGO_PKG_VERSION"));
+
+/// Default headers for SDK-created clients
+pub(crate) fn default_headers() -> HashMap<String, String> {
+    HashMap::from([("user-agent".to_string(), USER_AGENT.to_string())])
+}
+
 /// Network presets for Sui client connections
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Network {
@@ -23,6 +66,8 @@ pub enum Network {
     Mainnet,
     /// Custom network URL
     Custom(String),
+    /// Several RPC URLs for the same network, tried in order
+    Pool(Vec<String>),
 }
 
 impl Network {
@@ -34,6 +79,15 @@ impl Network {
             Network::Testnet => "https://fullnode.testnet.sui.io:443",
             Network::Mainnet => "https://fullnode.mainnet.sui.io:443",
             Network::Custom(url) => url,
+            Network::Pool(urls) => urls.first().map(String::as_str).unwrap_or_default(),
+        }
+    }
+
+    /// Get every RPC URL for this network (one, except for `Pool`)
+    pub fn urls(&self) -> Vec<&str> {
+        match self {
+            Network::Pool(urls) => urls.iter().map(String::as_str).collect(),
+            _ => vec![self.url()],
         }
     }
 }
@@ -42,15 +96,28 @@ impl Network {
 ///
 /// This struct combines a Sui client with a keystore, making it easy to
 /// create and sign transactions without managing the keystore separately.
+///
+/// The keystore may hold several keys, e.g. a member key and an admin key. `signer` is
+/// the active one; switch it with `switch_signer`, or name keys with `add_profile` and
+/// switch by name with `switch_profile`. A `CanaryTransactionBuilder` uses the signer
+/// that was active when it was created.
+#[cfg(feature = "signing")]
 pub struct SuiClientWithSigner {
     /// The Sui client for interacting with the network
     pub client: SuiClient,
-    /// The signer address derived from the keystore
+    /// The active signer address; its key is in the keystore
     pub signer: SuiAddress,
-    /// The keystore containing the private key
+    /// The keystore containing the private keys
     pub keystore: Keystore,
+    /// Retry policy applied to RPC calls made on behalf of this signer
+    pub retry: retry::RetryPolicy,
+    /// Profile names of keystore addresses
+    pub profiles: BTreeMap<String, SuiAddress>,
+    /// Gas budget policy for transactions built with this signer
+    pub gas_policy: GasPolicy,
 }
 
+#[cfg(feature = "signing")]
 impl SuiClientWithSigner {
     /// Get a reference to the Sui client
     pub fn client(&self) -> &SuiClient {
@@ -71,12 +138,251 @@ impl SuiClientWithSigner {
     pub fn keystore_mut(&mut self) -> &mut Keystore {
         &mut self.keystore
     }
+
+    /// Get the retry policy for RPC calls
+    pub fn retry_policy(&self) -> &retry::RetryPolicy {
+        &self.retry
+    }
+
+    /// Use a different retry policy (e.g. `RetryPolicy::none()` to fail fast)
+    pub fn with_retry_policy(mut self, policy: retry::RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Get the gas budget policy for transactions
+    pub fn gas_policy(&self) -> &GasPolicy {
+        &self.gas_policy
+    }
+
+    /// Use a different gas budget policy (e.g. `GasPolicy::fixed(budget)`)
+    pub fn with_gas_policy(mut self, policy: GasPolicy) -> Self {
+        self.gas_policy = policy;
+        self
+    }
+
+    /// Copy the client, its keys, and its profiles
+    ///
+    /// The copy's keys are held in memory, even if this client uses a file keystore.
+    /// Use it to call helpers that take a `SuiClientWithSigner` by value while keeping
+    /// this one.
+    ///
+    /// # Returns
+    ///
+    /// Returns the copy, or a `KeystoreError` if a key cannot be exported.
+    pub async fn try_clone(&self) -> Result<Self, KeystoreError> {
+        let mut keystore = Keystore::InMem(InMemKeystore::default());
+        for address in self.keystore.addresses() {
+            let keypair = self
+                .keystore
+                .export(&address)
+                .map_err(|e| KeystoreError::KeystoreOperation(e.to_string()))?;
+            keystore
+                .import(None, keypair.copy())
+                .await
+                .map_err(|e| KeystoreError::KeystoreOperation(e.to_string()))?;
+        }
+
+        Ok(Self {
+            client: self.client.clone(),
+            signer: self.signer,
+            keystore,
+            retry: self.retry,
+            profiles: self.profiles.clone(),
+            gas_policy: self.gas_policy.clone(),
+        })
+    }
+
+    /// Every address the keystore can sign for
+    pub fn signers(&self) -> Vec<SuiAddress> {
+        self.keystore.addresses()
+    }
+
+    /// Add a key to the keystore under a profile name
+    ///
+    /// The active signer does not change. Adding a profile name that already exists
+    /// points it at the new key.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The profile name, e.g. `"admin"`
+    /// * `parsed_key` - The private key
+    ///
+    /// # Returns
+    ///
+    /// Returns the key's `SuiAddress`, or a `KeystoreError` if the key cannot be added.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use canary_sdk::keystore::parse_bech32_private_key;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut client = create_client_with_key(Network::Testnet, "suiprivkey1...").await?;
+    ///     client.name_profile("member", client.signer())?;
+    ///     client
+    ///         .add_profile("admin", parse_bech32_private_key("suiprivkey1...")?)
+    ///         .await?;
+    ///
+    ///     client.switch_profile("admin")?;
+    ///     // ... admin operations ...
+    ///     client.switch_profile("member")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn add_profile(
+        &mut self,
+        name: impl Into<String>,
+        parsed_key: ParsedPrivateKey,
+    ) -> Result<SuiAddress, KeystoreError> {
+        let address = add_to_keystore(&mut self.keystore, parsed_key).await?;
+        self.profiles.insert(name.into(), address);
+        Ok(address)
+    }
+
+    /// Name an address already in the keystore
+    ///
+    /// # Returns
+    ///
+    /// Returns `KeystoreError::UnknownSigner` if the keystore has no key for `address`.
+    pub fn name_profile(
+        &mut self,
+        name: impl Into<String>,
+        address: SuiAddress,
+    ) -> Result<(), KeystoreError> {
+        self.ensure_signer(address)?;
+        self.profiles.insert(name.into(), address);
+        Ok(())
+    }
+
+    /// Make `address` the active signer
+    ///
+    /// # Returns
+    ///
+    /// Returns `KeystoreError::UnknownSigner` if the keystore has no key for `address`;
+    /// the active signer is then unchanged.
+    pub fn switch_signer(&mut self, address: SuiAddress) -> Result<(), KeystoreError> {
+        self.ensure_signer(address)?;
+        if self.signer != address {
+            tracing::info!(from = %self.signer, to = %address, "Switching signer");
+            self.signer = address;
+        }
+        Ok(())
+    }
+
+    /// Make the address of the profile `name` the active signer
+    ///
+    /// # Returns
+    ///
+    /// Returns `KeystoreError::UnknownProfile` if no profile has this name.
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), KeystoreError> {
+        let address = self
+            .profile(name)
+            .ok_or_else(|| KeystoreError::UnknownProfile(name.to_string()))?;
+        self.switch_signer(address)
+    }
+
+    /// The address of the profile `name`
+    pub fn profile(&self, name: &str) -> Option<SuiAddress> {
+        self.profiles.get(name).copied()
+    }
+
+    /// The name of the active signer's profile, if it has one
+    pub fn active_profile(&self) -> Option<&str> {
+        self.profiles
+            .iter()
+            .find(|(_, address)| **address == self.signer)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Check that the client's RPC node answers
+    ///
+    /// # Returns
+    ///
+    /// Returns the node's latest checkpoint, or `ClientError::Network` if it cannot be
+    /// reached.
+    pub async fn health_check(&self) -> Result<u64, ClientError> {
+        self.client
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .map_err(|e| ClientError::Network(e.to_string()))
+    }
+
+    /// Drop the keys and keep a client that can only read
+    ///
+    /// Use it to hand the connection to code that must never sign, such as a
+    /// dashboard; see `readonly::ReadOnlyClient`.
+    pub fn read_only(self) -> readonly::ReadOnlyClient {
+        readonly::ReadOnlyClient::new(self.client)
+    }
+
+    /// Reconnect if the client's RPC node stopped answering
+    ///
+    /// Long-running processes call this before each unit of work, so one dropped
+    /// connection doesn't fail every later run. The keys, profiles, and policies are
+    /// kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reconnect` - Creates the new client; only awaited if the health check fails
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the client was replaced, or `reconnect`'s error if the node is
+    /// unreachable and no new connection can be made.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_client_with_key, create_sui_client, Network};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let network = Network::Testnet;
+    /// let mut client = create_client_with_key(network.clone(), "suiprivkey1...").await?;
+    /// loop {
+    ///     if client.ensure_connected(create_sui_client(network.clone())).await? {
+    ///         println!("Reconnected to {}", network.url());
+    ///     }
+    ///     // ... work with `client` ...
+    ///     tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+    /// }
+    /// # }
+    /// ```
+    pub async fn ensure_connected<F, E>(&mut self, reconnect: F) -> Result<bool, E>
+    where
+        F: std::future::Future<Output = Result<SuiClient, E>>,
+    {
+        match self.health_check().await {
+            Ok(_) => Ok(false),
+            Err(e) => {
+                tracing::warn!("RPC health check failed, reconnecting: {}", e);
+                self.client = reconnect.await?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn ensure_signer(&self, address: SuiAddress) -> Result<(), KeystoreError> {
+        if self.keystore.addresses().contains(&address) {
+            Ok(())
+        } else {
+            Err(KeystoreError::UnknownSigner(address))
+        }
+    }
 }
 
 /// Create a Sui client connected to the specified network
 ///
 /// This function uses the Sui SDK's network-specific builder methods for
-/// convenience, or falls back to a custom URL for the `Custom` variant.
+/// convenience, or falls back to a custom URL for the `Custom` variant. For a `Pool`,
+/// the endpoints are tried in order and the client of the first healthy one is
+/// returned; use `create_failover_client` to keep rotating between them afterwards.
+///
+/// For `Testnet` and `Mainnet`, the node's chain identifier is checked against the
+/// network's (see `networks::verify_chain_id`).
 ///
 /// # Arguments
 ///
@@ -84,7 +390,8 @@ impl SuiClientWithSigner {
 ///
 /// # Returns
 ///
-/// Returns a `SuiClient` connected to the specified network, or a `ClientError` if connection fails.
+/// Returns a `SuiClient` connected to the specified network, or a `ClientError` if connection fails
+/// or the node is on another chain (`ClientError::ChainMismatch`).
 ///
 /// # Example
 ///
@@ -99,34 +406,98 @@ impl SuiClientWithSigner {
 /// }
 /// ```
 pub async fn create_sui_client(network: Network) -> Result<SuiClient, ClientError> {
-    let builder = SuiClientBuilder::default();
+    let builder = SuiClientBuilder::default().custom_headers(default_headers());
 
     let client = match network {
         Network::Localnet => builder
             .build_localnet()
             .await
-            .map_err(|e| ClientError::ClientCreation(e.to_string()))?,
+            .map_err(errors::creation_error)?,
         Network::Devnet => builder
             .build_devnet()
             .await
-            .map_err(|e| ClientError::ClientCreation(e.to_string()))?,
-        Network::Testnet => builder
-            .build_testnet()
-            .await
-            .map_err(|e| ClientError::ClientCreation(e.to_string()))?,
-        Network::Mainnet => builder
-            .build_mainnet()
-            .await
-            .map_err(|e| ClientError::ClientCreation(e.to_string()))?,
-        Network::Custom(url) => builder
-            .build(url)
-            .await
-            .map_err(|e| ClientError::ClientCreation(e.to_string()))?,
+            .map_err(errors::creation_error)?,
+        Network::Testnet => {
+            let client = builder
+                .build_testnet()
+                .await
+                .map_err(errors::creation_error)?;
+            crate::networks::verify_chain_id(&client, &Network::Testnet).await?;
+            client
+        }
+        Network::Mainnet => {
+            let client = builder
+                .build_mainnet()
+                .await
+                .map_err(errors::creation_error)?;
+            crate::networks::verify_chain_id(&client, &Network::Mainnet).await?;
+            client
+        }
+        Network::Custom(url) => builder.build(url).await.map_err(errors::creation_error)?,
+        Network::Pool(urls) => {
+            let mut failures = Vec::with_capacity(urls.len());
+            for url in &urls {
+                match connect_url(url).await {
+                    Ok(client) => return Ok(client),
+                    Err(e) => {
+                        tracing::warn!(endpoint = %url, "Endpoint unavailable: {}", e);
+                        failures.push(format!("{}: {}", url, e));
+                    }
+                }
+            }
+            return Err(if failures.is_empty() {
+                ClientError::InvalidUrl("endpoint pool is empty".to_string())
+            } else {
+                ClientError::NoHealthyEndpoint(failures.join("; "))
+            });
+        }
     };
 
     Ok(client)
 }
 
+/// Create a client that fails over between the endpoints of a network
+///
+/// # Arguments
+///
+/// * `network` - The network to connect to, typically a `Network::Pool`
+///
+/// # Returns
+///
+/// Returns a `FailoverClient` positioned on the first healthy endpoint, or a
+/// `ClientError` if no endpoint is healthy.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_failover_client, Network};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let network = Network::Pool(vec![
+///         "https://fullnode.mainnet.sui.io:443".to_string(),
+///         "https://sui-mainnet.example.com".to_string(),
+///     ]);
+///     let client = create_failover_client(&network).await?;
+///     println!("Connected to {}", client.current_url());
+///     Ok(())
+/// }
+/// ```
+pub async fn create_failover_client(
+    network: &Network,
+) -> Result<failover::FailoverClient, ClientError> {
+    failover::FailoverClient::connect(network).await
+}
+
+/// Build a client for a single RPC URL with the default headers
+pub(crate) async fn connect_url(url: &str) -> Result<SuiClient, ClientError> {
+    SuiClientBuilder::default()
+        .custom_headers(default_headers())
+        .build(url)
+        .await
+        .map_err(errors::creation_error)
+}
+
 /// Create a Sui client with a custom URL
 ///
 /// This is a convenience function for creating a client with a custom URL string.
@@ -183,6 +554,7 @@ pub async fn create_sui_client_with_url(url: &str) -> Result<SuiClient, ClientEr
 ///     Ok(())
 /// }
 /// ```
+#[cfg(feature = "signing")]
 pub async fn create_client_with_key(
     network: Network,
     bech32_key: &str,
@@ -199,6 +571,9 @@ pub async fn create_client_with_key(
         client,
         signer,
         keystore,
+        retry: retry::RetryPolicy::default(),
+        profiles: BTreeMap::new(),
+        gas_policy: GasPolicy::default(),
     })
 }
 
@@ -223,6 +598,39 @@ mod tests {
         assert_eq!(custom.url(), "http://custom.example.com:9000");
     }
 
+    #[test]
+    fn test_pool_urls() {
+        let pool = Network::Pool(vec![
+            "http://a:9000".to_string(),
+            "http://b:9000".to_string(),
+        ]);
+        assert_eq!(pool.url(), "http://a:9000");
+        assert_eq!(pool.urls(), vec!["http://a:9000", "http://b:9000"]);
+        assert_eq!(Network::Pool(Vec::new()).url(), "");
+        assert_eq!(Network::Devnet.urls(), vec![Network::Devnet.url()]);
+    }
+
+    #[tokio::test]
+    async fn test_empty_pool_rejected() {
+        assert!(matches!(
+            create_sui_client(Network::Pool(Vec::new())).await,
+            Err(ClientError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            create_failover_client(&Network::Pool(Vec::new())).await,
+            Err(ClientError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn test_user_agent() {
+        assert!(USER_AGENT.starts_with("canary-sdk/"));
+        assert_eq!(
+            default_headers().get("user-agent").map(String::as_str),
+            Some(USER_AGENT)
+        );
+    }
+
     #[test]
     fn test_network_equality() {
         assert_eq!(Network::Localnet, Network::Localnet);
@@ -266,6 +674,45 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "signing")]
+    #[tokio::test]
+    #[ignore] // Ignored by default - requires network connection
+    async fn test_switch_profiles() {
+        use sui_sdk::types::crypto::{get_key_pair, SuiKeyPair};
+
+        let member = SuiKeyPair::Ed25519(get_key_pair().1).encode().unwrap();
+        let admin = SuiKeyPair::Ed25519(get_key_pair().1).encode().unwrap();
+        let mut client = create_client_with_key(Network::Localnet, &member)
+            .await
+            .unwrap();
+        let member = client.signer();
+        client.name_profile("member", member).unwrap();
+        let admin = client
+            .add_profile(
+                "admin",
+                crate::keystore::parse_bech32_private_key(&admin).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(client.signer(), member);
+        assert_eq!(client.signers().len(), 2);
+
+        client.switch_profile("admin").unwrap();
+        assert_eq!(client.signer(), admin);
+        assert_eq!(client.active_profile(), Some("admin"));
+
+        assert!(matches!(
+            client.switch_profile("treasury"),
+            Err(KeystoreError::UnknownProfile(_))
+        ));
+        assert!(matches!(
+            client.switch_signer(SuiAddress::ZERO),
+            Err(KeystoreError::UnknownSigner(_))
+        ));
+        assert_eq!(client.signer(), admin);
+    }
+
+    #[cfg(feature = "signing")]
     #[tokio::test]
     #[ignore] // Ignored by default - requires network connection and valid key
     async fn test_create_client_with_key() {
@@ -0,0 +1,179 @@
+//! Latency and response size budgets for RPC calls
+//!
+//! A `CallBudget` wraps a single call: it fails with `BudgetError::Timeout` if the
+//! call takes too long, and with `BudgetError::ResponseTooLarge` if the decoded
+//! response would serialize to more than the allowed number of bytes. Size is measured
+//! with a writer that stops as soon as the limit is crossed, so checking a huge
+//! response costs no more than the limit itself.
+
+use crate::error::BudgetError;
+use serde::Serialize;
+use std::future::Future;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Per-call limits on latency and response size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CallBudget {
+    /// Maximum time the call may take (`None` for no limit)
+    pub max_latency: Option<Duration>,
+    /// Maximum JSON-serialized size of the response in bytes (`None` for no limit)
+    pub max_response_bytes: Option<usize>,
+}
+
+impl CallBudget {
+    /// A budget without limits
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Set the latency limit
+    pub fn with_max_latency(mut self, limit: Duration) -> Self {
+        self.max_latency = Some(limit);
+        self
+    }
+
+    /// Set the response size limit
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = Some(limit);
+        self
+    }
+
+    /// Run a call under this budget
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - Name of the call, used in error messages
+    /// * `call` - The call to run
+    ///
+    /// # Returns
+    ///
+    /// Returns the call's result, or a `BudgetError` (converted into the call's error
+    /// type) if a limit was exceeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::query_registry;
+    /// use canary_sdk::client::budget::CallBudget;
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use std::time::Duration;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let budget = CallBudget::unlimited()
+    ///     .with_max_latency(Duration::from_secs(10))
+    ///     .with_max_response_bytes(1 << 20);
+    /// let info = budget
+    ///     .run("query_registry", query_registry(&client, registry_id))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run<F, T, E>(&self, operation: &str, call: F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        T: Serialize,
+        E: From<BudgetError>,
+    {
+        let value =
+            match self.max_latency {
+                Some(limit) => tokio::time::timeout(limit, call).await.map_err(|_| {
+                    BudgetError::Timeout {
+                        operation: operation.to_string(),
+                        limit,
+                    }
+                })??,
+                None => call.await?,
+            };
+
+        if let Some(limit) = self.max_response_bytes {
+            check_size(operation, &value, limit)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Fail if `value` serializes to more than `limit` bytes of JSON
+pub fn check_size<T: Serialize>(
+    operation: &str,
+    value: &T,
+    limit: usize,
+) -> Result<(), BudgetError> {
+    let mut writer = LimitedWriter { written: 0, limit };
+    match serde_json::to_writer(&mut writer, value) {
+        Ok(()) => Ok(()),
+        Err(_) if writer.written > limit => Err(BudgetError::ResponseTooLarge {
+            operation: operation.to_string(),
+            limit,
+        }),
+        // Values that cannot be serialized are not subject to the size budget
+        Err(_) => Ok(()),
+    }
+}
+
+/// Counts bytes written and fails once the limit is crossed
+struct LimitedWriter {
+    written: usize,
+    limit: usize,
+}
+
+impl Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written += buf.len();
+        if self.written > self.limit {
+            return Err(io::Error::other("size limit exceeded"));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_size() {
+        let value = vec![0u8; 100];
+        assert!(check_size("test", &value, 10_000).is_ok());
+        assert!(matches!(
+            check_size("test", &value, 10),
+            Err(BudgetError::ResponseTooLarge { limit: 10, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_run_within_budget() {
+        let budget = CallBudget::unlimited()
+            .with_max_latency(Duration::from_secs(1))
+            .with_max_response_bytes(100);
+        let result: Result<u64, BudgetError> = budget.run("test", async { Ok(7) }).await;
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_run_timeout() {
+        let budget = CallBudget::unlimited().with_max_latency(Duration::from_millis(10));
+        let result: Result<u64, BudgetError> = budget
+            .run("slow", async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(7)
+            })
+            .await;
+        assert!(matches!(result, Err(BudgetError::Timeout { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_run_response_too_large() {
+        let budget = CallBudget::unlimited().with_max_response_bytes(8);
+        let result: Result<String, BudgetError> =
+            budget.run("big", async { Ok("x".repeat(1_000)) }).await;
+        assert!(matches!(result, Err(BudgetError::ResponseTooLarge { .. })));
+    }
+}
@@ -0,0 +1,164 @@
+//! Classification of RPC provider rejections
+//!
+//! The JSON-RPC transport reports HTTP failures as plain error messages (e.g.
+//! ``Request rejected `429` ``). `classify_rpc_error` recognizes auth, rate-limit and
+//! quota rejections in those messages and turns them into typed `ClientError`s, so
+//! callers can tell "back off", "switch provider" and "fix the API key" apart.
+
+use crate::error::ClientError;
+use std::time::Duration;
+
+/// Phrases providers use when the account's quota, rather than the request rate, is exhausted
+const QUOTA_MARKERS: &[&str] = &[
+    "quota",
+    "credit",
+    "monthly limit",
+    "daily limit",
+    "plan limit",
+    "upgrade your plan",
+];
+
+/// Classify an RPC error message into a typed `ClientError`
+///
+/// # Arguments
+///
+/// * `message` - The error message reported by the SDK or transport
+///
+/// # Returns
+///
+/// Returns `Unauthorized`, `RateLimited` or `QuotaExceeded` for HTTP 401, 403 and 429
+/// rejections, or `None` if the message is not one of these.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::errors::classify_rpc_error;
+/// use canary_sdk::error::ClientError;
+///
+/// match classify_rpc_error("Request rejected `429`") {
+///     Some(ClientError::RateLimited { retry_after }) => println!("Back off: {:?}", retry_after),
+///     Some(other) => println!("Provider rejected request: {}", other),
+///     None => println!("Not a provider rejection"),
+/// }
+/// ```
+pub fn classify_rpc_error(message: &str) -> Option<ClientError> {
+    let lower = message.to_ascii_lowercase();
+    let quota = QUOTA_MARKERS.iter().any(|marker| lower.contains(marker));
+
+    match http_status(&lower)? {
+        401 => Some(ClientError::Unauthorized(message.to_string())),
+        403 if quota => Some(ClientError::QuotaExceeded(message.to_string())),
+        403 => Some(ClientError::Unauthorized(message.to_string())),
+        429 if quota => Some(ClientError::QuotaExceeded(message.to_string())),
+        429 => Some(ClientError::RateLimited {
+            retry_after: retry_after(&lower),
+        }),
+        _ => None,
+    }
+}
+
+impl From<sui_sdk::error::Error> for ClientError {
+    fn from(error: sui_sdk::error::Error) -> Self {
+        let message = error.to_string();
+        classify_rpc_error(&message).unwrap_or(ClientError::Network(message))
+    }
+}
+
+/// Map a client construction failure, keeping provider rejections typed
+pub(crate) fn creation_error(error: sui_sdk::error::Error) -> ClientError {
+    let message = error.to_string();
+    classify_rpc_error(&message).unwrap_or(ClientError::ClientCreation(message))
+}
+
+/// Find an HTTP 401/403/429 status in a lowercased error message
+fn http_status(lower: &str) -> Option<u16> {
+    const STATUSES: [u16; 3] = [401, 403, 429];
+
+    STATUSES.into_iter().find(|status| {
+        let code = status.to_string();
+        [
+            format!("`{}`", code),
+            format!("status {}", code),
+            format!("status code {}", code),
+            format!("status code: {}", code),
+            format!("status: {}", code),
+            format!("http {}", code),
+            format!("({} ", code),
+        ]
+        .iter()
+        .any(|pattern| lower.contains(pattern.as_str()))
+    })
+}
+
+/// Parse a `retry-after` hint (in seconds) from a lowercased error message
+fn retry_after(lower: &str) -> Option<Duration> {
+    let rest = &lower[lower.find("retry-after")? + "retry-after".len()..];
+    let digits: String = rest
+        .trim_start_matches(|c: char| c == ':' || c == '=' || c == '"' || c.is_whitespace())
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_transport_rejections() {
+        assert!(matches!(
+            classify_rpc_error("Networking or low-level protocol error: Request rejected `401`"),
+            Some(ClientError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            classify_rpc_error("HTTP status client error (403 Forbidden)"),
+            Some(ClientError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            classify_rpc_error("Request rejected `429`"),
+            Some(ClientError::RateLimited { retry_after: None })
+        ));
+    }
+
+    #[test]
+    fn test_classify_quota() {
+        assert!(matches!(
+            classify_rpc_error("status code: 429, monthly quota exhausted"),
+            Some(ClientError::QuotaExceeded(_))
+        ));
+        assert!(matches!(
+            classify_rpc_error("HTTP 403: out of compute credits"),
+            Some(ClientError::QuotaExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_classify_retry_after() {
+        match classify_rpc_error("Request rejected `429` (Retry-After: 12)") {
+            Some(ClientError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(12)))
+            }
+            other => panic!("Expected RateLimited error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_classify_other_errors() {
+        assert!(classify_rpc_error("Request rejected `500`").is_none());
+        assert!(classify_rpc_error("connection refused").is_none());
+        // Status-like numbers inside object IDs are not statuses
+        assert!(classify_rpc_error("Object 0x429 not found").is_none());
+    }
+
+    #[test]
+    fn test_rate_limited_display() {
+        let error = ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "RPC provider rate limited the request (retry after 3s)"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Failover across a pool of RPC endpoints
+//!
+//! A single fullnode going down should not stop the worker. `FailoverClient` keeps
+//! one `SuiClient` per healthy endpoint of a `Network::Pool` and runs each call on the
+//! current endpoint. When a call fails with a transient error (see
+//! `retry::is_transient`), the client rotates to the next endpoint and runs the call
+//! there, until every endpoint has been tried once. Endpoints are health-checked by
+//! asking for the latest checkpoint; unhealthy ones are skipped until the next
+//! `health_check`, which also moves calls back to the first healthy endpoint in pool
+//! order, so a recovered primary is used again.
+
+use super::retry::is_transient;
+use super::{connect_url, Network};
+use crate::error::ClientError;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use sui_sdk::SuiClient;
+
+/// One endpoint of the pool
+struct Endpoint {
+    url: String,
+    client: Option<SuiClient>,
+}
+
+/// A client that rotates between RPC endpoints on transient errors
+pub struct FailoverClient {
+    endpoints: RwLock<Vec<Endpoint>>,
+    current: AtomicUsize,
+}
+
+impl FailoverClient {
+    /// Connect to every endpoint of a network
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to connect to; a `Network::Pool` contributes each of
+    ///   its URLs, any other network a single endpoint
+    ///
+    /// # Returns
+    ///
+    /// Returns a `FailoverClient` positioned on the first healthy endpoint, or a
+    /// `ClientError` if the pool is empty or no endpoint is healthy.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::query_registry;
+    /// use canary_sdk::client::failover::FailoverClient;
+    /// use canary_sdk::client::Network;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let network = Network::Pool(vec![
+    ///     "https://fullnode.mainnet.sui.io:443".to_string(),
+    ///     "https://sui-mainnet.example.com".to_string(),
+    /// ]);
+    /// let client = FailoverClient::connect(&network).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let info = client
+    ///     .run("query_registry", |client| async move {
+    ///         query_registry(&client, registry_id).await
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(network: &Network) -> Result<Self, ClientError> {
+        let urls = network.urls();
+        if urls.is_empty() {
+            return Err(ClientError::InvalidUrl(
+                "endpoint pool is empty".to_string(),
+            ));
+        }
+
+        let mut endpoints = Vec::with_capacity(urls.len());
+        for url in urls {
+            let client = match connect_url(url).await {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    tracing::warn!(endpoint = url, "Endpoint unavailable: {}", e);
+                    None
+                }
+            };
+            endpoints.push(Endpoint {
+                url: url.to_string(),
+                client,
+            });
+        }
+
+        let failover = Self {
+            endpoints: RwLock::new(endpoints),
+            current: AtomicUsize::new(0),
+        };
+        failover.first_healthy_from(0)?;
+        Ok(failover)
+    }
+
+    /// The URLs of the pool, in rotation order
+    pub fn urls(&self) -> Vec<String> {
+        self.read_endpoints()
+            .iter()
+            .map(|endpoint| endpoint.url.clone())
+            .collect()
+    }
+
+    /// The URL of the endpoint calls currently go to
+    pub fn current_url(&self) -> String {
+        let endpoints = self.read_endpoints();
+        endpoints[self.current.load(Ordering::SeqCst) % endpoints.len()]
+            .url
+            .clone()
+    }
+
+    /// The client of the endpoint calls currently go to
+    ///
+    /// # Returns
+    ///
+    /// Returns the current endpoint's client, or a `ClientError` if no endpoint is
+    /// healthy.
+    pub fn client(&self) -> Result<SuiClient, ClientError> {
+        let index = self.first_healthy_from(self.current.load(Ordering::SeqCst))?;
+        Ok(self.read_endpoints()[index]
+            .client
+            .clone()
+            .expect("healthy endpoint has a client"))
+    }
+
+    /// Probe every endpoint, reconnect the ones that have recovered, and move back to the
+    /// first healthy one
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of healthy endpoints.
+    pub async fn health_check(&self) -> usize {
+        let probes: Vec<(String, Option<SuiClient>)> = self
+            .read_endpoints()
+            .iter()
+            .map(|endpoint| (endpoint.url.clone(), endpoint.client.clone()))
+            .collect();
+
+        let mut checked = Vec::with_capacity(probes.len());
+        for (url, client) in probes {
+            let client = match client {
+                Some(client) => Some(client),
+                None => connect_url(&url).await.ok(),
+            };
+            let healthy = match &client {
+                Some(client) => client
+                    .read_api()
+                    .get_latest_checkpoint_sequence_number()
+                    .await
+                    .is_ok(),
+                None => false,
+            };
+            if !healthy {
+                tracing::warn!(endpoint = %url, "Endpoint failed health check");
+            }
+            checked.push(healthy.then_some(client).flatten());
+        }
+
+        let mut endpoints = self
+            .endpoints
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (endpoint, client) in endpoints.iter_mut().zip(checked) {
+            endpoint.client = client;
+        }
+        let healthy = health(&endpoints);
+        if let Some(index) = first_healthy(&healthy, 0) {
+            self.current.store(index, Ordering::SeqCst);
+        }
+        healthy.iter().filter(|healthy| **healthy).count()
+    }
+
+    /// Run a call, moving to the next endpoint while it fails with a transient error
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - Name of the call, used in log messages
+    /// * `call` - Creates the call's future for a given endpoint's client
+    ///
+    /// # Returns
+    ///
+    /// Returns the first successful result, or the last error once the error is not
+    /// transient or every healthy endpoint has been tried.
+    pub async fn run<F, Fut, T, E>(&self, operation: &str, mut call: F) -> Result<T, E>
+    where
+        F: FnMut(SuiClient) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display + From<ClientError>,
+    {
+        let attempts = self.read_endpoints().len();
+        let mut attempt = 1;
+        loop {
+            let index = self.first_healthy_from(self.current.load(Ordering::SeqCst))?;
+            let (url, client) = {
+                let endpoints = self.read_endpoints();
+                let endpoint = &endpoints[index];
+                (
+                    endpoint.url.clone(),
+                    endpoint
+                        .client
+                        .clone()
+                        .expect("healthy endpoint has a client"),
+                )
+            };
+
+            let error = match call(client).await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+
+            let message = format!("{:#}", error);
+            if attempt >= attempts || !is_transient(&message) {
+                return Err(error);
+            }
+
+            tracing::warn!(
+                operation,
+                endpoint = %url,
+                "Transient RPC failure, failing over: {}",
+                message
+            );
+            self.rotate_from(index);
+            attempt += 1;
+        }
+    }
+
+    /// Move past the endpoint at `index`, unless another call already has
+    fn rotate_from(&self, index: usize) {
+        let len = self.read_endpoints().len();
+        let _ = self.current.compare_exchange(
+            index,
+            (index + 1) % len,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+    }
+
+    /// Index of the first healthy endpoint at or after `start`, made current
+    fn first_healthy_from(&self, start: usize) -> Result<usize, ClientError> {
+        let endpoints = self.read_endpoints();
+        let index = first_healthy(&health(&endpoints), start).ok_or_else(|| {
+            let urls: Vec<&str> = endpoints.iter().map(|e| e.url.as_str()).collect();
+            ClientError::NoHealthyEndpoint(urls.join(", "))
+        })?;
+        self.current.store(index, Ordering::SeqCst);
+        Ok(index)
+    }
+
+    fn read_endpoints(&self) -> std::sync::RwLockReadGuard<'_, Vec<Endpoint>> {
+        self.endpoints
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Which endpoints currently have a client
+fn health(endpoints: &[Endpoint]) -> Vec<bool> {
+    endpoints.iter().map(|e| e.client.is_some()).collect()
+}
+
+/// Index of the first healthy endpoint at or after `start`, wrapping around the pool
+fn first_healthy(healthy: &[bool], start: usize) -> Option<usize> {
+    let len = healthy.len();
+    (0..len)
+        .map(|offset| (start + offset) % len)
+        .find(|&i| healthy[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_past_failed_endpoint() {
+        // A transient error on endpoint 0 moves calls to endpoint 1
+        assert_eq!(first_healthy(&[true, true, true], 1), Some(1));
+        // Unhealthy endpoints are skipped
+        assert_eq!(first_healthy(&[true, false, true], 1), Some(2));
+    }
+
+    #[test]
+    fn test_rotation_wraps_around() {
+        assert_eq!(first_healthy(&[true, true, true], 3), Some(0));
+        assert_eq!(first_healthy(&[true, false, false], 1), Some(0));
+        assert_eq!(first_healthy(&[false, true], 2), Some(1));
+    }
+
+    #[test]
+    fn test_no_healthy_endpoint() {
+        assert_eq!(first_healthy(&[false, false], 0), None);
+        assert_eq!(first_healthy(&[], 0), None);
+    }
+
+    #[test]
+    fn test_recovers_to_primary() {
+        // While the primary is down, calls stay on the fallback
+        assert_eq!(first_healthy(&[false, true], 0), Some(1));
+        // Once it is healthy again, `health_check` moves them back to it
+        assert_eq!(first_healthy(&[true, true], 0), Some(0));
+    }
+}
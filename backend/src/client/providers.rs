@@ -0,0 +1,209 @@
+//! Presets for public RPC providers that require an API key
+//!
+//! Providers are selected by name (e.g. `shinami-mainnet`). The API key is looked up
+//! through a `SecretSource` and wired into the request either as a header or as part
+//! of the URL path, so callers never have to assemble URLs with embedded keys.
+
+use super::default_headers;
+use crate::error::ClientError;
+use std::collections::HashMap;
+use sui_sdk::SuiClient;
+use sui_sdk::SuiClientBuilder;
+
+/// Where a provider expects its API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyPlacement {
+    /// Sent in the named HTTP header on every request
+    Header(&'static str),
+    /// Appended to the endpoint URL as the last path segment
+    PathSegment,
+}
+
+/// A public RPC provider preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcProvider {
+    /// Name used to select the provider in configuration
+    pub name: &'static str,
+    /// Base RPC URL
+    pub url: &'static str,
+    /// How the API key is attached to requests
+    pub key_placement: ApiKeyPlacement,
+    /// Name of the secret holding the API key
+    pub key_secret: &'static str,
+}
+
+impl RpcProvider {
+    /// Resolve the endpoint URL and headers for this provider using the given API key
+    pub fn endpoint(&self, api_key: &str) -> (String, HashMap<String, String>) {
+        match self.key_placement {
+            ApiKeyPlacement::Header(header) => {
+                let mut headers = HashMap::new();
+                headers.insert(header.to_string(), api_key.to_string());
+                (self.url.to_string(), headers)
+            }
+            ApiKeyPlacement::PathSegment => (
+                format!("{}/{}", self.url.trim_end_matches('/'), api_key),
+                HashMap::new(),
+            ),
+        }
+    }
+}
+
+/// Built-in provider presets
+pub const PROVIDERS: &[RpcProvider] = &[
+    RpcProvider {
+        name: "shinami-mainnet",
+        url: "https://api.us1.shinami.com/sui/node/v1",
+        key_placement: ApiKeyPlacement::Header("X-Api-Key"),
+        key_secret: "SHINAMI_MAINNET_API_KEY",
+    },
+    RpcProvider {
+        name: "shinami-testnet",
+        url: "https://api.us1.shinami.com/sui/node/v1",
+        key_placement: ApiKeyPlacement::Header("X-Api-Key"),
+        key_secret: "SHINAMI_TESTNET_API_KEY",
+    },
+    RpcProvider {
+        name: "blockvision-mainnet",
+        url: "https://sui-mainnet.blockvision.org/v1",
+        key_placement: ApiKeyPlacement::PathSegment,
+        key_secret: "BLOCKVISION_API_KEY",
+    },
+    RpcProvider {
+        name: "blockvision-testnet",
+        url: "https://sui-testnet.blockvision.org/v1",
+        key_placement: ApiKeyPlacement::PathSegment,
+        key_secret: "BLOCKVISION_API_KEY",
+    },
+];
+
+/// Look up a provider preset by name (case-insensitive)
+pub fn find_provider(name: &str) -> Option<&'static RpcProvider> {
+    PROVIDERS
+        .iter()
+        .find(|provider| provider.name.eq_ignore_ascii_case(name))
+}
+
+/// Source of secrets such as provider API keys
+pub trait SecretSource {
+    /// Get the secret with the given name, or `None` if it is not set
+    fn get_secret(&self, name: &str) -> Option<String>;
+}
+
+/// `SecretSource` reading secrets from environment variables
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSecretSource;
+
+impl SecretSource for EnvSecretSource {
+    fn get_secret(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok().filter(|value| !value.is_empty())
+    }
+}
+
+impl SecretSource for HashMap<String, String> {
+    fn get_secret(&self, name: &str) -> Option<String> {
+        self.get(name).cloned()
+    }
+}
+
+/// Create a Sui client for a named RPC provider preset
+///
+/// # Arguments
+///
+/// * `name` - The provider name (see `PROVIDERS`), e.g. `shinami-mainnet`
+/// * `secrets` - Where to look up the provider's API key
+///
+/// # Returns
+///
+/// Returns a `SuiClient` connected to the provider, or a `ClientError` if the provider is
+/// unknown, its API key is missing, or the connection fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::providers::{create_sui_client_with_provider, EnvSecretSource};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Reads SHINAMI_MAINNET_API_KEY from the environment
+///     let client = create_sui_client_with_provider("shinami-mainnet", &EnvSecretSource).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn create_sui_client_with_provider(
+    name: &str,
+    secrets: &dyn SecretSource,
+) -> Result<SuiClient, ClientError> {
+    let provider =
+        find_provider(name).ok_or_else(|| ClientError::UnknownProvider(name.to_string()))?;
+    let api_key = secrets
+        .get_secret(provider.key_secret)
+        .ok_or_else(|| ClientError::MissingSecret(provider.key_secret.to_string()))?;
+
+    let (url, provider_headers) = provider.endpoint(&api_key);
+    let mut headers = default_headers();
+    headers.extend(provider_headers);
+
+    SuiClientBuilder::default()
+        .custom_headers(headers)
+        .build(url)
+        .await
+        .map_err(super::errors::creation_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_provider() {
+        let provider = find_provider("shinami-mainnet").expect("preset should exist");
+        assert_eq!(provider.key_secret, "SHINAMI_MAINNET_API_KEY");
+        assert!(find_provider("SHINAMI-MAINNET").is_some());
+        assert!(find_provider("unknown-provider").is_none());
+    }
+
+    #[test]
+    fn test_provider_names_unique() {
+        for (i, a) in PROVIDERS.iter().enumerate() {
+            for b in &PROVIDERS[i + 1..] {
+                assert_ne!(a.name, b.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_endpoint_header_placement() {
+        let provider = find_provider("shinami-testnet").unwrap();
+        let (url, headers) = provider.endpoint("secret");
+        assert_eq!(url, "https://api.us1.shinami.com/sui/node/v1");
+        assert_eq!(headers.get("X-Api-Key"), Some(&"secret".to_string()));
+    }
+
+    #[test]
+    fn test_endpoint_path_placement() {
+        let provider = find_provider("blockvision-mainnet").unwrap();
+        let (url, headers) = provider.endpoint("secret");
+        assert_eq!(url, "https://sui-mainnet.blockvision.org/v1/secret");
+        assert!(headers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_client_missing_secret() {
+        let secrets: HashMap<String, String> = HashMap::new();
+        match create_sui_client_with_provider("shinami-mainnet", &secrets).await {
+            Err(ClientError::MissingSecret(name)) => assert_eq!(name, "SHINAMI_MAINNET_API_KEY"),
+            Err(e) => panic!("Expected MissingSecret error, got: {:?}", e),
+            Ok(_) => panic!("Expected MissingSecret error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_client_unknown_provider() {
+        let secrets: HashMap<String, String> = HashMap::new();
+        assert!(matches!(
+            create_sui_client_with_provider("nope", &secrets).await,
+            Err(ClientError::UnknownProvider(_))
+        ));
+    }
+}
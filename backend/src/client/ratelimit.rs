@@ -0,0 +1,179 @@
+//! Token-bucket rate limiting of RPC requests
+//!
+//! Public fullnodes ban clients that send bursts of requests, and bulk operations like
+//! member enumeration send one request per page of members. A `RateLimiter` hands out
+//! one token per request, refilled at a fixed rate up to one second's worth, and makes
+//! callers wait for a token when the bucket is empty.
+//!
+//! SDK requests go through `RateLimiter::global()`: every call made by
+//! `RetryPolicy::run`, and each page and batch of the member, snapshot, history, and
+//! relay queries. It allows `DEFAULT_REQUESTS_PER_SECOND` until reconfigured with
+//! `set_rate`.
+
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Request rate of the global limiter unless reconfigured
+pub const DEFAULT_REQUESTS_PER_SECOND: u32 = 50;
+
+/// Token-bucket limiter shared by concurrent callers
+#[derive(Debug)]
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Tokens added per second (0 for no limit)
+    rate: f64,
+    /// Tokens available; negative once waiting callers have reserved future tokens
+    tokens: f64,
+    /// When `tokens` was last refilled
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(requests_per_second: u32, now: Instant) -> Self {
+        let rate = f64::from(requests_per_second);
+        Self {
+            rate,
+            tokens: rate,
+            updated: now,
+        }
+    }
+
+    /// Take a token, returning how long the caller must wait before using it
+    fn take(&mut self, now: Instant) -> Duration {
+        if self.rate <= 0.0 {
+            return Duration::ZERO;
+        }
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated = now;
+
+        // Reserve the token even if it is not there yet, so waiters queue up in order
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_second`, with bursts of up to one
+    /// second's worth of requests (`0` for no limit)
+    pub fn new(requests_per_second: u32) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(requests_per_second, Instant::now())),
+        }
+    }
+
+    /// A limiter that never waits
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    /// The process-wide limiter applied to SDK requests
+    pub fn global() -> &'static RateLimiter {
+        static GLOBAL: OnceLock<RateLimiter> = OnceLock::new();
+        GLOBAL.get_or_init(|| RateLimiter::new(DEFAULT_REQUESTS_PER_SECOND))
+    }
+
+    /// Change the allowed rate (`0` for no limit), starting from a full bucket
+    pub fn set_rate(&self, requests_per_second: u32) {
+        *self.bucket.lock().unwrap_or_else(|e| e.into_inner()) =
+            Bucket::new(requests_per_second, Instant::now());
+    }
+
+    /// The allowed rate in requests per second (`0` for no limit)
+    pub fn rate(&self) -> u32 {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner()).rate as u32
+    }
+
+    /// Wait for a token
+    pub async fn acquire(&self) {
+        let wait = self
+            .bucket
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Wait for a token, then run a call
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::query_registry;
+    /// use canary_sdk::client::ratelimit::RateLimiter;
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Mainnet).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let limiter = RateLimiter::new(10);
+    /// let info = limiter.run(query_registry(&client, registry_id)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run<F: Future>(&self, call: F) -> F::Output {
+        self.acquire().await;
+        call.await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_wait(wait: Duration, millis: u64) {
+        let expected = Duration::from_millis(millis).as_secs_f64();
+        assert!((wait.as_secs_f64() - expected).abs() < 1e-6, "{:?}", wait);
+    }
+
+    #[test]
+    fn test_bucket_refill() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(10, start);
+
+        // A full second's worth of requests goes through at once
+        for _ in 0..10 {
+            assert_eq!(bucket.take(start), Duration::ZERO);
+        }
+        // Then each request waits for its own token
+        assert_wait(bucket.take(start), 100);
+        assert_wait(bucket.take(start), 200);
+
+        // Tokens refill over time, but never beyond one second's worth
+        let later = start + Duration::from_secs(10);
+        for _ in 0..10 {
+            assert_eq!(bucket.take(later), Duration::ZERO);
+        }
+        assert!(bucket.take(later) > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(0, start);
+        for _ in 0..1000 {
+            assert_eq!(bucket.take(start), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_set_rate() {
+        let limiter = RateLimiter::new(DEFAULT_REQUESTS_PER_SECOND);
+        assert_eq!(limiter.rate(), 50);
+        limiter.set_rate(5);
+        assert_eq!(limiter.rate(), 5);
+        assert_eq!(RateLimiter::unlimited().rate(), 0);
+    }
+}
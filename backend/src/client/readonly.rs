@@ -0,0 +1,196 @@
+//! Clients that cannot execute transactions
+//!
+//! Dashboards and analytics deployments only read the chain and must never sign.
+//! A `ReadOnlyClient` holds no keys and exposes only the read APIs of its `SuiClient`:
+//! it has no `quorum_driver_api()`, so it cannot submit transactions, and no SDK
+//! function that signs accepts it. Code given a `ReadOnlyClient` therefore cannot
+//! reach `execute`, `store_blob`, or any other write path; this is checked by the
+//! compiler rather than at run time.
+//!
+//! ```compile_fail
+//! use canary_sdk::client::readonly::ReadOnlyClient;
+//!
+//! fn submit(client: &ReadOnlyClient) {
+//!     // ReadOnlyClient has no execution API
+//!     let _ = client.quorum_driver_api();
+//! }
+//! ```
+//!
+//! Nor can it be handed to code that signs:
+//!
+//! ```compile_fail
+//! use canary_sdk::client::readonly::ReadOnlyClient;
+//! use canary_sdk::transaction::CanaryTransactionBuilder;
+//!
+//! fn sign(client: ReadOnlyClient) {
+//!     // Building a transaction needs a SuiClientWithSigner
+//!     let _ = CanaryTransactionBuilder::new(client);
+//! }
+//! ```
+//!
+//! A `SuiClientWithSigner` is turned into one with `read_only()`, which drops its
+//! keystore.
+
+use crate::canary::{
+    query_all_canary_blobs, query_all_members, query_blob_by_domain, query_canary_blob,
+    query_member, query_registry, query_registry_transactions, CanaryBlobInfo, MemberInfo,
+    MemberInfoWithAddress, RegistryInfo, RegistryTransactionPage,
+};
+use crate::error::{CanaryError, ClientError};
+use sui_sdk::apis::{CoinReadApi, EventApi, GovernanceApi, ReadApi};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// A Sui client restricted to reading
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::readonly::ReadOnlyClient;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ReadOnlyClient::new(create_sui_client(Network::Mainnet).await?);
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// for member in client.members(registry_id).await? {
+///     println!("{}: {}", member.member, member.domain);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ReadOnlyClient {
+    client: SuiClient,
+}
+
+impl ReadOnlyClient {
+    /// Restrict a client to reading
+    pub fn new(client: SuiClient) -> Self {
+        Self { client }
+    }
+
+    /// The read API (objects, transactions, checkpoints)
+    pub fn read_api(&self) -> &ReadApi {
+        self.client.read_api()
+    }
+
+    /// The coin read API (balances, coins, supply)
+    pub fn coin_read_api(&self) -> &CoinReadApi {
+        self.client.coin_read_api()
+    }
+
+    /// The event API
+    pub fn event_api(&self) -> &EventApi {
+        self.client.event_api()
+    }
+
+    /// The governance API (system state, reference gas price)
+    pub fn governance_api(&self) -> &GovernanceApi {
+        self.client.governance_api()
+    }
+
+    /// Check that the node still answers, returning its latest checkpoint
+    pub async fn health_check(&self) -> Result<u64, ClientError> {
+        self.client
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .map_err(|e| ClientError::Network(e.to_string()))
+    }
+
+    /// Reconnect if the node stopped answering (see `SuiClientWithSigner::ensure_connected`)
+    pub async fn ensure_connected<F, E>(&mut self, reconnect: F) -> Result<bool, E>
+    where
+        F: std::future::Future<Output = Result<SuiClient, E>>,
+    {
+        match self.health_check().await {
+            Ok(_) => Ok(false),
+            Err(e) => {
+                tracing::warn!("RPC health check failed, reconnecting: {}", e);
+                self.client = reconnect.await?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// A registry's fee, member count, and admin
+    pub async fn registry(&self, registry_id: ObjectID) -> Result<RegistryInfo, CanaryError> {
+        query_registry(&self.client, registry_id).await
+    }
+
+    /// A member's info, or `None` if `address` is not a member
+    pub async fn member(
+        &self,
+        registry_id: ObjectID,
+        address: SuiAddress,
+    ) -> Result<Option<MemberInfo>, CanaryError> {
+        query_member(&self.client, registry_id, address).await
+    }
+
+    /// All members of a registry
+    pub async fn members(
+        &self,
+        registry_id: ObjectID,
+    ) -> Result<Vec<MemberInfoWithAddress>, CanaryError> {
+        query_all_members(&self.client, registry_id).await
+    }
+
+    /// All canary blobs stored in a registry
+    pub async fn blobs(&self, registry_id: ObjectID) -> Result<Vec<CanaryBlobInfo>, CanaryError> {
+        query_all_canary_blobs(&self.client, registry_id).await
+    }
+
+    /// A canary blob by its object ID
+    pub async fn blob(&self, canary_blob_id: ObjectID) -> Result<CanaryBlobInfo, CanaryError> {
+        query_canary_blob(&self.client, canary_blob_id).await
+    }
+
+    /// The canary blob of a domain for an attested package, or `None` if there is none
+    pub async fn blob_by_domain(
+        &self,
+        registry_id: ObjectID,
+        domain: &str,
+        package_id: ObjectID,
+    ) -> Result<Option<CanaryBlobInfo>, CanaryError> {
+        query_blob_by_domain(&self.client, registry_id, domain, package_id).await
+    }
+
+    /// A page of the transactions that changed a registry, oldest first
+    pub async fn transactions(
+        &self,
+        registry_id: ObjectID,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+    ) -> Result<RegistryTransactionPage, CanaryError> {
+        query_registry_transactions(&self.client, registry_id, cursor, limit).await
+    }
+}
+
+impl From<SuiClient> for ReadOnlyClient {
+    fn from(client: SuiClient) -> Self {
+        Self::new(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{create_sui_client, Network};
+
+    #[test]
+    fn test_read_only_client_is_shareable() {
+        fn assert_shareable<T: Clone + Send + Sync>() {}
+        assert_shareable::<ReadOnlyClient>();
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored by default - requires network connection
+    async fn test_read_only_client_needs_no_key() {
+        // Only a connection is needed; no key is read from the environment
+        let client = ReadOnlyClient::new(create_sui_client(Network::Localnet).await.unwrap());
+        client.health_check().await.unwrap();
+        assert!(client.read_api().get_chain_identifier().await.is_ok());
+    }
+}
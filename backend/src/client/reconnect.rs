@@ -0,0 +1,134 @@
+//! A connection shared by several users that reconnects in place
+//!
+//! The worker's tasks and its health server hold the same `ReconnectingClient` and
+//! read the current `SuiClient` with `client()` on each use. When `ensure_connected`
+//! finds that the node stopped answering, it replaces the connection once, and every
+//! holder uses the new one from its next call on.
+
+use crate::error::ClientError;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::RwLock;
+use sui_sdk::SuiClient;
+
+/// Opens a new connection
+type Connect = Box<dyn Fn() -> BoxFuture<'static, Result<SuiClient, ClientError>> + Send + Sync>;
+
+/// A shared `SuiClient` that is replaced when the node stops answering
+pub struct ReconnectingClient {
+    current: RwLock<SuiClient>,
+    connect: Option<Connect>,
+    /// Held while checking, so concurrent callers reconnect once
+    checking: tokio::sync::Mutex<()>,
+}
+
+impl ReconnectingClient {
+    /// Share `client`, without reconnecting it (see `with_reconnect`)
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            current: RwLock::new(client),
+            connect: None,
+            checking: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Reconnect with `connect` when `ensure_connected` finds the node unresponsive
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::reconnect::ReconnectingClient;
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Testnet).await?;
+    /// let shared = Arc::new(
+    ///     ReconnectingClient::new(client).with_reconnect(|| create_sui_client(Network::Testnet)),
+    /// );
+    /// loop {
+    ///     shared.ensure_connected().await?;
+    ///     let checkpoint = shared
+    ///         .client()
+    ///         .read_api()
+    ///         .get_latest_checkpoint_sequence_number()
+    ///         .await?;
+    ///     println!("Latest checkpoint: {}", checkpoint);
+    ///     tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+    /// }
+    /// # }
+    /// ```
+    pub fn with_reconnect<F, Fut>(mut self, connect: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SuiClient, ClientError>> + Send + 'static,
+    {
+        self.connect = Some(Box::new(move || Box::pin(connect())));
+        self
+    }
+
+    /// The current connection
+    pub fn client(&self) -> SuiClient {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Reconnect if the node stopped answering
+    ///
+    /// Without `with_reconnect`, the connection is kept as is and nothing is checked.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the connection was replaced, `false` if it was healthy, or a
+    /// `ClientError` if reconnecting failed (the old connection is kept).
+    pub async fn ensure_connected(&self) -> Result<bool, ClientError> {
+        let Some(connect) = &self.connect else {
+            return Ok(false);
+        };
+        let _checking = self.checking.lock().await;
+        match self
+            .client()
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+        {
+            Ok(_) => Ok(false),
+            Err(e) => {
+                tracing::warn!("RPC health check failed, reconnecting: {}", e);
+                let client = connect().await?;
+                *self
+                    .current
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = client;
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl From<SuiClient> for ReconnectingClient {
+    fn from(client: SuiClient) -> Self {
+        Self::new(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{create_sui_client, Network};
+
+    #[tokio::test]
+    #[ignore] // Ignored by default - requires network connection
+    async fn test_healthy_client_is_kept() {
+        let client = create_sui_client(Network::Testnet).await.unwrap();
+        let shared =
+            ReconnectingClient::new(client).with_reconnect(|| create_sui_client(Network::Testnet));
+        assert!(!shared.ensure_connected().await.unwrap());
+        assert!(ReconnectingClient::new(shared.client())
+            .ensure_connected()
+            .await
+            .is_ok());
+    }
+}
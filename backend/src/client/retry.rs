@@ -0,0 +1,327 @@
+//! Retries with exponential backoff for transient RPC failures
+//!
+//! Public fullnodes regularly answer with 429s, 502s or timeouts under load. A
+//! `RetryPolicy` reruns a call when its error looks transient (see `is_transient`),
+//! waiting `initial_backoff * multiplier^n` (capped at `max_backoff`, with random
+//! jitter) between attempts. A `retry-after` hint from the provider is honored when it
+//! asks for a longer wait. Permanent failures such as bad arguments, auth or quota
+//! errors are returned immediately.
+
+use super::errors::classify_rpc_error;
+use super::ratelimit::RateLimiter;
+use crate::error::ClientError;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use std::collections::hash_map::RandomState;
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Error message fragments that indicate a transient failure
+const TRANSIENT_MARKERS: &[&str] = &[
+    "`502`",
+    "`503`",
+    "`504`",
+    "502 bad gateway",
+    "503 service unavailable",
+    "504 gateway timeout",
+    "timed out",
+    "timeout",
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "broken pipe",
+    "temporarily unavailable",
+    "too many requests",
+];
+
+/// How often and how patiently to retry a failing call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first (1 disables retries)
+    pub max_attempts: u32,
+    /// Wait before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the wait between attempts
+    pub max_backoff: Duration,
+    /// Factor applied to the wait after each retry
+    pub multiplier: f64,
+    /// Fraction of each wait that is randomized (0.0 to 1.0)
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that makes a single attempt
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set the total number of attempts
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the initial and maximum backoff
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Set the randomized fraction of each wait
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The wait before retry number `retry` (0-based), before jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.min(i32::MAX as u32) as i32);
+        let wait = self.initial_backoff.as_secs_f64() * factor;
+        Duration::from_secs_f64(wait.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// The wait before retry number `retry`, with jitter applied
+    fn jittered_backoff(&self, retry: u32) -> Duration {
+        let base = self.backoff(retry);
+        if self.jitter <= 0.0 {
+            return base;
+        }
+        // Scale into [1 - jitter, 1]; waits never exceed the computed backoff
+        let unit = random_u64() as f64 / u64::MAX as f64;
+        base.mul_f64(1.0 - self.jitter * unit)
+    }
+
+    /// Run a call, retrying it while it fails with a transient error
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - Name of the call, used in log messages
+    /// * `call` - Creates the call's future; invoked once per attempt
+    ///
+    /// # Returns
+    ///
+    /// Returns the first successful result, or the last error once the error is not
+    /// transient or the attempts are exhausted. Each attempt waits for a token from
+    /// `RateLimiter::global()`. With the `metrics` feature, the operation's total
+    /// latency, including retries, is recorded in `Metrics::global()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::query_registry;
+    /// use canary_sdk::client::retry::RetryPolicy;
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let info = RetryPolicy::default()
+    ///     .run("query_registry", || query_registry(&client, registry_id))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run<F, Fut, T, E>(&self, operation: &str, call: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+        let result = self
+            .run_untimed(operation, || {
+                let attempt = call();
+                RateLimiter::global().run(attempt)
+            })
+            .instrument(tracing::info_span!("rpc", operation))
+            .await;
+        #[cfg(feature = "metrics")]
+        Metrics::global().record_rpc(operation, started.elapsed(), result.is_ok());
+        result
+    }
+
+    /// `run` without recording latency metrics, for calls that are not RPC operations
+    pub(crate) async fn run_untimed<F, Fut, T, E>(
+        &self,
+        operation: &str,
+        mut call: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match call().await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+
+            let message = format!("{:#}", error);
+            if attempt >= self.max_attempts || !is_transient(&message) {
+                return Err(error);
+            }
+
+            let mut wait = self.jittered_backoff(attempt - 1);
+            if let Some(ClientError::RateLimited {
+                retry_after: Some(hint),
+            }) = classify_rpc_error(&message)
+            {
+                wait = wait.max(hint);
+            }
+            tracing::warn!(
+                operation,
+                attempt,
+                max_attempts = self.max_attempts,
+                "Transient RPC failure, retrying in {:?}: {}",
+                wait,
+                message
+            );
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether an error message describes a failure worth retrying
+///
+/// Rate limiting (429), gateway errors (502/503/504), timeouts and dropped connections
+/// are transient. Quota and auth rejections are not, since retrying cannot fix them.
+pub fn is_transient(message: &str) -> bool {
+    match classify_rpc_error(message) {
+        Some(ClientError::RateLimited { .. }) => return true,
+        Some(_) => return false,
+        None => {}
+    }
+    let lower = message.to_ascii_lowercase();
+    TRANSIENT_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// A random value from the standard library's per-process hasher keys
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0),
+    );
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+            .with_jitter(0.0)
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient("Request rejected `429`"));
+        assert!(is_transient("Request rejected `502`"));
+        assert!(is_transient("error sending request: operation timed out"));
+        assert!(is_transient("Connection reset by peer"));
+        assert!(!is_transient("Request rejected `401`"));
+        assert!(!is_transient("status code: 429, monthly quota exhausted"));
+        assert!(!is_transient("Object 0x123 not found"));
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+            .with_jitter(0.0);
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(60), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(0.5);
+        for _ in 0..100 {
+            let wait = policy.jittered_backoff(0);
+            assert!(wait >= Duration::from_millis(50) && wait <= Duration::from_millis(100));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<u32, String> = fast_policy()
+            .run("flaky", || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err("Request rejected `502`".to_string()),
+                    n => Ok(n),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), String> = fast_policy()
+            .with_max_attempts(3)
+            .run("down", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("operation timed out".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_does_not_retry_permanent_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), String> = fast_policy()
+            .run("bad", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("Invalid object ID".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
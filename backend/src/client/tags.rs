@@ -0,0 +1,123 @@
+//! Per-request tags for attributing RPC traffic
+//!
+//! Tags are free-form `key=value` labels (typically at least a `feature`) attached to
+//! a request future. They are recorded on a tracing span wrapping the request, so
+//! traces and any metrics derived from spans can be broken down by feature.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use tracing::instrument::Instrumented;
+use tracing::Instrument;
+
+/// Labels attached to an RPC request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTags {
+    labels: BTreeMap<String, String>,
+}
+
+impl RequestTags {
+    /// Create an empty tag set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tag set with the `feature` label set
+    pub fn feature(name: &str) -> Self {
+        Self::new().with("feature", name)
+    }
+
+    /// Add or replace a label
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Get a label's value
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    /// Iterate over the labels in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.labels.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Create the tracing span recording these tags for `operation`
+    pub fn span(&self, operation: &str) -> tracing::Span {
+        tracing::info_span!(
+            "rpc",
+            operation,
+            feature = self.get("feature").unwrap_or(""),
+            tags = %self
+        )
+    }
+}
+
+/// Formats as `key=value` pairs separated by commas, in key order
+impl fmt::Display for RequestTags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Attach `RequestTags` to a request future
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_registry;
+/// use canary_sdk::client::tags::{RequestTags, Tagged};
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let tags = RequestTags::feature("member-sync").with("job", "nightly");
+/// let info = query_registry(&client, registry_id)
+///     .tagged(&tags, "query_registry")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Tagged: Future + Sized {
+    /// Run this future inside a span carrying `tags`
+    fn tagged(self, tags: &RequestTags, operation: &str) -> Instrumented<Self> {
+        self.instrument(tags.span(operation))
+    }
+}
+
+impl<F: Future> Tagged for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_display_sorted() {
+        let tags = RequestTags::feature("snapshot").with("job", "nightly");
+        assert_eq!(tags.to_string(), "feature=snapshot,job=nightly");
+        assert_eq!(RequestTags::new().to_string(), "");
+    }
+
+    #[test]
+    fn test_tags_replace() {
+        let tags = RequestTags::feature("a").with("feature", "b");
+        assert_eq!(tags.get("feature"), Some("b"));
+        assert_eq!(tags.iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tagged_future_output() {
+        let tags = RequestTags::feature("test");
+        let value = async { 42 }.tagged(&tags, "answer").await;
+        assert_eq!(value, 42);
+    }
+}
@@ -0,0 +1,175 @@
+//! Move view function calls
+//!
+//! A view function is run with `dev_inspect_transaction_block`: nothing is signed or
+//! executed, so no key, gas coin, or budget is needed, and the node uses the reference
+//! gas price. `view_call` decodes the return values with BCS into any
+//! `DeserializeOwned` type. A function returning several values decodes into a tuple,
+//! since a BCS tuple is its fields' encodings concatenated.
+
+use crate::error::ClientError;
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{CallArg, TransactionKind};
+use sui_sdk::SuiClient;
+use sui_types::{Identifier, TypeTag};
+
+/// Call a Move view function and decode its return values
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for the call
+/// * `package_id` - The package defining the module
+/// * `module` - The module name
+/// * `function` - The function name
+/// * `type_args` - The function's type arguments, if it is generic
+/// * `args` - The function arguments
+///
+/// # Returns
+///
+/// Returns the decoded return value (a tuple for several values, `()` for none), or
+/// `ClientError::ViewCall` if the call aborts or the values don't decode into `T`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_sui_client, view_call, Network};
+/// use sui_sdk::types::base_types::ObjectID;
+/// use sui_sdk::types::transaction::{CallArg, ObjectArg, SharedObjectMutability};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let package_id = ObjectID::from_hex_literal("0x123...")?;
+/// let pool = CallArg::Object(ObjectArg::SharedObject {
+///     id: ObjectID::from_hex_literal("0x456...")?,
+///     initial_shared_version: 7.into(),
+///     mutability: SharedObjectMutability::Immutable,
+/// });
+/// // fun reserves(pool: &Pool): (u64, u64)
+/// let (a, b): (u64, u64) =
+///     view_call(&client, package_id, "pool", "reserves", vec![], vec![pool]).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn view_call<T: DeserializeOwned>(
+    client: &SuiClient,
+    package_id: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    args: Vec<CallArg>,
+) -> Result<T, ClientError> {
+    let values = view_call_raw(client, package_id, module, function, type_args, args, None).await?;
+    decode_return_values(&values)
+}
+
+/// Call a Move view function and return the BCS bytes of each return value
+///
+/// `epoch` sets the transaction context's epoch (`None` for the current one); shared
+/// objects are still read at their latest version.
+pub async fn view_call_raw(
+    client: &SuiClient,
+    package_id: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    args: Vec<CallArg>,
+    epoch: Option<u64>,
+) -> Result<Vec<Vec<u8>>, ClientError> {
+    let module_id = Identifier::from_str(module)
+        .map_err(|e| ClientError::ViewCall(format!("Invalid module name: {}", e)))?;
+    let function_id = Identifier::from_str(function)
+        .map_err(|e| ClientError::ViewCall(format!("Invalid function name: {}", e)))?;
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .move_call(package_id, module_id, function_id, type_args, args)
+        .map_err(|e| ClientError::ViewCall(format!("Failed to build move call: {}", e)))?;
+    let tx = TransactionKind::programmable(builder.finish());
+
+    // Any sender works: nothing is signed and no gas is charged
+    let result = client
+        .read_api()
+        .dev_inspect_transaction_block(
+            SuiAddress::ZERO,
+            tx,
+            None,
+            epoch.map(move_core_types::big_int::BigInt::from),
+            None,
+        )
+        .await
+        .map_err(|e| ClientError::Network(format!("dev_inspect failed: {}", e)))?;
+
+    if let Some(error) = result.error {
+        return Err(ClientError::ViewCall(format!(
+            "{}::{} failed: {}",
+            module, function, error
+        )));
+    }
+    let call = result
+        .results
+        .and_then(|results| results.into_iter().last())
+        .ok_or_else(|| {
+            ClientError::ViewCall(format!("{}::{} returned no results", module, function))
+        })?;
+    Ok(call
+        .return_values
+        .into_iter()
+        .map(|(bytes, _)| bytes)
+        .collect())
+}
+
+/// Decode BCS return values into `T`, a tuple if there are several
+pub fn decode_return_values<T: DeserializeOwned>(values: &[Vec<u8>]) -> Result<T, ClientError> {
+    bcs::from_bytes(&values.concat()).map_err(|e| {
+        ClientError::ViewCall(format!(
+            "Failed to decode {} return value(s) as {}: {}",
+            values.len(),
+            std::any::type_name::<T>(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_single_value() {
+        let values = vec![bcs::to_bytes(&true).unwrap()];
+        assert!(decode_return_values::<bool>(&values).unwrap());
+
+        let address = SuiAddress::random_for_testing_only();
+        let values = vec![bcs::to_bytes(&address).unwrap()];
+        assert_eq!(
+            decode_return_values::<SuiAddress>(&values).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_decode_tuple() {
+        let values = vec![
+            bcs::to_bytes(&"example.com".to_string()).unwrap(),
+            bcs::to_bytes(&42u64).unwrap(),
+        ];
+        let (domain, joined_at): (String, u64) = decode_return_values(&values).unwrap();
+        assert_eq!(domain, "example.com");
+        assert_eq!(joined_at, 42);
+
+        decode_return_values::<()>(&[]).unwrap();
+    }
+
+    #[test]
+    fn test_decode_mismatch() {
+        let values = vec![bcs::to_bytes(&42u64).unwrap()];
+        assert!(matches!(
+            decode_return_values::<(u64, u64)>(&values),
+            Err(ClientError::ViewCall(_))
+        ));
+        // Trailing bytes are an error too
+        assert!(decode_return_values::<bool>(&values).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Time sources for freshness and expiry logic
+//!
+//! Anything that decides whether a canary is stale reads the current time through a
+//! `TimeSource` instead of the system clock, so tests can substitute simulated time
+//! (see `testing::SimulatedClock`) and localnet runs can follow chain time.
+
+use crate::canary::{get_object_fields, json_u64};
+use crate::error::CanaryError;
+use async_trait::async_trait;
+use std::time::Duration;
+use sui_sdk::types::SUI_CLOCK_OBJECT_ID;
+use sui_sdk::SuiClient;
+
+/// Source of the current time, in Unix milliseconds
+#[async_trait]
+pub trait TimeSource: Send + Sync {
+    /// The current time in milliseconds
+    async fn now_ms(&self) -> Result<u64, CanaryError>;
+}
+
+/// `TimeSource` reading the local system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl TimeSource for SystemClock {
+    async fn now_ms(&self) -> Result<u64, CanaryError> {
+        Ok(std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0))
+    }
+}
+
+/// `TimeSource` reading the on-chain `0x6::clock::Clock`
+///
+/// This is the time Move code sees (e.g. `uploaded_at` of canary blobs), which on a
+/// localnet can drift far from the system clock.
+#[derive(Clone)]
+pub struct ChainClock {
+    client: SuiClient,
+}
+
+impl ChainClock {
+    /// Create a time source reading the chain clock through `client`
+    pub fn new(client: SuiClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TimeSource for ChainClock {
+    async fn now_ms(&self) -> Result<u64, CanaryError> {
+        let fields = get_object_fields(&self.client, SUI_CLOCK_OBJECT_ID).await?;
+        json_u64(&fields, "timestamp_ms")
+    }
+}
+
+/// Whether something last updated at `updated_at_ms` is older than `max_age` at `now_ms`
+pub fn is_stale(updated_at_ms: u64, now_ms: u64, max_age: Duration) -> bool {
+    now_ms.saturating_sub(updated_at_ms) > max_age.as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stale() {
+        let day = Duration::from_secs(86400);
+        assert!(!is_stale(1_000, 1_000, day));
+        assert!(!is_stale(0, 86_400_000, day));
+        assert!(is_stale(0, 86_400_001, day));
+        // Timestamps in the future are never stale
+        assert!(!is_stale(5_000, 1_000, day));
+    }
+
+    #[tokio::test]
+    async fn test_system_clock() {
+        assert!(SystemClock.now_ms().await.unwrap() > 1_600_000_000_000);
+    }
+}
@@ -0,0 +1,284 @@
+//! Schema versioning for exported artifacts
+//!
+//! Every JSON artifact the SDK exports (snapshots, ledgers, notarization receipts,
+//! monitor reports) carries a `schema_version` and the `sdk_version` that produced it. `load` reads an artifact of
+//! any schema version up to the current one, migrating older layouts step by step, so
+//! long-lived archives stay loadable as the structs evolve.
+//!
+//! Artifacts written before stamping was introduced have no `schema_version` and are
+//! treated as version 0.
+//!
+//! A notarization receipt's version lives in its signed body, and the body must stay
+//! byte-for-byte what the notary signed; receipts are therefore never migrated, only
+//! checked against the supported version.
+
+use crate::canary::{NotarizedReceipt, RegistryLedger};
+use crate::error::CompatError;
+#[cfg(feature = "monitor")]
+use crate::monitor::MonitorReport;
+use crate::snapshot::RegistrySnapshot;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Version of this SDK, stamped into every exported artifact
+pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An exported artifact with a versioned JSON schema
+pub trait Artifact: DeserializeOwned {
+    /// Current schema version written by this SDK
+    const SCHEMA_VERSION: u32;
+
+    /// Read the schema version of a JSON document of this artifact
+    ///
+    /// Defaults to the top-level `schema_version` (see `schema_version_of`).
+    fn schema_version(value: &Value) -> Result<u32, CompatError> {
+        schema_version_of(value)
+    }
+
+    /// Migrate a JSON document from schema version `from` to `from + 1`
+    ///
+    /// Called repeatedly by `load` until the document reaches `SCHEMA_VERSION`.
+    fn migrate(value: Value, from: u32) -> Result<Value, CompatError>;
+}
+
+/// Read the schema version of a JSON artifact (0 if unstamped)
+pub fn schema_version_of(value: &Value) -> Result<u32, CompatError> {
+    match value.get("schema_version") {
+        None | Some(Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| CompatError::Json(format!("Invalid schema_version: {}", v))),
+    }
+}
+
+/// Load an artifact from JSON, migrating it from older schema versions if needed
+///
+/// # Arguments
+///
+/// * `json` - The JSON document
+///
+/// # Returns
+///
+/// Returns the artifact in its current layout, or a `CompatError` if the document is
+/// malformed or was written by a newer SDK.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::compat::load;
+/// use canary_sdk::snapshot::RegistrySnapshot;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let json = std::fs::read_to_string("snapshot-2024-01-01.json")?;
+/// let snapshot: RegistrySnapshot = load(&json)?;
+/// println!("{} members", snapshot.members.len());
+/// # Ok(())
+/// # }
+/// ```
+pub fn load<T: Artifact>(json: &str) -> Result<T, CompatError> {
+    let value: Value = serde_json::from_str(json).map_err(|e| CompatError::Json(e.to_string()))?;
+    load_value(value)
+}
+
+/// Load an artifact from an already-parsed JSON value (see `load`)
+pub fn load_value<T: Artifact>(mut value: Value) -> Result<T, CompatError> {
+    let mut version = T::schema_version(&value)?;
+    if version > T::SCHEMA_VERSION {
+        return Err(CompatError::UnsupportedSchema {
+            found: version,
+            supported: T::SCHEMA_VERSION,
+        });
+    }
+
+    while version < T::SCHEMA_VERSION {
+        value = T::migrate(value, version)?;
+        version += 1;
+    }
+
+    serde_json::from_value(value).map_err(|e| CompatError::Json(e.to_string()))
+}
+
+/// Add the version stamp to an unstamped (version 0) document
+fn stamp_v0(mut value: Value) -> Result<Value, CompatError> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| CompatError::Json("Artifact is not a JSON object".to_string()))?;
+    object.insert("schema_version".to_string(), Value::from(1u32));
+    object
+        .entry("sdk_version")
+        .or_insert_with(|| Value::from("unknown"));
+    Ok(value)
+}
+
+impl Artifact for RegistrySnapshot {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(value: Value, from: u32) -> Result<Value, CompatError> {
+        match from {
+            // v0 -> v1: stamping introduced, layout unchanged
+            0 => stamp_v0(value),
+            _ => Err(CompatError::UnsupportedSchema {
+                found: from,
+                supported: Self::SCHEMA_VERSION,
+            }),
+        }
+    }
+}
+
+impl Artifact for RegistryLedger {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(value: Value, from: u32) -> Result<Value, CompatError> {
+        match from {
+            // v0 -> v1: stamping introduced, layout unchanged
+            0 => stamp_v0(value),
+            _ => Err(CompatError::UnsupportedSchema {
+                found: from,
+                supported: Self::SCHEMA_VERSION,
+            }),
+        }
+    }
+}
+
+/// Fail the migration of an artifact that has no older schema versions
+fn no_migration<T: Artifact>(from: u32) -> Result<Value, CompatError> {
+    Err(CompatError::UnsupportedSchema {
+        found: from,
+        supported: T::SCHEMA_VERSION,
+    })
+}
+
+impl Artifact for NotarizedReceipt {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version(value: &Value) -> Result<u32, CompatError> {
+        let body = value
+            .get("body")
+            .ok_or_else(|| CompatError::Json("Receipt has no body".to_string()))?;
+        schema_version_of(body)
+    }
+
+    fn migrate(_value: Value, from: u32) -> Result<Value, CompatError> {
+        // Migrating would change the signed body
+        no_migration::<Self>(from)
+    }
+}
+
+#[cfg(feature = "monitor")]
+impl Artifact for MonitorReport {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(_value: Value, from: u32) -> Result<Value, CompatError> {
+        // Stamped since introduction
+        no_migration::<Self>(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    #[cfg(feature = "monitor")]
+    use sui_sdk::types::base_types::ObjectID;
+
+    fn unstamped_snapshot() -> Value {
+        json!({
+            "registry_id": "0x0000000000000000000000000000000000000000000000000000000000000123",
+            "taken_at_ms": 1700000000000u64,
+            "member_count": 0,
+            "members": []
+        })
+    }
+
+    #[test]
+    fn test_schema_version_of() {
+        assert_eq!(schema_version_of(&json!({})).unwrap(), 0);
+        assert_eq!(schema_version_of(&json!({"schema_version": 3})).unwrap(), 3);
+        assert!(schema_version_of(&json!({"schema_version": "x"})).is_err());
+    }
+
+    #[test]
+    fn test_load_unstamped_snapshot() {
+        let snapshot: RegistrySnapshot = load_value(unstamped_snapshot()).unwrap();
+        assert_eq!(snapshot.schema_version, RegistrySnapshot::SCHEMA_VERSION);
+        assert_eq!(snapshot.sdk_version, "unknown");
+        assert_eq!(snapshot.taken_at_ms, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_load_current_snapshot_roundtrip() {
+        let mut value = unstamped_snapshot();
+        value["schema_version"] = json!(RegistrySnapshot::SCHEMA_VERSION);
+        value["sdk_version"] = json!(SDK_VERSION);
+
+        let snapshot: RegistrySnapshot = load(&value.to_string()).unwrap();
+        assert_eq!(snapshot.sdk_version, SDK_VERSION);
+
+        let reloaded: RegistrySnapshot = load(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+        assert_eq!(reloaded.registry_id, snapshot.registry_id);
+    }
+
+    #[test]
+    fn test_load_rejects_newer_schema() {
+        let mut value = unstamped_snapshot();
+        value["schema_version"] = json!(RegistrySnapshot::SCHEMA_VERSION + 1);
+
+        match load_value::<RegistrySnapshot>(value) {
+            Err(CompatError::UnsupportedSchema { found, supported }) => {
+                assert_eq!(found, RegistrySnapshot::SCHEMA_VERSION + 1);
+                assert_eq!(supported, RegistrySnapshot::SCHEMA_VERSION);
+            }
+            other => panic!("Expected UnsupportedSchema error, got: {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_load_unstamped_ledger() {
+        let value = json!({
+            "registry_id": "0x0000000000000000000000000000000000000000000000000000000000000123",
+            "entries": []
+        });
+        let ledger: RegistryLedger = load_value(value).unwrap();
+        assert_eq!(ledger.schema_version, RegistryLedger::SCHEMA_VERSION);
+        assert!(ledger.entries.is_empty());
+    }
+
+    #[cfg(feature = "monitor")]
+    #[test]
+    fn test_load_monitor_report() {
+        let report = MonitorReport::new(ObjectID::from_single_byte(1), 1_000, vec![]);
+        let json = serde_json::to_string(&report).unwrap();
+        let loaded: MonitorReport = load(&json).unwrap();
+        assert_eq!(loaded.schema_version, MonitorReport::SCHEMA_VERSION);
+        assert_eq!(loaded.checked_at_ms, 1_000);
+
+        // Monitor reports were never written unstamped
+        let unstamped = json!({
+            "registry_id": ObjectID::from_single_byte(1),
+            "checked_at_ms": 1_000,
+            "canaries": []
+        });
+        assert!(matches!(
+            load_value::<MonitorReport>(unstamped),
+            Err(CompatError::UnsupportedSchema { found: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_receipt_schema_version_is_read_from_body() {
+        let receipt = json!({
+            "body": {"schema_version": NotarizedReceipt::SCHEMA_VERSION + 1},
+            "notary": "0x1",
+            "signature": ""
+        });
+        assert_eq!(
+            NotarizedReceipt::schema_version(&receipt).unwrap(),
+            NotarizedReceipt::SCHEMA_VERSION + 1
+        );
+        assert!(matches!(
+            load_value::<NotarizedReceipt>(receipt),
+            Err(CompatError::UnsupportedSchema { .. })
+        ));
+    }
+}
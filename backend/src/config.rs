@@ -0,0 +1,926 @@
+//! Worker configuration
+//!
+//! The worker reads its settings from `canary.toml`, next to the `[[tasks]]` entries
+//! (see `crate::worker`):
+//!
+//! ```toml
+//! network = "testnet"
+//! registry_id = "0x123..."
+//! admin_cap_id = "0x456..."
+//! interval_seconds = 3600
+//! schedule = "0 */6 * * *"
+//! run_on_start = false
+//! log_format = "json"
+//! metrics_addr = "0.0.0.0:9184"
+//! state_path = "/app/state/worker-state.json"
+//!
+//! [key]
+//! env = "SUI_PRIVATE_KEY"
+//! expected_address = "0xabc..."
+//!
+//! [gas]
+//! max_budget = 1000000000
+//! max_price = 2000
+//!
+//! [rpc]
+//! max_latency_ms = 30000
+//! max_requests_per_second = 50
+//!
+//! [health]
+//! addr = "0.0.0.0:8080"
+//! min_balance_mist = 100000000
+//!
+//! [[notify]]
+//! url_env = "SLACK_WEBHOOK_URL"
+//! format = "slack"
+//! ```
+//!
+//! One worker can watch several registries (e.g. one per environment or product).
+//! Each `[[registries]]` entry runs its own tasks, with its own state file and
+//! webhooks; entries without `[[registries.notify]]` use the top-level webhooks, and
+//! entries without `[[registries.tasks]]` run a `members` task every
+//! `interval_seconds`. Task names are prefixed with the registry's name, and tasks
+//! without a `registry_id` operate on the entry's registry:
+//!
+//! ```toml
+//! [[registries]]
+//! name = "production"
+//! registry_id = "0x123..."
+//! state_path = "/app/state/production.json"
+//!
+//! [[registries.notify]]
+//! url_env = "PRODUCTION_SLACK_WEBHOOK_URL"
+//! format = "slack"
+//!
+//! [[registries.tasks]]
+//! kind = "monitor"
+//! interval_seconds = 3600
+//!
+//! [[registries]]
+//! name = "staging"
+//! registry_id = "0x456..."
+//! interval_seconds = 86400
+//! ```
+//!
+//! Every setting is optional in the file and can be overridden by an environment
+//! variable, so container deployments can keep a shared file and set per-instance
+//! values in the environment:
+//!
+//! | Setting | Variable |
+//! |---|---|
+//! | `network` | `SUI_NETWORK` |
+//! | `rpc_provider` | `SUI_RPC_PROVIDER` |
+//! | `registry_id` | `REGISTRY_ID` |
+//! | `admin_cap_id` | `ADMIN_CAP_ID` |
+//! | `interval_seconds` | `TASK_INTERVAL_SECONDS` |
+//! | `schedule` | `TASK_SCHEDULE` |
+//! | `run_on_start` | `TASK_RUN_ON_START` |
+//! | `log_format` | `LOG_FORMAT` |
+//! | `metrics_addr` | `METRICS_ADDR` |
+//! | `server_addr` | `SERVER_ADDR` |
+//! | `state_path` | `STATE_PATH` |
+//! | `audit_log_path` | `AUDIT_LOG_PATH` |
+//! | `key.env` | `SUI_KEY_ENV` |
+//! | `key.expected_address` | `EXPECTED_SIGNER_ADDRESS` |
+//! | `gas.estimation_budget` | `GAS_ESTIMATION_BUDGET` |
+//! | `gas.max_budget` | `GAS_MAX_BUDGET` |
+//! | `gas.fixed_budget` | `GAS_FIXED_BUDGET` |
+//! | `gas.buffer_percent` | `GAS_BUFFER_PERCENT` |
+//! | `gas.max_price` | `GAS_MAX_PRICE` |
+//! | `rpc.max_latency_ms` | `RPC_MAX_LATENCY_MS` |
+//! | `rpc.max_response_bytes` | `RPC_MAX_RESPONSE_BYTES` |
+//! | `rpc.max_requests_per_second` | `RPC_MAX_REQUESTS_PER_SECOND` |
+//! | `health.addr` | `HEALTH_ADDR` |
+//! | `health.min_balance_mist` | `HEALTH_MIN_BALANCE_MIST` |
+//!
+//! `NOTIFY_WEBHOOK_URL` (with `NOTIFY_WEBHOOK_FORMAT`, default `json`) adds a
+//! `[[notify]]` webhook rather than replacing the configured ones.
+
+use crate::client::budget::CallBudget;
+use crate::client::providers::{EnvSecretSource, SecretSource};
+use crate::client::ratelimit::DEFAULT_REQUESTS_PER_SECOND;
+use crate::client::Network;
+use crate::error::ConfigError;
+use crate::logging::LogFormat;
+use crate::notify::{Webhook, WebhookFormat};
+use crate::schedule::{CronSchedule, Schedule};
+use crate::worker::{TaskConfig, TasksConfig};
+use serde::{Deserialize, Deserializer};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+/// Settings of the `canary-worker` binary
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WorkerConfig {
+    /// Network preset, RPC URL, or comma-separated URLs for failover (default: devnet)
+    #[serde(deserialize_with = "deserialize_network")]
+    pub network: Network,
+    /// Named RPC provider preset, used instead of `network` when set
+    pub rpc_provider: Option<String>,
+    /// Registry watched by the default loop
+    pub registry_id: Option<ObjectID>,
+    /// Admin capability for the registry
+    pub admin_cap_id: Option<ObjectID>,
+    /// Seconds between runs of the default loop (default: 3600)
+    pub interval_seconds: u64,
+    /// Cron expression for the default loop, used instead of `interval_seconds`
+    #[serde(deserialize_with = "deserialize_cron")]
+    pub schedule: Option<CronSchedule>,
+    /// Run the default loop once at startup, before the first scheduled time
+    /// (default: `true`)
+    pub run_on_start: bool,
+    /// Log line format (default: text)
+    pub log_format: LogFormat,
+    /// Address to serve `/metrics` on
+    pub metrics_addr: Option<String>,
+    /// Address `canary-worker serve` serves the REST API on (default: `0.0.0.0:8000`)
+    pub server_addr: Option<String>,
+    /// File keeping worker state (e.g. the last member list seen) across restarts;
+    /// state is kept in memory when unset
+    pub state_path: Option<PathBuf>,
+    /// JSONL file recording every transaction the worker executes (see
+    /// `transaction::audit_log`); not recorded when unset
+    pub audit_log_path: Option<PathBuf>,
+    /// Where the signing key comes from
+    pub key: KeyConfig,
+    /// Gas budget limits
+    pub gas: GasConfig,
+    /// Per-call RPC budget
+    pub rpc: RpcConfig,
+    /// Health probes
+    pub health: HealthConfig,
+    /// Webhooks notified of task outcomes
+    pub notify: Vec<WebhookConfig>,
+    /// Scheduled tasks; when empty (and no registries are configured) the worker runs
+    /// the default loop
+    #[serde(flatten)]
+    pub tasks: TasksConfig,
+    /// Registries watched with their own tasks, state, and webhooks
+    pub registries: Vec<RegistryConfig>,
+}
+
+/// A `[[registries]]` entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegistryConfig {
+    /// Unique name, prefixed to the names of the registry's tasks
+    pub name: String,
+    /// The registry
+    pub registry_id: ObjectID,
+    /// Admin capability for the registry
+    pub admin_cap_id: Option<ObjectID>,
+    /// Seconds between runs of the default `members` task (default: 3600)
+    #[serde(default = "default_registry_interval")]
+    pub interval_seconds: u64,
+    /// File keeping this registry's state; kept in memory when unset
+    pub state_path: Option<PathBuf>,
+    /// Webhooks notified of this registry's task outcomes, instead of the top-level
+    /// `[[notify]]` webhooks
+    #[serde(default)]
+    pub notify: Vec<WebhookConfig>,
+    /// The registry's tasks; when empty, a `members` task
+    #[serde(default)]
+    pub tasks: Vec<TaskConfig>,
+}
+
+fn default_registry_interval() -> u64 {
+    3600
+}
+
+impl RegistryConfig {
+    /// The registry's tasks, named `<registry>/<task>` and defaulting to this registry
+    pub fn tasks(&self) -> TasksConfig {
+        let tasks = if self.tasks.is_empty() {
+            vec![TaskConfig {
+                kind: "members".to_string(),
+                name: None,
+                enabled: true,
+                interval_seconds: self.interval_seconds,
+                registry_id: None,
+                params: Default::default(),
+            }]
+        } else {
+            self.tasks.clone()
+        };
+        TasksConfig {
+            tasks: tasks
+                .into_iter()
+                .map(|task| TaskConfig {
+                    name: Some(format!("{}/{}", self.name, task.instance_name())),
+                    registry_id: task.registry_id.or(Some(self.registry_id)),
+                    ..task
+                })
+                .collect(),
+        }
+    }
+
+    /// The registry's own webhooks, with URLs read from their variables
+    pub fn webhooks(&self) -> Result<Vec<Webhook>, ConfigError> {
+        resolve_webhooks(&self.notify, &EnvSecretSource)
+    }
+}
+
+/// Signing key source
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct KeyConfig {
+    /// Environment variable holding the Bech32 private key (default: `SUI_PRIVATE_KEY`)
+    pub env: String,
+    /// Refuse to start unless the key derives this address
+    pub expected_address: Option<SuiAddress>,
+}
+
+/// Gas budget limits, in MIST
+///
+/// Unset values use the network defaults of `GasLimits::for_network`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GasConfig {
+    /// Budget for the dry run that estimates gas
+    pub estimation_budget: Option<u64>,
+    /// Largest gas budget a transaction may use
+    pub max_budget: Option<u64>,
+    /// Budget for every transaction, instead of estimating gas
+    pub fixed_budget: Option<u64>,
+    /// Safety margin over the estimated gas, in percent (default 20)
+    pub buffer_percent: Option<u64>,
+    /// Highest reference gas price to transact at, in MIST per gas unit; above it,
+    /// runs that would submit a transaction are deferred
+    pub max_price: Option<u64>,
+}
+
+/// Per-call RPC budget and request rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// Maximum latency of one call, in milliseconds (default: 30000)
+    pub max_latency_ms: u64,
+    /// Maximum response size, in bytes (default: 16 MiB)
+    pub max_response_bytes: usize,
+    /// Maximum requests per second to the RPC node, `0` for no limit (default: 50)
+    pub max_requests_per_second: u32,
+}
+
+/// Health probe settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Address to serve `/healthz`, `/readyz`, and `/metrics` on
+    pub addr: Option<String>,
+    /// Minimum signer balance for readiness, in MIST (default: 0)
+    pub min_balance_mist: u64,
+}
+
+/// A `[[notify]]` webhook
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct WebhookConfig {
+    /// Webhook URL
+    pub url: Option<String>,
+    /// Environment variable holding the webhook URL, for URLs that are secrets
+    pub url_env: Option<String>,
+    /// Payload format (default: json)
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            network: Network::Devnet,
+            rpc_provider: None,
+            registry_id: None,
+            admin_cap_id: None,
+            interval_seconds: 3600,
+            schedule: None,
+            run_on_start: true,
+            log_format: LogFormat::default(),
+            metrics_addr: None,
+            server_addr: None,
+            state_path: None,
+            audit_log_path: None,
+            key: KeyConfig::default(),
+            gas: GasConfig::default(),
+            rpc: RpcConfig::default(),
+            health: HealthConfig::default(),
+            notify: Vec::new(),
+            tasks: TasksConfig::default(),
+            registries: Vec::new(),
+        }
+    }
+}
+
+impl Default for KeyConfig {
+    fn default() -> Self {
+        Self {
+            env: "SUI_PRIVATE_KEY".to_string(),
+            expected_address: None,
+        }
+    }
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            max_latency_ms: 30_000,
+            max_response_bytes: 16 * 1024 * 1024,
+            max_requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Parse a TOML document, without environment overrides or validation
+    pub fn from_toml_str(s: &str) -> Result<Self, ConfigError> {
+        toml::from_str(s).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Load the config file, apply environment overrides, and validate the result
+    ///
+    /// A missing file is not an error: the worker then runs on defaults and the
+    /// environment alone.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::config::WorkerConfig;
+    /// use std::path::Path;
+    ///
+    /// let config = WorkerConfig::load(Path::new("canary.toml"))?;
+    /// println!("Connecting to {:?}", config.network);
+    /// # Ok::<(), canary_sdk::error::ConfigError>(())
+    /// ```
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let mut config = if path.exists() {
+            let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })?;
+            Self::from_toml_str(&contents)?
+        } else {
+            Self::default()
+        };
+        config.apply_overrides(&EnvSecretSource)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Override settings with the variables set in `source`
+    pub fn apply_overrides(&mut self, source: &dyn SecretSource) -> Result<(), ConfigError> {
+        if let Some(network) = source.get_secret("SUI_NETWORK") {
+            self.network = parse_network(&network);
+        }
+        if let Some(provider) = source.get_secret("SUI_RPC_PROVIDER") {
+            self.rpc_provider = Some(provider);
+        }
+        if let Some(id) = parse_var(source, "REGISTRY_ID", ObjectID::from_hex_literal)? {
+            self.registry_id = Some(id);
+        }
+        if let Some(id) = parse_var(source, "ADMIN_CAP_ID", ObjectID::from_hex_literal)? {
+            self.admin_cap_id = Some(id);
+        }
+        if let Some(seconds) = parse_var(source, "TASK_INTERVAL_SECONDS", u64::from_str)? {
+            self.interval_seconds = seconds;
+        }
+        if let Some(schedule) = parse_var(source, "TASK_SCHEDULE", CronSchedule::from_str)? {
+            self.schedule = Some(schedule);
+        }
+        if let Some(run) = parse_var(source, "TASK_RUN_ON_START", bool::from_str)? {
+            self.run_on_start = run;
+        }
+        if let Some(format) = parse_var(source, "LOG_FORMAT", LogFormat::from_str)? {
+            self.log_format = format;
+        }
+        if let Some(addr) = source.get_secret("METRICS_ADDR") {
+            self.metrics_addr = Some(addr);
+        }
+        if let Some(addr) = source.get_secret("SERVER_ADDR") {
+            self.server_addr = Some(addr);
+        }
+        if let Some(path) = source.get_secret("STATE_PATH") {
+            self.state_path = Some(PathBuf::from(path));
+        }
+        if let Some(path) = source.get_secret("AUDIT_LOG_PATH") {
+            self.audit_log_path = Some(PathBuf::from(path));
+        }
+        if let Some(env) = source.get_secret("SUI_KEY_ENV") {
+            self.key.env = env;
+        }
+        if let Some(address) = parse_var(source, "EXPECTED_SIGNER_ADDRESS", SuiAddress::from_str)? {
+            self.key.expected_address = Some(address);
+        }
+        if let Some(budget) = parse_var(source, "GAS_ESTIMATION_BUDGET", u64::from_str)? {
+            self.gas.estimation_budget = Some(budget);
+        }
+        if let Some(budget) = parse_var(source, "GAS_MAX_BUDGET", u64::from_str)? {
+            self.gas.max_budget = Some(budget);
+        }
+        if let Some(budget) = parse_var(source, "GAS_FIXED_BUDGET", u64::from_str)? {
+            self.gas.fixed_budget = Some(budget);
+        }
+        if let Some(percent) = parse_var(source, "GAS_BUFFER_PERCENT", u64::from_str)? {
+            self.gas.buffer_percent = Some(percent);
+        }
+        if let Some(price) = parse_var(source, "GAS_MAX_PRICE", u64::from_str)? {
+            self.gas.max_price = Some(price);
+        }
+        if let Some(ms) = parse_var(source, "RPC_MAX_LATENCY_MS", u64::from_str)? {
+            self.rpc.max_latency_ms = ms;
+        }
+        if let Some(bytes) = parse_var(source, "RPC_MAX_RESPONSE_BYTES", usize::from_str)? {
+            self.rpc.max_response_bytes = bytes;
+        }
+        if let Some(rate) = parse_var(source, "RPC_MAX_REQUESTS_PER_SECOND", u32::from_str)? {
+            self.rpc.max_requests_per_second = rate;
+        }
+        if let Some(addr) = source.get_secret("HEALTH_ADDR") {
+            self.health.addr = Some(addr);
+        }
+        if let Some(mist) = parse_var(source, "HEALTH_MIN_BALANCE_MIST", u64::from_str)? {
+            self.health.min_balance_mist = mist;
+        }
+        if source.get_secret("NOTIFY_WEBHOOK_URL").is_some() {
+            self.notify.push(WebhookConfig {
+                url: None,
+                url_env: Some("NOTIFY_WEBHOOK_URL".to_string()),
+                format: parse_var(source, "NOTIFY_WEBHOOK_FORMAT", WebhookFormat::from_str)?
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that the settings are consistent
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.network.urls().iter().any(|url| url.is_empty()) {
+            return Err(ConfigError::Invalid(
+                "network must not be empty".to_string(),
+            ));
+        }
+        if self.interval_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "interval_seconds must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(cron) = &self.schedule {
+            if cron.next_after(std::time::SystemTime::now()).is_none() {
+                return Err(ConfigError::Invalid(format!(
+                    "schedule {} never matches",
+                    cron
+                )));
+            }
+        }
+        if self.key.env.is_empty() {
+            return Err(ConfigError::Invalid(
+                "key.env must not be empty".to_string(),
+            ));
+        }
+        if let (Some(estimation), Some(max)) = (self.gas.estimation_budget, self.gas.max_budget) {
+            if estimation > max {
+                return Err(ConfigError::Invalid(format!(
+                    "gas.estimation_budget ({}) exceeds gas.max_budget ({})",
+                    estimation, max
+                )));
+            }
+        }
+        if let (Some(fixed), Some(max)) = (self.gas.fixed_budget, self.gas.max_budget) {
+            if fixed > max {
+                return Err(ConfigError::Invalid(format!(
+                    "gas.fixed_budget ({}) exceeds gas.max_budget ({})",
+                    fixed, max
+                )));
+            }
+        }
+        if self.gas.fixed_budget.is_some() && self.gas.buffer_percent.is_some() {
+            return Err(ConfigError::Invalid(
+                "gas.fixed_budget and gas.buffer_percent are mutually exclusive".to_string(),
+            ));
+        }
+        if self.rpc.max_latency_ms == 0 {
+            return Err(ConfigError::Invalid(
+                "rpc.max_latency_ms must be greater than 0".to_string(),
+            ));
+        }
+        validate_webhooks(&self.notify)?;
+        if self.tasks.tasks.is_empty() && self.registries.is_empty() && self.registry_id.is_none() {
+            return Err(ConfigError::Invalid(
+                "registry_id (or REGISTRY_ID) is required when no [[tasks]] or [[registries]] are configured"
+                    .to_string(),
+            ));
+        }
+        self.tasks.validate()?;
+
+        let mut names = std::collections::HashSet::new();
+        for registry in &self.registries {
+            if registry.name.is_empty() || registry.name.contains('/') {
+                return Err(ConfigError::Invalid(format!(
+                    "Registry name {:?} must be non-empty and must not contain '/'",
+                    registry.name
+                )));
+            }
+            if !names.insert(registry.name.as_str()) {
+                return Err(ConfigError::Invalid(format!(
+                    "Duplicate registry name {}",
+                    registry.name
+                )));
+            }
+            if registry.interval_seconds == 0 {
+                return Err(ConfigError::Invalid(format!(
+                    "Registry {}: interval_seconds must be greater than 0",
+                    registry.name
+                )));
+            }
+            validate_webhooks(&registry.notify)?;
+            registry.tasks().validate()?;
+        }
+        Ok(())
+    }
+
+    /// Time between runs of the default loop
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds)
+    }
+
+    /// Schedule of the default loop: the cron expression if set, else the interval
+    pub fn schedule(&self) -> Schedule {
+        match &self.schedule {
+            Some(cron) => Schedule::Cron(cron.clone()),
+            None => Schedule::Every(self.interval()),
+        }
+    }
+
+    /// The `[[notify]]` webhooks, with URLs read from their variables
+    pub fn webhooks(&self) -> Result<Vec<Webhook>, ConfigError> {
+        self.webhooks_from(&EnvSecretSource)
+    }
+
+    fn webhooks_from(&self, source: &dyn SecretSource) -> Result<Vec<Webhook>, ConfigError> {
+        resolve_webhooks(&self.notify, source)
+    }
+
+    /// Per-call RPC budget
+    pub fn call_budget(&self) -> CallBudget {
+        CallBudget::unlimited()
+            .with_max_latency(Duration::from_millis(self.rpc.max_latency_ms))
+            .with_max_response_bytes(self.rpc.max_response_bytes)
+    }
+
+    /// Gas limits: the network defaults, with the configured values applied
+    #[cfg(feature = "signing")]
+    pub fn gas_limits(&self) -> crate::transaction::GasLimits {
+        let mut limits = crate::transaction::GasLimits::for_network(&self.network);
+        if let Some(budget) = self.gas.estimation_budget {
+            limits.estimation_budget = budget;
+        }
+        if let Some(budget) = self.gas.max_budget {
+            limits.max_budget = budget;
+        }
+        limits.max_gas_price = self.gas.max_price;
+        limits
+    }
+
+    /// Gas policy: a fixed budget or an estimate with the configured buffer, within
+    /// `gas_limits()`
+    #[cfg(feature = "signing")]
+    pub fn gas_policy(&self) -> crate::transaction::GasPolicy {
+        use crate::transaction::GasPolicy;
+        let policy = match (self.gas.fixed_budget, self.gas.buffer_percent) {
+            (Some(budget), _) => GasPolicy::fixed(budget),
+            (None, Some(percent)) => GasPolicy::estimated(percent),
+            (None, None) => GasPolicy::default(),
+        };
+        policy.with_limits(self.gas_limits())
+    }
+}
+
+/// Read the URLs of `[[notify]]` entries from their variables
+fn resolve_webhooks(
+    entries: &[WebhookConfig],
+    source: &dyn SecretSource,
+) -> Result<Vec<Webhook>, ConfigError> {
+    entries
+        .iter()
+        .map(|webhook| {
+            let url = match (&webhook.url, &webhook.url_env) {
+                (Some(url), _) => url.clone(),
+                (None, Some(name)) => source.get_secret(name).ok_or_else(|| {
+                    ConfigError::Invalid(format!("Webhook URL variable {} is not set", name))
+                })?,
+                (None, None) => {
+                    return Err(ConfigError::Invalid(
+                        "[[notify]] entry has no url".to_string(),
+                    ))
+                }
+            };
+            Ok(Webhook {
+                url,
+                format: webhook.format,
+            })
+        })
+        .collect()
+}
+
+fn validate_webhooks(entries: &[WebhookConfig]) -> Result<(), ConfigError> {
+    for webhook in entries {
+        if webhook.url.is_some() == webhook.url_env.is_some() {
+            return Err(ConfigError::Invalid(
+                "each [[notify]] entry needs exactly one of url and url_env".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parse a network preset, RPC URL, or comma-separated URL pool
+pub fn parse_network(s: &str) -> Network {
+    match s.trim().to_lowercase().as_str() {
+        "localnet" => Network::Localnet,
+        "devnet" => Network::Devnet,
+        "testnet" => Network::Testnet,
+        "mainnet" => Network::Mainnet,
+        _ if s.contains(',') => Network::Pool(
+            s.split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        _ => Network::Custom(s.trim().to_string()),
+    }
+}
+
+fn deserialize_network<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Network, D::Error> {
+    Ok(parse_network(&String::deserialize(deserializer)?))
+}
+
+fn deserialize_cron<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<CronSchedule>, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Parse an override, naming the variable in the error
+fn parse_var<T, E: std::fmt::Display>(
+    source: &dyn SecretSource,
+    name: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Option<T>, ConfigError> {
+    source
+        .get_secret(name)
+        .map(|value| {
+            parse(value.trim())
+                .map_err(|e| ConfigError::Invalid(format!("Invalid {}: {}", name, e)))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const CONFIG: &str = r#"
+network = "testnet"
+registry_id = "0x1"
+interval_seconds = 600
+log_format = "json"
+
+[key]
+env = "CANARY_KEY"
+
+[gas]
+max_budget = 500000000
+
+[[tasks]]
+kind = "members"
+registry_id = "0x1"
+interval_seconds = 60
+"#;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = WorkerConfig::from_toml_str(CONFIG).unwrap();
+        assert_eq!(config.network, Network::Testnet);
+        assert_eq!(config.registry_id, Some(ObjectID::from_single_byte(1)));
+        assert_eq!(config.interval(), Duration::from_secs(600));
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.key.env, "CANARY_KEY");
+        assert_eq!(config.gas.max_budget, Some(500_000_000));
+        assert_eq!(config.rpc, RpcConfig::default());
+        assert_eq!(config.tasks.tasks.len(), 1);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = WorkerConfig::from_toml_str(CONFIG).unwrap();
+        config
+            .apply_overrides(&vars(&[
+                ("SUI_NETWORK", "https://a.example, https://b.example"),
+                ("TASK_INTERVAL_SECONDS", "30"),
+                ("HEALTH_ADDR", "0.0.0.0:8080"),
+                ("TASK_SCHEDULE", "0 */6 * * *"),
+                ("TASK_RUN_ON_START", "false"),
+                ("STATE_PATH", "/tmp/state.json"),
+                ("AUDIT_LOG_PATH", "/tmp/audit.jsonl"),
+                ("RPC_MAX_REQUESTS_PER_SECOND", "10"),
+            ]))
+            .unwrap();
+        assert_eq!(
+            config.network,
+            Network::Pool(vec![
+                "https://a.example".to_string(),
+                "https://b.example".to_string()
+            ])
+        );
+        assert_eq!(config.interval_seconds, 30);
+        assert_eq!(config.health.addr.as_deref(), Some("0.0.0.0:8080"));
+        assert_eq!(config.key.env, "CANARY_KEY");
+        assert!(matches!(config.schedule(), Schedule::Cron(_)));
+        assert!(!config.run_on_start);
+        assert_eq!(config.state_path, Some(PathBuf::from("/tmp/state.json")));
+        assert_eq!(
+            config.audit_log_path,
+            Some(PathBuf::from("/tmp/audit.jsonl"))
+        );
+        assert_eq!(config.rpc.max_requests_per_second, 10);
+    }
+
+    #[test]
+    #[cfg(feature = "signing")]
+    fn test_gas_policy() {
+        use crate::transaction::GasBudget;
+
+        let mut config = WorkerConfig::from_toml_str(CONFIG).unwrap();
+        assert_eq!(
+            config.gas_policy().budget,
+            GasBudget::Estimated { buffer_percent: 20 }
+        );
+        assert_eq!(config.gas_policy().limits.max_budget, 500_000_000);
+
+        config
+            .apply_overrides(&vars(&[("GAS_BUFFER_PERCENT", "50")]))
+            .unwrap();
+        assert_eq!(
+            config.gas_policy().budget,
+            GasBudget::Estimated { buffer_percent: 50 }
+        );
+
+        config.gas.buffer_percent = None;
+        config
+            .apply_overrides(&vars(&[("GAS_FIXED_BUDGET", "30000000")]))
+            .unwrap();
+        assert_eq!(config.gas_policy().budget, GasBudget::Fixed(30_000_000));
+
+        assert_eq!(config.gas_policy().limits.max_gas_price, None);
+        config
+            .apply_overrides(&vars(&[("GAS_MAX_PRICE", "2000")]))
+            .unwrap();
+        assert_eq!(config.gas_policy().limits.max_gas_price, Some(2000));
+    }
+
+    #[test]
+    fn test_invalid_override_names_variable() {
+        let mut config = WorkerConfig::default();
+        let err = config
+            .apply_overrides(&vars(&[("REGISTRY_ID", "not-an-id")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("REGISTRY_ID"));
+
+        let err = config
+            .apply_overrides(&vars(&[("TASK_SCHEDULE", "every hour")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("TASK_SCHEDULE"));
+    }
+
+    #[test]
+    fn test_webhooks() {
+        let mut config = WorkerConfig::from_toml_str(
+            r#"
+[[notify]]
+url = "https://hooks.example.com/canary"
+
+[[notify]]
+url_env = "SLACK_URL"
+format = "slack"
+"#,
+        )
+        .unwrap();
+        let env = vars(&[
+            ("SLACK_URL", "https://hooks.slack.com/services/x"),
+            ("NOTIFY_WEBHOOK_URL", "https://discord.com/api/webhooks/y"),
+            ("NOTIFY_WEBHOOK_FORMAT", "discord"),
+        ]);
+        config.apply_overrides(&env).unwrap();
+
+        let webhooks = config.webhooks_from(&env).unwrap();
+        assert_eq!(webhooks.len(), 3);
+        assert_eq!(webhooks[0].format, WebhookFormat::Json);
+        assert_eq!(webhooks[1].url, "https://hooks.slack.com/services/x");
+        assert_eq!(webhooks[2].format, WebhookFormat::Discord);
+
+        assert!(config.webhooks_from(&vars(&[])).is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        // The default loop needs a registry
+        assert!(WorkerConfig::default().validate().is_err());
+
+        let mut config = WorkerConfig {
+            registry_id: Some(ObjectID::from_single_byte(1)),
+            ..WorkerConfig::default()
+        };
+        config.validate().unwrap();
+
+        config.gas = GasConfig {
+            estimation_budget: Some(100),
+            max_budget: Some(10),
+            ..GasConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.gas = GasConfig {
+            fixed_budget: Some(100),
+            max_budget: Some(10),
+            ..GasConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.gas = GasConfig {
+            fixed_budget: Some(10),
+            buffer_percent: Some(30),
+            ..GasConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_registries() {
+        let config = WorkerConfig::from_toml_str(
+            r#"
+[[registries]]
+name = "production"
+registry_id = "0x1"
+
+[[registries.notify]]
+url = "https://hooks.example.com/production"
+
+[[registries.tasks]]
+kind = "monitor"
+interval_seconds = 3600
+
+[[registries.tasks]]
+kind = "members"
+name = "members-other"
+registry_id = "0x3"
+interval_seconds = 60
+
+[[registries]]
+name = "staging"
+registry_id = "0x2"
+interval_seconds = 86400
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.registries.len(), 2);
+
+        let production = config.registries[0].tasks();
+        assert_eq!(production.tasks[0].instance_name(), "production/monitor");
+        assert_eq!(
+            production.tasks[0].registry_id,
+            Some(ObjectID::from_single_byte(1))
+        );
+        assert_eq!(
+            production.tasks[1].instance_name(),
+            "production/members-other"
+        );
+        assert_eq!(
+            production.tasks[1].registry_id,
+            Some(ObjectID::from_single_byte(3))
+        );
+        assert_eq!(config.registries[0].webhooks().unwrap().len(), 1);
+
+        // Without tasks, a registry runs the members task on its interval
+        let staging = config.registries[1].tasks();
+        assert_eq!(staging.tasks.len(), 1);
+        assert_eq!(staging.tasks[0].instance_name(), "staging/members");
+        assert_eq!(staging.tasks[0].interval_seconds, 86400);
+
+        let mut duplicate = config.clone();
+        duplicate.registries[1].name = "production".to_string();
+        assert!(duplicate.validate().is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Domain ownership verification
+//!
+//! Before an address joins the registry for a domain, the domain owner proves control
+//! of the domain's web root by serving a challenge token at
+//! `https://<domain>/.well-known/sui-canary-challenge`. The token is derived from the
+//! domain and the joining address, so no state needs to be kept between issuing and
+//! checking it. A file may list several tokens, one per line, to authorize more than
+//! one address.
+//!
+//! Organizations usually register their apex domain (`example.com`) but serve canaries
+//! on subdomains. `SubdomainPolicy` controls whether a lookup for `sub.example.com`
+//! falls back to the registered parent, and `domain_matches` accepts wildcard
+//! registrations such as `*.example.com`.
+
+use crate::error::DomainError;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use sui_sdk::types::base_types::SuiAddress;
+
+/// Path at which the challenge token is served
+pub const CHALLENGE_PATH: &str = "/.well-known/sui-canary-challenge";
+
+/// Maximum size of a challenge file that will be read
+const MAX_CHALLENGE_BYTES: usize = 4096;
+
+/// Timeout for fetching the challenge file
+const CHALLENGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Generate the challenge token `address` must serve to prove ownership of `domain`
+///
+/// # Arguments
+///
+/// * `domain` - The domain name (e.g. `example.com`)
+/// * `address` - The address that will join the registry
+///
+/// # Returns
+///
+/// Returns the hex-encoded token, or a `DomainError` if the domain is malformed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::domain::{challenge_token, CHALLENGE_PATH};
+/// use sui_sdk::types::base_types::SuiAddress;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let address: SuiAddress = "0x456...".parse()?;
+/// let token = challenge_token("example.com", address)?;
+/// println!("Serve {} at https://example.com{}", token, CHALLENGE_PATH);
+/// # Ok(())
+/// # }
+/// ```
+pub fn challenge_token(domain: &str, address: SuiAddress) -> Result<String, DomainError> {
+    let domain = normalize_domain(domain)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"sui-canary-challenge:");
+    hasher.update(domain.as_bytes());
+    hasher.update(b":");
+    hasher.update(address.to_vec());
+    let digest = hasher.finalize();
+
+    Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Verify that `domain` serves the challenge token for `address` over HTTPS
+///
+/// # Arguments
+///
+/// * `domain` - The domain name (e.g. `example.com`)
+/// * `address` - The address that will join the registry
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the token is served, `DomainError::NotVerified` if it is not,
+/// or another `DomainError` if the domain is malformed or cannot be reached.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::domain::verify_domain_ownership_http;
+/// use sui_sdk::types::base_types::SuiAddress;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let address: SuiAddress = "0x456...".parse()?;
+/// verify_domain_ownership_http("example.com", address).await?;
+/// println!("Ownership confirmed");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn verify_domain_ownership_http(
+    domain: &str,
+    address: SuiAddress,
+) -> Result<(), DomainError> {
+    let domain = normalize_domain(domain)?;
+    let token = challenge_token(&domain, address)?;
+    let url = format!("https://{}{}", domain, CHALLENGE_PATH);
+
+    let client = reqwest::Client::builder()
+        .user_agent(crate::client::USER_AGENT)
+        .timeout(CHALLENGE_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::limited(3))
+        .build()
+        .map_err(|e| DomainError::Http(e.to_string()))?;
+
+    let mut response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| DomainError::Http(format!("{}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(DomainError::NotVerified(domain));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| DomainError::Http(format!("{}: {}", url, e)))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_CHALLENGE_BYTES {
+            return Err(DomainError::Http(format!(
+                "{}: challenge file exceeds {} bytes",
+                url, MAX_CHALLENGE_BYTES
+            )));
+        }
+    }
+
+    if body_contains_token(&String::from_utf8_lossy(&body), &token) {
+        Ok(())
+    } else {
+        Err(DomainError::NotVerified(domain))
+    }
+}
+
+/// How a host name is matched against registered domains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubdomainPolicy {
+    /// Only the exact host is considered
+    Exact,
+    /// The host and its immediate parent (`a.example.com` -> `example.com`)
+    NearestParent,
+    /// The host and every parent down to the two-label domain
+    #[default]
+    AnyParent,
+}
+
+/// List the domains to look up for `host`, most specific first
+///
+/// Parents are never walked past two labels, so `example.com` is the last candidate for
+/// `a.b.example.com`. Multi-label public suffixes such as `co.uk` are not recognized;
+/// use `SubdomainPolicy::NearestParent` or `Exact` for hosts under such suffixes.
+///
+/// # Arguments
+///
+/// * `host` - The host name (e.g. `status.example.com`)
+/// * `policy` - Which parent domains to include
+///
+/// # Returns
+///
+/// Returns the normalized candidate domains, or a `DomainError` if the host is malformed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::domain::{candidate_domains, SubdomainPolicy};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let candidates = candidate_domains("a.b.example.com", SubdomainPolicy::AnyParent)?;
+/// assert_eq!(candidates, vec!["a.b.example.com", "b.example.com", "example.com"]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn candidate_domains(host: &str, policy: SubdomainPolicy) -> Result<Vec<String>, DomainError> {
+    let host = normalize_domain(host)?;
+    let max_parents = match policy {
+        SubdomainPolicy::Exact => 0,
+        SubdomainPolicy::NearestParent => 1,
+        SubdomainPolicy::AnyParent => usize::MAX,
+    };
+
+    let mut candidates = vec![host.clone()];
+    let mut current = host.as_str();
+    while candidates.len() <= max_parents {
+        match current.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => {
+                candidates.push(parent.to_string());
+                current = parent;
+            }
+            _ => break,
+        }
+    }
+    Ok(candidates)
+}
+
+/// Whether a registered domain pattern matches `host`
+///
+/// A plain pattern (`example.com`) matches only that host. A wildcard pattern
+/// (`*.example.com`) matches any subdomain of `example.com` at any depth, but not
+/// `example.com` itself.
+///
+/// # Arguments
+///
+/// * `pattern` - The registered domain, optionally prefixed with `*.`
+/// * `host` - The host name to check
+///
+/// # Returns
+///
+/// Returns whether the pattern matches, or a `DomainError` if either name is malformed.
+pub fn domain_matches(pattern: &str, host: &str) -> Result<bool, DomainError> {
+    let host = normalize_domain(host)?;
+    match pattern.trim().strip_prefix("*.") {
+        Some(base) => {
+            let base = normalize_domain(base)?;
+            Ok(host
+                .strip_suffix(base.as_str())
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')))
+        }
+        None => Ok(normalize_domain(pattern)? == host),
+    }
+}
+
+/// Whether a member registered for `registered` may serve canaries on `host`
+///
+/// The registration covers the host if it matches directly (see `domain_matches`) or if
+/// it is one of the parent domains `policy` allows for the host.
+///
+/// # Arguments
+///
+/// * `registered` - The member's registered domain (e.g. `example.com` or `*.example.com`)
+/// * `host` - The host serving the canary
+/// * `policy` - Which parent domains of the host are accepted
+///
+/// # Returns
+///
+/// Returns whether the registration covers the host, or a `DomainError` if either name is
+/// malformed.
+pub fn registration_covers_host(
+    registered: &str,
+    host: &str,
+    policy: SubdomainPolicy,
+) -> Result<bool, DomainError> {
+    if domain_matches(registered, host)? {
+        return Ok(true);
+    }
+    if registered.trim().starts_with("*.") {
+        return Ok(false);
+    }
+    let registered = normalize_domain(registered)?;
+    Ok(candidate_domains(host, policy)?.contains(&registered))
+}
+
+/// Whether a challenge file lists `token` on one of its lines
+fn body_contains_token(body: &str, token: &str) -> bool {
+    body.lines().any(|line| line.trim() == token)
+}
+
+/// Lowercase and validate a bare domain name (no scheme, port, or path)
+fn normalize_domain(domain: &str) -> Result<String, DomainError> {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let invalid = || DomainError::InvalidDomain(domain.clone());
+
+    if domain.is_empty() || domain.len() > 253 || !domain.contains('.') {
+        return Err(invalid());
+    }
+    for label in domain.split('.') {
+        let valid = !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(invalid());
+        }
+    }
+    Ok(domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn address(hex: &str) -> SuiAddress {
+        SuiAddress::from_str(hex).unwrap()
+    }
+
+    #[test]
+    fn test_challenge_token_deterministic() {
+        let a = challenge_token("example.com", address("0x1")).unwrap();
+        let b = challenge_token("Example.COM.", address("0x1")).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn test_challenge_token_binds_address_and_domain() {
+        let base = challenge_token("example.com", address("0x1")).unwrap();
+        assert_ne!(
+            base,
+            challenge_token("example.com", address("0x2")).unwrap()
+        );
+        assert_ne!(
+            base,
+            challenge_token("example.org", address("0x1")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(
+            normalize_domain(" Sub.Example.com ").unwrap(),
+            "sub.example.com"
+        );
+        for bad in [
+            "",
+            "localhost",
+            "https://example.com",
+            "example.com/path",
+            "example.com:443",
+            "-bad.com",
+            "a..com",
+        ] {
+            assert!(normalize_domain(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_candidate_domains() {
+        assert_eq!(
+            candidate_domains("A.b.Example.com.", SubdomainPolicy::AnyParent).unwrap(),
+            vec!["a.b.example.com", "b.example.com", "example.com"]
+        );
+        assert_eq!(
+            candidate_domains("a.b.example.com", SubdomainPolicy::NearestParent).unwrap(),
+            vec!["a.b.example.com", "b.example.com"]
+        );
+        assert_eq!(
+            candidate_domains("a.example.com", SubdomainPolicy::Exact).unwrap(),
+            vec!["a.example.com"]
+        );
+        assert_eq!(
+            candidate_domains("example.com", SubdomainPolicy::AnyParent).unwrap(),
+            vec!["example.com"]
+        );
+        assert!(candidate_domains("localhost", SubdomainPolicy::AnyParent).is_err());
+    }
+
+    #[test]
+    fn test_domain_matches_wildcard() {
+        assert!(domain_matches("*.example.com", "a.example.com").unwrap());
+        assert!(domain_matches("*.example.com", "a.b.Example.com").unwrap());
+        assert!(!domain_matches("*.example.com", "example.com").unwrap());
+        assert!(!domain_matches("*.example.com", "badexample.com").unwrap());
+        assert!(domain_matches("example.com", "EXAMPLE.com.").unwrap());
+        assert!(!domain_matches("example.com", "a.example.com").unwrap());
+        assert!(domain_matches("*.", "a.example.com").is_err());
+    }
+
+    #[test]
+    fn test_registration_covers_host() {
+        let policy = SubdomainPolicy::AnyParent;
+        assert!(registration_covers_host("example.com", "a.b.example.com", policy).unwrap());
+        assert!(!registration_covers_host("example.com", "a.example.org", policy).unwrap());
+        assert!(
+            !registration_covers_host("example.com", "a.example.com", SubdomainPolicy::Exact)
+                .unwrap()
+        );
+        assert!(registration_covers_host("*.example.com", "a.example.com", policy).unwrap());
+        assert!(!registration_covers_host("*.example.com", "example.com", policy).unwrap());
+    }
+
+    #[test]
+    fn test_body_contains_token() {
+        assert!(body_contains_token("abc\n", "abc"));
+        assert!(body_contains_token("other\r\n  abc  \r\n", "abc"));
+        assert!(!body_contains_token("abcd", "abc"));
+        assert!(!body_contains_token("", "abc"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires network connection
+    async fn test_verify_unserved_domain() {
+        let result = verify_domain_ownership_http("example.com", address("0x1")).await;
+        assert!(result.is_err());
+    }
+}
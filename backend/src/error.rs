@@ -1,7 +1,13 @@
 //! Error types for the Sui Canary SDK
 
-use sui_sdk::types::base_types::SuiAddress;
+use std::time::Duration;
+use sui_sdk::rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
 use sui_sdk::types::crypto::SignatureScheme;
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::gas::GasCostSummary;
 
 /// Errors that can occur during keystore operations
 #[derive(Debug, thiserror::Error)]
@@ -22,6 +28,14 @@ pub enum KeystoreError {
     #[error("Unsupported key scheme: {0:?}")]
     UnsupportedKeyScheme(SignatureScheme),
 
+    /// Invalid BIP-39 mnemonic phrase
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    /// Invalid or unsupported derivation path for the key scheme
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
     /// Keystore operation error
     #[error("Keystore error: {0}")]
     KeystoreOperation(String),
@@ -29,6 +43,45 @@ pub enum KeystoreError {
     /// Error from Sui SDK
     #[error("Sui SDK error: {0}")]
     SuiSdkError(String),
+
+    /// Loaded key does not match the expected signer address
+    #[error("Signer address mismatch: expected {expected}, got {actual}")]
+    AddressMismatch {
+        expected: SuiAddress,
+        actual: SuiAddress,
+    },
+
+    /// Invalid multisig participants, threshold, or partial signature
+    #[error("Invalid multisig: {0}")]
+    InvalidMultisig(String),
+
+    /// The signature is from a key that is not a multisig participant
+    #[error("{0} is not a multisig participant")]
+    NotParticipant(SuiAddress),
+
+    /// The collected signatures do not reach the multisig threshold
+    #[error("Multisig signatures weigh {weight}, threshold is {threshold}")]
+    InsufficientWeight { weight: u16, threshold: u16 },
+
+    /// A remote signer (e.g. a cloud KMS) failed or returned an unusable key or signature
+    #[error("Remote signer error: {0}")]
+    Remote(String),
+
+    /// An encrypted key file cannot be read, parsed, or written
+    #[error("Key file error: {0}")]
+    KeyFile(String),
+
+    /// The keystore has no key for this address
+    #[error("No key for signer {0} in the keystore")]
+    UnknownSigner(SuiAddress),
+
+    /// No signer profile has this name
+    #[error("Unknown signer profile: {0}")]
+    UnknownProfile(String),
+
+    /// An encrypted key file did not decrypt
+    #[error("Failed to decrypt key file: wrong passphrase or modified file")]
+    DecryptionFailed,
 }
 
 /// Errors that can occur during client operations
@@ -45,6 +98,58 @@ pub enum ClientError {
     /// Invalid URL
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// Unknown RPC provider preset
+    #[error("Unknown RPC provider: {0}")]
+    UnknownProvider(String),
+
+    /// Required secret (e.g. provider API key) is not set
+    #[error("Missing secret: {0}")]
+    MissingSecret(String),
+
+    /// The provider rejected the API key (HTTP 401, or 403 without a quota message)
+    #[error("RPC provider rejected credentials: {0}")]
+    Unauthorized(String),
+
+    /// The provider is throttling requests (HTTP 429)
+    #[error("RPC provider rate limited the request{}", retry_after.map(|d| format!(" (retry after {}s)", d.as_secs())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// The account's request quota is used up; retrying before it resets will not help
+    #[error("RPC provider quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    /// None of the endpoints of a pool could be reached
+    #[error("No healthy RPC endpoint: {0}")]
+    NoHealthyEndpoint(String),
+
+    /// A Move view function aborted or returned values of an unexpected type
+    #[error("View call failed: {0}")]
+    ViewCall(String),
+
+    /// The node belongs to another chain than the configured network
+    #[error("Connected to chain {actual}, but {network} is chain {expected}")]
+    ChainMismatch {
+        /// The configured network
+        network: String,
+        /// The chain identifier the network is known to have
+        expected: String,
+        /// The chain identifier the node reported
+        actual: String,
+    },
+
+    /// An object ID belongs to the Canary deployment of another chain
+    #[error(
+        "Object {object_id} belongs to chain {object_chain}, but the client is on chain {chain}"
+    )]
+    WrongChain {
+        /// The object ID
+        object_id: ObjectID,
+        /// The chain whose deployment the object belongs to
+        object_chain: String,
+        /// The chain the client is connected to
+        chain: String,
+    },
 }
 
 /// Errors that can occur during transaction operations
@@ -55,8 +160,22 @@ pub enum TransactionError {
     BuildError(String),
 
     /// Transaction execution error
-    #[error("Transaction execution error: {0}")]
-    ExecutionError(String),
+    ///
+    /// Once the transaction was submitted, `digest` is set; once it executed, `status`,
+    /// `effects`, and `gas` describe the failure, so callers need not parse `message`.
+    #[error("Transaction execution error: {message}")]
+    ExecutionError {
+        /// What went wrong
+        message: String,
+        /// The transaction digest, if the transaction was built
+        digest: Option<TransactionDigest>,
+        /// The execution status from the effects, if the transaction executed
+        status: Option<SuiExecutionStatus>,
+        /// The raw effects, if the transaction executed
+        effects: Option<Box<SuiTransactionBlockEffects>>,
+        /// Gas charged for the failed execution, if the transaction executed
+        gas: Option<GasCostSummary>,
+    },
 
     /// Insufficient gas
     #[error("Insufficient gas: required {required}, available {available}")]
@@ -65,6 +184,63 @@ pub enum TransactionError {
     /// Object not found
     #[error("Object not found: {0}")]
     ObjectNotFound(SuiAddress),
+
+    /// The gas budget (set or estimated) is above the configured safety cap
+    #[error("Gas budget {budget} MIST exceeds cap of {cap} MIST")]
+    GasBudgetExceedsCap { budget: u64, cap: u64 },
+
+    /// The reference gas price is above the configured maximum
+    #[error("Reference gas price {price} MIST exceeds maximum of {max} MIST")]
+    GasPriceTooHigh { price: u64, max: u64 },
+
+    /// The transaction did not reach the requested finality in time
+    #[error("Transaction {digest} not final after {waited:?} (executed: {executed})")]
+    FinalityTimeout {
+        digest: String,
+        waited: Duration,
+        executed: bool,
+    },
+}
+
+impl TransactionError {
+    /// An execution error that happened before the transaction was submitted
+    pub fn execution(message: impl Into<String>) -> Self {
+        TransactionError::ExecutionError {
+            message: message.into(),
+            digest: None,
+            status: None,
+            effects: None,
+            gas: None,
+        }
+    }
+
+    /// An execution error for a transaction whose effects report a failure
+    pub fn failed_execution(effects: &SuiTransactionBlockEffects) -> Self {
+        let digest = *effects.transaction_digest();
+        let status = effects.status().clone();
+        let message = match &status {
+            SuiExecutionStatus::Failure { error } => {
+                format!("Transaction {} failed: {}", digest, error)
+            }
+            SuiExecutionStatus::Success => format!("Transaction {} failed", digest),
+        };
+        TransactionError::ExecutionError {
+            message,
+            digest: Some(digest),
+            gas: Some(effects.gas_cost_summary().clone()),
+            status: Some(status),
+            effects: Some(Box::new(effects.clone())),
+        }
+    }
+
+    /// The digest of the transaction the error is about, if known
+    pub fn digest(&self) -> Option<TransactionDigest> {
+        match self {
+            TransactionError::ExecutionError { digest, .. } => *digest,
+            TransactionError::FinalityTimeout { digest, .. } => digest.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 /// Errors that can occur during Canary contract operations
@@ -82,10 +258,49 @@ pub enum CanaryError {
     #[error("Not admin")]
     NotAdmin,
 
+    /// The address is already a member
+    #[error("Already a member")]
+    AlreadyMember,
+
+    /// The payment is below the registry fee
+    #[error("Insufficient fee")]
+    InsufficientFee,
+
+    /// The capability does not belong to the registry
+    #[error("Capability does not belong to the registry")]
+    InvalidCap,
+
+    /// A canary blob already exists for the domain and package
+    #[error("Domain already has a canary blob for this package")]
+    DomainTaken,
+
+    /// The transaction aborted with a code that has no dedicated variant
+    #[error("{module} aborted with code {code}")]
+    MoveAbort {
+        module: String,
+        function: Option<String>,
+        code: u64,
+    },
+
     /// Canary blob not found
     #[error("Canary blob not found")]
     CanaryBlobNotFound,
 
+    /// The object is not owned by the expected address
+    #[error("Object {object_id} is not owned by {owner}")]
+    NotOwner {
+        object_id: ObjectID,
+        owner: SuiAddress,
+    },
+
+    /// A write operation was called on a `CanaryClient` without a signer
+    #[error("No signer configured")]
+    NoSigner,
+
+    /// An admin operation was called on a `CanaryClient` without an AdminCap
+    #[error("No AdminCap configured")]
+    NoAdminCap,
+
     /// Transaction error
     #[error(transparent)]
     Transaction(#[from] TransactionError),
@@ -93,5 +308,406 @@ pub enum CanaryError {
     /// Client error
     #[error(transparent)]
     Client(#[from] ClientError),
+
+    /// Call exceeded its latency or response size budget
+    #[error(transparent)]
+    Budget(#[from] BudgetError),
+
+    /// Domain ownership verification failed
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+}
+
+/// Errors raised when an RPC call exceeds its budget
+#[derive(Debug, thiserror::Error)]
+pub enum BudgetError {
+    /// The call did not complete within its latency budget
+    #[error("{operation} timed out after {limit:?}")]
+    Timeout { operation: String, limit: Duration },
+
+    /// The response was larger than the allowed size
+    #[error("{operation} response exceeds {limit} bytes")]
+    ResponseTooLarge { operation: String, limit: usize },
 }
 
+/// Errors that can occur during worker state operations
+#[derive(Debug, thiserror::Error)]
+pub enum StateError {
+    /// Storage backend error
+    #[error("State backend error: {0}")]
+    Backend(String),
+
+    /// Failed to (de)serialize stored state
+    #[error("State serialization error: {0}")]
+    Serialization(String),
+}
+
+/// Errors that can occur when writing or reading the transaction audit log
+#[derive(Debug, thiserror::Error)]
+pub enum AuditLogError {
+    /// Failed to read, write, or rotate a log file
+    #[error("Audit log I/O error: {0}")]
+    Io(String),
+
+    /// A line of a log file is not a valid record
+    #[error("Invalid audit record at {file}:{line}: {message}")]
+    InvalidRecord {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
+    /// An audit log was installed before
+    #[error("An audit log is already installed")]
+    AlreadyInstalled,
+}
+
+/// Errors that can occur when loading versioned artifacts
+#[derive(Debug, thiserror::Error)]
+pub enum CompatError {
+    /// Artifact was written with a schema version this SDK cannot read
+    #[error("Unsupported schema version {found} (supported up to {supported})")]
+    UnsupportedSchema { found: u32, supported: u32 },
+
+    /// Malformed JSON document
+    #[error("Invalid artifact JSON: {0}")]
+    Json(String),
+}
+
+/// Errors that can occur while funding test accounts
+#[derive(Debug, thiserror::Error)]
+pub enum FundingError {
+    /// Faucet request failed
+    #[error("Faucet error: {0}")]
+    Faucet(String),
+
+    /// Funding account does not hold enough SUI for the requested layout
+    #[error("Insufficient funds: required {required}, available {available}")]
+    InsufficientFunds { required: u64, available: u64 },
+
+    /// Failed to load the funding key
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+
+    /// Funding transaction failed
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
+
+/// Errors that can occur during domain ownership verification
+#[derive(Debug, thiserror::Error)]
+pub enum DomainError {
+    /// The domain name is malformed
+    #[error("Invalid domain: {0}")]
+    InvalidDomain(String),
+
+    /// The challenge could not be fetched
+    #[error("Failed to fetch challenge: {0}")]
+    Http(String),
+
+    /// The domain does not serve the expected challenge token
+    #[error("Domain {0} does not serve the expected challenge token")]
+    NotVerified(String),
+}
+
+/// Errors that can occur when checking Walrus blobs
+#[derive(Debug, thiserror::Error)]
+pub enum WalrusError {
+    /// The aggregator could not be reached
+    #[error("Walrus request failed: {0}")]
+    Http(String),
+
+    /// The aggregator answered with an unexpected status
+    #[error("Walrus aggregator returned {status} for {url}")]
+    Status { url: String, status: u16 },
+}
+
+/// Errors that can occur in the blob proposal flow
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalError {
+    /// No proposal with this ID exists
+    #[error("Proposal not found: {0}")]
+    NotFound(String),
+
+    /// The proposal was already approved or rejected
+    #[error("Proposal {0} is no longer pending")]
+    NotPending(String),
+
+    /// The proposer is registered for a different domain
+    #[error("Proposer is registered for {registered}, not {proposed}")]
+    DomainMismatch {
+        registered: String,
+        proposed: String,
+    },
+
+    /// Proposal storage error
+    #[error(transparent)]
+    Store(#[from] StateError),
+
+    /// Canary contract error
+    #[error(transparent)]
+    Canary(#[from] CanaryError),
+}
+
+/// Errors that can occur when issuing or validating notarization receipts
+#[derive(Debug, thiserror::Error)]
+pub enum NotaryError {
+    /// The receipt could not be signed
+    #[error("Failed to sign receipt: {0}")]
+    Signing(String),
+
+    /// The receipt signature does not verify against the notary address
+    #[error("Invalid receipt signature: {0}")]
+    InvalidSignature(String),
+
+    /// A blob's content does not match the hash recorded in the receipt
+    #[error("Blob hash mismatch for {0}")]
+    HashMismatch(ObjectID),
+
+    /// The receipt does not record a hash for a referenced blob
+    #[error("Receipt has no hash for blob {0}")]
+    MissingHash(ObjectID),
+
+    /// Canary contract error while building the receipt
+    #[error(transparent)]
+    Canary(#[from] CanaryError),
+
+    /// The receipt bundle could not be loaded
+    #[error(transparent)]
+    Compat(#[from] CompatError),
+}
+
+/// Errors that can occur while checking an implementation against the test vectors
+#[derive(Debug, thiserror::Error)]
+pub enum VectorError {
+    /// The implementation produced another output than the vector expects
+    #[error("Test vector mismatch for {vector}: expected {expected}, got {actual}")]
+    Mismatch {
+        vector: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// A vector input could not be parsed or used
+    #[error("Invalid test vector: {0}")]
+    Invalid(String),
+}
+
+/// Errors that can occur when loading configuration
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The configuration file could not be read
+    #[error("Failed to read config {path}: {message}")]
+    Io { path: String, message: String },
+
+    /// The configuration is not valid TOML or has the wrong shape
+    #[error("Failed to parse config: {0}")]
+    Parse(String),
+
+    /// The configuration parsed but is semantically invalid
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+}
+
+/// Errors that can occur while rendering a statement template
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    /// The template file could not be read
+    #[error("Failed to read template {path}: {message}")]
+    Io { path: String, message: String },
+
+    /// The template's tags are malformed or unbalanced
+    #[error("Invalid template: {0}")]
+    Syntax(String),
+
+    /// The template uses a variable that was not provided
+    #[error("Unknown template variable: {0}")]
+    UnknownVariable(String),
+
+    /// The template uses a section toggle that was not provided
+    #[error("Unknown template toggle: {0}")]
+    UnknownToggle(String),
+
+    /// The rendered statement contains no assertions
+    #[error("Rendered statement contains no assertions")]
+    NoAssertions,
+}
+
+/// Errors that can occur while relaying registry activity to webhooks
+#[derive(Debug, thiserror::Error)]
+pub enum RelayError {
+    /// Registry transactions could not be read
+    #[error("Failed to read registry transactions: {0}")]
+    Chain(String),
+
+    /// The stored relay cursor is not a transaction digest
+    #[error("Invalid relay cursor: {0}")]
+    Cursor(String),
+
+    /// The event could not be serialized
+    #[error("Failed to encode webhook payload: {0}")]
+    Payload(String),
+
+    /// The webhook request could not be sent
+    #[error("Webhook request failed: {0}")]
+    Http(String),
+
+    /// The subscriber answered with a non-success status
+    #[error("Subscriber {url} rejected webhook with status {status}")]
+    Rejected { url: String, status: u16 },
+
+    /// Relay cursor storage error
+    #[error(transparent)]
+    State(#[from] StateError),
+}
+
+/// Errors that can occur while dual-writing to a primary and shadow registry
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// The mutation must be signed by a specific address
+    #[error("Mutation must be signed by {expected}, got {actual}")]
+    SignerMismatch {
+        expected: SuiAddress,
+        actual: SuiAddress,
+    },
+
+    /// An admin mutation was requested for a registry without a configured AdminCap
+    #[error("No AdminCap configured for registry {0}")]
+    MissingAdminCap(ObjectID),
+
+    /// Intent log error
+    #[error(transparent)]
+    Log(#[from] StateError),
+
+    /// Canary contract error
+    #[error(transparent)]
+    Canary(#[from] CanaryError),
+}
+
+/// Errors that can occur while publishing a canary
+#[derive(Debug, thiserror::Error)]
+pub enum PublishError {
+    /// An artifact file could not be read
+    #[error("Failed to read artifact {path}: {message}")]
+    Io { path: String, message: String },
+
+    /// An artifact file is empty
+    #[error("Artifact is empty: {0}")]
+    EmptyArtifact(String),
+
+    /// The domain already has a canary blob for this package
+    #[error("A canary is already published for {0}")]
+    AlreadyPublished(String),
+
+    /// Blob storage rejected an upload
+    #[error("Blob upload failed: {0}")]
+    Upload(String),
+
+    /// Canary contract error
+    #[error(transparent)]
+    Canary(#[from] CanaryError),
+}
+
+/// Errors that can occur while managing gas coins
+#[derive(Debug, thiserror::Error)]
+pub enum GasError {
+    /// Coins could not be read from chain
+    #[error("Gas coin query failed: {0}")]
+    Chain(String),
+
+    /// The owner has no coin large enough to pay for gas
+    #[error("{owner} has no SUI coin holding at least {min_balance} MIST")]
+    NoCoins { owner: SuiAddress, min_balance: u64 },
+
+    /// The coin to split does not hold enough SUI
+    #[error("Insufficient balance to split: required {required}, available {available}")]
+    InsufficientBalance { required: u64, available: u64 },
+
+    /// Splitting transaction failed
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
+
+/// Errors that can occur when parsing a schedule
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduleError {
+    /// The cron expression does not have five fields
+    #[error("Invalid cron expression {0:?}: expected 5 fields (minute hour day month weekday)")]
+    FieldCount(String),
+
+    /// A field of the cron expression is invalid
+    #[error("Invalid cron {field} field {value:?}: {reason}")]
+    Field {
+        field: &'static str,
+        value: String,
+        reason: String,
+    },
+}
+
+/// Errors that can occur while sending notifications
+#[derive(Debug, thiserror::Error)]
+pub enum NotifyError {
+    /// The webhook request could not be sent
+    #[error("Notification request failed: {0}")]
+    Http(String),
+
+    /// The webhook answered with a non-success status
+    #[error("Webhook {url} rejected notification with status {status}")]
+    Rejected { url: String, status: u16 },
+}
+
+/// Errors that can occur while indexing registry history
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    /// Registry transactions could not be read
+    #[error("Failed to read registry transactions: {0}")]
+    Chain(String),
+
+    /// A transaction's canary calls could not be decoded
+    #[error("Failed to decode transaction {digest}: {message}")]
+    Decode { digest: String, message: String },
+
+    /// SQLite error, or a stored value that no longer parses
+    #[error("Index database error: {0}")]
+    Database(String),
+}
+
+/// Errors that can occur while setting up a localnet test harness
+#[derive(Debug, thiserror::Error)]
+pub enum LocalnetError {
+    /// No localnet answered, and none could be started
+    #[error("Localnet unavailable: {0}")]
+    Unavailable(String),
+
+    /// `sui move build` failed or printed output that could not be read
+    #[error("Failed to build Move package: {0}")]
+    Build(String),
+
+    /// The publish transaction did not create the expected objects
+    #[error("Failed to publish Canary package: {0}")]
+    Publish(String),
+
+    /// A test account could not be funded
+    #[error(transparent)]
+    Funding(#[from] FundingError),
+
+    /// A test key could not be generated
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+
+    /// The publish transaction failed
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    /// A registry operation failed
+    #[error(transparent)]
+    Canary(#[from] CanaryError),
+}
+
+/// Errors that can occur while running worker tasks
+#[derive(Debug, thiserror::Error)]
+pub enum WorkerError {
+    /// Shutdown was requested before the run finished
+    #[error("Task run cancelled by shutdown")]
+    Cancelled,
+}
@@ -0,0 +1,263 @@
+//! CSV and JSON export of registry members and canary blobs
+//!
+//! Reporting pipelines load periodic dumps of a registry into spreadsheets and
+//! warehouses, which break when columns move. `export_members` and `export_blobs`
+//! write records with a fixed set of columns in a fixed order, in the order given:
+//!
+//! - members: `address,domain,joined_at`
+//! - blobs: `id,domain,package_id,contract_blob_id,explain_blob_id,uploaded_at,uploaded_by_admin`
+//!
+//! CSV output has a header row; JSON output is an array of objects with the same keys
+//! in the same order. New columns are only ever appended.
+
+use crate::canary::{CanaryBlobInfo, MemberInfoWithAddress};
+use serde::Serialize;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+/// Columns of `export_members`
+pub const MEMBER_COLUMNS: [&str; 3] = ["address", "domain", "joined_at"];
+
+/// Columns of `export_blobs`
+pub const BLOB_COLUMNS: [&str; 7] = [
+    "id",
+    "domain",
+    "package_id",
+    "contract_blob_id",
+    "explain_blob_id",
+    "uploaded_at",
+    "uploaded_by_admin",
+];
+
+/// Format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// A pretty-printed JSON array
+    Json,
+}
+
+impl ExportFormat {
+    /// The file extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(format!(
+                "Unknown export format '{}': expected csv or json",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+// Field order is the column order
+#[derive(Serialize)]
+struct MemberRow<'a> {
+    address: SuiAddress,
+    domain: &'a str,
+    joined_at: u64,
+}
+
+#[derive(Serialize)]
+struct BlobRow<'a> {
+    id: ObjectID,
+    domain: &'a str,
+    package_id: ObjectID,
+    contract_blob_id: ObjectID,
+    explain_blob_id: ObjectID,
+    uploaded_at: u64,
+    uploaded_by_admin: SuiAddress,
+}
+
+/// Write registry members as CSV or JSON
+///
+/// # Arguments
+///
+/// * `format` - CSV or JSON
+/// * `members` - The members, written in this order
+/// * `writer` - Where to write the export
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_all_members;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::export::{export_members, ExportFormat};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Mainnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let members = query_all_members(&client, registry_id).await?;
+/// let file = std::fs::File::create("members.csv")?;
+/// export_members(ExportFormat::Csv, &members, file)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_members<W: Write>(
+    format: ExportFormat,
+    members: &[MemberInfoWithAddress],
+    writer: W,
+) -> std::io::Result<()> {
+    let rows = members.iter().map(|member| MemberRow {
+        address: member.member,
+        domain: &member.domain,
+        joined_at: member.joined_at,
+    });
+    match format {
+        ExportFormat::Csv => write_csv(
+            writer,
+            &MEMBER_COLUMNS,
+            rows.map(|row| {
+                vec![
+                    row.address.to_string(),
+                    row.domain.to_string(),
+                    row.joined_at.to_string(),
+                ]
+            }),
+        ),
+        ExportFormat::Json => write_json(writer, &rows.collect::<Vec<_>>()),
+    }
+}
+
+/// Write canary blobs as CSV or JSON
+///
+/// # Arguments
+///
+/// * `format` - CSV or JSON
+/// * `blobs` - The blobs, written in this order
+/// * `writer` - Where to write the export
+pub fn export_blobs<W: Write>(
+    format: ExportFormat,
+    blobs: &[CanaryBlobInfo],
+    writer: W,
+) -> std::io::Result<()> {
+    let rows = blobs.iter().map(|blob| BlobRow {
+        id: blob.id,
+        domain: &blob.domain,
+        package_id: blob.package_id,
+        contract_blob_id: blob.contract_blob_id,
+        explain_blob_id: blob.explain_blob_id,
+        uploaded_at: blob.uploaded_at,
+        uploaded_by_admin: blob.uploaded_by_admin,
+    });
+    match format {
+        ExportFormat::Csv => write_csv(
+            writer,
+            &BLOB_COLUMNS,
+            rows.map(|row| {
+                vec![
+                    row.id.to_string(),
+                    row.domain.to_string(),
+                    row.package_id.to_string(),
+                    row.contract_blob_id.to_string(),
+                    row.explain_blob_id.to_string(),
+                    row.uploaded_at.to_string(),
+                    row.uploaded_by_admin.to_string(),
+                ]
+            }),
+        ),
+        ExportFormat::Json => write_json(writer, &rows.collect::<Vec<_>>()),
+    }
+}
+
+fn write_csv<W: Write>(
+    mut writer: W,
+    columns: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> std::io::Result<()> {
+    writeln!(writer, "{}", columns.join(","))?;
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    writer.flush()
+}
+
+fn write_json<W: Write, T: Serialize>(mut writer: W, rows: &[T]) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut writer, rows)?;
+    writeln!(writer)?;
+    writer.flush()
+}
+
+/// Quote a field if it contains a separator, quote, or line break (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(address: u8, domain: &str, joined_at: u64) -> MemberInfoWithAddress {
+        MemberInfoWithAddress {
+            member: SuiAddress::from(ObjectID::from_single_byte(address)),
+            domain: domain.to_string(),
+            joined_at,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn test_export_members_csv() {
+        let members = vec![member(1, "example.com", 10), member(2, "a,b\"c", 20)];
+        let mut out = Vec::new();
+        export_members(ExportFormat::Csv, &members, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "address,domain,joined_at");
+        assert_eq!(lines[1], format!("{},example.com,10", members[0].member));
+        assert_eq!(lines[2], format!("{},\"a,b\"\"c\",20", members[1].member));
+    }
+
+    #[test]
+    fn test_export_blobs_json_column_order() {
+        let blob = CanaryBlobInfo {
+            id: ObjectID::from_single_byte(1),
+            contract_blob_id: ObjectID::from_single_byte(2),
+            explain_blob_id: ObjectID::from_single_byte(3),
+            package_id: ObjectID::from_single_byte(4),
+            domain: "example.com".to_string(),
+            uploaded_at: 1_000,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        };
+        let mut out = Vec::new();
+        export_blobs(ExportFormat::Json, &[blob], &mut out).unwrap();
+        let json = String::from_utf8(out).unwrap();
+
+        let positions: Vec<usize> = BLOB_COLUMNS
+            .iter()
+            .map(|column| json.find(&format!("\"{}\"", column)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!("json".parse::<ExportFormat>(), Ok(ExportFormat::Json));
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
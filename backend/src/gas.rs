@@ -0,0 +1,482 @@
+//! Gas coin pool for concurrent transactions
+//!
+//! Two transactions paying gas with the same coin conflict: the second one is built
+//! against a coin version the first one consumes. Without a gas object set,
+//! `CanaryTransactionBuilder` pays with the signer's first SUI coin, so concurrent
+//! submissions from one signer collide. A `GasPool` tracks a set of gas coins and
+//! leases each one to at most one transaction at a time; `GasPool::split` creates the
+//! coins by splitting one large coin into several.
+//!
+//! The pool tracks coin IDs only. The builder reads the current version of a leased
+//! coin when it builds, so coins stay valid across transactions.
+//!
+//! The reference gas price only changes at epoch boundaries, so a `GasPriceOracle`
+//! reads it once per epoch instead of once per transaction. `GasLimits::max_gas_price`
+//! caps the price `build()` accepts: during a fee spike it fails with
+//! `TransactionError::GasPriceTooHigh` and the worker defers the run.
+
+use crate::client::SuiClientWithSigner;
+use crate::error::GasError;
+use crate::transaction::CanaryTransactionBuilder;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use sui_sdk::rpc_types::{Coin, SuiTransactionBlockResponse};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::SuiClient;
+use tokio::sync::Notify;
+
+/// Coin type of SUI
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Default minimum balance of a pooled coin (0.05 SUI), in MIST
+pub const DEFAULT_MIN_BALANCE: u64 = 50_000_000;
+
+/// A pool of gas coins owned by one address
+pub struct GasPool {
+    owner: SuiAddress,
+    min_balance: u64,
+    state: Mutex<PoolState>,
+    released: Notify,
+}
+
+#[derive(Default)]
+struct PoolState {
+    available: VecDeque<ObjectID>,
+    in_flight: HashSet<ObjectID>,
+}
+
+impl GasPool {
+    /// Create a pool from known coin IDs
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - The address owning the coins
+    /// * `coins` - The gas coin IDs
+    pub fn new(owner: SuiAddress, coins: Vec<ObjectID>) -> Self {
+        Self {
+            owner,
+            min_balance: DEFAULT_MIN_BALANCE,
+            state: Mutex::new(PoolState {
+                available: coins.into(),
+                in_flight: HashSet::new(),
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Load the owner's SUI coins holding at least `min_balance` MIST
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` for querying
+    /// * `owner` - The address owning the coins
+    /// * `min_balance` - Coins below this balance are left out of the pool
+    ///
+    /// # Returns
+    ///
+    /// Returns the pool, or a `GasError` if the coins cannot be read or none is large
+    /// enough.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use canary_sdk::gas::{GasPool, DEFAULT_MIN_BALANCE};
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+    /// let pool = Arc::new(GasPool::load(&client.client, client.signer, DEFAULT_MIN_BALANCE).await?);
+    /// let mut builder = CanaryTransactionBuilder::new(client);
+    /// builder.set_gas_pool(pool.clone());
+    /// // ... add operations and execute; the leased coin returns to the pool when the
+    /// // builder is dropped ...
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load(
+        client: &SuiClient,
+        owner: SuiAddress,
+        min_balance: u64,
+    ) -> Result<Self, GasError> {
+        let pool = Self {
+            min_balance,
+            ..Self::new(owner, Vec::new())
+        };
+        pool.refresh(client).await?;
+        Ok(pool)
+    }
+
+    /// The address owning the pooled coins
+    pub fn owner(&self) -> SuiAddress {
+        self.owner
+    }
+
+    /// Reload the owner's coins, keeping leased coins leased
+    ///
+    /// Coins that dropped below the minimum balance leave the pool; new coins of the
+    /// owner join it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of available coins, or a `GasError` if the coins cannot be
+    /// read or none is large enough.
+    pub async fn refresh(&self, client: &SuiClient) -> Result<usize, GasError> {
+        let coins: Vec<ObjectID> = owner_coins(client, self.owner)
+            .await?
+            .into_iter()
+            .filter(|coin| coin.balance >= self.min_balance)
+            .map(|coin| coin.coin_object_id)
+            .collect();
+
+        let mut state = self.lock();
+        if coins.is_empty() && state.in_flight.is_empty() {
+            return Err(GasError::NoCoins {
+                owner: self.owner,
+                min_balance: self.min_balance,
+            });
+        }
+        let in_flight = &state.in_flight;
+        let available: VecDeque<ObjectID> = coins
+            .into_iter()
+            .filter(|coin| !in_flight.contains(coin))
+            .collect();
+        state.available = available;
+        let count = state.available.len();
+        drop(state);
+
+        self.released.notify_waiters();
+        Ok(count)
+    }
+
+    /// Number of coins ready to be leased
+    pub fn available(&self) -> usize {
+        self.lock().available.len()
+    }
+
+    /// Number of coins currently leased
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight.len()
+    }
+
+    /// Lease a coin, waiting until one is released if all are in flight
+    pub async fn acquire(self: &Arc<Self>) -> GasLease {
+        loop {
+            let released = self.released.notified();
+            if let Some(lease) = self.try_acquire() {
+                return lease;
+            }
+            released.await;
+        }
+    }
+
+    /// Lease a coin if one is available
+    pub fn try_acquire(self: &Arc<Self>) -> Option<GasLease> {
+        let mut state = self.lock();
+        let coin_id = state.available.pop_front()?;
+        state.in_flight.insert(coin_id);
+        Some(GasLease {
+            coin_id,
+            pool: Arc::clone(self),
+        })
+    }
+
+    /// Split the signer's largest SUI coin into `count` coins of `amount` MIST each
+    ///
+    /// The new coins are sent back to the signer; `refresh` or `load` picks them up.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClientWithSigner` owning the coin to split
+    /// * `count` - Number of coins to create
+    /// * `amount` - Balance of each new coin, in MIST
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction response, or a `GasError` if the largest coin cannot
+    /// cover the split or the transaction fails.
+    pub async fn split(
+        client: SuiClientWithSigner,
+        count: usize,
+        amount: u64,
+    ) -> Result<SuiTransactionBlockResponse, GasError> {
+        let signer = client.signer;
+        let largest = owner_coins(&client.client, signer)
+            .await?
+            .into_iter()
+            .max_by_key(|coin| coin.balance)
+            .ok_or(GasError::NoCoins {
+                owner: signer,
+                min_balance: 0,
+            })?;
+        let required = amount.saturating_mul(count as u64);
+        if largest.balance <= required {
+            return Err(GasError::InsufficientBalance {
+                required,
+                available: largest.balance,
+            });
+        }
+
+        let mut builder = CanaryTransactionBuilder::new(client);
+        builder.set_gas_object(largest.coin_object_id);
+        let coins = builder
+            .split_coin(largest.coin_object_id, vec![amount; count])
+            .await?;
+        builder.transfer_results(coins, signer)?;
+        Ok(builder.execute().await?)
+    }
+
+    fn release(&self, coin_id: ObjectID) {
+        let mut state = self.lock();
+        if state.in_flight.remove(&coin_id) {
+            state.available.push_back(coin_id);
+        }
+        drop(state);
+        self.released.notify_one();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A gas coin leased from a `GasPool`, returned to the pool when dropped
+pub struct GasLease {
+    coin_id: ObjectID,
+    pool: Arc<GasPool>,
+}
+
+impl GasLease {
+    /// The leased coin's object ID
+    pub fn coin_id(&self) -> ObjectID {
+        self.coin_id
+    }
+
+    /// The address owning the leased coin
+    pub fn owner(&self) -> SuiAddress {
+        self.pool.owner
+    }
+}
+
+impl Drop for GasLease {
+    fn drop(&mut self) {
+        self.pool.release(self.coin_id);
+    }
+}
+
+/// All SUI coins of an address
+async fn owner_coins(client: &SuiClient, owner: SuiAddress) -> Result<Vec<Coin>, GasError> {
+    let mut coins = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client
+            .coin_read_api()
+            .get_coins(owner, Some(SUI_COIN_TYPE.to_string()), cursor, None)
+            .await
+            .map_err(|e| GasError::Chain(format!("Failed to get coins: {}", e)))?;
+        coins.extend(page.data);
+        if !page.has_next_page {
+            return Ok(coins);
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+/// The reference gas price of one epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EpochPrice {
+    epoch: u64,
+    price: u64,
+    /// When the epoch is scheduled to end, in Unix milliseconds
+    ends_at_ms: u64,
+}
+
+/// Reference gas price cache, refreshed when the epoch changes
+///
+/// The price is read from the latest system state together with the epoch's start and
+/// duration. Until the epoch is due to end, the cached price is returned without a
+/// request; after that, the next call reads the system state again.
+#[derive(Debug, Default)]
+pub struct GasPriceOracle {
+    cached: Mutex<Option<EpochPrice>>,
+}
+
+impl GasPriceOracle {
+    /// Create an empty oracle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide oracle used by `CanaryTransactionBuilder::build`
+    pub fn global() -> &'static GasPriceOracle {
+        static GLOBAL: OnceLock<GasPriceOracle> = OnceLock::new();
+        GLOBAL.get_or_init(GasPriceOracle::new)
+    }
+
+    /// The reference gas price of the current epoch, in MIST per gas unit
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` to read the system state with on a cache miss
+    ///
+    /// # Returns
+    ///
+    /// Returns the cached or freshly read price, or a `GasError` if the system state
+    /// cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::gas::GasPriceOracle;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Mainnet).await?;
+    /// let price = GasPriceOracle::global().reference_price(&client).await?;
+    /// println!("Reference gas price: {} MIST", price);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reference_price(&self, client: &SuiClient) -> Result<u64, GasError> {
+        if let Some(price) = self.cached(now_ms()) {
+            return Ok(price);
+        }
+
+        let state = client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await
+            .map_err(|e| GasError::Chain(format!("Failed to get system state: {}", e)))?;
+        let cached = EpochPrice {
+            epoch: state.epoch,
+            price: state.reference_gas_price,
+            ends_at_ms: state
+                .epoch_start_timestamp_ms
+                .saturating_add(state.epoch_duration_ms),
+        };
+        self.store(cached);
+        tracing::debug!(
+            "Reference gas price for epoch {}: {} MIST",
+            cached.epoch,
+            cached.price
+        );
+        Ok(cached.price)
+    }
+
+    /// The epoch of the cached price, if any
+    pub fn epoch(&self) -> Option<u64> {
+        self.current().map(|cached| cached.epoch)
+    }
+
+    /// Drop the cached price, so the next call reads it again
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn current(&self) -> Option<EpochPrice> {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The cached price, if its epoch has not ended at `now_ms`
+    fn cached(&self, now_ms: u64) -> Option<u64> {
+        self.current()
+            .filter(|cached| now_ms < cached.ends_at_ms)
+            .map(|cached| cached.price)
+    }
+
+    /// Cache a price, unless a later epoch's price is already cached
+    fn store(&self, price: EpochPrice) {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if cached.is_none_or(|cached| cached.epoch <= price.epoch) {
+            *cached = Some(price);
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn pool(coins: u64) -> Arc<GasPool> {
+        let ids = (1..=coins).map(ObjectID::from_single_byte).collect();
+        Arc::new(GasPool::new(SuiAddress::ZERO, ids))
+    }
+
+    #[test]
+    fn test_leases_are_exclusive() {
+        let pool = pool(2);
+        let a = pool.try_acquire().unwrap();
+        let b = pool.try_acquire().unwrap();
+        assert_ne!(a.coin_id(), b.coin_id());
+        assert!(pool.try_acquire().is_none());
+        assert_eq!(pool.in_flight(), 2);
+
+        drop(a);
+        assert_eq!(pool.available(), 1);
+        assert_eq!(pool.in_flight(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_release() {
+        let pool = pool(1);
+        let lease = pool.acquire().await;
+        let coin_id = lease.coin_id();
+
+        let waiter = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.acquire().await.coin_id() }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(lease);
+        let reacquired = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reacquired, coin_id);
+    }
+
+    #[test]
+    fn test_gas_price_cached_per_epoch() {
+        let oracle = GasPriceOracle::new();
+        assert_eq!(oracle.cached(0), None);
+
+        oracle.store(EpochPrice {
+            epoch: 10,
+            price: 750,
+            ends_at_ms: 1_000,
+        });
+        assert_eq!(oracle.epoch(), Some(10));
+        assert_eq!(oracle.cached(999), Some(750));
+        // Once the epoch is due to end, the price must be read again
+        assert_eq!(oracle.cached(1_000), None);
+
+        // A slow response for an older epoch does not replace a newer price
+        oracle.store(EpochPrice {
+            epoch: 11,
+            price: 1_000,
+            ends_at_ms: 2_000,
+        });
+        oracle.store(EpochPrice {
+            epoch: 10,
+            price: 750,
+            ends_at_ms: 1_000,
+        });
+        assert_eq!(oracle.cached(1_500), Some(1_000));
+
+        oracle.invalidate();
+        assert_eq!(oracle.cached(1_500), None);
+    }
+}
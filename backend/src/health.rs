@@ -0,0 +1,301 @@
+//! Liveness and readiness probes
+//!
+//! `serve` answers Kubernetes-style probes for the worker:
+//!
+//! - `GET /healthz` (liveness) fails when a task has not succeeded within its
+//!   maximum age, so a stuck worker gets restarted.
+//! - `GET /readyz` (readiness) additionally checks that the RPC endpoint answers and
+//!   that the signer can still pay for gas.
+//!
+//! Both return a JSON `HealthReport` with status 200 when every check passes and 503
+//! otherwise. The same listener serves `GET /metrics`.
+
+use crate::client::reconnect::ReconnectingClient;
+use crate::metrics::{metrics_response, serve_http, HttpResponse, Metrics};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::SuiClient;
+use tokio::net::TcpListener;
+
+/// Result of one health check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Check {
+    /// What was checked, e.g. `task:members`, `rpc`, or `signer_balance`
+    pub name: String,
+    /// Whether the check passed
+    pub ok: bool,
+    /// Human-readable detail
+    pub detail: String,
+}
+
+/// Outcome of a probe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Whether every check passed
+    pub healthy: bool,
+    /// The individual checks
+    pub checks: Vec<Check>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<Check>) -> Self {
+        Self {
+            healthy: checks.iter().all(|check| check.ok),
+            checks,
+        }
+    }
+}
+
+/// Health checks for a running worker
+pub struct HealthCheck {
+    connection: Arc<ReconnectingClient>,
+    metrics: &'static Metrics,
+    started: SystemTime,
+    tasks: Vec<(String, Duration)>,
+    signer: Option<(SuiAddress, u64)>,
+}
+
+impl HealthCheck {
+    /// Create health checks reading task runs from `Metrics::global()`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::health::{serve, HealthCheck};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use tokio::net::TcpListener;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let health = HealthCheck::new(client).with_task("members", Duration::from_secs(7200));
+    ///
+    /// let listener = TcpListener::bind("0.0.0.0:8080").await?;
+    /// tokio::spawn(serve(listener, Arc::new(health)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(client: SuiClient) -> Self {
+        Self::shared(Arc::new(ReconnectingClient::new(client)))
+    }
+
+    /// Create health checks on a connection shared with the worker's tasks, so readiness
+    /// follows their reconnects
+    pub fn shared(connection: Arc<ReconnectingClient>) -> Self {
+        Self {
+            connection,
+            metrics: Metrics::global(),
+            started: SystemTime::now(),
+            tasks: Vec::new(),
+            signer: None,
+        }
+    }
+
+    /// Require `task` to have succeeded within `max_age`
+    ///
+    /// A task that has not succeeded yet is healthy until `max_age` after startup.
+    pub fn with_task(mut self, task: impl Into<String>, max_age: Duration) -> Self {
+        self.tasks.push((task.into(), max_age));
+        self
+    }
+
+    /// Require `signer` to hold at least `min_balance` MIST for readiness
+    pub fn with_signer(mut self, signer: SuiAddress, min_balance: u64) -> Self {
+        self.signer = Some((signer, min_balance));
+        self
+    }
+
+    /// Liveness: every task succeeded recently enough
+    pub fn liveness(&self) -> HealthReport {
+        let now = SystemTime::now();
+        HealthReport::new(
+            self.tasks
+                .iter()
+                .map(|(task, max_age)| {
+                    task_check(
+                        task,
+                        self.metrics.last_success(task),
+                        self.started,
+                        *max_age,
+                        now,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Readiness: liveness plus RPC connectivity and the signer's balance
+    pub async fn readiness(&self) -> HealthReport {
+        let mut checks = self.liveness().checks;
+        let client = self.connection.client();
+
+        checks.push(
+            match client
+                .read_api()
+                .get_latest_checkpoint_sequence_number()
+                .await
+            {
+                Ok(checkpoint) => Check {
+                    name: "rpc".to_string(),
+                    ok: true,
+                    detail: format!("latest checkpoint {}", checkpoint),
+                },
+                Err(e) => Check {
+                    name: "rpc".to_string(),
+                    ok: false,
+                    detail: e.to_string(),
+                },
+            },
+        );
+
+        if let Some((signer, min_balance)) = self.signer {
+            checks.push(
+                match client.coin_read_api().get_balance(signer, None).await {
+                    Ok(balance) => balance_check(signer, balance.total_balance, min_balance),
+                    Err(e) => Check {
+                        name: "signer_balance".to_string(),
+                        ok: false,
+                        detail: e.to_string(),
+                    },
+                },
+            );
+        }
+
+        HealthReport::new(checks)
+    }
+}
+
+/// Serve `/healthz`, `/readyz`, and `/metrics` until the listener fails
+pub async fn serve(listener: TcpListener, health: Arc<HealthCheck>) -> std::io::Result<()> {
+    serve_http(listener, move |method, path| {
+        let health = health.clone();
+        async move {
+            match (method.as_str(), path.as_str()) {
+                ("GET", "/healthz") => report_response(&health.liveness()),
+                ("GET", "/readyz") => report_response(&health.readiness().await),
+                ("GET", "/metrics") => metrics_response(health.metrics),
+                _ => HttpResponse::not_found(&method),
+            }
+        }
+    })
+    .await
+}
+
+fn report_response(report: &HealthReport) -> HttpResponse {
+    HttpResponse {
+        status: if report.healthy {
+            "200 OK"
+        } else {
+            "503 Service Unavailable"
+        },
+        content_type: "application/json",
+        body: serde_json::to_string(report).unwrap_or_default(),
+    }
+}
+
+/// Check that `task` succeeded within `max_age` of `now`
+fn task_check(
+    task: &str,
+    last_success: Option<SystemTime>,
+    started: SystemTime,
+    max_age: Duration,
+    now: SystemTime,
+) -> Check {
+    let since = last_success.unwrap_or(started);
+    let age = now.duration_since(since).unwrap_or_default();
+    let ok = age <= max_age;
+    let detail = match (last_success, ok) {
+        (Some(_), _) => format!(
+            "last success {}s ago (max {}s)",
+            age.as_secs(),
+            max_age.as_secs()
+        ),
+        (None, true) => "no successful run yet".to_string(),
+        (None, false) => format!("no successful run in {}s", age.as_secs()),
+    };
+    Check {
+        name: format!("task:{}", task),
+        ok,
+        detail,
+    }
+}
+
+fn balance_check(signer: SuiAddress, balance: u128, min_balance: u64) -> Check {
+    Check {
+        name: "signer_balance".to_string(),
+        ok: balance >= min_balance as u128,
+        detail: format!("{} holds {} MIST (min {})", signer, balance, min_balance),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_check() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let max_age = Duration::from_secs(60);
+
+        // Not run yet, within the startup grace period
+        let check = task_check("members", None, started, max_age, started + max_age);
+        assert!(check.ok);
+        assert_eq!(check.name, "task:members");
+
+        // Never succeeded
+        let check = task_check(
+            "members",
+            None,
+            started,
+            max_age,
+            started + Duration::from_secs(61),
+        );
+        assert!(!check.ok);
+
+        // Recent and stale successes
+        let last = Some(started + Duration::from_secs(100));
+        assert!(
+            task_check(
+                "members",
+                last,
+                started,
+                max_age,
+                started + Duration::from_secs(150)
+            )
+            .ok
+        );
+        assert!(
+            !task_check(
+                "members",
+                last,
+                started,
+                max_age,
+                started + Duration::from_secs(200)
+            )
+            .ok
+        );
+    }
+
+    #[test]
+    fn test_report_response() {
+        let ok = Check {
+            name: "rpc".to_string(),
+            ok: true,
+            detail: String::new(),
+        };
+        let failed = balance_check(SuiAddress::ZERO, 10, 100);
+        assert!(!failed.ok);
+
+        let response = report_response(&HealthReport::new(vec![ok.clone()]));
+        assert_eq!(response.status, "200 OK");
+
+        let response = report_response(&HealthReport::new(vec![ok, failed]));
+        assert_eq!(response.status, "503 Service Unavailable");
+        let report: HealthReport = serde_json::from_str(&response.body).unwrap();
+        assert!(!report.healthy);
+        assert_eq!(report.checks[1].name, "signer_balance");
+    }
+}
@@ -0,0 +1,595 @@
+//! Admin key compromise response
+//!
+//! `lockdown` scripts the response to a suspected admin key compromise so nobody has
+//! to hand-write PTBs during an incident. Depending on what the deployed contract
+//! supports, it:
+//! 1. pauses joins (`member_registry::pause_joins`),
+//! 2. publishes an incident canary statement (`pkg_storage::store_blob`/`update_blob`),
+//! 3. hands the AdminCap to a recovery address, e.g. a multisig
+//!    (`member_registry::transfer_admin`),
+//! 4. fires every configured notifier.
+//!
+//! The on-chain steps run in a single transaction so the lockdown is all-or-nothing.
+//! Steps the contract does not expose are reported as unsupported rather than
+//! failing the lockdown. With `dry_run` set, the plan is computed and reported
+//! without sending anything.
+
+use crate::canary::{
+    derive_canary_address, extract_package_id_from_type, query_canary_blob, validate_admin_cap_for,
+    SharedObjectResolver,
+};
+use crate::client::SuiClientWithSigner;
+use crate::error::CanaryError;
+use crate::transaction::{pure_address, pure_string, CanaryTransactionBuilder};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::fmt;
+use sui_sdk::rpc_types::SuiObjectDataOptions;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::transaction::{CallArg, ObjectArg, SharedObjectMutability};
+use sui_sdk::SuiClient;
+
+/// `member_registry` function that stops new joins: `pause_joins(&mut Registry, &AdminCap)`
+pub const PAUSE_JOINS_FUNCTION: &str = "pause_joins";
+
+/// `member_registry` function that hands over the AdminCap:
+/// `transfer_admin(&mut Registry, AdminCap, address)`
+pub const TRANSFER_ADMIN_FUNCTION: &str = "transfer_admin";
+
+/// An incident statement already uploaded to storage, to be published as a canary blob
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentStatement {
+    /// Domain the statement is published under
+    pub domain: String,
+    /// Package the canary blob is keyed by
+    pub package_id: ObjectID,
+    /// Contract blob object ID
+    pub contract_blob_id: ObjectID,
+    /// Explain blob object ID (the incident notice)
+    pub explain_blob_id: ObjectID,
+}
+
+/// What `lockdown` should do
+#[derive(Debug, Clone)]
+pub struct LockdownOptions {
+    /// The (possibly compromised) AdminCap object ID, owned by the signer
+    pub admin_cap_id: ObjectID,
+    /// Where to send the AdminCap, e.g. a recovery multisig (`None` keeps it)
+    pub recovery_address: Option<SuiAddress>,
+    /// Incident statement to publish (`None` skips publishing)
+    pub statement: Option<IncidentStatement>,
+    /// Compute and report the plan without sending anything
+    pub dry_run: bool,
+}
+
+/// Which lockdown functions the deployed contract exposes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContractSupport {
+    /// `member_registry::pause_joins` exists
+    pub pause_joins: bool,
+    /// `member_registry::transfer_admin` exists
+    pub transfer_admin: bool,
+}
+
+/// A lockdown step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum LockdownStep {
+    /// Stop new members from joining
+    PauseJoins,
+    /// Publish the incident statement for a domain
+    PublishStatement { domain: String },
+    /// Send the AdminCap to the recovery address
+    RotateAdminCap { to: SuiAddress },
+    /// Fire a notifier
+    Notify { notifier: String },
+}
+
+/// What happened to a step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum StepStatus {
+    /// Will run (dry run, or not reached yet)
+    Planned,
+    /// The contract does not expose the required function
+    Unsupported(String),
+    /// Not requested by the options
+    Skipped,
+    /// Completed
+    Done,
+    /// Attempted and failed
+    Failed(String),
+}
+
+/// A step and its status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepOutcome {
+    /// The step
+    pub step: LockdownStep,
+    /// Its status
+    pub status: StepStatus,
+}
+
+/// Result of a lockdown (or its plan, for a dry run)
+#[derive(Debug, Clone, Serialize)]
+pub struct LockdownReport {
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    /// Steps in execution order
+    pub steps: Vec<StepOutcome>,
+    /// Digest of the lockdown transaction, if one was sent
+    pub digest: Option<TransactionDigest>,
+}
+
+impl LockdownReport {
+    /// Whether every requested and supported step completed
+    pub fn succeeded(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|s| !matches!(s.status, StepStatus::Failed(_) | StepStatus::Planned))
+    }
+}
+
+impl fmt::Display for LockdownReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Lockdown of registry {}{}",
+            self.registry_id,
+            if self.dry_run { " (dry run)" } else { "" }
+        )?;
+        for outcome in &self.steps {
+            writeln!(f, "  {:?}: {:?}", outcome.step, outcome.status)?;
+        }
+        if let Some(digest) = &self.digest {
+            writeln!(f, "  Transaction: {}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the lockdown report once on-chain steps are done
+#[async_trait]
+pub trait IncidentNotifier: Send + Sync {
+    /// Name used in the report
+    fn name(&self) -> &str;
+
+    /// Deliver the report
+    async fn notify(&self, report: &LockdownReport) -> anyhow::Result<()>;
+}
+
+/// Plan the steps for the given contract support and options
+pub fn plan_lockdown(
+    support: ContractSupport,
+    options: &LockdownOptions,
+    notifiers: &[&dyn IncidentNotifier],
+) -> Vec<StepOutcome> {
+    let unsupported = |function: &str| {
+        StepStatus::Unsupported(format!("member_registry::{} not found", function))
+    };
+
+    let mut steps = vec![StepOutcome {
+        step: LockdownStep::PauseJoins,
+        status: if support.pause_joins {
+            StepStatus::Planned
+        } else {
+            unsupported(PAUSE_JOINS_FUNCTION)
+        },
+    }];
+
+    if let Some(statement) = &options.statement {
+        steps.push(StepOutcome {
+            step: LockdownStep::PublishStatement {
+                domain: statement.domain.clone(),
+            },
+            status: StepStatus::Planned,
+        });
+    }
+
+    // Rotation consumes the AdminCap, so it must come after every step that uses it
+    if let Some(to) = options.recovery_address {
+        steps.push(StepOutcome {
+            step: LockdownStep::RotateAdminCap { to },
+            status: if support.transfer_admin {
+                StepStatus::Planned
+            } else {
+                unsupported(TRANSFER_ADMIN_FUNCTION)
+            },
+        });
+    }
+
+    steps.extend(notifiers.iter().map(|notifier| StepOutcome {
+        step: LockdownStep::Notify {
+            notifier: notifier.name().to_string(),
+        },
+        status: StepStatus::Planned,
+    }));
+
+    steps
+}
+
+/// Detect which lockdown functions the registry's package exposes
+pub async fn contract_support(
+    client: &SuiClient,
+    package_id: ObjectID,
+) -> Result<ContractSupport, CanaryError> {
+    let modules = client
+        .read_api()
+        .get_normalized_move_modules_by_package(package_id)
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get package modules: {}", e)))?;
+
+    let has = |function: &str| {
+        modules
+            .get("member_registry")
+            .map(|module| module.exposed_functions.contains_key(function))
+            .unwrap_or(false)
+    };
+    Ok(ContractSupport {
+        pause_joins: has(PAUSE_JOINS_FUNCTION),
+        transfer_admin: has(TRANSFER_ADMIN_FUNCTION),
+    })
+}
+
+/// Lock down a registry after a suspected admin key compromise
+///
+/// # Arguments
+///
+/// * `admin` - A `SuiClientWithSigner` owning the AdminCap
+/// * `registry_id` - The Registry object ID
+/// * `options` - Which steps to take, and whether this is a dry run
+/// * `notifiers` - Notifiers to fire with the final report
+///
+/// # Returns
+///
+/// Returns a `LockdownReport` with the status of every step, or a `CanaryError` if the
+/// plan could not be computed. Failures of individual steps are recorded in the
+/// report rather than returned.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use canary_sdk::incident::{lockdown, LockdownOptions};
+/// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let admin = create_client_with_key(Network::Mainnet, "suiprivkey1...").await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let options = LockdownOptions {
+///     admin_cap_id: ObjectID::from_hex_literal("0x456...")?,
+///     recovery_address: Some("0x789...".parse::<SuiAddress>()?),
+///     statement: None,
+///     dry_run: true,
+/// };
+/// let report = lockdown(admin, registry_id, &options, &[]).await?;
+/// println!("{}", report);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn lockdown(
+    admin: SuiClientWithSigner,
+    registry_id: ObjectID,
+    options: &LockdownOptions,
+    notifiers: &[&dyn IncidentNotifier],
+) -> Result<LockdownReport, CanaryError> {
+    let registry_obj = admin
+        .client
+        .read_api()
+        .get_object_with_options(registry_id, SuiObjectDataOptions::new().with_type())
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get registry object: {}", e)))?
+        .into_object()
+        .map_err(|_| CanaryError::Registry("Registry object not found".to_string()))?;
+    let package_id = registry_obj
+        .type_
+        .and_then(|t| extract_package_id_from_type(&t.to_string()))
+        .ok_or_else(|| CanaryError::Registry("Failed to extract package ID".to_string()))?;
+
+    let support = contract_support(&admin.client, package_id).await?;
+    let mut report = LockdownReport {
+        registry_id,
+        dry_run: options.dry_run,
+        steps: plan_lockdown(support, options, notifiers),
+        digest: None,
+    };
+    if options.dry_run {
+        return Ok(report);
+    }
+
+    let onchain_planned = report
+        .steps
+        .iter()
+        .any(|s| s.status == StepStatus::Planned && !matches!(s.step, LockdownStep::Notify { .. }));
+    if onchain_planned {
+        let result =
+            execute_onchain_steps(admin, registry_id, package_id, options, &report.steps).await;
+        for outcome in report
+            .steps
+            .iter_mut()
+            .filter(|s| !matches!(s.step, LockdownStep::Notify { .. }))
+            .filter(|s| s.status == StepStatus::Planned)
+        {
+            outcome.status = match &result {
+                Ok(_) => StepStatus::Done,
+                Err(e) => StepStatus::Failed(e.to_string()),
+            };
+        }
+        report.digest = result.ok();
+    }
+
+    // Notify with the on-chain outcome, then record how each notifier fared
+    let snapshot = report.clone();
+    for (notifier, outcome) in notifiers.iter().zip(
+        report
+            .steps
+            .iter_mut()
+            .filter(|s| matches!(s.step, LockdownStep::Notify { .. })),
+    ) {
+        outcome.status = match notifier.notify(&snapshot).await {
+            Ok(()) => StepStatus::Done,
+            Err(e) => StepStatus::Failed(e.to_string()),
+        };
+    }
+
+    Ok(report)
+}
+
+/// Build and send the single transaction performing all planned on-chain steps
+async fn execute_onchain_steps(
+    admin: SuiClientWithSigner,
+    registry_id: ObjectID,
+    package_id: ObjectID,
+    options: &LockdownOptions,
+    steps: &[StepOutcome],
+) -> Result<TransactionDigest, CanaryError> {
+    let client = admin.client.clone();
+    let planned = |step: &LockdownStep| {
+        steps
+            .iter()
+            .any(|s| &s.step == step && s.status == StepStatus::Planned)
+    };
+
+    let resolver = SharedObjectResolver::global();
+    let registry_isv = resolver
+        .initial_shared_version(&client, registry_id)
+        .await?;
+    let registry_arg = |mutable: bool| {
+        CallArg::Object(ObjectArg::SharedObject {
+            id: registry_id,
+            initial_shared_version: registry_isv,
+            mutability: if mutable {
+                SharedObjectMutability::Mutable
+            } else {
+                SharedObjectMutability::Immutable
+            },
+        })
+    };
+    let admin_cap_ref =
+        validate_admin_cap_for(&client, registry_id, options.admin_cap_id, admin.signer).await?;
+    let admin_cap_arg = CallArg::Object(ObjectArg::ImmOrOwnedObject(admin_cap_ref));
+    let clock_arg = SharedObjectResolver::clock_arg();
+
+    let mut builder = CanaryTransactionBuilder::new(admin);
+
+    if planned(&LockdownStep::PauseJoins) {
+        builder.move_call(
+            package_id,
+            "member_registry",
+            PAUSE_JOINS_FUNCTION,
+            vec![registry_arg(true), admin_cap_arg.clone()],
+        )?;
+    }
+
+    if let Some(statement) = &options.statement {
+        let canary_address = derive_canary_address(
+            &client,
+            registry_id,
+            statement.domain.clone(),
+            statement.package_id,
+        )
+        .await?;
+        let canary_blob_id = ObjectID::from(canary_address);
+
+        match query_canary_blob(&client, canary_blob_id).await {
+            Ok(_) => {
+                let canary_blob_arg = resolver
+                    .shared_arg(&client, canary_blob_id, SharedObjectMutability::Mutable)
+                    .await?;
+                builder.move_call(
+                    package_id,
+                    "pkg_storage",
+                    "update_blob",
+                    vec![
+                        registry_arg(false),
+                        admin_cap_arg.clone(),
+                        canary_blob_arg,
+                        pure_address(statement.contract_blob_id),
+                        pure_address(statement.explain_blob_id),
+                        clock_arg.clone(),
+                    ],
+                )?;
+            }
+            Err(CanaryError::CanaryBlobNotFound) => {
+                builder.move_call(
+                    package_id,
+                    "pkg_storage",
+                    "store_blob",
+                    vec![
+                        registry_arg(true),
+                        admin_cap_arg.clone(),
+                        pure_string(&statement.domain),
+                        pure_address(statement.contract_blob_id),
+                        pure_address(statement.explain_blob_id),
+                        pure_address(statement.package_id),
+                        clock_arg.clone(),
+                    ],
+                )?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Some(to) = options.recovery_address {
+        if planned(&LockdownStep::RotateAdminCap { to }) {
+            builder.move_call(
+                package_id,
+                "member_registry",
+                TRANSFER_ADMIN_FUNCTION,
+                vec![registry_arg(true), admin_cap_arg, pure_address(to)],
+            )?;
+        }
+    }
+
+    let response = builder.execute().await?;
+    Ok(response.digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+
+    struct RecordingNotifier {
+        name: String,
+        fail: bool,
+        received: Mutex<Vec<String>>,
+    }
+
+    impl RecordingNotifier {
+        fn new(name: &str, fail: bool) -> Self {
+            Self {
+                name: name.to_string(),
+                fail,
+                received: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IncidentNotifier for RecordingNotifier {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn notify(&self, report: &LockdownReport) -> anyhow::Result<()> {
+            self.received.lock().unwrap().push(report.to_string());
+            if self.fail {
+                anyhow::bail!("webhook unreachable");
+            }
+            Ok(())
+        }
+    }
+
+    fn object_id(hex: &str) -> ObjectID {
+        ObjectID::from_hex_literal(hex).unwrap()
+    }
+
+    fn options() -> LockdownOptions {
+        LockdownOptions {
+            admin_cap_id: object_id("0xa"),
+            recovery_address: Some(SuiAddress::from_str("0xb").unwrap()),
+            statement: Some(IncidentStatement {
+                domain: "example.com".to_string(),
+                package_id: object_id("0x1"),
+                contract_blob_id: object_id("0x2"),
+                explain_blob_id: object_id("0x3"),
+            }),
+            dry_run: true,
+        }
+    }
+
+    fn full_support() -> ContractSupport {
+        ContractSupport {
+            pause_joins: true,
+            transfer_admin: true,
+        }
+    }
+
+    #[test]
+    fn test_plan_full_support_order() {
+        let notifier = RecordingNotifier::new("slack", false);
+        let steps = plan_lockdown(full_support(), &options(), &[&notifier]);
+        let kinds: Vec<&LockdownStep> = steps.iter().map(|s| &s.step).collect();
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(kinds[0], &LockdownStep::PauseJoins);
+        assert!(matches!(kinds[1], LockdownStep::PublishStatement { .. }));
+        // AdminCap rotation must come after every step that still needs the cap
+        assert!(matches!(kinds[2], LockdownStep::RotateAdminCap { .. }));
+        assert!(matches!(kinds[3], LockdownStep::Notify { notifier } if notifier == "slack"));
+        assert!(steps.iter().all(|s| s.status == StepStatus::Planned));
+    }
+
+    #[test]
+    fn test_plan_without_contract_support() {
+        let steps = plan_lockdown(ContractSupport::default(), &options(), &[]);
+        assert!(matches!(steps[0].status, StepStatus::Unsupported(_)));
+        assert_eq!(steps[1].status, StepStatus::Planned);
+        assert!(
+            matches!(steps[2].status, StepStatus::Unsupported(ref m) if m.contains(TRANSFER_ADMIN_FUNCTION))
+        );
+    }
+
+    #[test]
+    fn test_plan_skips_unrequested_steps() {
+        let options = LockdownOptions {
+            recovery_address: None,
+            statement: None,
+            ..options()
+        };
+        let steps = plan_lockdown(full_support(), &options, &[]);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].step, LockdownStep::PauseJoins);
+    }
+
+    #[test]
+    fn test_report_succeeded() {
+        let mut report = LockdownReport {
+            registry_id: object_id("0x123"),
+            dry_run: false,
+            steps: plan_lockdown(ContractSupport::default(), &options(), &[]),
+            digest: None,
+        };
+        // Planned steps have not run yet
+        assert!(!report.succeeded());
+
+        report.steps[1].status = StepStatus::Done;
+        assert!(report.succeeded());
+
+        report.steps[1].status = StepStatus::Failed("aborted".to_string());
+        assert!(!report.succeeded());
+        assert!(report.to_string().contains("aborted"));
+    }
+
+    #[tokio::test]
+    async fn test_notifier_receives_report() {
+        let notifier = RecordingNotifier::new("pager", true);
+        let report = LockdownReport {
+            registry_id: object_id("0x123"),
+            dry_run: true,
+            steps: plan_lockdown(full_support(), &options(), &[&notifier]),
+            digest: None,
+        };
+        assert!(notifier.notify(&report).await.is_err());
+        let received = notifier.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(received[0].contains("(dry run)"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires network connection, a deployed registry, and an admin key
+    async fn test_lockdown_dry_run() {
+        let key = std::env::var("SUI_PRIVATE_KEY").unwrap();
+        let admin = crate::client::create_client_with_key(crate::client::Network::Devnet, &key)
+            .await
+            .unwrap();
+        let report = lockdown(admin, object_id("0x123"), &options(), &[])
+            .await
+            .unwrap();
+        assert!(report.dry_run);
+        assert!(report.digest.is_none());
+    }
+}
@@ -0,0 +1,886 @@
+//! Local SQLite index of registry history
+//!
+//! Answering "who was a member on March 1st?" or "which blobs has example.com
+//! published?" from the chain means replaying every transaction that touched the
+//! registry. The `Indexer` does that replay once and keeps the result in SQLite:
+//!
+//! - `transactions`: every registry transaction with its sender and canary call
+//! - `members`: one row per membership, from `join_registry` to `remove_member`
+//! - `blobs`: one row per `store_blob`, `update_blob`, or `delete_canary_blob`
+//!
+//! `Indexer::sync` ingests the transactions since the last sync, so running it on a
+//! schedule keeps the index current. Historical questions are then local queries
+//! (`members_at`, `blob_history`). The contract emits no Move events, so the canary
+//! events are decoded from each transaction's Move calls and effects.
+//!
+//! Requires the `indexer` feature.
+
+use crate::canary::MemberInfoWithAddress;
+use crate::error::IndexerError;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Mutex, MutexGuard};
+use sui_sdk::rpc_types::{
+    SuiArgument, SuiCallArg, SuiCommand, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockKind, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// Transactions fetched per sync page
+const INDEXER_PAGE_SIZE: usize = 50;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    digest TEXT PRIMARY KEY,
+    registry_id TEXT NOT NULL,
+    checkpoint INTEGER,
+    timestamp_ms INTEGER NOT NULL,
+    sender TEXT NOT NULL,
+    function TEXT,
+    success INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS members (
+    registry_id TEXT NOT NULL,
+    member TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    joined_at INTEGER NOT NULL,
+    joined_digest TEXT NOT NULL,
+    removed_at INTEGER,
+    removed_digest TEXT
+);
+CREATE INDEX IF NOT EXISTS members_by_time ON members (registry_id, joined_at);
+CREATE TABLE IF NOT EXISTS blobs (
+    registry_id TEXT NOT NULL,
+    blob_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    package_id TEXT NOT NULL,
+    contract_blob_id TEXT,
+    explain_blob_id TEXT,
+    timestamp_ms INTEGER NOT NULL,
+    sender TEXT NOT NULL,
+    digest TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS blobs_by_domain ON blobs (registry_id, domain, timestamp_ms);
+CREATE TABLE IF NOT EXISTS sync_cursors (
+    registry_id TEXT PRIMARY KEY,
+    digest TEXT NOT NULL
+);
+";
+
+/// A registry change decoded from a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CanaryEvent {
+    /// `member_registry::join_registry`
+    MemberJoined { member: SuiAddress, domain: String },
+    /// `member_registry::remove_member`
+    MemberRemoved { member: SuiAddress },
+    /// `pkg_storage::store_blob`
+    BlobStored {
+        blob_id: ObjectID,
+        domain: String,
+        package_id: SuiAddress,
+        contract_blob_id: SuiAddress,
+        explain_blob_id: SuiAddress,
+    },
+    /// `pkg_storage::update_blob`
+    BlobUpdated {
+        blob_id: ObjectID,
+        contract_blob_id: SuiAddress,
+        explain_blob_id: SuiAddress,
+    },
+    /// `pkg_storage::delete_canary_blob`
+    BlobDeleted { blob_id: ObjectID },
+}
+
+/// A registry transaction and the canary events it caused
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// Checkpoint the transaction was included in, if known
+    pub checkpoint: Option<u64>,
+    /// Checkpoint timestamp of the transaction (in milliseconds)
+    pub timestamp_ms: u64,
+    /// The transaction sender
+    pub sender: SuiAddress,
+    /// The first canary contract call, as `module::function`
+    pub function: Option<String>,
+    /// Whether the transaction executed successfully; failed ones cause no events
+    pub success: bool,
+    /// Events in command order
+    pub events: Vec<CanaryEvent>,
+}
+
+/// What happened to a canary blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobAction {
+    /// First upload
+    Stored,
+    /// New contract and explanation blobs
+    Updated,
+    /// Removed from the registry
+    Deleted,
+}
+
+impl BlobAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BlobAction::Stored => "stored",
+            BlobAction::Updated => "updated",
+            BlobAction::Deleted => "deleted",
+        }
+    }
+}
+
+impl FromStr for BlobAction {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stored" => Ok(BlobAction::Stored),
+            "updated" => Ok(BlobAction::Updated),
+            "deleted" => Ok(BlobAction::Deleted),
+            other => Err(IndexerError::Database(format!(
+                "Unknown blob action {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// One change to a canary blob
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobVersion {
+    /// The CanaryBlob object ID
+    pub blob_id: ObjectID,
+    /// What happened
+    pub action: BlobAction,
+    /// The domain the blob belongs to
+    pub domain: String,
+    /// The package the canary covers
+    pub package_id: SuiAddress,
+    /// Walrus contract blob ID after the change (`None` once deleted)
+    pub contract_blob_id: Option<SuiAddress>,
+    /// Walrus explanation blob ID after the change (`None` once deleted)
+    pub explain_blob_id: Option<SuiAddress>,
+    /// Checkpoint timestamp of the change (in milliseconds)
+    pub timestamp_ms: u64,
+    /// The admin who made the change
+    pub sender: SuiAddress,
+    /// The transaction that made the change
+    pub digest: TransactionDigest,
+}
+
+/// Outcome of one `Indexer::sync`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Transactions ingested
+    pub transactions: usize,
+    /// Canary events ingested
+    pub events: usize,
+}
+
+/// SQLite index of one or more registries' history
+pub struct Indexer {
+    conn: Mutex<Connection>,
+}
+
+impl Indexer {
+    /// Open (or create) an index database
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::indexer::Indexer;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+    ///
+    /// let indexer = Indexer::open("canary-index.sqlite")?;
+    /// let report = indexer.sync(&client, registry_id).await?;
+    /// println!("Indexed {} transactions", report.transactions);
+    ///
+    /// for version in indexer.blob_history(registry_id, "example.com")? {
+    ///     println!("{:?} at {}", version.action, version.timestamp_ms);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open(path).map_err(db_error)?)
+    }
+
+    /// Open a temporary in-memory index
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open_in_memory().map_err(db_error)?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, IndexerError> {
+        self.conn
+            .lock()
+            .map_err(|e| IndexerError::Database(format!("Index lock poisoned: {}", e)))
+    }
+
+    /// Ingest every registry transaction since the last sync
+    ///
+    /// The first sync walks the registry's whole history. Progress is committed with
+    /// each transaction, so an interrupted sync resumes where it stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` for querying
+    /// * `registry_id` - The Registry object ID
+    ///
+    /// # Returns
+    ///
+    /// Returns a `SyncReport`, or an `IndexerError` if the chain or the database
+    /// cannot be read.
+    pub async fn sync(
+        &self,
+        client: &SuiClient,
+        registry_id: ObjectID,
+    ) -> Result<SyncReport, IndexerError> {
+        // Blob updates and deletions borrow the registry immutably, so they are
+        // found as inputs rather than as changes
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::InputObject(registry_id)),
+            Some(
+                SuiTransactionBlockResponseOptions::new()
+                    .with_input()
+                    .with_effects(),
+            ),
+        );
+
+        let mut report = SyncReport::default();
+        let mut cursor = self.cursor(registry_id)?;
+        loop {
+            let page = client
+                .read_api()
+                .query_transaction_blocks(query.clone(), cursor, Some(INDEXER_PAGE_SIZE), false)
+                .await
+                .map_err(|e| IndexerError::Chain(e.to_string()))?;
+
+            for response in &page.data {
+                let transaction = decode_transaction(response)?;
+                report.events += transaction.events.len();
+                report.transactions += 1;
+                self.ingest(registry_id, &transaction)?;
+                cursor = Some(response.digest);
+            }
+
+            if !page.has_next_page {
+                return Ok(report);
+            }
+        }
+    }
+
+    /// Record one transaction and apply its events
+    ///
+    /// Ingesting a transaction that is already indexed does nothing, so overlapping
+    /// syncs are harmless. Otherwise the transaction becomes the registry's cursor.
+    pub fn ingest(
+        &self,
+        registry_id: ObjectID,
+        transaction: &IndexedTransaction,
+    ) -> Result<(), IndexerError> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(db_error)?;
+
+        let registry = registry_id.to_string();
+        let digest = transaction.digest.to_string();
+        let sender = transaction.sender.to_string();
+        let inserted = tx
+            .execute(
+                "INSERT OR IGNORE INTO transactions
+                     (digest, registry_id, checkpoint, timestamp_ms, sender, function, success)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    digest,
+                    registry,
+                    transaction.checkpoint,
+                    transaction.timestamp_ms,
+                    sender,
+                    transaction.function,
+                    transaction.success,
+                ],
+            )
+            .map_err(db_error)?;
+
+        if inserted == 0 {
+            return Ok(());
+        }
+
+        let at = transaction.timestamp_ms;
+        for event in &transaction.events {
+            match event {
+                CanaryEvent::MemberJoined { member, domain } => {
+                    tx.execute(
+                        "INSERT INTO members
+                             (registry_id, member, domain, joined_at, joined_digest)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![registry, member.to_string(), domain, at, digest],
+                    )
+                    .map_err(db_error)?;
+                }
+                CanaryEvent::MemberRemoved { member } => {
+                    tx.execute(
+                        "UPDATE members SET removed_at = ?1, removed_digest = ?2
+                         WHERE registry_id = ?3 AND member = ?4 AND removed_at IS NULL",
+                        params![at, digest, registry, member.to_string()],
+                    )
+                    .map_err(db_error)?;
+                }
+                CanaryEvent::BlobStored {
+                    blob_id,
+                    domain,
+                    package_id,
+                    contract_blob_id,
+                    explain_blob_id,
+                } => {
+                    tx.execute(
+                        "INSERT INTO blobs (registry_id, blob_id, action, domain, package_id,
+                             contract_blob_id, explain_blob_id, timestamp_ms, sender, digest)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                        params![
+                            registry,
+                            blob_id.to_string(),
+                            BlobAction::Stored.as_str(),
+                            domain,
+                            package_id.to_string(),
+                            contract_blob_id.to_string(),
+                            explain_blob_id.to_string(),
+                            at,
+                            sender,
+                            digest,
+                        ],
+                    )
+                    .map_err(db_error)?;
+                }
+                CanaryEvent::BlobUpdated {
+                    blob_id,
+                    contract_blob_id,
+                    explain_blob_id,
+                } => {
+                    insert_blob_change(
+                        &tx,
+                        &registry,
+                        *blob_id,
+                        BlobAction::Updated,
+                        Some((*contract_blob_id, *explain_blob_id)),
+                        at,
+                        &sender,
+                        &digest,
+                    )?;
+                }
+                CanaryEvent::BlobDeleted { blob_id } => {
+                    insert_blob_change(
+                        &tx,
+                        &registry,
+                        *blob_id,
+                        BlobAction::Deleted,
+                        None,
+                        at,
+                        &sender,
+                        &digest,
+                    )?;
+                }
+            }
+        }
+
+        tx.execute(
+            "INSERT INTO sync_cursors (registry_id, digest) VALUES (?1, ?2)
+             ON CONFLICT (registry_id) DO UPDATE SET digest = excluded.digest",
+            params![registry, digest],
+        )
+        .map_err(db_error)?;
+        tx.commit().map_err(db_error)
+    }
+
+    /// The last transaction ingested for a registry
+    pub fn cursor(&self, registry_id: ObjectID) -> Result<Option<TransactionDigest>, IndexerError> {
+        let conn = self.lock()?;
+        let digest: Option<String> = conn
+            .query_row(
+                "SELECT digest FROM sync_cursors WHERE registry_id = ?1",
+                params![registry_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?;
+        digest
+            .map(|d| parse_column(&d, TransactionDigest::from_str))
+            .transpose()
+    }
+
+    /// The registry's members as of a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_id` - The Registry object ID
+    /// * `timestamp_ms` - The point in time, in Unix milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Returns the members who had joined at or before `timestamp_ms` and were not yet
+    /// removed, in join order. Only the indexed history is considered, so sync first.
+    pub fn members_at(
+        &self,
+        registry_id: ObjectID,
+        timestamp_ms: u64,
+    ) -> Result<Vec<MemberInfoWithAddress>, IndexerError> {
+        let conn = self.lock()?;
+        let mut statement = conn
+            .prepare(
+                "SELECT member, domain, joined_at FROM members
+                 WHERE registry_id = ?1 AND joined_at <= ?2
+                   AND (removed_at IS NULL OR removed_at > ?2)
+                 ORDER BY joined_at, rowid",
+            )
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map(params![registry_id.to_string(), timestamp_ms], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, u64>(2)?,
+                ))
+            })
+            .map_err(db_error)?;
+
+        rows.map(|row| {
+            let (member, domain, joined_at) = row.map_err(db_error)?;
+            Ok(MemberInfoWithAddress {
+                member: parse_column(&member, SuiAddress::from_str)?,
+                domain,
+                joined_at,
+                raw: None,
+            })
+        })
+        .collect()
+    }
+
+    /// Every change to the canary blobs of a domain, oldest first
+    pub fn blob_history(
+        &self,
+        registry_id: ObjectID,
+        domain: &str,
+    ) -> Result<Vec<BlobVersion>, IndexerError> {
+        let conn = self.lock()?;
+        let mut statement = conn
+            .prepare(
+                "SELECT blob_id, action, domain, package_id, contract_blob_id, explain_blob_id,
+                        timestamp_ms, sender, digest
+                 FROM blobs
+                 WHERE registry_id = ?1 AND domain = ?2
+                 ORDER BY timestamp_ms, rowid",
+            )
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map(params![registry_id.to_string(), domain], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, u64>(6)?,
+                    row.get::<_, String>(7)?,
+                    row.get::<_, String>(8)?,
+                ))
+            })
+            .map_err(db_error)?;
+
+        rows.map(|row| {
+            let (
+                blob_id,
+                action,
+                domain,
+                package_id,
+                contract,
+                explain,
+                timestamp_ms,
+                sender,
+                digest,
+            ) = row.map_err(db_error)?;
+            Ok(BlobVersion {
+                blob_id: parse_column(&blob_id, ObjectID::from_hex_literal)?,
+                action: action.parse()?,
+                domain,
+                package_id: parse_column(&package_id, SuiAddress::from_str)?,
+                contract_blob_id: contract
+                    .map(|id| parse_column(&id, SuiAddress::from_str))
+                    .transpose()?,
+                explain_blob_id: explain
+                    .map(|id| parse_column(&id, SuiAddress::from_str))
+                    .transpose()?,
+                timestamp_ms,
+                sender: parse_column(&sender, SuiAddress::from_str)?,
+                digest: parse_column(&digest, TransactionDigest::from_str)?,
+            })
+        })
+        .collect()
+    }
+}
+
+/// Record an update or deletion, copying the domain and package of the blob's
+/// previous row
+#[allow(clippy::too_many_arguments)]
+fn insert_blob_change(
+    tx: &rusqlite::Transaction<'_>,
+    registry: &str,
+    blob_id: ObjectID,
+    action: BlobAction,
+    blobs: Option<(SuiAddress, SuiAddress)>,
+    timestamp_ms: u64,
+    sender: &str,
+    digest: &str,
+) -> Result<(), IndexerError> {
+    let blob = blob_id.to_string();
+    let previous: Option<(String, String)> = tx
+        .query_row(
+            "SELECT domain, package_id FROM blobs
+             WHERE registry_id = ?1 AND blob_id = ?2
+             ORDER BY timestamp_ms DESC, rowid DESC LIMIT 1",
+            params![registry, blob],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(db_error)?;
+    let Some((domain, package_id)) = previous else {
+        // Stored before the indexed history began
+        tracing::warn!(blob = %blob_id, digest, "Skipping change to an unindexed blob");
+        return Ok(());
+    };
+
+    let (contract, explain) = match blobs {
+        Some((contract, explain)) => (Some(contract.to_string()), Some(explain.to_string())),
+        None => (None, None),
+    };
+    tx.execute(
+        "INSERT INTO blobs (registry_id, blob_id, action, domain, package_id,
+             contract_blob_id, explain_blob_id, timestamp_ms, sender, digest)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            registry,
+            blob,
+            action.as_str(),
+            domain,
+            package_id,
+            contract,
+            explain,
+            timestamp_ms,
+            sender,
+            digest,
+        ],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+/// Decode the canary events of one transaction
+///
+/// # Returns
+///
+/// Returns the `IndexedTransaction`, or an `IndexerError` if the response lacks its
+/// input, effects, or timestamp, or a canary call has arguments of the wrong shape.
+pub fn decode_transaction(
+    response: &SuiTransactionBlockResponse,
+) -> Result<IndexedTransaction, IndexerError> {
+    let decode_error = |message: &str| IndexerError::Decode {
+        digest: response.digest.to_string(),
+        message: message.to_string(),
+    };
+
+    let transaction = response
+        .transaction
+        .as_ref()
+        .ok_or_else(|| decode_error("no transaction input"))?;
+    let effects = response
+        .effects
+        .as_ref()
+        .ok_or_else(|| decode_error("no effects"))?;
+    let timestamp_ms = response
+        .timestamp_ms
+        .ok_or_else(|| decode_error("no checkpoint timestamp"))?;
+    let sender = *transaction.data.sender();
+    let success = effects.status().is_ok();
+
+    let mut function = None;
+    let mut events = Vec::new();
+    if let SuiTransactionBlockKind::ProgrammableTransaction(pt) = transaction.data.transaction() {
+        // Objects shared by the transaction, in creation order: the blobs `store_blob` claimed
+        let mut created_shared = effects
+            .created()
+            .iter()
+            .filter(|object| object.owner.is_shared())
+            .map(|object| object.reference.object_id);
+
+        for command in &pt.commands {
+            let SuiCommand::MoveCall(call) = command else {
+                continue;
+            };
+            let name = match (call.module.as_str(), call.function.as_str()) {
+                ("member_registry", f @ ("join_registry" | "remove_member"))
+                | ("pkg_storage", f @ ("store_blob" | "update_blob" | "delete_canary_blob")) => {
+                    format!("{}::{}", call.module, f)
+                }
+                _ => continue,
+            };
+            function.get_or_insert(name);
+            if !success {
+                continue;
+            }
+
+            let arg = |index: usize| -> Result<&SuiCallArg, IndexerError> {
+                match call.arguments.get(index) {
+                    Some(SuiArgument::Input(input)) => pt
+                        .inputs
+                        .get(*input as usize)
+                        .ok_or_else(|| decode_error("argument refers to a missing input")),
+                    _ => Err(decode_error(&format!(
+                        "{}::{} argument {} is not a transaction input",
+                        call.module, call.function, index
+                    ))),
+                }
+            };
+            let pure = |index: usize| -> Result<serde_json::Value, IndexerError> {
+                arg(index)?
+                    .pure()
+                    .map(|value| value.to_json_value())
+                    .ok_or_else(|| decode_error("expected a pure argument"))
+            };
+            let address = |index: usize| -> Result<SuiAddress, IndexerError> {
+                pure(index)?
+                    .as_str()
+                    .and_then(|s| SuiAddress::from_str(s).ok())
+                    .ok_or_else(|| decode_error("expected an address argument"))
+            };
+            let object = |index: usize| -> Result<ObjectID, IndexerError> {
+                arg(index)?
+                    .object()
+                    .copied()
+                    .ok_or_else(|| decode_error("expected an object argument"))
+            };
+
+            match call.function.as_str() {
+                "join_registry" => events.push(CanaryEvent::MemberJoined {
+                    member: sender,
+                    domain: pure_string(&pure(2)?)
+                        .ok_or_else(|| decode_error("expected a string domain"))?,
+                }),
+                "remove_member" => events.push(CanaryEvent::MemberRemoved {
+                    member: address(2)?,
+                }),
+                "store_blob" => events.push(CanaryEvent::BlobStored {
+                    blob_id: created_shared
+                        .next()
+                        .ok_or_else(|| decode_error("store_blob created no shared object"))?,
+                    domain: pure_string(&pure(2)?)
+                        .ok_or_else(|| decode_error("expected a string domain"))?,
+                    contract_blob_id: address(3)?,
+                    explain_blob_id: address(4)?,
+                    package_id: address(5)?,
+                }),
+                "update_blob" => events.push(CanaryEvent::BlobUpdated {
+                    blob_id: object(2)?,
+                    contract_blob_id: address(3)?,
+                    explain_blob_id: address(4)?,
+                }),
+                "delete_canary_blob" => events.push(CanaryEvent::BlobDeleted {
+                    blob_id: object(2)?,
+                }),
+                _ => unreachable!("filtered above"),
+            }
+        }
+    }
+
+    Ok(IndexedTransaction {
+        digest: response.digest,
+        checkpoint: response.checkpoint,
+        timestamp_ms,
+        sender,
+        function,
+        success,
+        events,
+    })
+}
+
+/// Read a Move `String` argument, rendered either as a JSON string or as its UTF-8 bytes
+fn pure_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(bytes) => {
+            let bytes = bytes
+                .iter()
+                .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect::<Option<Vec<u8>>>()?;
+            String::from_utf8(bytes).ok()
+        }
+        _ => None,
+    }
+}
+
+fn parse_column<T, E: std::fmt::Display>(
+    value: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, IndexerError> {
+    parse(value).map_err(|e| IndexerError::Database(format!("Invalid value {}: {}", value, e)))
+}
+
+fn db_error(e: rusqlite::Error) -> IndexerError {
+    IndexerError::Database(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(byte: u8) -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(byte))
+    }
+
+    fn transaction(seq: u8, timestamp_ms: u64, events: Vec<CanaryEvent>) -> IndexedTransaction {
+        IndexedTransaction {
+            digest: TransactionDigest::new([seq; 32]),
+            checkpoint: Some(seq as u64),
+            timestamp_ms,
+            sender: address(0xad),
+            function: None,
+            success: true,
+            events,
+        }
+    }
+
+    #[test]
+    fn test_members_at() {
+        let indexer = Indexer::open_in_memory().unwrap();
+        let registry_id = ObjectID::from_single_byte(9);
+        let join = |member: u8, domain: &str| CanaryEvent::MemberJoined {
+            member: address(member),
+            domain: domain.to_string(),
+        };
+
+        for tx in [
+            transaction(1, 100, vec![join(1, "a.com")]),
+            transaction(2, 200, vec![join(2, "b.com")]),
+            transaction(
+                3,
+                300,
+                vec![CanaryEvent::MemberRemoved { member: address(1) }],
+            ),
+            transaction(4, 400, vec![join(1, "new-a.com")]),
+        ] {
+            indexer.ingest(registry_id, &tx).unwrap();
+        }
+
+        let domains = |t: u64| -> Vec<String> {
+            indexer
+                .members_at(registry_id, t)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.domain)
+                .collect()
+        };
+        assert!(domains(50).is_empty());
+        assert_eq!(domains(250), vec!["a.com", "b.com"]);
+        assert_eq!(domains(300), vec!["b.com"]);
+        assert_eq!(domains(500), vec!["b.com", "new-a.com"]);
+        assert!(indexer
+            .members_at(ObjectID::from_single_byte(8), 500)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_blob_history_and_reingest() {
+        let indexer = Indexer::open_in_memory().unwrap();
+        let registry_id = ObjectID::from_single_byte(9);
+        let blob_id = ObjectID::from_single_byte(0xb1);
+
+        let stored = transaction(
+            1,
+            100,
+            vec![CanaryEvent::BlobStored {
+                blob_id,
+                domain: "example.com".to_string(),
+                package_id: address(0xee),
+                contract_blob_id: address(0xc1),
+                explain_blob_id: address(0xe1),
+            }],
+        );
+        indexer.ingest(registry_id, &stored).unwrap();
+        indexer
+            .ingest(
+                registry_id,
+                &transaction(
+                    2,
+                    200,
+                    vec![CanaryEvent::BlobUpdated {
+                        blob_id,
+                        contract_blob_id: address(0xc2),
+                        explain_blob_id: address(0xe2),
+                    }],
+                ),
+            )
+            .unwrap();
+        indexer
+            .ingest(
+                registry_id,
+                &transaction(3, 300, vec![CanaryEvent::BlobDeleted { blob_id }]),
+            )
+            .unwrap();
+        // Already indexed
+        indexer.ingest(registry_id, &stored).unwrap();
+
+        let history = indexer.blob_history(registry_id, "example.com").unwrap();
+        let actions: Vec<BlobAction> = history.iter().map(|v| v.action).collect();
+        assert_eq!(
+            actions,
+            vec![BlobAction::Stored, BlobAction::Updated, BlobAction::Deleted]
+        );
+        assert_eq!(history[1].contract_blob_id, Some(address(0xc2)));
+        assert_eq!(history[2].package_id, address(0xee));
+        assert_eq!(history[2].contract_blob_id, None);
+        assert!(indexer
+            .blob_history(registry_id, "other.com")
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            indexer.cursor(registry_id).unwrap(),
+            Some(TransactionDigest::new([3; 32]))
+        );
+    }
+
+    #[test]
+    fn test_pure_string() {
+        assert_eq!(
+            pure_string(&serde_json::json!("example.com")),
+            Some("example.com".to_string())
+        );
+        assert_eq!(
+            pure_string(&serde_json::json!([104, 105])),
+            Some("hi".to_string())
+        );
+        assert_eq!(pure_string(&serde_json::json!([300])), None);
+    }
+}
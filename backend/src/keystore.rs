@@ -2,35 +2,78 @@
 //!
 //! This module provides utilities for:
 //! - Parsing Bech32-encoded private keys from `sui keytool export`
+//! - Deriving private keys from BIP-39 mnemonics (feature `mnemonic`)
+//! - Passphrase-encrypted key files (feature `encrypted-keys`)
 //! - Adding private keys to Sui keystores
 //! - Creating keystores from private keys
+//! - Fingerprinting keys and confirming the expected signer address
+//! - k-of-n multisig accounts (`multisig`)
+//! - Cloud KMS signers that never expose the private key (`kms`, feature `kms`)
 
 use crate::error::KeystoreError;
+#[cfg(feature = "mnemonic")]
+use bip32::DerivationPath;
+#[cfg(feature = "mnemonic")]
+use bip39::{Language, Mnemonic, Seed};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "mnemonic")]
+use sui_keys::key_derive::derive_key_pair_from_path;
 use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
 use sui_sdk::types::base_types::SuiAddress;
 use sui_sdk::types::crypto::{SignatureScheme, SuiKeyPair};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "encrypted-keys")]
+pub mod encrypted;
+#[cfg(feature = "kms")]
+pub mod kms;
+pub mod multisig;
+
+#[cfg(feature = "encrypted-keys")]
+pub use encrypted::{encrypt_key_to_file, load_encrypted_key};
+pub use multisig::{sign_partial, Multisig, PartialSignatures, Participant};
 
 /// Parsed private key information
 ///
 /// This struct holds the decoded private key information after parsing the Bech32 string.
 /// It serves as a bridge between parsing and keystore operations.
-#[derive(Debug, Clone)]
+///
+/// The key bytes are zeroed when the struct is dropped, and `Debug` never prints them.
+/// The struct is deliberately not `Clone`: pass it by reference instead of copying it.
 pub struct ParsedPrivateKey {
     /// The raw private key bytes (32 bytes)
-    pub private_key_bytes: [u8; 32],
+    private_key_bytes: Zeroizing<[u8; 32]>,
     /// The cryptographic scheme used (Ed25519, Secp256k1, or Secp256r1)
     pub scheme: SignatureScheme,
     /// The flag byte from the Bech32 encoding (first byte of the 33-byte payload)
     pub flag: u8,
 }
 
+impl std::fmt::Debug for ParsedPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParsedPrivateKey")
+            .field("private_key_bytes", &"<redacted>")
+            .field("scheme", &self.scheme)
+            .field("flag", &self.flag)
+            .finish()
+    }
+}
+
 impl ParsedPrivateKey {
+    /// The raw private key bytes
+    ///
+    /// Avoid copying the result into buffers that outlive this struct; they are not
+    /// zeroed on drop.
+    pub fn private_key_bytes(&self) -> &[u8; 32] {
+        &self.private_key_bytes
+    }
+
     /// Convert the parsed private key into a `SuiKeyPair`
     pub fn to_keypair(&self) -> Result<SuiKeyPair, KeystoreError> {
         // Reconstruct the 33-byte format: flag || private_key_bytes
-        let mut bytes = Vec::with_capacity(33);
+        let mut bytes = Zeroizing::new(Vec::with_capacity(33));
         bytes.push(self.flag);
-        bytes.extend_from_slice(&self.private_key_bytes);
+        bytes.extend_from_slice(self.private_key_bytes.as_slice());
 
         SuiKeyPair::from_bytes(&bytes).map_err(|e| KeystoreError::SuiSdkError(e.to_string()))
     }
@@ -40,6 +83,27 @@ impl ParsedPrivateKey {
         let keypair = self.to_keypair()?;
         Ok(SuiAddress::from(&keypair.public()))
     }
+
+    /// Get a short fingerprint identifying this key
+    ///
+    /// The fingerprint is the first 8 bytes of the SHA-256 digest of the flagged public key
+    /// (`flag || public_key`), formatted as colon-separated hex (e.g. `3f:a2:...`).
+    /// It never reveals private key material and is safe to print in logs.
+    pub fn fingerprint(&self) -> Result<String, KeystoreError> {
+        let keypair = self.to_keypair()?;
+        let public_key = keypair.public();
+
+        let mut hasher = Sha256::new();
+        hasher.update([public_key.flag()]);
+        hasher.update(public_key.as_ref());
+        let digest = hasher.finalize();
+
+        Ok(digest[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":"))
+    }
 }
 
 /// Parse a Bech32-encoded private key string
@@ -67,7 +131,64 @@ pub fn parse_bech32_private_key(bech32_str: &str) -> Result<ParsedPrivateKey, Ke
     // Use SuiKeyPair::decode which handles Bech32 decoding internally
     let keypair =
         SuiKeyPair::decode(bech32_str).map_err(|e| KeystoreError::InvalidBech32(e.to_string()))?;
+    parsed_from_keypair(&keypair)
+}
+
+/// Derive a private key from a BIP-39 mnemonic phrase
+///
+/// This matches `sui keytool import` with a mnemonic: the seed is derived with an empty
+/// passphrase, and the key at `derivation_path` is derived for `scheme`. When no path is
+/// given, the scheme's default Sui path is used (`m/44'/784'/0'/0'/0'` for Ed25519,
+/// `m/54'/784'/0'/0/0` for Secp256k1, `m/74'/784'/0'/0/0` for Secp256r1).
+///
+/// # Arguments
+///
+/// * `phrase` - The English BIP-39 mnemonic (12 to 24 words)
+/// * `derivation_path` - Optional derivation path, e.g. `m/44'/784'/1'/0'/0'`
+/// * `scheme` - The signature scheme of the derived key
+///
+/// # Returns
+///
+/// Returns a `ParsedPrivateKey` for the derived key, or a `KeystoreError` if the phrase or
+/// path is invalid.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::keystore::{add_to_keystore, parse_mnemonic};
+/// use sui_keys::keystore::{InMemKeystore, Keystore};
+/// use sui_sdk::types::crypto::SignatureScheme;
+///
+/// let phrase = std::env::var("SUI_MNEMONIC")?;
+/// let parsed = parse_mnemonic(&phrase, Some("m/44'/784'/1'/0'/0'"), SignatureScheme::ED25519)?;
+/// let mut keystore = Keystore::InMem(InMemKeystore::default());
+/// let address = add_to_keystore(&mut keystore, parsed).await?;
+/// ```
+#[cfg(feature = "mnemonic")]
+pub fn parse_mnemonic(
+    phrase: &str,
+    derivation_path: Option<&str>,
+    scheme: SignatureScheme,
+) -> Result<ParsedPrivateKey, KeystoreError> {
+    let mnemonic = Mnemonic::from_phrase(phrase.trim(), Language::English)
+        .map_err(|e| KeystoreError::InvalidMnemonic(e.to_string()))?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let path = derivation_path
+        .map(|path| {
+            path.trim()
+                .parse::<DerivationPath>()
+                .map_err(|e| KeystoreError::InvalidDerivationPath(format!("{}: {}", path, e)))
+        })
+        .transpose()?;
+
+    let (_, keypair) = derive_key_pair_from_path(seed.as_bytes(), path, &scheme)
+        .map_err(|e| KeystoreError::InvalidDerivationPath(format!("{} ({:?})", e, scheme)))?;
+    parsed_from_keypair(&keypair)
+}
 
+/// Split a keypair into the flag and raw private key bytes
+fn parsed_from_keypair(keypair: &SuiKeyPair) -> Result<ParsedPrivateKey, KeystoreError> {
     // Extract scheme and private key bytes
     let scheme = match keypair {
         SuiKeyPair::Ed25519(_) => SignatureScheme::ED25519,
@@ -79,12 +200,12 @@ pub fn parse_bech32_private_key(bech32_str: &str) -> Result<ParsedPrivateKey, Ke
     let flag = scheme.flag();
 
     // Extract private key bytes (32 bytes)
-    let private_key_bytes = keypair.to_bytes_no_flag();
+    let private_key_bytes = Zeroizing::new(keypair.to_bytes_no_flag());
     if private_key_bytes.len() != 32 {
         return Err(KeystoreError::InvalidKeyLength(private_key_bytes.len()));
     }
 
-    let mut key_bytes_array = [0u8; 32];
+    let mut key_bytes_array = Zeroizing::new([0u8; 32]);
     key_bytes_array.copy_from_slice(&private_key_bytes);
 
     Ok(ParsedPrivateKey {
@@ -94,6 +215,47 @@ pub fn parse_bech32_private_key(bech32_str: &str) -> Result<ParsedPrivateKey, Ke
     })
 }
 
+/// Confirm that a parsed private key derives the expected signer address
+///
+/// Use this at startup to make sure the key loaded from the environment is the one
+/// the deployment was configured for, e.g. to avoid running mainnet tasks with a
+/// devnet key after an environment variable mix-up.
+///
+/// # Arguments
+///
+/// * `parsed_key` - The parsed private key
+/// * `expected` - The address the key is expected to derive
+///
+/// # Returns
+///
+/// Returns the derived `SuiAddress` if it matches, or `KeystoreError::AddressMismatch` otherwise.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::keystore::{confirm_signer_address, parse_bech32_private_key};
+/// use sui_sdk::types::base_types::SuiAddress;
+/// use std::str::FromStr;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let parsed = parse_bech32_private_key("suiprivkey1...")?;
+/// let expected = SuiAddress::from_str("0x123...")?;
+/// let address = confirm_signer_address(&parsed, expected)?;
+/// println!("Signer {} ({})", address, parsed.fingerprint()?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn confirm_signer_address(
+    parsed_key: &ParsedPrivateKey,
+    expected: SuiAddress,
+) -> Result<SuiAddress, KeystoreError> {
+    let actual = parsed_key.to_address()?;
+    if actual != expected {
+        return Err(KeystoreError::AddressMismatch { expected, actual });
+    }
+    Ok(actual)
+}
+
 /// Add a parsed private key to a keystore
 ///
 /// This function adds a `ParsedPrivateKey` to an existing keystore and returns the Sui address.
@@ -204,6 +366,7 @@ pub async fn create_keystore_from_key(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
     use sui_sdk::types::crypto::deterministic_random_account_key;
 
     /// Helper function to generate a test Ed25519 keypair and encode it to Bech32
@@ -302,6 +465,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parsed_private_key_debug_is_redacted() {
+        let (bech32_key, _expected_keypair, _) = generate_test_bech32_key(SignatureScheme::ED25519);
+        let parsed = parse_bech32_private_key(&bech32_key).expect("Failed to parse");
+
+        let debug = format!("{:?}", parsed);
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains(&format!("{:?}", parsed.private_key_bytes())));
+    }
+
     #[test]
     fn test_parsed_private_key_to_address() {
         let (bech32_key, _expected_keypair, expected_address) =
@@ -436,4 +609,110 @@ mod tests {
         assert_eq!(parsed.flag, SignatureScheme::ED25519.flag());
         assert_eq!(parsed.flag, 0x00);
     }
+
+    #[cfg(feature = "mnemonic")]
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn test_parse_mnemonic_default_path() {
+        let parsed = parse_mnemonic(TEST_MNEMONIC, None, SignatureScheme::ED25519)
+            .expect("Failed to parse mnemonic");
+        assert_eq!(parsed.scheme, SignatureScheme::ED25519);
+
+        let explicit = parse_mnemonic(
+            TEST_MNEMONIC,
+            Some("m/44'/784'/0'/0'/0'"),
+            SignatureScheme::ED25519,
+        )
+        .expect("Failed to parse mnemonic");
+        assert_eq!(parsed.to_address().unwrap(), explicit.to_address().unwrap());
+
+        // Re-encoding the derived key must round-trip through the Bech32 parser
+        let bech32_key = parsed.to_keypair().unwrap().encode().unwrap();
+        let reparsed = parse_bech32_private_key(&bech32_key).unwrap();
+        assert_eq!(reparsed.private_key_bytes, parsed.private_key_bytes);
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn test_parse_mnemonic_account_index() {
+        let first = parse_mnemonic(TEST_MNEMONIC, None, SignatureScheme::ED25519).unwrap();
+        let second = parse_mnemonic(
+            TEST_MNEMONIC,
+            Some("m/44'/784'/1'/0'/0'"),
+            SignatureScheme::ED25519,
+        )
+        .unwrap();
+        assert_ne!(first.to_address().unwrap(), second.to_address().unwrap());
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn test_parse_mnemonic_secp256k1() {
+        let parsed = parse_mnemonic(TEST_MNEMONIC, None, SignatureScheme::Secp256k1).unwrap();
+        assert_eq!(parsed.scheme, SignatureScheme::Secp256k1);
+        assert_eq!(parsed.flag, SignatureScheme::Secp256k1.flag());
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn test_parse_mnemonic_invalid() {
+        assert!(matches!(
+            parse_mnemonic("not a valid phrase", None, SignatureScheme::ED25519),
+            Err(KeystoreError::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            parse_mnemonic(TEST_MNEMONIC, Some("not/a/path"), SignatureScheme::ED25519),
+            Err(KeystoreError::InvalidDerivationPath(_))
+        ));
+        // Ed25519 only supports fully hardened paths under purpose 44
+        assert!(matches!(
+            parse_mnemonic(
+                TEST_MNEMONIC,
+                Some("m/54'/784'/0'/0/0"),
+                SignatureScheme::ED25519
+            ),
+            Err(KeystoreError::InvalidDerivationPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_formatted() {
+        let (bech32_key, _, _) = generate_test_bech32_key(SignatureScheme::ED25519);
+        let parsed = parse_bech32_private_key(&bech32_key).expect("Failed to parse");
+
+        let first = parsed.fingerprint().expect("Failed to fingerprint");
+        let second = parsed.fingerprint().expect("Failed to fingerprint");
+        assert_eq!(first, second);
+
+        // 8 bytes rendered as "xx:" * 7 + "xx"
+        assert_eq!(first.len(), 23);
+        assert_eq!(first.split(':').count(), 8);
+    }
+
+    #[test]
+    fn test_confirm_signer_address_match() {
+        let (bech32_key, _, expected_address) = generate_test_bech32_key(SignatureScheme::ED25519);
+        let parsed = parse_bech32_private_key(&bech32_key).expect("Failed to parse");
+
+        let address =
+            confirm_signer_address(&parsed, expected_address).expect("Address should match");
+        assert_eq!(address, expected_address);
+    }
+
+    #[test]
+    fn test_confirm_signer_address_mismatch() {
+        let (bech32_key, _, expected_address) = generate_test_bech32_key(SignatureScheme::ED25519);
+        let parsed = parse_bech32_private_key(&bech32_key).expect("Failed to parse");
+
+        let wrong = SuiAddress::from_str("0x1").unwrap();
+        match confirm_signer_address(&parsed, wrong) {
+            Err(KeystoreError::AddressMismatch { expected, actual }) => {
+                assert_eq!(expected, wrong);
+                assert_eq!(actual, expected_address);
+            }
+            other => panic!("Expected AddressMismatch error, got: {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,330 @@
+//! Passphrase-encrypted key files
+//!
+//! An encrypted key file keeps a private key at rest without leaving it in plaintext
+//! in an environment variable or `.env` file. The key is encrypted with AES-256-GCM
+//! under a key derived from the passphrase with scrypt. The file is JSON:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "address": "0xabc...",
+//!   "kdf": { "log_n": 17, "r": 8, "p": 1, "salt": "..." },
+//!   "nonce": "...",
+//!   "ciphertext": "..."
+//! }
+//! ```
+//!
+//! The address is stored in clear so operators can tell key files apart without the
+//! passphrase. It is authenticated together with the ciphertext, so editing it makes
+//! decryption fail.
+
+use super::{parsed_from_keypair, ParsedPrivateKey};
+use crate::error::KeystoreError;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::SuiKeyPair;
+use zeroize::Zeroizing;
+
+/// Current key file format version
+const VERSION: u32 = 1;
+
+/// scrypt cost for new key files (N = 2^17, about 128 MiB of memory)
+const SCRYPT_LOG_N: u8 = 17;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Salt length, in bytes
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    address: SuiAddress,
+    kdf: ScryptParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+/// Load a private key from a passphrase-encrypted key file
+///
+/// # Arguments
+///
+/// * `path` - The key file written by `encrypt_key_to_file`
+/// * `passphrase` - The passphrase the file was encrypted with
+///
+/// # Returns
+///
+/// Returns the decrypted `ParsedPrivateKey`, or `KeystoreError::DecryptionFailed` if
+/// the passphrase is wrong or the file was modified, or `KeystoreError::KeyFile` if
+/// the file cannot be read or parsed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::keystore::{add_to_keystore, load_encrypted_key};
+/// use sui_keys::keystore::{InMemKeystore, Keystore};
+///
+/// let passphrase = std::env::var("SUI_KEY_PASSPHRASE")?;
+/// let parsed = load_encrypted_key("/run/secrets/signer.key.json", &passphrase)?;
+/// let mut keystore = Keystore::InMem(InMemKeystore::default());
+/// let address = add_to_keystore(&mut keystore, parsed).await?;
+/// ```
+pub fn load_encrypted_key(
+    path: impl AsRef<Path>,
+    passphrase: &str,
+) -> Result<ParsedPrivateKey, KeystoreError> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| KeystoreError::KeyFile(format!("Failed to read {}: {}", path.display(), e)))?;
+    decrypt_key(&contents, passphrase)
+}
+
+/// Encrypt a private key with a passphrase and write it to a new key file
+///
+/// The file is created with owner-only permissions on Unix. An existing file is
+/// never overwritten, so a typo cannot destroy another key.
+///
+/// # Arguments
+///
+/// * `path` - Where to write the key file
+/// * `parsed_key` - The private key to encrypt
+/// * `passphrase` - The passphrase to encrypt with (must not be empty)
+///
+/// # Returns
+///
+/// Returns the key's `SuiAddress`, or a `KeystoreError` if the passphrase is empty or
+/// the file exists or cannot be written.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::keystore::{encrypt_key_to_file, parse_bech32_private_key};
+///
+/// let parsed = parse_bech32_private_key("suiprivkey1...")?;
+/// let address = encrypt_key_to_file("signer.key.json", &parsed, "correct horse battery")?;
+/// println!("Wrote the key for {}", address);
+/// ```
+pub fn encrypt_key_to_file(
+    path: impl AsRef<Path>,
+    parsed_key: &ParsedPrivateKey,
+    passphrase: &str,
+) -> Result<SuiAddress, KeystoreError> {
+    let path = path.as_ref();
+    let address = parsed_key.to_address()?;
+    let contents = encrypt_key(parsed_key, passphrase, SCRYPT_LOG_N)?;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| {
+            KeystoreError::KeyFile(format!("Failed to write {}: {}", path.display(), e))
+        })?;
+
+    Ok(address)
+}
+
+/// Encrypt a key into the key file format with scrypt cost `log_n`
+fn encrypt_key(
+    parsed_key: &ParsedPrivateKey,
+    passphrase: &str,
+    log_n: u8,
+) -> Result<String, KeystoreError> {
+    if passphrase.is_empty() {
+        return Err(KeystoreError::KeyFile(
+            "Passphrase must not be empty".to_string(),
+        ));
+    }
+
+    let address = parsed_key.to_address()?;
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let kdf = ScryptParams {
+        log_n,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        salt: BASE64.encode(salt),
+    };
+
+    let cipher = cipher(passphrase, &kdf)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(33));
+    plaintext.push(parsed_key.flag);
+    plaintext.extend_from_slice(parsed_key.private_key_bytes());
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &plaintext,
+                aad: address.to_string().as_bytes(),
+            },
+        )
+        .map_err(|e| KeystoreError::KeyFile(format!("Encryption failed: {}", e)))?;
+
+    let file = KeyFile {
+        version: VERSION,
+        address,
+        kdf,
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| KeystoreError::KeyFile(e.to_string()))
+}
+
+/// Decrypt the contents of a key file
+fn decrypt_key(contents: &str, passphrase: &str) -> Result<ParsedPrivateKey, KeystoreError> {
+    let file: KeyFile = serde_json::from_str(contents)
+        .map_err(|e| KeystoreError::KeyFile(format!("Invalid key file: {}", e)))?;
+    if file.version != VERSION {
+        return Err(KeystoreError::KeyFile(format!(
+            "Unsupported key file version {}",
+            file.version
+        )));
+    }
+
+    let nonce = decode(&file.nonce, "nonce")?;
+    if nonce.len() != 12 {
+        return Err(KeystoreError::KeyFile(format!(
+            "Invalid nonce length {}",
+            nonce.len()
+        )));
+    }
+    let ciphertext = decode(&file.ciphertext, "ciphertext")?;
+
+    let cipher = cipher(passphrase, &file.kdf)?;
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: file.address.to_string().as_bytes(),
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| KeystoreError::DecryptionFailed)?;
+
+    let keypair = SuiKeyPair::from_bytes(&plaintext)
+        .map_err(|e| KeystoreError::SuiSdkError(e.to_string()))?;
+    let parsed = parsed_from_keypair(&keypair)?;
+
+    let actual = parsed.to_address()?;
+    if actual != file.address {
+        return Err(KeystoreError::AddressMismatch {
+            expected: file.address,
+            actual,
+        });
+    }
+    Ok(parsed)
+}
+
+/// Derive the AES-256-GCM cipher from the passphrase
+fn cipher(passphrase: &str, kdf: &ScryptParams) -> Result<Aes256Gcm, KeystoreError> {
+    let salt = decode(&kdf.salt, "salt")?;
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+        .map_err(|e| KeystoreError::KeyFile(format!("Invalid scrypt parameters: {}", e)))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut_slice())
+        .map_err(|e| KeystoreError::KeyFile(format!("Key derivation failed: {}", e)))?;
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_slice())))
+}
+
+fn decode(value: &str, field: &str) -> Result<Vec<u8>, KeystoreError> {
+    BASE64
+        .decode(value)
+        .map_err(|e| KeystoreError::KeyFile(format!("Invalid {}: {}", field, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::crypto::deterministic_random_account_key;
+
+    /// Cheap scrypt cost, so tests stay fast
+    const TEST_LOG_N: u8 = 4;
+
+    fn parsed_key() -> ParsedPrivateKey {
+        let (_, keypair) = deterministic_random_account_key();
+        parsed_from_keypair(&SuiKeyPair::Ed25519(keypair)).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let parsed = parsed_key();
+        let contents = encrypt_key(&parsed, "passphrase", TEST_LOG_N).unwrap();
+        assert!(contents.contains(&parsed.to_address().unwrap().to_string()));
+
+        let decrypted = decrypt_key(&contents, "passphrase").unwrap();
+        assert_eq!(decrypted.private_key_bytes(), parsed.private_key_bytes());
+        assert_eq!(decrypted.scheme, parsed.scheme);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let contents = encrypt_key(&parsed_key(), "passphrase", TEST_LOG_N).unwrap();
+        assert!(matches!(
+            decrypt_key(&contents, "wrong"),
+            Err(KeystoreError::DecryptionFailed)
+        ));
+        assert!(matches!(
+            encrypt_key(&parsed_key(), "", TEST_LOG_N),
+            Err(KeystoreError::KeyFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_address_is_authenticated() {
+        let contents = encrypt_key(&parsed_key(), "passphrase", TEST_LOG_N).unwrap();
+        let mut file: KeyFile = serde_json::from_str(&contents).unwrap();
+        file.address = SuiAddress::ZERO;
+        let tampered = serde_json::to_string(&file).unwrap();
+        assert!(matches!(
+            decrypt_key(&tampered, "passphrase"),
+            Err(KeystoreError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_file_is_never_overwritten() {
+        let path = std::env::temp_dir().join(format!(
+            "canary-key-{}-{}.json",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let parsed = parsed_key();
+        let address = encrypt_key_to_file(&path, &parsed, "passphrase").unwrap();
+        assert_eq!(
+            load_encrypted_key(&path, "passphrase")
+                .unwrap()
+                .to_address()
+                .unwrap(),
+            address
+        );
+        assert!(matches!(
+            encrypt_key_to_file(&path, &parsed, "passphrase"),
+            Err(KeystoreError::KeyFile(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
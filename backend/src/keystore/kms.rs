@@ -0,0 +1,571 @@
+//! Signers backed by cloud KMS keys
+//!
+//! A `RemoteSigner` signs transactions with a key that never leaves the key management
+//! service, so a production worker holds no private key on disk or in its environment:
+//!
+//! - `AwsKmsSigner`: AWS KMS `ECC_SECG_P256K1` keys (Sui Secp256k1)
+//! - `GcpKmsSigner`: Google Cloud KMS `EC_SIGN_SECP256K1_SHA256` (Sui Secp256k1) and
+//!   `EC_SIGN_ED25519` (Sui Ed25519) key versions
+//!
+//! The signer's address is derived from the KMS public key. Build transactions with it
+//! as the signer (see `client_for`) and execute them with
+//! `CanaryTransactionBuilder::execute_with_remote_signer`. Every signature is verified
+//! locally before it is returned, so a misconfigured key fails before submission.
+//!
+//! Requires the `kms` feature.
+
+use crate::client::{retry::RetryPolicy, SuiClientWithSigner};
+use crate::error::KeystoreError;
+use async_trait::async_trait;
+use base64::Engine;
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use sha2::{Digest, Sha256};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::keystore::{InMemKeystore, Keystore};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::{PublicKey, Signature, SignatureScheme, SuiSignature, ToFromBytes};
+use sui_sdk::types::transaction::TransactionData;
+use sui_sdk::SuiClient;
+
+/// Google Cloud KMS REST endpoint
+const GCP_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com/v1";
+
+/// Access token of the instance's service account, from the GCE/GKE metadata server
+const GCP_METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Order of the secp256k1 group
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Half the order of the secp256k1 group; Sui rejects signatures with a larger `s`
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Signs transactions without holding the private key
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    /// The signer's public key
+    fn public_key(&self) -> &PublicKey;
+
+    /// The signer's Sui address
+    fn address(&self) -> SuiAddress {
+        SuiAddress::from(self.public_key())
+    }
+
+    /// Sign a transaction
+    async fn sign_transaction(&self, tx_data: &TransactionData)
+        -> Result<Signature, KeystoreError>;
+}
+
+/// A `SuiClientWithSigner` for a remote signer
+///
+/// The keystore is empty: transactions must be executed with
+/// `CanaryTransactionBuilder::execute_with_remote_signer`.
+pub fn client_for(client: SuiClient, signer: &dyn RemoteSigner) -> SuiClientWithSigner {
+    SuiClientWithSigner {
+        client,
+        signer: signer.address(),
+        keystore: Keystore::InMem(InMemKeystore::default()),
+        retry: RetryPolicy::default(),
+        profiles: Default::default(),
+        gas_policy: Default::default(),
+    }
+}
+
+/// A signer backed by an AWS KMS `ECC_SECG_P256K1` key
+pub struct AwsKmsSigner {
+    client: aws_sdk_kms::Client,
+    key_id: String,
+    public_key: PublicKey,
+}
+
+impl AwsKmsSigner {
+    /// Connect to a KMS key with the default AWS credential chain (environment, profile,
+    /// or instance role)
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - Key ID, key ARN, or alias (e.g. `alias/canary-admin`)
+    ///
+    /// # Returns
+    ///
+    /// Returns the signer, or a `KeystoreError` if the public key cannot be read or the
+    /// key is not a secp256k1 signing key.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::keystore::kms::{client_for, AwsKmsSigner, RemoteSigner};
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let signer = AwsKmsSigner::connect("alias/canary-admin").await?;
+    /// println!("KMS signer address: {}", signer.address());
+    ///
+    /// let client = create_sui_client(Network::Testnet).await?;
+    /// let mut builder = CanaryTransactionBuilder::new(client_for(client, &signer));
+    /// // ... add operations ...
+    /// let response = builder.execute_with_remote_signer(&signer).await?;
+    /// println!("Transaction executed: {:?}", response.digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(key_id: impl Into<String>) -> Result<Self, KeystoreError> {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        Self::from_client(aws_sdk_kms::Client::new(&config), key_id).await
+    }
+
+    /// Use an already configured KMS client (e.g. with an explicit region)
+    pub async fn from_client(
+        client: aws_sdk_kms::Client,
+        key_id: impl Into<String>,
+    ) -> Result<Self, KeystoreError> {
+        let key_id = key_id.into();
+        let output = client
+            .get_public_key()
+            .key_id(&key_id)
+            .send()
+            .await
+            .map_err(|e| remote_error("AWS KMS", &key_id, e))?;
+
+        if output.key_spec() != Some(&aws_sdk_kms::types::KeySpec::EccSecgP256K1) {
+            return Err(KeystoreError::Remote(format!(
+                "AWS KMS key {} is {:?}, expected ECC_SECG_P256K1",
+                key_id,
+                output.key_spec()
+            )));
+        }
+        let der = output.public_key().ok_or_else(|| {
+            KeystoreError::Remote(format!("AWS KMS key {} has no public key", key_id))
+        })?;
+        let public_key = spki_public_key(der.as_ref(), SignatureScheme::Secp256k1)?;
+
+        Ok(Self {
+            client,
+            key_id,
+            public_key,
+        })
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for AwsKmsSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx_data: &TransactionData,
+    ) -> Result<Signature, KeystoreError> {
+        use aws_sdk_kms::primitives::Blob;
+        use aws_sdk_kms::types::{MessageType, SigningAlgorithmSpec};
+
+        // KMS hashes the message with SHA-256, as Sui's Secp256k1 signing does
+        let digest = intent_digest(tx_data)?;
+        let output = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest.to_vec()))
+            .message_type(MessageType::Raw)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .map_err(|e| remote_error("AWS KMS", &self.key_id, e))?;
+        let der = output.signature().ok_or_else(|| {
+            KeystoreError::Remote(format!("AWS KMS key {} returned no signature", self.key_id))
+        })?;
+
+        sui_signature(tx_data, &self.public_key, &der_to_compact(der.as_ref())?)
+    }
+}
+
+/// Where a `GcpKmsSigner` gets its OAuth access token
+#[derive(Clone)]
+pub enum GcpToken {
+    /// The service account of the GCE instance or GKE workload, via the metadata server
+    Metadata,
+    /// A fixed token (e.g. from `gcloud auth print-access-token`)
+    Static(String),
+}
+
+impl std::fmt::Debug for GcpToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GcpToken::Metadata => f.write_str("Metadata"),
+            GcpToken::Static(_) => f.write_str("Static(<redacted>)"),
+        }
+    }
+}
+
+/// A signer backed by a Google Cloud KMS key version
+pub struct GcpKmsSigner {
+    api: GcpKmsApi,
+    public_key: PublicKey,
+}
+
+impl GcpKmsSigner {
+    /// Connect to a KMS key version
+    ///
+    /// # Arguments
+    ///
+    /// * `key_version` - Full resource name, `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`
+    /// * `token` - Where the access token comes from
+    ///
+    /// # Returns
+    ///
+    /// Returns the signer, or a `KeystoreError` if the public key cannot be read or the
+    /// key is neither `EC_SIGN_SECP256K1_SHA256` nor `EC_SIGN_ED25519`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::keystore::kms::{GcpKmsSigner, GcpToken, RemoteSigner};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let signer = GcpKmsSigner::connect(
+    ///     "projects/canary/locations/global/keyRings/worker/cryptoKeys/admin/cryptoKeyVersions/1",
+    ///     GcpToken::Metadata,
+    /// )
+    /// .await?;
+    /// println!("KMS signer address: {}", signer.address());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(
+        key_version: impl Into<String>,
+        token: GcpToken,
+    ) -> Result<Self, KeystoreError> {
+        let api = GcpKmsApi {
+            http: reqwest::Client::builder()
+                .user_agent(crate::client::USER_AGENT)
+                .build()
+                .map_err(|e| KeystoreError::Remote(e.to_string()))?,
+            key_version: key_version.into(),
+            token,
+        };
+
+        let response = api.request(reqwest::Method::GET, "publicKey", None).await?;
+        let scheme = match response["algorithm"].as_str() {
+            Some("EC_SIGN_SECP256K1_SHA256") => SignatureScheme::Secp256k1,
+            Some("EC_SIGN_ED25519") => SignatureScheme::ED25519,
+            other => {
+                return Err(KeystoreError::Remote(format!(
+                    "Google Cloud KMS key {} uses {:?}, expected EC_SIGN_SECP256K1_SHA256 or EC_SIGN_ED25519",
+                    api.key_version, other
+                )))
+            }
+        };
+        let pem = response["pem"].as_str().ok_or_else(|| {
+            KeystoreError::Remote(format!(
+                "Google Cloud KMS key {} returned no public key",
+                api.key_version
+            ))
+        })?;
+        let public_key = spki_public_key(&pem_to_der(pem)?, scheme)?;
+
+        Ok(Self { api, public_key })
+    }
+}
+
+/// Google Cloud KMS REST calls for one key version
+struct GcpKmsApi {
+    http: reqwest::Client,
+    key_version: String,
+    token: GcpToken,
+}
+
+impl GcpKmsApi {
+    async fn access_token(&self) -> Result<String, KeystoreError> {
+        match &self.token {
+            GcpToken::Static(token) => Ok(token.clone()),
+            GcpToken::Metadata => {
+                let response: serde_json::Value = self
+                    .http
+                    .get(GCP_METADATA_TOKEN_URL)
+                    .header("Metadata-Flavor", "Google")
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| remote_error("GCP metadata server", "token", e))?
+                    .json()
+                    .await
+                    .map_err(|e| remote_error("GCP metadata server", "token", e))?;
+                response["access_token"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        KeystoreError::Remote("Metadata server returned no access token".into())
+                    })
+            }
+        }
+    }
+
+    /// Call `GET {key_version}/{method}` (e.g. `publicKey`) or
+    /// `POST {key_version}:{method}` (e.g. `asymmetricSign`)
+    async fn request(
+        &self,
+        http_method: reqwest::Method,
+        method: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, KeystoreError> {
+        let separator = if http_method == reqwest::Method::GET {
+            '/'
+        } else {
+            ':'
+        };
+        let url = format!(
+            "{}/{}{}{}",
+            GCP_KMS_ENDPOINT, self.key_version, separator, method
+        );
+        let mut request = self
+            .http
+            .request(http_method, url)
+            .bearer_auth(self.access_token().await?);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| remote_error("Google Cloud KMS", &self.key_version, e))?
+            .json()
+            .await
+            .map_err(|e| remote_error("Google Cloud KMS", &self.key_version, e))
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for GcpKmsSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx_data: &TransactionData,
+    ) -> Result<Signature, KeystoreError> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let digest = intent_digest(tx_data)?;
+        let body = match self.public_key.scheme() {
+            // Pre-hashed with SHA-256, as Sui's Secp256k1 signing does
+            SignatureScheme::Secp256k1 => {
+                serde_json::json!({ "digest": { "sha256": engine.encode(Sha256::digest(digest)) } })
+            }
+            // Ed25519 signs the intent digest itself
+            _ => serde_json::json!({ "data": engine.encode(digest) }),
+        };
+
+        let response = self
+            .api
+            .request(reqwest::Method::POST, "asymmetricSign", Some(body))
+            .await?;
+        let signature = response["signature"]
+            .as_str()
+            .and_then(|s| engine.decode(s).ok())
+            .ok_or_else(|| {
+                KeystoreError::Remote(format!(
+                    "Google Cloud KMS key {} returned no signature",
+                    self.api.key_version
+                ))
+            })?;
+
+        let signature = match self.public_key.scheme() {
+            SignatureScheme::Secp256k1 => der_to_compact(&signature)?.to_vec(),
+            _ => signature,
+        };
+        sui_signature(tx_data, &self.public_key, &signature)
+    }
+}
+
+/// The Blake2b-256 digest of a transaction's intent message, which Sui signs
+fn intent_digest(tx_data: &TransactionData) -> Result<[u8; 32], KeystoreError> {
+    let message = IntentMessage::new(Intent::sui_transaction(), tx_data.clone());
+    let bytes = bcs::to_bytes(&message).map_err(|e| KeystoreError::Remote(e.to_string()))?;
+    Ok(Blake2b::<U32>::digest(bytes).into())
+}
+
+/// Assemble a Sui signature (`flag || signature || public key`) and verify it
+fn sui_signature(
+    tx_data: &TransactionData,
+    public_key: &PublicKey,
+    signature: &[u8],
+) -> Result<Signature, KeystoreError> {
+    let mut bytes = vec![public_key.flag()];
+    bytes.extend_from_slice(signature);
+    bytes.extend_from_slice(public_key.as_ref());
+    let signature =
+        Signature::from_bytes(&bytes).map_err(|e| KeystoreError::Remote(e.to_string()))?;
+
+    let message = IntentMessage::new(Intent::sui_transaction(), tx_data.clone());
+    signature
+        .verify_secure(&message, SuiAddress::from(public_key), public_key.scheme())
+        .map_err(|e| KeystoreError::Remote(format!("KMS signature does not verify: {}", e)))?;
+    Ok(signature)
+}
+
+/// Extract the Sui public key from a DER `SubjectPublicKeyInfo`
+///
+/// The key is the trailing bit string: a 65-byte uncompressed point for secp256k1
+/// (compressed here, as Sui expects) or 32 bytes for Ed25519.
+fn spki_public_key(der: &[u8], scheme: SignatureScheme) -> Result<PublicKey, KeystoreError> {
+    let invalid = || KeystoreError::Remote("Unexpected KMS public key encoding".to_string());
+    let key = match scheme {
+        SignatureScheme::Secp256k1 => {
+            let point = der
+                .get(der.len().checked_sub(65).ok_or_else(invalid)?..)
+                .ok_or_else(invalid)?;
+            if point[0] != 0x04 {
+                return Err(invalid());
+            }
+            let mut compressed = Vec::with_capacity(33);
+            compressed.push(if point[64] & 1 == 0 { 0x02 } else { 0x03 });
+            compressed.extend_from_slice(&point[1..33]);
+            compressed
+        }
+        _ => der
+            .get(der.len().checked_sub(32).ok_or_else(invalid)?..)
+            .ok_or_else(invalid)?
+            .to_vec(),
+    };
+    PublicKey::try_from_bytes(scheme, &key).map_err(|e| KeystoreError::Remote(e.to_string()))
+}
+
+/// Decode the body of a PEM document
+fn pem_to_der(pem: &str) -> Result<Vec<u8>, KeystoreError> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .map_err(|e| KeystoreError::Remote(format!("Invalid PEM public key: {}", e)))
+}
+
+/// Convert a DER ECDSA signature to the 64-byte `r || s` form with a low `s`
+fn der_to_compact(der: &[u8]) -> Result<[u8; 64], KeystoreError> {
+    let invalid = || KeystoreError::Remote("Invalid DER signature".to_string());
+
+    // SEQUENCE { INTEGER r, INTEGER s }
+    if der.len() < 8 || der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+        return Err(invalid());
+    }
+    let mut rest = &der[2..];
+    let mut read_integer = || -> Result<[u8; 32], KeystoreError> {
+        if rest.len() < 2 || rest[0] != 0x02 {
+            return Err(invalid());
+        }
+        let len = rest[1] as usize;
+        let value = rest.get(2..2 + len).ok_or_else(invalid)?;
+        rest = &rest[2 + len..];
+
+        // Strip the sign padding, then left-pad to 32 bytes
+        let value = match value.iter().position(|b| *b != 0) {
+            Some(start) => &value[start..],
+            None => &[],
+        };
+        if value.len() > 32 {
+            return Err(invalid());
+        }
+        let mut out = [0u8; 32];
+        out[32 - value.len()..].copy_from_slice(value);
+        Ok(out)
+    };
+    let r = read_integer()?;
+    let mut s = read_integer()?;
+
+    if s > SECP256K1_HALF_ORDER {
+        s = subtract(&SECP256K1_ORDER, &s);
+    }
+    let mut compact = [0u8; 64];
+    compact[..32].copy_from_slice(&r);
+    compact[32..].copy_from_slice(&s);
+    Ok(compact)
+}
+
+/// `a - b` for big-endian 256-bit integers with `a >= b`
+fn subtract(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = if diff < 0 {
+            diff += 256;
+            1
+        } else {
+            0
+        };
+        out[i] = diff as u8;
+    }
+    out
+}
+
+fn remote_error(service: &str, key: &str, e: impl std::fmt::Display) -> KeystoreError {
+    KeystoreError::Remote(format!("{} ({}): {}", service, key, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_der_to_compact() {
+        // r with sign padding, short s
+        let mut der = vec![0x30, 0x00, 0x02, 33, 0x00];
+        der.extend_from_slice(&[0x80; 32]);
+        der.extend_from_slice(&[0x02, 2, 0x01, 0x02]);
+        der[1] = (der.len() - 2) as u8;
+
+        let compact = der_to_compact(&der).unwrap();
+        assert_eq!(&compact[..32], &[0x80; 32]);
+        assert_eq!(&compact[32..62], &[0; 30]);
+        assert_eq!(&compact[62..], &[0x01, 0x02]);
+
+        assert!(der_to_compact(&der[..der.len() - 1]).is_err());
+        assert!(der_to_compact(&[0x31, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_high_s_is_normalized() {
+        let mut high_s = SECP256K1_ORDER;
+        high_s[31] -= 1; // n - 1, normalized to 1
+
+        let mut der = vec![0x30, 0x00, 0x02, 1, 0x01, 0x02, 33, 0x00];
+        der.extend_from_slice(&high_s);
+        der[1] = (der.len() - 2) as u8;
+
+        let compact = der_to_compact(&der).unwrap();
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(&compact[32..], &one);
+    }
+
+    #[test]
+    fn test_spki_public_key() {
+        // Ed25519 SubjectPublicKeyInfo: 12-byte header, then the key
+        let mut der = vec![
+            0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+        ];
+        der.extend_from_slice(&[7; 32]);
+        let key = spki_public_key(&der, SignatureScheme::ED25519).unwrap();
+        assert_eq!(key.as_ref(), &[7; 32]);
+
+        let pem = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+            base64::engine::general_purpose::STANDARD.encode(&der)
+        );
+        assert_eq!(pem_to_der(&pem).unwrap(), der);
+
+        assert!(spki_public_key(&der[..10], SignatureScheme::Secp256k1).is_err());
+    }
+}
@@ -0,0 +1,373 @@
+//! k-of-n multisig signers
+//!
+//! A `Multisig` describes a Sui MultiSig account: weighted participant public keys and
+//! the total weight a transaction needs. Keeping the registry's AdminCap in that account
+//! means no single leaked key can act as admin.
+//!
+//! Signing is done by each participant separately, often on different machines:
+//!
+//! 1. Build the transaction with the multisig address as signer and prepare it with
+//!    `CanaryTransactionBuilder::digest_preview`.
+//! 2. Each participant signs the prepared `TransactionData` with `sign_partial`.
+//! 3. A `PartialSignatures` collects and checks the signatures until their weight
+//!    reaches the threshold, then `combine` produces the `GenericSignature` to pass to
+//!    `CanaryTransactionBuilder::execute_with_signature`.
+
+use crate::error::KeystoreError;
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::BTreeMap;
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiSignature};
+use sui_sdk::types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_sdk::types::signature::GenericSignature;
+use sui_sdk::types::transaction::TransactionData;
+
+/// A multisig participant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Participant {
+    /// The participant's public key
+    pub public_key: PublicKey,
+    /// Weight the participant's signature contributes
+    pub weight: WeightUnit,
+}
+
+impl Participant {
+    /// Create a participant
+    pub fn new(public_key: PublicKey, weight: WeightUnit) -> Self {
+        Self { public_key, weight }
+    }
+
+    /// Create a participant from a flagged Base64 public key, as printed by
+    /// `sui keytool list`
+    pub fn from_base64(public_key: &str, weight: WeightUnit) -> Result<Self, KeystoreError> {
+        let public_key = PublicKey::decode_base64(public_key).map_err(|e| {
+            KeystoreError::InvalidMultisig(format!("Invalid public key {}: {}", public_key, e))
+        })?;
+        Ok(Self::new(public_key, weight))
+    }
+
+    /// The participant's own address
+    pub fn address(&self) -> SuiAddress {
+        SuiAddress::from(&self.public_key)
+    }
+}
+
+/// A weighted k-of-n multisig account
+#[derive(Debug, Clone)]
+pub struct Multisig {
+    public_key: MultiSigPublicKey,
+}
+
+impl Multisig {
+    /// Create a multisig account
+    ///
+    /// # Arguments
+    ///
+    /// * `participants` - The participants and their weights (at most 10)
+    /// * `threshold` - Total weight a transaction needs
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Multisig`, or a `KeystoreError` if there are no participants, a
+    /// key appears twice, a weight is zero, or the threshold is zero or unreachable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::keystore::multisig::{Multisig, Participant};
+    ///
+    /// let multisig = Multisig::new(
+    ///     vec![
+    ///         Participant::from_base64("AK0v...", 1)?,
+    ///         Participant::from_base64("AHJf...", 1)?,
+    ///         Participant::from_base64("AOa4...", 1)?,
+    ///     ],
+    ///     2,
+    /// )?;
+    /// println!("Transfer the AdminCap to {}", multisig.address());
+    /// # Ok::<(), canary_sdk::error::KeystoreError>(())
+    /// ```
+    pub fn new(
+        participants: Vec<Participant>,
+        threshold: ThresholdUnit,
+    ) -> Result<Self, KeystoreError> {
+        let total: u32 = participants.iter().map(|p| p.weight as u32).sum();
+        if threshold as u32 > total {
+            return Err(KeystoreError::InvalidMultisig(format!(
+                "Threshold {} exceeds the total weight {}",
+                threshold, total
+            )));
+        }
+
+        let (keys, weights) = participants
+            .into_iter()
+            .map(|p| (p.public_key, p.weight))
+            .unzip();
+        let public_key = MultiSigPublicKey::new(keys, weights, threshold)
+            .map_err(|e| KeystoreError::InvalidMultisig(e.to_string()))?;
+        Ok(Self { public_key })
+    }
+
+    /// The multisig account's address
+    pub fn address(&self) -> SuiAddress {
+        SuiAddress::from(&self.public_key)
+    }
+
+    /// Total weight a transaction needs
+    pub fn threshold(&self) -> ThresholdUnit {
+        *self.public_key.threshold()
+    }
+
+    /// The participants, in key order
+    pub fn participants(&self) -> Vec<Participant> {
+        self.public_key
+            .pubkeys()
+            .iter()
+            .map(|(public_key, weight)| Participant::new(public_key.clone(), *weight))
+            .collect()
+    }
+
+    /// The underlying Sui MultiSig public key
+    pub fn public_key(&self) -> &MultiSigPublicKey {
+        &self.public_key
+    }
+
+    /// Start collecting signatures for a transaction
+    pub fn collect<'a>(&'a self, tx_data: &'a TransactionData) -> PartialSignatures<'a> {
+        PartialSignatures {
+            multisig: self,
+            tx_data,
+            signatures: BTreeMap::new(),
+        }
+    }
+}
+
+/// Sign a transaction as one multisig participant
+///
+/// # Arguments
+///
+/// * `keystore` - A keystore holding the participant's key
+/// * `participant` - The participant's own address (not the multisig address)
+/// * `tx_data` - The transaction to sign
+///
+/// # Returns
+///
+/// Returns the participant's signature, or a `KeystoreError` if the keystore cannot
+/// sign for `participant`.
+pub async fn sign_partial(
+    keystore: &Keystore,
+    participant: SuiAddress,
+    tx_data: &TransactionData,
+) -> Result<Signature, KeystoreError> {
+    keystore
+        .sign_secure(&participant, tx_data, Intent::sui_transaction())
+        .await
+        .map_err(|e| {
+            KeystoreError::KeystoreOperation(format!("Failed to sign as {}: {}", participant, e))
+        })
+}
+
+/// Signatures collected for one multisig transaction
+pub struct PartialSignatures<'a> {
+    multisig: &'a Multisig,
+    tx_data: &'a TransactionData,
+    signatures: BTreeMap<SuiAddress, (Signature, WeightUnit)>,
+}
+
+impl PartialSignatures<'_> {
+    /// Add a participant's signature
+    ///
+    /// Adding a second signature from the same participant replaces the first.
+    ///
+    /// # Returns
+    ///
+    /// Returns the weight collected so far, or a `KeystoreError` if the signer is not
+    /// a participant or the signature does not cover this transaction.
+    pub fn add(&mut self, signature: Signature) -> Result<u16, KeystoreError> {
+        let public_key = signature
+            .to_public_key()
+            .map_err(|e| KeystoreError::InvalidMultisig(e.to_string()))?;
+        let signer = SuiAddress::from(&public_key);
+        let weight = self
+            .multisig
+            .public_key
+            .pubkeys()
+            .iter()
+            .find(|(key, _)| *key == public_key)
+            .map(|(_, weight)| *weight)
+            .ok_or(KeystoreError::NotParticipant(signer))?;
+
+        let message = IntentMessage::new(Intent::sui_transaction(), self.tx_data.clone());
+        signature
+            .verify_secure(&message, signer, public_key.scheme())
+            .map_err(|e| {
+                KeystoreError::InvalidMultisig(format!(
+                    "Signature from {} does not match the transaction: {}",
+                    signer, e
+                ))
+            })?;
+
+        self.signatures.insert(signer, (signature, weight));
+        Ok(self.weight())
+    }
+
+    /// Weight of the signatures collected so far
+    pub fn weight(&self) -> u16 {
+        self.signatures
+            .values()
+            .map(|(_, weight)| *weight as u16)
+            .sum()
+    }
+
+    /// Whether the collected weight reaches the threshold
+    pub fn is_complete(&self) -> bool {
+        self.weight() >= self.multisig.threshold()
+    }
+
+    /// Combine the signatures into a MultiSig signature
+    ///
+    /// # Returns
+    ///
+    /// Returns the `GenericSignature` to execute the transaction with, or
+    /// `KeystoreError::InsufficientWeight` if the threshold is not reached yet.
+    pub fn combine(&self) -> Result<GenericSignature, KeystoreError> {
+        if !self.is_complete() {
+            return Err(KeystoreError::InsufficientWeight {
+                weight: self.weight(),
+                threshold: self.multisig.threshold(),
+            });
+        }
+
+        let signatures = self
+            .signatures
+            .values()
+            .map(|(signature, _)| GenericSignature::Signature(signature.clone()))
+            .collect();
+        let multisig = MultiSig::combine(signatures, self.multisig.public_key.clone())
+            .map_err(|e| KeystoreError::InvalidMultisig(e.to_string()))?;
+        Ok(GenericSignature::MultiSig(multisig))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_keys::keystore::InMemKeystore;
+    use sui_sdk::types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+    use sui_sdk::types::crypto::SuiKeyPair;
+
+    fn keypair(seed: u8) -> SuiKeyPair {
+        let mut bytes = vec![0x00];
+        bytes.extend_from_slice(&[seed; 32]);
+        SuiKeyPair::from_bytes(&bytes).unwrap()
+    }
+
+    fn transaction(sender: SuiAddress) -> TransactionData {
+        let gas = (
+            ObjectID::from_single_byte(1),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::new([0; 32]),
+        );
+        TransactionData::new_transfer_sui(sender, sender, Some(1), gas, 10_000_000, 1_000)
+    }
+
+    async fn keystore(keypairs: &[SuiKeyPair]) -> Keystore {
+        let mut keystore = Keystore::InMem(InMemKeystore::default());
+        for keypair in keypairs {
+            keystore.import(None, keypair.copy()).await.unwrap();
+        }
+        keystore
+    }
+
+    #[tokio::test]
+    async fn test_two_of_three() {
+        let keypairs: Vec<SuiKeyPair> = (1..=3).map(keypair).collect();
+        let multisig = Multisig::new(
+            keypairs
+                .iter()
+                .map(|kp| Participant::new(kp.public(), 1))
+                .collect(),
+            2,
+        )
+        .unwrap();
+        assert_eq!(multisig.threshold(), 2);
+        assert_eq!(multisig.participants().len(), 3);
+
+        let tx_data = transaction(multisig.address());
+        let keystore = keystore(&keypairs).await;
+        let mut signatures = multisig.collect(&tx_data);
+
+        let first = SuiAddress::from(&keypairs[0].public());
+        let weight = signatures
+            .add(sign_partial(&keystore, first, &tx_data).await.unwrap())
+            .unwrap();
+        assert_eq!(weight, 1);
+        assert!(matches!(
+            signatures.combine(),
+            Err(KeystoreError::InsufficientWeight {
+                weight: 1,
+                threshold: 2
+            })
+        ));
+
+        // Signing twice does not count twice
+        signatures
+            .add(sign_partial(&keystore, first, &tx_data).await.unwrap())
+            .unwrap();
+        assert!(!signatures.is_complete());
+
+        let third = SuiAddress::from(&keypairs[2].public());
+        signatures
+            .add(sign_partial(&keystore, third, &tx_data).await.unwrap())
+            .unwrap();
+        assert!(matches!(
+            signatures.combine().unwrap(),
+            GenericSignature::MultiSig(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_outsiders_and_other_transactions() {
+        let members: Vec<SuiKeyPair> = (1..=2).map(keypair).collect();
+        let outsider = keypair(9);
+        let multisig = Multisig::new(
+            members
+                .iter()
+                .map(|kp| Participant::new(kp.public(), 1))
+                .collect(),
+            1,
+        )
+        .unwrap();
+        let keystore = keystore(&[members[0].copy(), outsider.copy()]).await;
+
+        let tx_data = transaction(multisig.address());
+        let mut signatures = multisig.collect(&tx_data);
+        let outsider_address = SuiAddress::from(&outsider.public());
+        let signature = sign_partial(&keystore, outsider_address, &tx_data)
+            .await
+            .unwrap();
+        assert!(matches!(
+            signatures.add(signature),
+            Err(KeystoreError::NotParticipant(address)) if address == outsider_address
+        ));
+
+        // A signature over a different transaction
+        let other = transaction(SuiAddress::ZERO);
+        let member = SuiAddress::from(&members[0].public());
+        let signature = sign_partial(&keystore, member, &other).await.unwrap();
+        assert!(matches!(
+            signatures.add(signature),
+            Err(KeystoreError::InvalidMultisig(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        let participants = vec![Participant::new(keypair(1).public(), 1)];
+        assert!(matches!(
+            Multisig::new(participants, 2),
+            Err(KeystoreError::InvalidMultisig(_))
+        ));
+    }
+}
@@ -2,25 +2,133 @@
 //!
 //! A library that simplifies interaction with the Sui blockchain, specifically designed
 //! for the Canary contract. Provides utilities for:
-//! - Private key management (loading Bech32-encoded keys)
-//! - Sui client creation
-//! - Transaction building
+//! - Private key management (loading Bech32-encoded keys, k-of-n multisig)
+//! - Sui client creation, including read-only clients that cannot sign
+//! - Per-network Canary deployments and chain identifier checks
+//! - Transaction building, with an append-only audit log of executed transactions
 //! - Canary contract helpers
+//! - Domain ownership verification
+//! - Scripted incident response (registry lockdown)
+//! - Canary statement parsing and diffing
+//! - Canonical test vectors for reimplementations of address derivation, statement
+//!   hashing, and signatures
+//! - Persistent worker state (cursors, dedup sets, leader locks)
+//! - Registry snapshots and member diffs
+//! - CSV and JSON export of members and canary blobs
+//! - A local SQLite index of registry history
+//! - Structured progress events for long-running operations
+//! - Pluggable time sources for freshness checks
+//! - Canary freshness monitoring (fresh, stale, or missing per domain)
+//! - Schema-versioned artifact loading
+//! - Machine-readable output and stable exit codes for binaries
+//! - Funding helpers for integration test fixtures
+//! - Configurable, independently scheduled worker tasks
+//! - Interval and cron schedules
+//! - Typed worker configuration from `canary.toml` with environment overrides
+//! - Signed webhook relay of registry activity
+//! - Task outcome notifications to JSON, Slack, and Discord webhooks
+//! - Gas coin pooling for concurrent transactions
+//! - Prometheus metrics for monitoring the worker
+//! - Liveness and readiness probes for container orchestrators
+//! - Structured (text or JSON) logging with per-RPC and per-transaction spans
+//! - Walrus availability checks for canary contract and explain blobs
+//!
+//! # Features
+//!
+//! - `signing` (default): the keystore, signing, and transaction-execution code
+//!   (`keystore`, `transaction`, `gas`, `incident`, `SuiClientWithSigner`, and every
+//!   helper that submits a transaction). Pulls in `sui_keys` and `zeroize`. Build with
+//!   `--no-default-features` for read-only deployments such as public verification
+//!   backends; queries, statement and receipt verification, and domain probes remain.
+//! - `worker`: configurable worker tasks (`worker`), the worker config (`config`), the
+//!   webhook relay (`relay`), the `canary-worker` binary, and the `canary-cli` binary for
+//!   running SDK operations from the shell. Enables `notify`, `walrus`, `metrics`,
+//!   `health`, `monitor`, and `schedule`, and pulls in `toml`, `hmac`, and `dotenv`.
+//! - `notify`: task outcome notifications to JSON, Slack, and Discord webhooks
+//!   (`notify`). Enables `monitor`.
+//! - `walrus`: Walrus availability checks for canary blobs (`walrus`).
+//! - `metrics`: Prometheus metrics for RPCs and executed transactions (`metrics`).
+//! - `health`: liveness and readiness probes (`health`). Enables `metrics`.
+//! - `monitor`: canary freshness monitoring (`monitor`).
+//! - `schedule`: interval and cron schedules (`schedule`).
+//! - `bindings`: typed Rust bindings generated from a package's Move modules
+//!   (`canary::bindings`).
+//! - `testing`: funding helpers and simulated clocks for integration test fixtures
+//!   (`testing`).
+//! - `mnemonic`: BIP-39 mnemonic key import (`keystore::parse_mnemonic`).
+//! - `encrypted-keys`: passphrase-encrypted key files (`keystore::load_encrypted_key`).
+//! - `seal`: the Seal SDK dependency.
+//! - `full`: `worker`, `bindings`, `mnemonic`, and `seal`.
+//! - `redis`: Redis backend for shared worker state.
+//! - `indicatif`: progress bars as `ProgressReporter`s.
+//! - `indexer`: SQLite index of registry history (`indexer`). Pulls in `rusqlite`.
+//! - `kms`: AWS KMS and Google Cloud KMS signers (`keystore::kms`). Pulls in the AWS SDK.
+//! - `server`: read-only REST API and `/ws` live updates over a registry (`server`),
+//!   served by `canary-worker serve`. Enables `metrics` and pulls in `tokio-tungstenite`.
+//! - `localnet`: integration test harness that publishes the Canary package to a
+//!   localnet (`testing::localnet`). Enables `testing` and `signing`. Needs the `sui`
+//!   CLI at test time.
 
 pub mod canary;
 pub mod client;
+pub mod clock;
+pub mod compat;
+#[cfg(feature = "worker")]
+pub mod config;
+pub mod domain;
 pub mod error;
+pub mod export;
+#[cfg(feature = "signing")]
+pub mod gas;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "signing")]
+pub mod incident;
+#[cfg(feature = "indexer")]
+pub mod indexer;
+#[cfg(feature = "signing")]
 pub mod keystore;
+pub mod logging;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "monitor")]
+pub mod monitor;
+pub mod networks;
+#[cfg(feature = "notify")]
+pub mod notify;
+pub mod output;
+pub mod progress;
+#[cfg(feature = "worker")]
+pub mod relay;
+#[cfg(feature = "schedule")]
+pub mod schedule;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod snapshot;
+pub mod state;
+pub mod statement;
+pub mod template;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "signing")]
 pub mod transaction;
+pub mod vectors;
+#[cfg(feature = "walrus")]
+pub mod walrus;
+#[cfg(feature = "worker")]
+pub mod worker;
 
 // Re-export commonly used types
 pub use sui_sdk::types::base_types::SuiAddress;
 pub use sui_sdk::types::crypto::{SignatureScheme, SuiKeyPair};
 
 // Re-export client types for convenience
-pub use client::{Network, SuiClientWithSigner};
+pub use client::Network;
+#[cfg(feature = "signing")]
+pub use client::SuiClientWithSigner;
 
 // Re-export transaction types for convenience
+#[cfg(feature = "signing")]
 pub use transaction::CanaryTransactionBuilder;
 
 // Re-export canary types for convenience
@@ -0,0 +1,79 @@
+//! Log output setup
+//!
+//! The SDK logs through `tracing`: RPC operations run through `RetryPolicy` and
+//! submitted transactions each get a span, so every event they log carries the
+//! operation name or transaction digest. `init` installs a subscriber that writes these
+//! as human-readable lines or as one JSON object per line for log aggregators, with
+//! levels filtered by `RUST_LOG` (default: `info`).
+
+use serde::Deserialize;
+use std::str::FromStr;
+use tracing_subscriber::EnvFilter;
+
+/// Log line format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    #[serde(alias = "pretty")]
+    Text,
+    /// One JSON object per line, with span fields
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "pretty" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "Unknown log format: {} (expected text or json)",
+                other
+            )),
+        }
+    }
+}
+
+/// Install the global log subscriber
+///
+/// Does nothing if a subscriber is already installed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::logging::{init, LogFormat};
+///
+/// init(LogFormat::Json);
+/// tracing::info!(registry = "0x123", "Worker started");
+/// ```
+pub fn init(format: LogFormat) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    let result = match format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    };
+    if result.is_err() {
+        tracing::debug!("Log subscriber already installed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("TEXT".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert!("xml".parse::<LogFormat>().is_err());
+        assert_eq!(LogFormat::default(), LogFormat::Text);
+    }
+}
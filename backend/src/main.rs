@@ -1,88 +1,651 @@
-use std::time::Duration;
-use tokio::time::sleep;
-
-use canary_sdk::canary::query_all_members;
-use canary_sdk::client::{create_sui_client, Network};
-use sui_sdk::types::base_types::ObjectID;
+use canary_sdk::client::create_sui_client;
+use canary_sdk::client::providers::{create_sui_client_with_provider, EnvSecretSource};
+use canary_sdk::client::ratelimit::RateLimiter;
+use canary_sdk::client::readonly::ReadOnlyClient;
+use canary_sdk::client::reconnect::ReconnectingClient;
+#[cfg(feature = "signing")]
+use canary_sdk::client::SuiClientWithSigner;
+use canary_sdk::config::WorkerConfig;
+use canary_sdk::error::{ClientError, WorkerError};
+use canary_sdk::health::{self, HealthCheck};
+use canary_sdk::logging;
+use canary_sdk::metrics::{self, Metrics};
+use canary_sdk::networks::verify_chain_id;
+use canary_sdk::notify::{Notifier, TaskOutcome, Webhook};
+use canary_sdk::output::{render, ExitStatus, OutputFormat};
+use canary_sdk::schedule::Schedule;
+use canary_sdk::snapshot::{track_members, MemberChange};
+use canary_sdk::state::{FileStateStore, MemoryStateStore, StateStore};
+use canary_sdk::worker::{run_tasks, TaskContext, TaskRegistry};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+use sui_sdk::types::base_types::SuiAddress;
+use tokio::sync::watch;
 
 #[tokio::main]
 async fn main() {
-    println!("Canary Worker - Starting...");
+    // Member listings are printed as json, yaml, or a table
+    match output_format() {
+        Ok(output) => {
+            OUTPUT.set(output).ok();
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitStatus::UserError.exit();
+        }
+    }
 
     // Load environment variables
     dotenv::dotenv().ok();
 
-    // Get task interval from environment (default: 3600 seconds = 1 hour)
-    let interval_seconds: u64 = std::env::var("TASK_INTERVAL_SECONDS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(3600);
+    // Load the config file, with environment overrides
+    let config_path =
+        PathBuf::from(std::env::var("CANARY_CONFIG").unwrap_or_else(|_| "canary.toml".to_string()));
+    let config = match WorkerConfig::load(&config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            // Logging is not set up yet: its format is part of the config
+            eprintln!("{}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
 
-    println!("Task interval: {} seconds", interval_seconds);
-    println!("Worker started, waiting for first execution...");
+    logging::init(config.log_format);
+    tracing::info!("Canary Worker - Starting...");
+    if config_path.exists() {
+        tracing::info!("Loaded config from {}", config_path.display());
+    }
 
-    loop {
-        println!("\n=== Starting task execution ===");
+    // Record every transaction the worker signs
+    #[cfg(feature = "signing")]
+    if let Some(path) = &config.audit_log_path {
+        if let Err(e) = canary_sdk::transaction::AuditLog::new(path).install() {
+            tracing::error!("Failed to install audit log {}: {}", path.display(), e);
+            ExitStatus::UserError.exit();
+        }
+        tracing::info!("Recording transactions in {}", path.display());
+    }
+
+    // Space out RPC requests so bulk queries stay within public fullnode limits
+    RateLimiter::global().set_rate(config.rpc.max_requests_per_second);
 
-        match run_task().await {
-            Ok(_) => {
-                println!("Task completed successfully");
+    // Expose Prometheus metrics when a listener address is configured
+    if let Some(addr) = &config.metrics_addr {
+        match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => {
+                tracing::info!("Serving metrics on http://{}/metrics", addr);
+                tokio::spawn(metrics::serve(listener, Metrics::global()));
             }
             Err(e) => {
-                eprintln!("Task failed with error: {}", e);
+                tracing::error!("Failed to listen for metrics on {}: {}", addr, e);
+                ExitStatus::UserError.exit();
             }
         }
+    }
+
+    // `canary-worker serve` answers the REST API and `/ws` updates instead of running tasks
+    #[cfg(feature = "server")]
+    if std::env::args().nth(1).as_deref() == Some("serve") {
+        if let Err(e) = serve(&config).await {
+            tracing::error!("Server failed: {}", e);
+            ExitStatus::UserError.exit();
+        }
+        return;
+    }
+
+    // Refuse to start with the wrong key
+    let signer = match verify_signer(&config) {
+        Ok(signer) => signer,
+        Err(e) => {
+            tracing::error!("Signer verification failed: {}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
 
-        println!(
-            "Waiting {} seconds until next execution...",
-            interval_seconds
+    // Post task outcomes to the configured webhooks
+    let notifier = match notifier(&config) {
+        Ok(notifier) => notifier,
+        Err(e) => {
+            tracing::error!("Failed to set up notifications: {}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
+
+    // Keep state in a file when configured, so member changes survive restarts
+    let state = match state_store(&config) {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::error!("Failed to open worker state: {}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
+
+    // One connection for the tasks and the health probes, replaced when the node stops
+    // answering
+    let connection = match shared_connection(&config).await {
+        Ok(connection) => Arc::new(connection),
+        Err(e) => {
+            tracing::error!("Failed to connect: {}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
+
+    // Stop at SIGTERM or Ctrl-C, once in-flight runs have finished
+    let mut shutdown = shutdown_signal();
+
+    // Run the tasks and registries declared in the config file, if there are any
+    if !config.tasks.tasks.is_empty() || !config.registries.is_empty() {
+        if let Err(e) =
+            run_configured_tasks(&config, signer, connection, notifier, state, shutdown).await
+        {
+            tracing::error!("Failed to start configured tasks: {}", e);
+            ExitStatus::UserError.exit();
+        }
+        tracing::info!("Worker stopped");
+        return;
+    }
+
+    let schedule = config.schedule();
+    tracing::info!("Task schedule: {}", schedule);
+    if let Err(e) = start_health_server(
+        &config,
+        signer,
+        connection.clone(),
+        vec![("members".to_string(), longest_gap(&schedule))],
+    )
+    .await
+    {
+        tracing::error!("Failed to start health probes: {}", e);
+        ExitStatus::UserError.exit();
+    }
+    let mut client = match worker_client(&config, &connection).await {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Failed to load the signing key: {}", e);
+            ExitStatus::UserError.exit();
+        }
+    };
+    tracing::info!("Worker started, waiting for first execution...");
+
+    let mut run_now = config.run_on_start;
+    loop {
+        if run_now {
+            tracing::info!("Starting task execution");
+
+            // Reconnect if the node stopped answering since the previous run
+            let result = match client.ensure_connected(&connection).await {
+                Ok(_) => run_task(&config, &client.read_only(), state.as_ref(), &shutdown).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = &result {
+                if e.downcast_ref::<WorkerError>().is_some() {
+                    tracing::info!("Task stopped by shutdown");
+                    break;
+                }
+            }
+            Metrics::global().record_task_run("members", result.is_ok());
+            let outcome = match result {
+                Ok(changes) => {
+                    tracing::info!("Task completed successfully");
+                    TaskOutcome::success("members").with_member_changes(changes)
+                }
+                Err(e) => {
+                    tracing::error!("Task failed with error: {}", e);
+                    TaskOutcome::failure("members", e)
+                }
+            };
+            if let Some(notifier) = &notifier {
+                notifier.notify(&outcome).await;
+            }
+        }
+        run_now = true;
+
+        // A shutdown requested during the run takes effect once it finished
+        if *shutdown.borrow() {
+            break;
+        }
+
+        let now = SystemTime::now();
+        let next = match schedule.next_after(now) {
+            Some(next) => next,
+            None => {
+                tracing::error!("Schedule {} has no upcoming run", schedule);
+                ExitStatus::UserError.exit();
+            }
+        };
+        let wait = next.duration_since(now).unwrap_or_default();
+        tracing::info!("Waiting {} seconds until next execution...", wait.as_secs());
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shutdown.changed() => break,
+        }
+    }
+    if let Err(e) = state.flush().await {
+        tracing::error!("Failed to flush worker state: {}", e);
+    }
+    tracing::info!("Worker stopped");
+}
+
+/// A notifier for the `[[notify]]` webhooks, if any are configured
+fn notifier(config: &WorkerConfig) -> Result<Option<Arc<Notifier>>, Box<dyn std::error::Error>> {
+    notifier_for(config.webhooks()?)
+}
+
+/// A notifier for `webhooks`, if there are any
+fn notifier_for(
+    webhooks: Vec<Webhook>,
+) -> Result<Option<Arc<Notifier>>, Box<dyn std::error::Error>> {
+    if webhooks.is_empty() {
+        return Ok(None);
+    }
+    for webhook in &webhooks {
+        tracing::info!(
+            "Notifying {} ({:?})",
+            webhook.redacted_url(),
+            webhook.format
         );
-        sleep(Duration::from_secs(interval_seconds)).await;
     }
+    Ok(Some(Arc::new(Notifier::new(webhooks)?)))
+}
+
+/// The state store: a `FileStateStore` at `state_path`, or in memory
+fn state_store(config: &WorkerConfig) -> Result<Arc<dyn StateStore>, Box<dyn std::error::Error>> {
+    state_store_at(config.state_path.as_deref())
+}
+
+/// A `FileStateStore` at `path`, or an in-memory store without one
+fn state_store_at(path: Option<&Path>) -> Result<Arc<dyn StateStore>, Box<dyn std::error::Error>> {
+    match path {
+        Some(path) => {
+            tracing::info!("Keeping worker state in {}", path.display());
+            Ok(Arc::new(FileStateStore::open(path)?))
+        }
+        None => Ok(Arc::new(MemoryStateStore::new())),
+    }
+}
+
+/// Watch for SIGTERM or Ctrl-C
+///
+/// The returned receiver turns `true` once a signal arrives. It is the worker's one
+/// cancellation token: no new runs start, and runs in progress stop at their next RPC
+/// step.
+fn shutdown_signal() -> watch::Receiver<bool> {
+    let (sender, receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        tracing::info!("Shutdown requested, finishing in-flight tasks");
+        let _ = sender.send(true);
+    });
+    receiver
 }
 
-async fn run_task() -> Result<(), Box<dyn std::error::Error>> {
-    // Get network from environment (default: Devnet)
-    let network_str = std::env::var("SUI_NETWORK")
-        .unwrap_or_else(|_| "devnet".to_string())
-        .to_lowercase();
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to listen for SIGTERM: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
 
-    let network = match network_str.as_str() {
-        "localnet" => Network::Localnet,
-        "devnet" => Network::Devnet,
-        "testnet" => Network::Testnet,
-        "mainnet" => Network::Mainnet,
-        url => Network::Custom(url.to_string()),
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Longest wait between upcoming runs: the interval, or the longest gap in a week of
+/// cron runs
+///
+/// Used as the health probe's period, so schedules that skip weekends or nights are
+/// not reported stale.
+fn longest_gap(schedule: &Schedule) -> Duration {
+    if let Schedule::Every(interval) = schedule {
+        return *interval;
+    }
+    let mut longest = Duration::ZERO;
+    let mut at = SystemTime::now();
+    let week_later = at + Duration::from_secs(7 * 86400);
+    while at < week_later {
+        let Some(next) = schedule.next_after(at) else {
+            break;
+        };
+        longest = longest.max(next.duration_since(at).unwrap_or_default());
+        at = next;
+    }
+    longest
+}
+
+/// Print the configured signer and validate it against the expected address
+///
+/// When the key variable (`key.env`, default `SUI_PRIVATE_KEY`) is set, the derived
+/// address and key fingerprint are printed and the address is returned. If
+/// `key.expected_address` is also set, a mismatch is returned as an error.
+#[cfg(feature = "signing")]
+fn verify_signer(config: &WorkerConfig) -> Result<Option<SuiAddress>, Box<dyn std::error::Error>> {
+    use canary_sdk::keystore::{confirm_signer_address, parse_bech32_private_key};
+
+    let bech32_key = match std::env::var(&config.key.env) {
+        Ok(key) => zeroize::Zeroizing::new(key),
+        Err(_) => {
+            tracing::info!("No {} configured, running read-only", config.key.env);
+            return Ok(None);
+        }
     };
 
-    println!("Connecting to network: {:?}", network);
+    let parsed = parse_bech32_private_key(&bech32_key)?;
+    let address = parsed.to_address()?;
+    tracing::info!(
+        "Signer address: {} (fingerprint {})",
+        address,
+        parsed.fingerprint()?
+    );
 
-    // Create Sui client
-    let client = create_sui_client(network).await?;
-    println!("Connected to Sui network");
+    if let Some(expected) = config.key.expected_address {
+        confirm_signer_address(&parsed, expected)?;
+        tracing::info!("Signer address matches the expected address");
+    }
 
-    // Get registry ID from environment variable
-    let registry_id_str =
-        std::env::var("REGISTRY_ID").map_err(|_| "REGISTRY_ID environment variable is required")?;
+    Ok(Some(address))
+}
 
-    let registry_id = ObjectID::from_hex_literal(&registry_id_str)
-        .map_err(|e| format!("Invalid REGISTRY_ID format: {}", e))?;
+/// Builds without signing support never load a key
+#[cfg(not(feature = "signing"))]
+fn verify_signer(config: &WorkerConfig) -> Result<Option<SuiAddress>, Box<dyn std::error::Error>> {
+    if std::env::var(&config.key.env).is_ok() {
+        tracing::warn!("Built without signing, ignoring {}", config.key.env);
+    }
+    Ok(None)
+}
+
+/// Serve `/healthz`, `/readyz`, and `/metrics` on `health.addr`, if set
+///
+/// Each task must succeed within twice its interval. With a signer, readiness also
+/// requires a balance of at least `health.min_balance_mist`.
+async fn start_health_server(
+    config: &WorkerConfig,
+    signer: Option<SuiAddress>,
+    connection: Arc<ReconnectingClient>,
+    tasks: Vec<(String, Duration)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let addr = match &config.health.addr {
+        Some(addr) => addr,
+        None => return Ok(()),
+    };
 
-    println!("Querying members for registry: {}", registry_id);
+    let mut health = HealthCheck::shared(connection);
+    for (task, interval) in tasks {
+        health = health.with_task(task, interval * 2);
+    }
+    if let Some(signer) = signer {
+        health = health.with_signer(signer, config.health.min_balance_mist);
+    }
 
-    // Query all members
-    let members = query_all_members(&client, registry_id).await?;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to listen on {}: {}", addr, e))?;
+    tracing::info!(
+        "Serving health probes on http://{}/healthz and /readyz",
+        addr
+    );
+    tokio::spawn(health::serve(listener, Arc::new(health)));
+    Ok(())
+}
 
-    println!("Found {} members:", members.len());
-    for (idx, member) in members.iter().enumerate() {
-        println!(
-            "  {}. Address: {}, Domain: {}, Joined: {}",
-            idx + 1,
-            member.member,
-            member.domain,
-            member.joined_at
+/// Build the configured `[[tasks]]` and `[[registries]]` and run them on their schedules
+///
+/// The top-level tasks share the top-level state store and webhooks; each registry's
+/// tasks get the registry's own state store, and its own webhooks if it has any.
+async fn run_configured_tasks(
+    config: &WorkerConfig,
+    signer: Option<SuiAddress>,
+    connection: Arc<ReconnectingClient>,
+    notifier: Option<Arc<Notifier>>,
+    state: Arc<dyn StateStore>,
+    shutdown: watch::Receiver<bool>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let registry = TaskRegistry::with_builtin();
+    let mut groups = vec![(registry.build(&config.tasks)?, state, notifier.clone())];
+    for entry in &config.registries {
+        let tasks = registry.build(&entry.tasks())?;
+        tracing::info!(
+            "Registry {} ({}): {} enabled task(s)",
+            entry.name,
+            entry.registry_id,
+            tasks.len()
         );
+        let state = state_store_at(entry.state_path.as_deref())?;
+        let notifier = notifier_for(entry.webhooks()?)?.or_else(|| notifier.clone());
+        groups.push((tasks, state, notifier));
     }
 
+    let all_tasks: Vec<_> = groups.iter().flat_map(|(tasks, _, _)| tasks).collect();
+    tracing::info!("Loaded {} enabled task(s)", all_tasks.len());
+    for task in &all_tasks {
+        tracing::info!("  {} every {} seconds", task.name, task.interval.as_secs());
+    }
+    start_health_server(
+        config,
+        signer,
+        connection.clone(),
+        all_tasks
+            .iter()
+            .map(|task| (task.name.clone(), task.interval))
+            .collect(),
+    )
+    .await?;
+
+    let runs = groups
+        .into_iter()
+        .filter(|(tasks, _, _)| !tasks.is_empty())
+        .map(|(tasks, state, notifier)| {
+            let mut ctx = TaskContext::shared(connection.clone())
+                .with_state_store(state)
+                .with_shutdown(shutdown.clone());
+            if let Some(notifier) = notifier {
+                ctx = ctx.with_notifier(notifier);
+            }
+            run_tasks(tasks, Arc::new(ctx))
+        });
+    futures::future::join_all(runs).await;
+    Ok(())
+}
+
+/// Serve the REST API and `/ws` updates of the configured registry on `server_addr`
+#[cfg(feature = "server")]
+async fn serve(config: &WorkerConfig) -> Result<(), Box<dyn std::error::Error>> {
+    use canary_sdk::canary::CanaryClient;
+
+    let registry_id = config
+        .registry_id
+        .ok_or("registry_id (or REGISTRY_ID) is required")?;
+    let addr = config.server_addr.as_deref().unwrap_or("0.0.0.0:8000");
+    let canary = CanaryClient::new(connect(config).await?, registry_id);
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to listen on {}: {}", addr, e))?;
+    tracing::info!("Serving registry {} on http://{}", registry_id, addr);
+    canary_sdk::server::serve(listener, Arc::new(canary)).await?;
     Ok(())
 }
+
+/// Create a Sui client from the configured RPC provider or network
+async fn connect(config: &WorkerConfig) -> Result<sui_sdk::SuiClient, Box<dyn std::error::Error>> {
+    // Create Sui client, preferring a named RPC provider preset when configured
+    let client = match &config.rpc_provider {
+        Some(provider) => {
+            tracing::info!("Connecting via RPC provider: {}", provider);
+            create_sui_client_with_provider(provider, &EnvSecretSource).await?
+        }
+        None => {
+            tracing::info!("Connecting to network: {:?}", config.network);
+            create_sui_client(config.network.clone()).await?
+        }
+    };
+    // A provider URL may point at another chain than the configured network
+    let chain_id = verify_chain_id(&client, &config.network).await?;
+    tracing::info!("Connected to Sui network (chain {})", chain_id);
+    Ok(client)
+}
+
+/// Connect, and reconnect via `connect` whenever the node stops answering
+async fn shared_connection(
+    config: &WorkerConfig,
+) -> Result<ReconnectingClient, Box<dyn std::error::Error>> {
+    let client = connect(config).await?;
+    let config = config.clone();
+    Ok(ReconnectingClient::new(client).with_reconnect(move || {
+        let config = config.clone();
+        async move {
+            connect(&config)
+                .await
+                .map_err(|e| ClientError::ClientCreation(e.to_string()))
+        }
+    }))
+}
+
+/// The worker's connection, kept across runs
+enum WorkerClient {
+    /// A key is configured
+    #[cfg(feature = "signing")]
+    Signer(SuiClientWithSigner),
+    /// No key is configured, or the build cannot sign
+    ReadOnly(ReadOnlyClient),
+}
+
+impl WorkerClient {
+    /// Reconnect the shared connection if the node stopped answering, and switch to
+    /// the new one
+    async fn ensure_connected(
+        &mut self,
+        connection: &ReconnectingClient,
+    ) -> Result<bool, ClientError> {
+        if !connection.ensure_connected().await? {
+            return Ok(false);
+        }
+        match self {
+            #[cfg(feature = "signing")]
+            WorkerClient::Signer(client) => client.client = connection.client(),
+            WorkerClient::ReadOnly(client) => *client = ReadOnlyClient::new(connection.client()),
+        }
+        Ok(true)
+    }
+
+    /// The connection without its keys, for tasks that only read
+    fn read_only(&self) -> ReadOnlyClient {
+        match self {
+            #[cfg(feature = "signing")]
+            WorkerClient::Signer(client) => ReadOnlyClient::new(client.client.clone()),
+            WorkerClient::ReadOnly(client) => client.clone(),
+        }
+    }
+}
+
+/// Load the configured key (`key.env`) into a signing client on `connection` if it is
+/// set
+#[cfg(feature = "signing")]
+async fn worker_client(
+    config: &WorkerConfig,
+    connection: &ReconnectingClient,
+) -> Result<WorkerClient, Box<dyn std::error::Error>> {
+    use canary_sdk::keystore::{add_to_keystore, parse_bech32_private_key};
+    use sui_keys::keystore::{InMemKeystore, Keystore};
+
+    let client = connection.client();
+    let bech32_key = match std::env::var(&config.key.env) {
+        Ok(key) => zeroize::Zeroizing::new(key),
+        Err(_) => return Ok(WorkerClient::ReadOnly(ReadOnlyClient::new(client))),
+    };
+    let parsed = parse_bech32_private_key(&bech32_key)?;
+
+    let mut keystore = Keystore::InMem(InMemKeystore::default());
+    let signer = add_to_keystore(&mut keystore, parsed).await?;
+    Ok(WorkerClient::Signer(SuiClientWithSigner {
+        client,
+        signer,
+        keystore,
+        retry: Default::default(),
+        profiles: Default::default(),
+        gas_policy: config.gas_policy(),
+    }))
+}
+
+/// Builds without signing support only read
+#[cfg(not(feature = "signing"))]
+async fn worker_client(
+    _config: &WorkerConfig,
+    connection: &ReconnectingClient,
+) -> Result<WorkerClient, Box<dyn std::error::Error>> {
+    Ok(WorkerClient::ReadOnly(ReadOnlyClient::new(
+        connection.client(),
+    )))
+}
+
+/// Format of member listings, from `--output`
+static OUTPUT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// The `--output json|yaml|table` flag, `table` when absent
+fn output_format() -> Result<OutputFormat, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--output=") {
+            return value.parse();
+        }
+        if arg == "--output" {
+            let value = args.next().ok_or("--output requires a value")?;
+            return value.parse();
+        }
+    }
+    Ok(OutputFormat::default())
+}
+
+/// Log the registry's members and the changes since the previous run
+async fn run_task(
+    config: &WorkerConfig,
+    client: &ReadOnlyClient,
+    state: &dyn StateStore,
+    shutdown: &watch::Receiver<bool>,
+) -> Result<Vec<MemberChange>, Box<dyn std::error::Error>> {
+    let registry_id = config
+        .registry_id
+        .ok_or("registry_id (or REGISTRY_ID) is required")?;
+
+    tracing::info!("Querying members for registry: {}", registry_id);
+
+    // Query all members, bounded so a pathological response cannot stall the loop
+    let members = config
+        .call_budget()
+        .run("query_all_members", client.members(registry_id))
+        .await?;
+
+    tracing::info!("Found {} members", members.len());
+    let output = OUTPUT.get().copied().unwrap_or_default();
+    println!("{}", render(&members, output)?);
+
+    // Stop between RPC steps once shutdown is requested
+    if *shutdown.borrow() {
+        return Err(WorkerError::Cancelled.into());
+    }
+    // The first run only records the member list to compare against
+    let changes = track_members(state, registry_id, &members)
+        .await?
+        .unwrap_or_default();
+    if !changes.is_empty() {
+        tracing::info!("{} member changes since the previous run", changes.len());
+    }
+    for change in &changes {
+        tracing::info!("{}", change);
+    }
+
+    Ok(changes)
+}
@@ -0,0 +1,395 @@
+//! Prometheus metrics
+//!
+//! The SDK records what a production worker needs to be monitored: task runs and
+//! their outcome, the latency of each RPC operation run through `RetryPolicy`,
+//! executed transactions, and the gas they spent. Everything is recorded in the
+//! process-wide `Metrics::global()` registry and rendered in the Prometheus text
+//! format by `Metrics::render`; `serve` exposes it on `/metrics`, as does the
+//! `health` listener.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Content type of the Prometheus text format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Metrics registry
+#[derive(Default)]
+pub struct Metrics {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    task_runs: BTreeMap<(String, &'static str), u64>,
+    task_last_run: BTreeMap<String, f64>,
+    task_last_success: BTreeMap<String, f64>,
+    rpc: BTreeMap<String, RpcStats>,
+    transactions: BTreeMap<&'static str, u64>,
+    gas_spent_mist: u64,
+}
+
+#[derive(Default)]
+struct RpcStats {
+    calls: u64,
+    errors: u64,
+    seconds: f64,
+}
+
+impl Metrics {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry the SDK records into
+    pub fn global() -> &'static Metrics {
+        static GLOBAL: OnceLock<Metrics> = OnceLock::new();
+        GLOBAL.get_or_init(Metrics::new)
+    }
+
+    /// Record a finished task run
+    pub fn record_task_run(&self, task: &str, success: bool) {
+        let now = unix_seconds(SystemTime::now());
+        let mut inner = self.lock();
+        *inner
+            .task_runs
+            .entry((task.to_string(), outcome(success)))
+            .or_default() += 1;
+        inner.task_last_run.insert(task.to_string(), now);
+        if success {
+            inner.task_last_success.insert(task.to_string(), now);
+        }
+    }
+
+    /// Record one RPC operation, including its retries
+    pub fn record_rpc(&self, operation: &str, elapsed: Duration, success: bool) {
+        let mut inner = self.lock();
+        let stats = inner.rpc.entry(operation.to_string()).or_default();
+        stats.calls += 1;
+        stats.seconds += elapsed.as_secs_f64();
+        if !success {
+            stats.errors += 1;
+        }
+    }
+
+    /// Record an executed transaction and the gas it spent, in MIST
+    pub fn record_transaction(&self, success: bool, gas_mist: u64) {
+        let mut inner = self.lock();
+        *inner.transactions.entry(outcome(success)).or_default() += 1;
+        inner.gas_spent_mist = inner.gas_spent_mist.saturating_add(gas_mist);
+    }
+
+    /// Time of the last successful run of `task`, if it has succeeded yet
+    pub fn last_success(&self, task: &str) -> Option<SystemTime> {
+        let seconds = *self.lock().task_last_success.get(task)?;
+        Some(UNIX_EPOCH + Duration::from_secs_f64(seconds))
+    }
+
+    /// Render all metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let inner = self.lock();
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "canary_task_runs_total",
+            "counter",
+            "Worker task runs by outcome",
+        );
+        for ((task, outcome), count) in &inner.task_runs {
+            let _ = writeln!(
+                out,
+                "canary_task_runs_total{{task=\"{}\",outcome=\"{}\"}} {}",
+                escape(task),
+                outcome,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_task_last_run_timestamp_seconds",
+            "gauge",
+            "Unix time of the last task run",
+        );
+        for (task, at) in &inner.task_last_run {
+            let _ = writeln!(
+                out,
+                "canary_task_last_run_timestamp_seconds{{task=\"{}\"}} {}",
+                escape(task),
+                at
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_task_last_success_timestamp_seconds",
+            "gauge",
+            "Unix time of the last successful task run",
+        );
+        for (task, at) in &inner.task_last_success {
+            let _ = writeln!(
+                out,
+                "canary_task_last_success_timestamp_seconds{{task=\"{}\"}} {}",
+                escape(task),
+                at
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_rpc_duration_seconds",
+            "summary",
+            "Latency of RPC operations, including retries",
+        );
+        for (operation, stats) in &inner.rpc {
+            let operation = escape(operation);
+            let _ = writeln!(
+                out,
+                "canary_rpc_duration_seconds_sum{{operation=\"{}\"}} {}",
+                operation, stats.seconds
+            );
+            let _ = writeln!(
+                out,
+                "canary_rpc_duration_seconds_count{{operation=\"{}\"}} {}",
+                operation, stats.calls
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_rpc_errors_total",
+            "counter",
+            "RPC operations that failed after retries",
+        );
+        for (operation, stats) in &inner.rpc {
+            let _ = writeln!(
+                out,
+                "canary_rpc_errors_total{{operation=\"{}\"}} {}",
+                escape(operation),
+                stats.errors
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_transactions_total",
+            "counter",
+            "Executed transactions by outcome",
+        );
+        for (outcome, count) in &inner.transactions {
+            let _ = writeln!(
+                out,
+                "canary_transactions_total{{outcome=\"{}\"}} {}",
+                outcome, count
+            );
+        }
+
+        header(
+            &mut out,
+            "canary_gas_spent_mist_total",
+            "counter",
+            "Gas spent by executed transactions, in MIST",
+        );
+        let _ = writeln!(out, "canary_gas_spent_mist_total {}", inner.gas_spent_mist);
+
+        out
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Serve `metrics` on `GET /metrics` until the listener fails
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::metrics::{serve, Metrics};
+/// use tokio::net::TcpListener;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = TcpListener::bind("0.0.0.0:9184").await?;
+/// tokio::spawn(serve(listener, Metrics::global()));
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve(listener: TcpListener, metrics: &'static Metrics) -> std::io::Result<()> {
+    serve_http(listener, move |method, path| async move {
+        match (method.as_str(), path.as_str()) {
+            ("GET", "/metrics") => metrics_response(metrics),
+            _ => HttpResponse::not_found(&method),
+        }
+    })
+    .await
+}
+
+/// The `/metrics` response
+pub(crate) fn metrics_response(metrics: &Metrics) -> HttpResponse {
+    HttpResponse {
+        status: "200 OK",
+        content_type: CONTENT_TYPE,
+        body: metrics.render(),
+    }
+}
+
+/// A minimal HTTP response
+pub(crate) struct HttpResponse {
+    pub status: &'static str,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// 404 for unknown `GET` paths, 405 for other methods
+    pub fn not_found(method: &str) -> Self {
+        let (status, body) = if method == "GET" {
+            ("404 Not Found", "not found\n")
+        } else {
+            ("405 Method Not Allowed", "method not allowed\n")
+        };
+        Self {
+            status,
+            content_type: "text/plain",
+            body: body.to_string(),
+        }
+    }
+
+    fn to_http(&self) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+/// Answer each connection's request line with `handler(method, path)`
+///
+/// Just enough HTTP for scrapers and probes: one request per connection, no body.
+pub(crate) async fn serve_http<H, Fut>(listener: TcpListener, handler: H) -> std::io::Result<()>
+where
+    H: Fn(String, String) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = HttpResponse> + Send,
+{
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(handle_http(stream, handler.clone()));
+    }
+}
+
+/// Answer one connection's request line with `handler(method, path)`
+pub(crate) async fn handle_http<H, Fut>(mut stream: TcpStream, handler: H)
+where
+    H: Fn(String, String) -> Fut,
+    Fut: Future<Output = HttpResponse>,
+{
+    let mut buffer = [0u8; 4096];
+    let read = match stream.read(&mut buffer).await {
+        Ok(read) => read,
+        Err(e) => {
+            tracing::debug!("Failed to read HTTP request: {}", e);
+            return;
+        }
+    };
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("").to_string();
+
+    let response = handler(method, path).await;
+    if let Err(e) = stream.write_all(response.to_http().as_bytes()).await {
+        tracing::debug!("Failed to write HTTP response: {}", e);
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn outcome(success: bool) -> &'static str {
+    if success {
+        "success"
+    } else {
+        "failure"
+    }
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn unix_seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new();
+        metrics.record_task_run("snapshot", true);
+        metrics.record_task_run("snapshot", false);
+        metrics.record_rpc("get_object", Duration::from_millis(250), true);
+        metrics.record_rpc("get_object", Duration::from_millis(750), false);
+        metrics.record_transaction(true, 1_500);
+        metrics.record_transaction(true, 500);
+
+        let text = metrics.render();
+        assert!(text.contains("canary_task_runs_total{task=\"snapshot\",outcome=\"success\"} 1"));
+        assert!(text.contains("canary_task_runs_total{task=\"snapshot\",outcome=\"failure\"} 1"));
+        assert!(text.contains("canary_task_last_success_timestamp_seconds{task=\"snapshot\"}"));
+        assert!(text.contains("canary_rpc_duration_seconds_sum{operation=\"get_object\"} 1"));
+        assert!(text.contains("canary_rpc_duration_seconds_count{operation=\"get_object\"} 2"));
+        assert!(text.contains("canary_rpc_errors_total{operation=\"get_object\"} 1"));
+        assert!(text.contains("canary_transactions_total{outcome=\"success\"} 2"));
+        assert!(text.contains("canary_gas_spent_mist_total 2000"));
+        assert!(text.contains("# TYPE canary_rpc_duration_seconds summary"));
+
+        assert!(metrics.last_success("snapshot").is_some());
+        assert!(metrics.last_success("members").is_none());
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_http_responses() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(false, 0);
+
+        let ok = metrics_response(&metrics).to_http();
+        assert!(ok.starts_with("HTTP/1.1 200 OK"));
+        assert!(ok.contains(CONTENT_TYPE));
+        assert!(ok.contains("canary_transactions_total{outcome=\"failure\"} 1"));
+
+        assert!(HttpResponse::not_found("GET")
+            .to_http()
+            .starts_with("HTTP/1.1 404"));
+        assert!(HttpResponse::not_found("POST")
+            .to_http()
+            .starts_with("HTTP/1.1 405"));
+    }
+}
@@ -0,0 +1,358 @@
+//! Canary freshness monitoring
+//!
+//! A canary only means something while it is kept up to date: it must be renewed
+//! before it gets too old, and it must cover the package version that is actually
+//! deployed. `monitor_canaries` checks each watched domain of a registry and reports
+//! it as fresh, stale, or missing:
+//!
+//! - `Missing`: the registry holds no canary for the domain
+//! - `Stale`: the newest canary is older than the maximum age, or the domain's package
+//!   was upgraded and no canary covers the latest version yet
+//! - `Fresh`: otherwise
+//!
+//! The latest version of a domain's package is read from its `UpgradeCap`, which always
+//! points at the current version. Domains without a known `UpgradeCap` are only checked
+//! for age.
+
+use crate::canary::{json_string, object_fields, query_all_canary_blobs, CanaryBlobInfo};
+use crate::clock::is_stale;
+use crate::compat::{Artifact, SDK_VERSION};
+use crate::error::CanaryError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use sui_sdk::rpc_types::SuiObjectDataOptions;
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::SuiClient;
+
+/// Default maximum canary age (30 days)
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(30 * 86400);
+
+/// Freshness of a domain's canary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryStatus {
+    /// Recent enough and covering the latest package version
+    Fresh,
+    /// Too old, or covering an outdated package version
+    Stale,
+    /// No canary for the domain
+    Missing,
+}
+
+impl fmt::Display for CanaryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanaryStatus::Fresh => write!(f, "fresh"),
+            CanaryStatus::Stale => write!(f, "stale"),
+            CanaryStatus::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// The freshness report of one domain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanaryReport {
+    /// The domain
+    pub domain: String,
+    /// The canary's freshness
+    pub status: CanaryStatus,
+    /// The canary the status is based on, if the domain has one
+    pub canary_blob_id: Option<ObjectID>,
+    /// The package that canary covers
+    pub package_id: Option<ObjectID>,
+    /// The latest version of the domain's package, if its UpgradeCap is known
+    pub latest_package_id: Option<ObjectID>,
+    /// When that canary was uploaded, in Unix milliseconds
+    pub uploaded_at: Option<u64>,
+    /// Why the canary is not fresh
+    pub reason: Option<String>,
+}
+
+impl CanaryReport {
+    /// Whether the domain needs attention
+    pub fn is_alert(&self) -> bool {
+        self.status != CanaryStatus::Fresh
+    }
+}
+
+impl fmt::Display for CanaryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.domain, self.status)?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({})", reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// The freshness reports of a registry's canaries, as exported
+///
+/// Load archived reports with `compat::load`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorReport {
+    /// Schema version of this artifact (see `compat`)
+    pub schema_version: u32,
+    /// Version of the SDK that produced this artifact
+    pub sdk_version: String,
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// When the canaries were checked, in Unix milliseconds
+    pub checked_at_ms: u64,
+    /// One report per domain, sorted by domain
+    pub canaries: Vec<CanaryReport>,
+}
+
+impl MonitorReport {
+    /// Stamp the reports of a `monitor_canaries` run with the current schema and SDK version
+    pub fn new(registry_id: ObjectID, checked_at_ms: u64, canaries: Vec<CanaryReport>) -> Self {
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            sdk_version: SDK_VERSION.to_string(),
+            registry_id,
+            checked_at_ms,
+            canaries,
+        }
+    }
+
+    /// Whether any domain needs attention
+    pub fn has_alerts(&self) -> bool {
+        self.canaries.iter().any(CanaryReport::is_alert)
+    }
+}
+
+/// What `monitor_canaries` checks
+#[derive(Debug, Clone)]
+pub struct MonitorOptions {
+    /// Canaries older than this are stale
+    pub max_age: Duration,
+    /// Domains to check; if empty, every domain with a canary in the registry
+    pub domains: Vec<String>,
+    /// The `UpgradeCap` of each domain's package, to detect outdated canaries
+    pub upgrade_caps: BTreeMap<String, ObjectID>,
+}
+
+impl Default for MonitorOptions {
+    fn default() -> Self {
+        Self {
+            max_age: DEFAULT_MAX_AGE,
+            domains: Vec::new(),
+            upgrade_caps: BTreeMap::new(),
+        }
+    }
+}
+
+/// Check the freshness of a registry's canaries
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `options` - The domains to check, their UpgradeCaps, and the maximum age
+/// * `now_ms` - The current time in Unix milliseconds (e.g. from a `TimeSource`)
+///
+/// # Returns
+///
+/// Returns one report per domain, sorted by domain, or a `CanaryError` if the
+/// registry or an UpgradeCap cannot be read.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::monitor::{monitor_canaries, MonitorOptions};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Mainnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let options = MonitorOptions {
+///     domains: vec!["example.com".to_string()],
+///     ..Default::default()
+/// };
+/// for report in monitor_canaries(&client, registry_id, &options, 1_700_000_000_000).await? {
+///     println!("{}", report);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn monitor_canaries(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    options: &MonitorOptions,
+    now_ms: u64,
+) -> Result<Vec<CanaryReport>, CanaryError> {
+    let blobs = query_all_canary_blobs(client, registry_id).await?;
+
+    let mut domains: Vec<String> = if options.domains.is_empty() {
+        blobs.iter().map(|blob| blob.domain.clone()).collect()
+    } else {
+        options.domains.clone()
+    };
+    domains.sort();
+    domains.dedup();
+
+    let mut reports = Vec::with_capacity(domains.len());
+    for domain in domains {
+        let latest_package_id = match options.upgrade_caps.get(&domain) {
+            Some(upgrade_cap_id) => Some(latest_package(client, *upgrade_cap_id).await?),
+            None => None,
+        };
+        reports.push(evaluate_canary(
+            &domain,
+            &blobs,
+            latest_package_id,
+            now_ms,
+            options.max_age,
+        ));
+    }
+    Ok(reports)
+}
+
+/// Read the package version an `UpgradeCap` currently points at
+pub async fn latest_package(
+    client: &SuiClient,
+    upgrade_cap_id: ObjectID,
+) -> Result<ObjectID, CanaryError> {
+    let upgrade_cap = client
+        .read_api()
+        .get_object_with_options(upgrade_cap_id, SuiObjectDataOptions::new().with_content())
+        .await
+        .map_err(|e| {
+            CanaryError::Registry(format!(
+                "Failed to get UpgradeCap {}: {}",
+                upgrade_cap_id, e
+            ))
+        })?
+        .into_object()
+        .map_err(|_| CanaryError::Registry(format!("UpgradeCap {} not found", upgrade_cap_id)))?;
+
+    let package = json_string(&object_fields(upgrade_cap)?, "package")?;
+    ObjectID::from_hex_literal(&package).map_err(|e| {
+        CanaryError::Registry(format!("Invalid UpgradeCap package {}: {}", package, e))
+    })
+}
+
+/// Judge a domain's freshness from the registry's canaries
+///
+/// With `latest_package_id`, only canaries covering that package count; otherwise the
+/// domain's newest canary does.
+pub fn evaluate_canary(
+    domain: &str,
+    blobs: &[CanaryBlobInfo],
+    latest_package_id: Option<ObjectID>,
+    now_ms: u64,
+    max_age: Duration,
+) -> CanaryReport {
+    let newest = |package: Option<ObjectID>| {
+        blobs
+            .iter()
+            .filter(|blob| blob.domain.eq_ignore_ascii_case(domain))
+            .filter(|blob| package.is_none_or(|package| blob.package_id == package))
+            .max_by_key(|blob| blob.uploaded_at)
+    };
+    let report = |status, blob: Option<&CanaryBlobInfo>, reason: Option<String>| CanaryReport {
+        domain: domain.to_string(),
+        status,
+        canary_blob_id: blob.map(|blob| blob.id),
+        package_id: blob.map(|blob| blob.package_id),
+        latest_package_id,
+        uploaded_at: blob.map(|blob| blob.uploaded_at),
+        reason,
+    };
+
+    let Some(newest_any) = newest(None) else {
+        return report(CanaryStatus::Missing, None, None);
+    };
+    let Some(blob) = newest(latest_package_id) else {
+        let reason = format!(
+            "no canary for the latest package {}, newest covers {}",
+            latest_package_id.unwrap_or_default(),
+            newest_any.package_id
+        );
+        return report(CanaryStatus::Stale, Some(newest_any), Some(reason));
+    };
+
+    if is_stale(blob.uploaded_at, now_ms, max_age) {
+        let age_days = now_ms.saturating_sub(blob.uploaded_at) / 86_400_000;
+        let reason = format!(
+            "last updated {} days ago, max age {} days",
+            age_days,
+            max_age.as_secs() / 86400
+        );
+        report(CanaryStatus::Stale, Some(blob), Some(reason))
+    } else {
+        report(CanaryStatus::Fresh, Some(blob), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::SuiAddress;
+
+    const DAY_MS: u64 = 86_400_000;
+
+    fn blob(id: u8, domain: &str, package: u8, uploaded_at: u64) -> CanaryBlobInfo {
+        CanaryBlobInfo {
+            id: ObjectID::from_single_byte(id),
+            contract_blob_id: ObjectID::from_single_byte(100),
+            explain_blob_id: ObjectID::from_single_byte(101),
+            package_id: ObjectID::from_single_byte(package),
+            domain: domain.to_string(),
+            uploaded_at,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_canary() {
+        let now = 100 * DAY_MS;
+        let max_age = Duration::from_secs(30 * 86400);
+        let blobs = vec![
+            blob(1, "example.com", 10, 50 * DAY_MS),
+            blob(2, "example.com", 11, 90 * DAY_MS),
+            blob(3, "old.example", 10, 10 * DAY_MS),
+        ];
+
+        let report = evaluate_canary("example.com", &blobs, None, now, max_age);
+        assert_eq!(report.status, CanaryStatus::Fresh);
+        assert_eq!(report.canary_blob_id, Some(ObjectID::from_single_byte(2)));
+
+        let report = evaluate_canary("old.example", &blobs, None, now, max_age);
+        assert_eq!(report.status, CanaryStatus::Stale);
+        assert_eq!(
+            report.to_string(),
+            "old.example: stale (last updated 90 days ago, max age 30 days)"
+        );
+
+        let report = evaluate_canary("new.example", &blobs, None, now, max_age);
+        assert_eq!(report.status, CanaryStatus::Missing);
+        assert!(report.canary_blob_id.is_none());
+    }
+
+    #[test]
+    fn test_evaluate_canary_package_upgrade() {
+        let now = 100 * DAY_MS;
+        let max_age = Duration::from_secs(30 * 86400);
+        let blobs = vec![
+            blob(1, "example.com", 10, 95 * DAY_MS),
+            blob(2, "example.com", 11, 50 * DAY_MS),
+        ];
+
+        // The latest package only has an old canary
+        let latest = Some(ObjectID::from_single_byte(11));
+        let report = evaluate_canary("example.com", &blobs, latest, now, max_age);
+        assert_eq!(report.status, CanaryStatus::Stale);
+        assert_eq!(report.canary_blob_id, Some(ObjectID::from_single_byte(2)));
+
+        // The package was upgraded again and has no canary yet
+        let latest = Some(ObjectID::from_single_byte(12));
+        let report = evaluate_canary("example.com", &blobs, latest, now, max_age);
+        assert_eq!(report.status, CanaryStatus::Stale);
+        assert_eq!(report.canary_blob_id, Some(ObjectID::from_single_byte(1)));
+        assert!(report.reason.unwrap().contains("latest package"));
+    }
+}
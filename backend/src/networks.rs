@@ -0,0 +1,225 @@
+//! Per-network deployments and chain identity checks
+//!
+//! A network name in a config file says nothing about where its RPC URL actually
+//! points: a testnet config with a mainnet provider URL would send admin transactions
+//! to mainnet. Every Sui chain has a chain identifier (the first bytes of its genesis
+//! checkpoint digest), so `verify_chain_id` asks the node for it and compares it with
+//! the identifier the network is known to have. `create_sui_client` runs the check for
+//! the mainnet and testnet presets.
+//!
+//! `NetworkRegistry` maps chain identifiers to the Canary deployment (package and
+//! registry IDs) on that chain. `NetworkRegistry::check_object` rejects an ID that
+//! belongs to another chain's deployment, e.g. a mainnet registry ID used with a
+//! testnet client. Devnet and localnet are reset regularly and get a new identifier
+//! each time, so they have no known identifier and are not checked.
+
+use crate::error::ClientError;
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::SuiClient;
+
+use crate::client::Network;
+
+/// Chain identifier of Sui mainnet
+pub const MAINNET_CHAIN_ID: &str = "35834a8a";
+
+/// Chain identifier of Sui testnet
+pub const TESTNET_CHAIN_ID: &str = "4c78adac";
+
+/// The chain identifier a network is known to have, if it is stable
+pub fn expected_chain_id(network: &Network) -> Option<&'static str> {
+    match network {
+        Network::Mainnet => Some(MAINNET_CHAIN_ID),
+        Network::Testnet => Some(TESTNET_CHAIN_ID),
+        Network::Localnet | Network::Devnet | Network::Custom(_) | Network::Pool(_) => None,
+    }
+}
+
+/// The chain identifier reported by a client's node
+pub async fn chain_id(client: &SuiClient) -> Result<String, ClientError> {
+    client
+        .read_api()
+        .get_chain_identifier()
+        .await
+        .map_err(|e| ClientError::Network(format!("Failed to get chain identifier: {}", e)))
+}
+
+/// Check that a client is connected to the chain its network is known to have
+///
+/// # Arguments
+///
+/// * `client` - The client to check
+/// * `network` - The network the client is supposed to be connected to
+///
+/// # Returns
+///
+/// Returns the node's chain identifier, or `ClientError::ChainMismatch` if the network
+/// has a known identifier and the node reports another one.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_sui_client_with_url, Network};
+/// use canary_sdk::networks::verify_chain_id;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client_with_url("https://sui-testnet.example.com").await?;
+/// let chain_id = verify_chain_id(&client, &Network::Testnet).await?;
+/// println!("Connected to chain {}", chain_id);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn verify_chain_id(client: &SuiClient, network: &Network) -> Result<String, ClientError> {
+    let actual = chain_id(client).await?;
+    check_chain_id(network, &actual)?;
+    Ok(actual)
+}
+
+/// Compare a chain identifier with the one `network` is known to have
+pub fn check_chain_id(network: &Network, actual: &str) -> Result<(), ClientError> {
+    match expected_chain_id(network) {
+        Some(expected) if expected != actual => Err(ClientError::ChainMismatch {
+            network: format!("{:?}", network),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// The Canary objects deployed on one chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    /// The Canary package (its original ID, before any upgrade)
+    pub package_id: ObjectID,
+    /// The shared Registry object
+    pub registry_id: ObjectID,
+}
+
+impl Deployment {
+    /// Whether `object_id` is this deployment's package or registry
+    pub fn contains(&self, object_id: ObjectID) -> bool {
+        self.package_id == object_id || self.registry_id == object_id
+    }
+}
+
+/// Canary deployments, by chain identifier
+///
+/// The registry starts empty; register the deployments an application uses (e.g.
+/// from its config) so their IDs can be resolved and checked per chain.
+#[derive(Debug, Default)]
+pub struct NetworkRegistry {
+    deployments: RwLock<BTreeMap<String, Deployment>>,
+}
+
+impl NetworkRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry
+    pub fn global() -> &'static NetworkRegistry {
+        static GLOBAL: OnceLock<NetworkRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(NetworkRegistry::new)
+    }
+
+    /// Record the deployment on the chain with identifier `chain_id`
+    pub fn register(&self, chain_id: impl Into<String>, deployment: Deployment) {
+        self.deployments
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(chain_id.into(), deployment);
+    }
+
+    /// The deployment on a chain, if one is registered
+    pub fn deployment(&self, chain_id: &str) -> Option<Deployment> {
+        self.deployments
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(chain_id)
+            .copied()
+    }
+
+    /// The deployment of a network with a known chain identifier
+    pub fn for_network(&self, network: &Network) -> Option<Deployment> {
+        expected_chain_id(network).and_then(|chain_id| self.deployment(chain_id))
+    }
+
+    /// Check that an object does not belong to another chain's deployment
+    ///
+    /// IDs of no registered deployment pass: they may be the application's own objects.
+    ///
+    /// # Returns
+    ///
+    /// Returns `ClientError::WrongChain` if `object_id` is the package or registry of a
+    /// deployment on a chain other than `chain_id`.
+    pub fn check_object(&self, chain_id: &str, object_id: ObjectID) -> Result<(), ClientError> {
+        let deployments = self.deployments.read().unwrap_or_else(|e| e.into_inner());
+        if deployments
+            .get(chain_id)
+            .is_some_and(|deployment| deployment.contains(object_id))
+        {
+            return Ok(());
+        }
+        match deployments
+            .iter()
+            .find(|(_, deployment)| deployment.contains(object_id))
+        {
+            Some((object_chain, _)) => Err(ClientError::WrongChain {
+                object_id,
+                object_chain: object_chain.clone(),
+                chain: chain_id.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_chain_id() {
+        check_chain_id(&Network::Mainnet, MAINNET_CHAIN_ID).unwrap();
+        check_chain_id(&Network::Devnet, "anything").unwrap();
+        check_chain_id(&Network::Custom("http://x".to_string()), MAINNET_CHAIN_ID).unwrap();
+
+        let err = check_chain_id(&Network::Testnet, MAINNET_CHAIN_ID).unwrap_err();
+        assert!(matches!(err, ClientError::ChainMismatch { .. }));
+    }
+
+    #[test]
+    fn test_check_object() {
+        let networks = NetworkRegistry::new();
+        let mainnet = Deployment {
+            package_id: ObjectID::from_single_byte(1),
+            registry_id: ObjectID::from_single_byte(2),
+        };
+        let testnet = Deployment {
+            package_id: ObjectID::from_single_byte(3),
+            registry_id: ObjectID::from_single_byte(4),
+        };
+        networks.register(MAINNET_CHAIN_ID, mainnet);
+        networks.register(TESTNET_CHAIN_ID, testnet);
+        assert_eq!(networks.for_network(&Network::Testnet), Some(testnet));
+        assert_eq!(networks.for_network(&Network::Devnet), None);
+
+        networks
+            .check_object(TESTNET_CHAIN_ID, testnet.registry_id)
+            .unwrap();
+        networks
+            .check_object(TESTNET_CHAIN_ID, ObjectID::from_single_byte(9))
+            .unwrap();
+        // A mainnet registry with a testnet client
+        let err = networks
+            .check_object(TESTNET_CHAIN_ID, mainnet.registry_id)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ClientError::WrongChain { object_chain, .. } if object_chain == MAINNET_CHAIN_ID
+        ));
+    }
+}
@@ -0,0 +1,425 @@
+//! Task outcome notifications
+//!
+//! A `Notifier` posts the outcome of each worker task run to webhooks, so operators
+//! learn about failures and registry changes without scraping logs. Each webhook gets
+//! the outcome in its own format:
+//!
+//! - `json`: the `TaskOutcome` as a JSON object
+//! - `slack`: a Slack incoming-webhook message (`{"text": ...}`)
+//! - `discord`: a Discord webhook message with a colored embed
+//!
+//! Webhooks are configured as `[[notify]]` entries in the worker config (see
+//! `crate::config`). Slack and Discord webhook URLs are secrets, so they are never
+//! logged: errors name the webhook by its host only.
+
+use crate::client::retry::RetryPolicy;
+use crate::client::USER_AGENT;
+use crate::error::NotifyError;
+pub use crate::monitor::CanaryReport;
+pub use crate::snapshot::MemberChange;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_sdk::types::digests::TransactionDigest;
+
+/// Timeout for a single webhook request
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Discord embed colors
+const COLOR_SUCCESS: u32 = 0x2e_cc_71;
+const COLOR_FAILURE: u32 = 0xe7_4c_3c;
+
+/// Payload format of a webhook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// The `TaskOutcome` as JSON
+    #[default]
+    Json,
+    /// Slack incoming webhook
+    Slack,
+    /// Discord webhook
+    Discord,
+}
+
+impl std::str::FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(WebhookFormat::Json),
+            "slack" => Ok(WebhookFormat::Slack),
+            "discord" => Ok(WebhookFormat::Discord),
+            other => Err(format!(
+                "Unknown webhook format: {} (expected json, slack, or discord)",
+                other
+            )),
+        }
+    }
+}
+
+/// A notification target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    /// URL the notifications are posted to
+    pub url: String,
+    /// Payload format
+    pub format: WebhookFormat,
+}
+
+impl Webhook {
+    /// The URL without its path, safe to log
+    pub fn redacted_url(&self) -> String {
+        match reqwest::Url::parse(&self.url) {
+            Ok(url) => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+            Err(_) => "<invalid url>".to_string(),
+        }
+    }
+}
+
+/// Outcome of one task run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskOutcome {
+    /// Task instance name
+    pub task: String,
+    /// Whether the run succeeded
+    pub success: bool,
+    /// The error, if the run failed
+    pub error: Option<String>,
+    /// Digest of the transaction the run submitted, if any
+    pub digest: Option<TransactionDigest>,
+    /// Gas the transaction spent, in MIST
+    pub gas_used: Option<u64>,
+    /// Member changes the run observed
+    #[serde(default)]
+    pub member_changes: Vec<MemberChange>,
+    /// Canary freshness reports the run produced
+    #[serde(default)]
+    pub canaries: Vec<CanaryReport>,
+    /// When the run finished, in Unix milliseconds
+    pub timestamp_ms: u64,
+}
+
+impl TaskOutcome {
+    /// A successful run
+    pub fn success(task: impl Into<String>) -> Self {
+        Self {
+            task: task.into(),
+            success: true,
+            error: None,
+            digest: None,
+            gas_used: None,
+            member_changes: Vec::new(),
+            canaries: Vec::new(),
+            timestamp_ms: now_ms(),
+        }
+    }
+
+    /// A failed run
+    pub fn failure(task: impl Into<String>, error: impl std::fmt::Display) -> Self {
+        Self {
+            success: false,
+            error: Some(error.to_string()),
+            ..Self::success(task)
+        }
+    }
+
+    /// Record the transaction the run submitted
+    pub fn with_transaction(mut self, digest: TransactionDigest, gas_used: u64) -> Self {
+        self.digest = Some(digest);
+        self.gas_used = Some(gas_used);
+        self
+    }
+
+    /// Record the member changes the run observed
+    pub fn with_member_changes(mut self, changes: Vec<MemberChange>) -> Self {
+        self.member_changes = changes;
+        self
+    }
+
+    /// Record the canary freshness reports the run produced
+    pub fn with_canaries(mut self, canaries: Vec<CanaryReport>) -> Self {
+        self.canaries = canaries;
+        self
+    }
+
+    /// Whether the run failed or found canaries that need attention
+    pub fn is_alert(&self) -> bool {
+        !self.success || self.canaries.iter().any(CanaryReport::is_alert)
+    }
+
+    /// One-line summary, e.g. `Task members succeeded (2 member changes)`
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Task {} {}",
+            self.task,
+            if self.success { "succeeded" } else { "failed" }
+        );
+        let mut details = Vec::new();
+        if let Some(digest) = &self.digest {
+            details.push(format!("digest {}", digest));
+        }
+        if let Some(gas) = self.gas_used {
+            details.push(format!("gas {} MIST", gas));
+        }
+        if !self.member_changes.is_empty() {
+            details.push(format!("{} member changes", self.member_changes.len()));
+        }
+        let alerts = self.canaries.iter().filter(|c| c.is_alert()).count();
+        if alerts > 0 {
+            details.push(format!(
+                "{} of {} canaries not fresh",
+                alerts,
+                self.canaries.len()
+            ));
+        }
+        if !details.is_empty() {
+            let _ = write!(summary, " ({})", details.join(", "));
+        }
+        summary
+    }
+
+    /// Multi-line description: the error, each member change, and each canary that is
+    /// not fresh
+    fn details(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(error) = &self.error {
+            lines.push(format!("Error: {}", error));
+        }
+        lines.extend(self.member_changes.iter().map(ToString::to_string));
+        lines.extend(
+            self.canaries
+                .iter()
+                .filter(|c| c.is_alert())
+                .map(ToString::to_string),
+        );
+        lines.join("\n")
+    }
+}
+
+/// Render the payload posted to a webhook of the given format
+pub fn render(format: WebhookFormat, outcome: &TaskOutcome) -> serde_json::Value {
+    match format {
+        WebhookFormat::Json => serde_json::to_value(outcome).unwrap_or_default(),
+        WebhookFormat::Slack => {
+            let details = outcome.details();
+            let text = if details.is_empty() {
+                outcome.summary()
+            } else {
+                format!("{}\n```{}```", outcome.summary(), details)
+            };
+            json!({ "text": text })
+        }
+        WebhookFormat::Discord => json!({
+            "embeds": [{
+                "title": outcome.summary(),
+                "description": outcome.details(),
+                "color": if outcome.is_alert() { COLOR_FAILURE } else { COLOR_SUCCESS },
+            }]
+        }),
+    }
+}
+
+/// Posts task outcomes to webhooks
+pub struct Notifier {
+    webhooks: Vec<Webhook>,
+    retry: RetryPolicy,
+    http: reqwest::Client,
+}
+
+impl Notifier {
+    /// Create a notifier
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::notify::{Notifier, TaskOutcome, Webhook, WebhookFormat};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let notifier = Notifier::new(vec![Webhook {
+    ///     url: "https://hooks.slack.com/services/...".to_string(),
+    ///     format: WebhookFormat::Slack,
+    /// }])?;
+    /// notifier.notify(&TaskOutcome::failure("members", "RPC unavailable")).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(webhooks: Vec<Webhook>) -> Result<Self, NotifyError> {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(NOTIFY_TIMEOUT)
+            .build()
+            .map_err(|e| NotifyError::Http(e.to_string()))?;
+        Ok(Self {
+            webhooks,
+            retry: RetryPolicy::default(),
+            http,
+        })
+    }
+
+    /// Use a different retry policy for webhook requests
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// The configured webhooks
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Post an outcome to every webhook
+    ///
+    /// Notifications are best effort: failures are logged and counted, never returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of webhooks the outcome was delivered to.
+    pub async fn notify(&self, outcome: &TaskOutcome) -> usize {
+        let mut delivered = 0;
+        for webhook in &self.webhooks {
+            match self.send(webhook, outcome).await {
+                Ok(()) => delivered += 1,
+                Err(e) => tracing::error!(
+                    webhook = %webhook.redacted_url(),
+                    task = %outcome.task,
+                    "Notification failed: {}",
+                    e
+                ),
+            }
+        }
+        delivered
+    }
+
+    /// Post an outcome to one webhook, retrying `408`, `429`, `5xx`, and connection
+    /// errors with backoff
+    pub async fn send(&self, webhook: &Webhook, outcome: &TaskOutcome) -> Result<(), NotifyError> {
+        let payload = render(webhook.format, outcome);
+        let mut attempt = 1;
+        loop {
+            let (error, retryable) = match self.http.post(&webhook.url).json(&payload).send().await
+            {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    (
+                        NotifyError::Rejected {
+                            url: webhook.redacted_url(),
+                            status,
+                        },
+                        status == 408 || status == 429 || (500..600).contains(&status),
+                    )
+                }
+                // reqwest errors include the URL, which may be secret
+                Err(e) => (
+                    NotifyError::Http(format!("{}: {}", webhook.redacted_url(), e.without_url())),
+                    true,
+                ),
+            };
+
+            if !retryable || attempt >= self.retry.max_attempts {
+                return Err(error);
+            }
+            tokio::time::sleep(self.retry.backoff(attempt - 1)).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::SuiAddress;
+
+    fn outcome() -> TaskOutcome {
+        TaskOutcome::success("members").with_member_changes(vec![MemberChange::Joined {
+            member: SuiAddress::ZERO,
+            domain: "example.com".to_string(),
+        }])
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            outcome().summary(),
+            "Task members succeeded (1 member changes)"
+        );
+        assert_eq!(
+            TaskOutcome::failure("snapshot", "timeout").summary(),
+            "Task snapshot failed"
+        );
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let json = render(WebhookFormat::Json, &outcome());
+        assert_eq!(json["task"], "members");
+        assert_eq!(json["member_changes"][0]["change"], "joined");
+
+        let slack = render(WebhookFormat::Slack, &outcome());
+        let text = slack["text"].as_str().unwrap();
+        assert!(text.starts_with("Task members succeeded"));
+        assert!(text.contains("joined (example.com)"));
+
+        let discord = render(
+            WebhookFormat::Discord,
+            &TaskOutcome::failure("members", "boom"),
+        );
+        assert_eq!(discord["embeds"][0]["color"], COLOR_FAILURE);
+        assert_eq!(discord["embeds"][0]["description"], "Error: boom");
+    }
+
+    #[test]
+    fn test_canary_alerts() {
+        use crate::monitor::CanaryStatus;
+
+        let report = |domain: &str, status| CanaryReport {
+            domain: domain.to_string(),
+            status,
+            canary_blob_id: None,
+            package_id: None,
+            latest_package_id: None,
+            uploaded_at: None,
+            reason: None,
+        };
+        let outcome = TaskOutcome::success("monitor").with_canaries(vec![
+            report("example.com", CanaryStatus::Fresh),
+            report("example.org", CanaryStatus::Missing),
+        ]);
+        assert!(outcome.is_alert());
+        assert_eq!(
+            outcome.summary(),
+            "Task monitor succeeded (1 of 2 canaries not fresh)"
+        );
+
+        let discord = render(WebhookFormat::Discord, &outcome);
+        assert_eq!(discord["embeds"][0]["color"], COLOR_FAILURE);
+        assert_eq!(discord["embeds"][0]["description"], "example.org: missing");
+    }
+
+    #[test]
+    fn test_redacted_url() {
+        let webhook = Webhook {
+            url: "https://hooks.slack.com/services/T000/B000/secret".to_string(),
+            format: WebhookFormat::Slack,
+        };
+        assert_eq!(webhook.redacted_url(), "https://hooks.slack.com");
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            "Slack".parse::<WebhookFormat>().unwrap(),
+            WebhookFormat::Slack
+        );
+        assert!("teams".parse::<WebhookFormat>().is_err());
+    }
+}
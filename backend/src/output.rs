@@ -0,0 +1,375 @@
+//! Machine-readable results and stable exit codes for binaries
+//!
+//! Binaries built on this crate render their results through `render` in the format
+//! chosen with `--output json|yaml|table`, and exit with an `ExitStatus` code so shell
+//! pipelines and CI jobs can branch on the outcome.
+
+use crate::canary::{CanaryBlobInfo, LedgerEntry, MemberInfoWithAddress, RegistryInfo};
+use crate::error::{CanaryError, ClientError, DomainError, KeystoreError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Stable process exit codes
+///
+/// These values are part of the public interface and must not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Operation completed successfully
+    Success = 0,
+    /// Invalid input, configuration, or key (fix the invocation and retry)
+    UserError = 2,
+    /// RPC, network, or on-chain execution failure
+    ChainError = 3,
+    /// A verification check failed
+    VerificationFailed = 4,
+    /// At least one stale canary was found
+    StaleCanary = 5,
+}
+
+impl ExitStatus {
+    /// The numeric process exit code
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Exit the process with this status
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+impl From<&CanaryError> for ExitStatus {
+    fn from(error: &CanaryError) -> Self {
+        match error {
+            CanaryError::NotMember
+            | CanaryError::NotAdmin
+            | CanaryError::AlreadyMember
+            | CanaryError::InsufficientFee
+            | CanaryError::InvalidCap
+            | CanaryError::DomainTaken
+            | CanaryError::NoSigner
+            | CanaryError::NoAdminCap
+            | CanaryError::NotOwner { .. } => ExitStatus::UserError,
+            CanaryError::Client(e) => ExitStatus::from(e),
+            CanaryError::Domain(e) => ExitStatus::from(e),
+            CanaryError::Registry(_)
+            | CanaryError::CanaryBlobNotFound
+            | CanaryError::Transaction(_)
+            | CanaryError::MoveAbort { .. }
+            | CanaryError::Budget(_) => ExitStatus::ChainError,
+        }
+    }
+}
+
+impl From<&ClientError> for ExitStatus {
+    fn from(error: &ClientError) -> Self {
+        match error {
+            ClientError::InvalidUrl(_)
+            | ClientError::UnknownProvider(_)
+            | ClientError::MissingSecret(_)
+            | ClientError::Unauthorized(_)
+            | ClientError::ChainMismatch { .. }
+            | ClientError::WrongChain { .. } => ExitStatus::UserError,
+            ClientError::ClientCreation(_)
+            | ClientError::Network(_)
+            | ClientError::RateLimited { .. }
+            | ClientError::QuotaExceeded(_)
+            | ClientError::NoHealthyEndpoint(_)
+            | ClientError::ViewCall(_) => ExitStatus::ChainError,
+        }
+    }
+}
+
+impl From<&DomainError> for ExitStatus {
+    fn from(error: &DomainError) -> Self {
+        match error {
+            DomainError::InvalidDomain(_) => ExitStatus::UserError,
+            DomainError::Http(_) => ExitStatus::ChainError,
+            DomainError::NotVerified(_) => ExitStatus::VerificationFailed,
+        }
+    }
+}
+
+impl From<&KeystoreError> for ExitStatus {
+    fn from(_: &KeystoreError) -> Self {
+        ExitStatus::UserError
+    }
+}
+
+/// Output format selected with `--output`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    Json,
+    /// YAML
+    Yaml,
+    /// Human-readable aligned table
+    #[default]
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "table" => Ok(OutputFormat::Table),
+            other => Err(format!(
+                "Unknown output format '{}': expected json, yaml, or table",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Table => "table",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A record that can be rendered as a table row
+pub trait TableRow {
+    /// Column headers
+    fn headers() -> Vec<&'static str>;
+    /// Cell values, in the same order as `headers()`
+    fn row(&self) -> Vec<String>;
+}
+
+impl TableRow for MemberInfoWithAddress {
+    fn headers() -> Vec<&'static str> {
+        vec!["ADDRESS", "DOMAIN", "JOINED_AT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.member.to_string(),
+            self.domain.clone(),
+            self.joined_at.to_string(),
+        ]
+    }
+}
+
+impl TableRow for LedgerEntry {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "TIMESTAMP_MS",
+            "DIGEST",
+            "KIND",
+            "SENDER",
+            "FUNCTION",
+            "AMOUNT_MIST",
+        ]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.timestamp_ms.to_string(),
+            self.digest.to_string(),
+            format!("{:?}", self.kind).to_lowercase(),
+            self.sender.to_string(),
+            self.function.clone().unwrap_or_default(),
+            self.amount.to_string(),
+        ]
+    }
+}
+
+impl TableRow for RegistryInfo {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "FEE_MIST", "MEMBERS", "ADMIN"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.fee.to_string(),
+            self.member_count.to_string(),
+            self.admin.to_string(),
+        ]
+    }
+}
+
+impl TableRow for CanaryBlobInfo {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "ID",
+            "DOMAIN",
+            "PACKAGE",
+            "CONTRACT_BLOB",
+            "EXPLAIN_BLOB",
+            "UPLOADED_AT",
+        ]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.domain.clone(),
+            self.package_id.to_string(),
+            self.contract_blob_id.to_string(),
+            self.explain_blob_id.to_string(),
+            self.uploaded_at.to_string(),
+        ]
+    }
+}
+
+/// Render a list of records in the given format
+///
+/// # Arguments
+///
+/// * `items` - The records to render
+/// * `format` - The output format
+///
+/// # Returns
+///
+/// Returns the rendered text, or an error message if serialization fails.
+pub fn render<T: Serialize + TableRow>(
+    items: &[T],
+    format: OutputFormat,
+) -> Result<String, String> {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(items).map_err(|e| e.to_string()),
+        OutputFormat::Yaml => serde_yaml::to_string(items).map_err(|e| e.to_string()),
+        OutputFormat::Table => Ok(render_table(
+            &T::headers(),
+            &items.iter().map(TableRow::row).collect::<Vec<_>>(),
+        )),
+    }
+}
+
+/// Render rows as a left-aligned table with two spaces between columns
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_line = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_line(headers.to_vec())];
+    for row in rows {
+        lines.push(format_line(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+    fn sample_members() -> Vec<MemberInfoWithAddress> {
+        vec![
+            MemberInfoWithAddress {
+                member: SuiAddress::ZERO,
+                domain: "example.com".to_string(),
+                joined_at: 1_000,
+                raw: None,
+            },
+            MemberInfoWithAddress {
+                member: SuiAddress::ZERO,
+                domain: "a.io".to_string(),
+                joined_at: 2_000,
+                raw: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_exit_codes_are_stable() {
+        assert_eq!(ExitStatus::Success.code(), 0);
+        assert_eq!(ExitStatus::UserError.code(), 2);
+        assert_eq!(ExitStatus::ChainError.code(), 3);
+        assert_eq!(ExitStatus::VerificationFailed.code(), 4);
+        assert_eq!(ExitStatus::StaleCanary.code(), 5);
+    }
+
+    #[test]
+    fn test_exit_status_from_errors() {
+        assert_eq!(
+            ExitStatus::from(&CanaryError::NotAdmin),
+            ExitStatus::UserError
+        );
+        assert_eq!(
+            ExitStatus::from(&CanaryError::NotOwner {
+                object_id: ObjectID::ZERO,
+                owner: SuiAddress::ZERO,
+            }),
+            ExitStatus::UserError
+        );
+        assert_eq!(
+            ExitStatus::from(&CanaryError::Registry("boom".to_string())),
+            ExitStatus::ChainError
+        );
+        assert_eq!(
+            ExitStatus::from(&CanaryError::Client(ClientError::MissingSecret(
+                "KEY".to_string()
+            ))),
+            ExitStatus::UserError
+        );
+        assert_eq!(
+            ExitStatus::from(&KeystoreError::InvalidBech32("x".to_string())),
+            ExitStatus::UserError
+        );
+    }
+
+    #[test]
+    fn test_output_format_parse() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("YAML".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!("yml".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!(
+            "table".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Table
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::default(), OutputFormat::Table);
+    }
+
+    #[test]
+    fn test_render_json_and_yaml() {
+        let members = sample_members();
+
+        let json = render(&members, OutputFormat::Json).unwrap();
+        let decoded: Vec<MemberInfoWithAddress> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), 2);
+
+        let yaml = render(&members, OutputFormat::Yaml).unwrap();
+        assert!(yaml.contains("domain: example.com"));
+    }
+
+    #[test]
+    fn test_render_table_alignment() {
+        let table = render(&sample_members(), OutputFormat::Table).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("ADDRESS"));
+
+        // DOMAIN column starts at the same offset on every line
+        let offset = lines[0].find("DOMAIN").unwrap();
+        assert_eq!(
+            &lines[1][offset..offset + "example.com".len()],
+            "example.com"
+        );
+        assert_eq!(&lines[2][offset..offset + "a.io".len()], "a.io");
+    }
+}
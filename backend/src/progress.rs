@@ -0,0 +1,221 @@
+//! Progress reporting for long-running operations
+//!
+//! Operations that walk many items (snapshot export, migration checks, ...) accept a
+//! `&dyn ProgressReporter` and emit typed `ProgressEvent`s. The CLI renders them as
+//! progress bars (with the `indicatif` feature a `ProgressBar` is a reporter), and
+//! services can forward them to their own UIs through a channel. Pass `&()` to ignore
+//! progress.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// A progress update from a long-running operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgressEvent {
+    /// The operation started; `total` is the number of items, if known
+    Started {
+        operation: String,
+        total: Option<u64>,
+    },
+    /// More items were processed
+    Advanced {
+        /// Items processed so far
+        done: u64,
+        /// Total number of items, if known
+        total: Option<u64>,
+        /// The item just processed, if the operation names its items
+        current: Option<String>,
+        /// Estimated time remaining, once it can be estimated
+        eta: Option<Duration>,
+    },
+    /// The operation finished after processing `done` items
+    Finished { done: u64 },
+}
+
+/// Receives progress events from long-running operations
+pub trait ProgressReporter: Send + Sync {
+    /// Handle one progress event
+    fn report(&self, event: &ProgressEvent);
+}
+
+/// No-op progress reporter
+impl ProgressReporter for () {
+    fn report(&self, _event: &ProgressEvent) {}
+}
+
+/// Forwards events to a channel, e.g. to relay them to a service's own UI
+impl ProgressReporter for tokio::sync::mpsc::UnboundedSender<ProgressEvent> {
+    fn report(&self, event: &ProgressEvent) {
+        // A closed receiver means nobody is listening any more
+        let _ = self.send(event.clone());
+    }
+}
+
+#[cfg(feature = "indicatif")]
+impl ProgressReporter for indicatif::ProgressBar {
+    fn report(&self, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::Started { operation, total } => {
+                if let Some(total) = total {
+                    self.set_length(*total);
+                }
+                self.set_message(operation.clone());
+            }
+            ProgressEvent::Advanced { done, current, .. } => {
+                self.set_position(*done);
+                if let Some(current) = current {
+                    self.set_message(current.clone());
+                }
+            }
+            ProgressEvent::Finished { .. } => self.finish(),
+        }
+    }
+}
+
+/// Tracks an operation's progress and emits events with ETAs to a reporter
+///
+/// `Progress` is `Sync`, so concurrent workers of one operation can share it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::progress::{Progress, ProgressEvent, ProgressReporter};
+///
+/// struct Printer;
+///
+/// impl ProgressReporter for Printer {
+///     fn report(&self, event: &ProgressEvent) {
+///         println!("{:?}", event);
+///     }
+/// }
+///
+/// let progress = Progress::start(&Printer, "check domains", Some(2));
+/// progress.advance(1, Some("a.com"));
+/// progress.advance(1, Some("b.com"));
+/// progress.finish();
+/// ```
+pub struct Progress<'a> {
+    reporter: &'a dyn ProgressReporter,
+    total: Option<u64>,
+    done: AtomicU64,
+    started: Instant,
+}
+
+impl<'a> Progress<'a> {
+    /// Start tracking an operation and emit `ProgressEvent::Started`
+    pub fn start(reporter: &'a dyn ProgressReporter, operation: &str, total: Option<u64>) -> Self {
+        reporter.report(&ProgressEvent::Started {
+            operation: operation.to_string(),
+            total,
+        });
+        Self {
+            reporter,
+            total,
+            done: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// Record `delta` more processed items and emit `ProgressEvent::Advanced`
+    pub fn advance(&self, delta: u64, current: Option<&str>) {
+        let done = self.done.fetch_add(delta, Ordering::SeqCst) + delta;
+        self.reporter.report(&ProgressEvent::Advanced {
+            done,
+            total: self.total,
+            current: current.map(str::to_string),
+            eta: eta(done, self.total, self.started.elapsed()),
+        });
+    }
+
+    /// Items processed so far
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::SeqCst)
+    }
+
+    /// Emit `ProgressEvent::Finished`
+    pub fn finish(&self) {
+        self.reporter
+            .report(&ProgressEvent::Finished { done: self.done() });
+    }
+}
+
+/// Estimate the remaining time from the average time per item so far
+pub fn eta(done: u64, total: Option<u64>, elapsed: Duration) -> Option<Duration> {
+    let total = total?;
+    if done == 0 {
+        return None;
+    }
+    let remaining = total.saturating_sub(done);
+    Some(elapsed.mul_f64(remaining as f64 / done as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<ProgressEvent>>,
+    }
+
+    impl ProgressReporter for Recorder {
+        fn report(&self, event: &ProgressEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_eta() {
+        let elapsed = Duration::from_secs(10);
+        assert_eq!(eta(5, Some(10), elapsed), Some(Duration::from_secs(10)));
+        assert_eq!(eta(10, Some(10), elapsed), Some(Duration::ZERO));
+        assert_eq!(eta(0, Some(10), elapsed), None);
+        assert_eq!(eta(5, None, elapsed), None);
+    }
+
+    #[test]
+    fn test_progress_events() {
+        let recorder = Recorder::default();
+        let progress = Progress::start(&recorder, "export", Some(3));
+        progress.advance(2, None);
+        progress.advance(1, Some("0x1"));
+        progress.finish();
+
+        let events = recorder.events.into_inner().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[0],
+            ProgressEvent::Started {
+                operation: "export".to_string(),
+                total: Some(3)
+            }
+        );
+        match &events[2] {
+            ProgressEvent::Advanced {
+                done,
+                total,
+                current,
+                ..
+            } => {
+                assert_eq!(*done, 3);
+                assert_eq!(*total, Some(3));
+                assert_eq!(current.as_deref(), Some("0x1"));
+            }
+            other => panic!("Expected Advanced event, got: {:?}", other),
+        }
+        assert_eq!(events[3], ProgressEvent::Finished { done: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_channel_reporter() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        Progress::start(&tx, "replay", None).finish();
+        assert!(matches!(
+            rx.recv().await,
+            Some(ProgressEvent::Started { .. })
+        ));
+        assert_eq!(rx.recv().await, Some(ProgressEvent::Finished { done: 0 }));
+    }
+}
@@ -0,0 +1,480 @@
+//! Webhook relay for registry activity
+//!
+//! The relay watches the transactions touching a registry and forwards each one as a
+//! signed JSON webhook to every subscriber, so third parties can react to joins, fee
+//! changes and canary updates without talking to Sui RPC themselves. It runs as the
+//! worker's `relay` task kind:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "relay"
+//! registry_id = "0x123..."
+//! interval_seconds = 30
+//!
+//! [[tasks.params.subscribers]]
+//! url = "https://hooks.example.com/canary"
+//! secret_env = "EXAMPLE_WEBHOOK_SECRET"
+//! ```
+//!
+//! Each delivery is a `POST` of a `RegistryEvent` with these headers:
+//! - `X-Canary-Delivery`: the transaction digest, stable across retries
+//! - `X-Canary-Timestamp`: Unix seconds at signing time
+//! - `X-Canary-Signature`: `sha256=` followed by the hex HMAC-SHA256 of
+//!   `"{timestamp}.{body}"` keyed by the subscriber's secret (see `verify_signature`)
+//!
+//! Failed deliveries are retried with backoff. The relay's position is stored as a
+//! cursor in the worker's `StateStore`; on first start it begins at the registry's
+//! latest transaction rather than replaying its history.
+
+use crate::client::ratelimit::RateLimiter;
+use crate::client::retry::RetryPolicy;
+use crate::client::USER_AGENT;
+use crate::error::RelayError;
+use crate::state::StateStore;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::rpc_types::{
+    SuiCommand, SuiTransactionBlockDataAPI, SuiTransactionBlockKind, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// Header carrying the delivery ID (the transaction digest)
+pub const DELIVERY_HEADER: &str = "X-Canary-Delivery";
+
+/// Header carrying the signing timestamp in Unix seconds
+pub const TIMESTAMP_HEADER: &str = "X-Canary-Timestamp";
+
+/// Header carrying the `sha256=<hex>` HMAC signature
+pub const SIGNATURE_HEADER: &str = "X-Canary-Signature";
+
+/// Transactions fetched per poll page
+const RELAY_PAGE_SIZE: usize = 50;
+
+/// Timeout for a single webhook request
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Canary contract modules whose calls are reported as the event's function
+const CANARY_MODULES: &[&str] = &["member_registry", "pkg_storage"];
+
+/// A webhook subscriber
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscriber {
+    /// URL the events are posted to
+    pub url: String,
+    /// Shared secret the payloads are signed with
+    pub secret: String,
+}
+
+/// A Move event emitted by a relayed transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayMoveEvent {
+    /// Fully qualified event type
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Event fields as JSON
+    pub fields: serde_json::Value,
+}
+
+/// One transaction touching the registry, as delivered to subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryEvent {
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// Checkpoint the transaction was included in, if known
+    pub checkpoint: Option<u64>,
+    /// Checkpoint timestamp of the transaction (in milliseconds), if known
+    pub timestamp_ms: Option<u64>,
+    /// The transaction sender
+    pub sender: SuiAddress,
+    /// The first canary contract call, as `module::function` (e.g. `member_registry::join_registry`)
+    pub function: Option<String>,
+    /// Move events emitted by the transaction
+    pub events: Vec<RelayMoveEvent>,
+}
+
+/// Outcome of one relay poll
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayReport {
+    /// Transactions relayed
+    pub events: usize,
+    /// Webhooks accepted by subscribers
+    pub delivered: usize,
+    /// Webhooks that still failed after all retries
+    pub failed: usize,
+}
+
+/// Forwards a registry's transactions to webhook subscribers
+pub struct Relay {
+    registry_id: ObjectID,
+    subscribers: Vec<Subscriber>,
+    retry: RetryPolicy,
+    http: reqwest::Client,
+}
+
+impl Relay {
+    /// Create a relay for a registry
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_id` - The Registry object ID to watch
+    /// * `subscribers` - Where to deliver events
+    ///
+    /// # Returns
+    ///
+    /// Returns the relay, or a `RelayError` if the HTTP client cannot be created.
+    pub fn new(registry_id: ObjectID, subscribers: Vec<Subscriber>) -> Result<Self, RelayError> {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .map_err(|e| RelayError::Http(e.to_string()))?;
+        Ok(Self {
+            registry_id,
+            subscribers,
+            retry: RetryPolicy::default(),
+            http,
+        })
+    }
+
+    /// Use a different retry policy for webhook deliveries
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Name of the cursor holding the last relayed transaction digest
+    pub fn cursor_name(&self) -> String {
+        format!("relay:{}", self.registry_id)
+    }
+
+    /// Relay every registry transaction since the last poll
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` for querying
+    /// * `state` - Where the relay's cursor is kept
+    ///
+    /// # Returns
+    ///
+    /// Returns a `RelayReport`, or a `RelayError` if the chain or state store cannot
+    /// be read. Failed deliveries are counted in the report rather than returned, and
+    /// do not hold back the cursor.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::relay::{Relay, Subscriber};
+    /// use canary_sdk::state::MemoryStateStore;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Devnet).await?;
+    /// let relay = Relay::new(
+    ///     ObjectID::from_hex_literal("0x123...")?,
+    ///     vec![Subscriber {
+    ///         url: "https://hooks.example.com/canary".to_string(),
+    ///         secret: "shared-secret".to_string(),
+    ///     }],
+    /// )?;
+    /// let state = MemoryStateStore::new();
+    /// let report = relay.poll(&client, &state).await?;
+    /// println!("Relayed {} events", report.events);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn poll(
+        &self,
+        client: &SuiClient,
+        state: &dyn StateStore,
+    ) -> Result<RelayReport, RelayError> {
+        let cursor_name = self.cursor_name();
+        let mut report = RelayReport::default();
+
+        let mut cursor = match state.get_cursor(&cursor_name).await? {
+            Some(value) => Some(
+                TransactionDigest::from_str(&value)
+                    .map_err(|e| RelayError::Cursor(format!("{}: {}", value, e)))?,
+            ),
+            None => {
+                // Start from the present rather than replaying the registry's history
+                if let Some(latest) = self.latest_transaction(client).await? {
+                    state.set_cursor(&cursor_name, &latest.to_string()).await?;
+                }
+                return Ok(report);
+            }
+        };
+
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::ChangedObject(self.registry_id)),
+            Some(
+                SuiTransactionBlockResponseOptions::new()
+                    .with_input()
+                    .with_events(),
+            ),
+        );
+
+        loop {
+            RateLimiter::global().acquire().await;
+            let page = client
+                .read_api()
+                .query_transaction_blocks(query.clone(), cursor, Some(RELAY_PAGE_SIZE), false)
+                .await
+                .map_err(|e| RelayError::Chain(e.to_string()))?;
+
+            for response in &page.data {
+                let event = registry_event(self.registry_id, response)?;
+                for subscriber in &self.subscribers {
+                    match self.deliver(subscriber, &event).await {
+                        Ok(()) => report.delivered += 1,
+                        Err(e) => {
+                            tracing::error!(
+                                subscriber = %subscriber.url,
+                                digest = %event.digest,
+                                "Webhook delivery failed: {}",
+                                e
+                            );
+                            report.failed += 1;
+                        }
+                    }
+                }
+                report.events += 1;
+                state
+                    .set_cursor(&cursor_name, &response.digest.to_string())
+                    .await?;
+                cursor = Some(response.digest);
+            }
+
+            if !page.has_next_page {
+                return Ok(report);
+            }
+        }
+    }
+
+    /// Deliver one event to a subscriber, retrying failures with backoff
+    ///
+    /// Connection errors, timeouts and `408`, `429` or `5xx` responses are retried; other
+    /// non-success responses are returned immediately.
+    pub async fn deliver(
+        &self,
+        subscriber: &Subscriber,
+        event: &RegistryEvent,
+    ) -> Result<(), RelayError> {
+        let body = serde_json::to_vec(event).map_err(|e| RelayError::Payload(e.to_string()))?;
+
+        let mut attempt = 1;
+        loop {
+            let timestamp = unix_seconds();
+            let signature = sign_payload(&subscriber.secret, timestamp, &body);
+            let result = self
+                .http
+                .post(&subscriber.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(DELIVERY_HEADER, event.digest.to_string())
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, signature)
+                .body(body.clone())
+                .send()
+                .await;
+
+            let (error, retryable) = match result {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    (
+                        RelayError::Rejected {
+                            url: subscriber.url.clone(),
+                            status,
+                        },
+                        is_retryable_status(status),
+                    )
+                }
+                Err(e) => (RelayError::Http(format!("{}: {}", subscriber.url, e)), true),
+            };
+
+            if !retryable || attempt >= self.retry.max_attempts {
+                return Err(error);
+            }
+            let wait = self.retry.backoff(attempt - 1);
+            tracing::warn!(
+                subscriber = %subscriber.url,
+                attempt,
+                "Webhook delivery failed, retrying in {:?}: {}",
+                wait,
+                error
+            );
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+
+    /// Digest of the registry's most recent transaction, if it has any
+    async fn latest_transaction(
+        &self,
+        client: &SuiClient,
+    ) -> Result<Option<TransactionDigest>, RelayError> {
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::ChangedObject(self.registry_id)),
+            None,
+        );
+        let page = client
+            .read_api()
+            .query_transaction_blocks(query, None, Some(1), true)
+            .await
+            .map_err(|e| RelayError::Chain(e.to_string()))?;
+        Ok(page.data.first().map(|response| response.digest))
+    }
+}
+
+/// Sign a webhook body
+///
+/// # Returns
+///
+/// Returns the `X-Canary-Signature` header value: `sha256=` followed by the hex
+/// HMAC-SHA256 of `"{timestamp}.{body}"`.
+pub fn sign_payload(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mac = payload_mac(secret, timestamp, body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256={}", hex)
+}
+
+/// Check a webhook signature, for subscribers written in Rust
+///
+/// The comparison is constant-time. Callers should also reject timestamps too far
+/// from their own clock to prevent replays.
+pub fn verify_signature(secret: &str, timestamp: u64, body: &[u8], signature: &str) -> bool {
+    let Some(hex) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Some(expected) = decode_hex(hex) else {
+        return false;
+    };
+    payload_mac(secret, timestamp, body)
+        .verify_slice(&expected)
+        .is_ok()
+}
+
+fn payload_mac(secret: &str, timestamp: u64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Whether a subscriber's HTTP status is worth retrying
+fn is_retryable_status(status: u16) -> bool {
+    status == 408 || status == 429 || (500..600).contains(&status)
+}
+
+fn unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Build the event delivered for one transaction
+fn registry_event(
+    registry_id: ObjectID,
+    response: &SuiTransactionBlockResponse,
+) -> Result<RegistryEvent, RelayError> {
+    let transaction = response.transaction.as_ref().ok_or_else(|| {
+        RelayError::Chain(format!("Transaction {} has no input", response.digest))
+    })?;
+
+    let function = match transaction.data.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(pt) => {
+            pt.commands.iter().find_map(|command| match command {
+                SuiCommand::MoveCall(call) if CANARY_MODULES.contains(&call.module.as_str()) => {
+                    Some(format!("{}::{}", call.module, call.function))
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+
+    let events = response
+        .events
+        .iter()
+        .flat_map(|events| events.data.iter())
+        .map(|event| RelayMoveEvent {
+            type_: event.type_.to_string(),
+            fields: event.parsed_json.clone(),
+        })
+        .collect();
+
+    Ok(RegistryEvent {
+        registry_id,
+        digest: response.digest,
+        checkpoint: response.checkpoint,
+        timestamp_ms: response.timestamp_ms,
+        sender: *transaction.data.sender(),
+        function,
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_roundtrip() {
+        let body = br#"{"digest":"abc"}"#;
+        let ts = 1_700_000_000;
+        let signature = sign_payload("secret", ts, body);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+
+        assert!(verify_signature("secret", ts, body, &signature));
+        assert!(!verify_signature("other", ts, body, &signature));
+        assert!(!verify_signature("secret", ts + 1, body, &signature));
+        assert!(!verify_signature("secret", ts, b"{}", &signature));
+        assert!(!verify_signature("secret", ts, body, "sha256=zz"));
+        assert!(!verify_signature("secret", ts, body, "md5=00"));
+    }
+
+    #[test]
+    fn test_known_signature() {
+        // HMAC-SHA256(key = "key", message = "1.body")
+        assert_eq!(
+            sign_payload("key", 1, b"body"),
+            "sha256=91b5374b153842ad05b2c4eab9349b8321b14703165bd3fb8b034dfb8be98ae5"
+        );
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(429));
+        assert!(is_retryable_status(503));
+        assert!(is_retryable_status(408));
+        assert!(!is_retryable_status(400));
+        assert!(!is_retryable_status(410));
+    }
+}
@@ -0,0 +1,347 @@
+//! Run schedules
+//!
+//! A `Schedule` decides when the worker runs next: either a fixed interval after the
+//! previous run, or the next time matching a cron expression. Cron expressions use the
+//! standard five fields, evaluated in UTC:
+//!
+//! ```text
+//! ┌──────── minute (0-59)
+//! │ ┌────── hour (0-23)
+//! │ │ ┌──── day of month (1-31)
+//! │ │ │ ┌── month (1-12 or JAN-DEC)
+//! │ │ │ │ ┌ day of week (0-7 or SUN-SAT; 0 and 7 are Sunday)
+//! 0 */6 * * *
+//! ```
+//!
+//! Each field accepts `*`, values, ranges (`1-5`), steps (`*/15`, `0-30/10`), and
+//! comma-separated lists. As in cron, when both day fields are restricted a day matches
+//! if either does. The shorthands `@hourly`, `@daily`, `@weekly`, `@monthly`, and
+//! `@yearly` are accepted too.
+
+use crate::error::ScheduleError;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// When to run next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// A fixed delay after each run
+    Every(Duration),
+    /// The times matching a cron expression
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    /// The next run time after `after`
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if a cron expression never matches (e.g. `0 0 30 2 *`).
+    pub fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        match self {
+            Schedule::Every(interval) => Some(after + *interval),
+            Schedule::Cron(cron) => cron.next_after(after),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Every(interval) => write!(f, "every {} seconds", interval.as_secs()),
+            Schedule::Cron(cron) => write!(f, "cron {}", cron),
+        }
+    }
+}
+
+/// A parsed cron expression
+///
+/// # Example
+///
+/// ```rust
+/// use canary_sdk::schedule::CronSchedule;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let every_six_hours: CronSchedule = "0 */6 * * *".parse().unwrap();
+/// let next = every_six_hours.next_after(UNIX_EPOCH).unwrap();
+/// assert_eq!(next, UNIX_EPOCH + Duration::from_secs(6 * 3600));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Both day fields are restricted, so either may match
+    either_day: bool,
+}
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Give up on expressions that never match after this many search steps
+const MAX_SEARCH_STEPS: usize = 100_000;
+
+impl CronSchedule {
+    /// The next time after `after` matching the expression, at a whole minute
+    pub fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        let seconds = after.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let mut minutes = seconds / 60 + 1;
+
+        for _ in 0..MAX_SEARCH_STEPS {
+            let days = minutes / 1440;
+            let (year, month, day) = civil_from_days(days as i64);
+            if !has(self.months, month) {
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                minutes = days_from_civil(year, month, 1) as u64 * 1440;
+                continue;
+            }
+            if !self.day_matches(day, (days + 4) % 7) {
+                minutes = (days + 1) * 1440;
+                continue;
+            }
+            if !has(self.hours, (minutes % 1440) / 60) {
+                minutes = (minutes / 60 + 1) * 60;
+                continue;
+            }
+            if !has(self.minutes, minutes % 60) {
+                minutes += 1;
+                continue;
+            }
+            return Some(UNIX_EPOCH + Duration::from_secs(minutes * 60));
+        }
+        None
+    }
+
+    fn day_matches(&self, day: u64, weekday: u64) -> bool {
+        let by_day = has(self.days, day);
+        let by_weekday = has(self.weekdays, weekday);
+        if self.either_day {
+            by_day || by_weekday
+        } else {
+            by_day && by_weekday
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(ScheduleError::FieldCount(expression.to_string()));
+        }
+
+        let mut weekdays = parse_field("weekday", fields[4], 0, 7, &WEEKDAYS)?;
+        // 7 is Sunday too
+        if has(weekdays, 7) {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field("minute", fields[0], 0, 59, &[])?,
+            hours: parse_field("hour", fields[1], 0, 23, &[])?,
+            days: parse_field("day", fields[2], 1, 31, &[])?,
+            months: parse_field("month", fields[3], 1, 12, &MONTHS)?,
+            weekdays,
+            either_day: !fields[2].starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn has(mask: u64, value: u64) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse one field into a bitmask of the values it matches
+///
+/// `names` are accepted in place of numbers, the first name standing for `min`.
+fn parse_field(
+    field: &'static str,
+    value: &str,
+    min: u64,
+    max: u64,
+    names: &[&str],
+) -> Result<u64, ScheduleError> {
+    let invalid = |reason: String| ScheduleError::Field {
+        field,
+        value: value.to_string(),
+        reason,
+    };
+    let number = |part: &str| -> Result<u64, ScheduleError> {
+        let n = match names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(part))
+        {
+            Some(index) => min + index as u64,
+            None => part
+                .parse::<u64>()
+                .map_err(|_| invalid(format!("{:?} is not a number", part)))?,
+        };
+        if n < min || n > max {
+            return Err(invalid(format!("{} is outside {}-{}", n, min, max)));
+        }
+        Ok(n)
+    };
+
+    let mut mask = 0;
+    for part in value.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u64 =
+                    step.parse().ok().filter(|&step| step > 0).ok_or_else(|| {
+                        invalid(format!("step {:?} must be a positive number", step))
+                    })?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (number(start)?, number(end)?)
+        } else {
+            let start = number(range)?;
+            // `5/15` means from 5 to the end in steps of 15
+            (start, if step > 1 { max } else { start })
+        };
+        if start > end {
+            return Err(invalid(format!("range {}-{} is reversed", start, end)));
+        }
+        for n in (start..=end).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+/// Civil date (year, month, day) of a day count since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u64, u64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u64;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u64;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Day count since 1970-01-01 of a civil date
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-01 00:00:00 UTC, a Monday
+    const JAN_1_2024: u64 = 1_704_067_200;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn next(expression: &str, after: u64) -> u64 {
+        let cron: CronSchedule = expression.parse().unwrap();
+        cron.next_after(at(after))
+            .unwrap()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_next_after() {
+        // Every six hours, strictly after the given time
+        assert_eq!(next("0 */6 * * *", JAN_1_2024), JAN_1_2024 + 6 * 3600);
+        assert_eq!(next("0 */6 * * *", JAN_1_2024 - 1), JAN_1_2024);
+        // Every 15 minutes
+        assert_eq!(next("*/15 * * * *", JAN_1_2024 + 60), JAN_1_2024 + 900);
+        // Fridays at 09:30
+        assert_eq!(
+            next("30 9 * * FRI", JAN_1_2024),
+            JAN_1_2024 + 4 * 86400 + 9 * 3600 + 1800
+        );
+        // Leap day
+        assert_eq!(
+            next("0 0 29 2 *", JAN_1_2024),
+            days_from_civil(2024, 2, 29) as u64 * 86400
+        );
+        // Sunday as 7
+        assert_eq!(next("0 0 * * 7", JAN_1_2024), JAN_1_2024 + 6 * 86400);
+    }
+
+    #[test]
+    fn test_either_day_field_matches() {
+        // The 15th or any Monday: Monday Jan 8 comes first
+        assert_eq!(next("0 0 15 * 1", JAN_1_2024), JAN_1_2024 + 7 * 86400);
+    }
+
+    #[test]
+    fn test_never_matches() {
+        let cron: CronSchedule = "0 0 31 2 *".parse().unwrap();
+        assert!(cron.next_after(at(JAN_1_2024)).is_none());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "* * * *".parse::<CronSchedule>(),
+            Err(ScheduleError::FieldCount(_))
+        ));
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("5-1 * * * *".parse::<CronSchedule>().is_err());
+        assert!("* * * FOO *".parse::<CronSchedule>().is_err());
+        assert_eq!(next("@daily", JAN_1_2024), JAN_1_2024 + 86400);
+    }
+
+    #[test]
+    fn test_interval_schedule() {
+        let schedule = Schedule::Every(Duration::from_secs(60));
+        assert_eq!(schedule.next_after(at(100)), Some(at(160)));
+    }
+}
@@ -0,0 +1,374 @@
+//! Read-only REST API over a registry
+//!
+//! `serve` exposes canary state as JSON, so frontends can read it without talking to
+//! Sui RPC directly:
+//!
+//! - `GET /registry` - the registry's `RegistryInfo`
+//! - `GET /members` - every member, as `MemberInfoWithAddress`
+//! - `GET /blobs/:domain` - the domain's canary blobs, one per attested package
+//!
+//! - `GET /ws` - a WebSocket pushing a `RegistryUpdate` (as a JSON text frame) for every
+//!   member join and blob store or update
+//!
+//! Failed reads answer `502 Bad Gateway` with `{"error": "..."}`. Run `canary-worker serve`
+//! to serve the configured registry on `server_addr`.
+//!
+//! Updates come from polling the registry's transaction history every few seconds
+//! while any `/ws` client is connected, so they trail the chain by up to one poll.
+//! A client only sees changes made after it connected; it should read the REST routes
+//! once for the current state.
+
+use crate::canary::{
+    latest_registry_transaction, CanaryClient, RegistryCallKind, RegistryTransaction,
+};
+use crate::error::CanaryError;
+use crate::metrics::{handle_http, HttpResponse};
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_sdk::types::digests::TransactionDigest;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How often the registry is polled while `/ws` clients are connected
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Updates queued per `/ws` client before a slow client starts missing them
+const UPDATE_BUFFER: usize = 256;
+
+/// Transactions read per history page when polling
+const UPDATE_PAGE_SIZE: usize = 50;
+
+/// What a `RegistryUpdate` reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryEvent {
+    /// A member joined the registry
+    MemberJoined,
+    /// A canary blob was stored or updated
+    BlobUpdated,
+}
+
+/// A registry change pushed to `/ws` clients
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryUpdate {
+    /// What happened
+    pub event: RegistryEvent,
+    /// The transaction that made the change
+    #[serde(flatten)]
+    pub transaction: RegistryTransaction,
+}
+
+impl RegistryUpdate {
+    /// The update a transaction is pushed as, if it is one `/ws` reports
+    pub fn from_transaction(transaction: RegistryTransaction) -> Option<Self> {
+        if !transaction.success {
+            return None;
+        }
+        let event = match transaction.kind {
+            RegistryCallKind::Join => RegistryEvent::MemberJoined,
+            RegistryCallKind::StoreBlob | RegistryCallKind::UpdateBlob => {
+                RegistryEvent::BlobUpdated
+            }
+            _ => return None,
+        };
+        Some(Self { event, transaction })
+    }
+}
+
+/// A request the API answers
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Registry,
+    Members,
+    Blobs(String),
+}
+
+impl Route {
+    /// Match a `GET` path, ignoring any query string and trailing slash
+    fn parse(path: &str) -> Option<Self> {
+        let path = path.split('?').next().unwrap_or("");
+        match path.trim_end_matches('/') {
+            "/registry" => Some(Route::Registry),
+            "/members" => Some(Route::Members),
+            other => {
+                let domain = other.strip_prefix("/blobs/")?;
+                (!domain.is_empty() && !domain.contains('/'))
+                    .then(|| Route::Blobs(domain.to_lowercase()))
+            }
+        }
+    }
+}
+
+/// Serve the read API and `/ws` updates of `canary`'s registry until the listener fails
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::CanaryClient;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::server::serve;
+/// use std::sync::Arc;
+/// use sui_sdk::types::base_types::ObjectID;
+/// use tokio::net::TcpListener;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Testnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let listener = TcpListener::bind("0.0.0.0:8000").await?;
+/// serve(listener, Arc::new(CanaryClient::new(client, registry_id))).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve(listener: TcpListener, canary: Arc<CanaryClient>) -> std::io::Result<()> {
+    let (updates, _) = broadcast::channel(UPDATE_BUFFER);
+    tokio::spawn(watch_registry(canary.clone(), updates.clone()));
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(handle_connection(stream, canary.clone(), updates.clone()));
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    canary: Arc<CanaryClient>,
+    updates: broadcast::Sender<String>,
+) {
+    let mut head = [0u8; 16];
+    let peeked = match stream.peek(&mut head).await {
+        Ok(peeked) => peeked,
+        Err(e) => {
+            tracing::debug!("Failed to read HTTP request: {}", e);
+            return;
+        }
+    };
+
+    if is_ws_request(&head[..peeked]) {
+        stream_updates(stream, updates.subscribe()).await;
+    } else {
+        handle_http(stream, move |method, path| {
+            let canary = canary.clone();
+            async move {
+                match (method.as_str(), Route::parse(&path)) {
+                    ("GET", Some(route)) => respond(&canary, route).await,
+                    _ => HttpResponse::not_found(&method),
+                }
+            }
+        })
+        .await;
+    }
+}
+
+/// Whether a request starts with `GET /ws` (with an optional query or trailing slash)
+fn is_ws_request(head: &[u8]) -> bool {
+    head.strip_prefix(b"GET /ws")
+        .is_some_and(|rest| matches!(rest.first(), Some(b' ' | b'?' | b'/')))
+}
+
+/// Complete the WebSocket handshake and forward updates until the client goes away
+async fn stream_updates(stream: TcpStream, mut updates: broadcast::Receiver<String>) {
+    let mut socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::debug!("WebSocket handshake failed: {}", e);
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(text) => {
+                    if let Err(e) = socket.send(Message::text(text)).await {
+                        tracing::debug!("Failed to send registry update: {}", e);
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::debug!("WebSocket client missed {} registry updates", missed);
+                }
+                Err(RecvError::Closed) => return,
+            },
+            // Pings are answered by tungstenite; anything else from the client is ignored
+            incoming = socket.next() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Poll the registry's history and broadcast its updates while anyone is listening
+async fn watch_registry(canary: Arc<CanaryClient>, updates: broadcast::Sender<String>) {
+    let mut cursor = None;
+    let mut ticker = tokio::time::interval(UPDATE_POLL_INTERVAL);
+    loop {
+        ticker.tick().await;
+        if updates.receiver_count() == 0 {
+            // Start from the chain head again once a client connects
+            cursor = None;
+            continue;
+        }
+        if let Err(e) = poll_updates(&canary, &updates, &mut cursor).await {
+            tracing::warn!("Failed to poll registry updates: {}", e);
+        }
+    }
+}
+
+/// Broadcast the updates made after `cursor` and advance it
+///
+/// Without a cursor, this only records the latest transaction, so changes made before
+/// the first client connected are not pushed.
+async fn poll_updates(
+    canary: &CanaryClient,
+    updates: &broadcast::Sender<String>,
+    cursor: &mut Option<TransactionDigest>,
+) -> Result<(), CanaryError> {
+    if cursor.is_none() {
+        *cursor = latest_registry_transaction(canary.client(), canary.registry_id()).await?;
+        return Ok(());
+    }
+
+    loop {
+        let page = canary.transactions(*cursor, Some(UPDATE_PAGE_SIZE)).await?;
+        for update in page
+            .transactions
+            .into_iter()
+            .filter_map(RegistryUpdate::from_transaction)
+        {
+            match serde_json::to_string(&update) {
+                // Fails only when every client has disconnected
+                Ok(text) => {
+                    let _ = updates.send(text);
+                }
+                Err(e) => tracing::warn!("Failed to serialize registry update: {}", e),
+            }
+        }
+        if page.next_cursor.is_some() {
+            *cursor = page.next_cursor;
+        }
+        if !page.has_next_page {
+            return Ok(());
+        }
+    }
+}
+
+async fn respond(canary: &CanaryClient, route: Route) -> HttpResponse {
+    match route {
+        Route::Registry => json_response(canary.registry().await),
+        Route::Members => json_response(canary.members().await),
+        Route::Blobs(domain) => json_response(canary.blobs().await.map(|blobs| {
+            blobs
+                .into_iter()
+                .filter(|blob| blob.domain.eq_ignore_ascii_case(&domain))
+                .collect::<Vec<_>>()
+        })),
+    }
+}
+
+fn json_response<T: Serialize, E: std::fmt::Display>(result: Result<T, E>) -> HttpResponse {
+    let (status, body) = match result.map(|value| serde_json::to_string(&value)) {
+        Ok(Ok(body)) => ("200 OK", body),
+        Ok(Err(e)) => ("500 Internal Server Error", error_body(e)),
+        Err(e) => ("502 Bad Gateway", error_body(e)),
+    };
+    HttpResponse {
+        status,
+        content_type: "application/json",
+        body,
+    }
+}
+
+fn error_body(error: impl std::fmt::Display) -> String {
+    serde_json::json!({ "error": error.to_string() }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        assert_eq!(Route::parse("/registry"), Some(Route::Registry));
+        assert_eq!(Route::parse("/members/"), Some(Route::Members));
+        assert_eq!(Route::parse("/members?page=2"), Some(Route::Members));
+        assert_eq!(
+            Route::parse("/blobs/Example.com"),
+            Some(Route::Blobs("example.com".to_string()))
+        );
+        assert_eq!(Route::parse("/blobs/"), None);
+        assert_eq!(Route::parse("/blobs/a/b"), None);
+        assert_eq!(Route::parse("/"), None);
+    }
+
+    #[test]
+    fn test_ws_request() {
+        assert!(is_ws_request(b"GET /ws HTTP/1.1"));
+        assert!(is_ws_request(b"GET /ws/ HTTP/1.1"));
+        assert!(is_ws_request(b"GET /ws?token=1 "));
+        assert!(!is_ws_request(b"GET /wsx HTTP/1.1"));
+        assert!(!is_ws_request(b"POST /ws HTTP/1.1"));
+        assert!(!is_ws_request(b"GET /members HTTP"));
+        assert!(!is_ws_request(b"GET /w"));
+    }
+
+    fn transaction(kind: RegistryCallKind, success: bool) -> RegistryTransaction {
+        RegistryTransaction {
+            digest: TransactionDigest::random(),
+            checkpoint: Some(1),
+            timestamp_ms: Some(1_700_000_000_000),
+            sender: sui_sdk::types::base_types::SuiAddress::random_for_testing_only(),
+            kind,
+            member: None,
+            function: None,
+            success,
+        }
+    }
+
+    #[test]
+    fn test_registry_update_filter() {
+        let joined = RegistryUpdate::from_transaction(transaction(RegistryCallKind::Join, true));
+        assert_eq!(joined.unwrap().event, RegistryEvent::MemberJoined);
+        for kind in [RegistryCallKind::StoreBlob, RegistryCallKind::UpdateBlob] {
+            let update = RegistryUpdate::from_transaction(transaction(kind, true));
+            assert_eq!(update.unwrap().event, RegistryEvent::BlobUpdated);
+        }
+
+        assert!(
+            RegistryUpdate::from_transaction(transaction(RegistryCallKind::Join, false)).is_none()
+        );
+        assert!(
+            RegistryUpdate::from_transaction(transaction(RegistryCallKind::Leave, true)).is_none()
+        );
+        assert!(
+            RegistryUpdate::from_transaction(transaction(RegistryCallKind::DeleteBlob, true))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_registry_update_serialization() {
+        let update =
+            RegistryUpdate::from_transaction(transaction(RegistryCallKind::UpdateBlob, true))
+                .unwrap();
+        let json = serde_json::to_value(&update).unwrap();
+        assert_eq!(json["event"], "blob_updated");
+        assert_eq!(json["kind"], "update_blob");
+        assert_eq!(json["digest"], update.transaction.digest.to_string());
+    }
+
+    #[test]
+    fn test_json_response() {
+        let ok = json_response::<_, String>(Ok(vec![1, 2]));
+        assert_eq!(ok.status, "200 OK");
+        assert_eq!(ok.body, "[1,2]");
+
+        let failed = json_response::<Vec<u8>, _>(Err("rpc down"));
+        assert_eq!(failed.status, "502 Bad Gateway");
+        assert_eq!(failed.body, r#"{"error":"rpc down"}"#);
+    }
+}
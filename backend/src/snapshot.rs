@@ -0,0 +1,491 @@
+//! Registry snapshots
+//!
+//! This module exports a point-in-time copy of a registry's member list. Member
+//! entries live as dynamic fields of the registry's `members` table; the field IDs are
+//! listed page by page and the field objects are then fetched concurrently, reporting
+//! progress (see `progress`) as each batch completes.
+//!
+//! `diff_members` compares two member lists, and `track_members` keeps the last list
+//! seen in a `StateStore` so a worker can report what changed between its runs.
+
+use crate::canary::MemberInfoWithAddress;
+use crate::canary::{get_member_fields, get_object_fields, json_table_id, json_u64};
+use crate::client::ratelimit::RateLimiter;
+use crate::compat::{Artifact, SDK_VERSION};
+use crate::error::{CanaryError, StateError};
+use crate::progress::{Progress, ProgressReporter};
+use crate::state::StateStore;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::SuiClient;
+
+/// A point-in-time copy of a registry's state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    /// Schema version of this artifact (see `compat`)
+    pub schema_version: u32,
+    /// Version of the SDK that produced this artifact
+    pub sdk_version: String,
+    /// The Registry object ID
+    pub registry_id: ObjectID,
+    /// When the snapshot was taken (Unix time in milliseconds)
+    pub taken_at_ms: u64,
+    /// The registry's `member_count` at snapshot time
+    pub member_count: u64,
+    /// All members, sorted by join time
+    pub members: Vec<MemberInfoWithAddress>,
+}
+
+/// Options controlling how a snapshot is fetched
+#[derive(Debug, Clone)]
+pub struct SnapshotOptions {
+    /// Number of dynamic fields requested per page (max 50 for most fullnodes)
+    pub page_size: usize,
+    /// Number of object batches fetched concurrently
+    pub concurrency: usize,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 50,
+            concurrency: 8,
+        }
+    }
+}
+
+/// Export a snapshot of a registry's members
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `registry_id` - The Registry object ID
+/// * `options` - Page size and concurrency settings
+/// * `reporter` - Progress reporter (use `&()` to ignore progress)
+///
+/// # Returns
+///
+/// Returns a `RegistrySnapshot`, or a `CanaryError` if any query fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::snapshot::{export_snapshot, SnapshotOptions};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+/// let snapshot = export_snapshot(&client, registry_id, &SnapshotOptions::default(), &()).await?;
+/// println!("{}", serde_json::to_string_pretty(&snapshot)?);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn export_snapshot(
+    client: &SuiClient,
+    registry_id: ObjectID,
+    options: &SnapshotOptions,
+    reporter: &dyn ProgressReporter,
+) -> Result<RegistrySnapshot, CanaryError> {
+    let taken_at_ms = now_ms();
+
+    let registry_fields = get_object_fields(client, registry_id).await?;
+    let member_count = json_u64(&registry_fields, "member_count")?;
+    let members_table = json_table_id(&registry_fields, "members")?;
+    let progress = Progress::start(reporter, "export snapshot", Some(member_count));
+
+    // Listing dynamic fields is cursor-chained, so collect the field IDs sequentially
+    let mut field_ids = Vec::with_capacity(member_count as usize);
+    let mut cursor = None;
+    loop {
+        RateLimiter::global().acquire().await;
+        let page = client
+            .read_api()
+            .get_dynamic_fields(members_table, cursor, Some(options.page_size))
+            .await
+            .map_err(|e| CanaryError::Registry(format!("Failed to list members: {}", e)))?;
+        field_ids.extend(page.data.iter().map(|field| field.object_id));
+
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+
+    let members = fetch_members(&field_ids, options, &progress, |batch| {
+        get_member_fields(client, batch)
+    })
+    .await?;
+    progress.finish();
+
+    Ok(RegistrySnapshot {
+        schema_version: RegistrySnapshot::SCHEMA_VERSION,
+        sdk_version: SDK_VERSION.to_string(),
+        registry_id,
+        taken_at_ms,
+        member_count,
+        members,
+    })
+}
+
+/// Fetch members in concurrent batches of `page_size`, sorted by join time
+///
+/// `progress` advances as each batch completes.
+async fn fetch_members<F, Fut>(
+    field_ids: &[ObjectID],
+    options: &SnapshotOptions,
+    progress: &Progress<'_>,
+    fetch: F,
+) -> Result<Vec<MemberInfoWithAddress>, CanaryError>
+where
+    F: Fn(Vec<ObjectID>) -> Fut,
+    Fut: Future<Output = Result<Vec<MemberInfoWithAddress>, CanaryError>>,
+{
+    let batches: Vec<Vec<ObjectID>> = field_ids
+        .chunks(options.page_size.max(1))
+        .map(|chunk| chunk.to_vec())
+        .collect();
+
+    let fetch = &fetch;
+    let pages: Vec<Vec<MemberInfoWithAddress>> = stream::iter(batches)
+        .map(|batch| async move {
+            let members = fetch(batch).await?;
+            progress.advance(members.len() as u64, None);
+            Ok::<_, CanaryError>(members)
+        })
+        .buffer_unordered(options.concurrency.max(1))
+        .try_collect()
+        .await?;
+
+    let mut members: Vec<MemberInfoWithAddress> = pages.into_iter().flatten().collect();
+    members.sort_by(|a, b| {
+        a.joined_at
+            .cmp(&b.joined_at)
+            .then_with(|| a.member.cmp(&b.member))
+    });
+    Ok(members)
+}
+
+/// A change to a registry's member list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum MemberChange {
+    /// A member joined
+    Joined { member: SuiAddress, domain: String },
+    /// A member's domain changed
+    DomainChanged {
+        member: SuiAddress,
+        from: String,
+        to: String,
+    },
+    /// A member left or was removed
+    Removed { member: SuiAddress, domain: String },
+}
+
+impl std::fmt::Display for MemberChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberChange::Joined { member, domain } => {
+                write!(f, "+ {} joined ({})", member, domain)
+            }
+            MemberChange::DomainChanged { member, from, to } => {
+                write!(f, "~ {} changed domain {} -> {}", member, from, to)
+            }
+            MemberChange::Removed { member, domain } => {
+                write!(f, "- {} removed ({})", member, domain)
+            }
+        }
+    }
+}
+
+/// Compare two member lists
+///
+/// # Returns
+///
+/// Returns the joins and domain changes in the order of `current`, followed by the
+/// removals in the order of `previous`.
+pub fn diff_members(
+    previous: &[MemberInfoWithAddress],
+    current: &[MemberInfoWithAddress],
+) -> Vec<MemberChange> {
+    let before: BTreeMap<SuiAddress, &str> = previous
+        .iter()
+        .map(|member| (member.member, member.domain.as_str()))
+        .collect();
+    let after: BTreeMap<SuiAddress, &str> = current
+        .iter()
+        .map(|member| (member.member, member.domain.as_str()))
+        .collect();
+
+    let mut changes = Vec::new();
+    for member in current {
+        match before.get(&member.member) {
+            None => changes.push(MemberChange::Joined {
+                member: member.member,
+                domain: member.domain.clone(),
+            }),
+            Some(&domain) if domain != member.domain => changes.push(MemberChange::DomainChanged {
+                member: member.member,
+                from: domain.to_string(),
+                to: member.domain.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for member in previous {
+        if !after.contains_key(&member.member) {
+            changes.push(MemberChange::Removed {
+                member: member.member,
+                domain: member.domain.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// Name of the state entry holding the last member list seen for a registry
+pub fn tracked_members_key(registry_id: ObjectID) -> String {
+    format!("members:{}", registry_id)
+}
+
+/// The member list stored by the last `track_members` call for a registry, or `None`
+/// if it was never called
+pub async fn tracked_members(
+    state: &dyn StateStore,
+    registry_id: ObjectID,
+) -> Result<Option<Vec<MemberInfoWithAddress>>, StateError> {
+    match state.get_cursor(&tracked_members_key(registry_id)).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| StateError::Serialization(e.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Diff a registry's members against the list stored by the previous call, then store
+/// `current` for the next one
+///
+/// # Arguments
+///
+/// * `state` - Where the member list is kept between calls
+/// * `registry_id` - The Registry object ID the members belong to
+/// * `current` - The registry's current members
+///
+/// # Returns
+///
+/// Returns the changes since the previous call, or `None` on the first call for this
+/// registry, when there is nothing to compare against yet.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_all_members;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::snapshot::track_members;
+/// use canary_sdk::state::FileStateStore;
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let state = FileStateStore::open("worker-state.json")?;
+/// let registry_id = ObjectID::from_hex_literal("0x123...")?;
+///
+/// let members = query_all_members(&client, registry_id).await?;
+/// for change in track_members(&state, registry_id, &members).await?.unwrap_or_default() {
+///     println!("{}", change);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn track_members(
+    state: &dyn StateStore,
+    registry_id: ObjectID,
+    current: &[MemberInfoWithAddress],
+) -> Result<Option<Vec<MemberChange>>, StateError> {
+    let previous = tracked_members(state, registry_id).await?;
+
+    let json =
+        serde_json::to_string(current).map_err(|e| StateError::Serialization(e.to_string()))?;
+    state
+        .set_cursor(&tracked_members_key(registry_id), &json)
+        .await?;
+
+    Ok(previous.map(|previous| diff_members(&previous, current)))
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::ProgressEvent;
+
+    fn member(byte: u8, domain: &str) -> MemberInfoWithAddress {
+        MemberInfoWithAddress {
+            member: SuiAddress::from(ObjectID::from_single_byte(byte)),
+            domain: domain.to_string(),
+            joined_at: byte as u64,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_members() {
+        let previous = vec![member(1, "a.com"), member(2, "b.com"), member(3, "c.com")];
+        let current = vec![
+            member(1, "a.com"),
+            member(3, "new-c.com"),
+            member(4, "d.com"),
+        ];
+
+        let changes = diff_members(&previous, &current);
+        assert_eq!(
+            changes,
+            vec![
+                MemberChange::DomainChanged {
+                    member: current[1].member,
+                    from: "c.com".to_string(),
+                    to: "new-c.com".to_string(),
+                },
+                MemberChange::Joined {
+                    member: current[2].member,
+                    domain: "d.com".to_string(),
+                },
+                MemberChange::Removed {
+                    member: previous[1].member,
+                    domain: "b.com".to_string(),
+                },
+            ]
+        );
+        assert!(diff_members(&current, &current).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_track_members() {
+        let state = crate::state::MemoryStateStore::new();
+        let registry_id = ObjectID::from_single_byte(9);
+
+        let first = vec![member(1, "a.com")];
+        assert_eq!(
+            track_members(&state, registry_id, &first).await.unwrap(),
+            None
+        );
+
+        let second = vec![member(1, "a.com"), member(2, "b.com")];
+        let changes = track_members(&state, registry_id, &second)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0], MemberChange::Joined { .. }));
+    }
+
+    #[test]
+    fn test_default_options() {
+        let options = SnapshotOptions::default();
+        assert_eq!(options.page_size, 50);
+        assert!(options.concurrency > 1);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_json() {
+        let snapshot = RegistrySnapshot {
+            schema_version: RegistrySnapshot::SCHEMA_VERSION,
+            sdk_version: SDK_VERSION.to_string(),
+            registry_id: ObjectID::from_hex_literal("0x123").unwrap(),
+            taken_at_ms: 1_700_000_000_000,
+            member_count: 0,
+            members: vec![],
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let decoded: RegistrySnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.registry_id, snapshot.registry_id);
+        assert_eq!(decoded.taken_at_ms, snapshot.taken_at_ms);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_members_reports_progress() {
+        let field_ids: Vec<ObjectID> = (1..=5).map(ObjectID::from_single_byte).collect();
+        let options = SnapshotOptions {
+            page_size: 2,
+            concurrency: 2,
+        };
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let progress = Progress::start(&sender, "export snapshot", Some(5));
+
+        // Later IDs joined earlier, so sorting by join time reverses the batches
+        let members = fetch_members(&field_ids, &options, &progress, |batch| async move {
+            Ok(batch
+                .into_iter()
+                .map(|id| {
+                    let byte = id.into_bytes()[31];
+                    MemberInfoWithAddress {
+                        joined_at: 100 - byte as u64,
+                        ..member(byte, "example.com")
+                    }
+                })
+                .collect())
+        })
+        .await
+        .unwrap();
+        progress.finish();
+        drop(progress);
+        drop(sender);
+
+        let expected: Vec<SuiAddress> = field_ids
+            .iter()
+            .rev()
+            .map(|id| SuiAddress::from(*id))
+            .collect();
+        let actual: Vec<SuiAddress> = members.iter().map(|m| m.member).collect();
+        assert_eq!(actual, expected);
+
+        let mut events = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            events.push(event);
+        }
+        // Started, one Advanced per batch of at most two, Finished
+        assert_eq!(events.len(), 5);
+        assert!(matches!(
+            events[0],
+            ProgressEvent::Started { total: Some(5), .. }
+        ));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| matches!(e, ProgressEvent::Advanced { .. }))
+                .count(),
+            3
+        );
+        assert!(matches!(events[4], ProgressEvent::Finished { done: 5 }));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires network connection and a deployed registry
+    async fn test_export_snapshot() {
+        let client = crate::client::create_sui_client(crate::client::Network::Devnet)
+            .await
+            .unwrap();
+        let registry_id = ObjectID::from_hex_literal("0x1").unwrap();
+        let snapshot = export_snapshot(&client, registry_id, &SnapshotOptions::default(), &())
+            .await
+            .expect("export_snapshot failed");
+
+        assert_eq!(snapshot.registry_id, registry_id);
+        assert_eq!(snapshot.schema_version, RegistrySnapshot::SCHEMA_VERSION);
+        assert_eq!(snapshot.members.len() as u64, snapshot.member_count);
+        assert!(snapshot
+            .members
+            .windows(2)
+            .all(|pair| pair[0].joined_at <= pair[1].joined_at));
+    }
+}
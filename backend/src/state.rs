@@ -0,0 +1,546 @@
+//! Persistent worker state
+//!
+//! This module abstracts the state a worker needs to keep between iterations behind
+//! the `StateStore` trait:
+//! - Cursors (e.g. last processed checkpoint or pagination cursor)
+//! - Dedup sets (e.g. already-notified transaction digests)
+//! - Leader locks (so only one of several replicas runs a task)
+//!
+//! `MemoryStateStore` is suitable for tests and for single-instance deployments that can
+//! start over after a restart; `FileStateStore` keeps a single instance's state in a
+//! JSON file across restarts.
+//! `RedisStateStore` (feature `redis`) lets horizontally scaled deployments share state.
+
+use crate::error::StateError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Storage backend for worker state shared across iterations and replicas
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Get the value of a named cursor, or `None` if it was never set
+    async fn get_cursor(&self, name: &str) -> Result<Option<String>, StateError>;
+
+    /// Set the value of a named cursor
+    async fn set_cursor(&self, name: &str, value: &str) -> Result<(), StateError>;
+
+    /// Add an item to a dedup set
+    ///
+    /// Returns `true` if the item was newly added, `false` if it was already present.
+    async fn mark_seen(&self, set: &str, item: &str) -> Result<bool, StateError>;
+
+    /// Check whether an item is present in a dedup set
+    async fn is_seen(&self, set: &str, item: &str) -> Result<bool, StateError>;
+
+    /// Try to acquire a named lock for `owner`, expiring after `ttl`
+    ///
+    /// Returns `true` if the lock was acquired (or is already held by `owner`, in which
+    /// case its expiry is refreshed), `false` if another owner holds it.
+    async fn try_acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<bool, StateError>;
+
+    /// Release a named lock if it is held by `owner`
+    async fn release_lock(&self, name: &str, owner: &str) -> Result<(), StateError>;
+
+    /// Make every change so far durable, e.g. before the process exits
+    ///
+    /// Stores with nothing buffered keep the default, which does nothing.
+    async fn flush(&self) -> Result<(), StateError> {
+        Ok(())
+    }
+}
+
+/// In-memory `StateStore` for single-instance deployments and tests
+///
+/// State is lost when the process exits.
+#[derive(Default)]
+pub struct MemoryStateStore {
+    inner: Mutex<MemoryState>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    cursors: HashMap<String, String>,
+    sets: HashMap<String, HashSet<String>>,
+    locks: HashMap<String, (String, Instant)>,
+}
+
+impl MemoryStateStore {
+    /// Create an empty in-memory state store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, MemoryState>, StateError> {
+        self.inner
+            .lock()
+            .map_err(|e| StateError::Backend(format!("State lock poisoned: {}", e)))
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    async fn get_cursor(&self, name: &str) -> Result<Option<String>, StateError> {
+        Ok(self.lock()?.cursors.get(name).cloned())
+    }
+
+    async fn set_cursor(&self, name: &str, value: &str) -> Result<(), StateError> {
+        self.lock()?
+            .cursors
+            .insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn mark_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+        Ok(self
+            .lock()?
+            .sets
+            .entry(set.to_string())
+            .or_default()
+            .insert(item.to_string()))
+    }
+
+    async fn is_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+        Ok(self
+            .lock()?
+            .sets
+            .get(set)
+            .map(|items| items.contains(item))
+            .unwrap_or(false))
+    }
+
+    async fn try_acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<bool, StateError> {
+        let mut state = self.lock()?;
+        let now = Instant::now();
+
+        if let Some((holder, expires_at)) = state.locks.get(name) {
+            if holder != owner && *expires_at > now {
+                return Ok(false);
+            }
+        }
+
+        state
+            .locks
+            .insert(name.to_string(), (owner.to_string(), now + ttl));
+        Ok(true)
+    }
+
+    async fn release_lock(&self, name: &str, owner: &str) -> Result<(), StateError> {
+        let mut state = self.lock()?;
+        if matches!(state.locks.get(name), Some((holder, _)) if holder == owner) {
+            state.locks.remove(name);
+        }
+        Ok(())
+    }
+}
+
+/// `StateStore` persisted to a JSON file
+///
+/// Cursors and dedup sets are written to the file on every change, so they survive
+/// restarts. Locks are kept in memory only: a file serves a single instance.
+pub struct FileStateStore {
+    path: PathBuf,
+    persisted: Mutex<PersistedState>,
+    locks: MemoryStateStore,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct PersistedState {
+    #[serde(default)]
+    cursors: HashMap<String, String>,
+    #[serde(default)]
+    sets: HashMap<String, HashSet<String>>,
+}
+
+impl FileStateStore {
+    /// Open the state file at `path`, starting empty if it does not exist yet
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::state::{FileStateStore, StateStore};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let store = FileStateStore::open("/app/state/worker.json")?;
+    /// store.set_cursor("relay", "abc").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StateError> {
+        let path = path.into();
+        let persisted = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| StateError::Serialization(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => PersistedState::default(),
+            Err(e) => {
+                return Err(StateError::Backend(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+        Ok(Self {
+            path,
+            persisted: Mutex::new(persisted),
+            locks: MemoryStateStore::new(),
+        })
+    }
+
+    /// Path of the state file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Apply `change` and write the state back if it reports a modification
+    fn update<T>(
+        &self,
+        change: impl FnOnce(&mut PersistedState) -> (T, bool),
+    ) -> Result<T, StateError> {
+        let mut state = self
+            .persisted
+            .lock()
+            .map_err(|e| StateError::Backend(format!("State lock poisoned: {}", e)))?;
+        let (result, modified) = change(&mut state);
+        if modified {
+            let contents = serde_json::to_vec_pretty(&*state)
+                .map_err(|e| StateError::Serialization(e.to_string()))?;
+            // Write then rename, so a crash never leaves a truncated file
+            let temp = self.path.with_extension("tmp");
+            std::fs::write(&temp, contents)
+                .and_then(|_| std::fs::rename(&temp, &self.path))
+                .map_err(|e| {
+                    StateError::Backend(format!("Failed to write {}: {}", self.path.display(), e))
+                })?;
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl StateStore for FileStateStore {
+    async fn get_cursor(&self, name: &str) -> Result<Option<String>, StateError> {
+        self.update(|state| (state.cursors.get(name).cloned(), false))
+    }
+
+    async fn set_cursor(&self, name: &str, value: &str) -> Result<(), StateError> {
+        self.update(|state| {
+            let previous = state.cursors.insert(name.to_string(), value.to_string());
+            ((), previous.as_deref() != Some(value))
+        })
+    }
+
+    async fn mark_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+        self.update(|state| {
+            let added = state
+                .sets
+                .entry(set.to_string())
+                .or_default()
+                .insert(item.to_string());
+            (added, added)
+        })
+    }
+
+    async fn is_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+        self.update(|state| {
+            let seen = state
+                .sets
+                .get(set)
+                .map(|items| items.contains(item))
+                .unwrap_or(false);
+            (seen, false)
+        })
+    }
+
+    async fn try_acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<bool, StateError> {
+        self.locks.try_acquire_lock(name, owner, ttl).await
+    }
+
+    async fn release_lock(&self, name: &str, owner: &str) -> Result<(), StateError> {
+        self.locks.release_lock(name, owner).await
+    }
+
+    /// Sync the state file to disk, so the last write survives a power loss
+    async fn flush(&self) -> Result<(), StateError> {
+        // Hold the state lock so no write replaces the file while it is synced
+        let _state = self
+            .persisted
+            .lock()
+            .map_err(|e| StateError::Backend(format!("State lock poisoned: {}", e)))?;
+        match std::fs::File::open(&self.path).and_then(|file| file.sync_all()) {
+            Ok(()) => Ok(()),
+            // Nothing was written yet
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StateError::Backend(format!(
+                "Failed to sync {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+pub use self::redis_store::RedisStateStore;
+
+#[cfg(feature = "redis")]
+mod redis_store {
+    use super::StateStore;
+    use crate::error::StateError;
+    use async_trait::async_trait;
+    use redis::aio::MultiplexedConnection;
+    use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+    use std::time::Duration;
+
+    /// Lua script deleting a lock key only if it is still held by the caller
+    const RELEASE_LOCK_SCRIPT: &str = r#"
+        if redis.call("GET", KEYS[1]) == ARGV[1] then
+            return redis.call("DEL", KEYS[1])
+        else
+            return 0
+        end
+    "#;
+
+    /// Lua script refreshing a lock's expiry if it is still held by the caller
+    const REFRESH_LOCK_SCRIPT: &str = r#"
+        if redis.call("GET", KEYS[1]) == ARGV[1] then
+            return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+        else
+            return 0
+        end
+    "#;
+
+    /// Redis-backed `StateStore` shared by horizontally scaled workers
+    ///
+    /// All keys are namespaced under a prefix (default `canary`) so several deployments
+    /// can share one Redis instance:
+    /// - `<prefix>:cursor:<name>` - string
+    /// - `<prefix>:seen:<set>` - set
+    /// - `<prefix>:lock:<name>` - string holding the owner, with a TTL
+    #[derive(Clone)]
+    pub struct RedisStateStore {
+        connection: MultiplexedConnection,
+        prefix: String,
+    }
+
+    impl RedisStateStore {
+        /// Connect to Redis at the given URL (e.g. `redis://127.0.0.1:6379`)
+        ///
+        /// # Arguments
+        ///
+        /// * `url` - The Redis connection URL
+        /// * `prefix` - Key namespace for this deployment
+        ///
+        /// # Returns
+        ///
+        /// Returns a connected `RedisStateStore`, or a `StateError` if the connection fails.
+        pub async fn connect(url: &str, prefix: &str) -> Result<Self, StateError> {
+            let client = redis::Client::open(url)
+                .map_err(|e| StateError::Backend(format!("Invalid Redis URL: {}", e)))?;
+            let connection = client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| StateError::Backend(format!("Failed to connect to Redis: {}", e)))?;
+
+            Ok(Self {
+                connection,
+                prefix: prefix.to_string(),
+            })
+        }
+
+        fn key(&self, kind: &str, name: &str) -> String {
+            format!("{}:{}:{}", self.prefix, kind, name)
+        }
+    }
+
+    fn backend_error(e: redis::RedisError) -> StateError {
+        StateError::Backend(e.to_string())
+    }
+
+    #[async_trait]
+    impl StateStore for RedisStateStore {
+        async fn get_cursor(&self, name: &str) -> Result<Option<String>, StateError> {
+            let mut conn = self.connection.clone();
+            conn.get::<_, Option<String>>(self.key("cursor", name))
+                .await
+                .map_err(backend_error)
+        }
+
+        async fn set_cursor(&self, name: &str, value: &str) -> Result<(), StateError> {
+            let mut conn = self.connection.clone();
+            conn.set::<_, _, ()>(self.key("cursor", name), value)
+                .await
+                .map_err(backend_error)
+        }
+
+        async fn mark_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+            let mut conn = self.connection.clone();
+            let added: i64 = conn
+                .sadd(self.key("seen", set), item)
+                .await
+                .map_err(backend_error)?;
+            Ok(added == 1)
+        }
+
+        async fn is_seen(&self, set: &str, item: &str) -> Result<bool, StateError> {
+            let mut conn = self.connection.clone();
+            conn.sismember::<_, _, bool>(self.key("seen", set), item)
+                .await
+                .map_err(backend_error)
+        }
+
+        async fn try_acquire_lock(
+            &self,
+            name: &str,
+            owner: &str,
+            ttl: Duration,
+        ) -> Result<bool, StateError> {
+            let mut conn = self.connection.clone();
+            let key = self.key("lock", name);
+            let ttl_ms = ttl.as_millis() as u64;
+
+            let options = SetOptions::default()
+                .conditional_set(ExistenceCheck::NX)
+                .with_expiration(SetExpiry::PX(ttl_ms));
+            let acquired: Option<String> = conn
+                .set_options(&key, owner, options)
+                .await
+                .map_err(backend_error)?;
+            if acquired.is_some() {
+                return Ok(true);
+            }
+
+            // Already held; refresh it if we are the holder
+            let refreshed: i64 = redis::Script::new(REFRESH_LOCK_SCRIPT)
+                .key(&key)
+                .arg(owner)
+                .arg(ttl_ms)
+                .invoke_async(&mut conn)
+                .await
+                .map_err(backend_error)?;
+            Ok(refreshed == 1)
+        }
+
+        async fn release_lock(&self, name: &str, owner: &str) -> Result<(), StateError> {
+            let mut conn = self.connection.clone();
+            let _: i64 = redis::Script::new(RELEASE_LOCK_SCRIPT)
+                .key(self.key("lock", name))
+                .arg(owner)
+                .invoke_async(&mut conn)
+                .await
+                .map_err(backend_error)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_cursor_roundtrip() {
+        let store = MemoryStateStore::new();
+        assert_eq!(store.get_cursor("members").await.unwrap(), None);
+
+        store.set_cursor("members", "42").await.unwrap();
+        assert_eq!(
+            store.get_cursor("members").await.unwrap(),
+            Some("42".to_string())
+        );
+
+        store.set_cursor("members", "43").await.unwrap();
+        assert_eq!(
+            store.get_cursor("members").await.unwrap(),
+            Some("43".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_dedup_set() {
+        let store = MemoryStateStore::new();
+        assert!(!store.is_seen("digests", "abc").await.unwrap());
+
+        assert!(store.mark_seen("digests", "abc").await.unwrap());
+        assert!(!store.mark_seen("digests", "abc").await.unwrap());
+        assert!(store.is_seen("digests", "abc").await.unwrap());
+
+        // Sets are independent
+        assert!(!store.is_seen("other", "abc").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_survives_reopen() {
+        let path = std::env::temp_dir().join(format!(
+            "canary-state-{}-{}.json",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let store = FileStateStore::open(&path).unwrap();
+        // Flushing before anything was written is fine
+        store.flush().await.unwrap();
+        store.set_cursor("relay", "abc").await.unwrap();
+        assert!(store.mark_seen("digests", "d1").await.unwrap());
+        store.flush().await.unwrap();
+        drop(store);
+
+        let store = FileStateStore::open(&path).unwrap();
+        assert_eq!(
+            store.get_cursor("relay").await.unwrap(),
+            Some("abc".to_string())
+        );
+        assert!(store.is_seen("digests", "d1").await.unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_memory_lock_exclusive() {
+        let store = MemoryStateStore::new();
+        let ttl = Duration::from_secs(60);
+
+        assert!(store.try_acquire_lock("leader", "a", ttl).await.unwrap());
+        assert!(!store.try_acquire_lock("leader", "b", ttl).await.unwrap());
+        // Re-acquiring as the holder refreshes the lock
+        assert!(store.try_acquire_lock("leader", "a", ttl).await.unwrap());
+
+        // Releasing as a non-holder is a no-op
+        store.release_lock("leader", "b").await.unwrap();
+        assert!(!store.try_acquire_lock("leader", "b", ttl).await.unwrap());
+
+        store.release_lock("leader", "a").await.unwrap();
+        assert!(store.try_acquire_lock("leader", "b", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_lock_expires() {
+        let store = MemoryStateStore::new();
+
+        assert!(store
+            .try_acquire_lock("leader", "a", Duration::from_millis(0))
+            .await
+            .unwrap());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(store
+            .try_acquire_lock("leader", "b", Duration::from_secs(60))
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,313 @@
+//! Canary statements and their diffs
+//!
+//! A canary statement is plain text with a few `Key: value` header lines for its
+//! dates and one assertion per bullet line:
+//!
+//! ```text
+//! Issued: 2024-01-01
+//! Expires: 2024-04-01
+//!
+//! - We have not received any National Security Letters.
+//! - We have not been asked to weaken our build pipeline.
+//! ```
+//!
+//! `diff` compares two statements so reviewers see exactly which assertions were
+//! added or removed and how the dates moved before signing an `update_blob`.
+//! `statement_hash` identifies a statement's exact text.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A parsed canary statement
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanaryStatement {
+    /// Date the statement was issued (`Issued:` or `Date:` header)
+    pub issued: Option<String>,
+    /// Date the statement expires (`Expires:` or `Valid until:` header)
+    pub expires: Option<String>,
+    /// Assertions, in document order
+    pub assertions: Vec<String>,
+}
+
+impl CanaryStatement {
+    /// Parse a statement from text
+    ///
+    /// Header names are case-insensitive. Lines starting with `-`, `*`, or a number
+    /// followed by `.` or `)` are assertions; other lines are ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut statement = Self::default();
+
+        for line in text.lines().map(str::trim) {
+            if let Some(assertion) = bullet_text(line) {
+                if !assertion.is_empty() {
+                    statement.assertions.push(normalize(assertion));
+                }
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "issued" | "date" => statement.issued = Some(value.to_string()),
+                    "expires" | "valid until" => statement.expires = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        statement
+    }
+}
+
+/// A change of a single statement date
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateChange {
+    /// The previous value
+    pub old: Option<String>,
+    /// The new value
+    pub new: Option<String>,
+}
+
+/// Differences between two canary statements
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementDiff {
+    /// Change of the issue date, if any
+    pub issued: Option<DateChange>,
+    /// Change of the expiry date, if any
+    pub expires: Option<DateChange>,
+    /// Assertions only present in the new statement, in its order
+    pub added: Vec<String>,
+    /// Assertions only present in the old statement, in its order
+    pub removed: Vec<String>,
+    /// Number of assertions present in both
+    pub unchanged: usize,
+}
+
+impl StatementDiff {
+    /// Whether the statements are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.issued.is_none()
+            && self.expires.is_none()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+
+    /// Whether any assertion was dropped (the signal a canary exists to give)
+    pub fn has_removals(&self) -> bool {
+        !self.removed.is_empty()
+    }
+}
+
+/// Formats the diff for humans: date changes, then `-`/`+` assertion lines
+impl fmt::Display for StatementDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        let show = |value: &Option<String>| value.clone().unwrap_or_else(|| "(none)".to_string());
+        if let Some(change) = &self.issued {
+            writeln!(f, "Issued: {} -> {}", show(&change.old), show(&change.new))?;
+        }
+        if let Some(change) = &self.expires {
+            writeln!(f, "Expires: {} -> {}", show(&change.old), show(&change.new))?;
+        }
+        for assertion in &self.removed {
+            writeln!(f, "- {}", assertion)?;
+        }
+        for assertion in &self.added {
+            writeln!(f, "+ {}", assertion)?;
+        }
+        writeln!(f, "({} unchanged)", self.unchanged)
+    }
+}
+
+/// Compare two canary statements
+///
+/// # Arguments
+///
+/// * `old` - The currently published statement
+/// * `new` - The proposed statement
+///
+/// # Returns
+///
+/// Returns a `StatementDiff`; its `Display` output is suitable for notifications.
+///
+/// # Example
+///
+/// ```rust
+/// use canary_sdk::statement::{diff, CanaryStatement};
+///
+/// let old = CanaryStatement::parse("Issued: 2024-01-01\n- No subpoenas.\n- No gag orders.");
+/// let new = CanaryStatement::parse("Issued: 2024-04-01\n- No subpoenas.");
+/// let changes = diff(&old, &new);
+/// assert_eq!(changes.removed, vec!["No gag orders."]);
+/// println!("{}", changes);
+/// ```
+pub fn diff(old: &CanaryStatement, new: &CanaryStatement) -> StatementDiff {
+    // Count assertions so duplicated lines are matched one-for-one
+    let mut remaining: HashMap<&str, usize> = HashMap::new();
+    for assertion in &old.assertions {
+        *remaining.entry(assertion.as_str()).or_default() += 1;
+    }
+
+    let mut added = Vec::new();
+    let mut unchanged = 0;
+    for assertion in &new.assertions {
+        match remaining.get_mut(assertion.as_str()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                unchanged += 1;
+            }
+            _ => added.push(assertion.clone()),
+        }
+    }
+
+    let mut removed = Vec::new();
+    for assertion in &old.assertions {
+        if let Some(count) = remaining.get_mut(assertion.as_str()) {
+            if *count > 0 {
+                *count -= 1;
+                removed.push(assertion.clone());
+            }
+        }
+    }
+
+    StatementDiff {
+        issued: date_change(&old.issued, &new.issued),
+        expires: date_change(&old.expires, &new.expires),
+        added,
+        removed,
+        unchanged,
+    }
+}
+
+/// The hash of a statement: the hex-encoded SHA-256 of its exact text
+///
+/// The text is hashed as uploaded, without normalizing whitespace or line endings,
+/// so it matches the hash of the blob content (see `canary::notary::BlobHash`).
+pub fn statement_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn date_change(old: &Option<String>, new: &Option<String>) -> Option<DateChange> {
+    (old != new).then(|| DateChange {
+        old: old.clone(),
+        new: new.clone(),
+    })
+}
+
+/// The text of a bullet or numbered line, or `None` if the line is not an assertion
+fn bullet_text(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(rest.trim());
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return Some(rest.trim());
+        }
+    }
+    None
+}
+
+/// Collapse internal whitespace so reflowed lines compare equal
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "Canary for example.com
+Issued: 2024-01-01
+Expires: 2024-04-01
+
+- We have not received any National Security Letters.
+- We have not been   asked to weaken our builds.
+- No warrants.
+";
+
+    #[test]
+    fn test_parse() {
+        let statement = CanaryStatement::parse(OLD);
+        assert_eq!(statement.issued.as_deref(), Some("2024-01-01"));
+        assert_eq!(statement.expires.as_deref(), Some("2024-04-01"));
+        assert_eq!(statement.assertions.len(), 3);
+        assert_eq!(
+            statement.assertions[1],
+            "We have not been asked to weaken our builds."
+        );
+    }
+
+    #[test]
+    fn test_parse_numbered() {
+        let statement =
+            CanaryStatement::parse("Date: 2024-01-01\n1. First\n2) Second\n2024 was fine");
+        assert_eq!(statement.issued.as_deref(), Some("2024-01-01"));
+        assert_eq!(statement.assertions, vec!["First", "Second"]);
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let statement = CanaryStatement::parse(OLD);
+        let changes = diff(&statement, &statement);
+        assert!(changes.is_empty());
+        assert_eq!(changes.unchanged, 3);
+        assert_eq!(changes.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn test_diff_changes() {
+        let old = CanaryStatement::parse(OLD);
+        let new = CanaryStatement::parse(
+            "Issued: 2024-04-01
+Expires: 2024-07-01
+- We have not received any National Security Letters.
+- We have not been asked to weaken our builds.
+- We have not been asked to add backdoors.
+",
+        );
+        let changes = diff(&old, &new);
+        assert_eq!(changes.removed, vec!["No warrants."]);
+        assert_eq!(
+            changes.added,
+            vec!["We have not been asked to add backdoors."]
+        );
+        assert_eq!(changes.unchanged, 2);
+        assert!(changes.has_removals());
+        assert_eq!(
+            changes.issued,
+            Some(DateChange {
+                old: Some("2024-01-01".to_string()),
+                new: Some("2024-04-01".to_string()),
+            })
+        );
+
+        let text = changes.to_string();
+        assert!(text.contains("Issued: 2024-01-01 -> 2024-04-01"));
+        assert!(text.contains("- No warrants."));
+        assert!(text.contains("+ We have not been asked to add backdoors."));
+    }
+
+    #[test]
+    fn test_diff_duplicates() {
+        let old = CanaryStatement::parse("- A\n- A\n- B");
+        let new = CanaryStatement::parse("- A\n- B\n- B");
+        let changes = diff(&old, &new);
+        assert_eq!(changes.removed, vec!["A"]);
+        assert_eq!(changes.added, vec!["B"]);
+        assert_eq!(changes.unchanged, 2);
+    }
+}
@@ -0,0 +1,376 @@
+//! Canary statement templates
+//!
+//! Scheduled republication renders each statement from the same template so its
+//! structure never drifts between issues. Templates are plain statement text with
+//! `{{ name }}` placeholders and sections that are kept or dropped by boolean toggles:
+//!
+//! ```text
+//! Issued: {{ date }}
+//! Checkpoint: {{ checkpoint }}
+//! Domain: {{ domain }}
+//!
+//! {{#no_nsl}}
+//! - We have not received any National Security Letters.
+//! {{/no_nsl}}
+//! {{^no_nsl}}
+//! - We are no longer able to state that we have not received National Security Letters.
+//! {{/no_nsl}}
+//! ```
+//!
+//! `{{#name}}` keeps its section when the toggle is on, `{{^name}}` when it is off. A
+//! section tag alone on its line is removed together with the line. Unknown variables
+//! and toggles are errors rather than blanks, so a typo cannot silently drop an
+//! assertion.
+
+use crate::error::TemplateError;
+use crate::statement::CanaryStatement;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Variables available to a template
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVars {
+    /// Issue date, rendered as `{{ date }}` (e.g. `2024-01-01`)
+    pub date: Option<String>,
+    /// Checkpoint the statement was issued at, rendered as `{{ checkpoint }}`
+    pub checkpoint: Option<u64>,
+    /// Domain the statement is published for, rendered as `{{ domain }}`
+    pub domain: Option<String>,
+    /// Additional named values
+    pub values: BTreeMap<String, String>,
+    /// Assertion toggles used by `{{#name}}` and `{{^name}}` sections
+    pub toggles: BTreeMap<String, bool>,
+}
+
+impl TemplateVars {
+    /// Create an empty set of variables
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the issue date
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Set the issue date from a Unix timestamp in milliseconds (UTC)
+    pub fn with_date_ms(self, timestamp_ms: u64) -> Self {
+        self.with_date(format_date(timestamp_ms))
+    }
+
+    /// Set the checkpoint
+    pub fn with_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Set the domain
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Set an additional named value
+    pub fn with_value(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set an assertion toggle
+    pub fn with_toggle(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.toggles.insert(name.into(), enabled);
+        self
+    }
+
+    fn value(&self, name: &str) -> Result<String, TemplateError> {
+        let value = match name {
+            "date" => self.date.clone(),
+            "checkpoint" => self.checkpoint.map(|c| c.to_string()),
+            "domain" => self.domain.clone(),
+            _ => self.values.get(name).cloned(),
+        };
+        value.ok_or_else(|| TemplateError::UnknownVariable(name.to_string()))
+    }
+
+    fn toggle(&self, name: &str) -> Result<bool, TemplateError> {
+        self.toggles
+            .get(name)
+            .copied()
+            .ok_or_else(|| TemplateError::UnknownToggle(name.to_string()))
+    }
+}
+
+/// A canary statement template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementTemplate {
+    source: String,
+}
+
+impl StatementTemplate {
+    /// Create a template from its source text
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// Load a template from a file
+    pub fn load(path: &Path) -> Result<Self, TemplateError> {
+        let source = std::fs::read_to_string(path).map_err(|e| TemplateError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Self::new(source))
+    }
+
+    /// The template source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Render the statement content to upload
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - The variables and toggles to render with
+    ///
+    /// # Returns
+    ///
+    /// Returns the rendered statement bytes, or a `TemplateError` if the template is
+    /// malformed, uses an unknown variable or toggle, or renders no assertions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::template::{StatementTemplate, TemplateVars};
+    /// use std::path::Path;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let template = StatementTemplate::load(Path::new("canary.tmpl"))?;
+    /// let vars = TemplateVars::new()
+    ///     .with_date("2024-01-01")
+    ///     .with_checkpoint(1_234_567)
+    ///     .with_domain("example.com")
+    ///     .with_toggle("no_nsl", true);
+    /// let content = template.render(&vars)?;
+    /// std::fs::write("canary.txt", &content)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render(&self, vars: &TemplateVars) -> Result<Vec<u8>, TemplateError> {
+        let text = render(&self.source, vars)?;
+        if CanaryStatement::parse(&text).assertions.is_empty() {
+            return Err(TemplateError::NoAssertions);
+        }
+        Ok(text.into_bytes())
+    }
+}
+
+/// Render a template to text
+///
+/// Unlike `StatementTemplate::render`, the output is not required to be a statement.
+pub fn render(template: &str, vars: &TemplateVars) -> Result<String, TemplateError> {
+    let template = strip_standalone_sections(template);
+    let mut out = String::with_capacity(template.len());
+    // Open sections with whether their parent was being emitted
+    let mut sections: Vec<(&str, bool)> = Vec::new();
+    let mut active = true;
+    let mut rest = template.as_str();
+
+    while let Some(start) = rest.find("{{") {
+        if active {
+            out.push_str(&rest[..start]);
+        }
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| TemplateError::Syntax("unclosed `{{`".to_string()))?;
+        let tag = after[..end].trim();
+        rest = &after[end + 2..];
+
+        if let Some(name) = tag.strip_prefix('#') {
+            let name = name.trim();
+            sections.push((name, active));
+            active = active && vars.toggle(name)?;
+        } else if let Some(name) = tag.strip_prefix('^') {
+            let name = name.trim();
+            sections.push((name, active));
+            active = active && !vars.toggle(name)?;
+        } else if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            match sections.pop() {
+                Some((open, parent)) if open == name => active = parent,
+                Some((open, _)) => {
+                    return Err(TemplateError::Syntax(format!(
+                        "`{{{{/{}}}}}` closes section `{}`",
+                        name, open
+                    )))
+                }
+                None => {
+                    return Err(TemplateError::Syntax(format!(
+                        "`{{{{/{}}}}}` has no open section",
+                        name
+                    )))
+                }
+            }
+        } else if active {
+            out.push_str(&vars.value(tag)?);
+        }
+    }
+
+    if let Some((open, _)) = sections.last() {
+        return Err(TemplateError::Syntax(format!(
+            "section `{}` is never closed",
+            open
+        )));
+    }
+    if active {
+        out.push_str(rest);
+    }
+    Ok(out)
+}
+
+/// Remove the surrounding whitespace and line break of section tags on their own line
+fn strip_standalone_sections(template: &str) -> String {
+    template
+        .split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim();
+            let standalone = trimmed.starts_with("{{")
+                && trimmed.ends_with("}}")
+                && trimmed.matches("{{").count() == 1
+                && matches!(
+                    trimmed[2..].trim_start().chars().next(),
+                    Some('#' | '^' | '/')
+                );
+            if standalone {
+                trimmed
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Format a Unix timestamp in milliseconds as a UTC `YYYY-MM-DD` date
+pub fn format_date(timestamp_ms: u64) -> String {
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let days = (timestamp_ms / 86_400_000) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "Issued: {{ date }}\n\
+                            Checkpoint: {{checkpoint}}\n\
+                            \n\
+                            {{#no_nsl}}\n\
+                            - We have not received any National Security Letters.\n\
+                            {{/no_nsl}}\n\
+                            {{^no_nsl}}\n\
+                            - We can no longer make our NSL statement.\n\
+                            {{/no_nsl}}\n\
+                            - Our signing key for {{ domain }} is uncompromised.\n";
+
+    fn vars(no_nsl: bool) -> TemplateVars {
+        TemplateVars::new()
+            .with_date("2024-01-01")
+            .with_checkpoint(42)
+            .with_domain("example.com")
+            .with_toggle("no_nsl", no_nsl)
+    }
+
+    #[test]
+    fn test_render_sections() {
+        let text = render(TEMPLATE, &vars(true)).unwrap();
+        assert_eq!(
+            text,
+            "Issued: 2024-01-01\n\
+             Checkpoint: 42\n\
+             \n\
+             - We have not received any National Security Letters.\n\
+             - Our signing key for example.com is uncompromised.\n"
+        );
+
+        let text = render(TEMPLATE, &vars(false)).unwrap();
+        assert!(text.contains("no longer make"));
+        assert!(!text.contains("have not received"));
+    }
+
+    #[test]
+    fn test_render_statement_structure() {
+        let content = StatementTemplate::new(TEMPLATE)
+            .render(&vars(true))
+            .unwrap();
+        let statement = CanaryStatement::parse(&String::from_utf8(content).unwrap());
+        assert_eq!(statement.issued.as_deref(), Some("2024-01-01"));
+        assert_eq!(statement.assertions.len(), 2);
+    }
+
+    #[test]
+    fn test_nested_sections() {
+        let vars = TemplateVars::new()
+            .with_toggle("a", false)
+            .with_toggle("b", false);
+        // An inverted inner section stays hidden while its parent is hidden
+        assert_eq!(render("x{{#a}}y{{^b}}z{{/b}}{{/a}}", &vars).unwrap(), "x");
+    }
+
+    #[test]
+    fn test_unknown_names_rejected() {
+        assert!(matches!(
+            render("{{ missing }}", &TemplateVars::new()),
+            Err(TemplateError::UnknownVariable(name)) if name == "missing"
+        ));
+        assert!(matches!(
+            render("{{#typo}}x{{/typo}}", &TemplateVars::new()),
+            Err(TemplateError::UnknownToggle(name)) if name == "typo"
+        ));
+    }
+
+    #[test]
+    fn test_malformed_templates_rejected() {
+        let vars = vars(true);
+        for bad in [
+            "{{ date",
+            "{{#no_nsl}}x",
+            "x{{/no_nsl}}",
+            "{{#no_nsl}}x{{/other}}",
+        ] {
+            assert!(
+                matches!(render(bad, &vars), Err(TemplateError::Syntax(_))),
+                "{} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_requires_assertions() {
+        let template = StatementTemplate::new("Issued: {{ date }}\n");
+        assert!(matches!(
+            template.render(&vars(true)),
+            Err(TemplateError::NoAssertions)
+        ));
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400_000), "2000-02-29");
+        assert_eq!(format_date(1_704_067_199_999), "2023-12-31");
+        assert_eq!(format_date(1_704_067_200_000), "2024-01-01");
+    }
+}
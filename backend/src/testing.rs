@@ -0,0 +1,514 @@
+//! Funding helpers for integration test fixtures
+//!
+//! `Funder` sends SUI from a funded key (e.g. a localnet genesis key) to test
+//! addresses, creating one coin per entry of a `CoinLayout`. This lets tests set up
+//! realistic coin topologies such as dust or a single large coin. If a faucet URL is
+//! configured, the funding account tops itself up from the faucet when it runs low.
+//!
+//! For staleness scenarios, `SimulatedClock` is a `TimeSource` that only moves when
+//! the test advances it, and `SkewedClock` shifts another time source (typically the
+//! localnet `ChainClock`) by a configurable offset. Tests that need Move code itself
+//! to observe time passing can wait for localnet epochs with `advance_epochs`; start
+//! the localnet with a short `--epoch-duration-ms` for this.
+//!
+//! With the `localnet` feature, `localnet::Localnet` goes further: it attaches to (or
+//! starts) a localnet, publishes the Canary package, and hands out funded signers.
+
+use crate::clock::TimeSource;
+use crate::error::{BudgetError, CanaryError};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_sdk::SuiClient;
+
+// Funding sends transactions, so it needs a signing key
+#[cfg(feature = "signing")]
+use {
+    crate::client::SuiClientWithSigner,
+    crate::error::{FundingError, TransactionError},
+    crate::keystore::create_keystore_from_key,
+    crate::transaction::CanaryTransactionBuilder,
+    sui_sdk::rpc_types::SuiTransactionBlockResponse,
+    sui_sdk::types::base_types::{ObjectID, SuiAddress},
+};
+
+#[cfg(all(feature = "localnet", feature = "signing"))]
+pub mod localnet;
+
+/// Default localnet faucet endpoint (`sui start --with-faucet`)
+pub const LOCALNET_FAUCET_URL: &str = "http://127.0.0.1:9123";
+
+/// Gas reserved on top of the layout total when checking the funder's balance
+#[cfg(feature = "signing")]
+const GAS_RESERVE: u64 = 50_000_000;
+
+/// Number of times the funder's balance is polled after a faucet request
+#[cfg(feature = "signing")]
+const FAUCET_POLL_ATTEMPTS: u32 = 20;
+
+/// The coins a funded address should end up with, one amount (in MIST) per coin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinLayout {
+    /// Coin amounts in MIST
+    pub amounts: Vec<u64>,
+}
+
+impl CoinLayout {
+    /// A single coin holding `amount`
+    pub fn single(amount: u64) -> Self {
+        Self {
+            amounts: vec![amount],
+        }
+    }
+
+    /// `total` split evenly into `count` coins (the last coin takes the remainder)
+    pub fn even(total: u64, count: usize) -> Self {
+        if count == 0 {
+            return Self { amounts: vec![] };
+        }
+        let share = total / count as u64;
+        let mut amounts = vec![share; count];
+        amounts[count - 1] += total - share * count as u64;
+        Self { amounts }
+    }
+
+    /// One large coin plus `dust_count` coins of `dust_amount` each
+    pub fn with_dust(large: u64, dust_count: usize, dust_amount: u64) -> Self {
+        let mut amounts = vec![large];
+        amounts.extend(std::iter::repeat(dust_amount).take(dust_count));
+        Self { amounts }
+    }
+
+    /// Total amount across all coins, in MIST
+    pub fn total(&self) -> u64 {
+        self.amounts.iter().sum()
+    }
+}
+
+/// Sends SUI from a funded key to test addresses
+#[cfg(feature = "signing")]
+pub struct Funder {
+    client: SuiClient,
+    bech32_key: String,
+    address: SuiAddress,
+    faucet_url: Option<String>,
+}
+
+#[cfg(feature = "signing")]
+impl Funder {
+    /// Create a funder from a Bech32-encoded private key holding SUI
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A `SuiClient` connected to the test network
+    /// * `bech32_key` - The funding key (from `sui keytool export`)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_sui_client, Network};
+    /// use canary_sdk::testing::{CoinLayout, Funder, LOCALNET_FAUCET_URL};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_sui_client(Network::Localnet).await?;
+    /// let funder = Funder::from_key(client, "suiprivkey1...")
+    ///     .await?
+    ///     .with_faucet(LOCALNET_FAUCET_URL);
+    /// let recipient = "0x456...".parse()?;
+    /// funder.fund(recipient, 1_000_000_000).await?;
+    /// funder
+    ///     .fund_with_layout(recipient, &CoinLayout::with_dust(5_000_000_000, 10, 1_000))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_key(client: SuiClient, bech32_key: &str) -> Result<Self, FundingError> {
+        let (_, address) = create_keystore_from_key(bech32_key).await?;
+        Ok(Self {
+            client,
+            bech32_key: bech32_key.to_string(),
+            address,
+            faucet_url: None,
+        })
+    }
+
+    /// Top up the funding account from this faucet when it runs low
+    pub fn with_faucet(mut self, url: &str) -> Self {
+        self.faucet_url = Some(url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// The funding account's address
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    /// Send a single coin of `amount` MIST to `address`
+    pub async fn fund(
+        &self,
+        address: SuiAddress,
+        amount: u64,
+    ) -> Result<SuiTransactionBlockResponse, FundingError> {
+        self.fund_with_layout(address, &CoinLayout::single(amount))
+            .await
+    }
+
+    /// Send one coin per entry of `layout` to `address`, in a single transaction
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction response, or a `FundingError` if the funding account
+    /// cannot cover the layout or the transaction fails.
+    pub async fn fund_with_layout(
+        &self,
+        address: SuiAddress,
+        layout: &CoinLayout,
+    ) -> Result<SuiTransactionBlockResponse, FundingError> {
+        let required = layout.total() + GAS_RESERVE;
+        let (gas_coin, available) = self.largest_coin().await?;
+        let gas_coin = match gas_coin {
+            Some(coin) if available >= required => coin,
+            _ if self.faucet_url.is_some() => {
+                self.request_faucet().await?;
+                let (coin, available) = self.wait_for_balance(required).await?;
+                coin.ok_or(FundingError::InsufficientFunds {
+                    required,
+                    available,
+                })?
+            }
+            _ => {
+                return Err(FundingError::InsufficientFunds {
+                    required,
+                    available,
+                })
+            }
+        };
+
+        let (keystore, signer) = create_keystore_from_key(&self.bech32_key).await?;
+        let mut builder = CanaryTransactionBuilder::new(SuiClientWithSigner {
+            client: self.client.clone(),
+            signer,
+            keystore,
+            retry: Default::default(),
+            profiles: Default::default(),
+            gas_policy: Default::default(),
+        });
+        builder.set_gas_object(gas_coin);
+        for amount in &layout.amounts {
+            builder.transfer_sui(address, *amount)?;
+        }
+        Ok(builder.execute().await?)
+    }
+
+    /// The funder's largest SUI coin and its balance
+    ///
+    /// Every layout coin is split from the gas coin, so the largest coin bounds how
+    /// much a single funding transaction can send.
+    async fn largest_coin(&self) -> Result<(Option<ObjectID>, u64), FundingError> {
+        let coins = self
+            .client
+            .coin_read_api()
+            .get_coins(self.address, None, None, None)
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Failed to get coins: {}", e)))?;
+
+        Ok(coins
+            .data
+            .iter()
+            .max_by_key(|coin| coin.balance)
+            .map(|coin| (Some(coin.coin_object_id), coin.balance))
+            .unwrap_or((None, 0)))
+    }
+
+    async fn request_faucet(&self) -> Result<(), FundingError> {
+        match &self.faucet_url {
+            Some(url) => request_faucet(url, self.address).await,
+            None => Err(FundingError::Faucet("No faucet configured".to_string())),
+        }
+    }
+
+    async fn wait_for_balance(
+        &self,
+        required: u64,
+    ) -> Result<(Option<ObjectID>, u64), FundingError> {
+        let mut largest = self.largest_coin().await?;
+        for _ in 0..FAUCET_POLL_ATTEMPTS {
+            if largest.1 >= required {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            largest = self.largest_coin().await?;
+        }
+        Ok(largest)
+    }
+}
+
+/// Ask the faucet at `faucet_url` to send gas to `recipient`
+#[cfg(feature = "signing")]
+pub(crate) async fn request_faucet(
+    faucet_url: &str,
+    recipient: SuiAddress,
+) -> Result<(), FundingError> {
+    let url = format!("{}/gas", faucet_url.trim_end_matches('/'));
+    let body = serde_json::json!({
+        "FixedAmountRequest": { "recipient": recipient.to_string() }
+    });
+
+    let client = reqwest::Client::builder()
+        .user_agent(crate::client::USER_AGENT)
+        .build()
+        .map_err(|e| FundingError::Faucet(e.to_string()))?;
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| FundingError::Faucet(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(FundingError::Faucet(format!(
+            "{} returned {}",
+            url,
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+/// `TimeSource` that only moves when advanced
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::clock::TimeSource;
+/// use canary_sdk::testing::SimulatedClock;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let clock = SimulatedClock::new(1_700_000_000_000);
+/// clock.advance(Duration::from_secs(31 * 86400));
+/// assert_eq!(clock.now_ms().await?, 1_702_678_400_000);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct SimulatedClock {
+    now_ms: AtomicU64,
+}
+
+impl SimulatedClock {
+    /// Create a clock stopped at `now_ms`
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            now_ms: AtomicU64::new(now_ms),
+        }
+    }
+
+    /// Move the clock forward by `by`
+    pub fn advance(&self, by: Duration) {
+        self.now_ms
+            .fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// Set the clock to `now_ms` (may move backwards)
+    pub fn set(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl TimeSource for SimulatedClock {
+    async fn now_ms(&self) -> Result<u64, CanaryError> {
+        Ok(self.now_ms.load(Ordering::SeqCst))
+    }
+}
+
+/// `TimeSource` reporting another source's time shifted by an adjustable offset
+///
+/// Wrapping a `ChainClock` lets localnet tests pretend days have passed since a canary
+/// blob was uploaded, while `uploaded_at` still comes from the real chain clock.
+pub struct SkewedClock<T> {
+    inner: T,
+    offset_ms: AtomicI64,
+}
+
+impl<T: TimeSource> SkewedClock<T> {
+    /// Wrap `inner` with a zero offset
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            offset_ms: AtomicI64::new(0),
+        }
+    }
+
+    /// Shift reported time forward by `by` (cumulative)
+    pub fn advance(&self, by: Duration) {
+        self.offset_ms
+            .fetch_add(by.as_millis() as i64, Ordering::SeqCst);
+    }
+
+    /// Set the offset to `offset_ms` (negative values move time backwards)
+    pub fn set_offset_ms(&self, offset_ms: i64) {
+        self.offset_ms.store(offset_ms, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl<T: TimeSource> TimeSource for SkewedClock<T> {
+    async fn now_ms(&self) -> Result<u64, CanaryError> {
+        let now = self.inner.now_ms().await?;
+        Ok(now.saturating_add_signed(self.offset_ms.load(Ordering::SeqCst)))
+    }
+}
+
+/// Wait until the network has moved `epochs` epochs past the current one
+///
+/// Each epoch change advances the on-chain clock by at least the network's epoch
+/// duration, so on a localnet started with a short `--epoch-duration-ms` this is a
+/// deterministic way to let Move code observe time passing.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` connected to the localnet
+/// * `epochs` - Number of epochs to wait for
+/// * `timeout` - How long to wait before giving up
+///
+/// # Returns
+///
+/// Returns the new epoch, or a `CanaryError` if the query fails or `timeout` elapses.
+pub async fn advance_epochs(
+    client: &SuiClient,
+    epochs: u64,
+    timeout: Duration,
+) -> Result<u64, CanaryError> {
+    let target = system_state(client).await?.epoch + epochs;
+    let wait = async {
+        loop {
+            let epoch = system_state(client).await?.epoch;
+            if epoch >= target {
+                return Ok(epoch);
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    };
+
+    tokio::time::timeout(timeout, wait).await.map_err(|_| {
+        CanaryError::Budget(BudgetError::Timeout {
+            operation: format!("advance_epochs({})", epochs),
+            limit: timeout,
+        })
+    })?
+}
+
+async fn system_state(client: &SuiClient) -> Result<SuiSystemStateSummary, CanaryError> {
+    client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await
+        .map_err(|e| CanaryError::Registry(format!("Failed to get system state: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_single() {
+        let layout = CoinLayout::single(42);
+        assert_eq!(layout.amounts, vec![42]);
+        assert_eq!(layout.total(), 42);
+    }
+
+    #[test]
+    fn test_layout_even_keeps_remainder() {
+        let layout = CoinLayout::even(10, 3);
+        assert_eq!(layout.amounts, vec![3, 3, 4]);
+        assert_eq!(layout.total(), 10);
+        assert!(CoinLayout::even(10, 0).amounts.is_empty());
+    }
+
+    #[test]
+    fn test_layout_with_dust() {
+        let layout = CoinLayout::with_dust(1_000, 3, 1);
+        assert_eq!(layout.amounts, vec![1_000, 1, 1, 1]);
+        assert_eq!(layout.total(), 1_003);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_clock_staleness() {
+        use crate::clock::is_stale;
+
+        let uploaded_at = 1_700_000_000_000;
+        let max_age = Duration::from_secs(30 * 86400);
+        let clock = SimulatedClock::new(uploaded_at);
+        assert!(!is_stale(
+            uploaded_at,
+            clock.now_ms().await.unwrap(),
+            max_age
+        ));
+
+        clock.advance(max_age);
+        assert!(!is_stale(
+            uploaded_at,
+            clock.now_ms().await.unwrap(),
+            max_age
+        ));
+
+        clock.advance(Duration::from_millis(1));
+        assert!(is_stale(
+            uploaded_at,
+            clock.now_ms().await.unwrap(),
+            max_age
+        ));
+
+        clock.set(uploaded_at);
+        assert!(!is_stale(
+            uploaded_at,
+            clock.now_ms().await.unwrap(),
+            max_age
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_skewed_clock() {
+        let clock = SkewedClock::new(SimulatedClock::new(10_000));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clock.now_ms().await.unwrap(), 15_000);
+        clock.set_offset_ms(-20_000);
+        assert_eq!(clock.now_ms().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires a running localnet started with a short --epoch-duration-ms
+    async fn test_advance_epochs_localnet() {
+        let client = crate::client::create_sui_client(crate::client::Network::Localnet)
+            .await
+            .unwrap();
+        let chain = crate::clock::ChainClock::new(client.clone());
+        let before = chain.now_ms().await.unwrap();
+        advance_epochs(&client, 1, Duration::from_secs(120))
+            .await
+            .unwrap();
+        assert!(chain.now_ms().await.unwrap() > before);
+    }
+
+    #[cfg(feature = "signing")]
+    #[tokio::test]
+    #[ignore] // Requires a running localnet with faucet and SUI_FUNDING_KEY set
+    async fn test_fund_localnet() {
+        let key = std::env::var("SUI_FUNDING_KEY").unwrap();
+        let client = crate::client::create_sui_client(crate::client::Network::Localnet)
+            .await
+            .unwrap();
+        let funder = Funder::from_key(client, &key)
+            .await
+            .unwrap()
+            .with_faucet(LOCALNET_FAUCET_URL);
+        let recipient = SuiAddress::random_for_testing_only();
+        let response = funder
+            .fund_with_layout(recipient, &CoinLayout::even(3_000_000, 3))
+            .await
+            .unwrap();
+        assert!(response.effects.is_some());
+    }
+}
@@ -0,0 +1,481 @@
+//! Localnet harness for integration tests
+//!
+//! `Localnet::start` attaches to a local Sui network, or starts one with
+//! `sui start --with-faucet --force-regenesis` if none answers and `spawn` is set. It
+//! then builds and publishes the Canary Move package from a fresh faucet-funded key.
+//! Publishing runs the package's `init`, which creates the Registry and sends the
+//! AdminCap to that key. After that, `admin` returns a signer for that key, and
+//! `account` returns new funded signers that tests can use as members.
+//!
+//! Building shells out to `sui move build`, so the `sui` CLI must be on `PATH` (or
+//! configured with `LocalnetConfig::sui_binary`). A localnet started by the harness is
+//! stopped when the `Localnet` is dropped.
+
+use super::{request_faucet, LOCALNET_FAUCET_URL};
+use crate::canary::{
+    create_registry, CanaryClient, CanaryObjectKind, CreatedRegistry, TransactionReceipt,
+};
+use crate::client::{create_sui_client_with_url, SuiClientWithSigner};
+use crate::error::{FundingError, KeystoreError, LocalnetError};
+use crate::transaction::publish_package;
+use base64::Engine;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::crypto::SignatureScheme;
+use sui_sdk::SuiClient;
+use tokio::process::{Child, Command};
+
+/// Default localnet RPC endpoint
+pub const LOCALNET_RPC_URL: &str = "http://127.0.0.1:9000";
+
+/// How often the RPC, faucet, and balances are polled while waiting for them
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where and how to reach the localnet
+#[derive(Debug, Clone)]
+pub struct LocalnetConfig {
+    /// The localnet RPC endpoint
+    pub rpc_url: String,
+    /// The localnet faucet endpoint
+    pub faucet_url: String,
+    /// The Canary Move package directory
+    pub package_path: PathBuf,
+    /// The `sui` CLI binary
+    pub sui_binary: String,
+    /// Start a localnet if none answers at `rpc_url`
+    pub spawn: bool,
+    /// How long to wait for a started localnet and for faucet funds
+    pub startup_timeout: Duration,
+}
+
+impl Default for LocalnetConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: LOCALNET_RPC_URL.to_string(),
+            faucet_url: LOCALNET_FAUCET_URL.to_string(),
+            package_path: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../move")),
+            sui_binary: "sui".to_string(),
+            spawn: false,
+            startup_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A localnet with the Canary package published and a Registry created
+pub struct Localnet {
+    client: SuiClient,
+    config: LocalnetConfig,
+    admin: SuiClientWithSigner,
+    package_id: ObjectID,
+    registry_id: ObjectID,
+    admin_cap_id: ObjectID,
+    /// The `sui start` process, if the harness started the localnet
+    _process: Option<Child>,
+}
+
+impl Localnet {
+    /// Attach to (or start) a localnet and publish the Canary package
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The localnet endpoints, package path, and whether to start a localnet
+    ///
+    /// # Returns
+    ///
+    /// Returns the ready localnet, or a `LocalnetError` if no localnet is reachable or
+    /// the package cannot be built, funded, or published.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::canary::join_registry;
+    /// use canary_sdk::testing::localnet::{Localnet, LocalnetConfig};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let localnet = Localnet::start(LocalnetConfig {
+    ///     spawn: true,
+    ///     ..Default::default()
+    /// })
+    /// .await?;
+    /// let member = localnet.account().await?;
+    /// join_registry(
+    ///     member,
+    ///     localnet.registry_id(),
+    ///     "example.com".to_string(),
+    ///     1_000_000_000,
+    ///     false,
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn start(config: LocalnetConfig) -> Result<Self, LocalnetError> {
+        let (client, process) = match create_sui_client_with_url(&config.rpc_url).await {
+            Ok(client) => (client, None),
+            Err(e) if !config.spawn => {
+                return Err(LocalnetError::Unavailable(format!(
+                    "{}: {}",
+                    config.rpc_url, e
+                )))
+            }
+            Err(_) => {
+                let process = Command::new(&config.sui_binary)
+                    .args(["start", "--with-faucet", "--force-regenesis"])
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|e| {
+                        LocalnetError::Unavailable(format!(
+                            "Failed to run `{} start`: {}",
+                            config.sui_binary, e
+                        ))
+                    })?;
+                (wait_for_rpc(&config).await?, Some(process))
+            }
+        };
+
+        let admin = new_account(&client, &config).await?;
+        let (modules, dependencies) = build_package(&config).await?;
+        let (package_id, registry_id, admin_cap_id) =
+            publish_canary_package(&admin, modules, dependencies).await?;
+        tracing::info!(
+            "Published Canary package {} with registry {}",
+            package_id,
+            registry_id
+        );
+
+        Ok(Self {
+            client,
+            config,
+            admin,
+            package_id,
+            registry_id,
+            admin_cap_id,
+            _process: process,
+        })
+    }
+
+    /// The localnet client
+    pub fn client(&self) -> &SuiClient {
+        &self.client
+    }
+
+    /// The published Canary package ID
+    pub fn package_id(&self) -> ObjectID {
+        self.package_id
+    }
+
+    /// The Registry created by the package's `init`
+    pub fn registry_id(&self) -> ObjectID {
+        self.registry_id
+    }
+
+    /// The Registry's AdminCap, owned by `admin`
+    pub fn admin_cap_id(&self) -> ObjectID {
+        self.admin_cap_id
+    }
+
+    /// A signer for the key that published the package and owns the AdminCap
+    pub async fn admin(&self) -> Result<SuiClientWithSigner, LocalnetError> {
+        Ok(self.admin.try_clone().await?)
+    }
+
+    /// A `CanaryClient` over the registry, signing as `admin`
+    pub async fn admin_canary(&self) -> Result<CanaryClient, LocalnetError> {
+        Ok(
+            CanaryClient::with_signer(self.admin().await?, self.registry_id)
+                .with_admin_cap(self.admin_cap_id)
+                .with_package_id(self.package_id),
+        )
+    }
+
+    /// Create another registry administered by `admin`, for tests that need an empty
+    /// registry or a specific fee
+    pub async fn create_registry(&self, fee: u64) -> Result<CreatedRegistry, LocalnetError> {
+        Ok(create_registry(self.admin().await?, self.package_id, fee).await?)
+    }
+
+    /// A signer for a new key funded from the faucet
+    pub async fn account(&self) -> Result<SuiClientWithSigner, LocalnetError> {
+        new_account(&self.client, &self.config).await
+    }
+
+    /// `count` signers for new keys funded from the faucet
+    pub async fn accounts(&self, count: usize) -> Result<Vec<SuiClientWithSigner>, LocalnetError> {
+        let mut accounts = Vec::with_capacity(count);
+        for _ in 0..count {
+            accounts.push(self.account().await?);
+        }
+        Ok(accounts)
+    }
+}
+
+/// Wait for a just-started localnet to answer RPC requests
+async fn wait_for_rpc(config: &LocalnetConfig) -> Result<SuiClient, LocalnetError> {
+    let deadline = Instant::now() + config.startup_timeout;
+    loop {
+        match create_sui_client_with_url(&config.rpc_url).await {
+            Ok(client) => return Ok(client),
+            Err(e) if Instant::now() >= deadline => {
+                return Err(LocalnetError::Unavailable(format!(
+                    "{} did not come up within {:?}: {}",
+                    config.rpc_url, config.startup_timeout, e
+                )))
+            }
+            Err(_) => tokio::time::sleep(POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// Generate a key and fund its address from the faucet
+async fn new_account(
+    client: &SuiClient,
+    config: &LocalnetConfig,
+) -> Result<SuiClientWithSigner, LocalnetError> {
+    let mut keystore = Keystore::InMem(InMemKeystore::default());
+    let (signer, _, _) = keystore
+        .generate(None, SignatureScheme::ED25519, None, None)
+        .await
+        .map_err(|e| KeystoreError::KeystoreOperation(e.to_string()))?;
+    fund(client, config, signer).await?;
+
+    Ok(SuiClientWithSigner {
+        client: client.clone(),
+        signer,
+        keystore,
+        retry: Default::default(),
+        profiles: Default::default(),
+        gas_policy: Default::default(),
+    })
+}
+
+/// Request gas for `address` and wait until it arrives
+///
+/// The faucet of a just-started localnet may come up after the RPC, so failed requests
+/// are retried until `startup_timeout`.
+async fn fund(
+    client: &SuiClient,
+    config: &LocalnetConfig,
+    address: SuiAddress,
+) -> Result<(), FundingError> {
+    let deadline = Instant::now() + config.startup_timeout;
+    while let Err(e) = request_faucet(&config.faucet_url, address).await {
+        if Instant::now() >= deadline {
+            return Err(e);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    loop {
+        let balance = client
+            .coin_read_api()
+            .get_balance(address, None)
+            .await
+            .map_err(|e| FundingError::Faucet(format!("Failed to get balance: {}", e)))?;
+        if balance.total_balance > 0 {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(FundingError::Faucet(format!(
+                "No gas arrived for {} within {:?}",
+                address, config.startup_timeout
+            )));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// `sui move build --dump-bytecode-as-base64` output
+#[derive(Deserialize)]
+struct BuildOutput {
+    modules: Vec<String>,
+    dependencies: Vec<String>,
+}
+
+/// Compile the Move package into module bytecode and dependency IDs
+async fn build_package(
+    config: &LocalnetConfig,
+) -> Result<(Vec<Vec<u8>>, Vec<ObjectID>), LocalnetError> {
+    let output = Command::new(&config.sui_binary)
+        .args(["move", "build", "--dump-bytecode-as-base64", "--path"])
+        .arg(&config.package_path)
+        .output()
+        .await
+        .map_err(|e| {
+            LocalnetError::Build(format!(
+                "Failed to run `{} move build`: {}",
+                config.sui_binary, e
+            ))
+        })?;
+    if !output.status.success() {
+        return Err(LocalnetError::Build(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    parse_build_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Read the bytecode dump, skipping any build progress printed before it
+fn parse_build_output(stdout: &str) -> Result<(Vec<Vec<u8>>, Vec<ObjectID>), LocalnetError> {
+    let json = stdout
+        .lines()
+        .rev()
+        .find(|line| line.trim_start().starts_with('{'))
+        .ok_or_else(|| LocalnetError::Build("No bytecode in build output".to_string()))?;
+    let output: BuildOutput = serde_json::from_str(json)
+        .map_err(|e| LocalnetError::Build(format!("Invalid build output: {}", e)))?;
+
+    let modules = output
+        .modules
+        .iter()
+        .map(|module| base64::engine::general_purpose::STANDARD.decode(module))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LocalnetError::Build(format!("Invalid module bytecode: {}", e)))?;
+    let dependencies = output
+        .dependencies
+        .iter()
+        .map(|id| ObjectID::from_hex_literal(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LocalnetError::Build(format!("Invalid dependency ID: {}", e)))?;
+    Ok((modules, dependencies))
+}
+
+/// Publish the package and find the package, Registry, and AdminCap it created
+async fn publish_canary_package(
+    admin: &SuiClientWithSigner,
+    modules: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+) -> Result<(ObjectID, ObjectID, ObjectID), LocalnetError> {
+    let published = publish_package(admin.try_clone().await?, modules, dependencies).await?;
+
+    // The package's `init` created the Registry and sent the AdminCap to the publisher
+    let receipt = TransactionReceipt::from_response(published.response);
+    let registry_id = receipt
+        .created(CanaryObjectKind::Registry)
+        .ok_or_else(|| LocalnetError::Publish("No Registry was created".to_string()))?;
+    let admin_cap_id = receipt
+        .created(CanaryObjectKind::AdminCap)
+        .ok_or_else(|| LocalnetError::Publish("No AdminCap was created".to_string()))?;
+
+    Ok((published.package_id, registry_id, admin_cap_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_build_output() {
+        let stdout = "INCLUDING DEPENDENCY Sui\nBUILDING canary\n\
+            {\"modules\":[\"oRzrCwY=\"],\"dependencies\":[\"0x1\",\"0x2\"],\"digest\":[1]}\n";
+        let (modules, dependencies) = parse_build_output(stdout).unwrap();
+        assert_eq!(modules, vec![vec![0xa1, 0x1c, 0xeb, 0x0b, 0x06]]);
+        assert_eq!(
+            dependencies,
+            vec![ObjectID::from_single_byte(1), ObjectID::from_single_byte(2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_build_output_errors() {
+        assert!(matches!(
+            parse_build_output("BUILDING canary\n"),
+            Err(LocalnetError::Build(_))
+        ));
+        assert!(matches!(
+            parse_build_output("{\"modules\":[\"not base64!\"],\"dependencies\":[]}"),
+            Err(LocalnetError::Build(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires the `sui` CLI; starts a localnet if none is running
+    async fn test_localnet_publish() {
+        let localnet = Localnet::start(LocalnetConfig {
+            spawn: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let registry = crate::canary::query_registry(localnet.client(), localnet.registry_id())
+            .await
+            .unwrap();
+        assert_eq!(registry.member_count, 0);
+
+        let admin = localnet.admin().await.unwrap();
+        let member = localnet.account().await.unwrap();
+        assert_ne!(admin.signer, member.signer);
+
+        let created = localnet.create_registry(5_000).await.unwrap();
+        assert_ne!(created.registry_id, localnet.registry_id());
+        let registry = crate::canary::query_registry(localnet.client(), created.registry_id)
+            .await
+            .unwrap();
+        assert_eq!(registry.fee, 5_000);
+        assert_eq!(registry.admin, admin.signer);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires the `sui` CLI; starts a localnet if none is running
+    async fn test_localnet_leave_and_remove_member() {
+        use crate::canary::{join_registry, leave_registry, query_registry, remove_member};
+
+        let localnet = Localnet::start(LocalnetConfig {
+            spawn: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let registry_id = localnet.registry_id();
+        let fee = query_registry(localnet.client(), registry_id)
+            .await
+            .unwrap()
+            .fee;
+
+        let leaving = localnet.account().await.unwrap();
+        let evicted = localnet.account().await.unwrap();
+        let evicted_address = evicted.signer;
+        join_registry(
+            leaving.try_clone().await.unwrap(),
+            registry_id,
+            "leaving.com".to_string(),
+            fee,
+            false,
+        )
+        .await
+        .unwrap();
+        join_registry(evicted, registry_id, "evicted.com".to_string(), fee, false)
+            .await
+            .unwrap();
+        let registry = query_registry(localnet.client(), registry_id)
+            .await
+            .unwrap();
+        assert_eq!(registry.member_count, 2);
+
+        leave_registry(leaving.try_clone().await.unwrap(), registry_id)
+            .await
+            .unwrap();
+        let registry = query_registry(localnet.client(), registry_id)
+            .await
+            .unwrap();
+        assert_eq!(registry.member_count, 1);
+        assert!(matches!(
+            leave_registry(leaving, registry_id).await,
+            Err(crate::error::CanaryError::NotMember)
+        ));
+
+        let admin = localnet.admin().await.unwrap();
+        remove_member(admin, registry_id, localnet.admin_cap_id(), evicted_address)
+            .await
+            .unwrap();
+        let registry = query_registry(localnet.client(), registry_id)
+            .await
+            .unwrap();
+        assert_eq!(registry.member_count, 0);
+    }
+}
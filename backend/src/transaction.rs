@@ -3,22 +3,360 @@
 //! This module provides a simplified interface for building and executing Sui transactions.
 //! It wraps the Sui SDK's transaction building APIs with convenient helper methods.
 
-use crate::client::SuiClientWithSigner;
+use crate::client::retry::RetryPolicy;
+use crate::client::{Network, SuiClientWithSigner};
 use crate::error::TransactionError;
+use crate::gas::{GasLease, GasPool, GasPriceOracle};
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use serde::Serialize;
 use shared_crypto::intent::Intent;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::rpc_types::SuiTransactionBlockResponseOptions;
 use sui_sdk::rpc_types::{
-    SuiObjectDataOptions, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    ObjectChange, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
 };
-use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_sdk::types::committee::EpochId;
+use sui_sdk::types::crypto::Signature;
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::move_package::MovePackage;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_sdk::types::transaction::CallArg;
+use sui_sdk::types::signature::GenericSignature;
 use sui_sdk::types::transaction::Transaction;
-use sui_sdk::types::transaction::TransactionData;
+use sui_sdk::types::transaction::{Argument, CallArg, Command, ObjectArg, ProgrammableTransaction};
+use sui_sdk::types::transaction::{TransactionData, TransactionExpiration};
 use sui_sdk::SuiClient;
 use sui_types::base_types::SequenceNumber;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::{TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
+use tracing::Instrument;
+
+pub mod audit_log;
+pub mod executor;
+pub mod finality;
+pub mod review;
+pub mod simulation;
+pub mod upgrade;
+
+pub use audit_log::{AuditLog, AuditQuery, AuditRecord};
+pub use executor::ExecutorPool;
+pub use finality::{wait_for_transaction, FinalityOptions, FinalityStatus};
+pub use review::describe_transaction;
+pub use simulation::{GasSummary, MoveAbort, SimulationResult};
+pub use upgrade::{upgrade_package, PackageVersions, UpgradePolicy, UpgradedPackage};
+
+/// Compute the digest a transaction will have once submitted
+///
+/// The digest depends only on the transaction data, not on its signatures, so it can
+/// be computed and shared before signing.
+pub fn transaction_digest(tx_data: &TransactionData) -> TransactionDigest {
+    tx_data.digest()
+}
+
+/// Sign transaction data with `address`'s key from `keystore`
+async fn sign(
+    keystore: &sui_keys::keystore::Keystore,
+    address: SuiAddress,
+    tx_data: &TransactionData,
+) -> Result<Signature, TransactionError> {
+    keystore
+        .sign_secure(&address, tx_data, Intent::sui_transaction())
+        .await
+        .map_err(|e| {
+            TransactionError::BuildError(format!(
+                "Failed to sign transaction as {}: {}",
+                address, e
+            ))
+        })
+}
+
+// Pure arguments are the BCS encoding of the value. Most types are easy to get wrong
+// by hand: a `String` or `vector<u8>` needs a ULEB128 length prefix before its bytes,
+// while an `address` is its 32 bytes with no prefix.
+
+/// A pure Move `String` argument
+pub fn pure_string(value: &str) -> CallArg {
+    CallArg::Pure(bcs::to_bytes(value).expect("strings always encode to BCS"))
+}
+
+/// A pure Move `u64` argument
+pub fn pure_u64(value: u64) -> CallArg {
+    CallArg::Pure(value.to_le_bytes().to_vec())
+}
+
+/// A pure Move `address` or `ID` argument, from a `SuiAddress` or `ObjectID`
+pub fn pure_address(value: impl Into<SuiAddress>) -> CallArg {
+    CallArg::Pure(value.into().to_vec())
+}
+
+/// A pure Move `vector<T>` argument
+///
+/// # Returns
+///
+/// Returns the argument, or a `TransactionError::BuildError` if a value cannot be
+/// encoded.
+///
+/// # Example
+///
+/// ```rust
+/// use canary_sdk::transaction::pure_vec;
+///
+/// // vector<String>
+/// let domains = pure_vec(&["example.com", "example.org"])?;
+/// # Ok::<(), canary_sdk::error::TransactionError>(())
+/// ```
+pub fn pure_vec<T: Serialize>(values: &[T]) -> Result<CallArg, TransactionError> {
+    bcs::to_bytes(values)
+        .map(CallArg::Pure)
+        .map_err(|e| TransactionError::BuildError(format!("Failed to encode vector: {}", e)))
+}
+
+/// Parse a Move type argument such as `u64`, `vector<u8>`, or
+/// `0x2::coin::Coin<0x2::sui::SUI>`
+///
+/// # Returns
+///
+/// Returns the `TypeTag`, or a `TransactionError::BuildError` naming the type and what
+/// is wrong with it.
+///
+/// # Example
+///
+/// ```rust
+/// use canary_sdk::transaction::parse_type_tag;
+///
+/// assert!(parse_type_tag("0x2::coin::Coin<0x2::sui::SUI>").is_ok());
+/// assert!(parse_type_tag("0x2::coin::Coin<0x2::sui::SUI").is_err());
+/// ```
+pub fn parse_type_tag(type_arg: &str) -> Result<TypeTag, TransactionError> {
+    let invalid = |reason: String| {
+        TransactionError::BuildError(format!("Invalid type argument `{}`: {}", type_arg, reason))
+    };
+
+    let trimmed = type_arg.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("empty type".to_string()));
+    }
+
+    let mut depth = 0usize;
+    for (position, c) in trimmed.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if depth == 0 => {
+                return Err(invalid(format!("unmatched `>` at position {}", position)));
+            }
+            '>' => depth -= 1,
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        return Err(invalid("missing closing `>`".to_string()));
+    }
+
+    // Struct types need all three parts; the SDK parser's error for a missing one is vague
+    let base = trimmed.split('<').next().unwrap_or_default();
+    if base.contains("::") && base.split("::").count() != 3 {
+        return Err(invalid(format!(
+            "struct types are written `address::module::Name`, got `{}`",
+            base.trim()
+        )));
+    }
+
+    sui_types::parse_sui_type_tag(trimmed).map_err(|e| invalid(e.to_string()))
+}
+
+/// One SUI, in MIST
+const MIST_PER_SUI: u64 = 1_000_000_000;
+
+/// Address each value of a command result
+///
+/// A command producing several values yields `Argument::Result(i)`, whose values are
+/// `Argument::NestedResult(i, n)`; a single value is used as-is.
+pub(crate) fn nested_results(result: Argument, count: usize) -> Vec<Argument> {
+    match (count, result) {
+        (0, _) => Vec::new(),
+        (1, result) => vec![result],
+        (count, Argument::Result(index)) => (0..count as u16)
+            .map(|n| Argument::NestedResult(index, n))
+            .collect(),
+        (_, result) => vec![result],
+    }
+}
+
+/// Gas budget defaults and safety caps for a network
+///
+/// `build()` rejects any budget above `max_budget`, whether it was set explicitly or
+/// estimated, so a bad estimate can never authorize a runaway gas spend. With
+/// `max_gas_price` set, it also rejects transactions while the reference gas price is
+/// above it, so callers can wait out a fee spike instead of overpaying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimits {
+    /// Budget used for the dry run that estimates gas (in MIST)
+    pub estimation_budget: u64,
+    /// Largest gas budget `build()` will accept (in MIST)
+    pub max_budget: u64,
+    /// Highest reference gas price `build()` will accept (in MIST per gas unit)
+    pub max_gas_price: Option<u64>,
+}
+
+impl GasLimits {
+    /// Defaults for a network: 1 SUI cap on mainnet and unknown networks, 10 SUI elsewhere
+    pub fn for_network(network: &Network) -> Self {
+        let max_budget = match network {
+            Network::Localnet | Network::Devnet | Network::Testnet => 10 * MIST_PER_SUI,
+            Network::Mainnet | Network::Custom(_) | Network::Pool(_) => MIST_PER_SUI,
+        };
+        Self {
+            estimation_budget: 50_000_000,
+            max_budget,
+            max_gas_price: None,
+        }
+    }
+
+    /// Accept no reference gas price above `price` (in MIST per gas unit)
+    pub fn with_max_gas_price(mut self, price: u64) -> Self {
+        self.max_gas_price = Some(price);
+        self
+    }
+
+    /// Check a gas budget against the cap
+    pub fn check(&self, budget: u64) -> Result<u64, TransactionError> {
+        if budget > self.max_budget {
+            return Err(TransactionError::GasBudgetExceedsCap {
+                budget,
+                cap: self.max_budget,
+            });
+        }
+        Ok(budget)
+    }
+
+    /// Check a reference gas price against `max_gas_price`
+    pub fn check_price(&self, price: u64) -> Result<u64, TransactionError> {
+        match self.max_gas_price {
+            Some(max) if price > max => Err(TransactionError::GasPriceTooHigh { price, max }),
+            _ => Ok(price),
+        }
+    }
+}
+
+/// Mainnet limits, the safe choice when the network is not known
+impl Default for GasLimits {
+    fn default() -> Self {
+        Self::for_network(&Network::Mainnet)
+    }
+}
+
+/// How `build()` picks a gas budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasBudget {
+    /// Always use this budget (in MIST)
+    Fixed(u64),
+    /// Dry-run the transaction and add `buffer_percent` percent to the estimate
+    Estimated {
+        /// Safety margin over the estimated cost, in percent
+        buffer_percent: u64,
+    },
+}
+
+impl GasBudget {
+    /// The budget for an estimated cost, with the buffer applied
+    pub fn with_buffer(&self, estimated: u64) -> u64 {
+        match *self {
+            GasBudget::Fixed(budget) => budget,
+            GasBudget::Estimated { buffer_percent } => {
+                estimated.saturating_add(estimated.saturating_mul(buffer_percent) / 100)
+            }
+        }
+    }
+}
+
+/// Gas budget policy for transactions
+///
+/// The policy picks a budget for each transaction: a default `GasBudget`, replaced by
+/// a per-operation override when the transaction calls an overridden Move function.
+/// The result is always checked against `limits.max_budget`. A budget set explicitly
+/// with `CanaryTransactionBuilder::set_gas_budget` takes precedence over the policy.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::Network;
+/// use canary_sdk::transaction::{GasBudget, GasLimits, GasPolicy};
+///
+/// let policy = GasPolicy::estimated(30)
+///     .with_limits(GasLimits::for_network(&Network::Testnet))
+///     .with_override("member_registry", "remove_member", GasBudget::Fixed(20_000_000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasPolicy {
+    /// Budget for transactions without an override
+    pub budget: GasBudget,
+    /// Estimation budget and cap
+    pub limits: GasLimits,
+    /// Budgets for transactions calling a Move function, keyed by `module::function`
+    pub overrides: BTreeMap<String, GasBudget>,
+}
+
+impl GasPolicy {
+    /// Always use a fixed budget (in MIST)
+    pub fn fixed(budget: u64) -> Self {
+        Self {
+            budget: GasBudget::Fixed(budget),
+            ..Self::default()
+        }
+    }
+
+    /// Estimate gas with a dry run and add `buffer_percent` percent
+    pub fn estimated(buffer_percent: u64) -> Self {
+        Self {
+            budget: GasBudget::Estimated { buffer_percent },
+            ..Self::default()
+        }
+    }
+
+    /// Use different limits (defaults to mainnet limits)
+    pub fn with_limits(mut self, limits: GasLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Use `budget` for transactions calling `module::function`
+    pub fn with_override(mut self, module: &str, function: &str, budget: GasBudget) -> Self {
+        self.overrides
+            .insert(format!("{}::{}", module, function), budget);
+        self
+    }
+
+    /// The budget for a transaction
+    ///
+    /// Returns the override of the first overridden Move call in the transaction, or
+    /// the default budget if no call is overridden.
+    pub fn budget_for(&self, pt: &ProgrammableTransaction) -> GasBudget {
+        pt.commands
+            .iter()
+            .find_map(|command| match command {
+                Command::MoveCall(call) => self
+                    .overrides
+                    .get(&format!("{}::{}", call.module, call.function))
+                    .copied(),
+                _ => None,
+            })
+            .unwrap_or(self.budget)
+    }
+}
+
+/// A dry-run estimate plus a 20% buffer, capped at mainnet limits
+impl Default for GasPolicy {
+    fn default() -> Self {
+        Self {
+            budget: GasBudget::Estimated { buffer_percent: 20 },
+            limits: GasLimits::default(),
+            overrides: BTreeMap::new(),
+        }
+    }
+}
 
 /// A builder for creating and executing Sui transactions
 ///
@@ -37,6 +375,21 @@ pub struct CanaryTransactionBuilder {
     gas_budget: Option<u64>,
     /// Optional gas object ID
     gas_object: Option<ObjectID>,
+    /// Gas budget policy applied by `build()`
+    gas_policy: GasPolicy,
+    /// Optional sponsor paying for gas instead of the signer
+    sponsor: Option<SuiAddress>,
+    /// Last epoch in which the transaction may execute
+    expiration: Option<EpochId>,
+    /// Transaction built by `digest_preview()` or `simulate()`, submitted as-is by the next
+    /// `execute()`
+    prepared: Option<TransactionData>,
+    /// Retry policy for the builder's RPC calls
+    retry: RetryPolicy,
+    /// Pool to lease a gas coin from when no gas object is set
+    gas_pool: Option<Arc<GasPool>>,
+    /// Gas coin leased by `build()`, returned to the pool after execution
+    gas_lease: Option<GasLease>,
 }
 
 impl CanaryTransactionBuilder {
@@ -71,11 +424,21 @@ impl CanaryTransactionBuilder {
             builder: ProgrammableTransactionBuilder::new(),
             gas_budget: None,
             gas_object: None,
+            gas_policy: client_with_signer.gas_policy,
+            sponsor: None,
+            expiration: None,
+            prepared: None,
+            retry: client_with_signer.retry,
+            gas_pool: None,
+            gas_lease: None,
         }
     }
 
     /// Add a Move call to the transaction
     ///
+    /// Calls to a package recorded as upgraded in `PackageVersions::global()` go to its
+    /// latest version.
+    ///
     /// # Arguments
     ///
     /// * `package` - The package ID containing the module
@@ -92,7 +455,7 @@ impl CanaryTransactionBuilder {
     /// ```rust,no_run
     /// use canary_sdk::transaction::CanaryTransactionBuilder;
     /// use sui_sdk::types::base_types::ObjectID;
-    /// use sui_sdk::types::transaction::CallArg;
+    /// use sui_sdk::types::transaction::{Argument, CallArg};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client_with_signer = todo!();
@@ -109,296 +472,1114 @@ impl CanaryTransactionBuilder {
         function: &str,
         args: Vec<CallArg>,
     ) -> Result<&mut Self, TransactionError> {
-        // Convert strings to Identifier types for move_call
-        // Identifier is in sui_types::identifier, accessed through sui_sdk
-        use std::str::FromStr;
-        use sui_types::Identifier;
-        let module_id = Identifier::from_str(module)
-            .map_err(|e| TransactionError::BuildError(format!("Invalid module name: {}", e)))?;
-        let function_id = Identifier::from_str(function)
-            .map_err(|e| TransactionError::BuildError(format!("Invalid function name: {}", e)))?;
-
-        self.builder
-            .move_call(package, module_id, function_id, vec![], args)
-            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        self.move_call_with_result(package, module, function, args)?;
         Ok(self)
     }
 
-    /// Add a SUI transfer to the transaction
+    /// Add a Move call and return its result, for use as an argument of later commands
+    ///
+    /// A call returning several values yields `Argument::Result(i)`; its values are
+    /// addressed as `Argument::NestedResult(i, n)`.
     ///
     /// # Arguments
     ///
-    /// * `recipient` - The recipient address
-    /// * `amount` - The amount to transfer in MIST (1 SUI = 1_000_000_000 MIST)
+    /// * `package` - The package ID containing the module
+    /// * `module` - The module name
+    /// * `function` - The function name
+    /// * `args` - The function arguments
     ///
     /// # Returns
     ///
-    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the transfer fails.
+    /// Returns the call's result `Argument`, or a `TransactionError` if the call fails.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use canary_sdk::transaction::CanaryTransactionBuilder;
-    /// use sui_sdk::types::base_types::SuiAddress;
+    /// use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+    /// use sui_sdk::types::transaction::CallArg;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client_with_signer = todo!();
     /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
-    /// let recipient = SuiAddress::from_str("0x123...")?;
-    /// builder.transfer_sui(recipient, 1_000_000_000)?; // Transfer 1 SUI
+    /// let package_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let recipient: SuiAddress = "0x456...".parse()?;
+    /// // Mint an object and hand it to the recipient in the same transaction
+    /// let minted = builder.move_call_with_result(package_id, "badge", "mint", vec![])?;
+    /// builder.transfer_arg(minted, recipient)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn transfer_sui(
+    pub fn move_call_with_result(
         &mut self,
-        recipient: SuiAddress,
-        amount: u64,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        args: Vec<CallArg>,
+    ) -> Result<Argument, TransactionError> {
+        self.add_move_call(package, module, function, vec![], args)
+    }
+
+    /// Add a call to a generic Move function
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The package ID containing the module
+    /// * `module` - The module name
+    /// * `function` - The function name
+    /// * `type_args` - The function's type arguments, e.g. from `parse_type_tag`
+    /// * `args` - The function arguments
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the call fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::{parse_type_tag, CanaryTransactionBuilder};
+    /// use sui_sdk::types::base_types::ObjectID;
+    /// use sui_sdk::types::transaction::CallArg;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// # let coin: CallArg = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// let package_id = ObjectID::from_hex_literal("0x2")?;
+    /// let sui = parse_type_tag("0x2::sui::SUI")?;
+    /// builder.move_call_with_types(package_id, "coin", "value", vec![sui], vec![coin])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_call_with_types(
+        &mut self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
     ) -> Result<&mut Self, TransactionError> {
-        self.builder.transfer_sui(recipient, Some(amount));
+        self.add_move_call(package, module, function, type_args, args)?;
         Ok(self)
     }
 
-    /// Add an object transfer to the transaction
+    /// Add a Move call with type arguments and return its result
+    fn add_move_call(
+        &mut self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+    ) -> Result<Argument, TransactionError> {
+        self.ensure_not_prepared()?;
+
+        // Convert strings to Identifier types for move_call
+        // Identifier is in sui_types::identifier, accessed through sui_sdk
+        use std::str::FromStr;
+        use sui_types::Identifier;
+        let module_id = Identifier::from_str(module)
+            .map_err(|e| TransactionError::BuildError(format!("Invalid module name: {}", e)))?;
+        let function_id = Identifier::from_str(function)
+            .map_err(|e| TransactionError::BuildError(format!("Invalid function name: {}", e)))?;
+
+        let arguments = args
+            .into_iter()
+            .map(|arg| self.builder.input(arg))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        let package = PackageVersions::global().latest(package);
+        Ok(self.builder.programmable_move_call(
+            package,
+            module_id,
+            function_id,
+            type_args,
+            arguments,
+        ))
+    }
+
+    /// Transfer a value produced by an earlier command to `recipient`
     ///
     /// # Arguments
     ///
-    /// * `object_id` - The object ID to transfer
+    /// * `arg` - The value to transfer, e.g. from `move_call_with_result` or `split_coin`
     /// * `recipient` - The recipient address
     ///
     /// # Returns
     ///
-    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the transfer fails.
-    ///
-    /// Note: This method requires fetching the object's sequence number and digest.
-    /// For a simpler API, consider using the client to get the full object reference first.
-    pub async fn transfer_object(
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the
+    /// transaction was already prepared.
+    pub fn transfer_arg(
         &mut self,
-        object_id: ObjectID,
+        arg: Argument,
         recipient: SuiAddress,
     ) -> Result<&mut Self, TransactionError> {
-        // Get the object to obtain its sequence number and digest
-        let object = self
-            .client
-            .read_api()
-            .get_object_with_options(
-                object_id,
-                sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
-            )
-            .await
-            .map_err(|e| TransactionError::BuildError(format!("Failed to get object: {}", e)))?
-            .into_object()
-            .map_err(|e| {
-                TransactionError::BuildError(format!("Failed to convert to object: {}", e))
-            })?;
-
-        // Use the object_ref() method to get the object reference tuple
-        // FullObjectRef is a tuple struct (FullObjectID, SequenceNumber, ObjectDigest)
-        let object_ref = object.object_ref();
-        use sui_types::base_types::{FullObjectID, FullObjectRef};
-        // FullObjectID is an enum with Consensus variant that takes (ObjectID, SequenceNumber)
-        let full_object_id = FullObjectID::Consensus((object_ref.0, object_ref.1));
-        let full_ref = FullObjectRef(full_object_id, object_ref.1, object_ref.2);
+        self.transfer_results(vec![arg], recipient)
+    }
 
-        self.builder
-            .transfer_object(recipient, full_ref)
-            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+    /// Publish a Move package and transfer its `UpgradeCap` to the signer
+    ///
+    /// # Arguments
+    ///
+    /// * `modules` - The compiled modules (e.g. from `sui move build --dump-bytecode-as-base64`)
+    /// * `dependencies` - The IDs of the packages the modules depend on
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the
+    /// transaction was already prepared.
+    pub fn publish(
+        &mut self,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+    ) -> Result<&mut Self, TransactionError> {
+        self.ensure_not_prepared()?;
+        let upgrade_cap = self.builder.publish_upgradeable(modules, dependencies);
+        self.builder.transfer_arg(self.signer, upgrade_cap);
         Ok(self)
     }
 
-    /// Set a custom gas budget for the transaction
+    /// Upgrade a Move package: authorize with the `UpgradeCap`, publish the new
+    /// version, and commit the upgrade back to the cap
     ///
     /// # Arguments
     ///
-    /// * `budget` - The gas budget in MIST
+    /// * `upgrade_cap` - The reference of the package's `UpgradeCap`
+    /// * `package` - The package version the cap currently points at
+    /// * `modules` - The new version's compiled modules
+    /// * `dependencies` - The IDs of the packages the modules depend on
+    /// * `policy` - The upgrade policy to authorize
     ///
     /// # Returns
     ///
-    /// Returns `&mut Self` for method chaining.
-    pub fn set_gas_budget(&mut self, budget: u64) -> &mut Self {
-        self.gas_budget = Some(budget);
-        self
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the
+    /// transaction was already prepared.
+    pub fn upgrade(
+        &mut self,
+        upgrade_cap: ObjectRef,
+        package: ObjectID,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+        policy: UpgradePolicy,
+    ) -> Result<&mut Self, TransactionError> {
+        self.ensure_not_prepared()?;
+        use sui_types::Identifier;
+        let digest =
+            MovePackage::compute_digest_for_modules_and_deps(&modules, &dependencies, true);
+
+        let build_error = |e: anyhow::Error| TransactionError::BuildError(e.to_string());
+        let cap = self
+            .builder
+            .obj(ObjectArg::ImmOrOwnedObject(upgrade_cap))
+            .map_err(build_error)?;
+        let policy = self.builder.pure(policy.code()).map_err(build_error)?;
+        let digest = self.builder.pure(digest.to_vec()).map_err(build_error)?;
+
+        // package::authorize_upgrade(cap: &mut UpgradeCap, policy: u8, digest: vector<u8>): UpgradeTicket
+        let ticket = self.builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("package").expect("valid identifier"),
+            Identifier::new("authorize_upgrade").expect("valid identifier"),
+            vec![],
+            vec![cap, policy, digest],
+        );
+        let receipt = self.builder.upgrade(package, ticket, dependencies, modules);
+        // package::commit_upgrade(cap: &mut UpgradeCap, receipt: UpgradeReceipt)
+        self.builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("package").expect("valid identifier"),
+            Identifier::new("commit_upgrade").expect("valid identifier"),
+            vec![],
+            vec![cap, receipt],
+        );
+        Ok(self)
     }
 
-    /// Set a specific gas object to use for the transaction
+    /// Transfer values produced by earlier commands to `recipient`
+    pub(crate) fn transfer_results(
+        &mut self,
+        results: Vec<Argument>,
+        recipient: SuiAddress,
+    ) -> Result<&mut Self, TransactionError> {
+        self.ensure_not_prepared()?;
+        self.builder.transfer_args(recipient, results);
+        Ok(self)
+    }
+
+    /// Split a coin into new coins of the given amounts
+    ///
+    /// If `coin_id` is the gas object set with `set_gas_object`, the gas coin is split
+    /// instead of being added as a separate input.
     ///
     /// # Arguments
     ///
-    /// * `gas_object` - The gas object ID
+    /// * `coin_id` - The coin object ID to split
+    /// * `amounts` - The amount of each new coin, in the coin's smallest unit
     ///
     /// # Returns
     ///
-    /// Returns `&mut Self` for method chaining.
-    pub fn set_gas_object(&mut self, gas_object: ObjectID) -> &mut Self {
-        self.gas_object = Some(gas_object);
-        self
+    /// Returns one `Argument` per new coin, in the order of `amounts`, for use in later
+    /// commands, or a `TransactionError` if the coin cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// let coin_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let coins = builder.split_coin(coin_id, vec![1_000, 2_000]).await?;
+    /// assert_eq!(coins.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn split_coin(
+        &mut self,
+        coin_id: ObjectID,
+        amounts: Vec<u64>,
+    ) -> Result<Vec<Argument>, TransactionError> {
+        self.ensure_not_prepared()?;
+        if amounts.is_empty() {
+            return Err(TransactionError::BuildError(
+                "split_coin needs at least one amount".to_string(),
+            ));
+        }
+
+        let coin = self.coin_argument(coin_id).await?;
+        let amounts = amounts
+            .into_iter()
+            .map(|amount| self.builder.pure(amount))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        let count = amounts.len();
+        let result = self.builder.command(Command::SplitCoins(coin, amounts));
+        Ok(nested_results(result, count))
+    }
+
+    /// Merge coins into a primary coin
+    ///
+    /// If `primary` is the gas object set with `set_gas_object`, the coins are merged
+    /// into the gas coin.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary` - The coin object ID to merge into
+    /// * `others` - The coin object IDs merged into `primary` and destroyed
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Argument` of the merged primary coin, for use in later commands, or
+    /// a `TransactionError` if a coin cannot be read.
+    pub async fn merge_coins(
+        &mut self,
+        primary: ObjectID,
+        others: Vec<ObjectID>,
+    ) -> Result<Argument, TransactionError> {
+        self.ensure_not_prepared()?;
+        if others.is_empty() {
+            return Err(TransactionError::BuildError(
+                "merge_coins needs at least one coin to merge".to_string(),
+            ));
+        }
+
+        let primary = self.coin_argument(primary).await?;
+        let mut coins = Vec::with_capacity(others.len());
+        for coin_id in others {
+            coins.push(self.coin_argument(coin_id).await?);
+        }
+        self.builder.command(Command::MergeCoins(primary, coins));
+        Ok(primary)
+    }
+
+    /// Input argument for an owned coin, or the gas coin if it is the gas object
+    async fn coin_argument(&mut self, coin_id: ObjectID) -> Result<Argument, TransactionError> {
+        if self.gas_object == Some(coin_id) {
+            return Ok(Argument::GasCoin);
+        }
+
+        let client = &self.client;
+        let coin = self
+            .retry
+            .run("get_object", || {
+                client.read_api().get_object_with_options(
+                    coin_id,
+                    sui_sdk::rpc_types::SuiObjectDataOptions::new(),
+                )
+            })
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Failed to get coin: {}", e)))?
+            .into_object()
+            .map_err(|_| TransactionError::ObjectNotFound(SuiAddress::from(coin_id)))?;
+
+        self.builder
+            .obj(ObjectArg::ImmOrOwnedObject(coin.object_ref()))
+            .map_err(|e| TransactionError::BuildError(e.to_string()))
+    }
+
+    /// Add a SUI transfer to the transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient` - The recipient address
+    /// * `amount` - The amount to transfer in MIST (1 SUI = 1_000_000_000 MIST)
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the transfer fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    /// use sui_sdk::types::base_types::SuiAddress;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// let recipient = SuiAddress::from_str("0x123...")?;
+    /// builder.transfer_sui(recipient, 1_000_000_000)?; // Transfer 1 SUI
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfer_sui(
+        &mut self,
+        recipient: SuiAddress,
+        amount: u64,
+    ) -> Result<&mut Self, TransactionError> {
+        self.ensure_not_prepared()?;
+        self.builder.transfer_sui(recipient, Some(amount));
+        Ok(self)
+    }
+
+    /// Add an object transfer to the transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The object ID to transfer
+    /// * `recipient` - The recipient address
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining, or a `TransactionError` if the transfer fails.
+    ///
+    /// Note: This method requires fetching the object's sequence number and digest.
+    /// For a simpler API, consider using the client to get the full object reference first.
+    pub async fn transfer_object(
+        &mut self,
+        object_id: ObjectID,
+        recipient: SuiAddress,
+    ) -> Result<&mut Self, TransactionError> {
+        self.ensure_not_prepared()?;
+
+        // Get the object to obtain its sequence number and digest
+        let client = &self.client;
+        let object = self
+            .retry
+            .run("get_object", || {
+                client.read_api().get_object_with_options(
+                    object_id,
+                    sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
+                )
+            })
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Failed to get object: {}", e)))?
+            .into_object()
+            .map_err(|e| {
+                TransactionError::BuildError(format!("Failed to convert to object: {}", e))
+            })?;
+
+        // Use the object_ref() method to get the object reference tuple
+        // FullObjectRef is a tuple struct (FullObjectID, SequenceNumber, ObjectDigest)
+        let object_ref = object.object_ref();
+        use sui_types::base_types::{FullObjectID, FullObjectRef};
+        // FullObjectID is an enum with Consensus variant that takes (ObjectID, SequenceNumber)
+        let full_object_id = FullObjectID::Consensus((object_ref.0, object_ref.1));
+        let full_ref = FullObjectRef(full_object_id, object_ref.1, object_ref.2);
+
+        self.builder
+            .transfer_object(recipient, full_ref)
+            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        Ok(self)
+    }
+
+    /// Set a custom gas budget for the transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - The gas budget in MIST
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining.
+    pub fn set_gas_budget(&mut self, budget: u64) -> &mut Self {
+        self.gas_budget = Some(budget);
+        self
+    }
+
+    /// Set the gas budget defaults and cap (defaults to mainnet limits)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::Network;
+    /// use canary_sdk::transaction::{CanaryTransactionBuilder, GasLimits};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// builder.set_gas_limits(GasLimits::for_network(&Network::Testnet));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_gas_limits(&mut self, limits: GasLimits) -> &mut Self {
+        self.gas_policy.limits = limits;
+        self
+    }
+
+    /// Set the gas budget policy (defaults to the signer's `gas_policy`)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::{CanaryTransactionBuilder, GasPolicy};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// builder.set_gas_policy(GasPolicy::estimated(50));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_gas_policy(&mut self, policy: GasPolicy) -> &mut Self {
+        self.gas_policy = policy;
+        self
+    }
+
+    /// Have gas paid by `sponsor` instead of the signer
+    ///
+    /// Gas coins are then selected from the sponsor's coins (a gas object set with
+    /// `set_gas_object` must be owned by the sponsor), and the transaction must be
+    /// submitted with `execute_with_sponsor` so both parties sign it.
+    ///
+    /// # Arguments
+    ///
+    /// * `sponsor` - The address paying for gas
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining.
+    pub fn set_sponsor(&mut self, sponsor: SuiAddress) -> &mut Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    /// Make the transaction expire after `epoch`
+    ///
+    /// Validators reject a transaction whose expiration epoch has passed, so a
+    /// transaction signed offline and broadcast much later cannot execute once its
+    /// window closed. Without an expiration, a signed transaction stays valid for as
+    /// long as its gas coin and input objects keep their versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - The last epoch in which the transaction may execute
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::gas::GasPriceOracle;
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer: canary_sdk::client::SuiClientWithSigner = todo!();
+    /// let client = client_with_signer.client.clone();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// GasPriceOracle::global().reference_price(&client).await?;
+    /// let epoch = GasPriceOracle::global().epoch().unwrap_or_default();
+    /// builder.set_expiration(epoch + 1);
+    /// let transaction_data = builder.build().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_expiration(&mut self, epoch: EpochId) -> &mut Self {
+        self.expiration = Some(epoch);
+        self
+    }
+
+    /// Set a specific gas object to use for the transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `gas_object` - The gas object ID
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining.
+    pub fn set_gas_object(&mut self, gas_object: ObjectID) -> &mut Self {
+        self.gas_object = Some(gas_object);
+        self
+    }
+
+    /// Lease the gas coin from a pool instead of using the owner's first coin
+    ///
+    /// Ignored if a gas object is set. The coin is leased when the transaction is built
+    /// and returned to the pool once it has been executed (or the builder is dropped),
+    /// so concurrent builders sharing a pool never pay with the same coin.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The gas pool; its owner must be the address paying for gas
+    ///
+    /// # Returns
+    ///
+    /// Returns `&mut Self` for method chaining.
+    pub fn set_gas_pool(&mut self, pool: Arc<GasPool>) -> &mut Self {
+        self.gas_pool = Some(pool);
+        self
+    }
+
+    /// Estimate the gas cost for the transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_data` - The transaction data to estimate
+    ///
+    /// # Returns
+    ///
+    /// Returns the estimated gas cost in MIST, or a `TransactionError` if estimation fails.
+    pub async fn estimate_gas(
+        &self,
+        transaction_data: &TransactionData,
+    ) -> Result<u64, TransactionError> {
+        // Use the client's dry run to estimate gas
+        let response = self
+            .retry
+            .run("dry_run_transaction_block", || {
+                self.client
+                    .read_api()
+                    .dry_run_transaction_block(transaction_data.clone())
+            })
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Gas estimation failed: {}", e)))?;
+
+        // Extract gas cost from effects
+        let effects = response.effects;
+        let gas_summary = effects.gas_cost_summary();
+        Ok(gas_summary.computation_cost + gas_summary.storage_cost - gas_summary.storage_rebate)
+    }
+
+    /// Build the transaction block
+    ///
+    /// This method finalizes the transaction, sets up gas, and returns the transaction data.
+    /// Without an explicit budget, the budget comes from the `GasPolicy`. The gas budget,
+    /// whether set or estimated, must not exceed the policy's `GasLimits` cap. The gas
+    /// price is the epoch's reference price from `GasPriceOracle::global()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the built `TransactionData`, or a `TransactionError` if building fails
+    /// (`GasPriceTooHigh` while the reference price is above `GasLimits::max_gas_price`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// let transaction_data = builder.build().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build(&mut self) -> Result<TransactionData, TransactionError> {
+        // Finish building the programmable transaction (takes ownership of builder)
+        let pt =
+            std::mem::replace(&mut self.builder, ProgrammableTransactionBuilder::new()).finish();
+
+        // Gas is paid by the sponsor if there is one
+        let gas_owner = self.sponsor.unwrap_or(self.signer);
+        let client = &self.client;
+        let retry = self.retry;
+
+        // Lease a coin from the gas pool, if there is one
+        if self.gas_object.is_none() {
+            if let Some(pool) = &self.gas_pool {
+                if pool.owner() != gas_owner {
+                    return Err(TransactionError::BuildError(format!(
+                        "Gas pool belongs to {}, but gas is paid by {}",
+                        pool.owner(),
+                        gas_owner
+                    )));
+                }
+                let lease = pool.acquire().await;
+                self.gas_object = Some(lease.coin_id());
+                self.gas_lease = Some(lease);
+            }
+        }
+
+        // Get or select a gas object with full reference
+        let gas_object_ref = if let Some(gas_obj_id) = self.gas_object {
+            // Get the full object reference for the specified gas object
+            let object = retry
+                .run("get_object", || {
+                    client.read_api().get_object_with_options(
+                        gas_obj_id,
+                        sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
+                    )
+                })
+                .await
+                .map_err(|e| {
+                    TransactionError::BuildError(format!("Failed to get gas object: {}", e))
+                })?
+                .into_object()
+                .map_err(|e| {
+                    TransactionError::BuildError(format!("Failed to convert gas object: {}", e))
+                })?;
+
+            // Use the object_ref() method to get the object reference tuple
+            object.object_ref()
+        } else {
+            // Get available gas objects for the gas owner
+            let gas_objects = retry
+                .run("get_coins", || {
+                    client.coin_read_api().get_coins(
+                        gas_owner,
+                        Some("0x2::sui::SUI".to_string()),
+                        None,
+                        None,
+                    )
+                })
+                .await
+                .map_err(|e| {
+                    TransactionError::BuildError(format!("Failed to get gas objects: {}", e))
+                })?;
+
+            let first_gas =
+                gas_objects
+                    .data
+                    .first()
+                    .ok_or_else(|| TransactionError::InsufficientGas {
+                        required: 0,
+                        available: 0,
+                    })?;
+
+            // Get the full object reference
+            let object = retry
+                .run("get_object", || {
+                    client.read_api().get_object_with_options(
+                        first_gas.coin_object_id,
+                        sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
+                    )
+                })
+                .await
+                .map_err(|e| {
+                    TransactionError::BuildError(format!("Failed to get gas object: {}", e))
+                })?
+                .into_object()
+                .map_err(|e| {
+                    TransactionError::BuildError(format!("Failed to convert gas object: {}", e))
+                })?;
+
+            // Use the object_ref() method to get the object reference tuple
+            object.object_ref()
+        };
+
+        // Determine gas budget
+        let budget = match self.gas_budget {
+            Some(budget) => GasBudget::Fixed(budget),
+            None => self.gas_policy.budget_for(&pt),
+        };
+        // Reference gas price, cached per epoch; refuse to build during a fee spike
+        let gas_price = retry
+            .run("get_reference_gas_price", || {
+                GasPriceOracle::global().reference_price(client)
+            })
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Failed to get gas price: {}", e)))?;
+        let gas_price = self.gas_policy.limits.check_price(gas_price)?;
+
+        // The oracle has just read the epoch, so an already expired transaction is
+        // rejected here instead of by the validators
+        if let (Some(expiration), Some(epoch)) = (self.expiration, GasPriceOracle::global().epoch())
+        {
+            if expiration < epoch {
+                return Err(TransactionError::BuildError(format!(
+                    "Expiration epoch {} has passed (current epoch {})",
+                    expiration, epoch
+                )));
+            }
+        }
+
+        let gas_budget = if let GasBudget::Fixed(budget) = budget {
+            budget
+        } else {
+            // Build a temporary transaction to estimate gas
+            let temp_tx = TransactionData::new_programmable_allow_sponsor(
+                self.signer,
+                vec![gas_object_ref],
+                pt.clone(),
+                self.gas_policy.limits.estimation_budget,
+                gas_price,
+                gas_owner,
+            );
+
+            let estimated = self.estimate_gas(&temp_tx).await?;
+            budget.with_buffer(estimated)
+        };
+        let gas_budget = self.gas_policy.limits.check(gas_budget)?;
+
+        // Build the final transaction
+        let mut transaction_data = TransactionData::new_programmable_allow_sponsor(
+            self.signer,
+            vec![gas_object_ref],
+            pt,
+            gas_budget,
+            gas_price,
+            gas_owner,
+        );
+        if let Some(epoch) = self.expiration {
+            let TransactionData::V1(data) = &mut transaction_data;
+            data.expiration = TransactionExpiration::Epoch(epoch);
+        }
+
+        Ok(transaction_data)
+    }
+
+    /// Build a sponsored transaction (see `set_sponsor`)
+    ///
+    /// The returned data can be handed to the sponsor for signing; its gas owner is the
+    /// sponsor and its sender is the signer.
+    ///
+    /// # Returns
+    ///
+    /// Returns the built `TransactionData`, or a `TransactionError` if no sponsor is set
+    /// or building fails.
+    pub async fn build_sponsored(&mut self) -> Result<TransactionData, TransactionError> {
+        self.require_sponsor()?;
+        self.build().await
+    }
+
+    fn require_sponsor(&self) -> Result<SuiAddress, TransactionError> {
+        self.sponsor.ok_or_else(|| {
+            TransactionError::BuildError("No sponsor set; call set_sponsor() first".to_string())
+        })
+    }
+
+    /// Build the transaction and return its digest without submitting it
+    ///
+    /// The built transaction is kept and submitted unchanged by the next `execute()`,
+    /// so the previewed digest is exactly the digest that will land on chain. This lets
+    /// approval workflows and audit logs reference a transaction before it is sent.
+    ///
+    /// Once a transaction is prepared, adding further operations returns an error;
+    /// gas settings changed after the preview are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `TransactionDigest` of the prepared transaction, or a `TransactionError` if building fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// let digest = builder.digest_preview().await?;
+    /// println!("About to submit {}", digest);
+    /// let response = builder.execute().await?;
+    /// assert_eq!(response.digest, digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn digest_preview(&mut self) -> Result<TransactionDigest, TransactionError> {
+        if let Some(prepared) = &self.prepared {
+            return Ok(prepared.digest());
+        }
+
+        let tx_data = self.build().await?;
+        let digest = tx_data.digest();
+        self.prepared = Some(tx_data);
+        Ok(digest)
+    }
+
+    /// Dry-run the transaction and return a structured preview of its effects
+    ///
+    /// Like `digest_preview()`, this prepares the transaction: the simulated
+    /// transaction is exactly the one the next `execute()` submits.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `SimulationResult` with the balance changes, object changes, gas cost,
+    /// and decoded abort (if execution would fail), or a `TransactionError` if building
+    /// or the dry run fails. A transaction that would abort is not an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// let simulation = builder.simulate().await?;
+    /// if let Some(abort) = &simulation.abort {
+    ///     println!("Would fail: {}", abort);
+    /// } else {
+    ///     println!("Would cost {} MIST", simulation.gas.net());
+    ///     builder.execute().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn simulate(&mut self) -> Result<SimulationResult, TransactionError> {
+        self.digest_preview().await?;
+        let tx_data = self
+            .prepared
+            .clone()
+            .expect("digest_preview prepares the transaction");
+
+        let response = self
+            .retry
+            .run("dry_run_transaction_block", || {
+                self.client
+                    .read_api()
+                    .dry_run_transaction_block(tx_data.clone())
+            })
+            .await
+            .map_err(|e| TransactionError::BuildError(format!("Simulation failed: {}", e)))?;
+
+        Ok(SimulationResult::from_response(response))
+    }
+
+    /// Get the transaction prepared by `digest_preview()`, if any
+    pub fn prepared_transaction(&self) -> Option<&TransactionData> {
+        self.prepared.as_ref()
+    }
+
+    /// Prepare the transaction and encode it as Base64 BCS for review
+    ///
+    /// Like `digest_preview()`, this prepares the transaction, so the encoded bytes are
+    /// exactly what the next `execute()` would submit. The encoding can be reviewed with
+    /// `review::describe_transaction` and signed elsewhere after `from_base64`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the Base64 transaction bytes, or a `TransactionError` if building fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// let tx_bytes = builder.to_base64().await?;
+    /// println!("{}", builder.describe()?);
+    /// std::fs::write("proposal.tx", tx_bytes)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn to_base64(&mut self) -> Result<String, TransactionError> {
+        self.digest_preview().await?;
+        review::encode_transaction(
+            self.prepared
+                .as_ref()
+                .expect("digest_preview prepares the transaction"),
+        )
+    }
+
+    /// Load a transaction encoded by `to_base64()` for signing
+    ///
+    /// The transaction is prepared as-is: `execute()` (or `execute_with_sponsor()` if
+    /// gas is paid by another address) signs and submits exactly these bytes, and no
+    /// operations can be added.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_with_signer` - A `SuiClientWithSigner` whose signer is the transaction's sender
+    /// * `tx_bytes` - The Base64 transaction bytes
+    ///
+    /// # Returns
+    ///
+    /// Returns a builder holding the transaction, or a `TransactionError` if the bytes do
+    /// not decode or the transaction was built for a different sender.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client_with_signer = create_client_with_key(Network::Mainnet, "suiprivkey1...").await?;
+    /// let tx_bytes = std::fs::read_to_string("proposal.tx")?;
+    /// let mut builder = CanaryTransactionBuilder::from_base64(client_with_signer, &tx_bytes)?;
+    /// println!("{}", builder.describe()?);
+    /// builder.execute().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_base64(
+        client_with_signer: SuiClientWithSigner,
+        tx_bytes: &str,
+    ) -> Result<Self, TransactionError> {
+        let tx_data = review::decode_transaction(tx_bytes)?;
+        let TransactionData::V1(data) = &tx_data;
+        if data.sender != client_with_signer.signer {
+            return Err(TransactionError::BuildError(format!(
+                "Transaction sender {} is not the signer {}",
+                data.sender, client_with_signer.signer
+            )));
+        }
+        let gas_owner = data.gas_data.owner;
+
+        let mut builder = Self::new(client_with_signer);
+        if gas_owner != builder.signer {
+            builder.sponsor = Some(gas_owner);
+        }
+        builder.prepared = Some(tx_data);
+        Ok(builder)
+    }
+
+    /// Render the prepared transaction for review (see `review::describe_transaction`)
+    ///
+    /// # Returns
+    ///
+    /// Returns the description, or a `TransactionError` if no transaction is prepared yet
+    /// (call `to_base64()`, `digest_preview()`, or `simulate()` first).
+    pub fn describe(&self) -> Result<String, TransactionError> {
+        self.prepared
+            .as_ref()
+            .map(describe_transaction)
+            .ok_or_else(|| {
+                TransactionError::BuildError(
+                    "No prepared transaction; call to_base64() or digest_preview() first"
+                        .to_string(),
+                )
+            })
+    }
+
+    fn ensure_not_prepared(&self) -> Result<(), TransactionError> {
+        if self.prepared.is_some() {
+            return Err(TransactionError::BuildError(
+                "Transaction already prepared by digest_preview(); execute it or start a new builder"
+                    .to_string(),
+            ));
+        }
+        Ok(())
     }
 
-    /// Estimate the gas cost for the transaction
-    ///
-    /// # Arguments
+    /// Execute the transaction
     ///
-    /// * `transaction_data` - The transaction data to estimate
+    /// This method builds, signs, and executes the transaction in one step.
+    /// Sponsored transactions must use `execute_with_sponsor` instead.
+    /// If a transaction was prepared with `digest_preview()`, that exact transaction is submitted.
     ///
     /// # Returns
     ///
-    /// Returns the estimated gas cost in MIST, or a `TransactionError` if estimation fails.
-    pub async fn estimate_gas(
-        &self,
-        transaction_data: &TransactionData,
-    ) -> Result<u64, TransactionError> {
-        // Use the client's dry run to estimate gas
-        let response = self
-            .client
-            .read_api()
-            .dry_run_transaction_block(transaction_data.clone())
-            .await
-            .map_err(|e| TransactionError::BuildError(format!("Gas estimation failed: {}", e)))?;
+    /// Returns the transaction response, or a `TransactionError` if execution fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client_with_signer = todo!();
+    /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+    /// // ... add operations ...
+    /// let response = builder.execute().await?;
+    /// println!("Transaction executed: {:?}", response.digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute(&mut self) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        if self.sponsor.is_some() {
+            return Err(TransactionError::BuildError(
+                "Sponsored transactions need the sponsor's signature; use execute_with_sponsor()"
+                    .to_string(),
+            ));
+        }
 
-        // Extract gas cost from effects
-        let effects = response.effects;
-        let gas_summary = effects.gas_cost_summary();
-        Ok(gas_summary.computation_cost + gas_summary.storage_cost - gas_summary.storage_rebate)
+        let tx_data = self.take_or_build().await?;
+        let signature = sign(&self.keystore, self.signer, &tx_data).await?;
+        let response = self.submit(tx_data, vec![signature.into()]).await;
+        self.gas_lease = None;
+        response
     }
 
-    /// Build the transaction block
+    /// Execute a sponsored transaction, signed by both the signer and the sponsor
     ///
-    /// This method finalizes the transaction, sets up gas, and returns the transaction data.
+    /// # Arguments
+    ///
+    /// * `sponsor_keystore` - A keystore holding the sponsor's key (see `set_sponsor`)
     ///
     /// # Returns
     ///
-    /// Returns the built `TransactionData`, or a `TransactionError` if building fails.
+    /// Returns the transaction response, or a `TransactionError` if no sponsor is set,
+    /// either signature fails, or execution fails.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use canary_sdk::keystore::create_keystore_from_key;
     /// use canary_sdk::transaction::CanaryTransactionBuilder;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client_with_signer = todo!();
+    /// let (sponsor_keystore, sponsor) = create_keystore_from_key("suiprivkey1...").await?;
     /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
     /// // ... add operations ...
-    /// let transaction_data = builder.build().await?;
+    /// builder.set_sponsor(sponsor);
+    /// let response = builder.execute_with_sponsor(&sponsor_keystore).await?;
+    /// println!("Sponsored transaction executed: {:?}", response.digest);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn build(&mut self) -> Result<TransactionData, TransactionError> {
-        // Finish building the programmable transaction (takes ownership of builder)
-        let pt =
-            std::mem::replace(&mut self.builder, ProgrammableTransactionBuilder::new()).finish();
-
-        // Get or select a gas object with full reference
-        let gas_object_ref = if let Some(gas_obj_id) = self.gas_object {
-            // Get the full object reference for the specified gas object
-            let object = self
-                .client
-                .read_api()
-                .get_object_with_options(
-                    gas_obj_id,
-                    sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
-                )
-                .await
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to get gas object: {}", e))
-                })?
-                .into_object()
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to convert gas object: {}", e))
-                })?;
-
-            // Use the object_ref() method to get the object reference tuple
-            object.object_ref()
-        } else {
-            // Get available gas objects for the signer
-            let gas_objects = self
-                .client
-                .coin_read_api()
-                .get_coins(self.signer, Some("0x2::sui::SUI".to_string()), None, None)
-                .await
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to get gas objects: {}", e))
-                })?;
-
-            let first_gas =
-                gas_objects
-                    .data
-                    .first()
-                    .ok_or_else(|| TransactionError::InsufficientGas {
-                        required: 0,
-                        available: 0,
-                    })?;
-
-            // Get the full object reference
-            let object = self
-                .client
-                .read_api()
-                .get_object_with_options(
-                    first_gas.coin_object_id,
-                    sui_sdk::rpc_types::SuiObjectDataOptions::full_content(),
-                )
-                .await
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to get gas object: {}", e))
-                })?
-                .into_object()
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to convert gas object: {}", e))
-                })?;
-
-            // Use the object_ref() method to get the object reference tuple
-            object.object_ref()
-        };
-
-        // Determine gas budget
-        let gas_budget = if let Some(budget) = self.gas_budget {
-            budget
-        } else {
-            // Get reference gas price first
-            let gas_price = self
-                .client
-                .read_api()
-                .get_reference_gas_price()
-                .await
-                .map_err(|e| {
-                    TransactionError::BuildError(format!("Failed to get gas price: {}", e))
-                })?;
-
-            // Build a temporary transaction to estimate gas
-            let temp_tx = TransactionData::new_programmable(
-                self.signer,
-                vec![gas_object_ref],
-                pt.clone(),
-                gas_price,
-                10_000_000, // Default budget for estimation
-            );
-
-            // Estimate gas and add 20% buffer
-            let estimated = self.estimate_gas(&temp_tx).await?;
-            estimated + (estimated / 5) // Add 20% buffer
-        };
-
-        // Get reference gas price
-        let gas_price = self
-            .client
-            .read_api()
-            .get_reference_gas_price()
-            .await
-            .map_err(|e| TransactionError::BuildError(format!("Failed to get gas price: {}", e)))?;
-
-        // Build the final transaction
-        let transaction_data = TransactionData::new_programmable(
-            self.signer,
-            vec![gas_object_ref],
-            pt,
-            gas_price,
-            gas_budget,
-        );
+    pub async fn execute_with_sponsor(
+        &mut self,
+        sponsor_keystore: &sui_keys::keystore::Keystore,
+    ) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        let sponsor = self.require_sponsor()?;
+        let tx_data = self.take_or_build().await?;
 
-        Ok(transaction_data)
+        let sender_signature = sign(&self.keystore, self.signer, &tx_data).await?;
+        let sponsor_signature = sign(sponsor_keystore, sponsor, &tx_data).await?;
+        let response = self
+            .submit(
+                tx_data,
+                vec![sender_signature.into(), sponsor_signature.into()],
+            )
+            .await;
+        self.gas_lease = None;
+        response
     }
 
-    /// Execute the transaction
+    /// Execute the transaction with a signature produced outside the keystore
     ///
-    /// This method builds, signs, and executes the transaction in one step.
+    /// Used for signers the keystore cannot sign for, such as a multisig account:
+    /// prepare the transaction with `digest_preview()`, have the participants sign
+    /// `prepared_transaction()`, and pass the combined signature here.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - The signer's signature over the prepared transaction
     ///
     /// # Returns
     ///
@@ -407,48 +1588,231 @@ impl CanaryTransactionBuilder {
     /// # Example
     ///
     /// ```rust,no_run
+    /// use canary_sdk::keystore::multisig::{sign_partial, Multisig};
     /// use canary_sdk::transaction::CanaryTransactionBuilder;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client_with_signer = todo!();
+    /// # let multisig: Multisig = todo!();
+    /// # let (keystore, participants): (sui_keys::keystore::Keystore, Vec<sui_sdk::types::base_types::SuiAddress>) = todo!();
+    /// // The builder's signer is the multisig address
     /// let mut builder = CanaryTransactionBuilder::new(client_with_signer);
     /// // ... add operations ...
-    /// let response = builder.execute().await?;
-    /// println!("Transaction executed: {:?}", response.digest);
+    /// builder.digest_preview().await?;
+    /// let tx_data = builder.prepared_transaction().unwrap().clone();
+    ///
+    /// let mut signatures = multisig.collect(&tx_data);
+    /// for participant in participants {
+    ///     signatures.add(sign_partial(&keystore, participant, &tx_data).await?)?;
+    /// }
+    /// let response = builder.execute_with_signature(signatures.combine()?).await?;
+    /// println!("Multisig transaction executed: {:?}", response.digest);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn execute(&mut self) -> Result<SuiTransactionBlockResponse, TransactionError> {
-        // Build the transaction
-        let tx_data = self.build().await?;
-        let signature = self
-            .keystore
-            .sign_secure(&self.signer, &tx_data, Intent::sui_transaction())
-            .await
-            .map_err(|e| {
-                TransactionError::BuildError(format!("Failed to sign transaction: {}", e))
-            })?;
+    pub async fn execute_with_signature(
+        &mut self,
+        signature: GenericSignature,
+    ) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        let tx_data = self.take_or_build().await?;
+        let response = self.submit(tx_data, vec![signature]).await;
+        self.gas_lease = None;
+        response
+    }
+
+    /// Execute the transaction, signed by a remote signer such as a cloud KMS key
+    ///
+    /// The builder's signer must be the remote signer's address (see
+    /// `keystore::kms::client_for`).
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction response, or a `TransactionError` if signing or
+    /// execution fails.
+    #[cfg(feature = "kms")]
+    pub async fn execute_with_remote_signer(
+        &mut self,
+        signer: &dyn crate::keystore::kms::RemoteSigner,
+    ) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        if signer.address() != self.signer {
+            return Err(TransactionError::BuildError(format!(
+                "Transaction signer {} is not the remote signer {}",
+                self.signer,
+                signer.address()
+            )));
+        }
+
+        let tx_data = self.take_or_build().await?;
+        let signature = signer.sign_transaction(&tx_data).await.map_err(|e| {
+            TransactionError::BuildError(format!(
+                "Failed to sign transaction as {}: {}",
+                self.signer, e
+            ))
+        })?;
+        let response = self.submit(tx_data, vec![signature.into()]).await;
+        self.gas_lease = None;
+        response
+    }
 
+    /// Use the previewed transaction if there is one, otherwise build it now
+    async fn take_or_build(&mut self) -> Result<TransactionData, TransactionError> {
+        match self.prepared.take() {
+            Some(prepared) => Ok(prepared),
+            None => self.build().await,
+        }
+    }
+
+    async fn submit(
+        &self,
+        tx_data: TransactionData,
+        signatures: Vec<GenericSignature>,
+    ) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        let digest = tx_data.digest();
+        let span = tracing::info_span!(
+            "transaction",
+            digest = %digest,
+            sender = %tx_data.sender()
+        );
+        let audit_record = AuditLog::installed().map(|_| AuditRecord::new(&tx_data));
+        // Resubmitting the same signed transaction is idempotent: a retry after a
+        // timeout returns the effects of the first submission if it went through
+        let transaction = Transaction::from_generic_sig_data(tx_data, signatures);
         let response = self
-            .client
-            .quorum_driver_api()
-            .execute_transaction_block(
-                Transaction::from_data(tx_data, vec![signature]),
-                SuiTransactionBlockResponseOptions::new()
-                    .with_effects()
-                    .with_events()
-                    .with_balance_changes(),
-                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
-            )
+            .retry
+            .run("execute_transaction_block", || {
+                self.client.quorum_driver_api().execute_transaction_block(
+                    transaction.clone(),
+                    SuiTransactionBlockResponseOptions::new()
+                        .with_effects()
+                        .with_events()
+                        .with_balance_changes()
+                        .with_object_changes(),
+                    Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+                )
+            })
+            .instrument(span.clone())
             .await
-            .map_err(|e| {
-                TransactionError::ExecutionError(format!("Failed to execute transaction: {}", e))
-            })?;
+            .map_err(|e| TransactionError::ExecutionError {
+                message: format!("Failed to execute transaction: {}", e),
+                digest: Some(digest),
+                status: None,
+                effects: None,
+                gas: None,
+            });
+        span.in_scope(|| audit_log::record_submission(audit_record, &response));
+        let response = response?;
+
+        if let Some(effects) = &response.effects {
+            let gas = effects.gas_cost_summary();
+            let gas_spent =
+                (gas.computation_cost + gas.storage_cost).saturating_sub(gas.storage_rebate);
+            let success = effects.status().is_ok();
+            span.in_scope(|| {
+                tracing::info!(success, gas_spent, "Transaction executed");
+            });
+            #[cfg(feature = "metrics")]
+            Metrics::global().record_transaction(success, gas_spent);
+        }
 
         Ok(response)
     }
 }
 
+/// Result of `publish_package`
+#[derive(Debug, Clone)]
+pub struct PublishedPackage {
+    /// The ID of the published package
+    pub package_id: ObjectID,
+    /// The package's `UpgradeCap`, owned by the signer
+    pub upgrade_cap_id: ObjectID,
+    /// The publish transaction, with effects and object changes
+    pub response: SuiTransactionBlockResponse,
+}
+
+/// Publish a Move package and find the package and `UpgradeCap` it created
+///
+/// Objects created by the package's `init` functions (e.g. the Canary `Registry` and
+/// `AdminCap`) are listed in the response's object changes.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner`; the signer pays for and owns the package
+/// * `compiled_modules` - The compiled modules (e.g. from `sui move build --dump-bytecode-as-base64`)
+/// * `dependencies` - The IDs of the packages the modules depend on
+///
+/// # Returns
+///
+/// Returns the package and `UpgradeCap` IDs, or a `TransactionError` if the
+/// transaction fails or publishes no package.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use canary_sdk::transaction::publish_package;
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example(modules: Vec<Vec<u8>>) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let dependencies = vec![ObjectID::from_hex_literal("0x1")?, ObjectID::from_hex_literal("0x2")?];
+/// let published = publish_package(client, modules, dependencies).await?;
+/// println!("Published package {}", published.package_id);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn publish_package(
+    client: SuiClientWithSigner,
+    compiled_modules: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+) -> Result<PublishedPackage, TransactionError> {
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.publish(compiled_modules, dependencies)?;
+    let response = builder.execute().await?;
+
+    let effects = response.effects.as_ref().ok_or_else(|| {
+        TransactionError::execution(format!("Transaction {} has no effects", response.digest))
+    })?;
+    if !effects.status().is_ok() {
+        return Err(TransactionError::failed_execution(effects));
+    }
+
+    let changes = response.object_changes.as_deref().unwrap_or_default();
+    let package_id = changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            TransactionError::execution(format!(
+                "Transaction {} published no package",
+                response.digest
+            ))
+        })?;
+    let upgrade_cap_id = changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_id,
+                object_type,
+                ..
+            } if object_type.to_string().ends_with("::package::UpgradeCap") => Some(*object_id),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            TransactionError::execution(format!(
+                "Transaction {} created no UpgradeCap",
+                response.digest
+            ))
+        })?;
+
+    Ok(PublishedPackage {
+        package_id,
+        upgrade_cap_id,
+        response,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,9 +1848,178 @@ mod tests {
             client,
             signer: address,
             keystore,
+            retry: RetryPolicy::none(),
+            profiles: Default::default(),
+            gas_policy: Default::default(),
+        }
+    }
+
+    fn sample_transaction_data(gas_budget: u64) -> TransactionData {
+        use sui_sdk::types::base_types::{ObjectDigest, SequenceNumber};
+
+        let sender = SuiAddress::from_str("0x1").unwrap();
+        let gas_ref = (
+            ObjectID::from_hex_literal("0x5").unwrap(),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::MIN,
+        );
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(sender, Some(1));
+        TransactionData::new_programmable(
+            sender,
+            vec![gas_ref],
+            builder.finish(),
+            1_000,
+            gas_budget,
+        )
+    }
+
+    #[test]
+    fn test_transaction_digest_deterministic() {
+        let a = sample_transaction_data(10_000_000);
+        let b = sample_transaction_data(10_000_000);
+        assert_eq!(transaction_digest(&a), transaction_digest(&b));
+    }
+
+    #[test]
+    fn test_transaction_digest_changes_with_data() {
+        let a = sample_transaction_data(10_000_000);
+        let b = sample_transaction_data(20_000_000);
+        assert_ne!(transaction_digest(&a), transaction_digest(&b));
+    }
+
+    #[test]
+    fn test_gas_limits_per_network() {
+        assert_eq!(GasLimits::default().max_budget, 1_000_000_000);
+        assert_eq!(
+            GasLimits::for_network(&Network::Custom("http://x".to_string())),
+            GasLimits::default()
+        );
+        assert!(
+            GasLimits::for_network(&Network::Testnet).max_budget > GasLimits::default().max_budget
+        );
+    }
+
+    #[test]
+    fn test_gas_limits_check() {
+        let limits = GasLimits::for_network(&Network::Mainnet);
+        assert_eq!(limits.check(1_000_000_000).unwrap(), 1_000_000_000);
+        match limits.check(1_000_000_001) {
+            Err(TransactionError::GasBudgetExceedsCap { budget, cap }) => {
+                assert_eq!(budget, 1_000_000_001);
+                assert_eq!(cap, 1_000_000_000);
+            }
+            other => panic!("Expected GasBudgetExceedsCap error, got: {:?}", other),
         }
     }
 
+    #[test]
+    fn test_gas_budget_buffer() {
+        assert_eq!(
+            GasBudget::Estimated { buffer_percent: 20 }.with_buffer(1_000),
+            1_200
+        );
+        assert_eq!(
+            GasBudget::Estimated { buffer_percent: 0 }.with_buffer(1_000),
+            1_000
+        );
+        assert_eq!(GasBudget::Fixed(5).with_buffer(1_000), 5);
+        assert_eq!(
+            GasBudget::Estimated { buffer_percent: 50 }.with_buffer(u64::MAX),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_gas_policy_overrides() {
+        use sui_types::Identifier;
+
+        let policy = GasPolicy::estimated(30).with_override(
+            "member_registry",
+            "remove_member",
+            GasBudget::Fixed(20_000_000),
+        );
+        let call = |module: &str, function: &str| {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.programmable_move_call(
+                ObjectID::from_single_byte(9),
+                Identifier::from_str(module).unwrap(),
+                Identifier::from_str(function).unwrap(),
+                vec![],
+                vec![],
+            );
+            builder.finish()
+        };
+
+        assert_eq!(
+            policy.budget_for(&call("member_registry", "remove_member")),
+            GasBudget::Fixed(20_000_000)
+        );
+        assert_eq!(
+            policy.budget_for(&call("member_registry", "join_registry")),
+            GasBudget::Estimated { buffer_percent: 30 }
+        );
+        assert_eq!(
+            GasPolicy::default().budget,
+            GasBudget::Estimated { buffer_percent: 20 }
+        );
+    }
+
+    #[test]
+    fn test_nested_results() {
+        assert!(nested_results(Argument::Result(2), 0).is_empty());
+        assert_eq!(
+            nested_results(Argument::Result(2), 1),
+            vec![Argument::Result(2)]
+        );
+        assert_eq!(
+            nested_results(Argument::Result(2), 3),
+            vec![
+                Argument::NestedResult(2, 0),
+                Argument::NestedResult(2, 1),
+                Argument::NestedResult(2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pure_args() {
+        let pure = |arg: CallArg| match arg {
+            CallArg::Pure(bytes) => bytes,
+            _ => panic!("Expected a pure argument"),
+        };
+
+        // Strings carry a length prefix
+        assert_eq!(pure(pure_string("abc")), vec![3, b'a', b'b', b'c']);
+        assert_eq!(pure(pure_u64(1)), vec![1, 0, 0, 0, 0, 0, 0, 0]);
+        // Addresses and IDs do not
+        let id = ObjectID::from_single_byte(7);
+        assert_eq!(pure(pure_address(id)), id.to_vec());
+        assert_eq!(pure(pure_address(SuiAddress::from(id))), id.to_vec());
+        assert_eq!(
+            pure(pure_vec(&["a", "bc"]).unwrap()),
+            vec![2, 1, b'a', 2, b'b', b'c']
+        );
+        assert_eq!(pure(pure_vec::<u64>(&[]).unwrap()), vec![0]);
+    }
+
+    #[test]
+    fn test_parse_type_tag() {
+        assert_eq!(parse_type_tag("u64").unwrap(), TypeTag::U64);
+        assert_eq!(
+            parse_type_tag(" vector<u8> ").unwrap(),
+            TypeTag::Vector(Box::new(TypeTag::U8))
+        );
+        assert!(parse_type_tag("0x2::coin::Coin<0x2::sui::SUI>").is_ok());
+
+        let error = |type_arg: &str| parse_type_tag(type_arg).unwrap_err().to_string();
+        assert!(error("").contains("empty type"));
+        assert!(error("0x2::coin::Coin<0x2::sui::SUI").contains("missing closing `>`"));
+        assert!(error("u64>").contains("unmatched `>` at position 3"));
+        assert!(error("0x2::SUI").contains("address::module::Name"));
+        assert!(error("u65").contains("`u65`"));
+    }
+
     #[test]
     fn test_new_builder() {
         // This test requires network, so we'll test the structure separately
@@ -591,6 +2124,43 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[ignore] // Requires network connection
+    async fn test_sponsor_required_for_sponsored_flow() {
+        let client_with_signer = create_test_client_with_signer().await;
+        let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+        assert!(matches!(
+            builder.build_sponsored().await,
+            Err(TransactionError::BuildError(_))
+        ));
+
+        // A sponsored transaction cannot be executed with the sender's signature alone
+        builder.set_sponsor(SuiAddress::from_str("0x5").unwrap());
+        match builder.execute().await {
+            Err(TransactionError::BuildError(message)) => {
+                assert!(message.contains("execute_with_sponsor"))
+            }
+            other => panic!("Expected BuildError, got: {:?}", other.map(|r| r.digest)),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires network connection and gas objects
+    async fn test_expired_transaction_rejected() {
+        let client_with_signer = create_test_client_with_signer().await;
+        let mut builder = CanaryTransactionBuilder::new(client_with_signer);
+        builder
+            .transfer_sui(SuiAddress::from_str("0x1").unwrap(), 1)
+            .unwrap();
+
+        // Every public network is past epoch 0
+        builder.set_expiration(0);
+        match builder.build().await {
+            Err(TransactionError::BuildError(message)) => assert!(message.contains("has passed")),
+            other => panic!("Expected BuildError, got: {:?}", other.is_ok()),
+        }
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection, valid key, and gas
     async fn test_execute_requires_build() {
@@ -614,7 +2184,7 @@ mod tests {
                 // Verify it's a transaction error
                 match e {
                     TransactionError::BuildError(_) => assert!(true),
-                    TransactionError::ExecutionError(_) => assert!(true),
+                    TransactionError::ExecutionError { .. } => assert!(true),
                     TransactionError::InsufficientGas { .. } => assert!(true),
                     _ => assert!(false, "Unexpected error type"),
                 }
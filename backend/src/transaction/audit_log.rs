@@ -0,0 +1,456 @@
+//! Append-only audit log of executed transactions
+//!
+//! Compliance reviews of admin key usage need a record of every transaction the key
+//! signed, kept independently of any RPC node. Once an `AuditLog` is installed with
+//! `AuditLog::install`, every transaction the SDK submits (through any `execute*`
+//! method of `CanaryTransactionBuilder`, and so through every helper in `canary`) is
+//! appended to it as one JSON line: digest, operation, inputs, signer, gas, and outcome.
+//!
+//! The log only ever appends. When the file would grow beyond `max_bytes`, it is
+//! renamed to `<path>.1` (shifting older files to `<path>.2`, ...) and a new file is
+//! started; files beyond `max_files` are deleted. `AuditLog::query` reads all files,
+//! oldest first.
+//!
+//! A record is written after submission, with the outcome. If writing it fails, the
+//! error is logged; the transaction has already been submitted and is not affected.
+
+use super::review::{describe_command, describe_input};
+use crate::error::{AuditLogError, TransactionError};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_sdk::rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::transaction::{Command, TransactionData, TransactionKind};
+
+/// Default size at which the log is rotated (10 MiB)
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Default number of rotated files kept
+pub const DEFAULT_MAX_FILES: usize = 5;
+
+/// Outcome of an audited transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    /// Executed successfully
+    Success,
+    /// Executed and failed (e.g. a Move abort); gas was still charged
+    Failure,
+    /// Submission failed; the transaction may or may not have executed
+    Unknown,
+}
+
+/// One executed transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the record was written, in Unix milliseconds
+    pub timestamp_ms: u64,
+    /// The transaction digest
+    pub digest: TransactionDigest,
+    /// The calls the transaction made, e.g. `member_registry::join_registry`
+    pub operation: String,
+    /// The transaction inputs, as listed by `describe_transaction`
+    pub arguments: Vec<String>,
+    /// The commands, as listed by `describe_transaction`
+    pub commands: Vec<String>,
+    /// The sender, whose key signed the transaction
+    pub signer: SuiAddress,
+    /// The gas owner, if a sponsor paid for gas
+    pub sponsor: Option<SuiAddress>,
+    /// Gas budget in MIST
+    pub gas_budget: u64,
+    /// Gas price in MIST per unit
+    pub gas_price: u64,
+    /// Net gas charged in MIST, once the transaction executed
+    pub gas_used: Option<u64>,
+    /// The outcome
+    pub status: AuditStatus,
+    /// Why the transaction failed, if it did
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// A record of `tx_data` with an `Unknown` outcome
+    pub fn new(tx_data: &TransactionData) -> Self {
+        let TransactionData::V1(data) = tx_data;
+        let (operation, arguments, commands) = match &data.kind {
+            TransactionKind::ProgrammableTransaction(pt) => (
+                pt.commands
+                    .iter()
+                    .map(operation_of)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                pt.inputs.iter().map(describe_input).collect(),
+                pt.commands.iter().map(describe_command).collect(),
+            ),
+            other => (format!("{:?}", other), Vec::new(), Vec::new()),
+        };
+
+        Self {
+            timestamp_ms: now_ms(),
+            digest: tx_data.digest(),
+            operation,
+            arguments,
+            commands,
+            signer: data.sender,
+            sponsor: (data.gas_data.owner != data.sender).then_some(data.gas_data.owner),
+            gas_budget: data.gas_data.budget,
+            gas_price: data.gas_data.price,
+            gas_used: None,
+            status: AuditStatus::Unknown,
+            error: None,
+        }
+    }
+
+    /// Fill in the outcome of submitting the transaction
+    pub fn set_outcome(&mut self, result: &Result<SuiTransactionBlockResponse, TransactionError>) {
+        self.timestamp_ms = now_ms();
+        let effects = match result {
+            Ok(response) => response.effects.as_ref(),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+        let Some(effects) = effects else {
+            return;
+        };
+
+        let gas = effects.gas_cost_summary();
+        self.gas_used =
+            Some((gas.computation_cost + gas.storage_cost).saturating_sub(gas.storage_rebate));
+        match effects.status() {
+            SuiExecutionStatus::Success => self.status = AuditStatus::Success,
+            SuiExecutionStatus::Failure { error } => {
+                self.status = AuditStatus::Failure;
+                self.error = Some(error.clone());
+            }
+        }
+    }
+}
+
+/// Which records `AuditLog::query` returns
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only transactions signed by this address
+    pub signer: Option<SuiAddress>,
+    /// Only transactions whose operation contains this text (e.g. `store_blob`)
+    pub operation: Option<String>,
+    /// Only the transaction with this digest
+    pub digest: Option<TransactionDigest>,
+    /// Only records written at or after this time (Unix milliseconds)
+    pub since_ms: Option<u64>,
+    /// Only records written before this time (Unix milliseconds)
+    pub until_ms: Option<u64>,
+    /// Only the newest matching records
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, record: &AuditRecord) -> bool {
+        self.signer.is_none_or(|signer| record.signer == signer)
+            && self
+                .operation
+                .as_ref()
+                .is_none_or(|operation| record.operation.contains(operation.as_str()))
+            && self.digest.is_none_or(|digest| record.digest == digest)
+            && self
+                .since_ms
+                .is_none_or(|since| record.timestamp_ms >= since)
+            && self
+                .until_ms
+                .is_none_or(|until| record.timestamp_ms < until)
+    }
+}
+
+/// A JSONL file of `AuditRecord`s, rotated by size
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    // Serializes appends and rotation within the process
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// A log at `path`, rotated at `DEFAULT_MAX_BYTES` keeping `DEFAULT_MAX_FILES`
+    ///
+    /// The file is created on the first append.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Rotate when the file would exceed `max_bytes`, keeping `max_files` rotated files
+    pub fn with_rotation(mut self, max_bytes: u64, max_files: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self.max_files = max_files;
+        self
+    }
+
+    /// Record every transaction the SDK executes from now on in this log
+    ///
+    /// # Returns
+    ///
+    /// Returns the installed log, or `AuditLogError::AlreadyInstalled` if a log was
+    /// installed before.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::transaction::audit_log::{AuditLog, AuditQuery};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let log = AuditLog::new("/var/log/canary/transactions.jsonl").install()?;
+    /// // ... execute transactions ...
+    /// let query = AuditQuery {
+    ///     operation: Some("store_blob".to_string()),
+    ///     limit: Some(10),
+    ///     ..Default::default()
+    /// };
+    /// for record in log.query(&query)? {
+    ///     println!("{} {} by {}", record.digest, record.operation, record.signer);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn install(self) -> Result<&'static AuditLog, AuditLogError> {
+        let mut log = Some(self);
+        let installed = INSTALLED.get_or_init(|| log.take().expect("initialized once"));
+        match log {
+            None => Ok(installed),
+            Some(_) => Err(AuditLogError::AlreadyInstalled),
+        }
+    }
+
+    /// The installed log, if there is one
+    pub fn installed() -> Option<&'static AuditLog> {
+        INSTALLED.get()
+    }
+
+    /// Path of the current file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record, rotating first if it would not fit
+    pub fn append(&self, record: &AuditRecord) -> Result<(), AuditLogError> {
+        let mut line = serde_json::to_vec(record).map_err(|e| AuditLogError::Io(e.to_string()))?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let size = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(io_error(&self.path, e)),
+        };
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| io_error(&self.path, e))?;
+        file.write_all(&line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| io_error(&self.path, e))
+    }
+
+    /// The records matching `query`, oldest first
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditRecord>, AuditLogError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut records = Vec::new();
+        for index in (0..=self.max_files).rev() {
+            let path = self.file(index);
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(io_error(&path, e)),
+            };
+            for (number, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| io_error(&path, e))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: AuditRecord =
+                    serde_json::from_str(&line).map_err(|e| AuditLogError::InvalidRecord {
+                        file: path.display().to_string(),
+                        line: number + 1,
+                        message: e.to_string(),
+                    })?;
+                if query.matches(&record) {
+                    records.push(record);
+                }
+            }
+        }
+
+        if let Some(limit) = query.limit {
+            let skip = records.len().saturating_sub(limit);
+            records.drain(..skip);
+        }
+        Ok(records)
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1` and the current file to `<path>.1`
+    fn rotate(&self) -> Result<(), AuditLogError> {
+        if self.max_files == 0 {
+            return std::fs::remove_file(&self.path).map_err(|e| io_error(&self.path, e));
+        }
+        let oldest = self.file(self.max_files);
+        match std::fs::remove_file(&oldest) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(io_error(&oldest, e)),
+        }
+        for index in (0..self.max_files).rev() {
+            let from = self.file(index);
+            if from.exists() {
+                let to = self.file(index + 1);
+                std::fs::rename(&from, &to).map_err(|e| io_error(&from, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The current file for index 0, rotated file `index` otherwise
+    fn file(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            return self.path.clone();
+        }
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+}
+
+static INSTALLED: OnceLock<AuditLog> = OnceLock::new();
+
+/// Write the outcome of a submitted transaction to the installed log, if any
+pub(super) fn record_submission(
+    record: Option<AuditRecord>,
+    result: &Result<SuiTransactionBlockResponse, TransactionError>,
+) {
+    let (Some(log), Some(mut record)) = (AuditLog::installed(), record) else {
+        return;
+    };
+    record.set_outcome(result);
+    if let Err(e) = log.append(&record) {
+        tracing::error!(digest = %record.digest, "Failed to write audit record: {}", e);
+    }
+}
+
+/// The short name of a command, e.g. `member_registry::join_registry` or `transfer`
+fn operation_of(command: &Command) -> String {
+    match command {
+        Command::MoveCall(call) => format!("{}::{}", call.module, call.function),
+        Command::TransferObjects(..) => "transfer".to_string(),
+        Command::SplitCoins(..) => "split".to_string(),
+        Command::MergeCoins(..) => "merge".to_string(),
+        Command::Publish(..) => "publish".to_string(),
+        Command::Upgrade(..) => "upgrade".to_string(),
+        Command::MakeMoveVec(..) => "make_move_vec".to_string(),
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> AuditLogError {
+    AuditLogError::Io(format!("{}: {}", path.display(), e))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use sui_sdk::types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+    use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    fn record(sender: &str, amount: u64) -> AuditRecord {
+        let sender = SuiAddress::from_str(sender).unwrap();
+        let gas_ref = (
+            ObjectID::from_hex_literal("0x5").unwrap(),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::MIN,
+        );
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(sender, Some(amount));
+        AuditRecord::new(&TransactionData::new_programmable(
+            sender,
+            vec![gas_ref],
+            builder.finish(),
+            1_000,
+            750,
+        ))
+    }
+
+    #[test]
+    fn test_record() {
+        let record = record("0x1", 1);
+        assert_eq!(record.operation, "split, transfer");
+        assert_eq!(record.signer, SuiAddress::from_str("0x1").unwrap());
+        assert_eq!(record.sponsor, None);
+        assert_eq!((record.gas_budget, record.gas_price), (1_000, 750));
+        assert_eq!(record.status, AuditStatus::Unknown);
+        assert_eq!(record.commands.len(), 2);
+    }
+
+    #[test]
+    fn test_append_query_and_rotate() {
+        let dir = std::env::temp_dir().join(format!("canary-audit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("transactions.jsonl");
+        let line_len = serde_json::to_vec(&record("0x1", 1)).unwrap().len() as u64 + 1;
+        // Two records per file, one rotated file kept
+        let log = AuditLog::new(&path).with_rotation(2 * line_len, 1);
+
+        for amount in 1..=5 {
+            let sender = if amount % 2 == 0 { "0x2" } else { "0x1" };
+            log.append(&record(sender, amount)).unwrap();
+        }
+        assert!(log.file(1).exists());
+        assert!(!log.file(2).exists());
+
+        // The first file was deleted by the second rotation
+        let all = log.query(&AuditQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].digest, record("0x1", 5).digest);
+
+        let by_signer = log
+            .query(&AuditQuery {
+                signer: Some(SuiAddress::from_str("0x2").unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_signer.len(), 1);
+        assert_eq!(by_signer[0].digest, record("0x2", 4).digest);
+
+        let newest = log
+            .query(&AuditQuery {
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(newest[0].digest, record("0x1", 5).digest);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
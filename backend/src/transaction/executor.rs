@@ -0,0 +1,226 @@
+//! Concurrent transaction execution
+//!
+//! Transactions from one signer can run in parallel as long as they don't use the
+//! same owned objects: two in-flight transactions spending the same owned object (or
+//! gas coin) equivocate, and one of them fails or the object is locked until the end
+//! of the epoch. `ExecutorPool` queues transactions per object they touch and runs
+//! transactions with disjoint objects in parallel, up to a concurrency limit.
+//!
+//! Callers declare the owned and shared objects each transaction uses. The gas coin is
+//! handled by the pool: with a `GasPool` each transaction leases its own coin;
+//! without one, transactions paid by the same address are serialized.
+
+use super::CanaryTransactionBuilder;
+use crate::error::TransactionError;
+use crate::gas::GasPool;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use sui_sdk::rpc_types::SuiTransactionBlockResponse;
+use sui_sdk::types::base_types::ObjectID;
+use tokio::sync::{OwnedMutexGuard, Semaphore};
+
+/// Runs transactions in parallel without letting two of them use the same object
+pub struct ExecutorPool {
+    permits: Arc<Semaphore>,
+    locks: Arc<ObjectLocks>,
+    gas_pool: Option<Arc<GasPool>>,
+}
+
+impl ExecutorPool {
+    /// Create a pool running at most `concurrency` transactions at once
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use canary_sdk::client::{create_client_with_key, Network};
+    /// use canary_sdk::gas::{GasPool, DEFAULT_MIN_BALANCE};
+    /// use canary_sdk::transaction::executor::ExecutorPool;
+    /// use canary_sdk::transaction::CanaryTransactionBuilder;
+    /// use std::sync::Arc;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+    /// let gas = GasPool::load(&client.client, client.signer, DEFAULT_MIN_BALANCE).await?;
+    /// let executor = ExecutorPool::new(4).with_gas_pool(Arc::new(gas));
+    ///
+    /// let object_id = ObjectID::from_hex_literal("0x123...")?;
+    /// let builder = CanaryTransactionBuilder::new(client);
+    /// // ... add operations using object_id ...
+    /// let response = executor.execute(builder, &[object_id]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
+            locks: Arc::new(ObjectLocks::default()),
+            gas_pool: None,
+        }
+    }
+
+    /// Lease gas coins from `pool` for builders without a gas object or pool of their own
+    pub fn with_gas_pool(mut self, pool: Arc<GasPool>) -> Self {
+        self.gas_pool = Some(pool);
+        self
+    }
+
+    /// Execute a transaction once no other transaction is using its objects
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The transaction to execute
+    /// * `objects` - The owned and shared objects the transaction uses, excluding gas
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction response, or a `TransactionError` if building or
+    /// execution fails.
+    pub async fn execute(
+        &self,
+        mut builder: CanaryTransactionBuilder,
+        objects: &[ObjectID],
+    ) -> Result<SuiTransactionBlockResponse, TransactionError> {
+        if builder.gas_object.is_none() && builder.gas_pool.is_none() {
+            if let Some(pool) = &self.gas_pool {
+                builder.set_gas_pool(pool.clone());
+            }
+        }
+
+        let mut keys: BTreeSet<ObjectID> = objects.iter().copied().collect();
+        if let Some(gas_object) = builder.gas_object {
+            keys.insert(gas_object);
+        } else if builder.gas_pool.is_none() {
+            // Without a pool the builder pays with the payer's first coin
+            keys.insert(ObjectID::from(builder.sponsor.unwrap_or(builder.signer)));
+        }
+
+        let _guards = self.locks.lock(keys).await;
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| TransactionError::execution(e.to_string()))?;
+        builder.execute().await
+    }
+
+    /// Execute several transactions, in parallel where their objects allow
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Each transaction with the objects it uses, as for `execute`
+    ///
+    /// # Returns
+    ///
+    /// Returns one result per job, in the order of `jobs`.
+    pub async fn execute_all(
+        &self,
+        jobs: Vec<(CanaryTransactionBuilder, Vec<ObjectID>)>,
+    ) -> Vec<Result<SuiTransactionBlockResponse, TransactionError>> {
+        futures::future::join_all(
+            jobs.into_iter()
+                .map(|(builder, objects)| async move { self.execute(builder, &objects).await }),
+        )
+        .await
+    }
+}
+
+/// One async lock per object, created on demand
+#[derive(Default)]
+struct ObjectLocks {
+    locks: Mutex<HashMap<ObjectID, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+/// Guards of the objects locked for one transaction
+struct ObjectGuards<'a> {
+    locks: &'a ObjectLocks,
+    guards: Vec<(ObjectID, OwnedMutexGuard<()>)>,
+}
+
+impl ObjectLocks {
+    /// Lock every object in `keys`
+    ///
+    /// Locks are taken in ID order, so transactions sharing several objects cannot
+    /// deadlock.
+    async fn lock(&self, keys: BTreeSet<ObjectID>) -> ObjectGuards<'_> {
+        let mut guards = Vec::with_capacity(keys.len());
+        for key in keys {
+            let lock = self
+                .map()
+                .entry(key)
+                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+                .clone();
+            guards.push((key, lock.lock_owned().await));
+        }
+        ObjectGuards {
+            locks: self,
+            guards,
+        }
+    }
+
+    fn map(&self) -> std::sync::MutexGuard<'_, HashMap<ObjectID, Arc<tokio::sync::Mutex<()>>>> {
+        self.locks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for ObjectGuards<'_> {
+    fn drop(&mut self) {
+        let keys: Vec<ObjectID> = self.guards.iter().map(|(key, _)| *key).collect();
+        self.guards.clear();
+
+        // Forget locks nobody else holds or waits for
+        let mut map = self.locks.map();
+        for key in keys {
+            if map
+                .get(&key)
+                .is_some_and(|lock| Arc::strong_count(lock) == 1)
+            {
+                map.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn keys(ids: &[u8]) -> BTreeSet<ObjectID> {
+        ids.iter()
+            .map(|&id| ObjectID::from_single_byte(id))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_objects_are_serialized() {
+        let locks = Arc::new(ObjectLocks::default());
+        let held = locks.lock(keys(&[1, 2])).await;
+
+        let waiter = tokio::spawn({
+            let locks = locks.clone();
+            async move {
+                let _guards = locks.lock(keys(&[2, 3])).await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(held);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(locks.map().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disjoint_objects_run_in_parallel() {
+        let locks = ObjectLocks::default();
+        let _a = locks.lock(keys(&[1])).await;
+        let b = tokio::time::timeout(Duration::from_secs(1), locks.lock(keys(&[2]))).await;
+        assert!(b.is_ok());
+    }
+}
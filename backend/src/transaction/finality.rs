@@ -0,0 +1,179 @@
+//! Waiting for transactions to become final
+//!
+//! `execute()` returns as soon as the fullnode has executed the transaction locally.
+//! `wait_for_transaction` polls the transaction until its effects are available and,
+//! by default, until it is included in a checkpoint, so callers such as the worker can
+//! confirm that the effects landed before acting on them.
+
+use crate::error::TransactionError;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+
+/// How long and how often `wait_for_transaction` polls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinalityOptions {
+    /// Give up after this long
+    pub timeout: Duration,
+    /// Delay between polls
+    pub poll_interval: Duration,
+    /// Wait for checkpoint inclusion, not just for effects
+    pub require_checkpoint: bool,
+}
+
+impl Default for FinalityOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_millis(500),
+            require_checkpoint: true,
+        }
+    }
+}
+
+/// How far a transaction has progressed towards finality
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FinalityStatus {
+    /// Effects are final but the transaction is not yet in a checkpoint
+    Executed,
+    /// The transaction is included in a checkpoint
+    Checkpointed {
+        /// Sequence number of the checkpoint
+        checkpoint: u64,
+        /// Checkpoint timestamp, in milliseconds
+        timestamp_ms: Option<u64>,
+    },
+}
+
+impl FinalityStatus {
+    /// Status of a transaction as reported by a fullnode
+    pub fn of(response: &SuiTransactionBlockResponse) -> Self {
+        match response.checkpoint {
+            Some(checkpoint) => FinalityStatus::Checkpointed {
+                checkpoint,
+                timestamp_ms: response.timestamp_ms,
+            },
+            None => FinalityStatus::Executed,
+        }
+    }
+
+    /// Whether the transaction is included in a checkpoint
+    pub fn is_checkpointed(&self) -> bool {
+        matches!(self, FinalityStatus::Checkpointed { .. })
+    }
+}
+
+/// A transaction that reached the requested finality
+#[derive(Debug, Clone)]
+pub struct FinalizedTransaction {
+    /// How far the transaction progressed
+    pub status: FinalityStatus,
+    /// The transaction with its effects, events, and balance changes
+    pub response: SuiTransactionBlockResponse,
+}
+
+/// Wait until a transaction is final
+///
+/// Not-found errors and other failed polls are retried until the timeout, since a
+/// fullnode may not know a transaction submitted through another one yet. The result
+/// reports finality, not success: check the response's effects status for that.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClient` for querying
+/// * `digest` - The transaction digest
+/// * `options` - Timeout, poll interval, and whether to wait for a checkpoint
+///
+/// # Returns
+///
+/// Returns the finalized transaction, or `TransactionError::FinalityTimeout` if it
+/// did not reach the requested finality in time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::transaction::finality::{wait_for_transaction, FinalityOptions};
+/// use sui_sdk::types::digests::TransactionDigest;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Devnet).await?;
+/// let digest: TransactionDigest = "11111111111111111111111111111111".parse()?;
+/// let finalized = wait_for_transaction(&client, digest, FinalityOptions::default()).await?;
+/// println!("{:?}", finalized.status);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn wait_for_transaction(
+    client: &SuiClient,
+    digest: TransactionDigest,
+    options: FinalityOptions,
+) -> Result<FinalizedTransaction, TransactionError> {
+    let started = Instant::now();
+    let mut executed = false;
+    loop {
+        match client
+            .read_api()
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionBlockResponseOptions::new()
+                    .with_effects()
+                    .with_events()
+                    .with_balance_changes(),
+            )
+            .await
+        {
+            Ok(response) if response.effects.is_some() => {
+                let status = FinalityStatus::of(&response);
+                if status.is_checkpointed() || !options.require_checkpoint {
+                    return Ok(FinalizedTransaction { status, response });
+                }
+                executed = true;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::debug!(%digest, "Transaction not available yet: {}", e),
+        }
+
+        let waited = started.elapsed();
+        if waited >= options.timeout {
+            return Err(TransactionError::FinalityTimeout {
+                digest: digest.to_string(),
+                waited,
+                executed,
+            });
+        }
+        tokio::time::sleep(options.poll_interval.min(options.timeout - waited)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options_wait_for_checkpoint() {
+        let options = FinalityOptions::default();
+        assert!(options.require_checkpoint);
+        assert!(options.poll_interval < options.timeout);
+    }
+
+    #[test]
+    fn test_finality_status() {
+        let mut response = SuiTransactionBlockResponse::new(TransactionDigest::ZERO);
+        assert_eq!(FinalityStatus::of(&response), FinalityStatus::Executed);
+
+        response.checkpoint = Some(42);
+        response.timestamp_ms = Some(1_700_000_000_000);
+        let status = FinalityStatus::of(&response);
+        assert!(status.is_checkpointed());
+        assert_eq!(
+            status,
+            FinalityStatus::Checkpointed {
+                checkpoint: 42,
+                timestamp_ms: Some(1_700_000_000_000)
+            }
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Review-then-sign serialization of transactions
+//!
+//! Admin operations often need a second pair of eyes before the key holder signs
+//! them. `CanaryTransactionBuilder::to_base64` prepares a transaction and encodes it
+//! as Base64 BCS, the same `tx_bytes` format the Sui CLI and wallets use, so it can be
+//! stored or handed to a reviewer. `describe_transaction` renders it in plain text,
+//! and `CanaryTransactionBuilder::from_base64` loads it again on the signing machine,
+//! where `execute()` submits exactly the reviewed bytes.
+//!
+//! The encoding is deterministic: the same transaction always has the same bytes, and
+//! so the same digest.
+
+use crate::error::TransactionError;
+use base64::Engine;
+use std::fmt::Write;
+use sui_sdk::types::transaction::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionData,
+    TransactionExpiration, TransactionKind,
+};
+
+/// Encode a transaction as Base64 BCS
+pub fn encode_transaction(tx_data: &TransactionData) -> Result<String, TransactionError> {
+    let bytes = bcs::to_bytes(tx_data).map_err(|e| {
+        TransactionError::BuildError(format!("Failed to encode transaction: {}", e))
+    })?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// Decode a transaction encoded by `encode_transaction` (or the Sui CLI's `tx_bytes`)
+pub fn decode_transaction(tx_bytes: &str) -> Result<TransactionData, TransactionError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(tx_bytes.trim())
+        .map_err(|e| TransactionError::BuildError(format!("Invalid transaction Base64: {}", e)))?;
+    bcs::from_bytes(&bytes)
+        .map_err(|e| TransactionError::BuildError(format!("Invalid transaction bytes: {}", e)))
+}
+
+/// Render a transaction for human review
+///
+/// Lists the digest, sender, gas payment, expiration, inputs, and commands, one per
+/// line. Commands refer to inputs and earlier results by index, as in the transaction
+/// itself (`input 0`, `result 1`, `result 1.0`, `gas`).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::transaction::review::{decode_transaction, describe_transaction};
+///
+/// # fn example(tx_bytes: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// let tx_data = decode_transaction(tx_bytes)?;
+/// println!("{}", describe_transaction(&tx_data));
+/// # Ok(())
+/// # }
+/// ```
+pub fn describe_transaction(tx_data: &TransactionData) -> String {
+    let TransactionData::V1(data) = tx_data;
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Transaction {}", tx_data.digest());
+    let _ = writeln!(out, "Sender: {}", data.sender);
+    let _ = writeln!(
+        out,
+        "Gas: budget {} MIST at {} MIST/unit, paid by {}",
+        data.gas_data.budget, data.gas_data.price, data.gas_data.owner
+    );
+    for (id, version, _) in &data.gas_data.payment {
+        let _ = writeln!(out, "  coin {} (version {})", id, version.value());
+    }
+    match data.expiration {
+        TransactionExpiration::Epoch(epoch) => {
+            let _ = writeln!(out, "Expires: after epoch {}", epoch);
+        }
+        _ => {
+            let _ = writeln!(out, "Expires: never");
+        }
+    }
+
+    match &data.kind {
+        TransactionKind::ProgrammableTransaction(pt) => describe_programmable(&mut out, pt),
+        other => {
+            let _ = writeln!(out, "Kind: {:?}", other);
+        }
+    }
+    out
+}
+
+fn describe_programmable(out: &mut String, pt: &ProgrammableTransaction) {
+    let _ = writeln!(out, "Inputs:");
+    for (index, input) in pt.inputs.iter().enumerate() {
+        let _ = writeln!(out, "  {}: {}", index, describe_input(input));
+    }
+    let _ = writeln!(out, "Commands:");
+    for (index, command) in pt.commands.iter().enumerate() {
+        let _ = writeln!(out, "  {}: {}", index, describe_command(command));
+    }
+}
+
+pub(super) fn describe_input(input: &CallArg) -> String {
+    match input {
+        CallArg::Pure(bytes) => {
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            format!("pure 0x{} ({} bytes)", hex, bytes.len())
+        }
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, version, _))) => {
+            format!("object {} (version {})", id, version.value())
+        }
+        CallArg::Object(ObjectArg::SharedObject { id, mutability, .. }) => {
+            format!("shared object {} ({:?})", id, mutability)
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+pub(super) fn describe_command(command: &Command) -> String {
+    match command {
+        Command::MoveCall(call) => {
+            let type_arguments = if call.type_arguments.is_empty() {
+                String::new()
+            } else {
+                let types: Vec<String> = call
+                    .type_arguments
+                    .iter()
+                    .map(|tag| tag.to_canonical_string(true))
+                    .collect();
+                format!("<{}>", types.join(", "))
+            };
+            format!(
+                "call {}::{}::{}{}({})",
+                call.package,
+                call.module,
+                call.function,
+                type_arguments,
+                describe_arguments(&call.arguments)
+            )
+        }
+        Command::TransferObjects(objects, recipient) => format!(
+            "transfer [{}] to {}",
+            describe_arguments(objects),
+            describe_argument(recipient)
+        ),
+        Command::SplitCoins(coin, amounts) => format!(
+            "split {} into [{}]",
+            describe_argument(coin),
+            describe_arguments(amounts)
+        ),
+        Command::MergeCoins(coin, sources) => format!(
+            "merge [{}] into {}",
+            describe_arguments(sources),
+            describe_argument(coin)
+        ),
+        Command::Publish(modules, dependencies) => format!(
+            "publish {} module(s), {} dependencies",
+            modules.len(),
+            dependencies.len()
+        ),
+        Command::Upgrade(modules, _, package, ticket) => format!(
+            "upgrade {} with {} module(s), ticket {}",
+            package,
+            modules.len(),
+            describe_argument(ticket)
+        ),
+        other => format!("{:?}", other),
+    }
+}
+
+fn describe_arguments(arguments: &[Argument]) -> String {
+    arguments
+        .iter()
+        .map(describe_argument)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_argument(argument: &Argument) -> String {
+    match argument {
+        Argument::GasCoin => "gas".to_string(),
+        Argument::Input(index) => format!("input {}", index),
+        Argument::Result(index) => format!("result {}", index),
+        Argument::NestedResult(index, value) => format!("result {}.{}", index, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use sui_sdk::types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+    use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    fn sample() -> TransactionData {
+        let sender = SuiAddress::from_str("0x1").unwrap();
+        let gas_ref = (
+            ObjectID::from_hex_literal("0x5").unwrap(),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::MIN,
+        );
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(sender, Some(1));
+        TransactionData::new_programmable(sender, vec![gas_ref], builder.finish(), 1_000, 750)
+    }
+
+    #[test]
+    fn test_roundtrip_is_deterministic() {
+        let tx_data = sample();
+        let encoded = encode_transaction(&tx_data).unwrap();
+        assert_eq!(encode_transaction(&sample()).unwrap(), encoded);
+
+        let decoded = decode_transaction(&format!("{}\n", encoded)).unwrap();
+        assert_eq!(decoded.digest(), tx_data.digest());
+        assert!(decode_transaction("not base64!").is_err());
+        assert!(decode_transaction("AAAA").is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let description = describe_transaction(&sample());
+        assert!(description.starts_with(&format!("Transaction {}", sample().digest())));
+        assert!(description.contains("Gas: budget 1000 MIST at 750 MIST/unit"));
+        assert!(description.contains("Expires: never"));
+        assert!(description.contains("split gas into [input "));
+        assert!(description.contains("transfer [result 0] to input "));
+    }
+}
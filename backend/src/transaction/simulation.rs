@@ -0,0 +1,271 @@
+//! Structured dry-run results
+//!
+//! `CanaryTransactionBuilder::simulate` dry-runs a transaction and returns a
+//! `SimulationResult`, so callers can preview its balance changes, object changes, and
+//! gas cost before paying for it. When the transaction would abort, the Move abort is
+//! decoded from the execution status and, for the Canary modules, named after the
+//! contract's error constant.
+
+use crate::error::CanaryError;
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::{
+    BalanceChange, DryRunTransactionBlockResponse, ObjectChange, SuiExecutionStatus,
+    SuiTransactionBlockEffectsAPI,
+};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::gas::GasCostSummary;
+
+/// Outcome of a dry-run transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    /// Digest the transaction would have
+    pub digest: TransactionDigest,
+    /// Whether execution would succeed
+    pub success: bool,
+    /// The execution error, if execution would fail
+    pub error: Option<String>,
+    /// The decoded Move abort, if execution would fail with one
+    pub abort: Option<MoveAbort>,
+    /// Gas the transaction would cost
+    pub gas: GasSummary,
+    /// Balance changes per owner and coin type
+    pub balance_changes: Vec<BalanceChange>,
+    /// Objects the transaction would create, mutate, transfer, or delete
+    pub object_changes: Vec<ObjectChange>,
+}
+
+impl SimulationResult {
+    /// Summarize a dry-run response
+    pub fn from_response(response: DryRunTransactionBlockResponse) -> Self {
+        let effects = &response.effects;
+        let (success, error) = match effects.status() {
+            SuiExecutionStatus::Success => (true, None),
+            SuiExecutionStatus::Failure { error } => (false, Some(error.clone())),
+        };
+
+        Self {
+            digest: *effects.transaction_digest(),
+            success,
+            abort: error.as_deref().and_then(MoveAbort::parse),
+            error,
+            gas: GasSummary::from(effects.gas_cost_summary()),
+            balance_changes: response.balance_changes,
+            object_changes: response.object_changes,
+        }
+    }
+}
+
+/// Gas cost of a transaction, in MIST
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSummary {
+    /// Cost of computation
+    pub computation_cost: u64,
+    /// Cost of storing new or mutated objects
+    pub storage_cost: u64,
+    /// Rebate for storage freed by mutated or deleted objects
+    pub storage_rebate: u64,
+    /// Part of the storage cost that is not refundable
+    pub non_refundable_storage_fee: u64,
+}
+
+impl GasSummary {
+    /// Net gas charged: computation plus storage minus rebate
+    ///
+    /// Negative when the rebate exceeds the costs, e.g. when deleting objects.
+    pub fn net(&self) -> i64 {
+        self.computation_cost as i64 + self.storage_cost as i64 - self.storage_rebate as i64
+    }
+}
+
+impl From<&GasCostSummary> for GasSummary {
+    fn from(summary: &GasCostSummary) -> Self {
+        Self {
+            computation_cost: summary.computation_cost,
+            storage_cost: summary.storage_cost,
+            storage_rebate: summary.storage_rebate,
+            non_refundable_storage_fee: summary.non_refundable_storage_fee,
+        }
+    }
+}
+
+/// A Move abort decoded from an execution error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveAbort {
+    /// The module that aborted
+    pub module: String,
+    /// The function that aborted, if known
+    pub function: Option<String>,
+    /// The abort code
+    pub code: u64,
+    /// The name of the error constant, for Canary modules
+    pub name: Option<String>,
+    /// Index of the command that aborted, if known
+    pub command: Option<u64>,
+}
+
+impl MoveAbort {
+    /// Decode a `MoveAbort(...)` execution error
+    ///
+    /// # Returns
+    ///
+    /// Returns the decoded abort, or `None` if the error is not a Move abort.
+    pub fn parse(error: &str) -> Option<Self> {
+        let start = error.find("MoveAbort(")?;
+        let body = &error[start..];
+
+        let module = quoted_after(body, "name: Identifier(\"")?;
+        let function = quoted_after(body, "function_name: Some(\"");
+        // The abort code follows the location struct: `MoveAbort(MoveLocation { .. }, 1)`
+        let after_location = &body[body.rfind("}, ")? + 3..];
+        let code = leading_number(after_location)?;
+        let command = error
+            .find(" in command ")
+            .and_then(|index| leading_number(&error[index + " in command ".len()..]));
+
+        Some(Self {
+            name: error_name(&module, code).map(str::to_string),
+            module,
+            function,
+            code,
+            command,
+        })
+    }
+}
+
+impl std::fmt::Display for MoveAbort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.module)?;
+        if let Some(function) = &self.function {
+            write!(f, "::{}", function)?;
+        }
+        write!(f, " aborted with code {}", self.code)?;
+        if let Some(name) = &self.name {
+            write!(f, " ({})", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Typed error for a Canary contract abort, keeping module and code for unknown aborts
+impl From<MoveAbort> for CanaryError {
+    fn from(abort: MoveAbort) -> Self {
+        match abort.name.as_deref() {
+            Some("EInsufficientPayment") => CanaryError::InsufficientFee,
+            Some("EAlreadyMember") => CanaryError::AlreadyMember,
+            Some("ENotAdmin") => CanaryError::NotAdmin,
+            Some("ENotMember") => CanaryError::NotMember,
+            Some("EInvalidCap") => CanaryError::InvalidCap,
+            Some("EDerivedObjectAlreadyExists") => CanaryError::DomainTaken,
+            _ => CanaryError::MoveAbort {
+                module: abort.module,
+                function: abort.function,
+                code: abort.code,
+            },
+        }
+    }
+}
+
+/// Name of a Canary contract error constant
+fn error_name(module: &str, code: u64) -> Option<&'static str> {
+    match (module, code) {
+        ("member_registry", 0) => Some("EInsufficientPayment"),
+        ("member_registry", 1) => Some("EAlreadyMember"),
+        ("member_registry", 2) => Some("ENotAdmin"),
+        ("member_registry", 3) => Some("ENotMember"),
+        ("member_registry", 4) => Some("EInvalidCap"),
+        ("pkg_storage", 1) => Some("EDerivedObjectAlreadyExists"),
+        _ => None,
+    }
+}
+
+/// The text between `prefix` and the next double quote
+fn quoted_after(text: &str, prefix: &str) -> Option<String> {
+    let rest = &text[text.find(prefix)? + prefix.len()..];
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+/// The decimal number at the start of `text`
+fn leading_number(text: &str) -> Option<u64> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABORT: &str = "MoveAbort(MoveLocation { module: ModuleId { address: \
+        0000000000000000000000000000000000000000000000000000000000000abc, \
+        name: Identifier(\"member_registry\") }, function: 1, instruction: 14, \
+        function_name: Some(\"join_registry\") }, 1) in command 0";
+
+    #[test]
+    fn test_parse_move_abort() {
+        let abort = MoveAbort::parse(ABORT).unwrap();
+        assert_eq!(abort.module, "member_registry");
+        assert_eq!(abort.function.as_deref(), Some("join_registry"));
+        assert_eq!(abort.code, 1);
+        assert_eq!(abort.name.as_deref(), Some("EAlreadyMember"));
+        assert_eq!(abort.command, Some(0));
+        assert_eq!(
+            abort.to_string(),
+            "member_registry::join_registry aborted with code 1 (EAlreadyMember)"
+        );
+    }
+
+    #[test]
+    fn test_parse_non_abort() {
+        assert!(MoveAbort::parse("InsufficientGas").is_none());
+    }
+
+    #[test]
+    fn test_unknown_module_has_no_name() {
+        let error = ABORT.replace("member_registry", "other");
+        let abort = MoveAbort::parse(&error).unwrap();
+        assert_eq!(abort.code, 1);
+        assert!(abort.name.is_none());
+    }
+
+    #[test]
+    fn test_abort_to_canary_error() {
+        let abort = MoveAbort::parse(ABORT).unwrap();
+        assert!(matches!(
+            CanaryError::from(abort),
+            CanaryError::AlreadyMember
+        ));
+
+        let error = ABORT.replace(
+            "Identifier(\"member_registry\")",
+            "Identifier(\"pkg_storage\")",
+        );
+        let abort = MoveAbort::parse(&error).unwrap();
+        assert!(matches!(CanaryError::from(abort), CanaryError::DomainTaken));
+
+        let error = ABORT.replace("}, 1) in", "}, 42) in");
+        match CanaryError::from(MoveAbort::parse(&error).unwrap()) {
+            CanaryError::MoveAbort {
+                module,
+                function,
+                code,
+            } => {
+                assert_eq!(module, "member_registry");
+                assert_eq!(function.as_deref(), Some("join_registry"));
+                assert_eq!(code, 42);
+            }
+            other => panic!("Expected MoveAbort error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gas_summary_net() {
+        let gas = GasSummary {
+            computation_cost: 1_000,
+            storage_cost: 500,
+            storage_rebate: 2_000,
+            non_refundable_storage_fee: 10,
+        };
+        assert_eq!(gas.net(), -500);
+    }
+}
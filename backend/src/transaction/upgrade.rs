@@ -0,0 +1,269 @@
+//! Move package upgrades
+//!
+//! An upgrade is one programmable transaction of three commands:
+//! `package::authorize_upgrade` turns the `UpgradeCap` into an `UpgradeTicket` for the
+//! new modules' digest, the `Upgrade` command publishes the new version, and
+//! `package::commit_upgrade` hands the resulting `UpgradeReceipt` back to the cap.
+//! `upgrade_package` builds and runs that transaction from the cap alone.
+//!
+//! Objects keep the type of the package version that defined them, so code that
+//! derives the package from an object's type (as the canary helpers do) still finds
+//! the original package after an upgrade. `PackageVersions` tracks which package
+//! replaced which, and `CanaryTransactionBuilder::move_call` resolves every call
+//! through `PackageVersions::global()`, so calls go to the latest known version.
+
+use super::CanaryTransactionBuilder;
+use crate::client::SuiClientWithSigner;
+use crate::error::TransactionError;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use sui_sdk::rpc_types::{
+    ObjectChange, SuiObjectDataOptions, SuiParsedData, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_sdk::types::base_types::{ObjectID, SequenceNumber};
+use sui_sdk::types::object::Owner;
+
+/// Which changes an upgrade may make, as enforced by the `UpgradeCap`
+///
+/// Policies only get stricter: a cap restricted to `Additive` cannot authorize a
+/// `Compatible` upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradePolicy {
+    /// Change function bodies and add new functions and types
+    Compatible,
+    /// Only add new functions and types
+    Additive,
+    /// Only change dependencies
+    DepOnly,
+}
+
+impl UpgradePolicy {
+    /// The policy's on-chain code (`sui::package::COMPATIBLE` etc.)
+    pub fn code(self) -> u8 {
+        match self {
+            UpgradePolicy::Compatible => 0,
+            UpgradePolicy::Additive => 128,
+            UpgradePolicy::DepOnly => 192,
+        }
+    }
+}
+
+/// Result of `upgrade_package`
+#[derive(Debug, Clone)]
+pub struct UpgradedPackage {
+    /// The ID of the new package version
+    pub package_id: ObjectID,
+    /// The ID of the version it replaced
+    pub previous_package_id: ObjectID,
+    /// The new package version
+    pub version: SequenceNumber,
+    /// The upgrade transaction, with effects and object changes
+    pub response: SuiTransactionBlockResponse,
+}
+
+/// Which package version replaced which
+#[derive(Debug, Default)]
+pub struct PackageVersions {
+    upgrades: Mutex<HashMap<ObjectID, ObjectID>>,
+}
+
+impl PackageVersions {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide tracker used by `CanaryTransactionBuilder::move_call`
+    pub fn global() -> &'static PackageVersions {
+        static GLOBAL: OnceLock<PackageVersions> = OnceLock::new();
+        GLOBAL.get_or_init(PackageVersions::new)
+    }
+
+    /// Record that `upgraded` replaced `previous`
+    ///
+    /// `upgrade_package` records its upgrades; call this for upgrades made by another
+    /// process, e.g. with the package IDs from a deployment config.
+    pub fn record(&self, previous: ObjectID, upgraded: ObjectID) {
+        if previous != upgraded {
+            self.upgrades
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(previous, upgraded);
+        }
+    }
+
+    /// The latest known version of a package, or `package` itself if it was never
+    /// upgraded
+    pub fn latest(&self, package: ObjectID) -> ObjectID {
+        let upgrades = self.upgrades.lock().unwrap_or_else(|e| e.into_inner());
+        let mut latest = package;
+        // Bounded by the number of upgrades, so a cycle can't loop forever
+        for _ in 0..upgrades.len() {
+            match upgrades.get(&latest) {
+                Some(next) => latest = *next,
+                None => break,
+            }
+        }
+        latest
+    }
+}
+
+/// Upgrade a Move package with the authorize/upgrade/commit transaction
+///
+/// The package to upgrade is the one the `UpgradeCap` currently points at. On success
+/// the upgrade is recorded in `PackageVersions::global()`.
+///
+/// # Arguments
+///
+/// * `client` - A `SuiClientWithSigner` owning the `UpgradeCap`
+/// * `upgrade_cap_id` - The package's `UpgradeCap` object ID
+/// * `compiled_modules` - The new version's compiled modules
+/// * `dependencies` - The IDs of the packages the modules depend on
+/// * `policy` - The upgrade policy to authorize, at least as strict as the cap's
+///
+/// # Returns
+///
+/// Returns the new and previous package IDs, or a `TransactionError` if the signer
+/// does not own the cap, the transaction fails, or it publishes no package.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::client::{create_client_with_key, Network};
+/// use canary_sdk::transaction::upgrade::{upgrade_package, UpgradePolicy};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example(modules: Vec<Vec<u8>>) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_client_with_key(Network::Devnet, "suiprivkey1...").await?;
+/// let upgrade_cap_id = ObjectID::from_hex_literal("0x123...")?;
+/// let dependencies = vec![ObjectID::from_hex_literal("0x1")?, ObjectID::from_hex_literal("0x2")?];
+/// let upgraded = upgrade_package(
+///     client,
+///     upgrade_cap_id,
+///     modules,
+///     dependencies,
+///     UpgradePolicy::Compatible,
+/// )
+/// .await?;
+/// println!("{} -> {}", upgraded.previous_package_id, upgraded.package_id);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn upgrade_package(
+    client: SuiClientWithSigner,
+    upgrade_cap_id: ObjectID,
+    compiled_modules: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+    policy: UpgradePolicy,
+) -> Result<UpgradedPackage, TransactionError> {
+    let upgrade_cap = client
+        .client
+        .read_api()
+        .get_object_with_options(
+            upgrade_cap_id,
+            SuiObjectDataOptions::new().with_owner().with_content(),
+        )
+        .await
+        .map_err(|e| TransactionError::BuildError(format!("Failed to get UpgradeCap: {}", e)))?
+        .into_object()
+        .map_err(|_| TransactionError::ObjectNotFound(upgrade_cap_id.into()))?;
+
+    if !matches!(upgrade_cap.owner, Some(Owner::AddressOwner(owner)) if owner == client.signer) {
+        return Err(TransactionError::BuildError(format!(
+            "UpgradeCap {} is not owned by {}",
+            upgrade_cap_id, client.signer
+        )));
+    }
+    let previous_package_id = match &upgrade_cap.content {
+        Some(SuiParsedData::MoveObject(object)) => object.fields.clone().to_json_value()["package"]
+            .as_str()
+            .and_then(|package| ObjectID::from_hex_literal(package).ok()),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        TransactionError::BuildError(format!("Object {} is not an UpgradeCap", upgrade_cap_id))
+    })?;
+
+    let mut builder = CanaryTransactionBuilder::new(client);
+    builder.upgrade(
+        upgrade_cap.object_ref(),
+        previous_package_id,
+        compiled_modules,
+        dependencies,
+        policy,
+    )?;
+    let response = builder.execute().await?;
+
+    let effects = response.effects.as_ref().ok_or_else(|| {
+        TransactionError::execution(format!("Transaction {} has no effects", response.digest))
+    })?;
+    if !effects.status().is_ok() {
+        return Err(TransactionError::failed_execution(effects));
+    }
+
+    let (package_id, version) = response
+        .object_changes
+        .iter()
+        .flatten()
+        .find_map(|change| match change {
+            ObjectChange::Published {
+                package_id,
+                version,
+                ..
+            } => Some((*package_id, *version)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            TransactionError::execution(format!(
+                "Transaction {} published no package",
+                response.digest
+            ))
+        })?;
+
+    PackageVersions::global().record(previous_package_id, package_id);
+    tracing::info!(
+        "Upgraded package {} to {} (version {})",
+        previous_package_id,
+        package_id,
+        version
+    );
+
+    Ok(UpgradedPackage {
+        package_id,
+        previous_package_id,
+        version,
+        response,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_versions() {
+        let versions = PackageVersions::new();
+        let v1 = ObjectID::from_single_byte(1);
+        let v2 = ObjectID::from_single_byte(2);
+        let v3 = ObjectID::from_single_byte(3);
+        assert_eq!(versions.latest(v1), v1);
+
+        versions.record(v1, v2);
+        versions.record(v2, v3);
+        assert_eq!(versions.latest(v1), v3);
+        assert_eq!(versions.latest(v2), v3);
+        assert_eq!(versions.latest(v3), v3);
+
+        // A cycle from a bad record ends instead of looping
+        versions.record(v3, v1);
+        versions.latest(v1);
+    }
+
+    #[test]
+    fn test_policy_codes() {
+        assert_eq!(UpgradePolicy::Compatible.code(), 0);
+        assert_eq!(UpgradePolicy::Additive.code(), 128);
+        assert_eq!(UpgradePolicy::DepOnly.code(), 192);
+    }
+}
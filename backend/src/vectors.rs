@@ -0,0 +1,377 @@
+//! Canonical test vectors
+//!
+//! Verifiers reimplemented outside this crate (a JS verifier, Python tooling) must
+//! derive the same canary addresses, statement hashes, and signatures. This module
+//! publishes fixed inputs with the outputs this crate produces for them:
+//!
+//! - canary addresses: `canary::compute_canary_address` for a registry, Canary
+//!   package, domain, and package, covering non-ASCII domains and domains longer than
+//!   127 bytes (a two-byte BCS length)
+//! - statement hashes: `statement::statement_hash` of exact statement texts
+//! - signatures: Ed25519 signatures of Sui personal messages, as `notarize` signs
+//!   receipts, from a fixed test key; Ed25519 is deterministic, so the signature bytes
+//!   are fixed too
+//!
+//! `to_json` renders the vectors for other languages. The `check_*` functions run an
+//! implementation against them and return the first mismatch; `check_all` checks this
+//! crate's own implementation. Without the `signing` feature it checks that the
+//! expected signatures verify instead of signing.
+//!
+//! The test key is public. Never use it for anything but these vectors.
+
+use crate::canary::compute_canary_address;
+use crate::error::VectorError;
+use crate::statement::statement_hash;
+use base64::Engine;
+use serde::Serialize;
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+#[cfg(feature = "signing")]
+use sui_sdk::types::crypto::SuiKeyPair;
+use sui_sdk::types::crypto::{Signature, SuiSignature, ToFromBytes};
+
+/// Inputs and expected output of a canary address derivation
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CanaryAddressVector {
+    /// The Registry object ID
+    pub registry_id: &'static str,
+    /// The original ID of the Canary package
+    pub canary_package_id: &'static str,
+    /// The domain name
+    pub domain: &'static str,
+    /// The attested package ID
+    pub package_id: &'static str,
+    /// The expected canary address
+    pub expected_address: &'static str,
+}
+
+/// A statement text and its expected hash
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct StatementHashVector {
+    /// The exact statement text
+    pub statement: &'static str,
+    /// The expected hex-encoded SHA-256
+    pub expected_sha256: &'static str,
+}
+
+/// A personal message signed with a fixed Ed25519 key
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SignatureVector {
+    /// The hex-encoded 32-byte Ed25519 private key
+    pub private_key: &'static str,
+    /// The signed message (UTF-8)
+    pub message: &'static str,
+    /// The expected Sui address of the key
+    pub expected_address: &'static str,
+    /// The expected Base64 Sui signature (`flag || signature || public key`)
+    pub expected_signature: &'static str,
+}
+
+/// Every test vector
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TestVectors {
+    /// Canary address derivations
+    pub canary_addresses: &'static [CanaryAddressVector],
+    /// Statement hashes
+    pub statement_hashes: &'static [StatementHashVector],
+    /// Personal message signatures
+    pub signatures: &'static [SignatureVector],
+}
+
+const REGISTRY_ID: &str = "0x5f3c8e9a1b2d4c6e8f0a1b2c3d4e5f60718293a4b5c6d7e8f9a0b1c2d3e4f5a6";
+const CANARY_PACKAGE_ID: &str =
+    "0x0c4a8e2f6b1d3e5f7a9c0b2d4e6f8a1c3e5b7d9f0a2c4e6b8d0f1a3c5e7b9d1f";
+const PACKAGE_ID: &str = "0x7d2e4f6a8c0b1d3e5f7a9b0c2d4e6f8a1b3c5d7e9f0a2b4c6d8e0f1a3b5c7d9e";
+const TEST_PRIVATE_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const TEST_ADDRESS: &str = "0x160179a1565ea7cff27ead23f54cc7f50893bf58155cd7285156e57afa31c3ac";
+
+/// The published test vectors
+pub const VECTORS: TestVectors = TestVectors {
+    canary_addresses: &[
+        CanaryAddressVector {
+            registry_id: REGISTRY_ID,
+            canary_package_id: CANARY_PACKAGE_ID,
+            domain: "example.com",
+            package_id: PACKAGE_ID,
+            expected_address: "0x0fcbccb079d573fff3cab5736ee4ce12272f2ca1bc1b950dc46b17f5fcb935e7",
+        },
+        CanaryAddressVector {
+            registry_id: REGISTRY_ID,
+            canary_package_id: CANARY_PACKAGE_ID,
+            domain: "bücher.example",
+            package_id: PACKAGE_ID,
+            expected_address: "0x8e66e17b12f62fe72b1e01005247eb39490af5cdd5cdfe1cf3979314b5e2fa9a",
+        },
+        CanaryAddressVector {
+            registry_id: "0x1",
+            canary_package_id: "0x2",
+            domain:
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.\
+                     bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb.example",
+            package_id: "0x3",
+            expected_address: "0x66d17ff57ec983e4340af6b17e92b78013c358c4ec0cdd4f28f0a93d8bc96b28",
+        },
+    ],
+    statement_hashes: &[
+        StatementHashVector {
+            statement: "",
+            expected_sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        },
+        StatementHashVector {
+            statement: "Issued: 2024-01-01\nExpires: 2024-04-01\n\n\
+                        - We have not received any National Security Letters.\n\
+                        - We have not been asked to weaken our build pipeline.\n",
+            expected_sha256: "71b68c20725c9c8f5851ac075e4c246570ea0f4e6238d17338d27c46e27acf0f",
+        },
+        StatementHashVector {
+            statement: "Issued: 2024-01-01\r\n- Ünïcödé assertion\r\n",
+            expected_sha256: "1a0fb3f9948ce27d0db2d93511d2c82d4542092078acda433d660977356deffb",
+        },
+    ],
+    signatures: &[
+        SignatureVector {
+            private_key: TEST_PRIVATE_KEY,
+            message: "canary",
+            expected_address: TEST_ADDRESS,
+            expected_signature:
+                "APED0jNNZrUNDmJgejLd/8ZGDw4jphVDFnu7ckTremnDMkWrDLAk2sEl+Gs1yM2fLJGw\
+                                 4TEp/JnBJTd3d3LjqgMDoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+        },
+        SignatureVector {
+            private_key: TEST_PRIVATE_KEY,
+            message: r#"{"domain":"example.com","package_id":"0x3"}"#,
+            expected_address: TEST_ADDRESS,
+            expected_signature:
+                "ADn3IV4Be3A2dd5nnbg9+Wdf81KZ2GE9JtgmtDlZv6lGJy/dWuKQvyzhkAW5sqa744BI\
+                                 /gtyTFdPDpQ3uyVSkwQDoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+        },
+        SignatureVector {
+            private_key: TEST_PRIVATE_KEY,
+            message: "",
+            expected_address: TEST_ADDRESS,
+            expected_signature:
+                "AEgNA/0DwZUZs+3zu/gtObEFX9UZ3pq4oa+PF1pWL/pNI1l2CpFjtaLWgqYdTm+vHzvL\
+                                 /qNBNbdXl6SO0PYFNg8DoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+        },
+    ],
+};
+
+/// The test vectors as pretty-printed JSON
+pub fn to_json() -> String {
+    serde_json::to_string_pretty(&VECTORS).expect("test vectors serialize")
+}
+
+/// Check a canary address derivation against the vectors
+///
+/// # Example
+///
+/// ```rust
+/// use canary_sdk::canary::compute_canary_address;
+/// use canary_sdk::vectors::{check_canary_addresses, parse_id};
+///
+/// check_canary_addresses(|v| {
+///     compute_canary_address(
+///         parse_id(v.registry_id)?,
+///         parse_id(v.canary_package_id)?,
+///         v.domain,
+///         parse_id(v.package_id)?,
+///     )
+///     .map_err(|e| canary_sdk::error::VectorError::Invalid(e.to_string()))
+/// })
+/// .unwrap();
+/// ```
+pub fn check_canary_addresses<F>(mut derive: F) -> Result<(), VectorError>
+where
+    F: FnMut(&CanaryAddressVector) -> Result<SuiAddress, VectorError>,
+{
+    for vector in VECTORS.canary_addresses {
+        let expected = parse_address(vector.expected_address)?;
+        let actual = derive(vector)?;
+        if actual != expected {
+            return Err(mismatch(
+                format!("canary address of {}", vector.domain),
+                expected,
+                actual,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a statement hash implementation against the vectors
+///
+/// Hashes are compared as lowercase hex.
+pub fn check_statement_hashes<F>(mut hash: F) -> Result<(), VectorError>
+where
+    F: FnMut(&str) -> String,
+{
+    for vector in VECTORS.statement_hashes {
+        let actual = hash(vector.statement).to_lowercase();
+        if actual != vector.expected_sha256 {
+            return Err(mismatch(
+                format!("statement hash of {:?}", vector.statement),
+                vector.expected_sha256,
+                actual,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a personal message signer against the vectors
+///
+/// `sign` returns the key's address and the Base64 Sui signature of the message.
+pub fn check_signatures<F>(mut sign: F) -> Result<(), VectorError>
+where
+    F: FnMut(&SignatureVector) -> Result<(SuiAddress, String), VectorError>,
+{
+    for vector in VECTORS.signatures {
+        let expected_address = parse_address(vector.expected_address)?;
+        let (address, signature) = sign(vector)?;
+        if address != expected_address {
+            return Err(mismatch(
+                format!("address of signature key for {:?}", vector.message),
+                expected_address,
+                address,
+            ));
+        }
+        if signature != vector.expected_signature {
+            return Err(mismatch(
+                format!("signature of {:?}", vector.message),
+                vector.expected_signature,
+                signature,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every expected signature verifies for its expected address
+///
+/// This is the check a verifier that never signs needs: it decodes each signature and
+/// verifies it over the personal message, as `verify_receipt` does.
+pub fn verify_signatures() -> Result<(), VectorError> {
+    for vector in VECTORS.signatures {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(vector.expected_signature)
+            .map_err(|e| VectorError::Invalid(format!("Invalid base64: {}", e)))?;
+        let signature =
+            Signature::from_bytes(&bytes).map_err(|e| VectorError::Invalid(e.to_string()))?;
+        signature
+            .verify_secure(
+                &personal_message(vector.message),
+                parse_address(vector.expected_address)?,
+                signature.scheme(),
+            )
+            .map_err(|e| {
+                mismatch(
+                    format!("signature of {:?}", vector.message),
+                    "a valid signature",
+                    e,
+                )
+            })?;
+    }
+    Ok(())
+}
+
+/// Check this crate's implementation against every vector
+pub fn check_all() -> Result<(), VectorError> {
+    check_canary_addresses(|v| {
+        compute_canary_address(
+            parse_id(v.registry_id)?,
+            parse_id(v.canary_package_id)?,
+            v.domain,
+            parse_id(v.package_id)?,
+        )
+        .map_err(|e| VectorError::Invalid(e.to_string()))
+    })?;
+    check_statement_hashes(statement_hash)?;
+    verify_signatures()?;
+    #[cfg(feature = "signing")]
+    check_signatures(sign_personal_message)?;
+    Ok(())
+}
+
+/// Parse an object ID of a vector
+pub fn parse_id(id: &str) -> Result<ObjectID, VectorError> {
+    ObjectID::from_hex_literal(id)
+        .map_err(|e| VectorError::Invalid(format!("Invalid object ID {}: {}", id, e)))
+}
+
+fn parse_address(address: &str) -> Result<SuiAddress, VectorError> {
+    SuiAddress::from_str(address)
+        .map_err(|e| VectorError::Invalid(format!("Invalid address {}: {}", address, e)))
+}
+
+/// Sign a vector's message the way `notarize` signs receipts
+#[cfg(feature = "signing")]
+fn sign_personal_message(vector: &SignatureVector) -> Result<(SuiAddress, String), VectorError> {
+    // Ed25519 keys are flag 0x00 followed by the private key
+    let mut bytes = vec![0x00];
+    bytes.extend(decode_hex(vector.private_key)?);
+    let keypair = SuiKeyPair::from_bytes(&bytes)
+        .map_err(|e| VectorError::Invalid(format!("Invalid private key: {}", e)))?;
+    let signature = Signature::new_secure(&personal_message(vector.message), &keypair);
+
+    Ok((
+        SuiAddress::from(&keypair.public()),
+        base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
+    ))
+}
+
+fn personal_message(message: &str) -> IntentMessage<PersonalMessage> {
+    IntentMessage::new(
+        Intent::personal_message(),
+        PersonalMessage {
+            message: message.as_bytes().to_vec(),
+        },
+    )
+}
+
+#[cfg(feature = "signing")]
+fn decode_hex(hex: &str) -> Result<Vec<u8>, VectorError> {
+    if hex.len() % 2 != 0 {
+        return Err(VectorError::Invalid(format!("Odd-length hex: {}", hex)));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| VectorError::Invalid(format!("Invalid hex {}: {}", hex, e)))
+        })
+        .collect()
+}
+
+fn mismatch(
+    vector: String,
+    expected: impl std::fmt::Display,
+    actual: impl std::fmt::Display,
+) -> VectorError {
+    VectorError::Mismatch {
+        vector,
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_all() {
+        check_all().unwrap();
+    }
+
+    #[test]
+    fn test_mismatch_reported() {
+        let err = check_statement_hashes(|_| "00".to_string()).unwrap_err();
+        assert!(matches!(err, VectorError::Mismatch { expected, .. }
+            if expected == VECTORS.statement_hashes[0].expected_sha256));
+
+        let json = to_json();
+        assert!(json.contains("\"canary_addresses\""));
+        assert!(json.contains(TEST_ADDRESS));
+    }
+}
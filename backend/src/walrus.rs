@@ -0,0 +1,178 @@
+//! Walrus blob availability checks
+//!
+//! A canary's contract and explain documents live in Walrus, which only stores a blob
+//! for the epochs that were paid for. Once that storage expires (or a deletable blob
+//! is deleted) the `CanaryBlob` still points at the blob, but nobody can read it.
+//! `verify_blob` asks a Walrus aggregator for the blob by its Sui object ID and reports
+//! whether it can still be retrieved.
+
+use crate::canary::CanaryBlobInfo;
+use crate::error::WalrusError;
+use serde::Serialize;
+use std::time::Duration;
+use sui_sdk::types::base_types::ObjectID;
+
+/// The public mainnet aggregator
+pub const MAINNET_AGGREGATOR: &str = "https://aggregator.walrus-mainnet.walrus.space";
+
+/// The public testnet aggregator
+pub const TESTNET_AGGREGATOR: &str = "https://aggregator.walrus-testnet.walrus.space";
+
+/// Timeout for one aggregator request
+const BLOB_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether a blob can be read from Walrus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobStatus {
+    /// The aggregator serves the blob
+    Available,
+    /// The aggregator does not know the blob; its storage expired or it was deleted
+    Missing,
+}
+
+/// Availability of the blobs a `CanaryBlob` references
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CanaryBlobCheck {
+    /// The canary's domain
+    pub domain: String,
+    /// The CanaryBlob object ID
+    pub canary_blob_id: ObjectID,
+    /// The contract blob's status
+    pub contract: BlobStatus,
+    /// The explain blob's status
+    pub explain: BlobStatus,
+}
+
+impl CanaryBlobCheck {
+    /// Whether both blobs can be read
+    pub fn is_available(&self) -> bool {
+        self.contract == BlobStatus::Available && self.explain == BlobStatus::Available
+    }
+}
+
+/// Reads blobs through a Walrus aggregator
+#[derive(Debug, Clone)]
+pub struct WalrusClient {
+    http: reqwest::Client,
+    aggregator_url: String,
+}
+
+impl WalrusClient {
+    /// Create a client for an aggregator, e.g. `MAINNET_AGGREGATOR`
+    pub fn new(aggregator_url: impl Into<String>) -> Result<Self, WalrusError> {
+        let http = reqwest::Client::builder()
+            .user_agent(crate::client::USER_AGENT)
+            .timeout(BLOB_TIMEOUT)
+            .build()
+            .map_err(|e| WalrusError::Http(e.to_string()))?;
+        Ok(Self {
+            http,
+            aggregator_url: aggregator_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// The aggregator URL serving a blob by its Sui object ID
+    pub fn blob_url(&self, blob_id: ObjectID) -> String {
+        format!("{}/v1/blobs/by-object-id/{}", self.aggregator_url, blob_id)
+    }
+
+    /// Check whether a blob can be retrieved
+    ///
+    /// Only the response status is read, not the blob itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `blob_id` - The Walrus blob's Sui object ID, e.g. `CanaryBlobInfo::contract_blob_id`
+    ///
+    /// # Returns
+    ///
+    /// Returns the blob's status, or a `WalrusError` if the aggregator cannot be reached
+    /// or answers with an unexpected status.
+    pub async fn verify_blob(&self, blob_id: ObjectID) -> Result<BlobStatus, WalrusError> {
+        let url = self.blob_url(blob_id);
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| WalrusError::Http(format!("{}: {}", url, e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(BlobStatus::Available)
+        } else if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+            Ok(BlobStatus::Missing)
+        } else {
+            Err(WalrusError::Status {
+                url,
+                status: status.as_u16(),
+            })
+        }
+    }
+
+    /// Check the contract and explain blobs of a canary
+    pub async fn check_canary_blob(
+        &self,
+        blob: &CanaryBlobInfo,
+    ) -> Result<CanaryBlobCheck, WalrusError> {
+        Ok(CanaryBlobCheck {
+            domain: blob.domain.clone(),
+            canary_blob_id: blob.id,
+            contract: self.verify_blob(blob.contract_blob_id).await?,
+            explain: self.verify_blob(blob.explain_blob_id).await?,
+        })
+    }
+}
+
+/// Check whether a blob can be retrieved from the mainnet aggregator
+///
+/// # Arguments
+///
+/// * `blob_id` - The Walrus blob's Sui object ID
+///
+/// # Returns
+///
+/// Returns the blob's status, or a `WalrusError` if the aggregator cannot be reached.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use canary_sdk::canary::query_canary_blob;
+/// use canary_sdk::client::{create_sui_client, Network};
+/// use canary_sdk::walrus::{verify_blob, BlobStatus};
+/// use sui_sdk::types::base_types::ObjectID;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = create_sui_client(Network::Mainnet).await?;
+/// let canary_blob_id = ObjectID::from_hex_literal("0x123...")?;
+/// let blob = query_canary_blob(&client, canary_blob_id).await?;
+/// if verify_blob(blob.contract_blob_id).await? == BlobStatus::Missing {
+///     println!("{}: contract blob expired", blob.domain);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn verify_blob(blob_id: ObjectID) -> Result<BlobStatus, WalrusError> {
+    WalrusClient::new(MAINNET_AGGREGATOR)?
+        .verify_blob(blob_id)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_url() {
+        let client = WalrusClient::new("https://aggregator.example/").unwrap();
+        let blob_id = ObjectID::from_single_byte(1);
+        assert_eq!(
+            client.blob_url(blob_id),
+            format!(
+                "https://aggregator.example/v1/blobs/by-object-id/{}",
+                blob_id
+            )
+        );
+    }
+}
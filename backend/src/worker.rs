@@ -0,0 +1,1776 @@
+//! Configurable worker tasks
+//!
+//! The worker runs a set of tasks, each on its own schedule, declared as `[[tasks]]`
+//! entries in the config file:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "snapshot"
+//! name = "snapshot-main"
+//! registry_id = "0x123..."
+//! interval_seconds = 86400
+//!
+//! [tasks.params]
+//! output_dir = "/app/snapshots"
+//! ```
+//!
+//! With `export_format = "csv"` (or `"json"`), a snapshot also writes the members and
+//! canary blobs as flat tables for reporting pipelines (see `crate::export`).
+//!
+//! A `TaskRegistry` maps each `kind` to a factory that builds the task from its entry,
+//! so the same kind can run several times with different parameters (e.g. once per
+//! registry) and new kinds can be registered without touching the scheduler.
+//!
+//! The `republish` kind renders the canary statement from a template (see
+//! `crate::template`) on each run and writes the content to upload to `output_path`:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "republish"
+//! interval_seconds = 2592000
+//!
+//! [tasks.params]
+//! template = "/app/canary.tmpl"
+//! output_path = "/app/canary.txt"
+//! domain = "example.com"
+//!
+//! [tasks.params.toggles]
+//! no_nsl = true
+//! ```
+//!
+//! The `relay` kind forwards a registry's transactions to webhook subscribers (see
+//! `crate::relay`), keeping its position in the context's `StateStore`.
+//!
+//! The `members` kind keeps the last member list it saw in the same store and reports
+//! joins, domain changes, and removals (see `crate::snapshot::diff_members`) to the
+//! context's notifier.
+//!
+//! By default it re-reads the whole member list on every run. With `mode = "checkpoint"`
+//! it reads the list once, then records the last checkpoint and registry transaction it
+//! processed; each later run only fetches the registry transactions since then and
+//! re-queries the members they joined, left, or removed. On mainnet this costs a few
+//! requests per run instead of one per page of members:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "members"
+//! registry_id = "0x123..."
+//! interval_seconds = 300
+//!
+//! [tasks.params]
+//! mode = "checkpoint"
+//! ```
+//!
+//! The `walrus` kind checks that the contract and explain blobs of every canary in a
+//! registry can still be read from a Walrus aggregator (see `crate::walrus`), and fails
+//! with the domains whose storage expired. `aggregator` defaults to the public mainnet
+//! aggregator.
+//!
+//! The `monitor` kind reports each domain's canary as fresh, stale, or missing (see
+//! `crate::monitor`). The reports go to the notifier with the run's outcome, so stale
+//! canaries raise an alert without failing the task. Without `domains`, every domain
+//! with a canary is checked; `upgrade_caps` names the `UpgradeCap` of a domain's
+//! package, so a canary covering an upgraded-away version counts as stale:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "monitor"
+//! registry_id = "0x123..."
+//! interval_seconds = 3600
+//!
+//! [tasks.params]
+//! max_age_seconds = 2592000
+//! domains = ["example.com", "example.org"]
+//!
+//! [tasks.params.upgrade_caps]
+//! "example.com" = "0x456..."
+//! ```
+//!
+//! The `digest` kind takes the same parameters and posts one periodic summary: the
+//! member changes since the previous digest and every canary's freshness.
+//!
+//! The `verification` kind re-checks notarization receipts (see
+//! `crate::canary::notary`). It fails with the receipts whose signature no longer
+//! verifies, whose notary is not in `notaries` (if set), or whose canary was updated
+//! since it was notarized.
+//!
+//! The `deadman` kind is a dead-man switch: once a domain's canary is older than
+//! `deadline_seconds` (or missing), it writes the pre-staged `notice` to `output_path`
+//! and fails on every run until a fresh canary re-arms it:
+//!
+//! ```toml
+//! [[tasks]]
+//! kind = "deadman"
+//! registry_id = "0x123..."
+//! interval_seconds = 3600
+//!
+//! [tasks.params]
+//! domain = "example.com"
+//! deadline_seconds = 3024000
+//! notice = "/app/deadman-notice.txt"
+//! output_path = "/app/public/canary.txt"
+//! ```
+//!
+//! A run that fails because the reference gas price is above `gas.max_price` (see
+//! `GasLimits::max_gas_price`) is deferred: it is logged, but not reported as a failure,
+//! and the task tries again at its next interval.
+
+use crate::canary::{
+    latest_registry_transaction, query_all_canary_blobs, query_all_members, query_canary_blob,
+    query_member, query_registry_transactions, verify_receipt, CanaryBlobInfo,
+    MemberInfoWithAddress, NotarizedReceipt,
+};
+use crate::client::providers::{EnvSecretSource, SecretSource};
+use crate::client::reconnect::ReconnectingClient;
+use crate::client::retry::RetryPolicy;
+use crate::clock::{is_stale, SystemClock, TimeSource};
+use crate::error::{CanaryError, ConfigError, GasError, TransactionError, WorkerError};
+use crate::export::{export_blobs, export_members, ExportFormat};
+use crate::metrics::Metrics;
+use crate::monitor::{evaluate_canary, monitor_canaries, CanaryReport, MonitorOptions};
+use crate::notify::{MemberChange, Notifier, TaskOutcome};
+use crate::relay::{Relay, Subscriber};
+use crate::snapshot::{
+    diff_members, export_snapshot, track_members, tracked_members, SnapshotOptions,
+};
+use crate::state::{MemoryStateStore, StateStore};
+use crate::template::{StatementTemplate, TemplateVars};
+use crate::walrus::{BlobStatus, WalrusClient, MAINNET_AGGREGATOR};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::SuiClient;
+use tokio::sync::watch;
+use tracing::Instrument;
+
+/// One `[[tasks]]` entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskConfig {
+    /// Task kind, as registered in the `TaskRegistry`
+    pub kind: String,
+    /// Unique name of this instance (defaults to `kind`)
+    pub name: Option<String>,
+    /// Whether the task runs (default: `true`)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Seconds between runs
+    pub interval_seconds: u64,
+    /// Registry the task operates on, for registry-scoped kinds
+    pub registry_id: Option<ObjectID>,
+    /// Kind-specific parameters
+    #[serde(default)]
+    pub params: toml::value::Table,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl TaskConfig {
+    /// The instance name (`name`, or `kind` if unset)
+    pub fn instance_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.kind)
+    }
+
+    /// The registry ID, or an error naming this task if it is missing
+    pub fn require_registry(&self) -> Result<ObjectID, ConfigError> {
+        self.registry_id.ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "Task {} requires registry_id",
+                self.instance_name()
+            ))
+        })
+    }
+
+    /// Read a string parameter
+    pub fn param_str(&self, key: &str) -> Option<&str> {
+        self.params.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Read an integer parameter
+    pub fn param_u64(&self, key: &str) -> Result<Option<u64>, ConfigError> {
+        match self.params.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_integer()
+                .and_then(|v| u64::try_from(v).ok())
+                .map(Some)
+                .ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "Task {}: params.{} must be a non-negative integer",
+                        self.instance_name(),
+                        key
+                    ))
+                }),
+        }
+    }
+}
+
+/// The `[[tasks]]` section of the config file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TasksConfig {
+    /// Task entries, in file order
+    #[serde(default)]
+    pub tasks: Vec<TaskConfig>,
+}
+
+impl TasksConfig {
+    /// Parse and validate the task entries of a TOML document
+    pub fn from_toml_str(s: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(s).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load the task entries from a TOML file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Self::from_toml_str(&contents)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        let mut names = HashSet::new();
+        for task in &self.tasks {
+            let name = task.instance_name();
+            if !names.insert(name) {
+                return Err(ConfigError::Invalid(format!(
+                    "Duplicate task name {}; set a unique `name` for each instance",
+                    name
+                )));
+            }
+            if task.interval_seconds == 0 {
+                return Err(ConfigError::Invalid(format!(
+                    "Task {}: interval_seconds must be greater than 0",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shared resources passed to every task run
+pub struct TaskContext {
+    /// Connection for on-chain queries, reconnected before each run (see `client`)
+    pub connection: Arc<ReconnectingClient>,
+    /// Time source for freshness checks
+    pub clock: Arc<dyn TimeSource>,
+    /// Retry policy applied to each task run
+    pub retry: RetryPolicy,
+    /// State kept between runs (e.g. cursors)
+    pub state: Arc<dyn StateStore>,
+    /// Where task outcomes are posted, if anywhere
+    pub notifier: Option<Arc<Notifier>>,
+    /// Turns `true` when the worker should stop
+    pub shutdown: watch::Receiver<bool>,
+}
+
+impl TaskContext {
+    /// Create a context using the system clock and in-memory state
+    pub fn new(client: SuiClient) -> Self {
+        Self::shared(Arc::new(ReconnectingClient::new(client)))
+    }
+
+    /// Create a context on a connection shared with other users (e.g. the health
+    /// server), reconnected before each run if it was built `with_reconnect`
+    pub fn shared(connection: Arc<ReconnectingClient>) -> Self {
+        Self {
+            connection,
+            clock: Arc::new(SystemClock),
+            retry: RetryPolicy::default(),
+            state: Arc::new(MemoryStateStore::new()),
+            notifier: None,
+            // The sender is dropped, so this never turns `true`
+            shutdown: watch::channel(false).1,
+        }
+    }
+
+    /// The current client for on-chain queries
+    pub fn client(&self) -> SuiClient {
+        self.connection.client()
+    }
+
+    /// Use a different time source (e.g. simulated time in tests)
+    pub fn with_clock(mut self, clock: Arc<dyn TimeSource>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Use a different retry policy for task runs
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Use a different state store (e.g. Redis, shared between replicas)
+    pub fn with_state_store(mut self, state: Arc<dyn StateStore>) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Post the outcome of every task run to `notifier`
+    pub fn with_notifier(mut self, notifier: Arc<Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Stop task runs once `shutdown` turns `true`
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Fail with `WorkerError::Cancelled` if shutdown was requested
+    ///
+    /// Tasks call this between RPC steps, never while a transaction is in flight.
+    pub fn check_shutdown(&self) -> Result<(), WorkerError> {
+        if *self.shutdown.borrow() {
+            Err(WorkerError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// What a successful task run observed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskReport {
+    /// Member changes since the previous run
+    pub member_changes: Vec<MemberChange>,
+    /// Canary freshness reports
+    pub canaries: Vec<CanaryReport>,
+}
+
+/// A unit of periodic worker work
+#[async_trait]
+pub trait WorkerTask: Send + Sync {
+    /// Run the task once
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport>;
+}
+
+/// Builds a task from its config entry
+pub type TaskFactory =
+    Box<dyn Fn(&TaskConfig) -> Result<Box<dyn WorkerTask>, ConfigError> + Send + Sync>;
+
+/// A task instance with its schedule
+pub struct ScheduledTask {
+    /// Instance name
+    pub name: String,
+    /// Time between runs
+    pub interval: Duration,
+    /// The task
+    pub task: Box<dyn WorkerTask>,
+}
+
+/// Maps task kinds to factories
+#[derive(Default)]
+pub struct TaskRegistry {
+    factories: BTreeMap<String, TaskFactory>,
+}
+
+impl TaskRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in kinds: `members` (in `snapshot` or
+    /// `checkpoint` mode), `snapshot`, `freshness`, `walrus`, `monitor`, `republish`,
+    /// `relay`, `verification`, `digest`, `deadman`
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register("members", |config| {
+            let registry_id = config.require_registry()?;
+            match config.param_str("mode").unwrap_or("snapshot") {
+                "snapshot" => Ok(Box::new(MembersTask { registry_id })),
+                "checkpoint" => Ok(Box::new(CheckpointMembersTask { registry_id })),
+                mode => Err(ConfigError::Invalid(format!(
+                    "Task {}: unknown params.mode {} (expected snapshot or checkpoint)",
+                    config.instance_name(),
+                    mode
+                ))),
+            }
+        });
+        registry.register("snapshot", |config| {
+            let export_format = config
+                .param_str("export_format")
+                .map(|format| {
+                    format.parse::<ExportFormat>().map_err(|e| {
+                        ConfigError::Invalid(format!("Task {}: {}", config.instance_name(), e))
+                    })
+                })
+                .transpose()?;
+            Ok(Box::new(SnapshotTask {
+                registry_id: config.require_registry()?,
+                output_dir: PathBuf::from(config.param_str("output_dir").unwrap_or(".")),
+                export_format,
+            }))
+        });
+        registry.register("freshness", |config| {
+            let blob_ids = config
+                .params
+                .get("canary_blob_ids")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "Task {} requires params.canary_blob_ids",
+                        config.instance_name()
+                    ))
+                })?
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .and_then(|s| ObjectID::from_hex_literal(s).ok())
+                        .ok_or_else(|| {
+                            ConfigError::Invalid(format!("Invalid canary blob ID: {}", v))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let max_age_seconds = config.param_u64("max_age_seconds")?.unwrap_or(30 * 86400);
+            Ok(Box::new(FreshnessTask {
+                blob_ids,
+                max_age: Duration::from_secs(max_age_seconds),
+            }))
+        });
+        registry.register("walrus", |config| {
+            let aggregator = config.param_str("aggregator").unwrap_or(MAINNET_AGGREGATOR);
+            Ok(Box::new(WalrusTask {
+                registry_id: config.require_registry()?,
+                walrus: WalrusClient::new(aggregator)
+                    .map_err(|e| ConfigError::Invalid(e.to_string()))?,
+            }))
+        });
+        registry.register("monitor", |config| {
+            Ok(Box::new(MonitorTask {
+                registry_id: config.require_registry()?,
+                options: monitor_options(config)?,
+            }))
+        });
+        registry.register("republish", |config| {
+            let require = |key: &str| {
+                config.param_str(key).ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "Task {} requires params.{}",
+                        config.instance_name(),
+                        key
+                    ))
+                })
+            };
+            let template = StatementTemplate::load(Path::new(require("template")?))
+                .map_err(|e| ConfigError::Invalid(e.to_string()))?;
+            let output_path = PathBuf::from(require("output_path")?);
+            let mut vars = TemplateVars::new().with_domain(require("domain")?);
+            if let Some(toggles) = config.params.get("toggles") {
+                let toggles = toggles.as_table().ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "Task {}: params.toggles must be a table",
+                        config.instance_name()
+                    ))
+                })?;
+                for (name, value) in toggles {
+                    let enabled = value.as_bool().ok_or_else(|| {
+                        ConfigError::Invalid(format!(
+                            "Task {}: params.toggles.{} must be a boolean",
+                            config.instance_name(),
+                            name
+                        ))
+                    })?;
+                    vars = vars.with_toggle(name.as_str(), enabled);
+                }
+            }
+            Ok(Box::new(RepublishTask {
+                template,
+                vars,
+                output_path,
+            }))
+        });
+        registry.register("relay", |config| {
+            let registry_id = config.require_registry()?;
+            let invalid = |message: &str| {
+                ConfigError::Invalid(format!("Task {}: {}", config.instance_name(), message))
+            };
+            let entries = config
+                .params
+                .get("subscribers")
+                .and_then(|v| v.as_array())
+                .filter(|entries| !entries.is_empty())
+                .ok_or_else(|| invalid("requires params.subscribers"))?;
+
+            let mut subscribers = Vec::with_capacity(entries.len());
+            for entry in entries {
+                let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+                let url = field("url").ok_or_else(|| invalid("subscriber requires url"))?;
+                let secret_env =
+                    field("secret_env").ok_or_else(|| invalid("subscriber requires secret_env"))?;
+                let secret = EnvSecretSource
+                    .get_secret(secret_env)
+                    .ok_or_else(|| invalid(&format!("{} is not set", secret_env)))?;
+                subscribers.push(Subscriber {
+                    url: url.to_string(),
+                    secret,
+                });
+            }
+
+            let relay = Relay::new(registry_id, subscribers)
+                .map_err(|e| ConfigError::Invalid(e.to_string()))?;
+            Ok(Box::new(RelayTask { relay }))
+        });
+        registry.register("verification", |config| {
+            let invalid = |message: String| {
+                ConfigError::Invalid(format!("Task {}: {}", config.instance_name(), message))
+            };
+            let receipts = config
+                .params
+                .get("receipts")
+                .and_then(|v| v.as_array())
+                .filter(|receipts| !receipts.is_empty())
+                .ok_or_else(|| invalid("requires params.receipts".to_string()))?
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(PathBuf::from)
+                        .ok_or_else(|| invalid(format!("Invalid receipt path: {}", v)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let notaries = match config.params.get("notaries") {
+                None => Vec::new(),
+                Some(notaries) => notaries
+                    .as_array()
+                    .ok_or_else(|| invalid("params.notaries must be an array".to_string()))?
+                    .iter()
+                    .map(|v| {
+                        v.as_str()
+                            .and_then(|s| s.parse::<SuiAddress>().ok())
+                            .ok_or_else(|| invalid(format!("Invalid notary address: {}", v)))
+                    })
+                    .collect::<Result<_, _>>()?,
+            };
+            Ok(Box::new(VerificationTask { receipts, notaries }))
+        });
+        registry.register("digest", |config| {
+            Ok(Box::new(DigestTask {
+                registry_id: config.require_registry()?,
+                options: monitor_options(config)?,
+            }))
+        });
+        registry.register("deadman", |config| {
+            let invalid = |message: &str| {
+                ConfigError::Invalid(format!("Task {}: {}", config.instance_name(), message))
+            };
+            let domain = config
+                .param_str("domain")
+                .ok_or_else(|| invalid("requires params.domain"))?;
+            let deadline_seconds = config
+                .param_u64("deadline_seconds")?
+                .ok_or_else(|| invalid("requires params.deadline_seconds"))?;
+            let notice = match (config.param_str("notice"), config.param_str("output_path")) {
+                (None, None) => None,
+                (Some(notice), Some(output_path)) => {
+                    let content = std::fs::read_to_string(notice).map_err(|e| ConfigError::Io {
+                        path: notice.to_string(),
+                        message: e.to_string(),
+                    })?;
+                    Some((content, PathBuf::from(output_path)))
+                }
+                _ => return Err(invalid("params.notice and params.output_path go together")),
+            };
+            Ok(Box::new(DeadManTask {
+                registry_id: config.require_registry()?,
+                domain: domain.to_string(),
+                deadline: Duration::from_secs(deadline_seconds),
+                notice,
+            }))
+        });
+        registry
+    }
+
+    /// Register (or replace) the factory for a task kind
+    pub fn register<F>(&mut self, kind: &str, factory: F)
+    where
+        F: Fn(&TaskConfig) -> Result<Box<dyn WorkerTask>, ConfigError> + Send + Sync + 'static,
+    {
+        self.factories.insert(kind.to_string(), Box::new(factory));
+    }
+
+    /// Registered task kinds, sorted
+    pub fn kinds(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Build the enabled tasks of a config
+    ///
+    /// # Returns
+    ///
+    /// Returns the scheduled tasks in config order, or a `ConfigError` if a task has an
+    /// unknown kind or invalid parameters.
+    pub fn build(&self, config: &TasksConfig) -> Result<Vec<ScheduledTask>, ConfigError> {
+        config
+            .tasks
+            .iter()
+            .filter(|task| task.enabled)
+            .map(|task| {
+                let factory = self.factories.get(&task.kind).ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "Unknown task kind {} (available: {})",
+                        task.kind,
+                        self.kinds().join(", ")
+                    ))
+                })?;
+                Ok(ScheduledTask {
+                    name: task.instance_name().to_string(),
+                    interval: Duration::from_secs(task.interval_seconds),
+                    task: factory(task)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Read the `monitor` parameters (`max_age_seconds`, `domains`, `upgrade_caps`) of a
+/// task entry
+fn monitor_options(config: &TaskConfig) -> Result<MonitorOptions, ConfigError> {
+    let invalid = |message: String| {
+        ConfigError::Invalid(format!("Task {}: {}", config.instance_name(), message))
+    };
+    let mut options = MonitorOptions::default();
+    if let Some(max_age_seconds) = config.param_u64("max_age_seconds")? {
+        options.max_age = Duration::from_secs(max_age_seconds);
+    }
+    if let Some(domains) = config.params.get("domains") {
+        options.domains = domains
+            .as_array()
+            .ok_or_else(|| invalid("params.domains must be an array".to_string()))?
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| invalid(format!("Invalid domain: {}", v)))
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(upgrade_caps) = config.params.get("upgrade_caps") {
+        let upgrade_caps = upgrade_caps
+            .as_table()
+            .ok_or_else(|| invalid("params.upgrade_caps must be a table".to_string()))?;
+        for (domain, cap) in upgrade_caps {
+            let cap = cap
+                .as_str()
+                .and_then(|s| ObjectID::from_hex_literal(s).ok())
+                .ok_or_else(|| invalid(format!("Invalid UpgradeCap ID: {}", cap)))?;
+            options.upgrade_caps.insert(domain.clone(), cap);
+        }
+    }
+    Ok(options)
+}
+
+/// Run each task on its own interval until the context's `shutdown` turns `true`
+///
+/// Before each run, the context's connection is checked and reconnected if the node
+/// stopped answering (see `ReconnectingClient::ensure_connected`). A run failing with a
+/// transient RPC error is retried according to the context's `RetryPolicy`. A run that
+/// still fails is logged and the task runs again at its next interval. With a notifier
+/// in the context, every run's outcome is posted to it. A run refused because gas is
+/// too expensive is deferred to the next interval without a notification.
+///
+/// Shutdown is graceful: no new runs start, runs in progress stop at their next
+/// `TaskContext::check_shutdown` (transactions already submitted are awaited), and the
+/// state store is flushed once every task has stopped. A cancelled run is neither
+/// counted nor notified. Without `TaskContext::with_shutdown`, the tasks run forever.
+pub async fn run_tasks(tasks: Vec<ScheduledTask>, ctx: Arc<TaskContext>) {
+    let mut handles = Vec::with_capacity(tasks.len());
+    for scheduled in tasks {
+        let ctx = ctx.clone();
+        let mut shutdown = ctx.shutdown.clone();
+        handles.push(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(scheduled.interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    Ok(_) = shutdown.wait_for(|stop| *stop) => break,
+                }
+                let span = tracing::info_span!("task", task = %scheduled.name);
+                let result = async {
+                    tracing::info!("Running task");
+                    match ctx.connection.ensure_connected().await {
+                        Ok(true) => tracing::info!("Reconnected to the RPC node"),
+                        Ok(false) => {}
+                        Err(e) => tracing::error!("Failed to reconnect: {}", e),
+                    }
+                    ctx.retry
+                        .run_untimed(&scheduled.name, || scheduled.task.run(&ctx))
+                        .await
+                }
+                .instrument(span.clone())
+                .await;
+                if let Err(e) = &result {
+                    if e.downcast_ref::<WorkerError>().is_some() {
+                        span.in_scope(|| tracing::info!("Task stopped by shutdown"));
+                        break;
+                    }
+                    if is_deferred(e) {
+                        span.in_scope(|| tracing::warn!("Task deferred: {:#}", e));
+                        continue;
+                    }
+                }
+                Metrics::global().record_task_run(&scheduled.name, result.is_ok());
+                let outcome = match &result {
+                    Ok(report) => TaskOutcome::success(&scheduled.name)
+                        .with_member_changes(report.member_changes.clone())
+                        .with_canaries(report.canaries.clone()),
+                    Err(e) => {
+                        span.in_scope(|| tracing::error!("Task failed: {:#}", e));
+                        TaskOutcome::failure(&scheduled.name, format!("{:#}", e))
+                    }
+                };
+                if let Some(notifier) = &ctx.notifier {
+                    notifier.notify(&outcome).instrument(span).await;
+                }
+            }
+        }));
+    }
+    futures::future::join_all(handles).await;
+
+    if let Err(e) = ctx.state.flush().await {
+        tracing::error!("Failed to flush worker state: {}", e);
+    }
+}
+
+/// Whether a run failed only because the reference gas price is above the maximum
+fn is_deferred(error: &anyhow::Error) -> bool {
+    // Wrapping errors are transparent, so their source is the wrapped error's source
+    // rather than the `TransactionError` itself
+    error.chain().any(|cause| {
+        let transaction = cause
+            .downcast_ref::<TransactionError>()
+            .or_else(|| match cause.downcast_ref::<CanaryError>() {
+                Some(CanaryError::Transaction(e)) => Some(e),
+                _ => None,
+            })
+            .or_else(|| match cause.downcast_ref::<GasError>() {
+                Some(GasError::Transaction(e)) => Some(e),
+                _ => None,
+            });
+        matches!(transaction, Some(TransactionError::GasPriceTooHigh { .. }))
+    })
+}
+
+/// Print a registry's members and report changes since the previous run
+struct MembersTask {
+    registry_id: ObjectID,
+}
+
+#[async_trait]
+impl WorkerTask for MembersTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let snapshot = export_snapshot(
+            &ctx.client(),
+            self.registry_id,
+            &SnapshotOptions::default(),
+            &(),
+        )
+        .await?;
+        tracing::info!(registry = %self.registry_id, "Found {} members", snapshot.members.len());
+        for member in &snapshot.members {
+            tracing::info!(
+                "  Address: {}, Domain: {}, Joined: {}",
+                member.member,
+                member.domain,
+                member.joined_at
+            );
+        }
+
+        ctx.check_shutdown()?;
+        let changes = track_members(ctx.state.as_ref(), self.registry_id, &snapshot.members)
+            .await?
+            .unwrap_or_default();
+        for change in &changes {
+            tracing::info!(registry = %self.registry_id, "{}", change);
+        }
+        Ok(TaskReport {
+            member_changes: changes,
+            ..Default::default()
+        })
+    }
+}
+
+/// Registry transactions fetched per page by `CheckpointMembersTask`
+const MEMBERS_PAGE_SIZE: usize = 50;
+
+/// Where `CheckpointMembersTask` stopped, stored as JSON in the state store
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct MemberCursor {
+    /// Latest checkpoint when the previous run started
+    checkpoint: u64,
+    /// Last registry transaction processed (`None` if the registry had none)
+    digest: Option<TransactionDigest>,
+}
+
+/// Update the tracked member list from the registry transactions since the last run
+struct CheckpointMembersTask {
+    registry_id: ObjectID,
+}
+
+impl CheckpointMembersTask {
+    /// Name of the cursor holding the task's `MemberCursor`
+    fn cursor_name(&self) -> String {
+        format!("members-checkpoint:{}", self.registry_id)
+    }
+
+    async fn save_cursor(
+        &self,
+        state: &dyn StateStore,
+        cursor: &MemberCursor,
+    ) -> anyhow::Result<()> {
+        state
+            .set_cursor(&self.cursor_name(), &serde_json::to_string(cursor)?)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WorkerTask for CheckpointMembersTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let state = ctx.state.as_ref();
+        let checkpoint = ctx
+            .client()
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+
+        ctx.check_shutdown()?;
+        let cursor: Option<MemberCursor> = match state.get_cursor(&self.cursor_name()).await? {
+            Some(json) => Some(serde_json::from_str(&json)?),
+            None => None,
+        };
+        let (cursor, previous) = match (cursor, tracked_members(state, self.registry_id).await?) {
+            (Some(cursor), Some(previous)) => (cursor, previous),
+            _ => {
+                // Read the full list once. The latest transaction is looked up first, so
+                // one landing in between is replayed next run rather than missed.
+                let digest = latest_registry_transaction(&ctx.client(), self.registry_id).await?;
+                ctx.check_shutdown()?;
+                let members = query_all_members(&ctx.client(), self.registry_id).await?;
+                ctx.check_shutdown()?;
+                let changes = track_members(state, self.registry_id, &members)
+                    .await?
+                    .unwrap_or_default();
+                self.save_cursor(state, &MemberCursor { checkpoint, digest })
+                    .await?;
+                tracing::info!(
+                    registry = %self.registry_id,
+                    checkpoint,
+                    "Loaded {} members",
+                    members.len()
+                );
+                return Ok(TaskReport {
+                    member_changes: changes,
+                    ..Default::default()
+                });
+            }
+        };
+        if checkpoint <= cursor.checkpoint {
+            return Ok(TaskReport::default());
+        }
+
+        let mut affected = BTreeSet::new();
+        let mut digest = cursor.digest;
+        let mut transactions = 0;
+        loop {
+            ctx.check_shutdown()?;
+            let page = query_registry_transactions(
+                &ctx.client(),
+                self.registry_id,
+                digest,
+                Some(MEMBERS_PAGE_SIZE),
+            )
+            .await?;
+            for transaction in &page.transactions {
+                if transaction.success {
+                    affected.extend(transaction.member);
+                }
+                digest = Some(transaction.digest);
+            }
+            transactions += page.transactions.len();
+            if !page.has_next_page {
+                break;
+            }
+        }
+
+        let mut current = previous;
+        for member in affected {
+            current.retain(|entry| entry.member != member);
+            ctx.check_shutdown()?;
+            if let Some(info) = query_member(&ctx.client(), self.registry_id, member).await? {
+                current.push(MemberInfoWithAddress {
+                    member,
+                    domain: info.domain,
+                    joined_at: info.joined_at,
+                    raw: info.raw,
+                });
+            }
+        }
+        current.sort_by(|a, b| {
+            a.joined_at
+                .cmp(&b.joined_at)
+                .then_with(|| a.member.cmp(&b.member))
+        });
+
+        ctx.check_shutdown()?;
+        let changes = track_members(state, self.registry_id, &current)
+            .await?
+            .unwrap_or_default();
+        self.save_cursor(state, &MemberCursor { checkpoint, digest })
+            .await?;
+        tracing::info!(
+            registry = %self.registry_id,
+            checkpoint,
+            transactions,
+            "Tracking {} members",
+            current.len()
+        );
+        for change in &changes {
+            tracing::info!(registry = %self.registry_id, "{}", change);
+        }
+        Ok(TaskReport {
+            member_changes: changes,
+            ..Default::default()
+        })
+    }
+}
+
+/// Write a registry snapshot to `output_dir`, and optionally members and blob exports
+struct SnapshotTask {
+    registry_id: ObjectID,
+    output_dir: PathBuf,
+    export_format: Option<ExportFormat>,
+}
+
+#[async_trait]
+impl WorkerTask for SnapshotTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let snapshot = export_snapshot(
+            &ctx.client(),
+            self.registry_id,
+            &SnapshotOptions::default(),
+            &(),
+        )
+        .await?;
+        let path = self.output_dir.join(format!(
+            "snapshot-{}-{}.json",
+            self.registry_id, snapshot.taken_at_ms
+        ));
+        std::fs::write(&path, serde_json::to_vec_pretty(&snapshot)?)?;
+        tracing::info!("Wrote snapshot to {}", path.display());
+
+        if let Some(format) = self.export_format {
+            let file_name = |table: &str| {
+                format!(
+                    "{}-{}-{}.{}",
+                    table,
+                    self.registry_id,
+                    snapshot.taken_at_ms,
+                    format.extension()
+                )
+            };
+            let path = self.output_dir.join(file_name("members"));
+            export_members(
+                format,
+                &snapshot.members,
+                std::io::BufWriter::new(std::fs::File::create(&path)?),
+            )?;
+            tracing::info!("Wrote members to {}", path.display());
+
+            ctx.check_shutdown()?;
+            let blobs = query_all_canary_blobs(&ctx.client(), self.registry_id).await?;
+            let path = self.output_dir.join(file_name("blobs"));
+            export_blobs(
+                format,
+                &blobs,
+                std::io::BufWriter::new(std::fs::File::create(&path)?),
+            )?;
+            tracing::info!("Wrote blobs to {}", path.display());
+        }
+        Ok(TaskReport::default())
+    }
+}
+
+/// Fail if any of the given canary blobs is older than `max_age`
+struct FreshnessTask {
+    blob_ids: Vec<ObjectID>,
+    max_age: Duration,
+}
+
+#[async_trait]
+impl WorkerTask for FreshnessTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let now_ms = ctx.clock.now_ms().await?;
+
+        let mut stale = Vec::new();
+        for blob_id in &self.blob_ids {
+            ctx.check_shutdown()?;
+            let blob = query_canary_blob(&ctx.client(), *blob_id).await?;
+            if is_stale(blob.uploaded_at, now_ms, self.max_age) {
+                stale.push(blob.domain);
+            }
+        }
+
+        if stale.is_empty() {
+            Ok(TaskReport::default())
+        } else {
+            anyhow::bail!("Stale canaries: {}", stale.join(", "))
+        }
+    }
+}
+
+/// Fail if any canary's contract or explain blob can no longer be read from Walrus
+struct WalrusTask {
+    registry_id: ObjectID,
+    walrus: WalrusClient,
+}
+
+#[async_trait]
+impl WorkerTask for WalrusTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let blobs = query_all_canary_blobs(&ctx.client(), self.registry_id).await?;
+
+        let mut expired = Vec::new();
+        for blob in &blobs {
+            ctx.check_shutdown()?;
+            let check = self.walrus.check_canary_blob(blob).await?;
+            if check.is_available() {
+                continue;
+            }
+            let missing: Vec<&str> = [("contract", check.contract), ("explain", check.explain)]
+                .into_iter()
+                .filter(|(_, status)| *status == BlobStatus::Missing)
+                .map(|(name, _)| name)
+                .collect();
+            tracing::warn!(
+                registry = %self.registry_id,
+                canary_blob = %check.canary_blob_id,
+                "Walrus blobs of {} are missing: {}",
+                check.domain,
+                missing.join(", ")
+            );
+            expired.push(format!("{} ({})", check.domain, missing.join(", ")));
+        }
+
+        tracing::info!(
+            registry = %self.registry_id,
+            "Checked Walrus blobs of {} canaries",
+            blobs.len()
+        );
+        if expired.is_empty() {
+            Ok(TaskReport::default())
+        } else {
+            anyhow::bail!("Missing Walrus blobs: {}", expired.join(", "))
+        }
+    }
+}
+
+/// Report the freshness of a registry's canaries
+struct MonitorTask {
+    registry_id: ObjectID,
+    options: MonitorOptions,
+}
+
+#[async_trait]
+impl WorkerTask for MonitorTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let now_ms = ctx.clock.now_ms().await?;
+        let canaries =
+            monitor_canaries(&ctx.client(), self.registry_id, &self.options, now_ms).await?;
+        for report in canaries.iter().filter(|report| report.is_alert()) {
+            tracing::warn!(registry = %self.registry_id, "Canary {}", report);
+        }
+        tracing::info!(
+            registry = %self.registry_id,
+            "Checked {} canaries",
+            canaries.len()
+        );
+        Ok(TaskReport {
+            canaries,
+            ..Default::default()
+        })
+    }
+}
+
+/// Render the canary statement for the current date and checkpoint to `output_path`
+struct RepublishTask {
+    template: StatementTemplate,
+    vars: TemplateVars,
+    output_path: PathBuf,
+}
+
+#[async_trait]
+impl WorkerTask for RepublishTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let now_ms = ctx.clock.now_ms().await?;
+        let checkpoint = ctx
+            .client()
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+        let vars = self
+            .vars
+            .clone()
+            .with_date_ms(now_ms)
+            .with_checkpoint(checkpoint);
+
+        let content = self.template.render(&vars)?;
+        std::fs::write(&self.output_path, &content)?;
+        tracing::info!(
+            checkpoint,
+            "Rendered canary statement to {}",
+            self.output_path.display()
+        );
+        Ok(TaskReport::default())
+    }
+}
+
+/// Forward new registry transactions to webhook subscribers
+struct RelayTask {
+    relay: Relay,
+}
+
+#[async_trait]
+impl WorkerTask for RelayTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let report = self.relay.poll(&ctx.client(), ctx.state.as_ref()).await?;
+        tracing::info!(
+            events = report.events,
+            delivered = report.delivered,
+            failed = report.failed,
+            "Relayed registry activity"
+        );
+        Ok(TaskReport::default())
+    }
+}
+
+/// Fail if a notarization receipt no longer verifies or no longer matches its canary
+struct VerificationTask {
+    receipts: Vec<PathBuf>,
+    /// Trusted notaries; any notary if empty
+    notaries: Vec<SuiAddress>,
+}
+
+#[async_trait]
+impl WorkerTask for VerificationTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let mut failed = Vec::new();
+        for path in &self.receipts {
+            let receipt = match std::fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bundle| Ok(verify_receipt(&bundle)?))
+            {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    failed.push(format!("{} ({})", path.display(), e));
+                    continue;
+                }
+            };
+
+            ctx.check_shutdown()?;
+            let current = query_canary_blob(&ctx.client(), receipt.body.blob.id).await?;
+            if let Some(problem) = receipt_problem(&receipt, &current, &self.notaries) {
+                tracing::warn!(
+                    canary_blob = %receipt.body.blob.id,
+                    "Receipt {} for {}: {}",
+                    path.display(),
+                    receipt.body.domain,
+                    problem
+                );
+                failed.push(format!("{} ({})", path.display(), problem));
+            }
+        }
+
+        tracing::info!("Verified {} receipts", self.receipts.len());
+        if failed.is_empty() {
+            Ok(TaskReport::default())
+        } else {
+            anyhow::bail!("Receipts failed verification: {}", failed.join(", "))
+        }
+    }
+}
+
+/// Why a receipt whose signature verified should no longer be relied on, if it should
+/// not
+///
+/// `current` is the canary blob the receipt refers to, as it is on chain now.
+fn receipt_problem(
+    receipt: &NotarizedReceipt,
+    current: &CanaryBlobInfo,
+    notaries: &[SuiAddress],
+) -> Option<String> {
+    let notarized = &receipt.body.blob;
+    if !notaries.is_empty() && !notaries.contains(&receipt.notary) {
+        Some(format!("untrusted notary {}", receipt.notary))
+    } else if !receipt.body.verification.passed() {
+        Some("notary checks failed".to_string())
+    } else if current.contract_blob_id != notarized.contract_blob_id
+        || current.explain_blob_id != notarized.explain_blob_id
+        || current.package_id != notarized.package_id
+    {
+        Some("canary updated since notarization".to_string())
+    } else {
+        None
+    }
+}
+
+/// Report a registry's member changes since the previous digest and the freshness of
+/// its canaries in one notification
+struct DigestTask {
+    registry_id: ObjectID,
+    options: MonitorOptions,
+}
+
+impl DigestTask {
+    /// Name of the cursor holding the member list of the previous digest
+    ///
+    /// Kept apart from `tracked_members_key`, so a `members` task on the same registry
+    /// does not consume the changes before the digest reports them.
+    fn cursor_name(&self) -> String {
+        format!("digest-members:{}", self.registry_id)
+    }
+}
+
+#[async_trait]
+impl WorkerTask for DigestTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let now_ms = ctx.clock.now_ms().await?;
+        let members = query_all_members(&ctx.client(), self.registry_id).await?;
+
+        ctx.check_shutdown()?;
+        let canaries =
+            monitor_canaries(&ctx.client(), self.registry_id, &self.options, now_ms).await?;
+
+        let state = ctx.state.as_ref();
+        let previous: Option<Vec<MemberInfoWithAddress>> =
+            match state.get_cursor(&self.cursor_name()).await? {
+                Some(json) => Some(serde_json::from_str(&json)?),
+                None => None,
+            };
+        state
+            .set_cursor(&self.cursor_name(), &serde_json::to_string(&members)?)
+            .await?;
+        let member_changes = previous
+            .map(|previous| diff_members(&previous, &members))
+            .unwrap_or_default();
+
+        tracing::info!(
+            registry = %self.registry_id,
+            "Digest: {} members ({} changes), {} of {} canaries not fresh",
+            members.len(),
+            member_changes.len(),
+            canaries.iter().filter(|report| report.is_alert()).count(),
+            canaries.len()
+        );
+        Ok(TaskReport {
+            member_changes,
+            canaries,
+        })
+    }
+}
+
+/// What a `DeadManTask` run does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeadManAction {
+    /// The canary is fresh and the switch is armed
+    Wait,
+    /// The canary just went stale: publish the notice and raise the alarm
+    Trigger,
+    /// The switch already triggered and the canary is still stale
+    Triggered,
+    /// The canary was refreshed after the switch triggered
+    Rearm,
+}
+
+/// Decide a `DeadManTask` run from its domain's canary report and whether the switch
+/// already triggered
+fn dead_man_action(report: &CanaryReport, triggered: bool) -> DeadManAction {
+    match (report.is_alert(), triggered) {
+        (false, false) => DeadManAction::Wait,
+        (true, false) => DeadManAction::Trigger,
+        (true, true) => DeadManAction::Triggered,
+        (false, true) => DeadManAction::Rearm,
+    }
+}
+
+/// Publish a pre-staged notice and fail once a domain's canary is older than
+/// `deadline`, i.e. once its admins stopped refreshing it
+///
+/// The switch triggers once per lapse: the notice is written on the run that finds the
+/// canary stale, and again only after a fresh canary has re-armed the switch.
+struct DeadManTask {
+    registry_id: ObjectID,
+    domain: String,
+    deadline: Duration,
+    /// Notice content and where to write it when the switch triggers
+    notice: Option<(String, PathBuf)>,
+}
+
+impl DeadManTask {
+    /// Name of the cursor recording whether the switch triggered
+    fn cursor_name(&self) -> String {
+        format!("deadman:{}:{}", self.registry_id, self.domain)
+    }
+}
+
+#[async_trait]
+impl WorkerTask for DeadManTask {
+    async fn run(&self, ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+        let now_ms = ctx.clock.now_ms().await?;
+        let blobs = query_all_canary_blobs(&ctx.client(), self.registry_id).await?;
+        let report = evaluate_canary(&self.domain, &blobs, None, now_ms, self.deadline);
+
+        ctx.check_shutdown()?;
+        let state = ctx.state.as_ref();
+        let triggered =
+            state.get_cursor(&self.cursor_name()).await?.as_deref() == Some("triggered");
+        match dead_man_action(&report, triggered) {
+            DeadManAction::Wait => Ok(TaskReport::default()),
+            DeadManAction::Rearm => {
+                state.set_cursor(&self.cursor_name(), "armed").await?;
+                tracing::info!(registry = %self.registry_id, "Dead-man switch re-armed: {}", report);
+                Ok(TaskReport::default())
+            }
+            DeadManAction::Trigger => {
+                if let Some((content, output_path)) = &self.notice {
+                    std::fs::write(output_path, content)?;
+                    tracing::warn!("Wrote dead-man notice to {}", output_path.display());
+                }
+                state.set_cursor(&self.cursor_name(), "triggered").await?;
+                anyhow::bail!("Dead-man switch triggered: {}", report)
+            }
+            DeadManAction::Triggered => {
+                anyhow::bail!("Dead-man switch triggered: {}", report)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[[tasks]]
+kind = "snapshot"
+name = "snapshot-a"
+registry_id = "0x0000000000000000000000000000000000000000000000000000000000000123"
+interval_seconds = 60
+
+[tasks.params]
+output_dir = "/tmp"
+
+[[tasks]]
+kind = "snapshot"
+name = "snapshot-b"
+registry_id = "0x0000000000000000000000000000000000000000000000000000000000000456"
+interval_seconds = 120
+
+[[tasks]]
+kind = "freshness"
+interval_seconds = 3600
+enabled = false
+
+[tasks.params]
+canary_blob_ids = ["0x1"]
+max_age_seconds = 86400
+"#;
+
+    #[test]
+    fn test_parse_tasks() {
+        let config = TasksConfig::from_toml_str(CONFIG).unwrap();
+        assert_eq!(config.tasks.len(), 3);
+        assert_eq!(config.tasks[0].instance_name(), "snapshot-a");
+        assert_eq!(config.tasks[0].param_str("output_dir"), Some("/tmp"));
+        assert_eq!(config.tasks[2].instance_name(), "freshness");
+        assert!(!config.tasks[2].enabled);
+        assert_eq!(
+            config.tasks[2].param_u64("max_age_seconds").unwrap(),
+            Some(86400)
+        );
+    }
+
+    #[test]
+    fn test_build_skips_disabled() {
+        let config = TasksConfig::from_toml_str(CONFIG).unwrap();
+        let tasks = TaskRegistry::with_builtin().build(&config).unwrap();
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["snapshot-a", "snapshot-b"]);
+        assert_eq!(tasks[1].interval, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_duplicate_names_rejected() {
+        let config = "[[tasks]]\nkind = \"members\"\ninterval_seconds = 1\n\
+                      [[tasks]]\nkind = \"members\"\ninterval_seconds = 2\n";
+        assert!(matches!(
+            TasksConfig::from_toml_str(config),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_kind_and_missing_registry() {
+        let registry = TaskRegistry::with_builtin();
+
+        let config =
+            TasksConfig::from_toml_str("[[tasks]]\nkind = \"nope\"\ninterval_seconds = 1\n")
+                .unwrap();
+        match registry.build(&config) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("snapshot")),
+            _ => panic!("Expected unknown kind error"),
+        }
+
+        let config =
+            TasksConfig::from_toml_str("[[tasks]]\nkind = \"members\"\ninterval_seconds = 1\n")
+                .unwrap();
+        assert!(registry.build(&config).is_err());
+    }
+
+    #[test]
+    fn test_republish_requires_template() {
+        let config = TasksConfig::from_toml_str(
+            "[[tasks]]\nkind = \"republish\"\ninterval_seconds = 1\n\
+             [tasks.params]\noutput_path = \"/tmp/canary.txt\"\ndomain = \"example.com\"\n",
+        )
+        .unwrap();
+        match TaskRegistry::with_builtin().build(&config) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.template")),
+            _ => panic!("Expected missing template error"),
+        }
+    }
+
+    #[test]
+    fn test_relay_requires_subscribers() {
+        let config = TasksConfig::from_toml_str(
+            "[[tasks]]\nkind = \"relay\"\ninterval_seconds = 30\n\
+             registry_id = \"0x123\"\n",
+        )
+        .unwrap();
+        match TaskRegistry::with_builtin().build(&config) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.subscribers")),
+            _ => panic!("Expected missing subscribers error"),
+        }
+    }
+
+    #[test]
+    fn test_members_mode() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |mode: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"members\"\ninterval_seconds = 300\n\
+                 registry_id = \"0x123\"\n[tasks.params]\nmode = \"{}\"\n",
+                mode
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(registry.build(&config("snapshot")).unwrap().len(), 1);
+        assert_eq!(registry.build(&config("checkpoint")).unwrap().len(), 1);
+        match registry.build(&config("stream")) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.mode stream")),
+            _ => panic!("Expected unknown mode error"),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_export_format() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |format: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"snapshot\"\ninterval_seconds = 86400\n\
+                 registry_id = \"0x123\"\n[tasks.params]\nexport_format = \"{}\"\n",
+                format
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(registry.build(&config("csv")).unwrap().len(), 1);
+        assert_eq!(registry.build(&config("json")).unwrap().len(), 1);
+        match registry.build(&config("xml")) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("Unknown export format")),
+            _ => panic!("Expected unknown export format error"),
+        }
+    }
+
+    #[test]
+    fn test_monitor_params() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |params: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"monitor\"\ninterval_seconds = 3600\n\
+                 registry_id = \"0x123\"\n[tasks.params]\n{}",
+                params
+            ))
+            .unwrap()
+        };
+
+        let params = "domains = [\"example.com\"]\n[tasks.params.upgrade_caps]\n\
+                      \"example.com\" = \"0x456\"\n";
+        assert_eq!(registry.build(&config(params)).unwrap().len(), 1);
+        match registry.build(&config(
+            "[tasks.params.upgrade_caps]\n\"example.com\" = 1\n",
+        )) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("Invalid UpgradeCap ID")),
+            _ => panic!("Expected invalid UpgradeCap error"),
+        }
+    }
+
+    #[test]
+    fn test_member_cursor_roundtrip() {
+        let cursor = MemberCursor {
+            checkpoint: 42,
+            digest: Some(TransactionDigest::random()),
+        };
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(serde_json::from_str::<MemberCursor>(&json).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_register_custom_kind() {
+        struct Noop;
+
+        #[async_trait]
+        impl WorkerTask for Noop {
+            async fn run(&self, _ctx: &TaskContext) -> anyhow::Result<TaskReport> {
+                Ok(TaskReport::default())
+            }
+        }
+
+        let mut registry = TaskRegistry::new();
+        registry.register("noop", |_| Ok(Box::new(Noop)));
+        let config =
+            TasksConfig::from_toml_str("[[tasks]]\nkind = \"noop\"\ninterval_seconds = 5\n")
+                .unwrap();
+        assert_eq!(registry.build(&config).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_gas_price_spike_defers_run() {
+        let too_high = || TransactionError::GasPriceTooHigh {
+            price: 5_000,
+            max: 2_000,
+        };
+        assert!(is_deferred(&anyhow::Error::new(too_high())));
+        assert!(is_deferred(
+            &anyhow::Error::new(CanaryError::Transaction(too_high())).context("store_blob")
+        ));
+        assert!(!is_deferred(&anyhow::Error::new(CanaryError::NotMember)));
+        assert!(!is_deferred(&anyhow::anyhow!("connection reset")));
+    }
+
+    #[test]
+    fn test_verification_params() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |params: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"verification\"\ninterval_seconds = 86400\n\
+                 [tasks.params]\n{}",
+                params
+            ))
+            .unwrap()
+        };
+
+        let receipts = "receipts = [\"/app/receipt.json\"]\n";
+        assert_eq!(registry.build(&config(receipts)).unwrap().len(), 1);
+        assert_eq!(
+            registry
+                .build(&config(&format!(
+                    "{}notaries = [\"0x{}\"]\n",
+                    receipts,
+                    "ab".repeat(32)
+                )))
+                .unwrap()
+                .len(),
+            1
+        );
+        match registry.build(&config("receipts = []\n")) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.receipts")),
+            _ => panic!("Expected missing receipts error"),
+        }
+        match registry.build(&config(&format!("{}notaries = [\"alice\"]\n", receipts))) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("Invalid notary address")),
+            _ => panic!("Expected invalid notary error"),
+        }
+    }
+
+    #[test]
+    fn test_receipt_problem() {
+        use crate::canary::notary::{CheckpointAnchor, ReceiptBody, VerificationResult};
+        use crate::compat::Artifact;
+        use sui_sdk::types::messages_checkpoint::CheckpointDigest;
+
+        let blob = CanaryBlobInfo {
+            id: ObjectID::from_single_byte(1),
+            contract_blob_id: ObjectID::from_single_byte(2),
+            explain_blob_id: ObjectID::from_single_byte(3),
+            package_id: ObjectID::from_single_byte(4),
+            domain: "example.com".to_string(),
+            uploaded_at: 1_000,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        };
+        let notary = SuiAddress::random_for_testing_only();
+        let mut receipt = NotarizedReceipt {
+            body: ReceiptBody {
+                schema_version: NotarizedReceipt::SCHEMA_VERSION,
+                sdk_version: crate::compat::SDK_VERSION.to_string(),
+                domain: "example.com".to_string(),
+                blob: blob.clone(),
+                blob_hashes: Vec::new(),
+                anchor: CheckpointAnchor {
+                    sequence_number: 7,
+                    digest: CheckpointDigest::default(),
+                    timestamp_ms: 2_000,
+                },
+                verification: VerificationResult {
+                    domain_matches: true,
+                    package_matches: true,
+                    age_ms: 1_000,
+                },
+            },
+            notary,
+            signature: String::new(),
+        };
+
+        assert_eq!(receipt_problem(&receipt, &blob, &[]), None);
+        assert_eq!(receipt_problem(&receipt, &blob, &[notary]), None);
+        assert!(receipt_problem(&receipt, &blob, &[SuiAddress::ZERO])
+            .unwrap()
+            .contains("untrusted notary"));
+
+        let updated = CanaryBlobInfo {
+            contract_blob_id: ObjectID::from_single_byte(5),
+            uploaded_at: 3_000,
+            ..blob.clone()
+        };
+        assert_eq!(
+            receipt_problem(&receipt, &updated, &[]).as_deref(),
+            Some("canary updated since notarization")
+        );
+
+        receipt.body.verification.package_matches = false;
+        assert_eq!(
+            receipt_problem(&receipt, &blob, &[]).as_deref(),
+            Some("notary checks failed")
+        );
+    }
+
+    #[test]
+    fn test_digest_requires_registry() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |registry_id: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"digest\"\ninterval_seconds = 604800\n{}\
+                 [tasks.params]\nmax_age_seconds = 2592000\n",
+                registry_id
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            registry
+                .build(&config("registry_id = \"0x123\"\n"))
+                .unwrap()
+                .len(),
+            1
+        );
+        match registry.build(&config("")) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("requires registry_id")),
+            _ => panic!("Expected missing registry error"),
+        }
+    }
+
+    #[test]
+    fn test_deadman_params() {
+        let registry = TaskRegistry::with_builtin();
+        let config = |params: &str| {
+            TasksConfig::from_toml_str(&format!(
+                "[[tasks]]\nkind = \"deadman\"\ninterval_seconds = 3600\n\
+                 registry_id = \"0x123\"\n[tasks.params]\n{}",
+                params
+            ))
+            .unwrap()
+        };
+
+        let notice =
+            std::env::temp_dir().join(format!("deadman-notice-{}.txt", std::process::id()));
+        std::fs::write(&notice, "This canary is no longer maintained.\n").unwrap();
+        let params = "domain = \"example.com\"\ndeadline_seconds = 3024000\n";
+        assert_eq!(registry.build(&config(params)).unwrap().len(), 1);
+        assert_eq!(
+            registry
+                .build(&config(&format!(
+                    "{}notice = {:?}\noutput_path = \"/tmp/canary.txt\"\n",
+                    params,
+                    notice.display().to_string()
+                )))
+                .unwrap()
+                .len(),
+            1
+        );
+        std::fs::remove_file(&notice).unwrap();
+
+        match registry.build(&config("domain = \"example.com\"\n")) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.deadline_seconds")),
+            _ => panic!("Expected missing deadline error"),
+        }
+        match registry.build(&config(&format!(
+            "{}notice = \"/app/notice.txt\"\n",
+            params
+        ))) {
+            Err(ConfigError::Invalid(msg)) => assert!(msg.contains("params.output_path")),
+            _ => panic!("Expected notice without output_path error"),
+        }
+        assert!(matches!(
+            registry.build(&config(&format!(
+                "{}notice = {:?}\noutput_path = \"/tmp/canary.txt\"\n",
+                params,
+                notice.display().to_string()
+            ))),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dead_man_action() {
+        const DAY_MS: u64 = 86_400_000;
+        let deadline = Duration::from_secs(35 * 86400);
+        let blobs = vec![CanaryBlobInfo {
+            id: ObjectID::from_single_byte(1),
+            contract_blob_id: ObjectID::from_single_byte(2),
+            explain_blob_id: ObjectID::from_single_byte(3),
+            package_id: ObjectID::from_single_byte(4),
+            domain: "example.com".to_string(),
+            uploaded_at: 10 * DAY_MS,
+            uploaded_by_admin: SuiAddress::ZERO,
+            raw: None,
+        }];
+        let report =
+            |domain: &str, now_ms: u64| evaluate_canary(domain, &blobs, None, now_ms, deadline);
+
+        let fresh = report("example.com", 20 * DAY_MS);
+        let lapsed = report("example.com", 50 * DAY_MS);
+        assert_eq!(dead_man_action(&fresh, false), DeadManAction::Wait);
+        assert_eq!(dead_man_action(&lapsed, false), DeadManAction::Trigger);
+        assert_eq!(dead_man_action(&lapsed, true), DeadManAction::Triggered);
+        assert_eq!(dead_man_action(&fresh, true), DeadManAction::Rearm);
+        // A domain without any canary counts as lapsed
+        assert_eq!(
+            dead_man_action(&report("example.org", 20 * DAY_MS), false),
+            DeadManAction::Trigger
+        );
+    }
+}
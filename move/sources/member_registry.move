@@ -45,6 +45,15 @@ public struct MembershipCap has key, store {
 
 // Init function
 fun init(ctx: &mut TxContext) {
+    new_registry(1_000_000_000, ctx); // 1 SUI
+}
+
+// Create another registry administered by the sender
+public entry fun create_registry(fee: u64, ctx: &mut TxContext) {
+    new_registry(fee, ctx);
+}
+
+fun new_registry(fee: u64, ctx: &mut TxContext) {
     let sender = tx_context::sender(ctx);
 
     let registry = Registry {
@@ -52,7 +61,7 @@ fun init(ctx: &mut TxContext) {
         members: table::new(ctx),
         member_addresses: table::new(ctx),
         member_count: 0,
-        fee: 1_000_000_000, // 1 SUI
+        fee,
         balance: balance::zero(),
         admin: sender,
     };
@@ -115,12 +124,24 @@ public entry fun withdraw(
     admin_cap: &AdminCap,
     amount: u64,
     ctx: &mut TxContext,
+) {
+    let sender = tx_context::sender(ctx);
+    withdraw_to(registry, admin_cap, amount, sender, ctx);
+}
+
+// Withdraw to another address (admin only)
+public entry fun withdraw_to(
+    registry: &mut Registry,
+    admin_cap: &AdminCap,
+    amount: u64,
+    recipient: address,
+    ctx: &mut TxContext,
 ) {
     assert!(admin_cap.registry_id == object::id(registry), ENotAdmin);
 
     let withdrawn = balance::split(&mut registry.balance, amount);
     let coin = coin::from_balance(withdrawn, ctx);
-    transfer::public_transfer(coin, tx_context::sender(ctx));
+    transfer::public_transfer(coin, recipient);
 }
 
 // Update fee (admin only)
@@ -129,10 +150,37 @@ public entry fun update_fee(registry: &mut Registry, admin_cap: &AdminCap, new_f
     registry.fee = new_fee;
 }
 
+// Transfer admin (admin only): hands the AdminCap to the new admin and records it
+public entry fun transfer_admin(registry: &mut Registry, admin_cap: AdminCap, new_admin: address) {
+    assert!(admin_cap.registry_id == object::id(registry), ENotAdmin);
+    registry.admin = new_admin;
+    transfer::transfer(admin_cap, new_admin);
+}
+
+// Leave registry (member only): gives up the sender's membership and burns its cap
+public entry fun leave_registry(
+    registry: &mut Registry,
+    cap: MembershipCap,
+    ctx: &mut TxContext,
+) {
+    assert!(cap.registry_id == object::id(registry), EInvalidCap);
+    let sender = tx_context::sender(ctx);
+    assert!(cap.member == sender, EInvalidCap);
+    assert!(table::contains(&registry.members, sender), ENotMember);
+
+    let MembershipCap { id, registry_id: _, member: _ } = cap;
+    object::delete(id);
+    drop_member(registry, sender);
+}
+
 // Remove member (admin only)
 public entry fun remove_member(registry: &mut Registry, admin_cap: &AdminCap, member: address) {
     assert!(admin_cap.registry_id == object::id(registry), ENotAdmin);
     assert!(table::contains(&registry.members, member), ENotMember);
+    drop_member(registry, member);
+}
+
+fun drop_member(registry: &mut Registry, member: address) {
     table::remove(&mut registry.members, member);
 
     // Find and remove from member_addresses table using swap-with-last pattern
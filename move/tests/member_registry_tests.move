@@ -1,18 +1,277 @@
-/*
 #[test_only]
 module canary::member_registry_tests;
-// uncomment this line to import the module
-// use canary::canary;
 
-const ENotImplemented: u64 = 0;
+use canary::member_registry::{Self, AdminCap, MembershipCap, Registry};
+use std::string;
+use sui::clock;
+use sui::coin::{Self, Coin};
+use sui::sui::SUI;
+use sui::test_scenario::{Self, Scenario};
+
+const ADMIN: address = @0xA;
+const ALICE: address = @0xB;
+const NEW_ADMIN: address = @0xC;
+const BOB: address = @0xE;
+const TREASURY: address = @0xD;
+
+const FEE: u64 = 1_000;
+
+// Start a scenario with a registry administered by ADMIN
+fun setup(): Scenario {
+    let mut scenario = test_scenario::begin(ADMIN);
+    member_registry::create_registry(FEE, test_scenario::ctx(&mut scenario));
+    scenario
+}
+
+// Join the registry as `member`, paying the fee
+fun join(scenario: &mut Scenario, member: address, domain: vector<u8>) {
+    join_paying(scenario, member, domain, FEE);
+}
+
+// Join the registry as `member`, paying `amount`
+fun join_paying(scenario: &mut Scenario, member: address, domain: vector<u8>, amount: u64) {
+    test_scenario::next_tx(scenario, member);
+    let mut registry = test_scenario::take_shared<Registry>(scenario);
+    let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+    let payment = coin::mint_for_testing<SUI>(amount, test_scenario::ctx(scenario));
+    member_registry::join_registry(
+        &mut registry,
+        payment,
+        string::utf8(domain),
+        &clock,
+        test_scenario::ctx(scenario),
+    );
+    clock::destroy_for_testing(clock);
+    test_scenario::return_shared(registry);
+}
+
+// Leave the registry as `member` with the MembershipCap it holds
+fun leave(scenario: &mut Scenario, member: address) {
+    test_scenario::next_tx(scenario, member);
+    let mut registry = test_scenario::take_shared<Registry>(scenario);
+    let cap = test_scenario::take_from_sender<MembershipCap>(scenario);
+    member_registry::leave_registry(&mut registry, cap, test_scenario::ctx(scenario));
+    test_scenario::return_shared(registry);
+}
+
+// Hand ADMIN's AdminCap to NEW_ADMIN
+fun transfer_admin(scenario: &mut Scenario) {
+    test_scenario::next_tx(scenario, ADMIN);
+    let mut registry = test_scenario::take_shared<Registry>(scenario);
+    let cap = test_scenario::take_from_sender<AdminCap>(scenario);
+    member_registry::transfer_admin(&mut registry, cap, NEW_ADMIN);
+    test_scenario::return_shared(registry);
+}
+
+#[test]
+fun test_withdraw_to_pays_recipient() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    {
+        let mut registry = test_scenario::take_shared<Registry>(&scenario);
+        let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+        member_registry::withdraw_to(
+            &mut registry,
+            &cap,
+            FEE,
+            TREASURY,
+            test_scenario::ctx(&mut scenario),
+        );
+        test_scenario::return_to_sender(&scenario, cap);
+        test_scenario::return_shared(registry);
+    };
+
+    test_scenario::next_tx(&mut scenario, TREASURY);
+    {
+        let coin = test_scenario::take_from_sender<Coin<SUI>>(&scenario);
+        assert!(coin::value(&coin) == FEE);
+        test_scenario::return_to_sender(&scenario, coin);
+    };
+    // The admin keeps nothing
+    assert!(!test_scenario::has_most_recent_for_address<Coin<SUI>>(ADMIN));
+    test_scenario::end(scenario);
+}
+
+#[test]
+fun test_withdraw_pays_sender() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    {
+        let mut registry = test_scenario::take_shared<Registry>(&scenario);
+        let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+        member_registry::withdraw(&mut registry, &cap, FEE, test_scenario::ctx(&mut scenario));
+        test_scenario::return_to_sender(&scenario, cap);
+        test_scenario::return_shared(registry);
+    };
+
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    {
+        let coin = test_scenario::take_from_sender<Coin<SUI>>(&scenario);
+        assert!(coin::value(&coin) == FEE);
+        test_scenario::return_to_sender(&scenario, coin);
+    };
+    test_scenario::end(scenario);
+}
+
+#[test]
+fun test_transfer_admin_moves_cap() {
+    let mut scenario = setup();
+    transfer_admin(&mut scenario);
+
+    test_scenario::next_tx(&mut scenario, NEW_ADMIN);
+    assert!(test_scenario::has_most_recent_for_address<AdminCap>(NEW_ADMIN));
+    assert!(!test_scenario::has_most_recent_for_address<AdminCap>(ADMIN));
+    let registry = test_scenario::take_shared<Registry>(&scenario);
+    assert!(member_registry::get_admin(&registry) == NEW_ADMIN);
+    test_scenario::return_shared(registry);
+    test_scenario::end(scenario);
+}
 
 #[test]
-fun test_canary() {
-    // pass
+fun test_new_admin_can_act() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+    transfer_admin(&mut scenario);
+
+    test_scenario::next_tx(&mut scenario, NEW_ADMIN);
+    {
+        let mut registry = test_scenario::take_shared<Registry>(&scenario);
+        let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+        member_registry::verify_admin(&cap, &registry);
+        member_registry::remove_member(&mut registry, &cap, ALICE);
+        assert!(!member_registry::is_member(&registry, ALICE));
+        test_scenario::return_to_sender(&scenario, cap);
+        test_scenario::return_shared(registry);
+    };
+    test_scenario::end(scenario);
+}
+
+#[test, expected_failure(abort_code = test_scenario::EEmptyInventory)]
+fun test_old_admin_cannot_act() {
+    let mut scenario = setup();
+    transfer_admin(&mut scenario);
+
+    // The old admin no longer holds a cap to act with
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+    test_scenario::return_to_sender(&scenario, cap);
+    test_scenario::end(scenario);
+}
+
+#[test, expected_failure(abort_code = member_registry::ENotAdmin)]
+fun test_transfer_admin_rejects_foreign_cap() {
+    let mut scenario = setup();
+
+    // A second registry, whose cap does not administer the first
+    test_scenario::next_tx(&mut scenario, NEW_ADMIN);
+    let first = option::destroy_some(test_scenario::most_recent_id_shared<Registry>());
+    member_registry::create_registry(FEE, test_scenario::ctx(&mut scenario));
+
+    test_scenario::next_tx(&mut scenario, NEW_ADMIN);
+    let mut registry = test_scenario::take_shared_by_id<Registry>(&scenario, first);
+    let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+    member_registry::transfer_admin(&mut registry, cap, NEW_ADMIN);
+    test_scenario::return_shared(registry);
+    test_scenario::end(scenario);
+}
+
+#[test]
+fun test_leave_and_rejoin() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+    join(&mut scenario, BOB, b"bob.com");
+    leave(&mut scenario, ALICE);
+
+    test_scenario::next_tx(&mut scenario, ALICE);
+    {
+        let registry = test_scenario::take_shared<Registry>(&scenario);
+        assert!(!member_registry::is_member(&registry, ALICE));
+        assert!(member_registry::is_member(&registry, BOB));
+        assert!(vector::length(&member_registry::get_all_members(&registry)) == 1);
+        test_scenario::return_shared(registry);
+    };
+    // The cap was burned
+    assert!(!test_scenario::has_most_recent_for_address<MembershipCap>(ALICE));
+
+    join(&mut scenario, ALICE, b"alice.org");
+    test_scenario::next_tx(&mut scenario, ALICE);
+    {
+        let registry = test_scenario::take_shared<Registry>(&scenario);
+        assert!(member_registry::is_member(&registry, ALICE));
+        assert!(vector::length(&member_registry::get_all_members(&registry)) == 2);
+        test_scenario::return_shared(registry);
+    };
+    test_scenario::end(scenario);
+}
+
+#[test, expected_failure(abort_code = member_registry::ENotMember)]
+fun test_leave_with_stale_cap() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+
+    // Removal leaves ALICE's cap behind
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    {
+        let mut registry = test_scenario::take_shared<Registry>(&scenario);
+        let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+        member_registry::remove_member(&mut registry, &cap, ALICE);
+        test_scenario::return_to_sender(&scenario, cap);
+        test_scenario::return_shared(registry);
+    };
+
+    leave(&mut scenario, ALICE);
+    test_scenario::end(scenario);
+}
+
+#[test, expected_failure(abort_code = member_registry::EInvalidCap)]
+fun test_leave_with_foreign_cap() {
+    let mut scenario = setup();
+    join(&mut scenario, ALICE, b"alice.com");
+    join(&mut scenario, BOB, b"bob.com");
+
+    // BOB holds ALICE's cap as well as his own
+    test_scenario::next_tx(&mut scenario, ALICE);
+    let cap = test_scenario::take_from_sender<MembershipCap>(&scenario);
+    transfer::public_transfer(cap, BOB);
+
+    leave(&mut scenario, BOB);
+    test_scenario::end(scenario);
+}
+
+#[test]
+fun test_create_registry() {
+    let mut scenario = setup();
+
+    test_scenario::next_tx(&mut scenario, ADMIN);
+    {
+        // The registry is shared and the sender holds its AdminCap
+        let registry = test_scenario::take_shared<Registry>(&scenario);
+        let cap = test_scenario::take_from_sender<AdminCap>(&scenario);
+        member_registry::verify_admin(&cap, &registry);
+        assert!(member_registry::get_admin(&registry) == ADMIN);
+        assert!(vector::is_empty(&member_registry::get_all_members(&registry)));
+        test_scenario::return_to_sender(&scenario, cap);
+        test_scenario::return_shared(registry);
+    };
+
+    // Paying exactly the fee given to create_registry is enough
+    join_paying(&mut scenario, ALICE, b"alice.com", FEE);
+    test_scenario::next_tx(&mut scenario, ALICE);
+    {
+        let registry = test_scenario::take_shared<Registry>(&scenario);
+        assert!(member_registry::is_member(&registry, ALICE));
+        test_scenario::return_shared(registry);
+    };
+    test_scenario::end(scenario);
 }
 
-#[test, expected_failure(abort_code = ::canary::canary_tests::ENotImplemented)]
-fun test_canary_fail() {
-    abort ENotImplemented
+#[test, expected_failure(abort_code = member_registry::EInsufficientPayment)]
+fun test_create_registry_fee_is_enforced() {
+    let mut scenario = setup();
+    join_paying(&mut scenario, ALICE, b"alice.com", FEE - 1);
+    test_scenario::end(scenario);
 }
-*/